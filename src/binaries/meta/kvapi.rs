@@ -41,6 +41,7 @@ impl KvApiCommand {
                 let req = if let Some(expire_after) = config.expire_after {
                     req.with(KVMeta {
                         expire_at: Some(SeqV::<()>::now_ms() / 1000 + expire_after),
+                        lease: None,
                     })
                 } else {
                     req
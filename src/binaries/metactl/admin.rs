@@ -0,0 +1,52 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Config;
+
+/// Call an admin HTTP API of a metasrv and return the response body.
+async fn call_admin_api(conf: &Config, path_and_query: &str) -> anyhow::Result<String> {
+    if conf.admin_api_address.is_empty() {
+        return Err(anyhow::anyhow!("--admin-api-address is required"));
+    }
+
+    let url = format!("http://{}{}", conf.admin_api_address, path_and_query);
+    let resp = reqwest::get(&url).await?;
+
+    let status = resp.status();
+    let body = resp.text().await?;
+
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("{} returns {}: {}", url, status, body));
+    }
+    Ok(body)
+}
+
+/// Transfer the raft leadership to the voter specified by `--to`, or to another voter if it is absent.
+pub async fn transfer_leader(conf: &Config) -> anyhow::Result<()> {
+    let path = match conf.to {
+        Some(to) => format!("/v1/ctrl/transfer_leader?to={}", to),
+        None => "/v1/ctrl/transfer_leader".to_string(),
+    };
+
+    let body = call_admin_api(conf, &path).await?;
+    println!("{}", body);
+    Ok(())
+}
+
+/// Remove the metasrv at `--admin-api-address` from the cluster.
+pub async fn leave(conf: &Config) -> anyhow::Result<()> {
+    call_admin_api(conf, "/v1/ctrl/leave").await?;
+    println!("node at {} left the cluster", conf.admin_api_address);
+    Ok(())
+}
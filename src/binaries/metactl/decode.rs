@@ -0,0 +1,100 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_meta_api::deserialize_struct;
+use common_meta_app::schema::DBIdTableName;
+use common_meta_app::schema::DatabaseMeta;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_app::schema::DbIdList;
+use common_meta_app::schema::TableIdList;
+use common_meta_app::schema::TableMeta;
+use common_meta_types::RoleInfo;
+use common_meta_types::UserInfo;
+use common_meta_types::UserStageInfo;
+use common_proto_conv::FromToProto;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
+
+/// Decode the value of a key into readable json.
+///
+/// Values of well-known keys that are stored in protobuf, such as `__fd_database_by_id/<db_id>`,
+/// are decoded into the struct they store.
+/// Values of well-known keys that are stored in json, such as `__fd_roles/<role>`, are decoded the same way.
+/// Other values are returned as json if they are json, e.g., ids,
+/// or as a string if they are valid utf-8, or as raw bytes otherwise.
+///
+/// If a well-known value can not be decoded, the error and the raw value are both returned,
+/// so that a corrupted record can still be inspected.
+pub fn decode_value(key: &str, data: &[u8]) -> Value {
+    let prefix = key.split('/').next().unwrap_or_default();
+
+    match prefix {
+        "__fd_database_by_id" => decode_struct::<DatabaseMeta>(data),
+        "__fd_database_id_to_name" => decode_struct::<DatabaseNameIdent>(data),
+        "__fd_db_id_list" => decode_struct::<DbIdList>(data),
+        "__fd_table_by_id" => decode_struct::<TableMeta>(data),
+        "__fd_table_id_to_name" => decode_struct::<DBIdTableName>(data),
+        "__fd_table_id_list" => decode_struct::<TableIdList>(data),
+        "__fd_users" => decode_struct::<UserInfo>(data),
+        "__fd_stages" => decode_struct::<UserStageInfo>(data),
+        "__fd_roles" => decode_json::<RoleInfo>(data),
+        _ => decode_raw(data),
+    }
+}
+
+fn decode_struct<T>(data: &[u8]) -> Value
+where
+    T: FromToProto + Serialize,
+    T::PB: common_protos::prost::Message + Default,
+{
+    let res = deserialize_struct::<T>(data)
+        .map_err(|e| e.to_string())
+        .and_then(|v| serde_json::to_value(v).map_err(|e| e.to_string()));
+
+    match res {
+        Ok(v) => v,
+        Err(e) => json!({
+            "decode_error": e,
+            "raw": decode_raw(data),
+        }),
+    }
+}
+
+fn decode_json<T>(data: &[u8]) -> Value
+where T: DeserializeOwned + Serialize {
+    let res = serde_json::from_slice::<T>(data)
+        .map_err(|e| e.to_string())
+        .and_then(|v| serde_json::to_value(v).map_err(|e| e.to_string()));
+
+    match res {
+        Ok(v) => v,
+        Err(e) => json!({
+            "decode_error": e,
+            "raw": decode_raw(data),
+        }),
+    }
+}
+
+fn decode_raw(data: &[u8]) -> Value {
+    if let Ok(v) = serde_json::from_slice::<Value>(data) {
+        return v;
+    }
+
+    match std::str::from_utf8(data) {
+        Ok(s) => Value::String(s.to_string()),
+        Err(_) => json!(data),
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::sync::Arc;
+
+use common_meta_api::get_start_and_end_of_prefix;
+use common_meta_api::list_kv_stream;
+use common_meta_api::KVApi;
+use common_meta_client::ClientHandle;
+use common_meta_types::protobuf::watch_request::FilterType;
+use common_meta_types::protobuf::Event;
+use common_meta_types::protobuf::WatchRequest;
+use common_meta_types::KVMeta;
+use common_meta_types::ListKVRangeReq;
+use common_meta_types::SeqV;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKVReq;
+use serde::Serialize;
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use crate::decode::decode_value;
+use crate::Config;
+
+/// The number of records to fetch with one request when listing.
+const LIST_PAGE_SIZE: u64 = 1_000;
+
+/// A kv command that talks to a running metasrv.
+pub enum KvCommand {
+    Get(String),
+    List(String),
+    Watch(String),
+    Upsert(UpsertKVReq),
+    Txn(TxnRequest),
+}
+
+/// A record in the output, with the value decoded.
+#[derive(Serialize)]
+struct Record {
+    key: String,
+    seq: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<KVMeta>,
+    value: Value,
+}
+
+impl Record {
+    fn new(key: &str, seq_v: SeqV) -> Self {
+        Record {
+            key: key.to_string(),
+            seq: seq_v.seq,
+            meta: seq_v.meta,
+            value: decode_value(key, &seq_v.data),
+        }
+    }
+}
+
+impl KvCommand {
+    pub fn from_config(config: &Config, cmd: &str) -> anyhow::Result<Self> {
+        let c = match cmd {
+            "get" => Self::Get(required("--key", &config.key)?),
+            "list" => Self::List(config.prefix.clone()),
+            "watch" => Self::Watch(required("--prefix", &config.prefix)?),
+            "upsert" => {
+                let key = required("--key", &config.key)?;
+                Self::Upsert(UpsertKVReq::update(&key, config.value.as_bytes()))
+            }
+            "delete" => Self::Upsert(UpsertKVReq::delete(&required("--key", &config.key)?)),
+            "txn" => {
+                let path = required("--file", &config.file)?;
+                let txn: TxnRequest = serde_json::from_str(&fs::read_to_string(path)?)?;
+                Self::Txn(txn)
+            }
+            _ => return Err(anyhow::anyhow!("unknown kv command: {}", cmd)),
+        };
+        Ok(c)
+    }
+
+    /// Run the command and print the result to stdout.
+    ///
+    /// With `json` the output is compact json, one line for every record or event.
+    pub async fn execute(&self, client: Arc<ClientHandle>, json: bool) -> anyhow::Result<()> {
+        match self {
+            KvCommand::Get(key) => {
+                let res = client.get_kv(key).await?;
+                match res {
+                    Some(seq_v) => print_record(&Record::new(key, seq_v), json)?,
+                    None => eprintln!("key not found: {}", key),
+                }
+            }
+            KvCommand::List(prefix) => {
+                // An empty prefix lists every key, thus records are fetched page by page.
+                let req = ListKVRangeReq::prefix(prefix);
+                let mut strm = list_kv_stream(client.as_ref(), req, LIST_PAGE_SIZE);
+                while let Some(kv) = strm.next().await {
+                    let (key, seq_v) = kv?;
+                    print_record(&Record::new(&key, seq_v), json)?;
+                }
+            }
+            KvCommand::Watch(prefix) => {
+                let (key, key_end) = get_start_and_end_of_prefix(prefix)?;
+                let req = WatchRequest {
+                    key,
+                    key_end: Some(key_end),
+                    filter_type: FilterType::All.into(),
+                    start_revision: None,
+                };
+
+                let mut strm = Box::pin(client.watch_resumable(req));
+                while let Some(resp) = strm.next().await {
+                    let resp = resp?;
+                    if let Some(event) = resp.event {
+                        print_event(resp.revision, event, json)?;
+                    }
+                }
+            }
+            KvCommand::Upsert(req) => {
+                let res = client.upsert_kv(req.clone()).await?;
+                let prev = res.prev.map(|x| Record::new(&req.key, x));
+                let result = res.result.map(|x| Record::new(&req.key, x));
+                print_json(&serde_json::json!({ "prev": prev, "result": result }), json)?;
+            }
+            KvCommand::Txn(txn) => {
+                let res = client.transaction(txn.clone()).await?;
+                print_json(&res, json)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn required(flag: &str, v: &str) -> anyhow::Result<String> {
+    if v.is_empty() {
+        return Err(anyhow::anyhow!("{} is required", flag));
+    }
+    Ok(v.to_string())
+}
+
+fn print_record(record: &Record, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(record)?);
+    } else {
+        println!("{} (seq={}):", record.key, record.seq);
+        println!("{}", serde_json::to_string_pretty(&record.value)?);
+    }
+    Ok(())
+}
+
+fn print_event(revision: u64, event: Event, json: bool) -> anyhow::Result<()> {
+    let key = event.key;
+    let decode = |x: Option<common_meta_types::protobuf::SeqV>| {
+        x.map(|x| serde_json::json!({ "seq": x.seq, "value": decode_value(&key, &x.data) }))
+    };
+    let prev = decode(event.prev);
+    let current = decode(event.current);
+
+    let v = serde_json::json!({
+        "revision": revision,
+        "key": key,
+        "prev": prev,
+        "current": current,
+    });
+    print_json(&v, json)
+}
+
+fn print_json<T: Serialize>(v: &T, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(v)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(v)?);
+    }
+    Ok(())
+}
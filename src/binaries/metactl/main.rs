@@ -12,9 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod admin;
+
+mod decode;
+
 mod grpc;
 use grpc::export_meta;
 
+mod kvapi;
+use kvapi::KvCommand;
+
 mod snapshot;
 
 use clap::Parser;
@@ -47,9 +54,52 @@ pub struct Config {
     #[clap(long)]
     pub export: bool,
 
+    /// Restore a backup saved by a running metasrv and initialize a single-node cluster from it.
+    #[clap(long)]
+    pub restore: bool,
+
+    /// When restore, this is the uri of the backup storage, e.g., `s3://bucket/root` or `fs:///path/to/dir`.
+    #[clap(long, default_value = "")]
+    pub from: String,
+
+    /// When restore, the latest backup taken at or before this time is restored.
+    /// The time is in RFC 3339 format, e.g., `2022-11-01T08:00:00Z`.
+    /// If `at` is empty, the latest backup is restored.
+    #[clap(long, default_value = "")]
+    pub at: String,
+
     #[clap(long, env = "METASRV_GRPC_API_ADDRESS", default_value = "")]
     pub grpc_api_address: String,
 
+    /// The address of the admin HTTP API of a metasrv, used by `transfer-leader` and `leave`.
+    #[clap(long, env = "METASRV_ADMIN_API_ADDRESS", default_value = "")]
+    pub admin_api_address: String,
+
+    /// When transfer-leader, this is the id of the voter to become the new leader.
+    /// If it is absent, the leader chooses another voter.
+    #[clap(long)]
+    pub to: Option<u64>,
+
+    /// The key to get, upsert or delete, used by `get`, `upsert` and `delete`.
+    #[clap(long, default_value = "")]
+    pub key: String,
+
+    /// The value to upsert, used by `upsert`.
+    #[clap(long, default_value = "")]
+    pub value: String,
+
+    /// The key prefix to list or watch, used by `list` and `watch`.
+    #[clap(long, default_value = "")]
+    pub prefix: String,
+
+    /// The path of a json file containing a `TxnRequest`, used by `txn`.
+    #[clap(long, default_value = "")]
+    pub file: String,
+
+    /// Output kv command results as compact json, one line per record or event, for scripting.
+    #[clap(long)]
+    pub json: bool,
+
     /// When export raft data, this is the name of the save db file.
     /// If `db` is empty, output the exported data as json to stdout instead.
     /// When import raft data, this is the name of the restored db file.
@@ -208,12 +258,35 @@ async fn main() -> anyhow::Result<()> {
                 bench_client_num_conn(&config).await?;
                 Ok(())
             }
+            "transfer-leader" => admin::transfer_leader(&config).await,
+            "leave" => admin::leave(&config).await,
+            "get" | "list" | "watch" | "upsert" | "delete" | "txn" => run_kv_command(&config).await,
 
             _ => {
                 eprintln!("valid commands are");
                 eprintln!("  --cmd bench-client-conn-num");
                 eprintln!("    Keep create new connections to metasrv.");
                 eprintln!("    Requires --grpc-api-address.");
+                eprintln!("  --cmd transfer-leader");
+                eprintln!(
+                    "    Transfer the leadership to the voter specified by --to, or to another voter."
+                );
+                eprintln!("    Requires --admin-api-address.");
+                eprintln!("  --cmd leave");
+                eprintln!(
+                    "    Remove the node from the cluster, transferring its leadership first if it is the leader."
+                );
+                eprintln!("    Requires --admin-api-address of the node to remove.");
+                eprintln!("  --cmd get --key <key>");
+                eprintln!("  --cmd list --prefix <prefix>");
+                eprintln!("  --cmd watch --prefix <prefix>");
+                eprintln!("  --cmd upsert --key <key> --value <value>");
+                eprintln!("  --cmd delete --key <key>");
+                eprintln!("  --cmd txn --file <txn.json>");
+                eprintln!(
+                    "    Inspect or modify kv in metasrv, with values of well-known keys decoded."
+                );
+                eprintln!("    Requires --grpc-api-address. Add --json for json output.");
 
                 Err(anyhow::anyhow!("unknown cmd: {}", config.cmd))
             }
@@ -228,6 +301,10 @@ async fn main() -> anyhow::Result<()> {
         return snapshot::import_data(&config).await;
     }
 
+    if config.restore {
+        return snapshot::restore_data(&config).await;
+    }
+
     Err(anyhow::anyhow!("Nothing to do"))
 }
 
@@ -236,6 +313,25 @@ where T: Serialize {
     serde_json::to_string_pretty(v)
 }
 
+async fn run_kv_command(conf: &Config) -> anyhow::Result<()> {
+    if conf.grpc_api_address.is_empty() {
+        return Err(anyhow::anyhow!("--grpc-api-address is required"));
+    }
+
+    let kv_cmd = KvCommand::from_config(conf, &conf.cmd)?;
+
+    let client = MetaGrpcClient::try_create(
+        vec![conf.grpc_api_address.clone()],
+        "root",
+        "xxx",
+        None,
+        None,
+        None,
+    )?;
+
+    kv_cmd.execute(client, conf.json).await
+}
+
 async fn bench_client_num_conn(conf: &Config) -> anyhow::Result<()> {
     let addr = &conf.grpc_api_address;
 
@@ -25,6 +25,7 @@ use std::net::ToSocketAddrs;
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use chrono::DateTime;
 use common_base::base::tokio;
 use common_meta_raft_store::config::RaftConfig;
 use common_meta_raft_store::log::RaftLog;
@@ -40,12 +41,17 @@ use common_meta_types::Endpoint;
 use common_meta_types::LogEntry;
 use common_meta_types::LogId;
 use common_meta_types::Node;
+use databend_meta::backup::redact_backup_uri;
+use databend_meta::backup::BackupLines;
+use databend_meta::backup::MetaBackup;
 use databend_meta::export::deserialize_to_kv_variant;
 use databend_meta::export::serialize_kv_variant;
 use openraft::raft::Entry;
 use openraft::raft::EntryPayload;
+use openraft::storage::HardState;
 use openraft::Membership;
 use tokio::net::TcpSocket;
+use tokio_stream::StreamExt;
 use url::Url;
 
 use crate::export_meta;
@@ -86,6 +92,104 @@ pub async fn import_data(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Restore a state machine backup into an empty raft dir and initialize a single-node cluster from it.
+pub async fn restore_data(config: &Config) -> anyhow::Result<()> {
+    let raft_config = &config.raft_config;
+
+    if config.from.is_empty() {
+        return Err(anyhow!("--from is required to restore"));
+    }
+
+    let at = if config.at.is_empty() {
+        None
+    } else {
+        let t = DateTime::parse_from_rfc3339(&config.at)?;
+        Some(t.timestamp() as u64)
+    };
+
+    let initial_cluster = if config.initial_cluster.is_empty() {
+        if config.grpc_api_address.is_empty() {
+            return Err(anyhow!(
+                "--grpc-api-address or --initial-cluster is required to restore"
+            ));
+        }
+        vec![format!(
+            "{}={}:{},{}",
+            raft_config.id,
+            raft_config.raft_advertise_host,
+            raft_config.raft_api_port,
+            config.grpc_api_address
+        )]
+    } else {
+        config.initial_cluster.clone()
+    };
+
+    if initial_cluster.len() != 1 {
+        return Err(anyhow!(
+            "restore only initializes a single-node cluster, but got: {:?}",
+            initial_cluster
+        ));
+    }
+
+    let backup = MetaBackup::try_create(&config.from)?;
+    let (taken_at, lines) = backup.read(at).await?;
+
+    eprintln!(
+        "restore backup taken at {} from {} into: {}",
+        taken_at,
+        redact_backup_uri(&config.from),
+        raft_config.raft_dir
+    );
+
+    init_sled_db(raft_config.raft_dir.clone());
+
+    clear()?;
+    restore_state_machine(lines).await?;
+
+    init_new_cluster(initial_cluster, None, raft_config.id).await?;
+    Ok(())
+}
+
+/// Create a new raft state and write the state machine records into the state machine of it.
+///
+/// The records are read from the backup one by one, without loading the entire backup.
+async fn restore_state_machine(mut lines: BackupLines) -> anyhow::Result<()> {
+    let db = get_sled_db();
+    let config = RaftConfig {
+        ..Default::default()
+    };
+
+    let raft_state = RaftState::open_create(&db, &config, None, Some(())).await?;
+    let (sm_id, _prev_sm_id) = raft_state.read_state_machine_id()?;
+
+    let sm = StateMachine::open(&config, sm_id).await?;
+    let tree = &sm.sm_tree.tree;
+
+    let mut n = 0;
+    while let Some(l) = lines.next().await {
+        let l = l?;
+        let (_tree_name, kv_variant): (String, KeySpaceKV) = serde_json::from_str(&l)?;
+        let (k, v) = serialize_kv_variant(&kv_variant)?;
+        tree.insert(k, v)?;
+        n += 1;
+    }
+    tree.flush()?;
+
+    // The logs appended after the restored state machine must not have a term greater than the vote.
+    let last_applied = sm.get_last_applied()?;
+    if let Some(log_id) = last_applied {
+        raft_state
+            .write_hard_state(&HardState {
+                current_term: log_id.term,
+                voted_for: None,
+            })
+            .await?;
+    }
+
+    eprintln!("Restored {} records, last applied: {:?}", n, last_applied);
+    Ok(())
+}
+
 // return the max log id
 fn import_lines<B: BufRead>(lines: Lines<B>) -> anyhow::Result<Option<LogId>> {
     let db = get_sled_db();
@@ -29,10 +29,13 @@ use databend_query::api::HttpService;
 use databend_query::api::RpcService;
 use databend_query::clusters::ClusterDiscovery;
 use databend_query::metrics::MetricService;
+use databend_query::servers::FlightSQLServer;
 use databend_query::servers::HttpHandler;
 use databend_query::servers::HttpHandlerKind;
 use databend_query::servers::MySQLHandler;
+use databend_query::servers::PostgresHandler;
 use databend_query::servers::Server;
+use databend_query::servers::ServerTlsConfig;
 use databend_query::servers::ShutdownHandle;
 use databend_query::GlobalServices;
 use tracing::info;
@@ -109,7 +112,8 @@ async fn main_entrypoint() -> Result<()> {
     {
         let hostname = conf.query.mysql_handler_host.clone();
         let listening = format!("{}:{}", hostname, conf.query.mysql_handler_port);
-        let mut handler = MySQLHandler::create()?;
+        let tls = ServerTlsConfig::mysql(&conf);
+        let mut handler = MySQLHandler::create(tls)?;
         let listening = handler.start(listening.parse()?).await?;
         shutdown_handle.add_service(handler);
 
@@ -121,6 +125,34 @@ async fn main_entrypoint() -> Result<()> {
         );
     }
 
+    // PostgreSQL handler.
+    {
+        let hostname = conf.query.postgres_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.postgres_handler_port);
+        let tls = ServerTlsConfig::postgres(&conf);
+        let mut handler = PostgresHandler::create(tls)?;
+        let listening = handler.start(listening.parse()?).await?;
+        shutdown_handle.add_service(handler);
+
+        info!(
+            "Listening for PostgreSQL compatibility protocol: {}, Usage: psql -U root -h {} -p {} -d default",
+            listening,
+            listening.ip(),
+            listening.port(),
+        );
+    }
+
+    // Arrow Flight SQL handler.
+    {
+        let hostname = conf.query.flight_sql_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.flight_sql_handler_port);
+        let mut srv = FlightSQLServer::create(conf.clone())?;
+        let listening = srv.start(listening.parse()?).await?;
+        shutdown_handle.add_service(srv);
+
+        info!("Listening for Arrow Flight SQL API: {}", listening);
+    }
+
     // ClickHouse HTTP handler.
     {
         let hostname = conf.query.clickhouse_http_handler_host.clone();
@@ -251,6 +283,20 @@ async fn main_entrypoint() -> Result<()> {
         "    connect via: mysql -uroot -h{} -P{}",
         conf.query.mysql_handler_host, conf.query.mysql_handler_port
     );
+    println!("PostgreSQL");
+    println!(
+        "    listened at {}:{}",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
+    println!(
+        "    connect via: psql -U root -h {} -p {} -d default",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
+    println!("Arrow Flight SQL");
+    println!(
+        "    listened at {}:{}",
+        conf.query.flight_sql_handler_host, conf.query.flight_sql_handler_port
+    );
     println!("Clickhouse(http)");
     println!(
         "    listened at {}:{}",
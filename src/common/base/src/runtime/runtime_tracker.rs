@@ -391,6 +391,19 @@ impl MemStat {
         self.used.load(Ordering::Relaxed)
     }
 
+    /// Take the memory recorded by this tracker out of its parent tracker.
+    ///
+    /// Memory allocated under one tracker may be freed under another, e.g. the result blocks of
+    /// a query. Releasing a finished child keeps a long-living parent from accumulating the
+    /// difference. `GLOBAL_MEM_STAT` is left untouched.
+    pub fn release_from_parent(&self) {
+        let used = self.used.swap(0, Ordering::Relaxed);
+
+        if let Some(parent) = &self.parent_memory_tracker {
+            parent.used.fetch_sub(used, Ordering::Relaxed);
+        }
+    }
+
     pub fn on_start_thread(self: &Arc<Self>) -> impl Fn() {
         let mem_stat = self.clone();
 
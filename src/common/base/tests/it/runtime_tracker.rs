@@ -81,3 +81,27 @@ async fn test_async_thread_tracker() -> Result<()> {
     // assert_eq!(memory_tracker2.get_memory_usage(), 0);
     Ok(())
 }
+
+#[test]
+fn test_mem_stat_release_from_parent() -> Result<()> {
+    let parent = MemStat::create_child(None);
+    let child = MemStat::create_child(Some(parent.clone()));
+
+    let mut tracker = ThreadTracker::create(Some(child.clone()));
+    let memory = {
+        let _guard = ThreadTracker::enter(&mut tracker);
+        vec![0_u8; 8 * 1024 * 1024]
+    };
+
+    assert!(child.get_memory_usage() >= 8 * 1024 * 1024);
+    assert_eq!(parent.get_memory_usage(), child.get_memory_usage());
+
+    // The memory is freed outside of the child tracker.
+    drop(memory);
+    child.release_from_parent();
+
+    assert_eq!(child.get_memory_usage(), 0);
+    assert_eq!(parent.get_memory_usage(), 0);
+
+    Ok(())
+}
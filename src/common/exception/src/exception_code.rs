@@ -177,6 +177,8 @@ build_exceptions! {
     IllegalUserInfoFormat(2203),
     UnknownRole(2204),
     InvalidRole(2206),
+    UserLocked(2207),
+    MustChangePassword(2208),
 
     // Meta api error codes.
     DatabaseAlreadyExists(2301),
@@ -204,6 +206,8 @@ build_exceptions! {
     /// - not exists.
     /// - and without `IF EXISTS`
     CatalogNotFound(2320),
+    IndexAlreadyExists(2321),
+    UnknownIndex(2322),
 
     // Cluster error codes.
     ClusterUnknownNode(2401),
@@ -222,6 +226,28 @@ build_exceptions! {
     UnknownUDF(2602),
     UdfAlreadyExists(2603),
 
+    // Row access policy error codes.
+    IllegalRowAccessPolicyFormat(2611),
+    UnknownRowAccessPolicy(2612),
+    RowAccessPolicyAlreadyExists(2613),
+
+    // Masking policy error codes.
+    IllegalMaskingPolicyFormat(2621),
+    UnknownMaskingPolicy(2622),
+    MaskingPolicyAlreadyExists(2623),
+
+    // Workload group error codes.
+    IllegalWorkloadGroupFormat(2631),
+    UnknownWorkloadGroup(2632),
+    WorkloadGroupAlreadyExists(2633),
+    QueryQueueTimeout(2634),
+
+    // Password policy error codes.
+    IllegalPasswordPolicyFormat(2641),
+    UnknownPasswordPolicy(2642),
+    PasswordPolicyAlreadyExists(2643),
+    InvalidPassword(2644),
+
     // Database error codes.
     UnknownDatabaseEngine(2701),
     UnknownTableEngine(2702),
@@ -248,6 +274,12 @@ build_exceptions! {
     TenantQuotaUnknown(2902),
     TenantQuotaExceeded(2903),
 
+    // User quota error codes.
+    UserQuotaExceeded(2911),
+
+    // Lease error codes.
+    UnknownLease(2921),
+
 }
 
 // Storage errors [3001, 4000].
@@ -16,6 +16,8 @@ pub const TRUE_BYTES_LOWER: &str = "true";
 pub const FALSE_BYTES_LOWER: &str = "false";
 pub const TRUE_BYTES_NUM: &str = "1";
 pub const FALSE_BYTES_NUM: &str = "0";
+pub const TRUE_BYTES_CHAR: &str = "t";
+pub const FALSE_BYTES_CHAR: &str = "f";
 pub const NULL_BYTES_UPPER: &str = "NULL";
 pub const NULL_BYTES_LOWER: &str = "null";
 pub const NULL_BYTES_ESCAPE: &str = "\\N";
@@ -54,6 +54,7 @@ pub use config::STORAGE_S3_DEFAULT_ENDPOINT;
 
 mod operator;
 pub use operator::init_operator;
+pub use operator::init_operator_without_layers;
 pub use operator::CacheOperator;
 pub use operator::DataOperator;
 
@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 pub struct Config {
     pub file: FileConfig,
     pub stderr: StderrConfig,
+    pub audit: AuditConfig,
 }
 
 impl Config {
@@ -37,6 +38,7 @@ impl Config {
                 level: "DEBUG".to_string(),
                 format: "text".to_string(),
             },
+            audit: AuditConfig::default(),
         }
     }
 }
@@ -103,3 +105,32 @@ impl Default for StderrConfig {
         }
     }
 }
+
+/// Config of the audit log, which is written independently of the file logging.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct AuditConfig {
+    pub on: bool,
+    pub dir: String,
+    /// `all`, `none` or a comma separated list of `login`, `privilege`, `ddl` and `dml`.
+    pub categories: String,
+}
+
+impl Display for AuditConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "enabled={}, dir={}, categories={}",
+            self.on, self.dir, self.categories
+        )
+    }
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            on: true,
+            dir: "./.databend/logs/audit".to_string(),
+            categories: "all".to_string(),
+        }
+    }
+}
@@ -21,11 +21,13 @@ mod logging;
 mod panic_hook;
 mod tracing_to_jaeger;
 
+pub use config::AuditConfig;
 pub use config::Config;
 pub use config::FileConfig;
 pub use config::StderrConfig;
 pub use logging::init_logging;
 pub use logging::init_query_logger;
+pub use logging::AuditLogger;
 pub use logging::QueryLogger;
 pub use panic_hook::log_panic;
 pub use panic_hook::set_panic_hook;
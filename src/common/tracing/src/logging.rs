@@ -14,7 +14,9 @@
 
 use std::env;
 use std::io;
+use std::io::Write;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use common_base::base::GlobalInstance;
 use common_exception::Result;
@@ -43,6 +45,7 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Layer;
 use tracing_subscriber::Registry;
 
+use crate::AuditConfig;
 use crate::Config;
 
 /// Init logging and tracing.
@@ -289,6 +292,52 @@ impl QueryLogger {
     }
 }
 
+/// AuditLogger writes the audit events as json lines to hourly rotated files
+/// under `log.audit.dir`, they survive restarts unlike `system.query_log`.
+///
+/// Events are written synchronously, an event is never dropped silently as the
+/// non-blocking writers of the other logs do when their buffer is full.
+pub struct AuditLogger {
+    writer: Option<Mutex<RollingFileAppender>>,
+}
+
+impl AuditLogger {
+    pub fn init(app_name_shuffle: String, config: &Config) -> Result<()> {
+        GlobalInstance::set(Arc::new(Self::create(&app_name_shuffle, &config.audit)?));
+        Ok(())
+    }
+
+    pub fn create(log_name: &str, config: &AuditConfig) -> Result<AuditLogger> {
+        if !config.on {
+            return Ok(AuditLogger { writer: None });
+        }
+
+        std::fs::create_dir_all(&config.dir)?;
+        let appender = RollingFileAppender::new(Rotation::HOURLY, &config.dir, log_name);
+        Ok(AuditLogger {
+            writer: Some(Mutex::new(appender)),
+        })
+    }
+
+    pub fn instance() -> Arc<AuditLogger> {
+        GlobalInstance::get()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Write an event as a line, and flush it before returning.
+    pub fn write(&self, event: &str) -> Result<()> {
+        if let Some(writer) = &self.writer {
+            let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+            writer.write_all(format!("{}\n", event).as_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
 /// Format tracing events with span-id support.
 pub struct EventFormatter {}
 
@@ -19,12 +19,18 @@ use common_base::base::replace_nth_char;
 use common_exception::ErrorCode;
 use common_meta_types::GetKVReply;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::MGetKVReply;
+use common_meta_types::SeqV;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_meta_types::UpsertKVReply;
 use common_meta_types::UpsertKVReq;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use futures::TryStreamExt;
 
 /// Build an API impl instance or a cluster of API impl
 #[async_trait]
@@ -80,6 +86,9 @@ pub trait KVApi: Send + Sync {
 
     async fn prefix_list_kv(&self, prefix: &str) -> Result<ListKVReply, KVAppError>;
 
+    /// List kvs in a key range, at most `req.limit` kvs in one reply.
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError>;
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError>;
 }
 
@@ -101,11 +110,50 @@ impl<U: KVApi, T: Deref<Target = U> + Send + Sync> KVApi for T {
         self.deref().prefix_list_kv(prefix).await
     }
 
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        self.deref().list_kv(req).await
+    }
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         self.deref().transaction(txn).await
     }
 }
 
+/// List all kvs in a range as a stream, fetching `page_size` kvs with one `list_kv()` a time.
+///
+/// Only one page is held in memory. `req.limit` is ignored, use `StreamExt::take()` to limit the total number.
+pub fn list_kv_stream<'a>(
+    kv_api: &'a dyn KVApi,
+    req: ListKVRangeReq,
+    page_size: u64,
+) -> BoxStream<'a, Result<(String, SeqV<Vec<u8>>), KVAppError>> {
+    let page_size = page_size.max(1);
+
+    let pages = futures::stream::try_unfold(Some(req), move |req| async move {
+        let mut req = match req {
+            None => return Ok(None),
+            Some(req) => req,
+        };
+
+        req.limit = Some(page_size);
+        let reply = kv_api.list_kv(req.clone()).await?;
+
+        let next = if reply.more {
+            req.after_key = reply.kvs.last().map(|(k, _)| k.clone());
+            Some(req)
+        } else {
+            None
+        };
+
+        Ok(Some((reply.kvs, next)))
+    });
+
+    pages
+        .map_ok(|kvs| futures::stream::iter(kvs.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+}
+
 pub trait AsKVApi {
     fn as_kv_api(&self) -> &dyn KVApi;
 }
@@ -21,6 +21,8 @@ use common_meta_types::txn_op;
 use common_meta_types::txn_op_response;
 use common_meta_types::ConditionResult;
 use common_meta_types::KVMeta;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::MatchSeq;
 use common_meta_types::Operation;
 use common_meta_types::PbSeqV;
@@ -40,9 +42,11 @@ use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_meta_types::UpsertKVReq;
 use common_meta_types::With;
+use futures::TryStreamExt;
 use tracing::debug;
 use tracing::info;
 
+use crate::list_kv_stream;
 use crate::ApiBuilder;
 use crate::KVApi;
 
@@ -61,6 +65,7 @@ impl KVApiTestSuite {
         self.kv_timeout(&builder.build().await).await?;
         self.kv_meta(&builder.build().await).await?;
         self.kv_list(&builder.build().await).await?;
+        self.kv_list_range(&builder.build().await).await?;
         self.kv_mget(&builder.build().await).await?;
         self.kv_txn_absent_seq_0(&builder.build().await).await?;
         self.kv_transaction(&builder.build().await).await?;
@@ -243,6 +248,7 @@ impl KVApiTestSuite {
 
         kv.upsert_kv(UpsertKVReq::update("k1", b"v1").with(KVMeta {
             expire_at: Some(now + 2),
+            lease: None,
         }))
         .await?;
 
@@ -272,6 +278,7 @@ impl KVApiTestSuite {
                     .with(MatchSeq::Exact(0))
                     .with(KVMeta {
                         expire_at: Some(now - 1),
+                        lease: None,
                     }),
             )
             .await?;
@@ -280,6 +287,7 @@ impl KVApiTestSuite {
                     .with(MatchSeq::Exact(0))
                     .with(KVMeta {
                         expire_at: Some(now + 10),
+                        lease: None,
                     }),
             )
             .await?;
@@ -291,7 +299,8 @@ impl KVApiTestSuite {
                 Some(SeqV::with_meta(
                     3,
                     Some(KVMeta {
-                        expire_at: Some(now + 10)
+                        expire_at: Some(now + 10),
+                        lease: None,
                     }),
                     b"v2".to_vec()
                 ))
@@ -313,6 +322,7 @@ impl KVApiTestSuite {
                     .with(MatchSeq::Exact(3))
                     .with(KVMeta {
                         expire_at: Some(now - 1),
+                        lease: None,
                     }),
             )
             .await?;
@@ -348,6 +358,7 @@ impl KVApiTestSuite {
                 Operation::AsIs,
                 Some(KVMeta {
                     expire_at: Some(now + 20),
+                    lease: None,
                 }),
             ))
             .await?;
@@ -363,6 +374,7 @@ impl KVApiTestSuite {
                 Operation::AsIs,
                 Some(KVMeta {
                     expire_at: Some(now + 20),
+                    lease: None,
                 }),
             ))
             .await?;
@@ -371,7 +383,8 @@ impl KVApiTestSuite {
             Some(SeqV::with_meta(
                 2,
                 Some(KVMeta {
-                    expire_at: Some(now + 20)
+                    expire_at: Some(now + 20),
+                    lease: None,
                 }),
                 b"v1".to_vec()
             )),
@@ -385,7 +398,8 @@ impl KVApiTestSuite {
             SeqV::with_meta(
                 seq + 1,
                 Some(KVMeta {
-                    expire_at: Some(now + 20)
+                    expire_at: Some(now + 20),
+                    lease: None,
                 }),
                 b"v1".to_vec()
             ),
@@ -426,6 +440,69 @@ impl KVApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn kv_list_range<KV: KVApi>(&self, kv: &KV) -> anyhow::Result<()> {
+        info!("--- KVApiTestSuite::kv_list_range() start");
+
+        {
+            kv.upsert_kv(UpsertKVReq::update("a", b"")).await?;
+            for i in 0..5 {
+                let key = format!("b/{}", i);
+                kv.upsert_kv(UpsertKVReq::update(&key, key.as_bytes()))
+                    .await?;
+            }
+            kv.upsert_kv(UpsertKVReq::update("c", b"")).await?;
+        }
+
+        fn keys(reply: &ListKVRangeReply) -> Vec<String> {
+            reply.kvs.iter().map(|(k, _)| k.clone()).collect()
+        }
+
+        info!("--- list by prefix in pages");
+        {
+            let req = ListKVRangeReq::prefix("b/").with_limit(2);
+
+            let reply = kv.list_kv(req.clone()).await?;
+            assert_eq!(vec!["b/0", "b/1"], keys(&reply));
+            assert_eq!(b"b/0".to_vec(), reply.kvs[0].1.data);
+            assert!(reply.more);
+
+            let reply = kv.list_kv(req.clone().with_after_key("b/1")).await?;
+            assert_eq!(vec!["b/2", "b/3"], keys(&reply));
+            assert!(reply.more);
+
+            let reply = kv.list_kv(req.clone().with_after_key("b/3")).await?;
+            assert_eq!(vec!["b/4"], keys(&reply));
+            assert!(!reply.more);
+        }
+
+        info!("--- list keys only in reverse order");
+        {
+            let req = ListKVRangeReq::prefix("b/")
+                .with_limit(2)
+                .with_keys_only(true)
+                .with_reverse(true);
+
+            let reply = kv.list_kv(req.clone()).await?;
+            assert_eq!(vec!["b/4", "b/3"], keys(&reply));
+            assert!(reply.kvs[0].1.data.is_empty());
+            assert!(reply.more);
+
+            let reply = kv.list_kv(req.with_after_key("b/1")).await?;
+            assert_eq!(vec!["b/0"], keys(&reply));
+            assert!(!reply.more);
+        }
+
+        info!("--- list all as a stream");
+        {
+            let strm = list_kv_stream(kv, ListKVRangeReq::default(), 3);
+            let got = strm.map_ok(|(k, _)| k).try_collect::<Vec<_>>().await?;
+            assert_eq!(vec!["a", "b/0", "b/1", "b/2", "b/3", "b/4", "c"], got);
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(level = "info", skip(self, kv))]
     pub async fn kv_mget<KV: KVApi>(&self, kv: &KV) -> anyhow::Result<()> {
         info!("--- KVApiTestSuite::kv_mget() start");
@@ -484,6 +561,7 @@ impl KVApiTestSuite {
                 value: b"new_v1".to_vec(),
                 prev_value: true,
                 expire_at: None,
+                lease: None,
             })),
         }];
 
@@ -642,6 +720,7 @@ impl KVApiTestSuite {
                     value: b"new_v1".to_vec(),
                     prev_value: true,
                     expire_at: None,
+                    lease: None,
                 })),
             }];
 
@@ -694,6 +773,7 @@ impl KVApiTestSuite {
                     value: b"new_v1".to_vec(),
                     prev_value: true,
                     expire_at: None,
+                    lease: None,
                 })),
             }];
 
@@ -749,6 +829,7 @@ impl KVApiTestSuite {
                         value: val1_new.to_vec(),
                         prev_value: true,
                         expire_at: None,
+                        lease: None,
                     })),
                 },
                 // change k2
@@ -758,6 +839,7 @@ impl KVApiTestSuite {
                         value: b"new_v2".to_vec(),
                         prev_value: true,
                         expire_at: None,
+                        lease: None,
                     })),
                 },
                 // get k1
@@ -858,6 +940,7 @@ impl KVApiTestSuite {
                         value: val1_new.to_vec(),
                         prev_value: true,
                         expire_at: None,
+                        lease: None,
                     })),
                 },
                 // get k1
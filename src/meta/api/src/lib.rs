@@ -20,6 +20,8 @@ mod id_generator;
 mod kv_api;
 mod kv_api_key;
 mod kv_api_test_suite;
+mod lock_api;
+mod lock_api_test_suite;
 mod schema_api;
 mod schema_api_impl;
 mod schema_api_keys;
@@ -34,6 +36,7 @@ pub(crate) mod util;
 pub use id::Id;
 pub(crate) use id_generator::IdGenerator;
 pub use kv_api::get_start_and_end_of_prefix;
+pub use kv_api::list_kv_stream;
 pub use kv_api::prefix_of_string;
 pub use kv_api::ApiBuilder;
 pub use kv_api::AsKVApi;
@@ -41,6 +44,12 @@ pub use kv_api::KVApi;
 pub use kv_api_key::KVApiKey;
 pub use kv_api_key::KVApiKeyError;
 pub use kv_api_test_suite::KVApiTestSuite;
+pub use lock_api::LockApi;
+pub use lock_api::LockGuard;
+pub use lock_api::ELECTION_KEY_PREFIX;
+pub use lock_api::LOCK_KEY_PREFIX;
+pub use lock_api::MIN_LOCK_TTL;
+pub use lock_api_test_suite::LockApiTestSuite;
 pub use schema_api::SchemaApi;
 pub(crate) use schema_api_impl::get_db_or_err;
 pub use schema_api_test_suite::SchemaApiTestSuite;
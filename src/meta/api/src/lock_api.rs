@@ -0,0 +1,355 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use common_base::base::tokio;
+use common_base::base::tokio::select;
+use common_base::base::tokio::sync::oneshot;
+use common_base::base::tokio::task::JoinHandle;
+use common_meta_types::AppError;
+use common_meta_types::InvalidLockTtl;
+use common_meta_types::KVAppError;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::UpsertKVReq;
+use common_meta_types::With;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+use crate::KVApi;
+
+/// Key prefix of all locks.
+pub const LOCK_KEY_PREFIX: &str = "__fd_lock";
+
+/// Key prefix of all elections.
+pub const ELECTION_KEY_PREFIX: &str = "__fd_election";
+
+/// The minimum ttl of a lock.
+///
+/// The expiration of a record is in seconds, a shorter ttl leaves no time to renew the lock before it expires.
+pub const MIN_LOCK_TTL: Duration = Duration::from_secs(2);
+
+/// The interval to re-check a lock held by others.
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Distributed lock and leader election built upon a [`KVApi`].
+///
+/// A lock is a record `<LOCK_KEY_PREFIX>/<key>` with `expire_at` set and with the holder as its value.
+/// It is acquired by inserting the record with `MatchSeq::Exact(0)`,
+/// and is renewed and released by updating or deleting the record with the seq it last wrote.
+/// Thus a holder that loses the lock, e.g., because it is not renewed in time,
+/// will never overwrite or delete the record of another holder.
+///
+/// The seq of the record at acquire time is used as the fencing token:
+/// seq is globally increasing, so a later holder always has a greater token.
+#[async_trait]
+pub trait LockApi: Send + Sync {
+    /// Try to acquire the lock `key` once for `holder`.
+    ///
+    /// It returns `None` if the lock is held by others.
+    /// The lock expires if it is not renewed within `ttl`, which is rounded up to seconds.
+    /// An `InvalidLockTtl` error is returned if `ttl` is less than [`MIN_LOCK_TTL`].
+    async fn try_acquire_lock(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Option<LockGuard>, KVAppError>;
+
+    /// Acquire the lock `key` for `holder`, waiting until it is released or expired if it is held by others.
+    async fn acquire_lock(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<LockGuard, KVAppError>;
+
+    /// Returns the current holder of the lock `key` and its fencing token.
+    async fn get_lock_holder(&self, key: &str) -> Result<Option<(String, u64)>, KVAppError>;
+
+    /// Campaign to be the leader of `election`, waiting until `candidate` is elected.
+    ///
+    /// The returned guard keeps the leadership until it is resigned or dropped.
+    async fn campaign(
+        &self,
+        election: &str,
+        candidate: &str,
+        ttl: Duration,
+    ) -> Result<LockGuard, KVAppError>;
+
+    /// Returns the current leader of `election`.
+    async fn observe(&self, election: &str) -> Result<Option<String>, KVAppError>;
+
+    /// Give up the leadership held by `leader`.
+    async fn resign(&self, leader: LockGuard) -> Result<(), KVAppError>;
+}
+
+/// A lock held on a key.
+///
+/// The lock is renewed in background every `ttl/3` until it is released.
+/// Dropping the guard releases the lock in background.
+pub struct LockGuard {
+    key: String,
+    holder: String,
+    fencing_token: u64,
+    held: Arc<AtomicBool>,
+    cancel_tx: oneshot::Sender<()>,
+    renewer: JoinHandle<Result<(), KVAppError>>,
+}
+
+impl LockGuard {
+    /// The key of the underlying record of this lock.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// A token that increases every time the lock is acquired.
+    ///
+    /// A resource protected by this lock should reject requests with a token smaller than the last seen one.
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// Returns `false` if the lock is lost, i.e., it is not renewed before expiration.
+    pub fn is_held(&self) -> bool {
+        self.held.load(Ordering::Acquire)
+    }
+
+    /// Stop renewing and delete the lock if it is still held.
+    pub async fn release(self) -> Result<(), KVAppError> {
+        let _ = self.cancel_tx.send(());
+
+        match self.renewer.await {
+            Ok(res) => res,
+            Err(e) => {
+                warn!("lock renewer of {} quit abnormally: {}", self.key, e);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<KV: KVApi + ?Sized + 'static> LockApi for Arc<KV> {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn try_acquire_lock(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<Option<LockGuard>, KVAppError> {
+        let lock_key = format!("{}/{}", LOCK_KEY_PREFIX, key);
+        try_acquire(self.clone(), lock_key, holder, ttl).await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn acquire_lock(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> Result<LockGuard, KVAppError> {
+        let lock_key = format!("{}/{}", LOCK_KEY_PREFIX, key);
+        acquire(self.clone(), lock_key, holder, ttl).await
+    }
+
+    async fn get_lock_holder(&self, key: &str) -> Result<Option<(String, u64)>, KVAppError> {
+        let lock_key = format!("{}/{}", LOCK_KEY_PREFIX, key);
+        let res = self.as_ref().get_kv(&lock_key).await?;
+        Ok(res.map(|sv| (String::from_utf8_lossy(&sv.data).to_string(), sv.seq)))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn campaign(
+        &self,
+        election: &str,
+        candidate: &str,
+        ttl: Duration,
+    ) -> Result<LockGuard, KVAppError> {
+        let election_key = format!("{}/{}", ELECTION_KEY_PREFIX, election);
+        acquire(self.clone(), election_key, candidate, ttl).await
+    }
+
+    async fn observe(&self, election: &str) -> Result<Option<String>, KVAppError> {
+        let election_key = format!("{}/{}", ELECTION_KEY_PREFIX, election);
+        let res = self.as_ref().get_kv(&election_key).await?;
+        Ok(res.map(|sv| String::from_utf8_lossy(&sv.data).to_string()))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, leader), fields(key = leader.key()))]
+    async fn resign(&self, leader: LockGuard) -> Result<(), KVAppError> {
+        leader.release().await
+    }
+}
+
+async fn acquire<KV: KVApi + ?Sized + 'static>(
+    kv_api: Arc<KV>,
+    key: String,
+    holder: &str,
+    ttl: Duration,
+) -> Result<LockGuard, KVAppError> {
+    loop {
+        let res = try_acquire(kv_api.clone(), key.clone(), holder, ttl).await?;
+        if let Some(guard) = res {
+            return Ok(guard);
+        }
+
+        debug!("lock {} is held by others, retry later", key);
+        tokio::time::sleep(LOCK_RETRY_INTERVAL).await;
+    }
+}
+
+async fn try_acquire<KV: KVApi + ?Sized + 'static>(
+    kv_api: Arc<KV>,
+    key: String,
+    holder: &str,
+    ttl: Duration,
+) -> Result<Option<LockGuard>, KVAppError> {
+    if ttl < MIN_LOCK_TTL {
+        return Err(KVAppError::AppError(AppError::InvalidLockTtl(
+            InvalidLockTtl::new(key, ttl.as_millis() as u64, MIN_LOCK_TTL.as_millis() as u64),
+        )));
+    }
+
+    let seq = match upsert_lock(kv_api.as_ref(), &key, MatchSeq::Exact(0), holder, ttl).await? {
+        Some(seq) => seq,
+        None => return Ok(None),
+    };
+
+    info!(
+        "lock {} acquired by {}, fencing token: {}",
+        key, holder, seq
+    );
+
+    let held = Arc::new(AtomicBool::new(true));
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+
+    let renewer = tokio::spawn(renew_lock(
+        kv_api,
+        key.clone(),
+        holder.to_string(),
+        ttl,
+        seq,
+        held.clone(),
+        cancel_rx,
+    ));
+
+    Ok(Some(LockGuard {
+        key,
+        holder: holder.to_string(),
+        fencing_token: seq,
+        held,
+        cancel_tx,
+        renewer,
+    }))
+}
+
+/// Keep renewing a lock until it is lost or cancelled, and then delete it if it is still held.
+///
+/// A dropped `cancel_rx` sender is treated as a cancel, thus dropping a `LockGuard` releases the lock.
+async fn renew_lock<KV: KVApi + ?Sized + 'static>(
+    kv_api: Arc<KV>,
+    key: String,
+    holder: String,
+    ttl: Duration,
+    mut seq: u64,
+    held: Arc<AtomicBool>,
+    mut cancel_rx: oneshot::Receiver<()>,
+) -> Result<(), KVAppError> {
+    let interval = ttl / 3;
+    let mut renewed_at = Instant::now();
+
+    loop {
+        select! {
+            _ = &mut cancel_rx => {
+                break;
+            }
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        match upsert_lock(kv_api.as_ref(), &key, MatchSeq::Exact(seq), &holder, ttl).await {
+            Ok(Some(new_seq)) => {
+                debug!("lock {} renewed, seq: {}", key, new_seq);
+                seq = new_seq;
+                renewed_at = Instant::now();
+            }
+            Ok(None) => {
+                warn!("lock {} is lost by {}", key, holder);
+                held.store(false, Ordering::Release);
+                return Ok(());
+            }
+            Err(e) => {
+                warn!("fail to renew lock {}: {}", key, e);
+                if renewed_at.elapsed() >= ttl {
+                    warn!("lock {} is expired since it is not renewed in time", key);
+                    held.store(false, Ordering::Release);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    held.store(false, Ordering::Release);
+
+    kv_api
+        .as_ref()
+        .upsert_kv(UpsertKVReq::delete(&key).with(MatchSeq::Exact(seq)))
+        .await?;
+
+    info!("lock {} released by {}", key, holder);
+    Ok(())
+}
+
+/// Write the lock record if its seq matches `seq`, returns the new seq or `None` if it does not match.
+async fn upsert_lock<KV: KVApi + ?Sized>(
+    kv_api: &KV,
+    key: &str,
+    seq: MatchSeq,
+    holder: &str,
+    ttl: Duration,
+) -> Result<Option<u64>, KVAppError> {
+    let req = UpsertKVReq::update(key, holder.as_bytes())
+        .with(seq)
+        .with(KVMeta {
+            expire_at: Some(expire_at_sec(ttl)),
+            lease: None,
+        });
+
+    let res = kv_api.upsert_kv(req).await?;
+    if !res.changed() {
+        return Ok(None);
+    }
+
+    Ok(res.result.map(|sv| sv.seq))
+}
+
+/// Returns the expiration time in seconds, rounding it up so that the record lives for at least `ttl`.
+fn expire_at_sec(ttl: Duration) -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let expire_at_ms = now.as_millis() as u64 + ttl.as_millis() as u64;
+    (expire_at_ms + 999) / 1000
+}
@@ -0,0 +1,307 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_types::AppError;
+use common_meta_types::KVAppError;
+use common_meta_types::UpsertKVReq;
+use tracing::info;
+
+use crate::lock_api::LOCK_KEY_PREFIX;
+use crate::lock_api::MIN_LOCK_TTL;
+use crate::ApiBuilder;
+use crate::KVApi;
+use crate::LockApi;
+
+pub struct LockApiTestSuite {}
+
+impl LockApiTestSuite {
+    #[tracing::instrument(level = "info", skip(self, builder))]
+    pub async fn test_all<KV, B>(&self, builder: B) -> anyhow::Result<()>
+    where
+        KV: KVApi + 'static,
+        B: ApiBuilder<Arc<KV>>,
+    {
+        self.lock_acquire_release(&builder.build().await).await?;
+        self.lock_renew(&builder.build().await).await?;
+        self.lock_lost(&builder.build().await).await?;
+        self.lock_release_on_drop(&builder.build().await).await?;
+        self.lock_acquire_wait(&builder.build().await).await?;
+        self.election(&builder.build().await).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn lock_acquire_release<KV: KVApi + 'static>(
+        &self,
+        kv: &Arc<KV>,
+    ) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::lock_acquire_release() start");
+
+        let ttl = Duration::from_secs(3);
+
+        info!("--- ttl less than the minimum is rejected");
+        {
+            for invalid_ttl in [Duration::ZERO, MIN_LOCK_TTL - Duration::from_millis(1)] {
+                let res = kv.try_acquire_lock("l1", "a", invalid_ttl).await;
+                assert!(
+                    matches!(res, Err(KVAppError::AppError(AppError::InvalidLockTtl(_)))),
+                    "ttl: {:?}",
+                    invalid_ttl
+                );
+            }
+            assert_eq!(None, kv.get_lock_holder("l1").await?);
+        }
+
+        info!("--- acquire a free lock");
+        let guard = kv.try_acquire_lock("l1", "a", ttl).await?;
+        let guard = guard.expect("l1 is free");
+        assert!(guard.is_held());
+        assert_eq!("a", guard.holder());
+
+        let token_a = guard.fencing_token();
+        assert_eq!(
+            Some(("a".to_string(), token_a)),
+            kv.get_lock_holder("l1").await?
+        );
+
+        info!("--- a held lock can not be acquired");
+        {
+            let res = kv.try_acquire_lock("l1", "b", ttl).await?;
+            assert!(res.is_none());
+
+            let res = kv.try_acquire_lock("l1", "a", ttl).await?;
+            assert!(res.is_none(), "not reentrant");
+        }
+
+        info!("--- other locks are not affected");
+        {
+            let res = kv.try_acquire_lock("l2", "b", ttl).await?;
+            let g = res.expect("l2 is free");
+            g.release().await?;
+        }
+
+        info!("--- release");
+        {
+            guard.release().await?;
+            assert_eq!(None, kv.get_lock_holder("l1").await?);
+        }
+
+        info!("--- re-acquire gets a greater fencing token");
+        {
+            let res = kv.try_acquire_lock("l1", "b", ttl).await?;
+            let g = res.expect("l1 is released");
+            assert!(g.fencing_token() > token_a);
+            g.release().await?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn lock_renew<KV: KVApi + 'static>(&self, kv: &Arc<KV>) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::lock_renew() start");
+
+        let ttl = Duration::from_secs(2);
+
+        let guard = kv.try_acquire_lock("l1", "a", ttl).await?;
+        let guard = guard.expect("l1 is free");
+        let token = guard.fencing_token();
+
+        info!("--- lock is kept by renewal after ttl");
+        {
+            tokio::time::sleep(Duration::from_millis(4_000)).await;
+            assert!(guard.is_held());
+
+            let res = kv.get_lock_holder("l1").await?;
+            let (holder, seq) = res.unwrap();
+            assert_eq!("a", holder);
+            assert!(seq > token, "renewal updates the record");
+
+            let res = kv.try_acquire_lock("l1", "b", ttl).await?;
+            assert!(res.is_none());
+        }
+
+        info!("--- fencing token does not change after renewal");
+        {
+            assert_eq!(token, guard.fencing_token());
+        }
+
+        guard.release().await?;
+        assert_eq!(None, kv.get_lock_holder("l1").await?);
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn lock_lost<KV: KVApi + 'static>(&self, kv: &Arc<KV>) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::lock_lost() start");
+
+        let ttl = Duration::from_secs(3);
+
+        let guard = kv.try_acquire_lock("l1", "a", ttl).await?;
+        let guard = guard.expect("l1 is free");
+
+        info!("--- remove the lock record behind the holder, then b acquires it");
+        let guard_b = {
+            kv.upsert_kv(UpsertKVReq::delete(format!("{}/l1", LOCK_KEY_PREFIX)))
+                .await?;
+
+            let res = kv.try_acquire_lock("l1", "b", ttl).await?;
+            res.expect("l1 is removed")
+        };
+
+        info!("--- a finds out the lock is lost when renewing");
+        {
+            tokio::time::sleep(Duration::from_millis(2_000)).await;
+            assert!(!guard.is_held());
+            assert!(guard_b.is_held());
+        }
+
+        info!("--- releasing a lost lock does not affect the new holder");
+        {
+            guard.release().await?;
+
+            let res = kv.get_lock_holder("l1").await?;
+            let (holder, _seq) = res.unwrap();
+            assert_eq!("b", holder);
+        }
+
+        guard_b.release().await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn lock_release_on_drop<KV: KVApi + 'static>(
+        &self,
+        kv: &Arc<KV>,
+    ) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::lock_release_on_drop() start");
+
+        let ttl = Duration::from_secs(3);
+
+        let guard = kv.try_acquire_lock("l1", "a", ttl).await?;
+        assert!(guard.is_some());
+
+        info!("--- dropping the guard releases the lock in background");
+        {
+            drop(guard);
+
+            let mut released = false;
+            for _ in 0..20 {
+                if kv.get_lock_holder("l1").await?.is_none() {
+                    released = true;
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            assert!(released, "lock is released before it expires");
+        }
+
+        let res = kv.try_acquire_lock("l1", "b", ttl).await?;
+        res.expect("l1 is released").release().await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn lock_acquire_wait<KV: KVApi + 'static>(&self, kv: &Arc<KV>) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::lock_acquire_wait() start");
+
+        let ttl = Duration::from_secs(3);
+
+        let guard = kv.acquire_lock("l1", "a", ttl).await?;
+
+        let waiter = {
+            let kv = kv.clone();
+            tokio::spawn(async move { kv.acquire_lock("l1", "b", ttl).await })
+        };
+
+        info!("--- b waits while a holds the lock");
+        {
+            tokio::time::sleep(Duration::from_millis(1_000)).await;
+            assert!(!waiter.is_finished());
+        }
+
+        info!("--- b acquires the lock after a releases it");
+        {
+            let token_a = guard.fencing_token();
+            guard.release().await?;
+
+            let res = tokio::time::timeout(Duration::from_secs(3), waiter).await?;
+            let guard_b = res??;
+
+            assert_eq!("b", guard_b.holder());
+            assert!(guard_b.fencing_token() > token_a);
+            guard_b.release().await?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, kv))]
+    pub async fn election<KV: KVApi + 'static>(&self, kv: &Arc<KV>) -> anyhow::Result<()> {
+        info!("--- LockApiTestSuite::election() start");
+
+        let ttl = Duration::from_secs(3);
+
+        info!("--- no leader");
+        {
+            assert_eq!(None, kv.observe("e1").await?);
+        }
+
+        info!("--- a is elected");
+        let leader_a = {
+            let leader = kv.campaign("e1", "a", ttl).await?;
+            assert_eq!(Some("a".to_string()), kv.observe("e1").await?);
+            leader
+        };
+
+        info!("--- election is not a lock with the same name");
+        {
+            let res = kv.try_acquire_lock("e1", "b", ttl).await?;
+            res.expect("lock e1 is free").release().await?;
+        }
+
+        info!("--- b campaigns while a is the leader");
+        let campaign_b = {
+            let kv = kv.clone();
+            tokio::spawn(async move { kv.campaign("e1", "b", ttl).await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+        assert!(!campaign_b.is_finished());
+        assert_eq!(Some("a".to_string()), kv.observe("e1").await?);
+
+        info!("--- b is elected after a resigns");
+        {
+            kv.resign(leader_a).await?;
+
+            let res = tokio::time::timeout(Duration::from_secs(3), campaign_b).await?;
+            let leader_b = res??;
+
+            assert_eq!(Some("b".to_string()), kv.observe("e1").await?);
+
+            kv.resign(leader_b).await?;
+            assert_eq!(None, kv.observe("e1").await?);
+        }
+
+        Ok(())
+    }
+}
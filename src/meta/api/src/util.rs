@@ -248,6 +248,7 @@ pub fn txn_op_put(key: &impl KVApiKey, value: Vec<u8>) -> TxnOp {
             value,
             prev_value: true,
             expire_at: None,
+            lease: None,
         })),
     }
 }
@@ -260,6 +261,7 @@ pub fn txn_op_put_with_expire(key: &impl KVApiKey, value: Vec<u8>, expire_at: u6
             value,
             prev_value: true,
             expire_at: Some(expire_at),
+            lease: None,
         })),
     }
 }
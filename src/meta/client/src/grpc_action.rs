@@ -17,11 +17,17 @@ use std::fmt::Debug;
 
 use common_meta_types::protobuf::meta_service_client::MetaServiceClient;
 use common_meta_types::protobuf::ClientInfo;
+use common_meta_types::protobuf::LeaseGrantRequest;
+use common_meta_types::protobuf::LeaseGrantResponse;
+use common_meta_types::protobuf::LeaseRevokeRequest;
+use common_meta_types::protobuf::LeaseRevokeResponse;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
 use common_meta_types::GetKVReply;
 use common_meta_types::GetKVReq;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::ListKVReq;
 use common_meta_types::MGetKVReply;
@@ -35,6 +41,7 @@ use tonic::transport::Channel;
 use tonic::Request;
 
 use crate::grpc_client::AuthInterceptor;
+use crate::message::ConsistentRead;
 use crate::message::ExportReq;
 use crate::message::GetClientInfo;
 use crate::message::GetEndpoints;
@@ -52,6 +59,7 @@ pub enum MetaGrpcReq {
     GetKV(GetKVReq),
     MGetKV(MGetKVReq),
     ListKV(ListKVReq),
+    ListKVRange(ListKVRangeReq),
 }
 
 impl TryInto<MetaGrpcReq> for Request<RaftRequest> {
@@ -92,6 +100,16 @@ impl RequestFor for ListKVReq {
     type Reply = ListKVReply;
 }
 
+impl RequestFor for ListKVRangeReq {
+    type Reply = ListKVRangeReply;
+}
+
+impl<T> RequestFor for ConsistentRead<T>
+where T: RequestFor
+{
+    type Reply = T::Reply;
+}
+
 impl RequestFor for UpsertKVReq {
     type Reply = UpsertKVReply;
 }
@@ -119,3 +137,11 @@ impl RequestFor for TxnRequest {
 impl RequestFor for GetClientInfo {
     type Reply = ClientInfo;
 }
+
+impl RequestFor for LeaseGrantRequest {
+    type Reply = LeaseGrantResponse;
+}
+
+impl RequestFor for LeaseRevokeRequest {
+    type Reply = LeaseRevokeResponse;
+}
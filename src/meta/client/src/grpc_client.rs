@@ -44,6 +44,12 @@ use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::ExportedChunk;
 use common_meta_types::protobuf::HandshakeRequest;
+use common_meta_types::protobuf::LeaseGrantRequest;
+use common_meta_types::protobuf::LeaseGrantResponse;
+use common_meta_types::protobuf::LeaseKeepAliveRequest;
+use common_meta_types::protobuf::LeaseKeepAliveResponse;
+use common_meta_types::protobuf::LeaseRevokeRequest;
+use common_meta_types::protobuf::LeaseRevokeResponse;
 use common_meta_types::protobuf::MemberListReply;
 use common_meta_types::protobuf::MemberListRequest;
 use common_meta_types::protobuf::RaftReply;
@@ -57,13 +63,16 @@ use common_meta_types::MetaClientError;
 use common_meta_types::MetaError;
 use common_meta_types::MetaHandshakeError;
 use common_meta_types::MetaNetworkError;
+use common_meta_types::ReadConsistency;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
+use common_meta_types::READ_CONSISTENCY_KEY;
 use common_metrics::label_counter_with_val_and_labels;
 use common_metrics::label_decrement_gauge_with_val_and_labels;
 use common_metrics::label_histogram_with_val;
 use common_metrics::label_increment_gauge_with_val_and_labels;
 use futures::stream::StreamExt;
+use futures::Stream;
 use parking_lot::Mutex;
 use prost::Message;
 use semver::Version;
@@ -71,6 +80,7 @@ use serde::de::DeserializeOwned;
 use tonic::async_trait;
 use tonic::client::GrpcService;
 use tonic::codegen::InterceptedService;
+use tonic::metadata::Ascii;
 use tonic::metadata::MetadataValue;
 use tonic::service::Interceptor;
 use tonic::transport::Channel;
@@ -81,6 +91,7 @@ use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
+use tracing::Instrument;
 
 use crate::from_digit_ver;
 use crate::grpc_action::RequestFor;
@@ -171,6 +182,7 @@ impl ClientHandle {
             let req = message::ClientWorkerRequest {
                 resp_tx: tx,
                 req: req.into(),
+                span: tracing::Span::current(),
             };
 
             label_increment_gauge_with_val_and_labels(
@@ -238,6 +250,124 @@ impl ClientHandle {
     pub async fn get_endpoints(&self) -> Result<Vec<String>, MetaError> {
         self.request(message::GetEndpoints {}).await
     }
+
+    /// Send a read request, such as a `GetKVReq`, that is served with the specified consistency.
+    ///
+    /// `ReadConsistency::Linearizable` and `ReadConsistency::BoundedStaleness` let the meta-service node
+    /// this client connects to serve the read, even if it is a follower or learner.
+    pub async fn read_with<T, R>(
+        &self,
+        req: T,
+        consistency: ReadConsistency,
+    ) -> Result<R, KVAppError>
+    where
+        T: RequestFor<Reply = R>,
+        T: Into<MetaGrpcReq>,
+        Result<R, KVAppError>: TryFrom<message::Response>,
+        <Result<R, KVAppError> as TryFrom<message::Response>>::Error: std::fmt::Display,
+    {
+        self.request(message::ConsistentRead::new(req, consistency))
+            .await
+    }
+
+    /// Grant a lease that expires after `ttl_ms` unless it is kept alive.
+    pub async fn lease_grant(&self, ttl_ms: u64) -> Result<LeaseGrantResponse, MetaError> {
+        self.request(LeaseGrantRequest { ttl_ms }).await
+    }
+
+    /// Revoke a lease and delete the keys attached to it.
+    pub async fn lease_revoke(&self, id: u64) -> Result<LeaseRevokeResponse, MetaError> {
+        self.request(LeaseRevokeRequest { id }).await
+    }
+
+    /// Keep leases alive by sending keep-alive requests through a stream.
+    ///
+    /// A response is sent back for every request, with `ttl_ms` being 0 if the lease is gone.
+    pub async fn lease_keep_alive(
+        &self,
+        requests: impl Stream<Item = LeaseKeepAliveRequest> + Send + 'static,
+    ) -> Result<tonic::codec::Streaming<LeaseKeepAliveResponse>, MetaError> {
+        let mut client = self.make_client().await?;
+        let res = client.lease_keep_alive(requests).await?;
+        Ok(res.into_inner())
+    }
+
+    /// Watch kv changes and resume from the next revision if the stream is broken.
+    ///
+    /// No change is lost or received twice across a reconnection,
+    /// thus a consumer such as a local cache can be invalidated by it without re-listing.
+    ///
+    /// Connecting or resuming is retried with an exponential backoff, at most [`WATCH_RESUME_MAX_RETRIES`] times
+    /// in a row. The stream yields an error and ends if it can not be resumed:
+    /// e.g., with `Code::OutOfRange` if the changes to resume from are no longer in the history on server side,
+    /// in which case the consumer has to re-list and watch again.
+    pub fn watch_resumable(
+        self: &Arc<Self>,
+        watch_request: WatchRequest,
+    ) -> impl Stream<Item = Result<WatchResponse, Status>> + Send + 'static {
+        let state = (self.clone(), watch_request, None, false);
+
+        futures::stream::unfold(state, |(handle, mut req, mut strm, finished)| async move {
+            if finished {
+                return None;
+            }
+
+            // Failures since the last received message.
+            let mut retries = 0;
+
+            loop {
+                let status = match strm {
+                    Some(ref mut s) => match s.message().await {
+                        Ok(Some(resp)) => {
+                            req.start_revision = Some(resp.revision + 1);
+                            return Some((Ok(resp), (handle, req, strm, false)));
+                        }
+                        Ok(None) => Status::unavailable("watch stream is closed by server"),
+                        Err(status) => status,
+                    },
+                    None => match handle.connect_watch(req.clone()).await {
+                        Ok(s) => {
+                            strm = Some(s);
+                            continue;
+                        }
+                        Err(status) => status,
+                    },
+                };
+
+                // Without a revision to resume from, a broken stream can not be reconnected without losing changes.
+                let resumable = req.start_revision.is_some() || strm.is_none();
+
+                if !resumable
+                    || status.code() == Code::OutOfRange
+                    || retries >= WATCH_RESUME_MAX_RETRIES
+                {
+                    return Some((Err(status), (handle, req, None, true)));
+                }
+
+                let backoff = watch_resume_backoff(retries);
+                warn!(
+                    "watch stream is broken: {}, resume from revision: {:?} after {:?}",
+                    status, req.start_revision, backoff
+                );
+
+                retries += 1;
+                strm = None;
+                sleep(backoff).await;
+            }
+        })
+    }
+
+    async fn connect_watch(
+        &self,
+        watch_request: WatchRequest,
+    ) -> Result<tonic::codec::Streaming<WatchResponse>, Status> {
+        let mut client = self
+            .make_client()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+        let res = client.watch(watch_request).await?;
+        Ok(res.into_inner())
+    }
 }
 
 pub struct MetaGrpcClient {
@@ -335,6 +465,90 @@ impl MetaGrpcClient {
         Ok(handle)
     }
 
+    /// Handle a request sent from a handle to the worker, and build the response.
+    async fn handle_req(&self, req: message::Request) -> message::Response {
+        match req {
+            message::Request::Get(r) => {
+                let resp = self.kv_api(r).await;
+                message::Response::Get(resp)
+            }
+            message::Request::MGet(r) => {
+                let resp = self.kv_api(r).await;
+                message::Response::MGet(resp)
+            }
+            message::Request::PrefixList(r) => {
+                let resp = self.kv_api(r).await;
+                message::Response::PrefixList(resp)
+            }
+            message::Request::ListRange(r) => {
+                let resp = self.kv_api(r).await;
+                message::Response::ListRange(resp)
+            }
+            message::Request::Upsert(r) => {
+                let resp = self.kv_api(r).await;
+                message::Response::Upsert(resp)
+            }
+            message::Request::ConsistentRead(r) => {
+                let consistency = r.consistency;
+                match r.req {
+                    MetaGrpcReq::GetKV(a) => {
+                        let resp = self.kv_api_with(a, consistency).await;
+                        message::Response::Get(resp)
+                    }
+                    MetaGrpcReq::MGetKV(a) => {
+                        let resp = self.kv_api_with(a, consistency).await;
+                        message::Response::MGet(resp)
+                    }
+                    MetaGrpcReq::ListKV(a) => {
+                        let resp = self.kv_api_with(a, consistency).await;
+                        message::Response::PrefixList(resp)
+                    }
+                    MetaGrpcReq::ListKVRange(a) => {
+                        let resp = self.kv_api_with(a, consistency).await;
+                        message::Response::ListRange(resp)
+                    }
+                    // A write is always served by the leader
+                    MetaGrpcReq::UpsertKV(a) => {
+                        let resp = self.kv_api(a).await;
+                        message::Response::Upsert(resp)
+                    }
+                }
+            }
+            message::Request::Txn(r) => {
+                let resp = self.transaction(r).await;
+                message::Response::Txn(resp)
+            }
+            message::Request::Watch(r) => {
+                let resp = self.watch(r).await;
+                message::Response::Watch(resp)
+            }
+            message::Request::Export(r) => {
+                let resp = self.export(r).await;
+                message::Response::Export(resp)
+            }
+            message::Request::LeaseGrant(r) => {
+                let resp = self.lease_grant(r).await;
+                message::Response::LeaseGrant(resp)
+            }
+            message::Request::LeaseRevoke(r) => {
+                let resp = self.lease_revoke(r).await;
+                message::Response::LeaseRevoke(resp)
+            }
+            message::Request::MakeClient(_) => {
+                let resp = self.make_client().await;
+                message::Response::MakeClient(resp)
+            }
+            message::Request::GetEndpoints(_) => {
+                let resp = self.get_endpoints().await;
+                message::Response::GetEndpoints(Ok(resp))
+            }
+            message::Request::GetClientInfo(_) => {
+                let resp = self.get_client_info().await;
+                message::Response::GetClientInfo(resp)
+            }
+        }
+    }
+
     /// A worker runs a receiving-loop to accept user-request to metasrv and deals with request in the dedicated runtime.
     #[tracing::instrument(level = "info", skip_all)]
     async fn worker_loop(self: Arc<Self>, mut req_rx: Receiver<message::ClientWorkerRequest>) {
@@ -361,54 +575,14 @@ impl MetaGrpcClient {
             }
 
             let resp_tx = req.resp_tx;
+            let span = req.span;
             let req = req.req;
             let req_name = req.name();
             let req_str = format!("{:?}", req);
 
             let start = Instant::now();
 
-            let resp = match req {
-                message::Request::Get(r) => {
-                    let resp = self.kv_api(r).await;
-                    message::Response::Get(resp)
-                }
-                message::Request::MGet(r) => {
-                    let resp = self.kv_api(r).await;
-                    message::Response::MGet(resp)
-                }
-                message::Request::PrefixList(r) => {
-                    let resp = self.kv_api(r).await;
-                    message::Response::PrefixList(resp)
-                }
-                message::Request::Upsert(r) => {
-                    let resp = self.kv_api(r).await;
-                    message::Response::Upsert(resp)
-                }
-                message::Request::Txn(r) => {
-                    let resp = self.transaction(r).await;
-                    message::Response::Txn(resp)
-                }
-                message::Request::Watch(r) => {
-                    let resp = self.watch(r).await;
-                    message::Response::Watch(resp)
-                }
-                message::Request::Export(r) => {
-                    let resp = self.export(r).await;
-                    message::Response::Export(resp)
-                }
-                message::Request::MakeClient(_) => {
-                    let resp = self.make_client().await;
-                    message::Response::MakeClient(resp)
-                }
-                message::Request::GetEndpoints(_) => {
-                    let resp = self.get_endpoints().await;
-                    message::Response::GetEndpoints(Ok(resp))
-                }
-                message::Request::GetClientInfo(_) => {
-                    let resp = self.get_client_info().await;
-                    message::Response::GetClientInfo(resp)
-                }
-            };
+            let resp = self.handle_req(req).instrument(span).await;
 
             debug!(
                 resp = debug(&resp),
@@ -797,8 +971,46 @@ impl MetaGrpcClient {
         Ok(res.into_inner())
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn lease_grant(
+        &self,
+        req: LeaseGrantRequest,
+    ) -> Result<LeaseGrantResponse, MetaError> {
+        debug!("MetaGrpcClient::lease_grant");
+
+        let mut client = self.make_client().await?;
+        let res = client.lease_grant(req).await?;
+        Ok(res.into_inner())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn lease_revoke(
+        &self,
+        req: LeaseRevokeRequest,
+    ) -> Result<LeaseRevokeResponse, MetaError> {
+        debug!("MetaGrpcClient::lease_revoke");
+
+        let mut client = self.make_client().await?;
+        let res = client.lease_revoke(req).await?;
+        Ok(res.into_inner())
+    }
+
     #[tracing::instrument(level = "debug", skip(self, v))]
     pub(crate) async fn kv_api<T, R>(&self, v: T) -> Result<R, KVAppError>
+    where
+        T: RequestFor<Reply = R>,
+        T: Into<MetaGrpcReq>,
+        R: DeserializeOwned,
+    {
+        self.kv_api_with(v, ReadConsistency::Leader).await
+    }
+
+    /// Send a kv api request, which is a read served with the specified consistency.
+    pub(crate) async fn kv_api_with<T, R>(
+        &self,
+        v: T,
+        consistency: ReadConsistency,
+    ) -> Result<R, KVAppError>
     where
         T: RequestFor<Reply = R>,
         T: Into<MetaGrpcReq>,
@@ -817,7 +1029,8 @@ impl MetaGrpcClient {
             "MetaGrpcClient::kv_api serialized request"
         );
 
-        let req = common_tracing::inject_span_to_tonic_request(req);
+        let mut req = common_tracing::inject_span_to_tonic_request(req);
+        set_read_consistency(&mut req, consistency)?;
 
         let mut client = self.make_client().await?;
         let result = client.kv_api(req).await;
@@ -836,7 +1049,8 @@ impl MetaGrpcClient {
                             "fail to encode request",
                         ))
                     })?;
-                    let req = common_tracing::inject_span_to_tonic_request(req);
+                    let mut req = common_tracing::inject_span_to_tonic_request(req);
+                    set_read_consistency(&mut req, consistency)?;
                     Ok(client.kv_api(req).await?.into_inner())
                 } else {
                     Err(s)
@@ -890,6 +1104,15 @@ impl MetaGrpcClient {
     }
 }
 
+/// Max number of times in a row that a resumable watch stream is reconnected.
+pub const WATCH_RESUME_MAX_RETRIES: u32 = 8;
+
+/// The delay before the `retries`-th reconnection of a resumable watch stream: 100ms, 200ms, 400ms ... up to 5s.
+fn watch_resume_backoff(retries: u32) -> Duration {
+    let ms = 100u64.saturating_mul(1 << retries.min(6));
+    Duration::from_millis(ms.min(5_000))
+}
+
 fn status_is_retryable(status: &Status) -> bool {
     matches!(
         status.code(),
@@ -897,6 +1120,28 @@ fn status_is_retryable(status: &Status) -> bool {
     )
 }
 
+/// Specify the consistency of a read in the request metadata.
+///
+/// Nothing is added for the default `ReadConsistency::Leader`, to be compatible with a server that does not
+/// recognize it.
+fn set_read_consistency<T>(
+    req: &mut Request<T>,
+    consistency: ReadConsistency,
+) -> Result<(), MetaNetworkError> {
+    if consistency == ReadConsistency::Leader {
+        return Ok(());
+    }
+
+    let value = serde_json::to_string(&consistency)
+        .map_err(|e| InvalidArgument::new(e, "fail to encode read consistency"))?;
+    let value: MetadataValue<Ascii> = value
+        .parse()
+        .map_err(|e| InvalidArgument::new(e, "fail to encode read consistency"))?;
+
+    req.metadata_mut().insert(READ_CONSISTENCY_KEY, value);
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct AuthInterceptor {
     pub token: Vec<u8>,
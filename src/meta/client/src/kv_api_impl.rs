@@ -16,6 +16,8 @@ use common_meta_api::KVApi;
 use common_meta_types::GetKVReply;
 use common_meta_types::GetKVReq;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::ListKVReq;
 use common_meta_types::MGetKVReply;
@@ -59,6 +61,11 @@ impl KVApi for MetaGrpcClient {
         Ok(reply)
     }
 
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        let reply = self.kv_api(req).await?;
+        Ok(reply)
+    }
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         let reply = self.transaction(txn).await?;
         Ok(reply)
@@ -96,6 +103,11 @@ impl KVApi for ClientHandle {
         Ok(reply)
     }
 
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        let reply = self.request(req).await?;
+        Ok(reply)
+    }
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         let reply = self.request(txn).await?;
         Ok(reply)
@@ -16,17 +16,24 @@ use common_base::base::tokio::sync::oneshot::Sender;
 use common_meta_types::protobuf::meta_service_client::MetaServiceClient;
 use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::ExportedChunk;
+use common_meta_types::protobuf::LeaseGrantRequest;
+use common_meta_types::protobuf::LeaseGrantResponse;
+use common_meta_types::protobuf::LeaseRevokeRequest;
+use common_meta_types::protobuf::LeaseRevokeResponse;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
 use common_meta_types::GetKVReply;
 use common_meta_types::GetKVReq;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::ListKVReq;
 use common_meta_types::MGetKVReply;
 use common_meta_types::MGetKVReq;
 use common_meta_types::MetaClientError;
 use common_meta_types::MetaError;
+use common_meta_types::ReadConsistency;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_meta_types::UpsertKVReply;
@@ -35,6 +42,7 @@ use tonic::codegen::InterceptedService;
 use tonic::transport::Channel;
 
 use crate::grpc_client::AuthInterceptor;
+use crate::MetaGrpcReq;
 
 /// A request that is sent by a meta-client handle to its worker.
 #[derive(Debug)]
@@ -44,6 +52,10 @@ pub struct ClientWorkerRequest {
 
     /// Request body
     pub(crate) req: Request,
+
+    /// The span of the caller, in which the worker handles the request,
+    /// so that the trace context sent to meta-service is the one of the caller.
+    pub(crate) span: tracing::Span,
 }
 
 /// Meta-client handle-to-worker request body
@@ -58,9 +70,16 @@ pub enum Request {
     /// List KVs by key prefix
     PrefixList(ListKVReq),
 
+    /// List KVs in a key range, in pages
+    ListRange(ListKVRangeReq),
+
     /// Update or insert KV
     Upsert(UpsertKVReq),
 
+    /// Read with a consistency other than the default one
+    #[from(ignore)]
+    ConsistentRead(ConsistentRead<MetaGrpcReq>),
+
     /// Run a transaction on remote
     Txn(TxnRequest),
 
@@ -70,6 +89,12 @@ pub enum Request {
     /// Export all data
     Export(ExportReq),
 
+    /// Grant a lease
+    LeaseGrant(LeaseGrantRequest),
+
+    /// Revoke a lease and delete the keys attached to it
+    LeaseRevoke(LeaseRevokeRequest),
+
     /// Get a initialized grpc-client
     MakeClient(MakeClient),
 
@@ -86,10 +111,14 @@ impl Request {
             Request::Get(_) => "Get",
             Request::MGet(_) => "MGet",
             Request::PrefixList(_) => "PrefixList",
+            Request::ListRange(_) => "ListRange",
             Request::Upsert(_) => "Upsert",
+            Request::ConsistentRead(_) => "ConsistentRead",
             Request::Txn(_) => "Txn",
             Request::Watch(_) => "Watch",
             Request::Export(_) => "Export",
+            Request::LeaseGrant(_) => "LeaseGrant",
+            Request::LeaseRevoke(_) => "LeaseRevoke",
             Request::MakeClient(_) => "MakeClient",
             Request::GetEndpoints(_) => "GetEndpoints",
             Request::GetClientInfo(_) => "GetClientInfo",
@@ -103,10 +132,13 @@ pub enum Response {
     Get(Result<GetKVReply, KVAppError>),
     MGet(Result<MGetKVReply, KVAppError>),
     PrefixList(Result<ListKVReply, KVAppError>),
+    ListRange(Result<ListKVRangeReply, KVAppError>),
     Upsert(Result<UpsertKVReply, KVAppError>),
     Txn(Result<TxnReply, KVAppError>),
     Watch(Result<tonic::codec::Streaming<WatchResponse>, MetaError>),
     Export(Result<tonic::codec::Streaming<ExportedChunk>, MetaError>),
+    LeaseGrant(Result<LeaseGrantResponse, MetaError>),
+    LeaseRevoke(Result<LeaseRevokeResponse, MetaError>),
     MakeClient(
         Result<MetaServiceClient<InterceptedService<Channel, AuthInterceptor>>, MetaClientError>,
     ),
@@ -120,10 +152,13 @@ impl Response {
             Response::Get(res) => res.is_err(),
             Response::MGet(res) => res.is_err(),
             Response::PrefixList(res) => res.is_err(),
+            Response::ListRange(res) => res.is_err(),
             Response::Upsert(res) => res.is_err(),
             Response::Txn(res) => res.is_err(),
             Response::Watch(res) => res.is_err(),
             Response::Export(res) => res.is_err(),
+            Response::LeaseGrant(res) => res.is_err(),
+            Response::LeaseRevoke(res) => res.is_err(),
             Response::MakeClient(res) => res.is_err(),
             Response::GetEndpoints(res) => res.is_err(),
             Response::GetClientInfo(res) => res.is_err(),
@@ -144,6 +179,10 @@ impl Response {
                 .as_ref()
                 .err()
                 .map(|x| x as &(dyn std::error::Error + 'static)),
+            Response::ListRange(res) => res
+                .as_ref()
+                .err()
+                .map(|x| x as &(dyn std::error::Error + 'static)),
             Response::Upsert(res) => res
                 .as_ref()
                 .err()
@@ -160,6 +199,14 @@ impl Response {
                 .as_ref()
                 .err()
                 .map(|x| x as &(dyn std::error::Error + 'static)),
+            Response::LeaseGrant(res) => res
+                .as_ref()
+                .err()
+                .map(|x| x as &(dyn std::error::Error + 'static)),
+            Response::LeaseRevoke(res) => res
+                .as_ref()
+                .err()
+                .map(|x| x as &(dyn std::error::Error + 'static)),
             Response::MakeClient(res) => res
                 .as_ref()
                 .err()
@@ -177,6 +224,30 @@ impl Response {
     }
 }
 
+/// A read request that is served with the specified consistency.
+///
+/// E.g., with `ReadConsistency::Linearizable` a read can be served by a follower or learner the client
+/// connects to, without being forwarded to the leader.
+#[derive(Clone, Debug)]
+pub struct ConsistentRead<T> {
+    pub req: T,
+    pub consistency: ReadConsistency,
+}
+
+impl<T> ConsistentRead<T> {
+    pub fn new(req: T, consistency: ReadConsistency) -> Self {
+        Self { req, consistency }
+    }
+}
+
+impl<T> From<ConsistentRead<T>> for Request
+where T: Into<MetaGrpcReq>
+{
+    fn from(r: ConsistentRead<T>) -> Self {
+        Request::ConsistentRead(ConsistentRead::new(r.req.into(), r.consistency))
+    }
+}
+
 /// Export all data stored in metasrv
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ExportReq {}
@@ -25,6 +25,12 @@ use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::ExportedChunk;
 use common_meta_types::protobuf::HandshakeResponse;
+use common_meta_types::protobuf::LeaseGrantRequest;
+use common_meta_types::protobuf::LeaseGrantResponse;
+use common_meta_types::protobuf::LeaseKeepAliveRequest;
+use common_meta_types::protobuf::LeaseKeepAliveResponse;
+use common_meta_types::protobuf::LeaseRevokeRequest;
+use common_meta_types::protobuf::LeaseRevokeResponse;
 use common_meta_types::protobuf::MemberListReply;
 use common_meta_types::protobuf::MemberListRequest;
 use common_meta_types::protobuf::RaftReply;
@@ -122,6 +128,36 @@ impl MetaService for GrpcServiceForTestImpl {
     ) -> Result<Response<ClientInfo>, Status> {
         todo!()
     }
+
+    async fn lease_grant(
+        &self,
+        _request: Request<LeaseGrantRequest>,
+    ) -> Result<Response<LeaseGrantResponse>, Status> {
+        todo!()
+    }
+
+    type LeaseKeepAliveStream = Pin<
+        Box<
+            dyn Stream<Item = Result<LeaseKeepAliveResponse, tonic::Status>>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    >;
+
+    async fn lease_keep_alive(
+        &self,
+        _request: Request<Streaming<LeaseKeepAliveRequest>>,
+    ) -> Result<Response<Self::LeaseKeepAliveStream>, Status> {
+        todo!()
+    }
+
+    async fn lease_revoke(
+        &self,
+        _request: Request<LeaseRevokeRequest>,
+    ) -> Result<Response<LeaseRevokeResponse>, Status> {
+        todo!()
+    }
 }
 
 pub fn start_grpc_server() -> String {
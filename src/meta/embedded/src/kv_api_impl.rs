@@ -17,6 +17,8 @@ use common_meta_api::KVApi;
 pub use common_meta_sled_store::init_temp_sled_db;
 use common_meta_types::GetKVReply;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::MGetKVReply;
 use common_meta_types::TxnReply;
@@ -48,6 +50,11 @@ impl KVApi for MetaEmbedded {
         sm.prefix_list_kv(prefix).await
     }
 
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        let sm = self.inner.lock().await;
+        sm.list_kv(req).await
+    }
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         let sm = self.inner.lock().await;
         sm.transaction(txn).await
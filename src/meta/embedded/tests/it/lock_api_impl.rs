@@ -0,0 +1,55 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_meta_api::LockApiTestSuite;
+use common_meta_embedded::MetaEmbedded;
+
+#[tokio::test]
+async fn test_lock_acquire_release() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.lock_acquire_release(&kv).await
+}
+
+#[tokio::test]
+async fn test_lock_renew() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.lock_renew(&kv).await
+}
+
+#[tokio::test]
+async fn test_lock_lost() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.lock_lost(&kv).await
+}
+
+#[tokio::test]
+async fn test_lock_release_on_drop() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.lock_release_on_drop(&kv).await
+}
+
+#[tokio::test]
+async fn test_lock_acquire_wait() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.lock_acquire_wait(&kv).await
+}
+
+#[tokio::test]
+async fn test_election() -> anyhow::Result<()> {
+    let kv = Arc::new(MetaEmbedded::new_temp().await?);
+    LockApiTestSuite {}.election(&kv).await
+}
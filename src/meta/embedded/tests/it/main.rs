@@ -13,4 +13,5 @@
 //  limitations under the License.
 
 mod kv_api_impl;
+mod lock_api_impl;
 mod schema_api_impl;
@@ -92,7 +92,9 @@ impl FromToProto for mt::UserOption {
 
         Ok(mt::UserOption::default()
             .with_flags(flags)
-            .with_default_role(p.default_role))
+            .with_default_role(p.default_role)
+            .with_password_policy(p.password_policy)
+            .with_must_change_password(p.must_change_password))
     }
 
     fn to_pb(&self) -> Result<pb::UserOption, Incompatible> {
@@ -101,6 +103,8 @@ impl FromToProto for mt::UserOption {
             min_compatible: MIN_COMPATIBLE_VER,
             flags: self.flags().bits(),
             default_role: self.default_role().cloned(),
+            password_policy: self.password_policy().cloned(),
+            must_change_password: self.must_change_password(),
         })
     }
 }
@@ -269,6 +273,24 @@ impl FromToProto for mt::UserInfo {
             option: mt::UserOption::from_pb(p.option.ok_or_else(|| Incompatible {
                 reason: "UserInfo.option cannot be None".to_string(),
             })?)?,
+            history_auth_infos: p
+                .history_auth_infos
+                .into_iter()
+                .map(mt::AuthInfo::from_pb)
+                .collect::<Result<Vec<_>, _>>()?,
+            password_fails: p
+                .password_fails
+                .into_iter()
+                .map(DateTime::<Utc>::from_pb)
+                .collect::<Result<Vec<_>, _>>()?,
+            password_update_on: match p.password_update_on {
+                Some(update_on) => Some(DateTime::<Utc>::from_pb(update_on)?),
+                None => None,
+            },
+            lockout_time: match p.lockout_time {
+                Some(lockout_time) => Some(DateTime::<Utc>::from_pb(lockout_time)?),
+                None => None,
+            },
         })
     }
 
@@ -282,6 +304,24 @@ impl FromToProto for mt::UserInfo {
             grants: Some(mt::UserGrantSet::to_pb(&self.grants)?),
             quota: Some(mt::UserQuota::to_pb(&self.quota)?),
             option: Some(mt::UserOption::to_pb(&self.option)?),
+            history_auth_infos: self
+                .history_auth_infos
+                .iter()
+                .map(mt::AuthInfo::to_pb)
+                .collect::<Result<Vec<_>, _>>()?,
+            password_fails: self
+                .password_fails
+                .iter()
+                .map(|t| t.to_pb())
+                .collect::<Result<Vec<_>, _>>()?,
+            password_update_on: match self.password_update_on {
+                Some(update_on) => Some(update_on.to_pb()?),
+                None => None,
+            },
+            lockout_time: match self.lockout_time {
+                Some(lockout_time) => Some(lockout_time.to_pb()?),
+                None => None,
+            },
         })
     }
 }
@@ -75,6 +75,10 @@ const META_CHANGE_LOG: &[(u64, &str)] = &[
         "2022-11-24: Add: users.proto/FileFormatOptions::nan_display",
     ),
     (22, "2022-12-13: Add: users.proto/FileFormatOptions::quote"),
+    (
+        23,
+        "2026-10-19: Add: user.proto/UserOption::password_policy, must_change_password; UserInfo::history_auth_infos, password_fails, password_update_on, lockout_time",
+    ),
 ];
 
 pub const VER: u64 = META_CHANGE_LOG.last().unwrap().0;
@@ -64,6 +64,46 @@ fn test_user_info() -> UserInfo {
             max_storage_in_bytes: 20480,
         },
         option,
+        history_auth_infos: vec![],
+        password_fails: vec![],
+        password_update_on: None,
+        lockout_time: None,
+    }
+}
+
+fn test_user_info_v23() -> UserInfo {
+    let option = mt::UserOption::default()
+        .with_set_flag(mt::UserOptionFlag::TenantSetting)
+        .with_default_role(Some("role1".into()))
+        .with_password_policy(Some("policy1".into()))
+        .with_must_change_password(true);
+
+    let at = |hour, min| {
+        DateTime::<Utc>::from_utc(
+            NaiveDateTime::new(
+                NaiveDate::from_ymd(2022, 10, 1),
+                NaiveTime::from_hms(hour, min, 0),
+            ),
+            Utc,
+        )
+    };
+
+    mt::UserInfo {
+        option,
+        history_auth_infos: vec![mt::AuthInfo::Password {
+            hash_value: b"old_password".to_vec(),
+            hash_method: mt::PasswordHashMethod::DoubleSha1,
+        }],
+        password_fails: vec![at(0, 0)],
+        password_update_on: Some(DateTime::<Utc>::from_utc(
+            NaiveDateTime::new(
+                NaiveDate::from_ymd(2022, 9, 1),
+                NaiveTime::from_hms(0, 0, 0),
+            ),
+            Utc,
+        )),
+        lockout_time: Some(at(0, 15)),
+        ..test_user_info()
     }
 }
 
@@ -319,6 +359,11 @@ fn test_user_pb_from_to() -> anyhow::Result<()> {
     let got = mt::UserInfo::from_pb(test_user_info_pb)?;
     assert_eq!(got, test_user_info);
 
+    let test_user_info = test_user_info_v23();
+    let test_user_info_pb = test_user_info.to_pb()?;
+    let got = mt::UserInfo::from_pb(test_user_info_pb)?;
+    assert_eq!(got, test_user_info);
+
     Ok(())
 }
 
@@ -484,6 +529,16 @@ fn test_build_user_pb_buf() -> anyhow::Result<()> {
         println!("user_info: {:?}", buf);
     }
 
+    // UserInfo with password policy and login state, supported in version >= 23.
+    {
+        let user_info = test_user_info_v23();
+        let p = user_info.to_pb()?;
+
+        let mut buf = vec![];
+        common_protos::prost::Message::encode(&p, &mut buf)?;
+        println!("user_info_v23: {:?}", buf);
+    }
+
     // StageFile
     {
         let stage_file = test_stage_file();
@@ -580,6 +635,29 @@ fn test_load_old_user() -> anyhow::Result<()> {
         assert_eq!(want, got);
     }
 
+    {
+        // UserInfo with password policy and login state generated by test_build_user_pb_buf()
+        let user_info_v23: Vec<u8> = vec![
+            10, 9, 116, 101, 115, 116, 95, 117, 115, 101, 114, 18, 9, 108, 111, 99, 97, 108, 104,
+            111, 115, 116, 26, 25, 18, 17, 10, 13, 116, 101, 115, 116, 95, 112, 97, 115, 115, 119,
+            111, 114, 100, 16, 1, 160, 6, 23, 168, 6, 1, 34, 26, 10, 18, 10, 8, 10, 0, 160, 6, 23,
+            168, 6, 1, 16, 2, 160, 6, 23, 168, 6, 1, 160, 6, 23, 168, 6, 1, 42, 15, 8, 10, 16, 128,
+            80, 24, 128, 160, 1, 160, 6, 23, 168, 6, 1, 50, 26, 8, 1, 18, 5, 114, 111, 108, 101,
+            49, 26, 7, 112, 111, 108, 105, 99, 121, 49, 32, 1, 160, 6, 23, 168, 6, 1, 58, 24, 18,
+            16, 10, 12, 111, 108, 100, 95, 112, 97, 115, 115, 119, 111, 114, 100, 16, 1, 160, 6,
+            23, 168, 6, 1, 66, 23, 50, 48, 50, 50, 45, 49, 48, 45, 48, 49, 32, 48, 48, 58, 48, 48,
+            58, 48, 48, 32, 85, 84, 67, 74, 23, 50, 48, 50, 50, 45, 48, 57, 45, 48, 49, 32, 48, 48,
+            58, 48, 48, 58, 48, 48, 32, 85, 84, 67, 82, 23, 50, 48, 50, 50, 45, 49, 48, 45, 48, 49,
+            32, 48, 48, 58, 49, 53, 58, 48, 48, 32, 85, 84, 67, 160, 6, 23, 168, 6, 1,
+        ];
+        let p: pb::UserInfo =
+            common_protos::prost::Message::decode(user_info_v23.as_slice()).map_err(print_err)?;
+        let got = mt::UserInfo::from_pb(p).map_err(print_err)?;
+        let want = test_user_info_v23();
+
+        assert_eq!(want, got);
+    }
+
     // UserInfo is loadable
     {
         let user_info_v1: Vec<u8> = vec![
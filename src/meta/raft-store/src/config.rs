@@ -89,6 +89,25 @@ pub struct RaftConfig {
     /// The value is one or more addresses of a node in the cluster, to which this node sends a `join` request.
     pub join: Vec<String>,
 
+    /// Join the cluster as a non-voting learner.
+    ///
+    /// A learner replicates logs and serves reads but does not vote, thus it can be added to scale reads
+    /// without slowing down writes or elections.
+    /// It is ignored if this node is already a member of a cluster.
+    pub learner: bool,
+
+    /// The max number of keys a tenant can have. 0 means no limit.
+    ///
+    /// Writes that put keys for a tenant that has reached the limit are rejected; deletes are always allowed.
+    pub max_keys_per_tenant: u64,
+
+    /// The max total size in bytes of keys and values a tenant can have. 0 means no limit.
+    pub max_bytes_per_tenant: u64,
+
+    /// A request that takes longer than this, in milliseconds, is logged with the time spent in every phase,
+    /// such as forwarding, raft-log append and apply. 0 disables the slow request log.
+    pub slow_request_threshold_ms: u64,
+
     /// Do not run databend-meta, but just remove a node from its cluster.
     ///
     /// The value is one or more addresses of a node in the cluster, to which this node sends a `leave` request.
@@ -137,6 +156,10 @@ impl Default for RaftConfig {
             max_applied_log_to_keep: 1000,
             single: false,
             join: vec![],
+            learner: false,
+            max_keys_per_tenant: 0,
+            max_bytes_per_tenant: 0,
+            slow_request_threshold_ms: 1000,
             leave_via: vec![],
             leave_id: None,
             id: 0,
@@ -209,6 +232,12 @@ impl RaftConfig {
             )));
         }
 
+        if self.learner && self.single {
+            return Err(MetaStartupError::InvalidConfig(String::from(
+                "`learner` can not be used with `single`",
+            )));
+        }
+
         let self_addr = self.raft_api_listen_host_string();
         if self.join.contains(&self_addr) {
             return Err(MetaStartupError::InvalidConfig(String::from(
@@ -102,6 +102,18 @@ impl RaftLog {
         self.logs().append(logs).await
     }
 
+    /// Append logs into RaftLog without fsync.
+    ///
+    /// The logs are guaranteed to be fsync-ed only after [`Self::flush`] returns.
+    pub async fn append_unflushed(&self, logs: &[Entry<LogEntry>]) -> Result<(), MetaStorageError> {
+        self.logs().append_unflushed(logs).await
+    }
+
+    /// Fsync the logs appended with [`Self::append_unflushed`].
+    pub async fn flush(&self) -> Result<(), MetaStorageError> {
+        self.logs().flush().await
+    }
+
     /// Returns a borrowed key space in sled::Tree for logs
     pub fn logs(&self) -> AsKeySpace<Logs> {
         self.inner.key_space()
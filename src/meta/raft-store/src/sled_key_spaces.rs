@@ -14,6 +14,8 @@
 
 use common_meta_sled_store::openraft;
 use common_meta_sled_store::SledKeySpace;
+use common_meta_types::Change;
+use common_meta_types::Lease;
 use common_meta_types::LogEntry;
 use common_meta_types::LogIndex;
 use common_meta_types::Node;
@@ -29,6 +31,7 @@ use crate::state::RaftStateValue;
 use crate::state_machine::ClientLastRespValue;
 use crate::state_machine::ExpireKey;
 use crate::state_machine::ExpireValue;
+use crate::state_machine::LeaseKey;
 use crate::state_machine::LogMetaKey;
 use crate::state_machine::LogMetaValue;
 use crate::state_machine::StateMachineMetaKey;
@@ -120,6 +123,42 @@ impl SledKeySpace for ClientLastResps {
     type V = ClientLastRespValue;
 }
 
+/// Key-Value Types for leases in sled::Tree, keyed by lease id.
+pub struct Leases {}
+impl SledKeySpace for Leases {
+    const PREFIX: u8 = 11;
+    const NAME: &'static str = "leases";
+    type K = u64;
+    type V = Lease;
+}
+
+/// Stores an index of leases in expire time order: `(expire_at_ms, lease_id) -> lease_id`.
+pub struct LeaseExpires {}
+impl SledKeySpace for LeaseExpires {
+    const PREFIX: u8 = 14;
+    const NAME: &'static str = "lease-expires";
+    type K = ExpireKey;
+    type V = u64;
+}
+
+/// Stores the keys attached to leases: `(lease_id, key) -> ()`.
+pub struct LeaseKeys {}
+impl SledKeySpace for LeaseKeys {
+    const PREFIX: u8 = 15;
+    const NAME: &'static str = "lease-keys";
+    type K = LeaseKey;
+    type V = ();
+}
+
+/// Key-Value Types for the recent kv changes in sled::Tree, keyed by change revision.
+pub struct ChangeHistory {}
+impl SledKeySpace for ChangeHistory {
+    const PREFIX: u8 = 12;
+    const NAME: &'static str = "change-history";
+    type K = u64;
+    type V = Change<Vec<u8>, String>;
+}
+
 /// Enum of key-value pair types of all key spaces.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum KeySpaceKV {
@@ -159,4 +198,20 @@ pub enum KeySpaceKV {
         key: <LogMeta as SledKeySpace>::K,
         value: <LogMeta as SledKeySpace>::V,
     },
+    Leases {
+        key: <Leases as SledKeySpace>::K,
+        value: <Leases as SledKeySpace>::V,
+    },
+    ChangeHistory {
+        key: <ChangeHistory as SledKeySpace>::K,
+        value: <ChangeHistory as SledKeySpace>::V,
+    },
+    LeaseExpires {
+        key: <LeaseExpires as SledKeySpace>::K,
+        value: <LeaseExpires as SledKeySpace>::V,
+    },
+    LeaseKeys {
+        key: <LeaseKeys as SledKeySpace>::K,
+        value: <LeaseKeys as SledKeySpace>::V,
+    },
 }
@@ -0,0 +1,304 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use common_meta_api::deserialize_struct;
+use common_meta_app::schema::DBIdTableName;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_types::SeqV;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Prefixes of keys whose second segment is the escaped tenant, e.g., `__fd_database/<tenant>/<db_name>`.
+pub const TENANT_KEY_PREFIXES: &[&str] = &[
+    "__fd_clusters",
+    "__fd_database",
+    "__fd_db_id_list",
+    "__fd_quotas",
+    "__fd_roles",
+    "__fd_settings",
+    "__fd_share",
+    "__fd_stage_files",
+    "__fd_stages",
+    "__fd_table_count",
+    "__fd_udfs",
+    "__fd_users",
+];
+
+/// Prefixes of keys whose second segment is the id of the database they belong to,
+/// e.g., `__fd_table/<db_id>/<table_name>`.
+pub const DB_ID_KEY_PREFIXES: &[&str] = &[
+    "__fd_database_by_id",
+    "__fd_database_id_to_name",
+    "__fd_table",
+    "__fd_table_id_list",
+];
+
+/// Prefixes of keys whose second segment is the id of the table they belong to,
+/// e.g., `__fd_table_by_id/<table_id>`.
+pub const TABLE_ID_KEY_PREFIXES: &[&str] = &[
+    "__fd_table_by_id",
+    "__fd_table_copied_file_lock",
+    "__fd_table_copied_files",
+    "__fd_table_id_to_name",
+];
+
+/// The database id to tenant mapping is learned from `__fd_database_id_to_name/<db_id> -> DatabaseNameIdent`.
+const DB_ID_TO_NAME_PREFIX: &str = "__fd_database_id_to_name";
+
+/// The table id to database id mapping is learned from `__fd_table_id_to_name/<table_id> -> DBIdTableName`.
+const TABLE_ID_TO_NAME_PREFIX: &str = "__fd_table_id_to_name";
+
+/// The owner of a key, by which the key is attributed to a tenant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyOwner<'a> {
+    /// The unescaped tenant, as in the records of the tenant, e.g., [`DatabaseNameIdent`].
+    Tenant(Cow<'a, str>),
+    Database(u64),
+    Table(u64),
+}
+
+/// The number of keys and the total size in bytes of keys and values of a set of kv records.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeySpaceUsage {
+    pub keys: u64,
+    pub bytes: u64,
+}
+
+impl KeySpaceUsage {
+    fn add(&mut self, key: &str, seq_v: &SeqV) {
+        self.keys += 1;
+        self.bytes += (key.len() + seq_v.data.len()) as u64;
+    }
+
+    fn sub(&mut self, key: &str, seq_v: &SeqV) {
+        self.keys = self.keys.saturating_sub(1);
+        self.bytes = self
+            .bytes
+            .saturating_sub((key.len() + seq_v.data.len()) as u64);
+    }
+}
+
+/// Usage of the kv key space, by tenant and by top-level `__fd_*` prefix.
+///
+/// Keys with a database id or a table id are attributed to the tenant owning the database.
+/// The owner of an id is learned from the `*_id_to_name` records.
+///
+/// It is kept in memory and is rebuilt when a state machine is opened or installed from a snapshot.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeySpaceStats {
+    pub tenants: BTreeMap<String, KeySpaceUsage>,
+    pub prefixes: BTreeMap<String, KeySpaceUsage>,
+
+    /// The tenant of every known database id.
+    #[serde(skip)]
+    db_tenants: BTreeMap<u64, String>,
+
+    /// The database id of every known table id.
+    #[serde(skip)]
+    table_dbs: BTreeMap<u64, u64>,
+}
+
+impl KeySpaceStats {
+    /// Add an existing kv record to the stats, e.g., when rebuilding the stats from all records.
+    ///
+    /// The owners of ids must be learned with [`Self::learn_owner`] before adding the keys of them.
+    pub fn add(&mut self, key: &str, seq_v: &SeqV) {
+        self.account(key, None, Some(seq_v));
+    }
+
+    /// Update the stats with the changes made by one log entry, each of which changes `key` from `prev` to `result`.
+    ///
+    /// Owners are learned before accounting and forgotten after,
+    /// because a database or table is created or removed along with its `*_id_to_name` record in one transaction.
+    pub fn apply_changes<'a>(
+        &mut self,
+        changes: impl Iterator<Item = (&'a str, Option<&'a SeqV>, Option<&'a SeqV>)> + Clone,
+    ) {
+        for (key, _prev, result) in changes.clone() {
+            if let Some(r) = result {
+                self.learn_owner(key, r);
+            }
+        }
+
+        for (key, prev, result) in changes.clone() {
+            self.account(key, prev, result);
+        }
+
+        for (key, _prev, result) in changes {
+            if result.is_none() {
+                self.forget_owner(key);
+            }
+        }
+    }
+
+    /// Returns a copy of the usage of every tenant and prefix, without the owners of ids.
+    pub fn usage(&self) -> Self {
+        Self {
+            tenants: self.tenants.clone(),
+            prefixes: self.prefixes.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the usage of a tenant.
+    pub fn tenant(&self, tenant: &str) -> KeySpaceUsage {
+        self.tenants.get(tenant).copied().unwrap_or_default()
+    }
+
+    /// Returns the tenant a key belongs to, or `None` if it does not belong to a tenant or its owner is unknown.
+    pub fn tenant_of_key<'a>(&'a self, key: &'a str) -> Option<Cow<'a, str>> {
+        match owner_of_key(key)? {
+            KeyOwner::Tenant(tenant) => Some(tenant),
+            KeyOwner::Database(db_id) => self.tenant_of_db(db_id).map(Cow::Borrowed),
+            KeyOwner::Table(table_id) => self
+                .tenant_of_db(*self.table_dbs.get(&table_id)?)
+                .map(Cow::Borrowed),
+        }
+    }
+
+    fn tenant_of_db(&self, db_id: u64) -> Option<&str> {
+        self.db_tenants.get(&db_id).map(|x| x.as_str())
+    }
+
+    fn account(&mut self, key: &str, prev: Option<&SeqV>, result: Option<&SeqV>) {
+        if let Some(tenant) = self.tenant_of_key(key).map(|x| x.into_owned()) {
+            Self::update(&mut self.tenants, &tenant, key, prev, result);
+        }
+        if let Some(prefix) = prefix_of_key(key) {
+            Self::update(&mut self.prefixes, prefix, key, prev, result);
+        }
+    }
+
+    /// Learn the owner of a database id or a table id if `key` is a `*_id_to_name` record.
+    ///
+    /// A record that can not be decoded is ignored, keys of the id are then not attributed to any tenant.
+    pub fn learn_owner(&mut self, key: &str, seq_v: &SeqV) {
+        match id_of_key(key, DB_ID_TO_NAME_PREFIX) {
+            Some(db_id) => {
+                if let Ok(ident) = deserialize_struct::<DatabaseNameIdent>(&seq_v.data) {
+                    self.db_tenants.insert(db_id, ident.tenant);
+                }
+            }
+            None => {
+                let table_id = match id_of_key(key, TABLE_ID_TO_NAME_PREFIX) {
+                    Some(x) => x,
+                    None => return,
+                };
+                if let Ok(name) = deserialize_struct::<DBIdTableName>(&seq_v.data) {
+                    self.table_dbs.insert(table_id, name.db_id);
+                }
+            }
+        }
+    }
+
+    fn forget_owner(&mut self, key: &str) {
+        if let Some(db_id) = id_of_key(key, DB_ID_TO_NAME_PREFIX) {
+            self.db_tenants.remove(&db_id);
+        } else if let Some(table_id) = id_of_key(key, TABLE_ID_TO_NAME_PREFIX) {
+            self.table_dbs.remove(&table_id);
+        }
+    }
+
+    fn update(
+        m: &mut BTreeMap<String, KeySpaceUsage>,
+        name: &str,
+        key: &str,
+        prev: Option<&SeqV>,
+        result: Option<&SeqV>,
+    ) {
+        let usage = m.entry(name.to_string()).or_default();
+
+        if let Some(p) = prev {
+            usage.sub(key, p);
+        }
+        if let Some(r) = result {
+            usage.add(key, r);
+        }
+
+        if usage.keys == 0 {
+            m.remove(name);
+        }
+    }
+}
+
+/// Returns the owner of a key by its prefix, or `None` if the key does not belong to a tenant.
+pub fn owner_of_key(key: &str) -> Option<KeyOwner<'_>> {
+    let mut segments = key.split('/');
+
+    let prefix = segments.next()?;
+    let second = segments.next().filter(|x| !x.is_empty())?;
+
+    if TENANT_KEY_PREFIXES.contains(&prefix) {
+        return Some(KeyOwner::Tenant(unescape_segment(second)));
+    }
+    if DB_ID_KEY_PREFIXES.contains(&prefix) {
+        return second.parse().ok().map(KeyOwner::Database);
+    }
+    if TABLE_ID_KEY_PREFIXES.contains(&prefix) {
+        return second.parse().ok().map(KeyOwner::Table);
+    }
+    None
+}
+
+/// Unescapes a key segment escaped by `common_meta_api`, in which a special character is `%` and two hex digits.
+///
+/// A segment that is not well escaped is returned as is, it is never an error to apply a key.
+fn unescape_segment(segment: &str) -> Cow<'_, str> {
+    if !segment.contains('%') {
+        return Cow::Borrowed(segment);
+    }
+
+    let bytes = segment.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let num = segment
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match num {
+                Some(num) => unescaped.push(num),
+                None => return Cow::Borrowed(segment),
+            }
+            i += 3;
+        } else {
+            unescaped.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    match String::from_utf8(unescaped) {
+        Ok(x) => Cow::Owned(x),
+        Err(_) => Cow::Borrowed(segment),
+    }
+}
+
+/// Returns the id in a key `<prefix>/<id>`, or `None` if the key is not of this form.
+fn id_of_key(key: &str, prefix: &str) -> Option<u64> {
+    let id = key.strip_prefix(prefix)?.strip_prefix('/')?;
+    id.parse().ok()
+}
+
+/// Returns the top-level prefix of a key, or `None` if it is not a `__fd_*` key.
+pub fn prefix_of_key(key: &str) -> Option<&str> {
+    let prefix = key.split('/').next()?;
+    if prefix.starts_with("__fd_") {
+        Some(prefix)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This mod defines a key space in state machine to store the keys attached to leases.
+//!
+//! The index is `(lease_id, key) -> ()`, so that the keys of a lease are listed with a range scan,
+//! and attaching a key does not rewrite the lease record.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::mem::size_of;
+
+use byteorder::BigEndian;
+use byteorder::ByteOrder;
+use common_meta_sled_store::sled::IVec;
+use common_meta_sled_store::SledBytesError;
+use common_meta_sled_store::SledOrderedSerde;
+
+/// The identifier of a key attached to a lease.
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct LeaseKey {
+    pub lease_id: u64,
+
+    /// The record key attached to the lease.
+    pub key: String,
+}
+
+impl SledOrderedSerde for LeaseKey {
+    fn ser(&self) -> Result<IVec, SledBytesError> {
+        let mut buf = vec![0; size_of::<u64>()];
+
+        BigEndian::write_u64(&mut buf, self.lease_id);
+        buf.extend_from_slice(self.key.as_bytes());
+        Ok(buf.into())
+    }
+
+    fn de<V: AsRef<[u8]>>(v: V) -> Result<Self, SledBytesError>
+    where Self: Sized {
+        let b = v.as_ref();
+
+        let lease_id = BigEndian::read_u64(b);
+        let key = String::from_utf8(b[size_of::<u64>()..].to_vec())?;
+
+        Ok(Self { lease_id, key })
+    }
+}
+
+impl Display for LeaseKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.lease_id, self.key)
+    }
+}
+
+impl LeaseKey {
+    pub fn new(lease_id: u64, key: impl Into<String>) -> Self {
+        Self {
+            lease_id,
+            key: key.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta_sled_store::SledOrderedSerde;
+
+    use crate::state_machine::LeaseKey;
+
+    #[test]
+    fn test_lease_key_serde() -> anyhow::Result<()> {
+        let k = LeaseKey::new(0x01020304, "ab");
+        let enc = <LeaseKey as SledOrderedSerde>::ser(&k)?;
+        assert_eq!(vec![0u8, 0, 0, 0, 1, 2, 3, 4, b'a', b'b'], enc.as_ref());
+
+        let got_dec = <LeaseKey as SledOrderedSerde>::de(enc)?;
+        assert_eq!(k, got_dec);
+
+        // Keys of a lease are ordered before the keys of the next lease.
+        let k2 = <LeaseKey as SledOrderedSerde>::ser(&LeaseKey::new(0x01020305, ""))?;
+        assert!(enc.as_ref() < k2.as_ref());
+
+        Ok(())
+    }
+}
@@ -15,6 +15,14 @@
 pub use client_last_resp::ClientLastRespValue;
 pub use expire::ExpireKey;
 pub use expire::ExpireValue;
+pub use key_space_usage::owner_of_key;
+pub use key_space_usage::KeyOwner;
+pub use key_space_usage::KeySpaceStats;
+pub use key_space_usage::KeySpaceUsage;
+pub use key_space_usage::DB_ID_KEY_PREFIXES;
+pub use key_space_usage::TABLE_ID_KEY_PREFIXES;
+pub use key_space_usage::TENANT_KEY_PREFIXES;
+pub use lease_key::LeaseKey;
 pub use log_meta::LogMetaKey;
 pub use log_meta::LogMetaValue;
 pub use sm::SerializableSnapshot;
@@ -28,6 +36,8 @@ pub use state_machine_meta::StateMachineMetaValue;
 
 pub mod client_last_resp;
 mod expire;
+mod key_space_usage;
+mod lease_key;
 pub mod log_meta;
 pub mod sm;
 mod sm_kv_api_impl;
@@ -15,6 +15,7 @@
 use std::collections::BTreeMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 use std::time::SystemTime;
@@ -39,6 +40,7 @@ use common_meta_types::Change;
 use common_meta_types::Cmd;
 use common_meta_types::ConditionResult;
 use common_meta_types::KVMeta;
+use common_meta_types::Lease;
 use common_meta_types::LogEntry;
 use common_meta_types::LogId;
 use common_meta_types::MatchSeqExt;
@@ -72,15 +74,22 @@ use tracing::error;
 use tracing::info;
 
 use crate::config::RaftConfig;
+use crate::sled_key_spaces::ChangeHistory;
 use crate::sled_key_spaces::ClientLastResps;
 use crate::sled_key_spaces::Expire;
 use crate::sled_key_spaces::GenericKV;
+use crate::sled_key_spaces::LeaseExpires;
+use crate::sled_key_spaces::LeaseKeys;
+use crate::sled_key_spaces::Leases;
 use crate::sled_key_spaces::Nodes;
 use crate::sled_key_spaces::Sequences;
 use crate::sled_key_spaces::StateMachineMeta;
 use crate::state_machine::ClientLastRespValue;
 use crate::state_machine::ExpireKey;
 use crate::state_machine::ExpireValue;
+use crate::state_machine::KeySpaceStats;
+use crate::state_machine::KeySpaceUsage;
+use crate::state_machine::LeaseKey;
 use crate::state_machine::MetaSnapshotId;
 use crate::state_machine::StateMachineMetaKey;
 use crate::state_machine::StateMachineMetaKey::Initialized;
@@ -93,9 +102,15 @@ use crate::state_machine::StateMachineMetaValue;
 // const TREE_META: &str = "meta";
 const TREE_STATE_MACHINE: &str = "state_machine";
 
+/// Max number of the most recent kv changes kept in the change history.
+///
+/// It is a constant instead of a config item because every node has to trim the history identically.
+pub const CHANGE_HISTORY_SIZE: u64 = 10_000;
+
 /// StateMachine subscriber trait
 pub trait StateMachineSubscriber: Debug + Sync + Send {
-    fn kv_changed(&self, change: Change<Vec<u8>, String>);
+    /// Called when a kv is changed, with the `revision` of the change in the change history.
+    fn kv_changed(&self, revision: u64, change: Change<Vec<u8>, String>);
 }
 
 /// The state machine of the `MemStore`.
@@ -110,6 +125,11 @@ pub struct StateMachine {
 
     /// subscriber of statemachine data
     pub subscriber: Option<Box<dyn StateMachineSubscriber>>,
+
+    /// Number of keys and bytes used by every tenant and every top-level prefix.
+    ///
+    /// It is not persisted but rebuilt from the kv records when the state machine is opened.
+    pub key_space_stats: Mutex<KeySpaceStats>,
 }
 
 /// A key-value pair in a snapshot is a vec of two `Vec<u8>`.
@@ -164,6 +184,7 @@ impl StateMachine {
         let sm = StateMachine {
             sm_tree,
             subscriber: None,
+            key_space_stats: Mutex::new(KeySpaceStats::default()),
         };
 
         let inited = {
@@ -172,6 +193,7 @@ impl StateMachine {
         };
 
         if inited.is_some() {
+            sm.rebuild_key_space_stats()?;
             Ok(sm)
         } else {
             let sm_meta = sm.sm_meta();
@@ -182,6 +204,82 @@ impl StateMachine {
         }
     }
 
+    /// Re-count the keys and bytes used by every tenant and prefix by scanning all kv records.
+    ///
+    /// It has to be called after kv records are written without going through `apply()`,
+    /// e.g., when a snapshot is installed.
+    pub fn rebuild_key_space_stats(&self) -> Result<(), MetaStorageError> {
+        let mut stats = KeySpaceStats::default();
+
+        // Learn the owners of database ids and table ids first:
+        // a key with an id may be listed before the record telling the owner of the id.
+        for item in self.kvs().range(..)? {
+            let (key, seq_v) = item?.kv()?;
+            stats.learn_owner(&key, &seq_v);
+        }
+
+        for item in self.kvs().range(..)? {
+            let (key, seq_v) = item?.kv()?;
+            stats.add(&key, &seq_v);
+        }
+
+        info!(
+            "rebuilt key space stats: {} tenants, {} prefixes",
+            stats.tenants.len(),
+            stats.prefixes.len()
+        );
+
+        *self.key_space_stats.lock().unwrap() = stats;
+        Ok(())
+    }
+
+    /// Returns a copy of the current usage of every tenant and prefix.
+    ///
+    /// The owners of database ids and table ids are not copied.
+    pub fn get_key_space_stats(&self) -> KeySpaceStats {
+        self.key_space_stats.lock().unwrap().usage()
+    }
+
+    /// Returns the usage of every tenant whose usage would grow by the `ops`.
+    ///
+    /// An op is a key with the size of the value to put, or `None` to delete it.
+    /// The usage grows if the ops add keys, or add bytes without removing keys.
+    /// Thus ops removing keys, e.g. dropping a table, are not reported even if the values they put grow.
+    pub fn get_tenant_usage_grown_by<'a>(
+        &self,
+        ops: impl IntoIterator<Item = (&'a str, Option<usize>)>,
+    ) -> Result<BTreeMap<String, KeySpaceUsage>, MetaStorageError> {
+        let stats = self.key_space_stats.lock().unwrap();
+
+        // tenant -> (delta of keys, delta of bytes)
+        let mut deltas: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for (key, value_len) in ops {
+            let tenant = match stats.tenant_of_key(key) {
+                Some(x) => x,
+                None => continue,
+            };
+            let delta = deltas.entry(tenant.into_owned()).or_default();
+
+            if let Some(prev) = self.kvs().get(&key.to_string())? {
+                delta.0 -= 1;
+                delta.1 -= (key.len() + prev.data.len()) as i64;
+            }
+            if let Some(len) = value_len {
+                delta.0 += 1;
+                delta.1 += (key.len() + len) as i64;
+            }
+        }
+
+        Ok(deltas
+            .into_iter()
+            .filter(|(_, (keys, bytes))| *keys > 0 || (*keys == 0 && *bytes > 0))
+            .map(|(tenant, _)| {
+                let usage = stats.tenant(&tenant);
+                (tenant, usage)
+            })
+            .collect())
+    }
+
     pub fn set_subscriber(&mut self, subscriber: Box<dyn StateMachineSubscriber>) {
         self.subscriber = Some(subscriber);
     }
@@ -252,6 +350,22 @@ impl StateMachine {
         }
     }
 
+    /// List the keys attached to the lease a `RevokeLease` log removes.
+    ///
+    /// A sled transaction can not scan, the keys are listed before the transaction.
+    fn list_lease_keys_if_needed(
+        &self,
+        entry: &Entry<LogEntry>,
+    ) -> Result<Vec<String>, MetaStorageError> {
+        match entry.payload {
+            EntryPayload::Normal(LogEntry {
+                cmd: Cmd::RevokeLease { id },
+                ..
+            }) => self.list_lease_keys(id),
+            _ => Ok(vec![]),
+        }
+    }
+
     /// Apply an log entry to state machine.
     ///
     /// If a duplicated log entry is detected by checking data.txid, no update
@@ -268,10 +382,18 @@ impl StateMachine {
         let expired = self.list_expired_kvs(log_time_ms)?;
         debug!("expired keys: {:?}", expired);
 
+        let mut expired_leases = vec![];
+        for id in self.list_expired_leases(log_time_ms)? {
+            expired_leases.push((id, self.list_lease_keys(id)?));
+        }
+        debug!("expired leases: {:?}", expired_leases);
+
         let kv_pairs = self.scan_prefix_if_needed(entry)?;
+        let lease_keys = self.list_lease_keys_if_needed(entry)?;
 
         let result = self.sm_tree.txn(true, move |mut txn_tree| {
             self.clean_expired_kvs(&mut txn_tree, &expired)?;
+            self.clean_expired_leases(&mut txn_tree, &expired_leases)?;
 
             let txn_sm_meta = txn_tree.key_space::<StateMachineMeta>();
             txn_sm_meta.insert(&LastApplied, &StateMachineMetaValue::LogId(*log_id))?;
@@ -286,12 +408,17 @@ impl StateMachine {
                         let (serial, resp) =
                             self.txn_get_client_last_resp(&txid.client, &txn_tree)?;
                         if serial == txid.serial {
-                            return Ok((Some(resp), txn_tree.changes));
+                            return Ok((Some(resp), Self::txn_record_changes(&mut txn_tree)?));
                         }
                     }
 
-                    let res =
-                        self.apply_cmd(&data.cmd, &mut txn_tree, kv_pairs.as_ref(), log_time_ms);
+                    let res = self.apply_cmd(
+                        &data.cmd,
+                        &mut txn_tree,
+                        kv_pairs.as_ref(),
+                        &lease_keys,
+                        log_time_ms,
+                    );
                     if let Ok(ok) = &res {
                         info!("apply_result: summary: {}; res ok: {}", entry.summary(), ok);
                     }
@@ -312,7 +439,10 @@ impl StateMachine {
                             &txn_tree,
                         )?;
                     }
-                    return Ok((Some(applied_state), txn_tree.changes));
+                    return Ok((
+                        Some(applied_state),
+                        Self::txn_record_changes(&mut txn_tree)?,
+                    ));
                 }
                 EntryPayload::Membership(ref mem) => {
                     info!("apply: membership: {:?}", mem);
@@ -323,11 +453,14 @@ impl StateMachine {
                             membership: mem.clone(),
                         }),
                     )?;
-                    return Ok((Some(AppliedState::None), txn_tree.changes));
+                    return Ok((
+                        Some(AppliedState::None),
+                        Self::txn_record_changes(&mut txn_tree)?,
+                    ));
                 }
             };
 
-            Ok((None, txn_tree.changes))
+            Ok((None, Self::txn_record_changes(&mut txn_tree)?))
         });
 
         let (opt_applied_state, changes) = result?;
@@ -339,10 +472,21 @@ impl StateMachine {
             None => AppliedState::None,
         };
 
+        {
+            let kv_changes = changes.iter().filter_map(|(_revision, change)| {
+                let key = change.ident.as_ref()?;
+                Some((key.as_str(), change.prev.as_ref(), change.result.as_ref()))
+            });
+            self.key_space_stats
+                .lock()
+                .unwrap()
+                .apply_changes(kv_changes);
+        }
+
         // Send queued change events to subscriber
         if let Some(subscriber) = &self.subscriber {
-            for event in changes {
-                subscriber.kv_changed(event);
+            for (revision, event) in changes {
+                subscriber.kv_changed(revision, event);
             }
         }
 
@@ -430,6 +574,11 @@ impl StateMachine {
     ) -> Result<AppliedState, MetaStorageError> {
         debug!(upsert_kv = debug(upsert_kv), "apply_update_kv_cmd");
 
+        // Attaching a record to a dead lease is rejected with `Lease(None)`.
+        if !Self::txn_is_lease_alive(txn_tree, upsert_kv, log_time_ms)? {
+            return Ok(AppliedState::Lease(None));
+        }
+
         let (expired, prev, result) = Self::txn_upsert_kv(txn_tree, upsert_kv, log_time_ms)?;
 
         debug!("applied UpsertKV: {:?} {:?}", upsert_kv, result);
@@ -442,6 +591,64 @@ impl StateMachine {
         Ok(Change::new(prev, result).into())
     }
 
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_grant_lease_cmd(
+        &self,
+        ttl_ms: u64,
+        txn_tree: &TransactionSledTree,
+        log_time_ms: u64,
+    ) -> Result<AppliedState, MetaStorageError> {
+        let id = Self::txn_incr_seq(Leases::NAME, txn_tree)?;
+        let lease = Lease::new(id, ttl_ms, log_time_ms);
+
+        txn_tree.key_space::<Leases>().insert(&id, &lease)?;
+        txn_tree
+            .key_space::<LeaseExpires>()
+            .insert(&ExpireKey::new(lease.expire_at_ms, id), &id)?;
+        info!("applied GrantLease: {}", lease);
+
+        Ok(AppliedState::Lease(Some(lease)))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_keep_alive_lease_cmd(
+        &self,
+        id: u64,
+        txn_tree: &TransactionSledTree,
+        log_time_ms: u64,
+    ) -> Result<AppliedState, MetaStorageError> {
+        let leases = txn_tree.key_space::<Leases>();
+        let lease_expires = txn_tree.key_space::<LeaseExpires>();
+
+        let lease = match leases.get(&id)? {
+            Some(mut lease) if !lease.is_expired(log_time_ms) => {
+                lease_expires.remove(&ExpireKey::new(lease.expire_at_ms, id))?;
+                lease.renew(log_time_ms);
+                leases.insert(&id, &lease)?;
+                lease_expires.insert(&ExpireKey::new(lease.expire_at_ms, id), &id)?;
+                Some(lease)
+            }
+            _ => None,
+        };
+
+        Ok(AppliedState::Lease(lease))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_revoke_lease_cmd(
+        &self,
+        id: u64,
+        keys: &[String],
+        txn_tree: &mut TransactionSledTree,
+    ) -> Result<AppliedState, MetaStorageError> {
+        let prev = Self::txn_revoke_lease(txn_tree, id, keys)?;
+        if let Some(lease) = &prev {
+            info!("applied RevokeLease: {}", lease);
+        }
+
+        Ok(AppliedState::Lease(prev))
+    }
+
     fn return_value_condition_result(
         &self,
         expected: i32,
@@ -567,6 +774,7 @@ impl StateMachine {
             txn_tree,
             &UpsertKV::update(&put.key, &put.value).with(KVMeta {
                 expire_at: put.expire_at,
+                lease: put.lease,
             }),
             log_time_ms,
         )?;
@@ -728,6 +936,18 @@ impl StateMachine {
             false
         };
 
+        // Attaching a record to a dead lease is rejected with `Lease(None)`, as the upsert does,
+        // before any operation of the txn is applied.
+        for op in ops {
+            if let Some(txn_op::Request::Put(put)) = &op.request {
+                if let Some(lease_id) = put.lease {
+                    if !Self::txn_is_lease_id_alive(txn_tree, lease_id, log_time_ms)? {
+                        return Ok(AppliedState::Lease(None));
+                    }
+                }
+            }
+        }
+
         let mut resp: TxnReply = TxnReply {
             success,
             error: "".to_string(),
@@ -752,6 +972,7 @@ impl StateMachine {
         cmd: &Cmd,
         txn_tree: &mut TransactionSledTree,
         kv_pairs: Option<&(DeleteByPrefixKeyMap, DeleteByPrefixKeyMap)>,
+        lease_keys: &[String],
         log_time_ms: u64,
     ) -> Result<AppliedState, MetaStorageError> {
         info!("apply_cmd: {}", cmd);
@@ -773,6 +994,16 @@ impl StateMachine {
             }
 
             Cmd::Transaction(txn) => self.apply_txn_cmd(txn, txn_tree, kv_pairs, log_time_ms),
+
+            Cmd::GrantLease { ttl_ms } => {
+                self.apply_grant_lease_cmd(*ttl_ms, txn_tree, log_time_ms)
+            }
+
+            Cmd::KeepAliveLease { id } => {
+                self.apply_keep_alive_lease_cmd(*id, txn_tree, log_time_ms)
+            }
+
+            Cmd::RevokeLease { id } => self.apply_revoke_lease_cmd(*id, lease_keys, txn_tree),
         };
 
         let elapsed = now.elapsed().as_micros();
@@ -831,6 +1062,7 @@ impl StateMachine {
 
                     txn_tree.key_space::<GenericKV>().remove(key)?;
                     txn_tree.key_space::<Expire>().remove(expire_key)?;
+                    Self::txn_update_lease_keys(txn_tree, key, sv.as_ref(), None)?;
 
                     txn_tree.push_change(key, sv, None);
                     continue;
@@ -845,6 +1077,159 @@ impl StateMachine {
         Ok(())
     }
 
+    /// Before applying, list leases expired at the time of the log.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn list_expired_leases(&self, log_time_ms: u64) -> Result<Vec<u64>, MetaStorageError> {
+        if log_time_ms == 0 {
+            return Ok(vec![]);
+        }
+
+        let at_most = 32;
+        let mut to_clean = Vec::with_capacity(at_most);
+
+        let lease_expires = self.sm_tree.key_space::<LeaseExpires>();
+
+        // Leases are indexed in expire time order, stop at the first living one.
+        let it = lease_expires.range(..)?.take(at_most);
+        for item_res in it {
+            let item = item_res?;
+            let k: ExpireKey = item.key()?;
+            if log_time_ms <= k.time_ms {
+                break;
+            }
+            to_clean.push(item.value()?);
+        }
+
+        Ok(to_clean)
+    }
+
+    /// List the keys attached to a lease.
+    pub fn list_lease_keys(&self, id: u64) -> Result<Vec<String>, MetaStorageError> {
+        let lease_keys = self.sm_tree.key_space::<LeaseKeys>();
+
+        let mut keys = vec![];
+        for item_res in lease_keys.range(LeaseKey::new(id, "")..LeaseKey::new(id + 1, ""))? {
+            let k: LeaseKey = item_res?.key()?;
+            keys.push(k.key);
+        }
+
+        Ok(keys)
+    }
+
+    /// Remove expired leases and the key-values attached to them.
+    ///
+    /// This should be done inside a sled-transaction.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn clean_expired_leases(
+        &self,
+        txn_tree: &mut TransactionSledTree,
+        expired: &[(u64, Vec<String>)],
+    ) -> Result<(), MetaStorageError> {
+        for (id, keys) in expired.iter() {
+            if let Some(lease) = Self::txn_revoke_lease(txn_tree, *id, keys)? {
+                info!("clean expired: {}", lease);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a lease and delete every key-value attached to it,
+    /// `keys` are the attached keys listed before the transaction.
+    ///
+    /// A delete event is emitted for every deleted key-value.
+    fn txn_revoke_lease(
+        txn_tree: &mut TransactionSledTree,
+        id: u64,
+        keys: &[String],
+    ) -> Result<Option<Lease>, MetaStorageError> {
+        let lease = txn_tree.key_space::<Leases>().get(&id)?;
+
+        if let Some(lease) = &lease {
+            txn_tree.key_space::<Leases>().remove(&id)?;
+            txn_tree
+                .key_space::<LeaseExpires>()
+                .remove(&ExpireKey::new(lease.expire_at_ms, id))?;
+
+            for key in keys.iter() {
+                txn_tree
+                    .key_space::<LeaseKeys>()
+                    .remove(&LeaseKey::new(id, key.as_str()))?;
+
+                let sv = txn_tree.key_space::<GenericKV>().get(key)?;
+
+                if let Some(seq_v) = &sv {
+                    if seq_v.get_lease() != Some(id) {
+                        continue;
+                    }
+
+                    txn_tree.key_space::<GenericKV>().remove(key)?;
+                    if let Some(exp) = seq_v.meta.as_ref().and_then(|m| m.expire_at) {
+                        txn_tree
+                            .key_space::<Expire>()
+                            .remove(&ExpireKey::new(exp * 1000, seq_v.seq))?;
+                    }
+
+                    txn_tree.push_change(key, sv, None);
+                }
+            }
+        }
+
+        Ok(lease)
+    }
+
+    /// Keep the keys of a lease consistent with the key-values attached to it.
+    ///
+    /// The key is detached from the lease of the previous record and attached to the lease of the new record.
+    fn txn_update_lease_keys(
+        txn_tree: &TransactionSledTree,
+        key: &str,
+        prev: Option<&SeqV>,
+        result: Option<&SeqV>,
+    ) -> Result<(), MetaStorageError> {
+        let prev_lease = prev.and_then(|sv| sv.get_lease());
+        let result_lease = result.and_then(|sv| sv.get_lease());
+
+        if prev_lease == result_lease {
+            return Ok(());
+        }
+
+        let lease_keys = txn_tree.key_space::<LeaseKeys>();
+
+        if let Some(id) = prev_lease {
+            lease_keys.remove(&LeaseKey::new(id, key))?;
+        }
+
+        if let Some(id) = result_lease {
+            lease_keys.insert(&LeaseKey::new(id, key), &())?;
+        }
+
+        Ok(())
+    }
+
+    /// Assign a revision to every change collected in a transaction and save them in the change history.
+    ///
+    /// Only the last [`CHANGE_HISTORY_SIZE`] changes are kept.
+    fn txn_record_changes(
+        txn_tree: &mut TransactionSledTree,
+    ) -> Result<Vec<(u64, Change<Vec<u8>, String>)>, MetaStorageError> {
+        let changes = std::mem::take(&mut txn_tree.changes);
+        let mut recorded = Vec::with_capacity(changes.len());
+
+        for change in changes {
+            let revision = Self::txn_incr_seq(ChangeHistory::NAME, txn_tree)?;
+
+            let history = txn_tree.key_space::<ChangeHistory>();
+            history.insert(&revision, &change)?;
+            if revision > CHANGE_HISTORY_SIZE {
+                history.remove(&(revision - CHANGE_HISTORY_SIZE))?;
+            }
+
+            recorded.push((revision, change));
+        }
+
+        Ok(recorded)
+    }
+
     fn txn_incr_seq(key: &str, txn_tree: &TransactionSledTree) -> Result<u64, MetaStorageError> {
         let seqs = txn_tree.key_space::<Sequences>();
 
@@ -862,11 +1247,12 @@ impl StateMachine {
     /// Execute an upsert-kv operation on a transactional sled tree.
     ///
     /// KV has two indexes:
-    /// - The primary index: `key -> (seq, meta(expire_time), value)`,
+    /// - The primary index: `key -> (seq, meta(expire_time, lease), value)`,
     /// - and a secondary expiration index: `(expire_time, seq) -> key`.
     ///
     /// Thus upsert a kv record is done in two steps:
     /// update the primary index and optionally update the secondary index.
+    /// The keys of the lease a record is attached to are updated too.
     ///
     /// It returns 3 SeqV:
     /// - `(None, None, x)`: upsert nonexistent key;
@@ -889,6 +1275,7 @@ impl StateMachine {
                     expires.remove(&ExpireKey::new(exp * 1000, sv.seq))?;
                 }
             }
+            Self::txn_update_lease_keys(txn_tree, &upsert_kv.key, Some(sv), None)?;
         }
 
         // No change, no need to update expiration index
@@ -896,6 +1283,8 @@ impl StateMachine {
             return Ok((expired, prev, res));
         }
 
+        Self::txn_update_lease_keys(txn_tree, &upsert_kv.key, prev.as_ref(), res.as_ref())?;
+
         // Remove previous expiration index, add a new one.
 
         if let Some(sv) = &prev {
@@ -943,6 +1332,11 @@ impl StateMachine {
             return Ok((expired, prev.clone(), prev));
         }
 
+        // A record can only be attached to a living lease.
+        if !Self::txn_is_lease_alive(txn_tree, upsert_kv, log_time_ms)? {
+            return Ok((expired, prev.clone(), prev));
+        }
+
         let mut new_seq_v = match &upsert_kv.value {
             Operation::Update(v) => SeqV::with_meta(0, upsert_kv.value_meta.clone(), v.clone()),
             Operation::Delete => {
@@ -964,6 +1358,33 @@ impl StateMachine {
         Ok((expired, prev, Some(new_seq_v)))
     }
 
+    /// Returns false if the upsert attaches a record to a lease that does not exist or is expired.
+    ///
+    /// A delete does not attach anything and is always allowed.
+    fn txn_is_lease_alive(
+        txn_tree: &TransactionSledTree,
+        upsert_kv: &UpsertKV,
+        log_time_ms: u64,
+    ) -> Result<bool, MetaStorageError> {
+        if upsert_kv.value == Operation::Delete {
+            return Ok(true);
+        }
+
+        match upsert_kv.value_meta.as_ref().and_then(|m| m.lease) {
+            Some(lease_id) => Self::txn_is_lease_id_alive(txn_tree, lease_id, log_time_ms),
+            None => Ok(true),
+        }
+    }
+
+    fn txn_is_lease_id_alive(
+        txn_tree: &TransactionSledTree,
+        lease_id: u64,
+        log_time_ms: u64,
+    ) -> Result<bool, MetaStorageError> {
+        let lease = txn_tree.key_space::<Leases>().get(&lease_id)?;
+        Ok(lease.map(|l| !l.is_expired(log_time_ms)).unwrap_or(false))
+    }
+
     fn txn_client_last_resp_update(
         &self,
         key: &str,
@@ -1032,6 +1453,32 @@ impl StateMachine {
         Ok((0, AppliedState::None))
     }
 
+    pub fn get_lease(&self, id: u64) -> Result<Option<Lease>, MetaStorageError> {
+        let leases = self.leases();
+        leases.get(&id)
+    }
+
+    /// Returns the revision of the oldest change in the change history, or None if it is empty.
+    pub fn first_change_revision(&self) -> Result<Option<u64>, MetaStorageError> {
+        let mut it = self.change_history().range(..)?;
+        match it.next() {
+            None => Ok(None),
+            Some(item) => Ok(Some(item?.key()?)),
+        }
+    }
+
+    /// List the changes in the change history with revision no less than `start_revision`.
+    pub fn list_changes(
+        &self,
+        start_revision: u64,
+    ) -> Result<Vec<(u64, Change<Vec<u8>, String>)>, MetaStorageError> {
+        let mut changes = vec![];
+        for item in self.change_history().range(start_revision..)? {
+            changes.push(item?.kv()?);
+        }
+        Ok(changes)
+    }
+
     pub fn get_node(&self, node_id: &NodeId) -> Result<Option<Node>, MetaStorageError> {
         let sm_nodes = self.nodes();
         sm_nodes.get(node_id)
@@ -1084,6 +1531,16 @@ impl StateMachine {
         self.sm_tree.key_space()
     }
 
+    /// storage of leases and the keys attached to them.
+    pub fn leases(&self) -> AsKeySpace<Leases> {
+        self.sm_tree.key_space()
+    }
+
+    /// storage of the most recent kv changes for watchers to resume from.
+    pub fn change_history(&self) -> AsKeySpace<ChangeHistory> {
+        self.sm_tree.key_space()
+    }
+
     /// storage of client last resp to keep idempotent.
     pub fn client_last_resps(&self) -> AsKeySpace<ClientLastResps> {
         self.sm_tree.key_space()
@@ -1106,9 +1563,18 @@ mod tests {
         assert_eq!((None, Some(sv())), expire_seq_v(Some(sv()), 10_000));
 
         assert_eq!(
-            (None, Some(sv().set_meta(Some(KVMeta { expire_at: None })))),
+            (
+                None,
+                Some(sv().set_meta(Some(KVMeta {
+                    expire_at: None,
+                    lease: None
+                })))
+            ),
             expire_seq_v(
-                Some(sv().set_meta(Some(KVMeta { expire_at: None }))),
+                Some(sv().set_meta(Some(KVMeta {
+                    expire_at: None,
+                    lease: None
+                }))),
                 10_000
             )
         );
@@ -1116,23 +1582,31 @@ mod tests {
             (
                 None,
                 Some(sv().set_meta(Some(KVMeta {
-                    expire_at: Some(20)
+                    expire_at: Some(20),
+                    lease: None,
                 })))
             ),
             expire_seq_v(
                 Some(sv().set_meta(Some(KVMeta {
-                    expire_at: Some(20)
+                    expire_at: Some(20),
+                    lease: None,
                 }))),
                 10_000
             )
         );
         assert_eq!(
             (
-                Some(sv().set_meta(Some(KVMeta { expire_at: Some(5) }))),
+                Some(sv().set_meta(Some(KVMeta {
+                    expire_at: Some(5),
+                    lease: None
+                }))),
                 None
             ),
             expire_seq_v(
-                Some(sv().set_meta(Some(KVMeta { expire_at: Some(5) }))),
+                Some(sv().set_meta(Some(KVMeta {
+                    expire_at: Some(5),
+                    lease: None
+                }))),
                 10_000
             )
         );
@@ -12,15 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Bound;
+
 use common_meta_api::KVApi;
+use common_meta_stoerr::MetaStorageError;
+use common_meta_types::txn_op;
+use common_meta_types::AppError;
 use common_meta_types::AppliedState;
 use common_meta_types::Cmd;
 use common_meta_types::GetKVReply;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::MGetKVReply;
 use common_meta_types::SeqV;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
+use common_meta_types::UnknownLease;
 use common_meta_types::UpsertKV;
 use common_meta_types::UpsertKVReply;
 use common_meta_types::UpsertKVReq;
@@ -31,6 +39,7 @@ use crate::state_machine::StateMachine;
 #[async_trait::async_trait]
 impl KVApi for StateMachine {
     async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, KVAppError> {
+        let lease_id = act.value_meta.as_ref().and_then(|m| m.lease);
         let cmd = Cmd::UpsertKV(UpsertKV {
             key: act.key,
             seq: act.seq,
@@ -40,13 +49,16 @@ impl KVApi for StateMachine {
 
         let res = self.sm_tree.txn(true, |mut txn_sled_tree| {
             let r = self
-                .apply_cmd(&cmd, &mut txn_sled_tree, None, SeqV::<()>::now_ms())
+                .apply_cmd(&cmd, &mut txn_sled_tree, None, &[], SeqV::<()>::now_ms())
                 .unwrap();
             Ok(r)
         })?;
 
         match res {
             AppliedState::KV(x) => Ok(x),
+            AppliedState::Lease(None) => Err(KVAppError::AppError(AppError::UnknownLease(
+                UnknownLease::new(lease_id.unwrap_or_default(), "upsert_kv"),
+            ))),
             _ => {
                 panic!("expect AppliedState::KV");
             }
@@ -54,17 +66,28 @@ impl KVApi for StateMachine {
     }
 
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
+        let lease_id = txn
+            .if_then
+            .iter()
+            .chain(txn.else_then.iter())
+            .find_map(|op| match &op.request {
+                Some(txn_op::Request::Put(put)) => put.lease,
+                _ => None,
+            });
         let cmd = Cmd::Transaction(txn);
 
         let res = self.sm_tree.txn(true, |mut txn_sled_tree| {
             let r = self
-                .apply_cmd(&cmd, &mut txn_sled_tree, None, SeqV::<()>::now_ms())
+                .apply_cmd(&cmd, &mut txn_sled_tree, None, &[], SeqV::<()>::now_ms())
                 .unwrap();
             Ok(r)
         })?;
 
         match res {
             AppliedState::TxnReply(x) => Ok(x),
+            AppliedState::Lease(None) => Err(KVAppError::AppError(AppError::UnknownLease(
+                UnknownLease::new(lease_id.unwrap_or_default(), "transaction"),
+            ))),
             _ => {
                 panic!("expect AppliedState::TxnReply");
             }
@@ -116,4 +139,92 @@ impl KVApi for StateMachine {
 
         Ok(x.collect())
     }
+
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        let reply = self.list_kv_range(&req)?;
+        Ok(reply)
+    }
+}
+
+impl StateMachine {
+    /// List kvs in a range, skipping expired ones.
+    fn list_kv_range(&self, req: &ListKVRangeReq) -> Result<ListKVRangeReply, MetaStorageError> {
+        let (left, right) = Self::list_kv_bounds(req);
+
+        // An empty range
+        match (&left, &right) {
+            (Bound::Included(l), Bound::Included(r)) if l > r => {
+                return Ok(ListKVRangeReply::default());
+            }
+            (Bound::Included(l), Bound::Excluded(r))
+            | (Bound::Excluded(l), Bound::Included(r))
+            | (Bound::Excluded(l), Bound::Excluded(r))
+                if l >= r =>
+            {
+                return Ok(ListKVRangeReply::default());
+            }
+            _ => {}
+        }
+
+        let kvs = self.kvs();
+        let it = kvs.range((left, right))?;
+        let it: Box<dyn Iterator<Item = _> + '_> = if req.reverse {
+            Box::new(it.rev())
+        } else {
+            Box::new(it)
+        };
+
+        let limit = req.limit.unwrap_or(u64::MAX) as usize;
+        let local_now_ms = SeqV::<()>::now_ms();
+
+        let mut reply = ListKVRangeReply::default();
+
+        for item in it {
+            let (k, v) = item?.kv()?;
+
+            let (_expired, v) = Self::expire_seq_v(Some(v), local_now_ms);
+            let mut v = match v {
+                None => continue,
+                Some(v) => v,
+            };
+
+            if reply.kvs.len() >= limit {
+                reply.more = true;
+                break;
+            }
+
+            if req.keys_only {
+                v.data = vec![];
+            }
+            reply.kvs.push((k, v));
+        }
+
+        Ok(reply)
+    }
+
+    /// Build the key range to scan for a `list_kv` request,
+    /// excluding keys before `after_key` in the listing order.
+    fn list_kv_bounds(req: &ListKVRangeReq) -> (Bound<String>, Bound<String>) {
+        let mut left = Bound::Included(req.start.clone());
+        let mut right = match &req.end {
+            None => Bound::Unbounded,
+            Some(end) => Bound::Excluded(end.clone()),
+        };
+
+        if let Some(after) = &req.after_key {
+            if req.reverse {
+                let before_end = match &req.end {
+                    None => true,
+                    Some(end) => after < end,
+                };
+                if before_end {
+                    right = Bound::Excluded(after.clone());
+                }
+            } else if after >= &req.start {
+                left = Bound::Excluded(after.clone());
+            }
+        }
+
+        (left, right)
+    }
 }
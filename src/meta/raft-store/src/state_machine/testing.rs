@@ -77,7 +77,9 @@ pub fn snapshot_logs() -> (Vec<Entry<LogEntry>>, Vec<String>) {
         "[3, 3]:{\"Membership\":{\"log_id\":{\"term\":1,\"index\":5},\"membership\":{\"configs\":[[4,5,6]],\"all_nodes\":[4,5,6]}}}", // membership
         "[6, 97]:{\"seq\":1,\"meta\":null,\"data\":[65]}", // generic kv
         "[7, 99]:1",                                       // sequence: c
+        "[7, 99, 104, 97, 110, 103, 101, 45, 104, 105, 115, 116, 111, 114, 121]:1", // sequence: by change history
         "[7, 103, 101, 110, 101, 114, 105, 99, 45, 107, 118]:1", // sequence: by upsertkv
+        "[12, 0, 0, 0, 0, 0, 0, 0, 1]:{\"ident\":\"a\",\"prev\":null,\"result\":{\"seq\":1,\"meta\":null,\"data\":[65]}}", // change history
     ]
     .iter()
     .map(|x| x.to_string())
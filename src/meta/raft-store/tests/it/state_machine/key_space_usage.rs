@@ -0,0 +1,263 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_meta_api::serialize_struct;
+use common_meta_app::schema::DBIdTableName;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_raft_store::state_machine::KeySpaceUsage;
+use common_meta_raft_store::state_machine::StateMachine;
+use common_meta_sled_store::openraft::Entry;
+use common_meta_sled_store::openraft::EntryPayload;
+use common_meta_sled_store::openraft::LogId;
+use common_meta_types::txn_op;
+use common_meta_types::Cmd;
+use common_meta_types::LogEntry;
+use common_meta_types::TxnDeleteRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnPutRequest;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKV;
+
+use crate::init_raft_store_ut;
+use crate::testing::new_raft_test_context;
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_key_space_stats() -> anyhow::Result<()> {
+    // - Stats are updated on insert, override and delete.
+    // - Stats are rebuilt when re-opening a state machine.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+
+    let usage = |keys: u64, bytes: u64| KeySpaceUsage { keys, bytes };
+
+    sm.apply(&ent(1, UpsertKV::update("__fd_users/t1/u1", b"ab")))
+        .await?;
+    sm.apply(&ent(2, UpsertKV::update("__fd_users/t1/u2", b"abc")))
+        .await?;
+    sm.apply(&ent(3, UpsertKV::update("__fd_stages/t2/s1", b"a")))
+        .await?;
+    sm.apply(&ent(4, UpsertKV::update("__fd_table_by_id/1", b"a")))
+        .await?;
+    sm.apply(&ent(5, UpsertKV::update("foo", b"a"))).await?;
+
+    let stats = sm.get_key_space_stats();
+    assert_eq!(usage(2, 16 + 2 + 16 + 3), stats.tenant("t1"));
+    assert_eq!(usage(1, 17 + 1), stats.tenant("t2"));
+    assert_eq!(usage(0, 0), stats.tenant("t3"));
+    assert_eq!(2, stats.tenants.len());
+
+    assert_eq!(usage(2, 16 + 2 + 16 + 3), stats.prefixes["__fd_users"]);
+    assert_eq!(usage(1, 17 + 1), stats.prefixes["__fd_stages"]);
+    assert_eq!(usage(1, 18 + 1), stats.prefixes["__fd_table_by_id"]);
+    assert_eq!(3, stats.prefixes.len());
+
+    // override and delete
+
+    sm.apply(&ent(6, UpsertKV::update("__fd_users/t1/u1", b"abcd")))
+        .await?;
+    sm.apply(&ent(7, UpsertKV::delete("__fd_stages/t2/s1")))
+        .await?;
+
+    let stats = sm.get_key_space_stats();
+    assert_eq!(usage(2, 16 + 4 + 16 + 3), stats.tenant("t1"));
+    assert_eq!(usage(0, 0), stats.tenant("t2"));
+    assert_eq!(1, stats.tenants.len());
+    assert!(!stats.prefixes.contains_key("__fd_stages"));
+
+    // rebuild from disk
+
+    let want = sm.get_key_space_stats();
+    drop(sm);
+
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+    assert_eq!(want, sm.get_key_space_stats());
+
+    Ok(())
+}
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_key_space_stats_by_id() -> anyhow::Result<()> {
+    // - Keys with a database id or a table id are attributed to the tenant owning the database,
+    //   even if they are applied before the owner record in a transaction.
+    // - Keys of an unknown id are not attributed to any tenant.
+    // - The owner is forgotten when its `*_id_to_name` record is removed.
+    // - Owners are learned again when re-opening a state machine.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+
+    let db_name = serialize_struct(&DatabaseNameIdent {
+        tenant: "t1".to_string(),
+        db_name: "db1".to_string(),
+    })?;
+    let table_name = serialize_struct(&DBIdTableName {
+        db_id: 1,
+        table_name: "tb1".to_string(),
+    })?;
+
+    let create = TxnRequest {
+        condition: vec![],
+        if_then: vec![
+            put("__fd_database_by_id/1", b"a"),
+            put("__fd_table/1/tb1", b"2"),
+            put("__fd_table_by_id/2", b"a"),
+            put("__fd_table_id_to_name/2", &table_name),
+            put("__fd_database_id_to_name/1", &db_name),
+        ],
+        else_then: vec![],
+    };
+    sm.apply(&cmd_ent(1, Cmd::Transaction(create))).await?;
+    sm.apply(&ent(2, UpsertKV::update("__fd_table_by_id/3", b"a")))
+        .await?;
+
+    let stats = sm.get_key_space_stats();
+    let want_bytes =
+        (21 + 1) + (16 + 1) + (18 + 1) + (23 + table_name.len()) + (26 + db_name.len());
+    assert_eq!(
+        KeySpaceUsage {
+            keys: 5,
+            bytes: want_bytes as u64
+        },
+        stats.tenant("t1")
+    );
+    assert_eq!(1, stats.tenants.len());
+    assert_eq!(2, stats.prefixes["__fd_table_by_id"].keys);
+
+    // rebuild from disk
+
+    let want = sm.get_key_space_stats();
+    drop(sm);
+
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+    assert_eq!(want, sm.get_key_space_stats());
+
+    // remove the table
+
+    let remove = TxnRequest {
+        condition: vec![],
+        if_then: vec![
+            del("__fd_table_id_to_name/2"),
+            del("__fd_table_by_id/2"),
+            del("__fd_table/1/tb1"),
+        ],
+        else_then: vec![],
+    };
+    sm.apply(&cmd_ent(3, Cmd::Transaction(remove))).await?;
+
+    let stats = sm.get_key_space_stats();
+    assert_eq!(2, stats.tenant("t1").keys);
+
+    // table 2 no longer belongs to t1
+    sm.apply(&ent(4, UpsertKV::update("__fd_table_by_id/2", b"a")))
+        .await?;
+    assert_eq!(2, sm.get_key_space_stats().tenant("t1").keys);
+
+    Ok(())
+}
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_key_space_stats_escaped_tenant() -> anyhow::Result<()> {
+    // - The escaped tenant in a key is attributed to the same tenant as the ids of its databases.
+    // - Only the tenants whose usage grows are reported.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+
+    let db_name = serialize_struct(&DatabaseNameIdent {
+        tenant: "t-1".to_string(),
+        db_name: "db1".to_string(),
+    })?;
+
+    sm.apply(&ent(1, UpsertKV::update("__fd_users/t%2d1/u1", b"ab")))
+        .await?;
+    sm.apply(&ent(
+        2,
+        UpsertKV::update("__fd_database_id_to_name/1", &db_name),
+    ))
+    .await?;
+    sm.apply(&ent(3, UpsertKV::update("__fd_database_by_id/1", b"a")))
+        .await?;
+
+    let stats = sm.get_key_space_stats();
+    assert_eq!(3, stats.tenant("t-1").keys);
+    assert_eq!(1, stats.tenants.len());
+
+    let grown = sm.get_tenant_usage_grown_by([("__fd_users/t%2d1/u2", Some(1))])?;
+    assert_eq!(Some(&stats.tenant("t-1")), grown.get("t-1"));
+
+    let grown = sm.get_tenant_usage_grown_by([("__fd_database_by_id/1", Some(2))])?;
+    assert_eq!(1, grown.len());
+
+    // removing a key while growing another value does not grow the usage
+    let grown = sm.get_tenant_usage_grown_by([
+        ("__fd_users/t%2d1/u1", None),
+        ("__fd_database_by_id/1", Some(10)),
+    ])?;
+    assert!(grown.is_empty());
+
+    let grown = sm.get_tenant_usage_grown_by([("__fd_database_by_id/1", Some(1))])?;
+    assert!(grown.is_empty());
+
+    Ok(())
+}
+
+fn put(key: &str, value: &[u8]) -> TxnOp {
+    TxnOp {
+        request: Some(txn_op::Request::Put(TxnPutRequest {
+            key: key.to_string(),
+            value: value.to_vec(),
+            prev_value: false,
+            expire_at: None,
+            lease: None,
+        })),
+    }
+}
+
+fn del(key: &str) -> TxnOp {
+    TxnOp {
+        request: Some(txn_op::Request::Delete(TxnDeleteRequest {
+            key: key.to_string(),
+            prev_value: false,
+        })),
+    }
+}
+
+fn ent(index: u64, upsert: UpsertKV) -> Entry<LogEntry> {
+    cmd_ent(index, Cmd::UpsertKV(upsert))
+}
+
+fn cmd_ent(index: u64, cmd: Cmd) -> Entry<LogEntry> {
+    Entry {
+        log_id: LogId { term: 1, index },
+        payload: EntryPayload::Normal(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd,
+        }),
+    }
+}
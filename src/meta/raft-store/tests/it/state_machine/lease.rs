@@ -0,0 +1,272 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_meta_raft_store::sled_key_spaces::GenericKV;
+use common_meta_raft_store::state_machine::StateMachine;
+use common_meta_sled_store::openraft::Entry;
+use common_meta_sled_store::openraft::EntryPayload;
+use common_meta_sled_store::openraft::LogId;
+use common_meta_sled_store::AsKeySpace;
+use common_meta_types::txn_op;
+use common_meta_types::AppliedState;
+use common_meta_types::Cmd;
+use common_meta_types::KVMeta;
+use common_meta_types::Lease;
+use common_meta_types::LogEntry;
+use common_meta_types::SeqV;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnPutRequest;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKV;
+use common_meta_types::With;
+
+use crate::init_raft_store_ut;
+use crate::testing::new_raft_test_context;
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_lease_grant_keep_alive_revoke() -> anyhow::Result<()> {
+    // - Grant a lease.
+    // - Attach keys to it and detach by overriding.
+    // - Keep it alive.
+    // - Revoke it and all attached keys are deleted.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+    let kvs = sm.sm_tree.key_space::<GenericKV>();
+
+    let now = SeqV::<()>::now_ms();
+
+    let res = sm
+        .apply(&ent(3, Cmd::GrantLease { ttl_ms: 10_000 }, now))
+        .await?;
+    assert_eq!(AppliedState::Lease(Some(Lease::new(1, 10_000, now))), res);
+
+    sm.apply(&ent(4, upsert_with_lease("a", Some(1)), now))
+        .await?;
+    sm.apply(&ent(5, upsert_with_lease("c", Some(1)), now))
+        .await?;
+    assert_eq!(keys(&["a", "c"]), sm.list_lease_keys(1)?);
+
+    // Can not attach to a nonexistent lease.
+    let res = sm
+        .apply(&ent(6, upsert_with_lease("b", Some(100)), now))
+        .await?;
+    assert_eq!(AppliedState::Lease(None), res);
+    assert!(kvs.get(&s("b"))?.is_none());
+
+    // Override without lease detaches the key.
+    sm.apply(&ent(7, upsert_with_lease("c", None), now)).await?;
+    assert_eq!(keys(&["a"]), sm.list_lease_keys(1)?);
+
+    // Keep alive
+    let res = sm
+        .apply(&ent(8, Cmd::KeepAliveLease { id: 1 }, now + 5_000))
+        .await?;
+    match res {
+        AppliedState::Lease(Some(lease)) => {
+            assert_eq!(now + 15_000, lease.expire_at_ms);
+        }
+        _ => unreachable!("expect AppliedState::Lease, got: {:?}", res),
+    }
+
+    let res = sm
+        .apply(&ent(9, Cmd::KeepAliveLease { id: 100 }, now))
+        .await?;
+    assert_eq!(AppliedState::Lease(None), res);
+
+    // Revoke
+    let res = sm.apply(&ent(10, Cmd::RevokeLease { id: 1 }, now)).await?;
+    assert!(matches!(res, AppliedState::Lease(Some(_))));
+
+    assert!(sm.get_lease(1)?.is_none());
+    assert!(sm.list_lease_keys(1)?.is_empty());
+    assert!(kvs.get(&s("a"))?.is_none());
+    assert!(kvs.get(&s("c"))?.is_some());
+
+    Ok(())
+}
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_lease_expire() -> anyhow::Result<()> {
+    // - Grant leases and attach keys.
+    // - Apply a log after one of the lease expired, keys attached to it are cleaned.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+    let kvs = sm.sm_tree.key_space::<GenericKV>();
+
+    let now = SeqV::<()>::now_ms();
+
+    sm.apply(&ent(3, Cmd::GrantLease { ttl_ms: 1_000 }, now))
+        .await?;
+    sm.apply(&ent(4, Cmd::GrantLease { ttl_ms: 10_000 }, now))
+        .await?;
+    sm.apply(&ent(5, upsert_with_lease("a", Some(1)), now))
+        .await?;
+    sm.apply(&ent(6, upsert_with_lease("b", Some(2)), now))
+        .await?;
+
+    assert_eq!(Vec::<u64>::new(), sm.list_expired_leases(now + 1_000)?);
+    assert_eq!(vec![1], sm.list_expired_leases(now + 2_000)?);
+
+    sm.apply(&ent(7, upsert_with_lease("x", None), now + 2_000))
+        .await?;
+
+    assert!(sm.get_lease(1)?.is_none());
+    assert!(kvs.get(&s("a"))?.is_none());
+
+    assert!(sm.get_lease(2)?.is_some());
+    assert!(kvs.get(&s("b"))?.is_some());
+
+    // Can not attach to an expired lease.
+    let res = sm
+        .apply(&ent(8, upsert_with_lease("c", Some(2)), now + 20_000))
+        .await?;
+    assert_eq!(AppliedState::Lease(None), res);
+    assert!(kvs.get(&s("c"))?.is_none());
+
+    Ok(())
+}
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_lease_expire_order() -> anyhow::Result<()> {
+    // - Grant leases in an order different from their expire time.
+    // - Expired leases are listed by expire time, a renewed lease is listed by its new expire time.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+
+    let now = SeqV::<()>::now_ms();
+
+    sm.apply(&ent(3, Cmd::GrantLease { ttl_ms: 3_000 }, now))
+        .await?;
+    sm.apply(&ent(4, Cmd::GrantLease { ttl_ms: 1_000 }, now))
+        .await?;
+    sm.apply(&ent(5, Cmd::GrantLease { ttl_ms: 2_000 }, now))
+        .await?;
+
+    assert_eq!(vec![2], sm.list_expired_leases(now + 1_500)?);
+    assert_eq!(vec![2, 3, 1], sm.list_expired_leases(now + 3_500)?);
+
+    // Lease 2 expires at now + 1_500 after being kept alive, still before lease 3.
+    sm.apply(&ent(6, Cmd::KeepAliveLease { id: 2 }, now + 500))
+        .await?;
+    assert_eq!(Vec::<u64>::new(), sm.list_expired_leases(now + 1_500)?);
+    assert_eq!(vec![2], sm.list_expired_leases(now + 1_600)?);
+    assert_eq!(vec![2, 3], sm.list_expired_leases(now + 2_500)?);
+
+    Ok(())
+}
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_lease_txn_put() -> anyhow::Result<()> {
+    // - A txn that puts a key with a nonexistent or expired lease fails as a whole.
+    // - A txn that puts a key with a living lease attaches the key to it.
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+    let kvs = sm.sm_tree.key_space::<GenericKV>();
+
+    let now = SeqV::<()>::now_ms();
+
+    sm.apply(&ent(3, Cmd::GrantLease { ttl_ms: 1_000 }, now))
+        .await?;
+
+    // Can not attach to a nonexistent lease, the other puts are not applied either.
+    let res = sm
+        .apply(&ent(4, txn_put(&[("a", None), ("b", Some(100))]), now))
+        .await?;
+    assert_eq!(AppliedState::Lease(None), res);
+    assert!(kvs.get(&s("a"))?.is_none());
+    assert!(kvs.get(&s("b"))?.is_none());
+
+    let res = sm.apply(&ent(5, txn_put(&[("c", Some(1))]), now)).await?;
+    match res {
+        AppliedState::TxnReply(reply) => assert!(reply.success),
+        _ => unreachable!("expect AppliedState::TxnReply, got: {:?}", res),
+    }
+    assert_eq!(keys(&["c"]), sm.list_lease_keys(1)?);
+
+    // Can not attach to an expired lease.
+    let res = sm
+        .apply(&ent(6, txn_put(&[("d", Some(1))]), now + 2_000))
+        .await?;
+    assert_eq!(AppliedState::Lease(None), res);
+    assert!(kvs.get(&s("d"))?.is_none());
+
+    Ok(())
+}
+
+fn s(x: &str) -> String {
+    x.to_string()
+}
+
+fn keys(x: &[&str]) -> Vec<String> {
+    x.iter().map(|k| k.to_string()).collect()
+}
+
+fn upsert_with_lease(key: &str, lease: Option<u64>) -> Cmd {
+    Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes()).with(KVMeta {
+        expire_at: None,
+        lease,
+    }))
+}
+
+fn txn_put(puts: &[(&str, Option<u64>)]) -> Cmd {
+    Cmd::Transaction(TxnRequest {
+        condition: vec![],
+        if_then: puts
+            .iter()
+            .map(|(key, lease)| TxnOp {
+                request: Some(txn_op::Request::Put(TxnPutRequest {
+                    key: key.to_string(),
+                    value: key.as_bytes().to_vec(),
+                    prev_value: false,
+                    expire_at: None,
+                    lease: *lease,
+                })),
+            })
+            .collect(),
+        else_then: vec![],
+    })
+}
+
+/// Build a raft log entry proposed at `time_ms`
+fn ent(index: u64, cmd: Cmd, time_ms: u64) -> Entry<LogEntry> {
+    Entry {
+        log_id: LogId { term: 1, index },
+        payload: EntryPayload::Normal(LogEntry {
+            txid: None,
+            time_ms: Some(time_ms),
+            cmd,
+        }),
+    }
+}
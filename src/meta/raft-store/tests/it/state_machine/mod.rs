@@ -39,6 +39,8 @@ use crate::init_raft_store_ut;
 use crate::testing::new_raft_test_context;
 
 mod expire;
+mod key_space_usage;
+mod lease;
 mod schema_api_impl;
 mod snapshot;
 
@@ -62,6 +64,7 @@ async fn test_state_machine_apply_non_dup_incr_seq() -> anyhow::Result<()> {
                     },
                     &mut t,
                     None,
+                    &[],
                     0,
                 )
                 .unwrap())
@@ -79,6 +82,7 @@ async fn test_state_machine_apply_non_dup_incr_seq() -> anyhow::Result<()> {
                     },
                     &mut t,
                     None,
+                    &[],
                     0,
                 )
                 .unwrap())
@@ -145,7 +149,10 @@ async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Res
         prev: Option<(u64, &'static str)>,
         result: Option<(u64, &'static str)>,
     ) -> T {
-        let m = meta.map(|x| KVMeta { expire_at: Some(x) });
+        let m = meta.map(|x| KVMeta {
+            expire_at: Some(x),
+            lease: None,
+        });
         T {
             key: name.to_string(),
             seq,
@@ -225,6 +232,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Res
                     }),
                     &mut t,
                     None,
+                    &[],
                     SeqV::<()>::now_ms(),
                 )
                 .unwrap())
@@ -288,10 +296,12 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
                     value: Operation::AsIs,
                     value_meta: Some(KVMeta {
                         expire_at: Some(now + 10),
+                        lease: None,
                     }),
                 }),
                 &mut t,
                 None,
+                &[],
                 0,
             )
             .unwrap())
@@ -315,10 +325,12 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
                     value: Operation::Update(b"value_meta_bar".to_vec()),
                     value_meta: Some(KVMeta {
                         expire_at: Some(now + 10),
+                        lease: None,
                     }),
                 }),
                 &mut t,
                 None,
+                &[],
                 0,
             )
             .unwrap())
@@ -334,10 +346,12 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
                     value: Operation::AsIs,
                     value_meta: Some(KVMeta {
                         expire_at: Some(now + 20),
+                        lease: None,
                     }),
                 }),
                 &mut t,
                 None,
+                &[],
                 0,
             )
             .unwrap())
@@ -352,7 +366,8 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
         SeqV {
             seq: got.seq,
             meta: Some(KVMeta {
-                expire_at: Some(now + 20)
+                expire_at: Some(now + 20),
+                lease: None,
             }),
             data: b"value_meta_bar".to_vec()
         },
@@ -415,6 +430,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
                     &Cmd::UpsertKV(UpsertKV::update("foo", b"x")),
                     &mut t,
                     None,
+                    &[],
                     0,
                 )
                 .unwrap())
@@ -427,6 +443,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
                     &Cmd::UpsertKV(UpsertKV::delete(&c.key).with(c.seq)),
                     &mut t,
                     None,
+                    &[],
                     0,
                 )
                 .unwrap())
@@ -446,3 +463,57 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
 
     Ok(())
 }
+
+#[async_entry::test(
+    worker_threads = 3,
+    init = "init_raft_store_ut!()",
+    tracing_span = "debug"
+)]
+async fn test_state_machine_apply_record_change_history() -> anyhow::Result<()> {
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    assert_eq!(None, sm.first_change_revision()?);
+
+    let cmds = vec![
+        Cmd::UpsertKV(UpsertKV::update("foo", b"a")),
+        // Not changed, no change is recorded.
+        Cmd::UpsertKV(UpsertKV::update("bar", b"b").with(MatchSeq::Exact(5))),
+        Cmd::UpsertKV(UpsertKV::update("foo", b"b")),
+        Cmd::UpsertKV(UpsertKV::delete("foo")),
+    ];
+
+    for (i, cmd) in cmds.into_iter().enumerate() {
+        sm.apply(&Entry {
+            log_id: LogId {
+                term: 1,
+                index: i as u64 + 1,
+            },
+            payload: EntryPayload::Normal(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd,
+            }),
+        })
+        .await?;
+    }
+
+    assert_eq!(Some(1), sm.first_change_revision()?);
+
+    let a = SeqV::new(1, b"a".to_vec());
+    let b = SeqV::new(2, b"b".to_vec());
+
+    let changes = sm.list_changes(2)?;
+    assert_eq!(
+        vec![
+            (
+                2,
+                Change::new_with_id("foo".to_string(), Some(a), Some(b.clone()))
+            ),
+            (3, Change::new_with_id("foo".to_string(), Some(b), None)),
+        ],
+        changes
+    );
+
+    Ok(())
+}
@@ -16,8 +16,10 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use common_arrow::arrow_format::flight::data::BasicAuth;
+use common_base::base::tokio;
 use common_base::base::tokio::sync::mpsc;
 use common_grpc::GrpcClaim;
 use common_grpc::GrpcToken;
@@ -29,14 +31,27 @@ use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::ExportedChunk;
 use common_meta_types::protobuf::HandshakeRequest;
 use common_meta_types::protobuf::HandshakeResponse;
+use common_meta_types::protobuf::LeaseGrantRequest;
+use common_meta_types::protobuf::LeaseGrantResponse;
+use common_meta_types::protobuf::LeaseKeepAliveRequest;
+use common_meta_types::protobuf::LeaseKeepAliveResponse;
+use common_meta_types::protobuf::LeaseRevokeRequest;
+use common_meta_types::protobuf::LeaseRevokeResponse;
 use common_meta_types::protobuf::MemberListReply;
 use common_meta_types::protobuf::MemberListRequest;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
+use common_meta_types::GetKVReply;
+use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVReply;
+use common_meta_types::MGetKVReply;
+use common_meta_types::ReadConsistency;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
+use common_meta_types::READ_CONSISTENCY_KEY;
 use common_metrics::counter::Count;
 use futures::StreamExt;
 use prost::Message;
@@ -52,12 +67,16 @@ use tracing::info;
 
 use crate::meta_service::meta_service_impl::GrpcStream;
 use crate::meta_service::MetaNode;
+use crate::metrics::log_if_slow;
 use crate::metrics::network_metrics;
+use crate::metrics::txn_keys;
+use crate::metrics::ReqKeys;
 use crate::metrics::RequestInFlight;
 use crate::version::from_digit_ver;
 use crate::version::to_digit_ver;
 use crate::version::METASRV_SEMVER;
 use crate::version::MIN_METACLI_SEMVER;
+use crate::watcher::WATCHER_BUFFER_SIZE;
 
 pub struct MetaServiceImpl {
     token: GrpcToken,
@@ -85,6 +104,54 @@ impl MetaServiceImpl {
         Ok(claim)
     }
 
+    /// Parse the consistency of a read from request metadata.
+    ///
+    /// It defaults to `ReadConsistency::Leader` if absent.
+    fn read_consistency(metadata: &MetadataMap) -> Result<ReadConsistency, Status> {
+        let v = match metadata.get(READ_CONSISTENCY_KEY) {
+            None => return Ok(ReadConsistency::default()),
+            Some(v) => v,
+        };
+
+        let s = v.to_str().map_err(|e| {
+            Status::invalid_argument(format!("invalid {}: {}", READ_CONSISTENCY_KEY, e))
+        })?;
+
+        serde_json::from_str(s).map_err(|e| {
+            Status::invalid_argument(format!("invalid {}: {}: {}", READ_CONSISTENCY_KEY, s, e))
+        })
+    }
+
+    async fn handle_kv_req(&self, req: MetaGrpcReq, consistency: ReadConsistency) -> RaftReply {
+        let m = &self.meta_node;
+        match req {
+            MetaGrpcReq::UpsertKV(a) => {
+                let res = m.upsert_kv(a).await;
+                RaftReply::from(res)
+            }
+            MetaGrpcReq::GetKV(a) => {
+                let res: Result<GetKVReply, KVAppError> =
+                    m.read_with(a, consistency).await.map_err(KVAppError::from);
+                RaftReply::from(res)
+            }
+            MetaGrpcReq::MGetKV(a) => {
+                let res: Result<MGetKVReply, KVAppError> =
+                    m.read_with(a, consistency).await.map_err(KVAppError::from);
+                RaftReply::from(res)
+            }
+            MetaGrpcReq::ListKV(a) => {
+                let res: Result<ListKVReply, KVAppError> =
+                    m.read_with(a, consistency).await.map_err(KVAppError::from);
+                RaftReply::from(res)
+            }
+            MetaGrpcReq::ListKVRange(a) => {
+                let res: Result<ListKVRangeReply, KVAppError> =
+                    m.read_with(a, consistency).await.map_err(KVAppError::from);
+                RaftReply::from(res)
+            }
+        }
+    }
+
     async fn execute_txn(&self, req: TxnRequest) -> TxnReply {
         let ret = self.meta_node.transaction(req).await;
         network_metrics::incr_request_result(ret.is_ok());
@@ -171,6 +238,7 @@ impl MetaService for MetaServiceImpl {
         self.kv_api(r).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn kv_api(&self, r: Request<RaftRequest>) -> Result<Response<RaftReply>, Status> {
         let _guard = RequestInFlight::guard();
 
@@ -178,28 +246,16 @@ impl MetaService for MetaServiceImpl {
         common_tracing::extract_remote_span_as_parent(&r);
         network_metrics::incr_recv_bytes(r.get_ref().encoded_len() as u64);
 
+        let consistency = Self::read_consistency(r.metadata())?;
         let req: MetaGrpcReq = r.try_into()?;
-        info!("Received MetaGrpcReq: {:?}", req);
+        info!(
+            "Received MetaGrpcReq: {:?}, consistency: {:?}",
+            req, consistency
+        );
 
-        let m = &self.meta_node;
-        let reply = match req {
-            MetaGrpcReq::UpsertKV(a) => {
-                let res = m.upsert_kv(a).await;
-                RaftReply::from(res)
-            }
-            MetaGrpcReq::GetKV(a) => {
-                let res = m.get_kv(&a.key).await;
-                RaftReply::from(res)
-            }
-            MetaGrpcReq::MGetKV(a) => {
-                let res = m.mget_kv(&a.keys).await;
-                RaftReply::from(res)
-            }
-            MetaGrpcReq::ListKV(a) => {
-                let res = m.prefix_list_kv(&a.prefix).await;
-                RaftReply::from(res)
-            }
-        };
+        let threshold = self.meta_node.slow_request_threshold();
+        let (op, keys) = kv_req_summary(&req, threshold);
+        let reply = log_if_slow(op, keys, threshold, self.handle_kv_req(req, consistency)).await;
 
         network_metrics::incr_request_result(reply.error.is_empty());
         network_metrics::incr_sent_bytes(reply.encoded_len() as u64);
@@ -237,14 +293,15 @@ impl MetaService for MetaServiceImpl {
         &self,
         request: Request<WatchRequest>,
     ) -> Result<Response<Self::WatchStream>, Status> {
-        let (tx, rx) = mpsc::channel(4);
+        let (tx, rx) = mpsc::channel(WATCHER_BUFFER_SIZE);
 
-        self.meta_node.add_watcher(request.into_inner(), tx);
+        self.meta_node.add_watcher(request.into_inner(), tx).await;
 
         let output_stream = tokio_stream::wrappers::ReceiverStream::new(rx);
         Ok(Response::new(Box::pin(output_stream) as Self::WatchStream))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn transaction(
         &self,
         request: Request<TxnRequest>,
@@ -259,7 +316,9 @@ impl MetaService for MetaServiceImpl {
 
         info!("Receive txn_request: {}", request);
 
-        let body = self.execute_txn(request).await;
+        let threshold = self.meta_node.slow_request_threshold();
+        let keys = ReqKeys::collect(threshold, || txn_keys(&request));
+        let body = log_if_slow("Transaction", keys, threshold, self.execute_txn(request)).await;
         network_metrics::incr_sent_bytes(body.encoded_len() as u64);
 
         Ok(Response::new(body))
@@ -298,6 +357,94 @@ impl MetaService for MetaServiceImpl {
         }
         Err(Status::unavailable("can not get client ip address"))
     }
+
+    async fn lease_grant(
+        &self,
+        request: Request<LeaseGrantRequest>,
+    ) -> Result<Response<LeaseGrantResponse>, Status> {
+        self.check_token(request.metadata())?;
+        let _guard = RequestInFlight::guard();
+
+        let req = request.into_inner();
+        info!("Receive lease_grant: ttl_ms: {}", req.ttl_ms);
+
+        let ret = self.meta_node.grant_lease(req.ttl_ms).await;
+        network_metrics::incr_request_result(ret.is_ok());
+
+        let lease = ret.map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(LeaseGrantResponse {
+            id: lease.id,
+            ttl_ms: lease.ttl_ms,
+        }))
+    }
+
+    type LeaseKeepAliveStream = Pin<
+        Box<
+            dyn Stream<Item = Result<LeaseKeepAliveResponse, tonic::Status>>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    >;
+
+    async fn lease_keep_alive(
+        &self,
+        request: Request<Streaming<LeaseKeepAliveRequest>>,
+    ) -> Result<Response<Self::LeaseKeepAliveStream>, Status> {
+        self.check_token(request.metadata())?;
+
+        let mut in_stream = request.into_inner();
+        let (tx, rx) = mpsc::channel(4);
+        let meta_node = self.meta_node.clone();
+
+        tokio::spawn(async move {
+            while let Some(req) = in_stream.next().await {
+                let resp = match req {
+                    Ok(req) => {
+                        let ret = meta_node.keep_alive_lease(req.id).await;
+                        network_metrics::incr_request_result(ret.is_ok());
+
+                        ret.map(|lease| LeaseKeepAliveResponse {
+                            id: req.id,
+                            ttl_ms: lease.map(|l| l.ttl_ms).unwrap_or_default(),
+                        })
+                        .map_err(|e| Status::internal(e.to_string()))
+                    }
+                    Err(status) => Err(status),
+                };
+
+                if tx.send(resp).await.is_err() {
+                    // The client closed the stream.
+                    break;
+                }
+            }
+            debug!("lease keep-alive stream closed");
+        });
+
+        let output_stream = tokio_stream::wrappers::ReceiverStream::new(rx);
+        Ok(Response::new(
+            Box::pin(output_stream) as Self::LeaseKeepAliveStream
+        ))
+    }
+
+    async fn lease_revoke(
+        &self,
+        request: Request<LeaseRevokeRequest>,
+    ) -> Result<Response<LeaseRevokeResponse>, Status> {
+        self.check_token(request.metadata())?;
+        let _guard = RequestInFlight::guard();
+
+        let req = request.into_inner();
+        info!("Receive lease_revoke: id: {}", req.id);
+
+        let ret = self.meta_node.revoke_lease(req.id).await;
+        network_metrics::incr_request_result(ret.is_ok());
+
+        let prev = ret.map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(LeaseRevokeResponse {
+            found: prev.is_some(),
+        }))
+    }
 }
 
 pub struct ExportStream {
@@ -319,3 +466,26 @@ impl Stream for ExportStream {
         Poll::Ready(Some(self.data.drain(0..chunk_size).collect()))
     }
 }
+
+/// Returns the type and the keys of a kv request, for the slow request log.
+fn kv_req_summary(req: &MetaGrpcReq, threshold: Duration) -> (&'static str, ReqKeys) {
+    let op = match req {
+        MetaGrpcReq::UpsertKV(_) => "UpsertKV",
+        MetaGrpcReq::GetKV(_) => "GetKV",
+        MetaGrpcReq::MGetKV(_) => "MGetKV",
+        MetaGrpcReq::ListKV(_) => "ListKV",
+        MetaGrpcReq::ListKVRange(_) => "ListKVRange",
+    };
+
+    let keys = ReqKeys::collect(threshold, || match req {
+        MetaGrpcReq::UpsertKV(a) => vec![a.key.clone()],
+        MetaGrpcReq::GetKV(a) => vec![a.key.clone()],
+        MetaGrpcReq::MGetKV(a) => a.keys.clone(),
+        MetaGrpcReq::ListKV(a) => vec![a.prefix.clone()],
+        MetaGrpcReq::ListKVRange(a) => {
+            vec![format!("[{}, {})", a.start, a.end.as_deref().unwrap_or(""))]
+        }
+    });
+
+    (op, keys)
+}
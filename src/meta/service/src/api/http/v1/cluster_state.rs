@@ -47,3 +47,14 @@ pub async fn status_handler(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<imp
 
     Ok(Json(status))
 }
+
+/// Key space usage of the local state machine, by tenant and by top-level key prefix.
+///
+/// request: None
+/// return: `KeySpaceStats`
+#[poem::handler]
+pub async fn key_space_handler(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<impl IntoResponse> {
+    let stats = meta_node.get_state_machine().await.get_key_space_stats();
+
+    Ok(Json(stats))
+}
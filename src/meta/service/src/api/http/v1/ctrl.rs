@@ -14,10 +14,14 @@
 
 use std::sync::Arc;
 
+use common_meta_types::NodeId;
 use poem::http::StatusCode;
 use poem::web::Data;
 use poem::web::IntoResponse;
 use poem::web::Json;
+use poem::web::Query;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::meta_service::MetaNode;
 
@@ -33,3 +37,42 @@ pub async fn trigger_snapshot(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<i
         .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
     Ok(Json(()))
 }
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TransferLeaderQuery {
+    /// The voter to transfer the leadership to. Another voter is chosen if it is absent.
+    pub to: Option<NodeId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferLeaderResponse {
+    pub leader: NodeId,
+}
+
+/// Transfer the raft leadership to the voter `to`, e.g., before restarting the leader.
+///
+/// It returns the id of the new leader once it is elected.
+#[poem::handler]
+pub async fn transfer_leader(
+    meta_node: Data<&Arc<MetaNode>>,
+    Query(query): Query<TransferLeaderQuery>,
+) -> poem::Result<impl IntoResponse> {
+    let leader = meta_node
+        .transfer_leader(query.to)
+        .await
+        .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(TransferLeaderResponse { leader }))
+}
+
+/// Remove this node from the cluster.
+///
+/// If this node is the leader, the leadership is transferred to another voter first.
+/// It returns once this node is no longer a voter.
+#[poem::handler]
+pub async fn leave(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<impl IntoResponse> {
+    meta_node
+        .drain()
+        .await
+        .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(()))
+}
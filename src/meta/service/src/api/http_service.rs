@@ -60,6 +60,11 @@ impl HttpService {
                 "/v1/ctrl/trigger_snapshot",
                 get(super::http::v1::ctrl::trigger_snapshot),
             )
+            .at(
+                "/v1/ctrl/transfer_leader",
+                get(super::http::v1::ctrl::transfer_leader),
+            )
+            .at("/v1/ctrl/leave", get(super::http::v1::ctrl::leave))
             .at(
                 "/v1/cluster/nodes",
                 get(super::http::v1::cluster_state::nodes_handler),
@@ -68,6 +73,10 @@ impl HttpService {
                 "/v1/cluster/status",
                 get(super::http::v1::cluster_state::status_handler),
             )
+            .at(
+                "/v1/cluster/key_space",
+                get(super::http::v1::cluster_state::key_space_handler),
+            )
             .at(
                 "/v1/metrics",
                 get(super::http::v1::metrics::metrics_handler),
@@ -0,0 +1,272 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backup the state machine to an object storage and read it back for restoring.
+//!
+//! A backup is a state machine snapshot in the same format as `metactl --export` output:
+//! one `[tree_name, {key_space: {key, value}}]` json per line.
+//! Every backup is stored as an object named after the time it is taken,
+//! e.g., `00000000001667260800.backup`, thus listing them in lexical order lists them by time.
+
+use std::pin::Pin;
+
+use anyerror::AnyError;
+use common_storage::init_operator_without_layers;
+use common_storage::StorageFsConfig;
+use common_storage::StorageParams;
+use common_storage::StorageS3Config;
+use futures::io::AsyncBufReadExt;
+use futures::io::BufReader;
+use futures::Stream;
+use futures::TryStreamExt;
+use opendal::ObjectMode;
+use opendal::Operator;
+use tracing::info;
+use url::Url;
+
+const BACKUP_FILE_SUFFIX: &str = ".backup";
+
+/// The lines in a backup, read on demand.
+pub type BackupLines = Pin<Box<dyn Stream<Item = Result<String, AnyError>> + Send>>;
+
+/// Build the name of a backup taken at `at_secs`.
+pub fn backup_file_name(at_secs: u64) -> String {
+    format!("{:020}{}", at_secs, BACKUP_FILE_SUFFIX)
+}
+
+/// Parse the time in seconds a backup is taken from its name.
+///
+/// It returns `None` if it is not a backup file.
+pub fn parse_backup_file_name(name: &str) -> Option<u64> {
+    let secs = name.strip_suffix(BACKUP_FILE_SUFFIX)?;
+    secs.parse::<u64>().ok()
+}
+
+/// Hide the credentials in a backup uri, so that it can be put in logs and errors.
+pub fn redact_backup_uri(uri: &str) -> String {
+    let mut u = match Url::parse(uri) {
+        Ok(u) => u,
+        // An invalid uri may still contain credentials.
+        Err(_) => return "<invalid backup uri>".to_string(),
+    };
+
+    if u.password().is_some() {
+        let _ = u.set_password(Some("***"));
+    }
+
+    let pairs: Vec<(String, String)> = u
+        .query_pairs()
+        .map(|(k, v)| match k.as_ref() {
+            "secret_access_key" => (k.to_string(), "***".to_string()),
+            _ => (k.to_string(), v.to_string()),
+        })
+        .collect();
+
+    if !pairs.is_empty() {
+        u.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    u.to_string()
+}
+
+/// Build storage params from a backup uri.
+///
+/// Supported uri:
+/// - `fs:///path/to/dir`
+/// - `s3://<bucket>/<root>?endpoint_url=<url>&region=<region>`;
+///   Credentials are loaded from the environment if `access_key_id` and `secret_access_key` are absent.
+pub fn parse_backup_uri(uri: &str) -> Result<StorageParams, AnyError> {
+    let u = Url::parse(uri).map_err(|e| AnyError::new(&e).add_context(|| "parse backup uri"))?;
+
+    match u.scheme() {
+        "fs" => {
+            if u.path().is_empty() {
+                return Err(AnyError::error(format!(
+                    "no dir in backup uri: {}",
+                    redact_backup_uri(uri)
+                )));
+            }
+
+            Ok(StorageParams::Fs(StorageFsConfig {
+                root: u.path().to_string(),
+            }))
+        }
+        "s3" => {
+            let bucket = match u.host_str() {
+                Some(b) => b.to_string(),
+                None => {
+                    return Err(AnyError::error(format!(
+                        "no bucket in backup uri: {}",
+                        redact_backup_uri(uri)
+                    )));
+                }
+            };
+
+            let mut cfg = StorageS3Config {
+                bucket,
+                root: u.path().to_string(),
+                ..Default::default()
+            };
+
+            for (k, v) in u.query_pairs() {
+                match k.as_ref() {
+                    "endpoint_url" => cfg.endpoint_url = v.to_string(),
+                    "region" => cfg.region = v.to_string(),
+                    "access_key_id" => cfg.access_key_id = v.to_string(),
+                    "secret_access_key" => cfg.secret_access_key = v.to_string(),
+                    _ => {
+                        return Err(AnyError::error(format!(
+                            "unknown option {} in backup uri: {}",
+                            k,
+                            redact_backup_uri(uri)
+                        )));
+                    }
+                }
+            }
+
+            Ok(StorageParams::S3(cfg))
+        }
+        x => Err(AnyError::error(format!(
+            "unsupported backup storage: {}, only fs and s3 are supported",
+            x
+        ))),
+    }
+}
+
+/// Write state machine backups to and read them from an object storage.
+#[derive(Clone)]
+pub struct MetaBackup {
+    op: Operator,
+}
+
+impl MetaBackup {
+    pub fn try_create(uri: &str) -> Result<Self, AnyError> {
+        let params = parse_backup_uri(uri)?;
+        let op = init_operator_without_layers(&params).map_err(|e| {
+            AnyError::new(&e)
+                .add_context(|| format!("open backup storage: {}", redact_backup_uri(uri)))
+        })?;
+
+        Ok(Self { op })
+    }
+
+    /// Save a backup taken at `at_secs` and return the name of it.
+    ///
+    /// `data` is the exported lines, each one ends with a `\n`.
+    pub async fn write(&self, at_secs: u64, data: Vec<u8>) -> Result<String, AnyError> {
+        let name = backup_file_name(at_secs);
+        let size = data.len();
+
+        self.op
+            .object(&name)
+            .write(data)
+            .await
+            .map_err(|e| AnyError::new(&e).add_context(|| format!("write backup: {}", name)))?;
+
+        info!("backup written: {}, size: {}", name, size);
+        Ok(name)
+    }
+
+    /// List the time of all backups, the oldest first.
+    pub async fn list(&self) -> Result<Vec<u64>, AnyError> {
+        let mut res = vec![];
+
+        let mut ds = self
+            .op
+            .object("/")
+            .list()
+            .await
+            .map_err(|e| AnyError::new(&e).add_context(|| "list backups"))?;
+
+        while let Some(de) = ds
+            .try_next()
+            .await
+            .map_err(|e| AnyError::new(&e).add_context(|| "list backups"))?
+        {
+            let mode = de
+                .mode()
+                .await
+                .map_err(|e| AnyError::new(&e).add_context(|| "list backups"))?;
+
+            if mode != ObjectMode::FILE {
+                continue;
+            }
+
+            if let Some(at) = parse_backup_file_name(de.name()) {
+                res.push(at);
+            }
+        }
+
+        res.sort_unstable();
+        Ok(res)
+    }
+
+    /// Read the latest backup taken at or before `at_secs`, or the latest one if `at_secs` is `None`.
+    ///
+    /// It returns the time the backup is taken and a stream of the lines in it.
+    pub async fn read(&self, at_secs: Option<u64>) -> Result<(u64, BackupLines), AnyError> {
+        let backups = self.list().await?;
+
+        let found = backups
+            .into_iter()
+            .rev()
+            .find(|t| at_secs.map(|at| *t <= at).unwrap_or(true));
+
+        let t = match found {
+            Some(t) => t,
+            None => {
+                return Err(AnyError::error(format!(
+                    "no backup found at or before: {:?}",
+                    at_secs
+                )));
+            }
+        };
+
+        let name = backup_file_name(t);
+        let reader = self
+            .op
+            .object(&name)
+            .range_reader(0..)
+            .await
+            .map_err(|e| AnyError::new(&e).add_context(|| format!("read backup: {}", name)))?;
+
+        let lines = BufReader::new(reader)
+            .lines()
+            .try_filter(|l| futures::future::ready(!l.is_empty()))
+            .map_err(move |e| AnyError::new(&e).add_context(|| format!("read backup: {}", name)));
+
+        Ok((t, Box::pin(lines)))
+    }
+
+    /// Remove old backups and keep at most `keep` latest ones.
+    pub async fn purge(&self, keep: usize) -> Result<(), AnyError> {
+        let backups = self.list().await?;
+
+        if backups.len() <= keep {
+            return Ok(());
+        }
+
+        let n = backups.len() - keep;
+        for t in backups.into_iter().take(n) {
+            let name = backup_file_name(t);
+            self.op.object(&name).delete().await.map_err(|e| {
+                AnyError::new(&e).add_context(|| format!("remove backup: {}", name))
+            })?;
+
+            info!("removed expired backup: {}", name);
+        }
+
+        Ok(())
+    }
+}
@@ -37,6 +37,13 @@ pub struct Config {
     /// Certificate for server to identify itself
     pub grpc_tls_server_cert: String,
     pub grpc_tls_server_key: String,
+    /// The storage to save state machine backups to, e.g., `s3://bucket/root` or `fs:///path/to/dir`.
+    /// Backup is disabled if it is empty.
+    pub backup_uri: String,
+    /// The interval in seconds at which the leader takes a backup.
+    pub backup_interval: u64,
+    /// The number of the latest backups to keep.
+    pub backup_keep: u64,
     pub raft_config: RaftConfig,
 }
 
@@ -58,6 +65,9 @@ impl Default for Config {
             grpc_api_address: "127.0.0.1:9191".to_string(),
             grpc_tls_server_cert: "".to_string(),
             grpc_tls_server_key: "".to_string(),
+            backup_uri: "".to_string(),
+            backup_interval: 3600,
+            backup_keep: 24,
             raft_config: Default::default(),
         }
     }
@@ -113,6 +113,19 @@ pub struct Config {
     #[clap(long, default_value = "")]
     pub grpc_tls_server_key: String,
 
+    /// The storage to save state machine backups to, e.g., `s3://bucket/root` or `fs:///path/to/dir`.
+    /// Backup is disabled if it is empty.
+    #[clap(long, default_value = "")]
+    pub backup_uri: String,
+
+    /// The interval in seconds at which the leader takes a backup.
+    #[clap(long, default_value = "3600")]
+    pub backup_interval: u64,
+
+    /// The number of the latest backups to keep.
+    #[clap(long, default_value = "24")]
+    pub backup_keep: u64,
+
     #[clap(flatten)]
     pub raft_config: RaftConfig,
 }
@@ -149,6 +162,9 @@ impl From<Config> for InnerConfig {
             grpc_api_address: x.grpc_api_address,
             grpc_tls_server_cert: x.grpc_tls_server_cert,
             grpc_tls_server_key: x.grpc_tls_server_key,
+            backup_uri: x.backup_uri,
+            backup_interval: x.backup_interval,
+            backup_keep: x.backup_keep,
             raft_config: x.raft_config.into(),
         }
     }
@@ -174,6 +190,9 @@ impl From<InnerConfig> for Config {
             grpc_api_address: inner.grpc_api_address,
             grpc_tls_server_cert: inner.grpc_tls_server_cert,
             grpc_tls_server_key: inner.grpc_tls_server_key,
+            backup_uri: inner.backup_uri,
+            backup_interval: inner.backup_interval,
+            backup_keep: inner.backup_keep,
             raft_config: inner.raft_config.into(),
         }
     }
@@ -255,6 +274,9 @@ pub struct ConfigViaEnv {
     pub metasrv_grpc_api_address: String,
     pub grpc_tls_server_cert: String,
     pub grpc_tls_server_key: String,
+    pub metasrv_backup_uri: String,
+    pub metasrv_backup_interval: u64,
+    pub metasrv_backup_keep: u64,
 
     pub config_id: String,
     pub kvsrv_listen_host: String,
@@ -268,6 +290,10 @@ pub struct ConfigViaEnv {
     pub raft_max_applied_log_to_keep: u64,
     pub kvsrv_single: bool,
     pub metasrv_join: Vec<String>,
+    pub metasrv_learner: bool,
+    pub metasrv_max_keys_per_tenant: u64,
+    pub metasrv_max_bytes_per_tenant: u64,
+    pub metasrv_slow_request_threshold_ms: u64,
     pub kvsrv_id: u64,
     pub sled_tree_prefix: String,
     pub cluster_name: String,
@@ -298,6 +324,9 @@ impl From<Config> for ConfigViaEnv {
             metasrv_grpc_api_address: cfg.grpc_api_address,
             grpc_tls_server_cert: cfg.grpc_tls_server_cert,
             grpc_tls_server_key: cfg.grpc_tls_server_key,
+            metasrv_backup_uri: cfg.backup_uri,
+            metasrv_backup_interval: cfg.backup_interval,
+            metasrv_backup_keep: cfg.backup_keep,
             config_id: cfg.raft_config.config_id,
             kvsrv_listen_host: cfg.raft_config.raft_listen_host,
             kvsrv_advertise_host: cfg.raft_config.raft_advertise_host,
@@ -310,6 +339,10 @@ impl From<Config> for ConfigViaEnv {
             raft_max_applied_log_to_keep: cfg.raft_config.max_applied_log_to_keep,
             kvsrv_single: cfg.raft_config.single,
             metasrv_join: cfg.raft_config.join,
+            metasrv_learner: cfg.raft_config.learner,
+            metasrv_max_keys_per_tenant: cfg.raft_config.max_keys_per_tenant,
+            metasrv_max_bytes_per_tenant: cfg.raft_config.max_bytes_per_tenant,
+            metasrv_slow_request_threshold_ms: cfg.raft_config.slow_request_threshold_ms,
             kvsrv_id: cfg.raft_config.id,
             sled_tree_prefix: cfg.raft_config.sled_tree_prefix,
             cluster_name: cfg.raft_config.cluster_name,
@@ -334,6 +367,10 @@ impl Into<Config> for ConfigViaEnv {
             max_applied_log_to_keep: self.raft_max_applied_log_to_keep,
             single: self.kvsrv_single,
             join: self.metasrv_join,
+            learner: self.metasrv_learner,
+            max_keys_per_tenant: self.metasrv_max_keys_per_tenant,
+            max_bytes_per_tenant: self.metasrv_max_bytes_per_tenant,
+            slow_request_threshold_ms: self.metasrv_slow_request_threshold_ms,
             // Do not allow to leave via environment variable
             leave_via: vec![],
             // Do not allow to leave via environment variable
@@ -375,6 +412,9 @@ impl Into<Config> for ConfigViaEnv {
             grpc_api_address: self.metasrv_grpc_api_address,
             grpc_tls_server_cert: self.grpc_tls_server_cert,
             grpc_tls_server_key: self.grpc_tls_server_key,
+            backup_uri: self.metasrv_backup_uri,
+            backup_interval: self.metasrv_backup_interval,
+            backup_keep: self.metasrv_backup_keep,
             raft_config,
         }
     }
@@ -445,6 +485,27 @@ pub struct RaftConfig {
     #[clap(long, multiple_occurrences = true, multiple_values = true)]
     pub join: Vec<String>,
 
+    /// Join the cluster as a non-voting learner, which replicates logs and serves reads but does not vote.
+    ///
+    /// It is ignored if this node is already a member of a cluster.
+    #[clap(long)]
+    pub learner: bool,
+
+    /// The max number of keys a tenant can have. 0 means no limit.
+    ///
+    /// Writes that put keys for a tenant that has reached the limit are rejected.
+    #[clap(long, default_value = "0")]
+    pub max_keys_per_tenant: u64,
+
+    /// The max total size in bytes of keys and values a tenant can have. 0 means no limit.
+    #[clap(long, default_value = "0")]
+    pub max_bytes_per_tenant: u64,
+
+    /// A request that takes longer than this, in milliseconds, is logged with its phase breakdown.
+    /// 0 disables the slow request log.
+    #[clap(long, default_value = "1000")]
+    pub slow_request_threshold_ms: u64,
+
     /// Do not run databend-meta, but just remove a node from its cluster via the provided endpoints.
     ///
     /// This node will be removed by `id`.
@@ -492,6 +553,10 @@ impl From<RaftConfig> for InnerRaftConfig {
             max_applied_log_to_keep: x.max_applied_log_to_keep,
             single: x.single,
             join: x.join,
+            learner: x.learner,
+            max_keys_per_tenant: x.max_keys_per_tenant,
+            max_bytes_per_tenant: x.max_bytes_per_tenant,
+            slow_request_threshold_ms: x.slow_request_threshold_ms,
             leave_via: x.leave_via,
             leave_id: x.leave_id,
             id: x.id,
@@ -516,6 +581,10 @@ impl From<InnerRaftConfig> for RaftConfig {
             max_applied_log_to_keep: inner.max_applied_log_to_keep,
             single: inner.single,
             join: inner.join,
+            learner: inner.learner,
+            max_keys_per_tenant: inner.max_keys_per_tenant,
+            max_bytes_per_tenant: inner.max_bytes_per_tenant,
+            slow_request_threshold_ms: inner.slow_request_threshold_ms,
             leave_via: inner.leave_via,
             leave_id: inner.leave_id,
             id: inner.id,
@@ -547,6 +616,7 @@ impl Into<InnerLogConfig> for LogConfig {
         InnerLogConfig {
             file: self.file.into(),
             stderr: self.stderr.into(),
+            ..Default::default()
         }
     }
 }
@@ -56,6 +56,10 @@ pub fn serialize_kv_variant(kv: &KeySpaceKV) -> Result<(sled::IVec, sled::IVec),
         KeySpaceKV::Sequences { key, value } => ser!(Sequences, key, value),
         KeySpaceKV::ClientLastResps { key, value } => ser!(ClientLastResps, key, value),
         KeySpaceKV::LogMeta { key, value } => ser!(LogMeta, key, value),
+        KeySpaceKV::Leases { key, value } => ser!(Leases, key, value),
+        KeySpaceKV::ChangeHistory { key, value } => ser!(ChangeHistory, key, value),
+        KeySpaceKV::LeaseExpires { key, value } => ser!(LeaseExpires, key, value),
+        KeySpaceKV::LeaseKeys { key, value } => ser!(LeaseKeys, key, value),
     }
 }
 
@@ -79,7 +83,11 @@ pub fn deserialize_to_kv_variant(d: &[Vec<u8>]) -> Result<KeySpaceKV, MetaStorag
         GenericKV,
         Sequences,
         ClientLastResps,
-        LogMeta
+        LogMeta,
+        Leases,
+        ChangeHistory,
+        LeaseExpires,
+        LeaseKeys
     );
 
     unreachable!("unknown prefix: {}", prefix);
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod api;
+pub mod backup;
 pub mod configs;
 pub mod export;
 pub mod logging;
@@ -12,19 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common_meta_api::KVApi;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_meta_sled_store::openraft::error::AddLearnerError;
+use common_meta_sled_store::openraft::error::ClientReadError;
 use common_meta_sled_store::openraft::error::RemoveLearnerError;
 use common_meta_types::AppliedState;
 use common_meta_types::Cmd;
+use common_meta_types::ElectRequest;
 use common_meta_types::ForwardRequest;
 use common_meta_types::ForwardResponse;
 use common_meta_types::LogEntry;
 use common_meta_types::MetaDataReadError;
 use common_meta_types::MetaOperationError;
 use common_meta_types::Node;
+use common_meta_types::NodeId;
 use common_meta_types::RaftChangeMembershipError;
 use common_meta_types::RaftWriteError;
 use common_meta_types::SeqV;
+use common_meta_types::TransferLeaderError;
+use common_meta_types::TransferLeaderRequest;
 use common_metrics::counter::Count;
 use tracing::debug;
 use tracing::error;
@@ -34,8 +42,12 @@ use crate::meta_service::ForwardRequestBody;
 use crate::meta_service::JoinRequest;
 use crate::meta_service::LeaveRequest;
 use crate::meta_service::MetaNode;
+use crate::metrics::record_phase;
 use crate::metrics::ProposalPending;
 
+/// The max time to wait for a new leader to be elected when transferring leadership.
+const TRANSFER_LEADER_TIMEOUT: Duration = Duration::from_millis(10_000);
+
 /// The container of APIs of a metasrv leader in a metasrv cluster.
 ///
 /// A meta leader does not imply it is actually the leader granted by the cluster.
@@ -67,42 +79,58 @@ impl<'a> MetaLeader<'a> {
                 self.leave(leave_req).await?;
                 Ok(ForwardResponse::Leave(()))
             }
+            ForwardRequestBody::TransferLeader(transfer_req) => {
+                let new_leader = self.transfer_leader(transfer_req).await?;
+                Ok(ForwardResponse::TransferLeader(new_leader))
+            }
+            ForwardRequestBody::Elect(_) => {
+                // This node is already the leader.
+                Ok(ForwardResponse::Elect(()))
+            }
             ForwardRequestBody::Write(entry) => {
                 let res = self.write(entry.clone()).await?;
                 Ok(ForwardResponse::AppliedState(res))
             }
 
-            ForwardRequestBody::GetKV(req) => {
-                let sm = self.meta_node.get_state_machine().await;
-                let res = sm
-                    .get_kv(&req.key)
-                    .await
-                    .map_err(|meta_err| MetaDataReadError::new("get_kv", "", &meta_err))?;
-                Ok(ForwardResponse::GetKV(res))
-            }
-            ForwardRequestBody::MGetKV(req) => {
-                let sm = self.meta_node.get_state_machine().await;
-                let res = sm
-                    .mget_kv(&req.keys)
-                    .await
-                    .map_err(|meta_err| MetaDataReadError::new("mget_kv", "", &meta_err))?;
-                Ok(ForwardResponse::MGetKV(res))
+            ForwardRequestBody::GetKV(_)
+            | ForwardRequestBody::MGetKV(_)
+            | ForwardRequestBody::ListKV(_)
+            | ForwardRequestBody::ListKVRange(_) => {
+                let res = self.meta_node.read_local(req.body).await?;
+                Ok(res)
             }
-            ForwardRequestBody::ListKV(req) => {
-                let sm = self.meta_node.get_state_machine().await;
-                let res = sm
-                    .prefix_list_kv(&req.prefix)
-                    .await
-                    .map_err(|meta_err| MetaDataReadError::new("list_kv", "", &meta_err))?;
-                Ok(ForwardResponse::ListKV(res))
+            ForwardRequestBody::ReadIndex(_) => {
+                let read_index = self.read_index().await?;
+                Ok(ForwardResponse::ReadIndex(read_index))
             }
         }
     }
 
+    /// Confirm this node is still the leader and return the index of the last log it applied.
+    ///
+    /// Every write that is completed before this call has been applied thus the returned index is the
+    /// least index a node has to apply before serving a linearizable read locally.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn read_index(&self) -> Result<u64, MetaOperationError> {
+        let res = self.meta_node.raft.client_read().await;
+        if let Err(e) = res {
+            return match e {
+                ClientReadError::ForwardToLeader(to_leader) => Err(to_leader.into()),
+                _ => Err(MetaDataReadError::new("read_index", "confirm leadership", &e).into()),
+            };
+        }
+
+        let metrics = self.meta_node.raft.metrics().borrow().clone();
+        let read_index = metrics.last_applied.map(|x| x.index).unwrap_or_default();
+
+        debug!("read_index: {}", read_index);
+        Ok(read_index)
+    }
+
     /// Join a new node to the cluster.
     ///
     /// - Adds the node to cluster as a non-voter persistently and starts replication.
-    /// - Adds the node to membership to let it become a voter.
+    /// - Adds the node to membership to let it become a voter, unless it joins as a learner.
     ///
     /// If the node is already in cluster membership, it still returns Ok.
     #[tracing::instrument(level = "debug", skip(self))]
@@ -110,6 +138,7 @@ impl<'a> MetaLeader<'a> {
         let node_id = req.node_id;
         let endpoint = req.endpoint;
         let grpc_api_addr = req.grpc_api_addr;
+        let learner = req.learner;
         let metrics = self.meta_node.raft.metrics().borrow().clone();
         let membership = metrics.membership_config.membership.clone();
 
@@ -134,6 +163,20 @@ impl<'a> MetaLeader<'a> {
         };
         self.write(ent).await?;
 
+        if learner {
+            let res = self.meta_node.raft.add_learner(node_id, false).await;
+            if let Err(e) = res {
+                return match e {
+                    AddLearnerError::ForwardToLeader(e) => {
+                        Err(RaftChangeMembershipError::ForwardToLeader(e))
+                    }
+                    AddLearnerError::Fatal(e) => Err(RaftChangeMembershipError::Fatal(e)),
+                };
+            }
+            info!("node {} joined as a learner", node_id);
+            return Ok(());
+        }
+
         self.meta_node
             .raft
             .change_membership(membership, false)
@@ -195,6 +238,73 @@ impl<'a> MetaLeader<'a> {
         Ok(())
     }
 
+    /// Transfer the leadership to another voter and return the id of the new leader.
+    ///
+    /// - Asks the target voter to start an election once it has received all logs of this leader.
+    /// - Waits until this node sees a new leader.
+    ///
+    /// If this node is the target, it returns at once.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn transfer_leader(
+        &self,
+        req: TransferLeaderRequest,
+    ) -> Result<NodeId, MetaOperationError> {
+        let my_id = self.meta_node.sto.id;
+        let metrics = self.meta_node.raft.metrics().borrow().clone();
+        let membership = metrics.membership_config.membership.clone();
+
+        // safe unwrap: if the first config is None, panic is the expected behavior here.
+        let voters = membership.get_ith_config(0).unwrap().clone();
+
+        let to = match req.to {
+            Some(to) => to,
+            None => {
+                let to = voters.iter().find(|id| **id != my_id).copied();
+                to.ok_or_else(|| TransferLeaderError::new(None, "no other voter"))?
+            }
+        };
+
+        if to == my_id {
+            return Ok(my_id);
+        }
+
+        if !voters.contains(&to) {
+            return Err(TransferLeaderError::new(Some(to), "target is not a voter").into());
+        }
+
+        let last_log_index = metrics.last_log_index.unwrap_or_default();
+        info!(
+            "transfer leadership from {} to {}, last_log_index: {}",
+            my_id, to, last_log_index
+        );
+
+        let elect_req = ForwardRequest {
+            forward_to_leader: 0,
+            body: ForwardRequestBody::Elect(ElectRequest { last_log_index }),
+        };
+        self.meta_node
+            .forward_to(&to, elect_req)
+            .await
+            .map_err(|e| TransferLeaderError::new(Some(to), e))?;
+
+        let metrics = self
+            .meta_node
+            .raft
+            .wait(Some(TRANSFER_LEADER_TIMEOUT))
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(my_id),
+                format!("leadership transferred to {}", to),
+            )
+            .await
+            .map_err(|e| TransferLeaderError::new(Some(to), e))?;
+
+        // safe unwrap: the condition above ensures there is a leader.
+        let new_leader = metrics.current_leader.unwrap();
+        info!("leadership transferred from {} to {}", my_id, new_leader);
+
+        Ok(new_leader)
+    }
+
     /// Write a log through local raft node and return the states before and after applying the log.
     ///
     /// If the raft node is not a leader, it returns MetaRaftError::ForwardToLeader.
@@ -207,12 +317,36 @@ impl<'a> MetaLeader<'a> {
         let _guard = ProposalPending::guard();
 
         info!("write LogEntry: {}", entry);
+        let start = Instant::now();
         let write_res = self.meta_node.raft.client_write(entry).await;
+        let elapsed = start.elapsed();
+
         if let Ok(ok) = &write_res {
             info!(
                 "raft.client_write res ok: log_id: {}, data: {}, membership: {:?}",
                 ok.log_id, ok.data, ok.membership
             );
+
+            // Break down the time into append, flush, replication and apply.
+            // Replication is what is left, including waiting for a quorum to commit.
+            let timings = {
+                let mut log_timings = self.meta_node.sto.log_timings.lock().unwrap();
+                log_timings.take(ok.log_id.index)
+            };
+            let append = timings.get("append").unwrap_or_default();
+            let flush = timings.get("flush").unwrap_or_default();
+            let apply = timings.get("apply").unwrap_or_default();
+
+            record_phase("append", append);
+            record_phase("flush", flush);
+            record_phase(
+                "replicate",
+                elapsed
+                    .saturating_sub(append)
+                    .saturating_sub(flush)
+                    .saturating_sub(apply),
+            );
+            record_phase("apply", apply);
         }
         if let Err(err) = &write_res {
             info!("raft.client_write res err: {:?}", err);
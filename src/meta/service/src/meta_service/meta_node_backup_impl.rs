@@ -0,0 +1,119 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyerror::AnyError;
+use common_base::base::tokio;
+use common_base::base::tokio::select;
+use common_meta_types::MetaStartupError;
+use tracing::error;
+use tracing::info;
+use tracing::Instrument;
+
+use crate::backup::MetaBackup;
+use crate::configs::Config as MetaConfig;
+use crate::meta_service::MetaNode;
+
+/// Backup API of MetaNode.
+///
+/// Only the leader takes backups, thus there is at most one backup for every interval in a cluster.
+impl MetaNode {
+    /// Save a consistent snapshot of the state machine to `backup` and keep only the latest `keep` backups.
+    ///
+    /// It returns the name of the saved backup.
+    #[tracing::instrument(level = "debug", skip(self, backup))]
+    pub async fn backup(&self, backup: &MetaBackup, keep: u64) -> Result<String, AnyError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let mut data = vec![];
+        let n = self
+            .sto
+            .export_state_machine(|line| {
+                data.extend_from_slice(line.as_bytes());
+                data.push(b'\n');
+                Ok(())
+            })
+            .await
+            .map_err(|e| AnyError::new(&e).add_context(|| "export state machine"))?;
+
+        info!("exported {} state machine records for backup", n);
+
+        let name = backup.write(now, data).await?;
+        backup.purge(keep as usize).await?;
+
+        Ok(name)
+    }
+
+    /// Spawn a task that takes a backup every `backup_interval` seconds when this node is the leader.
+    ///
+    /// It does nothing if `backup_uri` is not configured.
+    pub async fn spawn_backup(mn: Arc<Self>, conf: &MetaConfig) -> Result<(), MetaStartupError> {
+        if conf.backup_uri.is_empty() {
+            info!("backup_uri is empty, backup is disabled");
+            return Ok(());
+        }
+
+        let backup = MetaBackup::try_create(&conf.backup_uri)
+            .map_err(|e| MetaStartupError::InvalidConfig(e.to_string()))?;
+        let interval = Duration::from_secs(conf.backup_interval);
+        let keep = conf.backup_keep;
+
+        let meta_node = mn.clone();
+        let mut running_rx = mn.running_rx.clone();
+
+        let fut = async move {
+            loop {
+                select! {
+                    _ = running_rx.changed() => {
+                        info!("backup task quit");
+                        break;
+                    }
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                if meta_node.assume_leader().await.is_err() {
+                    continue;
+                }
+
+                match meta_node.backup(&backup, keep).await {
+                    Ok(name) => {
+                        info!("backup saved: {}", name);
+                    }
+                    Err(e) => {
+                        error!("fail to backup: {}", e);
+                    }
+                }
+            }
+
+            Ok::<(), AnyError>(())
+        };
+
+        let span = tracing::span!(tracing::Level::INFO, "backup");
+        let h = tokio::task::spawn(fut.instrument(span));
+
+        {
+            let mut jh = mn.join_handles.lock().await;
+            jh.push(h);
+        }
+
+        Ok(())
+    }
+}
@@ -14,18 +14,25 @@
 
 use async_trait::async_trait;
 use common_meta_api::KVApi;
+use common_meta_types::txn_op;
+use common_meta_types::AppError;
 use common_meta_types::AppliedState;
 use common_meta_types::Cmd;
 use common_meta_types::GetKVReply;
 use common_meta_types::GetKVReq;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::ListKVReq;
 use common_meta_types::LogEntry;
 use common_meta_types::MGetKVReply;
 use common_meta_types::MGetKVReq;
+use common_meta_types::Operation;
+use common_meta_types::TenantKeySpaceQuotaExceeded;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
+use common_meta_types::UnknownLease;
 use common_meta_types::UpsertKV;
 use common_meta_types::UpsertKVReply;
 use common_meta_types::UpsertKVReq;
@@ -33,6 +40,52 @@ use tracing::info;
 
 use crate::meta_service::MetaNode;
 
+impl MetaNode {
+    /// Check that no tenant whose key space the `ops` would grow has used up its quota.
+    ///
+    /// An op is a key with the size of the value to put, or `None` to delete it.
+    /// Ops that do not grow the usage, e.g. dropping a table or a database, are always allowed,
+    /// so that a tenant over quota can free its key space.
+    ///
+    /// The check is made against the local state machine before proposing, thus it is a soft limit:
+    /// concurrent writes may exceed the quota a little.
+    async fn check_tenant_quota<'a>(
+        &self,
+        ops: impl IntoIterator<Item = (&'a str, Option<usize>)>,
+    ) -> Result<(), KVAppError> {
+        let max_keys = self.sto.config().max_keys_per_tenant;
+        let max_bytes = self.sto.config().max_bytes_per_tenant;
+
+        if max_keys == 0 && max_bytes == 0 {
+            return Ok(());
+        }
+
+        // A key with a database id or a table id belongs to the tenant owning the database.
+        let usages = self
+            .get_state_machine()
+            .await
+            .get_tenant_usage_grown_by(ops)?;
+
+        for (tenant, usage) in usages {
+            if (max_keys > 0 && usage.keys >= max_keys)
+                || (max_bytes > 0 && usage.bytes >= max_bytes)
+            {
+                return Err(KVAppError::AppError(AppError::from(
+                    TenantKeySpaceQuotaExceeded::new(
+                        tenant,
+                        usage.keys,
+                        usage.bytes,
+                        max_keys,
+                        max_bytes,
+                    ),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Impl KVApi for MetaNode.
 ///
 /// Write through raft-log.
@@ -41,6 +94,13 @@ use crate::meta_service::MetaNode;
 #[async_trait]
 impl KVApi for MetaNode {
     async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, KVAppError> {
+        if let Operation::Update(v) = &act.value {
+            self.check_tenant_quota([(act.key.as_str(), Some(v.len()))])
+                .await?;
+        }
+
+        let lease_id = act.value_meta.as_ref().and_then(|m| m.lease);
+
         let ent = LogEntry {
             txid: None,
             time_ms: None,
@@ -55,6 +115,9 @@ impl KVApi for MetaNode {
 
         match rst {
             AppliedState::KV(x) => Ok(x),
+            AppliedState::Lease(None) => Err(KVAppError::AppError(AppError::UnknownLease(
+                UnknownLease::new(lease_id.unwrap_or_default(), "upsert_kv"),
+            ))),
             _ => {
                 unreachable!("expect type {}", "AppliedState::KV")
             }
@@ -94,9 +157,35 @@ impl KVApi for MetaNode {
         Ok(res)
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        let res = self.consistent_read(req).await?;
+
+        Ok(res)
+    }
+
     #[tracing::instrument(level = "debug", skip(self, txn))]
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         info!("MetaNode::transaction(): {}", txn);
+
+        // Only one of the branches is applied, each is checked on its own.
+        for ops in [&txn.if_then, &txn.else_then] {
+            let ops = ops.iter().filter_map(|op| match &op.request {
+                Some(txn_op::Request::Put(put)) => Some((put.key.as_str(), Some(put.value.len()))),
+                Some(txn_op::Request::Delete(del)) => Some((del.key.as_str(), None)),
+                _ => None,
+            });
+            self.check_tenant_quota(ops).await?;
+        }
+
+        let lease_id = txn
+            .if_then
+            .iter()
+            .chain(txn.else_then.iter())
+            .find_map(|op| match &op.request {
+                Some(txn_op::Request::Put(put)) => put.lease,
+                _ => None,
+            });
         let ent = LogEntry {
             txid: None,
             time_ms: None,
@@ -106,6 +195,9 @@ impl KVApi for MetaNode {
 
         match rst {
             AppliedState::TxnReply(x) => Ok(x),
+            AppliedState::Lease(None) => Err(KVAppError::AppError(AppError::UnknownLease(
+                UnknownLease::new(lease_id.unwrap_or_default(), "transaction"),
+            ))),
             _ => {
                 unreachable!("expect type {}", "AppliedState::transaction",)
             }
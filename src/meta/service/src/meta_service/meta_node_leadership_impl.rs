@@ -0,0 +1,116 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use anyerror::AnyError;
+use common_meta_types::ElectRequest;
+use common_meta_types::ForwardRequest;
+use common_meta_types::ForwardResponse;
+use common_meta_types::MetaAPIError;
+use common_meta_types::MetaOperationError;
+use common_meta_types::NodeId;
+use common_meta_types::TransferLeaderError;
+use common_meta_types::TransferLeaderRequest;
+use tracing::info;
+
+use crate::meta_service::ForwardRequestBody;
+use crate::meta_service::LeaveRequest;
+use crate::meta_service::MetaNode;
+
+/// The max time for a node to wait for the logs of the leader before starting an election.
+const ELECT_WAIT_LOG_TIMEOUT: Duration = Duration::from_millis(5_000);
+
+/// Leadership transfer and removal of a node, e.g., before maintenance.
+impl MetaNode {
+    /// Transfer the leadership to voter `to` and return the id of the new leader.
+    ///
+    /// If `to` is `None`, another voter is chosen by the leader.
+    /// The request is forwarded to the leader if this node is not the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn transfer_leader(&self, to: Option<NodeId>) -> Result<NodeId, MetaAPIError> {
+        let res = self
+            .handle_forwardable_request(ForwardRequest {
+                forward_to_leader: 1,
+                body: ForwardRequestBody::TransferLeader(TransferLeaderRequest { to }),
+            })
+            .await?;
+
+        match res {
+            ForwardResponse::TransferLeader(new_leader) => Ok(new_leader),
+            _ => Err(MetaAPIError::CanNotForward(AnyError::error(format!(
+                "expect ForwardResponse::TransferLeader, got: {:?}",
+                res
+            )))),
+        }
+    }
+
+    /// Start an election on this node once it has received the logs up to `req.last_log_index`.
+    ///
+    /// It is called on the target node when the leader transfers its leadership.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn elect(&self, req: ElectRequest) -> Result<(), MetaOperationError> {
+        let my_id = self.sto.id;
+
+        self.raft
+            .wait(Some(ELECT_WAIT_LOG_TIMEOUT))
+            .metrics(
+                |m| m.last_log_index.unwrap_or_default() >= req.last_log_index,
+                format!("receive logs up to {}", req.last_log_index),
+            )
+            .await
+            .map_err(|e| TransferLeaderError::new(Some(my_id), e))?;
+
+        info!(
+            "{} start election, last_log_index: {}",
+            my_id, req.last_log_index
+        );
+
+        self.raft
+            .trigger_elect()
+            .await
+            .map_err(|e| TransferLeaderError::new(Some(my_id), e))?;
+
+        Ok(())
+    }
+
+    /// Remove this node from the cluster safely.
+    ///
+    /// If this node is the leader, it transfers the leadership to another voter first.
+    /// Then it asks the leader to remove this node from the membership.
+    /// It returns after the membership without this node is committed.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn drain(&self) -> Result<(), MetaAPIError> {
+        let my_id = self.sto.id;
+
+        if let Ok(leader) = self.assume_leader().await {
+            let new_leader = leader
+                .transfer_leader(TransferLeaderRequest { to: None })
+                .await?;
+            info!(
+                "{} transferred leadership to {} before leaving",
+                my_id, new_leader
+            );
+        }
+
+        self.handle_forwardable_request(ForwardRequest {
+            forward_to_leader: 1,
+            body: ForwardRequestBody::Leave(LeaveRequest { node_id: my_id }),
+        })
+        .await?;
+
+        info!("{} left the cluster", my_id);
+        Ok(())
+    }
+}
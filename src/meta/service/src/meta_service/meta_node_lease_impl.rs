@@ -0,0 +1,133 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use common_base::base::tokio;
+use common_base::base::tokio::select;
+use common_meta_types::AppliedState;
+use common_meta_types::Cmd;
+use common_meta_types::Lease;
+use common_meta_types::LogEntry;
+use common_meta_types::MetaAPIError;
+use common_meta_types::SeqV;
+use tracing::error;
+use tracing::info;
+use tracing::Instrument;
+
+use crate::meta_service::MetaNode;
+
+/// The interval at which a leader checks for expired leases.
+const LEASE_REVOKE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Lease API of MetaNode.
+///
+/// Leases are granted, renewed and revoked through raft-log,
+/// thus the keys attached to a lease are deleted on every node at the same log.
+impl MetaNode {
+    /// Grant a lease that expires after `ttl_ms` unless it is kept alive.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn grant_lease(&self, ttl_ms: u64) -> Result<Lease, MetaAPIError> {
+        let res = self.write_lease_cmd(Cmd::GrantLease { ttl_ms }).await?;
+        Ok(res.expect("GrantLease always returns a lease"))
+    }
+
+    /// Push forward the expiration of a lease.
+    ///
+    /// It returns `None` if the lease does not exist or is already expired.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn keep_alive_lease(&self, id: u64) -> Result<Option<Lease>, MetaAPIError> {
+        self.write_lease_cmd(Cmd::KeepAliveLease { id }).await
+    }
+
+    /// Revoke a lease and delete all keys attached to it.
+    ///
+    /// It returns the revoked lease, or `None` if the lease does not exist.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn revoke_lease(&self, id: u64) -> Result<Option<Lease>, MetaAPIError> {
+        self.write_lease_cmd(Cmd::RevokeLease { id }).await
+    }
+
+    async fn write_lease_cmd(&self, cmd: Cmd) -> Result<Option<Lease>, MetaAPIError> {
+        let ent = LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd,
+        };
+        let rst = self.write(ent).await?;
+
+        match rst {
+            AppliedState::Lease(x) => Ok(x),
+            _ => {
+                unreachable!("expect type {}", "AppliedState::Lease")
+            }
+        }
+    }
+
+    /// Spawn a task that revokes expired leases when this node is the leader.
+    ///
+    /// Expired leases are also cleaned when applying any log,
+    /// this task makes sure the attached keys are deleted and watchers are notified in time
+    /// even if there is no other write.
+    pub async fn spawn_lease_revoker(mn: Arc<Self>) {
+        let meta_node = mn.clone();
+        let mut running_rx = mn.running_rx.clone();
+
+        let fut = async move {
+            loop {
+                select! {
+                    _ = running_rx.changed() => {
+                        info!("lease revoker quit");
+                        break;
+                    }
+                    _ = tokio::time::sleep(LEASE_REVOKE_INTERVAL) => {}
+                }
+
+                if meta_node.assume_leader().await.is_err() {
+                    continue;
+                }
+
+                let expired = {
+                    let sm = meta_node.get_state_machine().await;
+                    sm.list_expired_leases(SeqV::<()>::now_ms())
+                };
+                let expired = match expired {
+                    Ok(x) => x,
+                    Err(e) => {
+                        error!("fail to list expired leases: {}", e);
+                        continue;
+                    }
+                };
+
+                for id in expired {
+                    if let Err(e) = meta_node.revoke_lease(id).await {
+                        error!("fail to revoke expired lease {}: {}", id, e);
+                    }
+                }
+            }
+
+            Ok::<(), AnyError>(())
+        };
+
+        let span = tracing::span!(tracing::Level::INFO, "lease-revoker");
+        let h = tokio::task::spawn(fut.instrument(span));
+
+        {
+            let mut jh = mn.join_handles.lock().await;
+            jh.push(h);
+        }
+    }
+}
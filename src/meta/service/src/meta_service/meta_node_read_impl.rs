@@ -0,0 +1,242 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyerror::AnyError;
+use common_meta_api::KVApi;
+use common_meta_types::ForwardRequest;
+use common_meta_types::ForwardResponse;
+use common_meta_types::MetaAPIError;
+use common_meta_types::MetaDataReadError;
+use common_meta_types::ReadIndexReq;
+use common_meta_types::SeqV;
+use tracing::debug;
+
+use crate::meta_service::ForwardRequestBody;
+use crate::meta_service::MetaNode;
+use crate::metrics::record_phase;
+
+/// The max time to wait for the local state machine to catch up with the read index.
+const READ_INDEX_WAIT_TIMEOUT: Duration = Duration::from_millis(5_000);
+
+/// The max number of leader commit index observations to keep.
+///
+/// Observations are pruned once the local state machine applies up to them,
+/// this bound only matters if applying falls far behind replication.
+const MAX_LEADER_COMMITS: usize = 1024;
+
+/// Leader commit indexes seen by a non-leader node, in the order they are observed.
+///
+/// Each entry is `(leader_commit_index, observed_at_ms)`:
+/// the leader has committed up to `leader_commit_index` at the wall clock time `observed_at_ms`.
+#[derive(Debug, Default)]
+pub struct LeaderCommits {
+    observed: Mutex<VecDeque<(u64, u64)>>,
+}
+
+impl LeaderCommits {
+    /// Record that the leader has committed logs up to `commit_index` by the time `now_ms`.
+    pub fn observe(&self, commit_index: u64, now_ms: u64) {
+        let mut observed = self.observed.lock().unwrap();
+
+        if let Some(last) = observed.back_mut() {
+            if last.0 >= commit_index {
+                // No new commit since the last observation:
+                // the leader is still at the same commit index by now.
+                last.1 = std::cmp::max(last.1, now_ms);
+                return;
+            }
+        }
+
+        observed.push_back((commit_index, now_ms));
+        if observed.len() > MAX_LEADER_COMMITS {
+            observed.pop_front();
+        }
+    }
+
+    /// Returns the latest time at which the leader's committed state had been applied locally up to `last_applied`.
+    ///
+    /// Observations older than the returned one are discarded since they will never be used again.
+    pub fn fresh_as_of_ms(&self, last_applied: u64) -> Option<u64> {
+        let mut observed = self.observed.lock().unwrap();
+
+        let mut fresh = None;
+        while let Some(ob) = observed.front().copied() {
+            if ob.0 > last_applied {
+                break;
+            }
+            fresh = Some(ob);
+            observed.pop_front();
+        }
+
+        // Keep the latest applied observation for later queries.
+        if let Some(ob) = fresh {
+            observed.push_front(ob);
+        }
+
+        fresh.map(|(_, at_ms)| at_ms)
+    }
+}
+
+/// Reads served by a non-leader node, without forwarding them to the leader.
+impl MetaNode {
+    /// Record the commit index carried by a successful append-entries or heartbeat from the leader.
+    pub(crate) fn observe_leader_commit(&self, commit_index: u64) {
+        self.leader_commits
+            .observe(commit_index, SeqV::<()>::now_ms());
+    }
+
+    /// Returns the approximate time the applied data in this node lags behind the leader.
+    ///
+    /// It is the time since the leader had committed no more than what this node has applied,
+    /// or `None` if no such leader commit index has been observed since this node started.
+    ///
+    /// A node that keeps receiving heartbeats but can not apply logs in time becomes increasingly stale.
+    pub fn staleness(&self) -> Option<Duration> {
+        let last_applied = self
+            .raft
+            .metrics()
+            .borrow()
+            .last_applied
+            .map(|x| x.index)
+            .unwrap_or_default();
+
+        let at_ms = self.leader_commits.fresh_as_of_ms(last_applied)?;
+        Some(Duration::from_millis(
+            SeqV::<()>::now_ms().saturating_sub(at_ms),
+        ))
+    }
+
+    /// Serve a linearizable read on this node with ReadIndex.
+    ///
+    /// It gets the read index from the leader and waits for the local state machine to apply logs up to it.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn linearizable_read(
+        &self,
+        body: ForwardRequestBody,
+    ) -> Result<ForwardResponse, MetaAPIError> {
+        let res = self
+            .handle_forwardable_request(ForwardRequest {
+                forward_to_leader: 1,
+                body: ForwardRequestBody::ReadIndex(ReadIndexReq {}),
+            })
+            .await?;
+
+        let read_index: u64 = res.try_into().map_err(|e| {
+            MetaDataReadError::new(
+                "read_index",
+                "expect ForwardResponse::ReadIndex",
+                &AnyError::error(e),
+            )
+        })?;
+
+        debug!("got read_index: {}", read_index);
+
+        let start = Instant::now();
+        self.raft
+            .wait(Some(READ_INDEX_WAIT_TIMEOUT))
+            .metrics(
+                |m| m.last_applied.map(|x| x.index).unwrap_or_default() >= read_index,
+                format!("apply logs up to read index {}", read_index),
+            )
+            .await
+            .map_err(|e| MetaDataReadError::new("read_index", "wait for apply", &e))?;
+        record_phase("wait_apply", start.elapsed());
+
+        let res = self.read_local(body).await?;
+        Ok(res)
+    }
+
+    /// Serve a read on this node if its data lags behind the leader for no more than `max_staleness`.
+    ///
+    /// Otherwise, or if this node does not know how stale it is, the read is forwarded to the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn bounded_staleness_read(
+        &self,
+        body: ForwardRequestBody,
+        max_staleness: Duration,
+    ) -> Result<ForwardResponse, MetaAPIError> {
+        let staleness = self.staleness();
+        let local = match staleness {
+            Some(s) => s <= max_staleness,
+            None => false,
+        };
+
+        debug!(
+            "staleness: {:?}, max_staleness: {:?}, read locally: {}",
+            staleness, max_staleness, local
+        );
+
+        if local {
+            let res = self.read_local(body).await?;
+            return Ok(res);
+        }
+
+        self.handle_forwardable_request(ForwardRequest {
+            forward_to_leader: 1,
+            body,
+        })
+        .await
+    }
+
+    /// Read from the local state machine, regardless of whether this node is the leader
+    /// or how far it lags behind the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn read_local(
+        &self,
+        body: ForwardRequestBody,
+    ) -> Result<ForwardResponse, MetaDataReadError> {
+        let sm = self.get_state_machine().await;
+
+        match body {
+            ForwardRequestBody::GetKV(req) => {
+                let res = sm
+                    .get_kv(&req.key)
+                    .await
+                    .map_err(|meta_err| MetaDataReadError::new("get_kv", "", &meta_err))?;
+                Ok(ForwardResponse::GetKV(res))
+            }
+            ForwardRequestBody::MGetKV(req) => {
+                let res = sm
+                    .mget_kv(&req.keys)
+                    .await
+                    .map_err(|meta_err| MetaDataReadError::new("mget_kv", "", &meta_err))?;
+                Ok(ForwardResponse::MGetKV(res))
+            }
+            ForwardRequestBody::ListKV(req) => {
+                let res = sm
+                    .prefix_list_kv(&req.prefix)
+                    .await
+                    .map_err(|meta_err| MetaDataReadError::new("list_kv", "", &meta_err))?;
+                Ok(ForwardResponse::ListKV(res))
+            }
+            ForwardRequestBody::ListKVRange(req) => {
+                let res = sm
+                    .list_kv(req)
+                    .await
+                    .map_err(|meta_err| MetaDataReadError::new("list_kv_range", "", &meta_err))?;
+                Ok(ForwardResponse::ListKVRange(res))
+            }
+            _ => Err(MetaDataReadError::new(
+                "read_local",
+                format!("not a read request: {:?}", body),
+                &AnyError::error("invalid request"),
+            )),
+        }
+    }
+}
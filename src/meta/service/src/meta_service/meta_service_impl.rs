@@ -18,13 +18,16 @@
 use std::convert::TryInto;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::Instant;
 
 use anyerror::AnyError;
+use common_meta_sled_store::openraft::raft::AppendEntriesRequest;
 use common_meta_types::protobuf::raft_service_server::RaftService;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::AppliedState;
+use common_meta_types::Cmd;
 use common_meta_types::ForwardRequest;
 use common_meta_types::InvalidReply;
 use common_meta_types::LogEntry;
@@ -34,8 +37,11 @@ use tonic::codegen::futures_core::Stream;
 
 use crate::meta_service::ForwardRequestBody;
 use crate::meta_service::MetaNode;
+use crate::metrics::log_if_slow;
 use crate::metrics::raft_metrics;
 use crate::metrics::server_metrics;
+use crate::metrics::txn_keys;
+use crate::metrics::ReqKeys;
 
 pub type GrpcStream<T> =
     Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send + Sync + 'static>>;
@@ -116,7 +122,15 @@ impl RaftService for RaftServiceImpl {
         let forward_req: ForwardRequest = serde_json::from_str(&req.data)
             .map_err(|x| tonic::Status::invalid_argument(x.to_string()))?;
 
-        let res = self.meta_node.handle_forwardable_request(forward_req).await;
+        let threshold = self.meta_node.slow_request_threshold();
+        let (op, keys) = forward_req_summary(&forward_req.body, threshold);
+        let res = log_if_slow(
+            op,
+            keys,
+            threshold,
+            self.meta_node.handle_forwardable_request(forward_req),
+        )
+        .await;
 
         let raft_mes: RaftReply = res.into();
 
@@ -133,8 +147,9 @@ impl RaftService for RaftServiceImpl {
         self.incr_meta_metrics_recv_bytes_from_peer(&request);
         let req = request.into_inner();
 
-        let ae_req =
+        let ae_req: AppendEntriesRequest<LogEntry> =
             serde_json::from_str(&req.data).map_err(|x| tonic::Status::internal(x.to_string()))?;
+        let leader_commit = ae_req.leader_commit.map(|x| x.index);
 
         let resp = self
             .meta_node
@@ -142,6 +157,13 @@ impl RaftService for RaftServiceImpl {
             .append_entries(ae_req)
             .await
             .map_err(|x| tonic::Status::internal(x.to_string()))?;
+
+        if resp.success {
+            if let Some(commit_index) = leader_commit {
+                self.meta_node.observe_leader_commit(commit_index);
+            }
+        }
+
         let data = serde_json::to_string(&resp).expect("fail to serialize resp");
         let mes = RaftReply {
             data,
@@ -227,3 +249,42 @@ impl RaftService for RaftServiceImpl {
         Ok(tonic::Response::new(mes))
     }
 }
+
+/// Returns the type and the keys of a forwarded request, for the slow request log.
+fn forward_req_summary(body: &ForwardRequestBody, threshold: Duration) -> (&'static str, ReqKeys) {
+    let op = match body {
+        ForwardRequestBody::Write(ent) => match &ent.cmd {
+            Cmd::UpsertKV(_) => "Forward:UpsertKV",
+            Cmd::Transaction(_) => "Forward:Transaction",
+            Cmd::IncrSeq { .. } => "Forward:IncrSeq",
+            _ => "Forward:Write",
+        },
+        ForwardRequestBody::GetKV(_) => "Forward:GetKV",
+        ForwardRequestBody::MGetKV(_) => "Forward:MGetKV",
+        ForwardRequestBody::ListKV(_) => "Forward:ListKV",
+        ForwardRequestBody::ListKVRange(_) => "Forward:ListKVRange",
+        ForwardRequestBody::ReadIndex(_) => "Forward:ReadIndex",
+        _ => "Forward",
+    };
+
+    let keys = ReqKeys::collect(threshold, || match body {
+        ForwardRequestBody::Write(ent) => match &ent.cmd {
+            Cmd::UpsertKV(upsert) => vec![upsert.key.clone()],
+            Cmd::Transaction(txn) => txn_keys(txn),
+            Cmd::IncrSeq { key } => vec![key.clone()],
+            cmd => vec![cmd.to_string()],
+        },
+        ForwardRequestBody::GetKV(req) => vec![req.key.clone()],
+        ForwardRequestBody::MGetKV(req) => req.keys.clone(),
+        ForwardRequestBody::ListKV(req) => vec![req.prefix.clone()],
+        ForwardRequestBody::ListKVRange(req) => vec![format!(
+            "[{}, {})",
+            req.start,
+            req.end.as_deref().unwrap_or("")
+        )],
+        ForwardRequestBody::ReadIndex(_) => vec![],
+        _ => vec![format!("{:?}", body)],
+    });
+
+    (op, keys)
+}
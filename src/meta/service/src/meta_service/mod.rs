@@ -16,10 +16,15 @@ pub use common_meta_types::ForwardRequest;
 pub use common_meta_types::ForwardRequestBody;
 pub use common_meta_types::JoinRequest;
 pub use common_meta_types::LeaveRequest;
+pub use meta_node_read_impl::LeaderCommits;
 pub use meta_service_impl::RaftServiceImpl;
 pub use raftmeta::MetaNode;
 
 pub mod meta_leader;
+mod meta_node_backup_impl;
 mod meta_node_kv_api_impl;
+mod meta_node_leadership_impl;
+mod meta_node_lease_impl;
+mod meta_node_read_impl;
 pub mod meta_service_impl;
 pub mod raftmeta;
@@ -32,6 +32,7 @@ use common_grpc::ConnectionFactory;
 use common_grpc::DNSResolver;
 use common_meta_raft_store::config::RaftConfig;
 use common_meta_raft_store::sled_key_spaces::GenericKV;
+use common_meta_raft_store::state_machine::KeySpaceStats;
 use common_meta_raft_store::state_machine::StateMachine;
 use common_meta_sled_store::openraft;
 use common_meta_sled_store::openraft::error::AddLearnerError;
@@ -62,6 +63,7 @@ use common_meta_types::MetaOperationError;
 use common_meta_types::MetaStartupError;
 use common_meta_types::Node;
 use common_meta_types::NodeId;
+use common_meta_types::ReadConsistency;
 use itertools::Itertools;
 use openraft::Config;
 use openraft::LogId;
@@ -79,7 +81,10 @@ use crate::configs::Config as MetaConfig;
 use crate::meta_service::meta_leader::MetaLeader;
 use crate::meta_service::ForwardRequestBody;
 use crate::meta_service::JoinRequest;
+use crate::meta_service::LeaderCommits;
 use crate::meta_service::RaftServiceImpl;
+use crate::metrics::raft_metrics;
+use crate::metrics::record_phase;
 use crate::metrics::server_metrics;
 use crate::network::Network;
 use crate::store::RaftStore;
@@ -88,6 +93,7 @@ use crate::watcher::DispatcherSender;
 use crate::watcher::EventDispatcher;
 use crate::watcher::WatchEvent;
 use crate::watcher::WatcherSender;
+use crate::watcher::WATCHER_BUFFER_SIZE;
 use crate::Opened;
 
 #[derive(serde::Serialize)]
@@ -132,6 +138,8 @@ pub struct MetaNode {
     pub running_rx: watch::Receiver<()>,
     pub join_handles: Mutex<Vec<JoinHandle<Result<(), AnyError>>>>,
     pub joined_tasks: AtomicI32,
+    /// The commit indexes of the leader observed by this node, to estimate how stale the applied data is.
+    pub leader_commits: LeaderCommits,
 }
 
 impl Opened for MetaNode {
@@ -185,6 +193,7 @@ impl MetaNodeBuilder {
             running_rx: rx,
             join_handles: Mutex::new(Vec::new()),
             joined_tasks: AtomicI32::new(1),
+            leader_commits: LeaderCommits::default(),
         });
 
         if self.monitor_metrics {
@@ -192,6 +201,8 @@ impl MetaNodeBuilder {
             MetaNode::subscribe_metrics(mn.clone(), metrics_rx).await;
         }
 
+        MetaNode::spawn_lease_revoker(mn.clone()).await;
+
         let endpoint = if let Some(a) = self.endpoint.take() {
             a
         } else {
@@ -445,6 +456,7 @@ impl MetaNode {
 
         let fut = async move {
             let mut last_leader: Option<u64> = None;
+            let mut last_key_space_stats = KeySpaceStats::default();
 
             loop {
                 let changed = metrics_rx.changed().await;
@@ -481,6 +493,12 @@ impl MetaNode {
                         .map_err(|e| AnyError::new(&e))?,
                 );
 
+                // metrics about key space usage.
+
+                let stats = meta_node.get_state_machine().await.get_key_space_stats();
+                report_key_space_stats(&last_key_space_stats, &stats);
+                last_key_space_stats = stats;
+
                 last_leader = mm.current_leader;
             }
 
@@ -502,6 +520,7 @@ impl MetaNode {
     pub async fn start(config: &MetaConfig) -> Result<Arc<MetaNode>, MetaStartupError> {
         info!(?config, "start()");
         let mn = Self::do_start(config).await?;
+        MetaNode::spawn_backup(mn.clone(), config).await?;
         info!("Done starting MetaNode: {:?}", config);
         Ok(mn)
     }
@@ -642,6 +661,7 @@ impl MetaNode {
                     node_id: conf.id,
                     endpoint: advertise_endpoint.clone(),
                     grpc_api_addr: grpc_api_addr.clone(),
+                    learner: conf.learner,
                 }),
             };
 
@@ -880,12 +900,41 @@ impl MetaNode {
         ForwardResponse: TryInto<Reply>,
         <ForwardResponse as TryInto<Reply>>::Error: std::fmt::Display,
     {
-        let res = self
-            .handle_forwardable_request(ForwardRequest {
-                forward_to_leader: 1,
-                body: req.into(),
-            })
-            .await;
+        self.read_with(req, ReadConsistency::Leader).await
+    }
+
+    /// Serve a read with the specified consistency.
+    ///
+    /// - `Leader`: the read is forwarded to the leader.
+    /// - `Linearizable`: the read is served locally after applying logs up to the read index got from the leader.
+    /// - `BoundedStaleness`: the read is served locally if this node is not too stale, otherwise forwarded to the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn read_with<Request, Reply>(
+        &self,
+        req: Request,
+        consistency: ReadConsistency,
+    ) -> Result<Reply, MetaAPIError>
+    where
+        Request: Into<ForwardRequestBody> + Debug,
+        ForwardResponse: TryInto<Reply>,
+        <ForwardResponse as TryInto<Reply>>::Error: std::fmt::Display,
+    {
+        let body = req.into();
+
+        let res = match consistency {
+            ReadConsistency::Leader => {
+                self.handle_forwardable_request(ForwardRequest {
+                    forward_to_leader: 1,
+                    body,
+                })
+                .await
+            }
+            ReadConsistency::Linearizable => self.linearizable_read(body).await,
+            ReadConsistency::BoundedStaleness { max_staleness_ms } => {
+                self.bounded_staleness_read(body, Duration::from_millis(max_staleness_ms))
+                    .await
+            }
+        };
 
         match res {
             Err(e) => {
@@ -916,6 +965,12 @@ impl MetaNode {
 
         let forward = req.forward_to_leader;
 
+        // An election request is handled by the node it is sent to, not by the leader.
+        if let ForwardRequestBody::Elect(elect_req) = &req.body {
+            self.elect(elect_req.clone()).await?;
+            return Ok(ForwardResponse::Elect(()));
+        }
+
         let as_leader_res = self.assume_leader().await;
         debug!("as_leader: is_err: {}", as_leader_res.is_err());
 
@@ -953,7 +1008,11 @@ impl MetaNode {
         // Avoid infinite forward
         r2.decr_forward();
 
-        let res: ForwardResponse = self.forward_to(&leader_id, r2).await?;
+        let start = Instant::now();
+        let res = self.forward_to(&leader_id, r2).await;
+        record_phase("forward", start.elapsed());
+
+        let res: ForwardResponse = res?;
 
         Ok(res)
     }
@@ -1007,6 +1066,11 @@ impl MetaNode {
         self.sto.state_machine.read().await
     }
 
+    /// A request that takes longer than this is logged as a slow request. Zero disables it.
+    pub fn slow_request_threshold(&self) -> Duration {
+        Duration::from_millis(self.sto.config().slow_request_threshold_ms)
+    }
+
     /// Submit a write request to the known leader. Returns the response after applying the request.
     #[tracing::instrument(level = "debug", skip(self, req))]
     pub async fn write(&self, req: LogEntry) -> Result<AppliedState, MetaAPIError> {
@@ -1074,6 +1138,8 @@ impl MetaNode {
                 ))
             })?;
 
+        let req = common_tracing::inject_span_to_tonic_request(req);
+
         let resp = client.forward(req).await.map_err(|e| {
             MetaNetworkError::from(e)
                 .add_context(format!("target: {}, endpoint: {}", node_id, endpoint))
@@ -1085,10 +1151,61 @@ impl MetaNode {
         Ok(resp)
     }
 
-    pub fn add_watcher(&self, request: WatchRequest, tx: WatcherSender) {
-        // TODO: handle error?
+    /// Add a watcher.
+    ///
+    /// If `start_revision` is specified, changes in history since this revision are sent to the watcher first,
+    /// by a task of its own. An `OUT_OF_RANGE` error is sent if these changes have been removed from history.
+    pub async fn add_watcher(&self, request: WatchRequest, tx: WatcherSender) {
+        let start_revision = match request.start_revision {
+            None => {
+                // TODO: handle error?
+                let _ = self
+                    .dispatcher_tx
+                    .send(WatchEvent::AddWatcher((request, tx)));
+                return;
+            }
+            Some(x) => x,
+        };
+
+        // Only the tree handle is taken, the history is read without holding the state machine.
+        let sm_tree = self.sto.state_machine.read().await.sm_tree.clone();
+
+        // Register the watcher before reading history,
+        // so that a change that is not in the history read will be dispatched to it.
+        let (live_tx, live_rx) = mpsc::channel(WATCHER_BUFFER_SIZE);
         let _ = self
             .dispatcher_tx
-            .send(WatchEvent::AddWatcher((request, tx)));
+            .send(WatchEvent::AddWatcher((request.clone(), live_tx)));
+
+        tokio::spawn(EventDispatcher::replay_history(
+            sm_tree,
+            request,
+            start_revision,
+            live_rx,
+            tx,
+        ));
+    }
+}
+
+/// Report key space usage to metrics.
+///
+/// Tenants and prefixes that no longer have any key are reported as 0.
+fn report_key_space_stats(prev: &KeySpaceStats, curr: &KeySpaceStats) {
+    for tenant in prev.tenants.keys() {
+        if !curr.tenants.contains_key(tenant) {
+            raft_metrics::storage::set_tenant_key_space(tenant, 0, 0);
+        }
+    }
+    for (tenant, usage) in curr.tenants.iter() {
+        raft_metrics::storage::set_tenant_key_space(tenant, usage.keys, usage.bytes);
+    }
+
+    for prefix in prev.prefixes.keys() {
+        if !curr.prefixes.contains_key(prefix) {
+            raft_metrics::storage::set_prefix_key_space(prefix, 0, 0);
+        }
+    }
+    for (prefix, usage) in curr.prefixes.iter() {
+        raft_metrics::storage::set_prefix_key_space(prefix, usage.keys, usage.bytes);
     }
 }
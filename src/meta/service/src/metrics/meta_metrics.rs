@@ -182,6 +182,7 @@ pub mod raft_metrics {
 
     pub mod storage {
         use metrics::counter;
+        use metrics::gauge;
 
         macro_rules! key {
             ($key: literal) => {
@@ -196,6 +197,18 @@ pub mod raft_metrics {
                 counter!(key!("raft_store_read_failed"), 1, &labels);
             }
         }
+
+        pub fn set_tenant_key_space(tenant: &str, keys: u64, bytes: u64) {
+            let labels = [("tenant", tenant.to_string())];
+            gauge!(key!("tenant_keys"), keys as f64, &labels);
+            gauge!(key!("tenant_bytes"), bytes as f64, &labels);
+        }
+
+        pub fn set_prefix_key_space(prefix: &str, keys: u64, bytes: u64) {
+            let labels = [("prefix", prefix.to_string())];
+            gauge!(key!("prefix_keys"), keys as f64, &labels);
+            gauge!(key!("prefix_bytes"), bytes as f64, &labels);
+        }
     }
 }
 
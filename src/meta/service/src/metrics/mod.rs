@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod meta_metrics;
+mod request_timing;
 
 pub use meta_metrics::meta_metrics_to_prometheus_string;
 pub use meta_metrics::network_metrics;
@@ -20,3 +21,9 @@ pub use meta_metrics::raft_metrics;
 pub use meta_metrics::server_metrics;
 pub(crate) use meta_metrics::ProposalPending;
 pub(crate) use meta_metrics::RequestInFlight;
+pub use request_timing::log_if_slow;
+pub use request_timing::record_phase;
+pub use request_timing::txn_keys;
+pub use request_timing::LogTimings;
+pub use request_timing::Phases;
+pub use request_timing::ReqKeys;
@@ -0,0 +1,199 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Track the time spent in every phase of serving a request and log slow requests.
+//!
+//! A request handler runs the request with [`log_if_slow`], which installs a task-local [`Phases`].
+//! Code in the request path, e.g., forwarding and raft write, records the time it spends with [`record_phase`].
+//! Raft-log append and apply run in the raft-core task, thus they are recorded by log index in [`LogTimings`]
+//! and are collected by the leader when the write is applied.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::base::tokio;
+use common_meta_types::txn_op;
+use common_meta_types::TxnRequest;
+use tracing::warn;
+
+/// Max number of recent logs to keep timings for.
+///
+/// Timings of a log are removed when the leader collects them.
+/// Logs that are never collected, e.g., on a follower, are evicted when this limit is reached.
+const MAX_LOG_TIMINGS: usize = 1024;
+
+tokio::task_local! {
+    static PHASES: RefCell<Phases>;
+}
+
+/// Time spent in every phase of serving a request, in the order a phase is first seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Phases {
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Phases {
+    /// Add time spent in a phase. Time of a phase that is already present is accumulated.
+    pub fn add(&mut self, phase: &'static str, d: Duration) {
+        for (name, total) in self.phases.iter_mut() {
+            if *name == phase {
+                *total += d;
+                return;
+            }
+        }
+        self.phases.push((phase, d));
+    }
+
+    pub fn get(&self, phase: &str) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|(name, _)| *name == phase)
+            .map(|(_, d)| *d)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, d)| *d).sum()
+    }
+}
+
+impl Display for Phases {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.phases.is_empty() {
+            return write!(f, "-");
+        }
+
+        for (i, (name, d)) in self.phases.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {:?}", name, d)?;
+        }
+        Ok(())
+    }
+}
+
+/// Time spent appending and applying recent raft-logs, indexed by log index.
+#[derive(Debug, Default)]
+pub struct LogTimings {
+    logs: BTreeMap<u64, Phases>,
+}
+
+impl LogTimings {
+    pub fn record(&mut self, index: u64, phase: &'static str, d: Duration) {
+        self.logs.entry(index).or_default().add(phase, d);
+
+        while self.logs.len() > MAX_LOG_TIMINGS {
+            let first = *self.logs.keys().next().unwrap();
+            self.logs.remove(&first);
+        }
+    }
+
+    /// Remove and return the timings of the log at `index`.
+    pub fn take(&mut self, index: u64) -> Phases {
+        self.logs.remove(&index).unwrap_or_default()
+    }
+}
+
+/// Record the time spent in a phase of the request served by the current task.
+///
+/// It does nothing if the current task is not serving a request with [`log_if_slow`].
+pub fn record_phase(phase: &'static str, d: Duration) {
+    let _ = PHASES.try_with(|p| p.borrow_mut().add(phase, d));
+}
+
+/// The keys a request accesses, for the slow request log.
+///
+/// Keys are collected only if the slow request log is enabled,
+/// and are formatted only when a slow request is logged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReqKeys {
+    keys: Vec<String>,
+}
+
+impl ReqKeys {
+    /// Collect the keys with `f` if requests slower than `threshold` are logged.
+    pub fn collect(threshold: Duration, f: impl FnOnce() -> Vec<String>) -> Self {
+        if threshold.is_zero() {
+            return Self::default();
+        }
+        Self { keys: f() }
+    }
+}
+
+impl Display for ReqKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.keys.join(","))
+    }
+}
+
+/// Serve a request by running `fut`, and log it with the phase breakdown if it takes longer than `threshold`.
+///
+/// `op` is the type of the request and `keys` are the keys it accesses.
+/// A zero `threshold` disables the slow request log.
+pub async fn log_if_slow<F>(op: &str, keys: ReqKeys, threshold: Duration, fut: F) -> F::Output
+where F: Future {
+    let start = Instant::now();
+
+    let (output, phases) = PHASES
+        .scope(RefCell::new(Phases::default()), async move {
+            let output = fut.await;
+            let phases = PHASES.with(|p| p.take());
+            (output, phases)
+        })
+        .await;
+
+    let elapsed = start.elapsed();
+
+    if !threshold.is_zero() && elapsed >= threshold {
+        warn!(
+            "slow request: op: {}, key: {}, takes: {:?}, phases: {}",
+            op, keys, elapsed, phases
+        );
+    }
+
+    output
+}
+
+/// Returns the keys a transaction accesses, including the keys in conditions, for the slow request log.
+///
+/// A key is listed once even if it is accessed by more than one condition or operation.
+pub fn txn_keys(txn: &TxnRequest) -> Vec<String> {
+    let cond_keys = txn.condition.iter().map(|c| c.key.as_str());
+
+    let op_keys = txn
+        .if_then
+        .iter()
+        .chain(txn.else_then.iter())
+        .filter_map(|op| match &op.request {
+            Some(txn_op::Request::Get(r)) => Some(r.key.as_str()),
+            Some(txn_op::Request::Put(r)) => Some(r.key.as_str()),
+            Some(txn_op::Request::Delete(r)) => Some(r.key.as_str()),
+            Some(txn_op::Request::DeleteByPrefix(r)) => Some(r.prefix.as_str()),
+            None => None,
+        });
+
+    let mut seen = BTreeSet::new();
+    cond_keys
+        .chain(op_keys)
+        .filter(|k| seen.insert(*k))
+        .map(|k| k.to_string())
+        .collect()
+}
@@ -16,6 +16,9 @@ use std::fmt::Debug;
 use std::io::Cursor;
 use std::io::ErrorKind;
 use std::ops::RangeBounds;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use anyerror::AnyError;
 use common_base::base::tokio::sync::RwLock;
@@ -54,10 +57,12 @@ use openraft::StorageError;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
+use tracing::Instrument;
 
 use crate::export::vec_kv_to_json;
 use crate::metrics::raft_metrics;
 use crate::metrics::server_metrics;
+use crate::metrics::LogTimings;
 use crate::store::ToStorageError;
 use crate::Opened;
 
@@ -105,6 +110,9 @@ pub struct RaftStoreBare {
 
     /// The current snapshot.
     pub current_snapshot: RwLock<Option<Snapshot>>,
+
+    /// Time spent appending and applying recent logs, for the leader to build the phase breakdown of a write.
+    pub log_timings: Mutex<LogTimings>,
 }
 
 impl AsRef<RaftStoreBare> for RaftStoreBare {
@@ -162,9 +170,30 @@ impl RaftStoreBare {
             log,
             state_machine: sm,
             current_snapshot,
+            log_timings: Mutex::new(LogTimings::default()),
         })
     }
 
+    pub fn config(&self) -> &RaftConfig {
+        &self.config
+    }
+
+    /// Record the time spent in a phase of appending or applying logs.
+    ///
+    /// A span event is emitted for every log, so that the phases of a log can be found by its log id.
+    fn record_log_timings(
+        &self,
+        log_ids: impl IntoIterator<Item = LogId>,
+        phase: &'static str,
+        elapsed: Duration,
+    ) {
+        let mut timings = self.log_timings.lock().unwrap();
+        for log_id in log_ids {
+            debug!(%log_id, phase, ?elapsed, "raft-log phase done");
+            timings.record(log_id.index, phase, elapsed);
+        }
+    }
+
     /// Get a handle to the state machine for testing purposes.
     pub async fn get_state_machine(&self) -> RwLockWriteGuard<'_, StateMachine> {
         self.state_machine.write().await
@@ -275,6 +304,8 @@ impl RaftStoreBare {
 
         info!("flushed tree, no_kvs: {}", nkvs);
 
+        new_sm.rebuild_key_space_stats()?;
+
         // Start to use the new tree, the old can be cleaned.
         self.raft_state
             .write_state_machine_id(&(new_sm_id, sm_id))
@@ -324,22 +355,40 @@ impl RaftStoreBare {
             res.push(line);
         }
 
-        let name = self.state_machine.write().await.sm_tree.name.clone();
-        let sm_kvs = self
-            .state_machine
-            .write()
-            .await
-            .sm_tree
-            .export()
-            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?;
+        self.export_state_machine(|line| {
+            res.push(line);
+            Ok(())
+        })
+        .await?;
+
+        Ok(res)
+    }
 
-        for kv in sm_kvs.iter() {
-            let line = vec_kv_to_json(&name, kv)
+    /// Export all records in the state machine, in the same format as [`RaftStoreBare::export`].
+    ///
+    /// Every record is passed to `f` once it is encoded, without loading the entire state machine.
+    /// The state machine is read-locked during exporting thus the output is a consistent snapshot,
+    /// and logs are applied after it is done.
+    /// It returns the number of exported records.
+    #[tracing::instrument(level = "debug", skip(self, f))]
+    pub async fn export_state_machine(
+        &self,
+        mut f: impl FnMut(String) -> Result<(), std::io::Error>,
+    ) -> Result<u64, std::io::Error> {
+        let sm = self.state_machine.read().await;
+
+        let name = sm.sm_tree.name.clone();
+        let mut n = 0;
+
+        for rkv in sm.sm_tree.tree.iter() {
+            let (k, v) = rkv?;
+            let line = vec_kv_to_json(&name, &[k.to_vec(), v.to_vec()])
                 .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))?;
-            res.push(line);
+            f(line)?;
+            n += 1;
         }
 
-        Ok(res)
+        Ok(n)
     }
 }
 
@@ -438,12 +487,34 @@ impl RaftStorage<LogEntry, AppliedState> for RaftStoreBare {
         }
 
         let entries = entries.iter().map(|x| (*x).clone()).collect::<Vec<_>>();
-        match self
+
+        // Append and flush are timed separately:
+        // the latter is usually the major cost and is shared by all of the entries.
+        let start = Instant::now();
+        let res = self
             .log
-            .append(&entries)
+            .append_unflushed(&entries)
+            .instrument(tracing::debug_span!("append", n = entries.len()))
             .await
-            .map_to_sto_err(ErrorSubject::Logs, ErrorVerb::Write)
-        {
+            .map_to_sto_err(ErrorSubject::Logs, ErrorVerb::Write);
+        self.record_log_timings(entries.iter().map(|x| x.log_id), "append", start.elapsed());
+
+        let res = match res {
+            Ok(_) => {
+                let start = Instant::now();
+                let res = self
+                    .log
+                    .flush()
+                    .instrument(tracing::debug_span!("flush", n = entries.len()))
+                    .await
+                    .map_to_sto_err(ErrorSubject::Logs, ErrorVerb::Write);
+                self.record_log_timings(entries.iter().map(|x| x.log_id), "flush", start.elapsed());
+                res
+            }
+            Err(err) => Err(err),
+        };
+
+        match res {
             Err(err) => {
                 raft_metrics::storage::incr_raft_storage_fail("append_to_log", true);
                 Err(err)
@@ -465,11 +536,16 @@ impl RaftStorage<LogEntry, AppliedState> for RaftStoreBare {
 
         let sm = self.state_machine.write().await;
         for entry in entries {
-            let r = match sm
+            let start = Instant::now();
+            let apply_res = sm
                 .apply(entry)
+                .instrument(tracing::debug_span!("apply", log_id = %entry.log_id))
                 .await
-                .map_to_sto_err(ErrorSubject::Apply(entry.log_id), ErrorVerb::Write)
-            {
+                .map_to_sto_err(ErrorSubject::Apply(entry.log_id), ErrorVerb::Write);
+
+            self.record_log_timings([entry.log_id], "apply", start.elapsed());
+
+            let r = match apply_res {
                 Err(err) => {
                     raft_metrics::storage::incr_raft_storage_fail("apply_to_state_machine", true);
                     return Err(err);
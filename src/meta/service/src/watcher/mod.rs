@@ -17,7 +17,9 @@ mod watcher_stream;
 
 pub(crate) use watcher_manager::DispatcherSender;
 pub(crate) use watcher_manager::EventDispatcher;
+pub use watcher_manager::RevisionedChange;
 pub use watcher_manager::WatchEvent;
 pub use watcher_manager::WatcherId;
 pub use watcher_manager::WatcherSender;
+pub use watcher_manager::WATCHER_BUFFER_SIZE;
 pub use watcher_stream::WatcherStream;
@@ -15,10 +15,14 @@ use core::ops::Range;
 
 use common_base::base::tokio;
 use common_base::base::tokio::sync::mpsc;
+use common_base::base::tokio::sync::mpsc::error::TrySendError;
+use common_base::base::tokio::sync::mpsc::Receiver;
 use common_base::base::tokio::sync::mpsc::Sender;
 use common_base::rangemap::RangeMap;
 use common_base::rangemap::RangeMapKey;
+use common_meta_raft_store::sled_key_spaces::ChangeHistory;
 use common_meta_raft_store::state_machine::StateMachineSubscriber;
+use common_meta_sled_store::SledTree;
 use common_meta_types::protobuf::watch_request::FilterType;
 use common_meta_types::protobuf::Event;
 use common_meta_types::protobuf::WatchRequest;
@@ -43,10 +47,22 @@ pub type WatcherSender = Sender<Result<WatchResponse, Status>>;
 #[derive(Clone, Debug)]
 pub(crate) struct DispatcherSender(pub(crate) mpsc::UnboundedSender<WatchEvent>);
 
+/// A kv change and its revision in the change history.
+pub type RevisionedChange = (u64, Change<Vec<u8>, String>);
+
+/// Size of the buffer of new changes for a watcher.
+///
+/// The dispatcher never waits for a watcher, a watcher that falls behind by more changes
+/// is closed, and its client resumes watching from the last revision it received.
+pub const WATCHER_BUFFER_SIZE: usize = 1024;
+
+/// Max number of history changes read at a time when replaying.
+const WATCH_REPLAY_CHUNK_SIZE: usize = 256;
+
 #[derive(Clone)]
 pub enum WatchEvent {
     AddWatcher((WatchRequest, WatcherSender)),
-    KVChange(Change<Vec<u8>, String>),
+    KVChange(RevisionedChange),
 }
 
 /// Receives events from event sources, dispatches them to interested watchers.
@@ -81,10 +97,10 @@ impl EventDispatcher {
             if let Some(event) = self.event_rx.recv().await {
                 match event {
                     WatchEvent::AddWatcher((req, tx)) => {
-                        self.add_watcher(req, tx).await;
+                        self.add_watcher(req, tx);
                     }
-                    WatchEvent::KVChange(kv_change) => {
-                        self.dispatch_event(kv_change).await;
+                    WatchEvent::KVChange((revision, kv_change)) => {
+                        self.dispatch_event(revision, kv_change);
                     }
                 }
             } else {
@@ -102,47 +118,42 @@ impl EventDispatcher {
     }
 
     /// Dispatch a kv change event to interested watchers.
-    async fn dispatch_event(&mut self, change: Change<Vec<u8>, String>) {
+    ///
+    /// A watcher whose buffer is full is closed instead of being waited for,
+    /// so that a slow watcher does not hold up the others.
+    fn dispatch_event(&mut self, revision: u64, change: Change<Vec<u8>, String>) {
         let k = change.ident.as_ref().unwrap();
         let set = self.watcher_range_map.get_by_point(k);
         if set.is_empty() {
             return;
         }
 
-        let current = change.result;
-        let prev = change.prev;
-
-        let is_delete_event = current.is_none();
         let mut remove_range_keys: Vec<RangeMapKey<String, WatcherId>> = vec![];
 
         for range_key_stream in set.iter() {
             let filter = range_key_stream.1.filter_type;
 
-            // filter out event
-            if (filter == FilterType::Delete && !is_delete_event)
-                || (filter == FilterType::Update && is_delete_event)
-            {
+            if !Self::is_accepted(filter, &change) {
                 continue;
             }
 
             let watcher_id = range_key_stream.0.key;
             let stream = range_key_stream.1;
             assert_eq!(stream.id, watcher_id);
-            let resp = WatchResponse {
-                event: Some(Event {
-                    key: k.to_string(),
-                    current: current.clone().map(PbSeqV::from),
-                    prev: prev.clone().map(PbSeqV::from),
-                }),
-            };
+            let resp = Self::build_response(revision, &change);
 
             network_metrics::incr_sent_bytes(resp.encoded_len() as u64);
 
-            if let Err(err) = stream.send(resp).await {
-                warn!(
-                    "close watcher stream {:?} cause send err: {:?}",
-                    watcher_id, err
-                );
+            if let Err(err) = stream.try_send(resp) {
+                match err {
+                    TrySendError::Full(_) => warn!(
+                        "close watcher stream {:?} cause it lags behind more than {} changes",
+                        watcher_id, WATCHER_BUFFER_SIZE
+                    ),
+                    TrySendError::Closed(_) => {
+                        warn!("close watcher stream {:?} cause it is closed", watcher_id)
+                    }
+                }
                 remove_range_keys.push(RangeMapKey::new(
                     stream.key.clone()..stream.key_end.clone(),
                     watcher_id,
@@ -157,7 +168,7 @@ impl EventDispatcher {
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    pub async fn add_watcher(&mut self, create: WatchRequest, tx: WatcherSender) {
+    pub fn add_watcher(&mut self, create: WatchRequest, tx: WatcherSender) {
         info!("create_watcher_stream: {:?}", create);
 
         let range = match EventDispatcher::get_range_key(create.key.clone(), &create.key_end) {
@@ -183,6 +194,136 @@ impl EventDispatcher {
         server_metrics::incr_watchers(1);
     }
 
+    /// Send the changes in history since `start_revision` to a watcher, then forward new changes to it.
+    ///
+    /// It runs in a task of its own, so that replaying a long history does not block the dispatcher.
+    /// The watcher is registered with the sender of `live_rx` before the history is read,
+    /// thus a new change is received from `live_rx` if it is not read from history,
+    /// and it is skipped if it has already been replayed.
+    pub(crate) async fn replay_history(
+        sm_tree: SledTree,
+        create: WatchRequest,
+        start_revision: u64,
+        mut live_rx: Receiver<Result<WatchResponse, Status>>,
+        tx: WatcherSender,
+    ) {
+        let range = match EventDispatcher::get_range_key(create.key.clone(), &create.key_end) {
+            Ok(range) => range,
+            Err(_) => return,
+        };
+        let filter: FilterType = create.filter_type();
+
+        // Revision starts from 1.
+        let mut next_revision = start_revision.max(1);
+
+        loop {
+            let changes = match Self::read_history(&sm_tree, next_revision) {
+                Ok(changes) => changes,
+                Err(status) => {
+                    let _ = tx.send(Err(status)).await;
+                    return;
+                }
+            };
+
+            if changes.is_empty() {
+                break;
+            }
+
+            for (revision, change) in changes {
+                next_revision = revision + 1;
+
+                let k = change.ident.as_ref().unwrap();
+                if !Self::range_contains(&range, k) || !Self::is_accepted(filter, &change) {
+                    continue;
+                }
+
+                let resp = Self::build_response(revision, &change);
+                network_metrics::incr_sent_bytes(resp.encoded_len() as u64);
+
+                if let Err(err) = tx.send(Ok(resp)).await {
+                    warn!(
+                        "stop replaying history for watcher {:?}, cause send err: {:?}",
+                        create, err
+                    );
+                    return;
+                }
+            }
+        }
+
+        while let Some(resp) = live_rx.recv().await {
+            if let Ok(r) = &resp {
+                if r.revision < next_revision {
+                    continue;
+                }
+            }
+
+            if tx.send(resp).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Read at most [`WATCH_REPLAY_CHUNK_SIZE`] changes from history, starting from `start_revision`.
+    ///
+    /// It returns an `OUT_OF_RANGE` error if the change at `start_revision` has been removed from history.
+    fn read_history(
+        sm_tree: &SledTree,
+        start_revision: u64,
+    ) -> Result<Vec<RevisionedChange>, Status> {
+        let history = sm_tree.key_space::<ChangeHistory>();
+        let it = history
+            .range(start_revision..)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let mut changes = Vec::with_capacity(WATCH_REPLAY_CHUNK_SIZE);
+        for item in it.take(WATCH_REPLAY_CHUNK_SIZE) {
+            let (revision, change) = item
+                .map_err(|e| Status::internal(e.to_string()))?
+                .kv()
+                .map_err(|e| Status::internal(e.to_string()))?;
+
+            // Revisions are continuous, a gap means the changes before have been removed.
+            if changes.is_empty() && revision != start_revision {
+                return Err(Status::out_of_range(format!(
+                    "watch start_revision {} is compacted, the oldest revision in history is {}",
+                    start_revision, revision
+                )));
+            }
+
+            changes.push((revision, change));
+        }
+
+        Ok(changes)
+    }
+
+    /// Returns if a change passes the filter of a watcher.
+    fn is_accepted(filter: FilterType, change: &Change<Vec<u8>, String>) -> bool {
+        let is_delete_event = change.result.is_none();
+
+        !((filter == FilterType::Delete && !is_delete_event)
+            || (filter == FilterType::Update && is_delete_event))
+    }
+
+    /// Returns if `key` is in a watched range, the same way [`RangeMap::get_by_point`] finds a range.
+    fn range_contains(range: &Range<String>, key: &str) -> bool {
+        if range.start == range.end {
+            range.start == key
+        } else {
+            range.start.as_str() <= key && key < range.end.as_str()
+        }
+    }
+
+    fn build_response(revision: u64, change: &Change<Vec<u8>, String>) -> WatchResponse {
+        WatchResponse {
+            event: Some(Event {
+                key: change.ident.clone().unwrap(),
+                current: change.result.clone().map(PbSeqV::from),
+                prev: change.prev.clone().map(PbSeqV::from),
+            }),
+            revision,
+        }
+    }
+
     fn get_range_key(key: String, key_end: &Option<String>) -> Result<Range<String>, bool> {
         match key_end {
             Some(key_end) => {
@@ -197,7 +338,7 @@ impl EventDispatcher {
 }
 
 impl StateMachineSubscriber for DispatcherSender {
-    fn kv_changed(&self, change: Change<Vec<u8>, String>) {
-        let _ = self.0.send(WatchEvent::KVChange(change));
+    fn kv_changed(&self, revision: u64, change: Change<Vec<u8>, String>) {
+        let _ = self.0.send(WatchEvent::KVChange((revision, change)));
     }
 }
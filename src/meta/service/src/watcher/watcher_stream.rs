@@ -12,7 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
-use common_base::base::tokio::sync::mpsc::error::SendError;
+use common_base::base::tokio::sync::mpsc::error::TrySendError;
 use common_meta_types::protobuf::watch_request::FilterType;
 use common_meta_types::protobuf::WatchResponse;
 use tonic::Status;
@@ -49,10 +49,10 @@ impl WatcherStream {
         }
     }
 
-    pub async fn send(
+    pub fn try_send(
         &self,
         resp: WatchResponse,
-    ) -> Result<(), SendError<Result<WatchResponse, Status>>> {
-        self.tx.send(Ok(resp)).await
+    ) -> Result<(), TrySendError<Result<WatchResponse, Status>>> {
+        self.tx.try_send(Ok(resp))
     }
 }
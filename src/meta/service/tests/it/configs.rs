@@ -48,6 +48,9 @@ admin_tls_server_key = "admin tls key"
 grpc_api_address = "127.0.0.1:10000"
 grpc_tls_server_cert = "grpc server cert"
 grpc_tls_server_key = "grpc server key"
+backup_uri = "fs:///tmp/backup"
+backup_interval = 600
+backup_keep = 3
 
 [raft_config]
 config_id = "raft config id"
@@ -61,6 +64,10 @@ heartbeat_interval = 2000
 install_snapshot_timeout = 3000
 single = false
 join = ["j1", "j2"]
+learner = true
+max_keys_per_tenant = 1000000
+max_bytes_per_tenant = 1073741824
+slow_request_threshold_ms = 500
 id = 20
 sled_tree_prefix = "sled_foo"
 cluster_name = "foo_cluster"
@@ -77,6 +84,9 @@ cluster_name = "foo_cluster"
         assert_eq!(cfg.grpc_api_address, "127.0.0.1:10000");
         assert_eq!(cfg.grpc_tls_server_cert, "grpc server cert");
         assert_eq!(cfg.grpc_tls_server_key, "grpc server key");
+        assert_eq!(cfg.backup_uri, "fs:///tmp/backup");
+        assert_eq!(cfg.backup_interval, 600);
+        assert_eq!(cfg.backup_keep, 3);
         assert_eq!(cfg.raft_config.config_id, "raft config id");
         assert_eq!(cfg.raft_config.raft_listen_host, "127.0.0.1");
         assert_eq!(cfg.raft_config.raft_api_port, 11000);
@@ -87,6 +97,10 @@ cluster_name = "foo_cluster"
         assert_eq!(cfg.raft_config.install_snapshot_timeout, 3000);
         assert!(!cfg.raft_config.single);
         assert_eq!(cfg.raft_config.join, vec!["j1", "j2"]);
+        assert!(cfg.raft_config.learner);
+        assert_eq!(cfg.raft_config.max_keys_per_tenant, 1000000);
+        assert_eq!(cfg.raft_config.max_bytes_per_tenant, 1073741824);
+        assert_eq!(cfg.raft_config.slow_request_threshold_ms, 500);
         assert_eq!(cfg.raft_config.id, 20);
         assert_eq!(cfg.raft_config.sled_tree_prefix, "sled_foo");
         assert_eq!(cfg.raft_config.cluster_name, "foo_cluster");
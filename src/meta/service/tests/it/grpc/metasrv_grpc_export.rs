@@ -87,7 +87,11 @@ async fn test_export() -> anyhow::Result<()> {
         r#"["test-29000-state_machine/0",{"GenericKV":{"key":"bar","value":{"seq":2,"meta":null,"data":[98,97,114]}}}]"#,
         r#"["test-29000-state_machine/0",{"GenericKV":{"key":"foo","value":{"seq":1,"meta":null,"data":[102,111,111]}}}]"#,
         r#"["test-29000-state_machine/0",{"GenericKV":{"key":"wow","value":{"seq":3,"meta":null,"data":[119,111,119]}}}]"#,
+        r#"["test-29000-state_machine/0",{"Sequences":{"key":"change-history","value":3}}]"#,
         r#"["test-29000-state_machine/0",{"Sequences":{"key":"generic-kv","value":3}}]"#,
+        r#"["test-29000-state_machine/0",{"ChangeHistory":{"key":1,"value":{"ident":"foo","prev":null,"result":{"seq":1,"meta":null,"data":[102,111,111]}}}}]"#,
+        r#"["test-29000-state_machine/0",{"ChangeHistory":{"key":2,"value":{"ident":"bar","prev":null,"result":{"seq":2,"meta":null,"data":[98,97,114]}}}}]"#,
+        r#"["test-29000-state_machine/0",{"ChangeHistory":{"key":3,"value":{"ident":"wow","prev":null,"result":{"seq":3,"meta":null,"data":[119,111,119]}}}}]"#,
     ];
 
     // The addresses are built from random number.
@@ -0,0 +1,65 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_client::MetaGrpcClient;
+use common_meta_types::protobuf::LeaseKeepAliveRequest;
+use databend_meta::init_meta_ut;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_lease_grant_keep_alive_revoke() -> anyhow::Result<()> {
+    // - Start a metasrv server.
+    // - Grant a lease, keep it alive, then revoke it.
+
+    let (_tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = MetaGrpcClient::try_create(
+        vec![addr],
+        "root",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        None,
+    )?;
+
+    let granted = client.lease_grant(10_000).await?;
+    assert_eq!(10_000, granted.ttl_ms);
+
+    let reqs = futures::stream::iter(vec![
+        LeaseKeepAliveRequest { id: granted.id },
+        LeaseKeepAliveRequest {
+            id: granted.id + 100,
+        },
+    ]);
+    let mut strm = client.lease_keep_alive(reqs).await?;
+
+    let resp = strm.next().await.unwrap()?;
+    assert_eq!(granted.id, resp.id);
+    assert_eq!(10_000, resp.ttl_ms);
+
+    let resp = strm.next().await.unwrap()?;
+    assert_eq!(0, resp.ttl_ms, "nonexistent lease");
+
+    let revoked = client.lease_revoke(granted.id).await?;
+    assert!(revoked.found);
+
+    let revoked = client.lease_revoke(granted.id).await?;
+    assert!(!revoked.found);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test reading from followers with a specified consistency through grpc client.
+
+use common_base::base::tokio;
+use common_meta_api::KVApi;
+use common_meta_types::GetKVReq;
+use common_meta_types::ListKVReq;
+use common_meta_types::MGetKVReq;
+use common_meta_types::ReadConsistency;
+use common_meta_types::UpsertKVReq;
+use databend_meta::init_meta_ut;
+
+use crate::tests::service::start_metasrv_cluster;
+
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_metasrv_read_with_consistency() -> anyhow::Result<()> {
+    // - Start a cluster of 3 and write to a follower.
+    // - Read from the other follower with every consistency.
+
+    let tcs = start_metasrv_cluster(&[0, 1, 2]).await?;
+
+    let follower1 = tcs[1].grpc_client().await?;
+    let follower2 = tcs[2].grpc_client().await?;
+
+    follower1
+        .upsert_kv(UpsertKVReq::update("read-with-a", b"a"))
+        .await?;
+
+    // A bounded-staleness read goes last:
+    // the linearizable read before it ensures follower2 has applied the write.
+    for consistency in [
+        ReadConsistency::Leader,
+        ReadConsistency::Linearizable,
+        ReadConsistency::BoundedStaleness {
+            max_staleness_ms: 0,
+        },
+    ] {
+        let got = follower2
+            .read_with(
+                GetKVReq {
+                    key: "read-with-a".to_string(),
+                },
+                consistency,
+            )
+            .await?;
+        assert_eq!(
+            Some(b"a".to_vec()),
+            got.map(|x| x.data),
+            "{:?}",
+            consistency
+        );
+
+        let got = follower2
+            .read_with(
+                MGetKVReq {
+                    keys: vec!["read-with-a".to_string(), "read-with-b".to_string()],
+                },
+                consistency,
+            )
+            .await?;
+        assert_eq!(
+            vec![Some(b"a".to_vec()), None],
+            got.into_iter()
+                .map(|x| x.map(|x| x.data))
+                .collect::<Vec<_>>(),
+            "{:?}",
+            consistency
+        );
+
+        let got = follower2
+            .read_with(
+                ListKVReq {
+                    prefix: "read-with-".to_string(),
+                },
+                consistency,
+            )
+            .await?;
+        assert_eq!(
+            vec!["read-with-a".to_string()],
+            got.into_iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            "{:?}",
+            consistency
+        );
+    }
+
+    Ok(())
+}
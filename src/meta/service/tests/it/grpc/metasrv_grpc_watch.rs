@@ -40,6 +40,7 @@ use common_meta_types::TxnOp;
 use common_meta_types::TxnPutRequest;
 use common_meta_types::UpsertKVReq;
 use databend_meta::init_meta_ut;
+use futures::StreamExt;
 use tracing::info;
 
 async fn test_watch_main(
@@ -130,6 +131,7 @@ async fn test_watch() -> common_exception::Result<()> {
             key: "a".to_string(),
             key_end: Some("z".to_string()),
             filter_type: FilterType::All.into(),
+            start_revision: None,
         };
 
         let key_a = "a".to_string();
@@ -203,6 +205,7 @@ async fn test_watch() -> common_exception::Result<()> {
             key_end: None,
             // filter only delete events
             filter_type: FilterType::Delete.into(),
+            start_revision: None,
         };
 
         let key = key_str.to_string();
@@ -282,6 +285,7 @@ async fn test_watch() -> common_exception::Result<()> {
             key: start,
             key_end: Some(end),
             filter_type: FilterType::All.into(),
+            start_revision: None,
         };
 
         let conditions = vec![TxnCondition {
@@ -297,6 +301,7 @@ async fn test_watch() -> common_exception::Result<()> {
                     value: txn_val.clone(),
                     prev_value: true,
                     expire_at: None,
+                    lease: None,
                 })),
             },
             TxnOp {
@@ -386,6 +391,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                     value: b(&k),
                     prev_value: true,
                     expire_at: Some(now - 10),
+                    lease: None,
                 })),
             });
         }
@@ -398,6 +404,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                 value: b("w_b1"),
                 prev_value: true,
                 expire_at: Some(now - 1),
+                lease: None,
             })),
         });
         txn.if_then.push(TxnOp {
@@ -406,6 +413,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                 value: b("w_b2"),
                 prev_value: true,
                 expire_at: Some(now - 1),
+                lease: None,
             })),
         });
         txn.if_then.push(TxnOp {
@@ -414,6 +422,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                 value: b("w_b3a"),
                 prev_value: true,
                 expire_at: Some(now - 1),
+                lease: None,
             })),
         });
         txn.if_then.push(TxnOp {
@@ -422,6 +431,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                 value: b("w_b3b"),
                 prev_value: true,
                 expire_at: Some(now - 1),
+                lease: None,
             })),
         });
 
@@ -433,6 +443,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
         key: start,
         key_end: Some(end),
         filter_type: FilterType::All.into(),
+        start_revision: None,
     };
 
     let txn = TxnRequest {
@@ -444,6 +455,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
                     value: b("w_b1_override"),
                     prev_value: true,
                     expire_at: None,
+                    lease: None,
                 })),
             },
             TxnOp {
@@ -501,6 +513,90 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_watch_from_revision() -> anyhow::Result<()> {
+    // - Write some data.
+    // - Watch from a revision in history, changes since then are received before new changes.
+    // - Resumable watch receives the same changes.
+
+    let (_tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = make_client(&addr)?;
+
+    info!("--- prepare data");
+    {
+        for (k, v) in [("r_a", "a"), ("x", "x"), ("r_b", "b")] {
+            client
+                .upsert_kv(UpsertKVReq::new(
+                    k,
+                    MatchSeq::Any,
+                    Operation::Update(b(v)),
+                    None,
+                ))
+                .await?;
+        }
+    }
+
+    let (start, end) = get_start_and_end_of_prefix("r_")?;
+
+    info!("--- watch from revision 2");
+    {
+        let watch = WatchRequest {
+            key: start.clone(),
+            key_end: Some(end.clone()),
+            filter_type: FilterType::All.into(),
+            start_revision: Some(2),
+        };
+
+        let mut client_stream = client.request(watch).await?;
+
+        let msg = client_stream.message().await?.unwrap();
+        assert_eq!(3, msg.revision);
+        assert_eq!(Some(add_event("r_b", 3, "b")), msg.event);
+
+        client
+            .upsert_kv(UpsertKVReq::new(
+                "r_a",
+                MatchSeq::Any,
+                Operation::Update(b("a2")),
+                None,
+            ))
+            .await?;
+
+        let msg = client_stream.message().await?.unwrap();
+        assert_eq!(4, msg.revision);
+        assert_eq!(
+            Some(Event {
+                key: s("r_a"),
+                current: pb_seqv(4, "a2"),
+                prev: pb_seqv(1, "a"),
+            }),
+            msg.event
+        );
+    }
+
+    info!("--- resumable watch from revision 1");
+    {
+        let watch = WatchRequest {
+            key: start,
+            key_end: Some(end),
+            filter_type: FilterType::All.into(),
+            start_revision: Some(1),
+        };
+
+        let mut strm = client.watch_resumable(watch).boxed();
+
+        let mut revisions = vec![];
+        for _ in 0..3 {
+            let msg = strm.next().await.unwrap()?;
+            revisions.push(msg.revision);
+        }
+        assert_eq!(vec![1, 3, 4], revisions);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn prefix_of_string_test() -> common_exception::Result<()> {
     assert_eq!("b".to_string(), prefix_of_string("a")?);
@@ -0,0 +1,139 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_meta_api::KVApi;
+use common_meta_types::UpsertKVReq;
+use databend_meta::backup::parse_backup_uri;
+use databend_meta::backup::redact_backup_uri;
+use databend_meta::backup::MetaBackup;
+use databend_meta::init_meta_ut;
+use futures::TryStreamExt;
+use tempfile::tempdir;
+use tracing::info;
+
+use crate::tests::meta_node::start_meta_node_leader;
+
+#[test]
+fn test_parse_backup_uri() -> anyhow::Result<()> {
+    let p = parse_backup_uri("fs:///tmp/backup")?;
+    assert_eq!("fs | root=/tmp/backup", p.to_string());
+
+    let p = parse_backup_uri(
+        "s3://bkt/meta/backup?endpoint_url=http://127.0.0.1:9900&region=us-east-2",
+    )?;
+    assert_eq!(
+        "s3 | bucket=bkt,root=/meta/backup,endpoint=http://127.0.0.1:9900",
+        p.to_string()
+    );
+
+    assert!(parse_backup_uri("s3://bkt/meta?foo=bar").is_err());
+    assert!(parse_backup_uri("hdfs://foo/bar").is_err());
+    assert!(parse_backup_uri("no-scheme").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_redact_backup_uri() -> anyhow::Result<()> {
+    assert_eq!(
+        "s3://bkt/meta?region=us-east-2&access_key_id=ak&secret_access_key=***",
+        redact_backup_uri("s3://bkt/meta?region=us-east-2&access_key_id=ak&secret_access_key=sk")
+    );
+    assert_eq!("fs:///tmp/backup", redact_backup_uri("fs:///tmp/backup"));
+
+    let err = parse_backup_uri("s3://bkt/meta?secret_access_key=sk&foo=bar").unwrap_err();
+    assert!(!err.to_string().contains("=sk"), "{}", err);
+
+    Ok(())
+}
+
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_backup_read_purge() -> anyhow::Result<()> {
+    let d = tempdir()?;
+    let uri = format!("fs://{}/", d.path().display());
+    let backup = MetaBackup::try_create(&uri)?;
+
+    info!("--- no backup");
+    {
+        assert_eq!(Vec::<u64>::new(), backup.list().await?);
+        assert!(backup.read(None).await.is_err());
+    }
+
+    for t in [10, 20, 30] {
+        backup
+            .write(t, format!("line-{}\n\n", t).into_bytes())
+            .await?;
+    }
+
+    info!("--- read the latest or the latest before a time");
+    {
+        assert_eq!(vec![10, 20, 30], backup.list().await?);
+
+        let (t, lines) = backup.read(None).await?;
+        assert_eq!(30, t);
+        let lines: Vec<String> = lines.try_collect().await?;
+        assert_eq!(vec!["line-30".to_string()], lines);
+
+        let (t, _) = backup.read(Some(25)).await?;
+        assert_eq!(20, t);
+
+        let (t, _) = backup.read(Some(20)).await?;
+        assert_eq!(20, t);
+
+        assert!(backup.read(Some(5)).await.is_err());
+    }
+
+    info!("--- purge keeps the latest ones");
+    {
+        backup.purge(2).await?;
+        assert_eq!(vec![20, 30], backup.list().await?);
+
+        backup.purge(5).await?;
+        assert_eq!(vec![20, 30], backup.list().await?);
+    }
+
+    Ok(())
+}
+
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_backup() -> anyhow::Result<()> {
+    let d = tempdir()?;
+    let uri = format!("fs://{}/", d.path().display());
+    let backup = MetaBackup::try_create(&uri)?;
+
+    let (_id, tc) = start_meta_node_leader().await?;
+    let mn = tc.meta_node();
+
+    mn.upsert_kv(UpsertKVReq::update("foo", b"bar")).await?;
+
+    info!("--- backup contains state machine records");
+    {
+        let name = mn.backup(&backup, 1).await?;
+        info!("backup: {}", name);
+
+        let (_t, lines) = backup.read(None).await?;
+        let lines: Vec<String> = lines.try_collect().await?;
+
+        let kv_line = lines
+            .iter()
+            .find(|l| l.contains("\"GenericKV\"") && l.contains("\"foo\""));
+        assert!(kv_line.is_some(), "found foo in {:?}", lines);
+
+        let sm_only = lines.iter().all(|l| l.contains("state_machine/"));
+        assert!(sm_only, "only state machine is backed up: {:?}", lines);
+    }
+
+    Ok(())
+}
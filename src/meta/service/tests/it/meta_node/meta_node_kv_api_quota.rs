@@ -0,0 +1,230 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_meta_api::serialize_struct;
+use common_meta_api::KVApi;
+use common_meta_api::SchemaApi;
+use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::DBIdTableName;
+use common_meta_app::schema::DatabaseMeta;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_app::schema::DropDatabaseReq;
+use common_meta_app::schema::DropTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_meta_sled_store::openraft::State;
+use common_meta_types::txn_op;
+use common_meta_types::AppError;
+use common_meta_types::KVAppError;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnPutRequest;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKVReq;
+use databend_meta::init_meta_ut;
+use databend_meta::meta_service::MetaNode;
+use tracing::info;
+
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+
+/// A tenant that has used up its key space quota can not put more keys, but can still delete keys.
+/// Keys with the id of a database or a table of the tenant are limited too.
+/// Other tenants and keys without a known tenant are not affected.
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_kv_api_tenant_quota() -> anyhow::Result<()> {
+    info!("--- bring up a leader with max_keys_per_tenant=3");
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.raft_config.max_keys_per_tenant = 3;
+
+    let mn = MetaNode::boot(&tc.config).await?;
+    tc.meta_node = Some(mn.clone());
+
+    mn.raft
+        .wait(timeout())
+        .state(State::Leader, "leader started")
+        .await?;
+
+    info!("--- tenant t1 puts keys until the quota is used up");
+    {
+        mn.upsert_kv(UpsertKVReq::update("__fd_users/t1/u1", b"a"))
+            .await?;
+
+        // database 1 and table 2 belong to t1
+        let db_name = DatabaseNameIdent {
+            tenant: "t1".to_string(),
+            db_name: "db1".to_string(),
+        };
+        mn.transaction(put_txn(
+            "__fd_database_id_to_name/1",
+            serialize_struct(&db_name)?,
+        ))
+        .await?;
+
+        let table_name = DBIdTableName {
+            db_id: 1,
+            table_name: "tb1".to_string(),
+        };
+        mn.upsert_kv(UpsertKVReq::update(
+            "__fd_table_id_to_name/2",
+            &serialize_struct(&table_name)?,
+        ))
+        .await?;
+
+        let res = mn
+            .upsert_kv(UpsertKVReq::update("__fd_users/t1/u3", b"a"))
+            .await;
+        assert_quota_exceeded(res.map(|_| ()));
+
+        let res = mn
+            .transaction(put_txn("__fd_users/t1/u3", b"a".to_vec()))
+            .await;
+        assert_quota_exceeded(res.map(|_| ()));
+
+        let got = mn.get_kv("__fd_users/t1/u3").await?;
+        assert!(got.is_none());
+    }
+
+    info!("--- keys with the id of a database or a table of t1 are limited");
+    {
+        for key in [
+            "__fd_database_by_id/1",
+            "__fd_table/1/tb1",
+            "__fd_table_by_id/2",
+            "__fd_table_copied_files/2/f1",
+        ] {
+            let res = mn.upsert_kv(UpsertKVReq::update(key, b"a")).await;
+            assert_quota_exceeded(res.map(|_| ()));
+        }
+    }
+
+    info!("--- other tenants and keys without a known tenant are not limited");
+    {
+        mn.upsert_kv(UpsertKVReq::update("__fd_users/t2/u1", b"a"))
+            .await?;
+        mn.upsert_kv(UpsertKVReq::update("__fd_table_by_id/3", b"a"))
+            .await?;
+        mn.upsert_kv(UpsertKVReq::update("foo", b"a")).await?;
+    }
+
+    info!("--- tenant t1 can delete keys and then put again");
+    {
+        mn.upsert_kv(UpsertKVReq::delete("__fd_users/t1/u1"))
+            .await?;
+        mn.upsert_kv(UpsertKVReq::update("__fd_table_by_id/2", b"a"))
+            .await?;
+
+        let got = mn.get_kv("__fd_table_by_id/2").await?;
+        assert!(got.is_some());
+    }
+
+    Ok(())
+}
+
+/// A tenant over its key space quota can not create tables, but can still drop tables and databases,
+/// which do not grow its usage.
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_kv_api_tenant_quota_drop() -> anyhow::Result<()> {
+    info!("--- bring up a leader with max_keys_per_tenant=5");
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.raft_config.max_keys_per_tenant = 5;
+
+    let mn = MetaNode::boot(&tc.config).await?;
+    tc.meta_node = Some(mn.clone());
+
+    mn.raft
+        .wait(timeout())
+        .state(State::Leader, "leader started")
+        .await?;
+
+    let db_name = DatabaseNameIdent {
+        tenant: "t1".to_string(),
+        db_name: "db1".to_string(),
+    };
+    let table_name = |name: &str| TableNameIdent {
+        tenant: "t1".to_string(),
+        db_name: "db1".to_string(),
+        table_name: name.to_string(),
+    };
+
+    info!("--- tenant t1 creates a database and a table, which exceeds the quota");
+    {
+        mn.create_database(CreateDatabaseReq {
+            if_not_exists: false,
+            name_ident: db_name.clone(),
+            meta: DatabaseMeta::default(),
+        })
+        .await?;
+
+        mn.create_table(CreateTableReq {
+            if_not_exists: false,
+            name_ident: table_name("tb1"),
+            table_meta: TableMeta::default(),
+        })
+        .await?;
+
+        let res = mn
+            .create_table(CreateTableReq {
+                if_not_exists: false,
+                name_ident: table_name("tb2"),
+                table_meta: TableMeta::default(),
+            })
+            .await;
+        assert_quota_exceeded(res.map(|_| ()));
+    }
+
+    info!("--- tenant t1 can still drop the table and the database");
+    {
+        mn.drop_table(DropTableReq {
+            if_exists: false,
+            name_ident: table_name("tb1"),
+        })
+        .await?;
+
+        mn.drop_database(DropDatabaseReq {
+            if_exists: false,
+            name_ident: db_name.clone(),
+        })
+        .await?;
+
+        let got = mn.get_kv("__fd_database/t1/db1").await?;
+        assert!(got.is_none());
+    }
+
+    Ok(())
+}
+
+fn put_txn(key: &str, value: Vec<u8>) -> TxnRequest {
+    TxnRequest {
+        condition: vec![],
+        if_then: vec![TxnOp {
+            request: Some(txn_op::Request::Put(TxnPutRequest {
+                key: key.to_string(),
+                value,
+                prev_value: false,
+                expire_at: None,
+                lease: None,
+            })),
+        }],
+        else_then: vec![],
+    }
+}
+
+fn assert_quota_exceeded(res: Result<(), KVAppError>) {
+    match res {
+        Err(KVAppError::AppError(AppError::TenantKeySpaceQuotaExceeded(_))) => {}
+        other => panic!("expect TenantKeySpaceQuotaExceeded, got: {:?}", other),
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_meta_api::KVApi;
+use common_meta_types::UpsertKVReq;
+use databend_meta::init_meta_ut;
+use databend_meta::meta_service::MetaNode;
+use maplit::btreeset;
+use tracing::info;
+
+use crate::tests::meta_node::start_meta_node_cluster;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_transfer_leader() -> anyhow::Result<()> {
+    // - Start a leader and 2 followers.
+    // - Transfer the leadership to node-2 by sending the request to follower node-1.
+    // - Every node sees node-2 as the leader and node-2 serves writes.
+    // - Transferring to a non-voter fails.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![3]).await?;
+    let all = test_context_nodes(&tcs);
+
+    info!("--- transfer leadership from node-0 to node-2 via node-1");
+    {
+        let new_leader = all[1].transfer_leader(Some(2)).await?;
+        assert_eq!(2, new_leader);
+
+        for mn in all.iter() {
+            mn.raft
+                .wait(timeout())
+                .current_leader(2, format!("node-{} sees leader node-2", mn.sto.id))
+                .await?;
+        }
+    }
+
+    info!("--- write to the new leader");
+    {
+        all[2]
+            .upsert_kv(UpsertKVReq::update("after-transfer", b"v"))
+            .await?;
+    }
+
+    info!("--- transfer leadership to the leader itself is a no-op");
+    {
+        let new_leader = all[0].transfer_leader(Some(2)).await?;
+        assert_eq!(2, new_leader);
+    }
+
+    info!("--- can not transfer leadership to a non-voter");
+    {
+        let res = all[0].transfer_leader(Some(3)).await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_drain_leader() -> anyhow::Result<()> {
+    // - Start a leader and 2 followers.
+    // - Drain the leader node-0.
+    // - The leadership is moved to another voter and node-0 is removed from the cluster.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    info!("--- drain leader node-0");
+    all[0].drain().await?;
+
+    let new_leader_id = all[1].get_leader().await?.unwrap();
+    assert_ne!(0, new_leader_id);
+
+    let new_leader = all[new_leader_id as usize].clone();
+
+    info!("--- node-0 is no longer a voter");
+    {
+        new_leader
+            .raft
+            .wait(timeout())
+            .members(btreeset! {1,2}, "node-0 left the cluster")
+            .await?;
+    }
+
+    info!("--- check nodes list: node-0 is removed");
+    {
+        let nodes = new_leader.get_nodes().await?;
+        assert_eq!(
+            vec!["1", "2"],
+            nodes.iter().map(|x| x.name.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    Ok(())
+}
+
+fn test_context_nodes(tcs: &[MetaSrvTestContext]) -> Vec<Arc<MetaNode>> {
+    tcs.iter().map(|tc| tc.meta_node()).collect::<Vec<_>>()
+}
@@ -190,6 +190,85 @@ async fn test_meta_node_join() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_join_as_learner() -> anyhow::Result<()> {
+    // - Bring up a cluster
+    // - Join a new node as a learner.
+    // - The learner replicates logs but is not added to the membership.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+    let mut all = test_context_nodes(&tcs);
+
+    info!("--- bring up learner 1");
+
+    let node_id = 1;
+    let tc1 = MetaSrvTestContext::new(node_id);
+    let mn1 = MetaNode::open_create(&tc1.config.raft_config, None, Some(())).await?;
+
+    info!("--- join node-1 as a learner");
+
+    let leader = all[0].clone();
+    let admin_req = ForwardRequest {
+        forward_to_leader: 0,
+        body: ForwardRequestBody::Join(JoinRequest {
+            node_id,
+            endpoint: tc1.config.raft_config.raft_api_addr().await?,
+            grpc_api_addr: tc1.config.grpc_api_address.clone(),
+            learner: true,
+        }),
+    };
+    leader.handle_forwardable_request(admin_req).await?;
+
+    all.push(mn1.clone());
+
+    info!("--- write a log and check it is replicated to the learner");
+
+    let key = "learner-replicated";
+    leader
+        .write(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd: Cmd::UpsertKV(UpsertKV::update(key, b"v")),
+        })
+        .await?;
+    let last_applied = leader.raft.metrics().borrow().last_applied.index();
+
+    mn1.raft
+        .wait(timeout())
+        .log(last_applied, "learner replicated logs")
+        .await?;
+    mn1.raft
+        .wait(timeout())
+        .state(State::Learner, "node-1 is a learner")
+        .await?;
+
+    let got = mn1.get_state_machine().await.get_kv(key).await?;
+    assert_eq!(Some(b"v".to_vec()), got.map(|x| x.data));
+
+    info!("--- check node-1 is not a voter");
+
+    for mn in all.iter() {
+        mn.raft
+            .wait(timeout())
+            .members(btreeset! {0}, format!("node-{} membership", mn.sto.id))
+            .await?;
+    }
+
+    let status = leader.get_status().await?;
+    let non_voters = status
+        .non_voters
+        .iter()
+        .map(|n| n.name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![node_id.to_string()], non_voters);
+
+    for mn in all.drain(..) {
+        mn.stop().await?;
+    }
+
+    Ok(())
+}
+
 #[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
 async fn test_meta_node_leave() -> anyhow::Result<()> {
     // - Bring up a cluster
@@ -643,6 +722,7 @@ fn join_req(
             node_id,
             endpoint,
             grpc_api_addr,
+            learner: false,
         }),
     }
 }
@@ -0,0 +1,181 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_meta_api::KVApi;
+use common_meta_sled_store::openraft::LogIdOptionExt;
+use common_meta_types::GetKVReply;
+use common_meta_types::GetKVReq;
+use common_meta_types::ReadConsistency;
+use common_meta_types::UpsertKVReq;
+use databend_meta::init_meta_ut;
+use databend_meta::meta_service::LeaderCommits;
+use databend_meta::meta_service::MetaNode;
+use maplit::btreeset;
+use tracing::info;
+
+use crate::tests::meta_node::start_meta_node_cluster;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_linearizable_read() -> anyhow::Result<()> {
+    // - Start a leader, 2 followers and a non-voter;
+    // - Write to the leader.
+    // - A linearizable read on every node sees the write at once.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![3]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let leader_id = all[0].raft.metrics().borrow().current_leader.unwrap();
+    let leader = all[leader_id as usize].clone();
+
+    for i in 0..3 {
+        let key = format!("linearizable-{}", i);
+        let value = format!("v{}", i);
+
+        info!("--- write {} on leader", key);
+        leader
+            .upsert_kv(UpsertKVReq::update(&key, value.as_bytes()))
+            .await?;
+
+        for mn in all.iter() {
+            info!("--- linearizable read {} on node {}", key, mn.sto.id);
+
+            let got: GetKVReply = mn
+                .read_with(GetKVReq { key: key.clone() }, ReadConsistency::Linearizable)
+                .await?;
+            assert_eq!(
+                Some(value.as_bytes().to_vec()),
+                got.map(|x| x.data),
+                "node {}",
+                mn.sto.id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_bounded_staleness_read() -> anyhow::Result<()> {
+    // - Start a leader, a follower and a non-voter;
+    // - Write to the leader and wait for it to be applied on every node.
+    // - A bounded-staleness read is served locally if the node is not too stale.
+    // - Otherwise it is forwarded to the leader.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1], btreeset![2]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let leader_id = all[0].raft.metrics().borrow().current_leader.unwrap();
+    let leader = all[leader_id as usize].clone();
+
+    let key = "bounded-staleness";
+    leader.upsert_kv(UpsertKVReq::update(key, b"v1")).await?;
+
+    let last_applied = leader.raft.metrics().borrow().last_applied.index();
+
+    for mn in all.iter() {
+        mn.raft
+            .wait(timeout())
+            .metrics(
+                |m| m.last_applied.index() >= last_applied,
+                format!("node {} applied {:?}", mn.sto.id, last_applied),
+            )
+            .await?;
+    }
+
+    info!("--- a non-leader knows its staleness after being replicated to by the leader");
+    for mn in all.iter() {
+        if mn.sto.id == leader_id {
+            continue;
+        }
+        assert!(mn.staleness().is_some(), "node {}", mn.sto.id);
+    }
+
+    info!("--- read locally with a large max staleness");
+    for mn in all.iter() {
+        let got: GetKVReply = mn
+            .read_with(
+                GetKVReq {
+                    key: key.to_string(),
+                },
+                ReadConsistency::BoundedStaleness {
+                    max_staleness_ms: 60_000,
+                },
+            )
+            .await?;
+        assert_eq!(
+            Some(b"v1".to_vec()),
+            got.map(|x| x.data),
+            "node {}",
+            mn.sto.id
+        );
+    }
+
+    info!("--- a read that can not be served locally is forwarded to the leader");
+    for mn in all.iter() {
+        let got: GetKVReply = mn
+            .read_with(
+                GetKVReq {
+                    key: key.to_string(),
+                },
+                ReadConsistency::BoundedStaleness {
+                    max_staleness_ms: 0,
+                },
+            )
+            .await?;
+        assert_eq!(
+            Some(b"v1".to_vec()),
+            got.map(|x| x.data),
+            "node {}",
+            mn.sto.id
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_commits_fresh_as_of() {
+    let lc = LeaderCommits::default();
+
+    assert_eq!(None, lc.fresh_as_of_ms(10), "nothing observed");
+
+    lc.observe(5, 100);
+    lc.observe(8, 200);
+    // A heartbeat without new commit refreshes the time.
+    lc.observe(8, 300);
+    lc.observe(12, 400);
+
+    assert_eq!(
+        None,
+        lc.fresh_as_of_ms(4),
+        "applied less than any leader commit"
+    );
+    assert_eq!(Some(100), lc.fresh_as_of_ms(5));
+    assert_eq!(Some(300), lc.fresh_as_of_ms(11));
+    assert_eq!(
+        Some(300),
+        lc.fresh_as_of_ms(11),
+        "the latest applied observation is kept"
+    );
+    assert_eq!(Some(400), lc.fresh_as_of_ms(12));
+}
+
+fn test_context_nodes(tcs: &[MetaSrvTestContext]) -> Vec<Arc<MetaNode>> {
+    tcs.iter().map(|tc| tc.meta_node()).collect::<Vec<_>>()
+}
@@ -0,0 +1,151 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_types::txn_condition::Target;
+use common_meta_types::txn_op;
+use common_meta_types::Cmd;
+use common_meta_types::ConditionResult;
+use common_meta_types::LogEntry;
+use common_meta_types::TxnCondition;
+use common_meta_types::TxnGetRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnPutRequest;
+use common_meta_types::TxnRequest;
+use common_meta_types::UpsertKV;
+use databend_meta::init_meta_ut;
+use databend_meta::metrics::txn_keys;
+use databend_meta::metrics::LogTimings;
+use databend_meta::metrics::Phases;
+use databend_meta::metrics::ReqKeys;
+use tracing::info;
+
+use crate::tests::meta_node::start_meta_node_leader;
+use crate::tests::meta_node::start_meta_node_non_voter;
+use crate::tests::meta_node::timeout;
+
+#[test]
+fn test_phases() -> anyhow::Result<()> {
+    let ms = Duration::from_millis;
+
+    let mut phases = Phases::default();
+    assert_eq!("-", phases.to_string());
+
+    phases.add("forward", ms(3));
+    phases.add("append", ms(1));
+    phases.add("forward", ms(2));
+
+    assert_eq!(Some(ms(5)), phases.get("forward"));
+    assert_eq!(Some(ms(1)), phases.get("append"));
+    assert_eq!(None, phases.get("apply"));
+    assert_eq!(ms(6), phases.total());
+    assert_eq!("forward: 5ms, append: 1ms", phases.to_string());
+
+    let mut timings = LogTimings::default();
+    timings.record(1, "append", ms(1));
+    timings.record(1, "apply", ms(2));
+
+    let got = timings.take(1);
+    assert_eq!(Some(ms(1)), got.get("append"));
+    assert_eq!(Some(ms(2)), got.get("apply"));
+    assert_eq!(Phases::default(), timings.take(1), "taken");
+
+    Ok(())
+}
+
+/// The leader collects the time spent appending and applying a log when the write proposing it is done.
+#[test]
+fn test_txn_keys() -> anyhow::Result<()> {
+    let txn = TxnRequest {
+        condition: vec![TxnCondition {
+            key: "a".to_string(),
+            expected: ConditionResult::Eq as i32,
+            target: Some(Target::Seq(1)),
+        }],
+        if_then: vec![put("a"), put("b")],
+        else_then: vec![TxnOp {
+            request: Some(txn_op::Request::Get(TxnGetRequest {
+                key: "c".to_string(),
+            })),
+        }],
+    };
+
+    assert_eq!(vec!["a", "b", "c"], txn_keys(&txn));
+
+    let keys = ReqKeys::collect(Duration::from_millis(1), || txn_keys(&txn));
+    assert_eq!("a,b,c", keys.to_string());
+
+    let keys = ReqKeys::collect(Duration::ZERO, || panic!("not collected if disabled"));
+    assert_eq!("", keys.to_string());
+
+    Ok(())
+}
+
+fn put(key: &str) -> TxnOp {
+    TxnOp {
+        request: Some(txn_op::Request::Put(TxnPutRequest {
+            key: key.to_string(),
+            value: b"v".to_vec(),
+            prev_value: false,
+            expire_at: None,
+            lease: None,
+        })),
+    }
+}
+
+/// A learner keeps the timings of the logs it appended and applied.
+#[async_entry::test(worker_threads = 5, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_meta_node_log_timings() -> anyhow::Result<()> {
+    info!("--- bring up a leader and a learner");
+    let (_id, tc0) = start_meta_node_leader().await?;
+    let leader = tc0.meta_node();
+
+    let (_id, tc1) = start_meta_node_non_voter(leader.clone(), 1).await?;
+    let learner = tc1.meta_node();
+
+    info!("--- write a kv");
+    leader
+        .write(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd: Cmd::UpsertKV(UpsertKV::update("foo", b"bar")),
+        })
+        .await?;
+
+    let log_index = leader.raft.metrics().borrow().last_applied.unwrap().index;
+
+    learner
+        .raft
+        .wait(timeout())
+        .log(Some(log_index), "learner applied the write")
+        .await?;
+
+    info!("--- leader has collected the timings of the write");
+    {
+        let got = leader.sto.log_timings.lock().unwrap().take(log_index);
+        assert_eq!(Phases::default(), got);
+    }
+
+    info!("--- learner keeps the timings");
+    {
+        let got = learner.sto.log_timings.lock().unwrap().take(log_index);
+        assert!(got.get("append").is_some());
+        assert!(got.get("flush").is_some());
+        assert!(got.get("apply").is_some());
+    }
+
+    Ok(())
+}
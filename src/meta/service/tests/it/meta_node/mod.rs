@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod meta_node_backup;
 pub(crate) mod meta_node_kv_api;
 pub(crate) mod meta_node_kv_api_expire;
+pub(crate) mod meta_node_kv_api_quota;
+pub(crate) mod meta_node_leadership;
 pub(crate) mod meta_node_lifecycle;
+pub(crate) mod meta_node_read;
 pub(crate) mod meta_node_replication;
 pub(crate) mod meta_node_request_forwarding;
+pub(crate) mod meta_node_request_timing;
 pub(crate) mod meta_node_seq_api;
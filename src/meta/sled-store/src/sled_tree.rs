@@ -305,8 +305,8 @@ impl SledTree {
         Ok(res)
     }
 
-    /// Append many key-values into SledTree.
-    pub(crate) async fn append<KV, T>(&self, kvs: &[T]) -> Result<(), MetaStorageError>
+    /// Append many key-values into SledTree, and flush them if `flush` is true.
+    pub(crate) async fn append<KV, T>(&self, kvs: &[T], flush: bool) -> Result<(), MetaStorageError>
     where
         KV: SledKeySpace,
         T: SledAsRef<KV::K, KV::V>,
@@ -325,7 +325,7 @@ impl SledTree {
 
         self.tree.apply_batch(batch).context(|| "batch append")?;
 
-        self.flush_async(true).await?;
+        self.flush_async(flush).await?;
 
         Ok(())
     }
@@ -374,7 +374,7 @@ impl SledTree {
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    async fn flush_async(&self, flush: bool) -> Result<(), MetaStorageError> {
+    pub(crate) async fn flush_async(&self, flush: bool) -> Result<(), MetaStorageError> {
         if flush && self.sync {
             self.tree
                 .flush_async()
@@ -543,7 +543,18 @@ impl<'a, KV: SledKeySpace> AsKeySpace<'a, KV> {
 
     pub async fn append<T>(&self, kvs: &[T]) -> Result<(), MetaStorageError>
     where T: SledAsRef<KV::K, KV::V> {
-        self.inner.append::<KV, _>(kvs).await
+        self.inner.append::<KV, _>(kvs, true).await
+    }
+
+    /// Append kvs without flushing them. They are durable after [`Self::flush`] returns.
+    pub async fn append_unflushed<T>(&self, kvs: &[T]) -> Result<(), MetaStorageError>
+    where T: SledAsRef<KV::K, KV::V> {
+        self.inner.append::<KV, _>(kvs, false).await
+    }
+
+    /// Flush all of the written data in the underlying tree.
+    pub async fn flush(&self) -> Result<(), MetaStorageError> {
+        self.inner.flush_async(true).await
     }
 
     pub async fn insert(
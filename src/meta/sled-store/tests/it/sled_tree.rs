@@ -503,6 +503,40 @@ async fn test_key_space_get() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_key_space_append_unflushed() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_sled_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_sled_test_context();
+    let db = &tc.db;
+    let tree = SledTree::open(db, tc.tree_name, true)?;
+    let log_tree = tree.key_space::<Logs>();
+
+    let logs: Vec<Entry<LogEntry>> = vec![
+        Entry {
+            log_id: LogId { term: 1, index: 2 },
+            payload: EntryPayload::Blank,
+        },
+        Entry {
+            log_id: LogId { term: 1, index: 3 },
+            payload: EntryPayload::Blank,
+        },
+    ];
+
+    log_tree.append_unflushed(&logs).await?;
+
+    // Unflushed data is visible at once.
+    assert_eq!(Some(logs[0].clone()), log_tree.get(&2)?);
+    assert_eq!(Some(logs[1].clone()), log_tree.get(&3)?);
+
+    log_tree.flush().await?;
+
+    assert_eq!(Some(logs[1].clone()), log_tree.get(&3)?);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_key_space_range_remove() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_sled_ut!();
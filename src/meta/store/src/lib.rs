@@ -23,6 +23,8 @@ use common_meta_client::MetaGrpcClient;
 use common_meta_embedded::MetaEmbedded;
 use common_meta_types::GetKVReply;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::MGetKVReply;
 use common_meta_types::MetaError;
@@ -97,6 +99,13 @@ impl KVApi for MetaStore {
         }
     }
 
+    async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError> {
+        match self {
+            MetaStore::L(x) => x.list_kv(req).await,
+            MetaStore::R(x) => x.list_kv(req).await,
+        }
+    }
+
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
         match self {
             MetaStore::L(x) => x.transaction(txn).await,
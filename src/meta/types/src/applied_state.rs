@@ -21,6 +21,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::Change;
+use crate::Lease;
 use crate::Node;
 use crate::TxnReply;
 
@@ -43,6 +44,12 @@ pub enum AppliedState {
 
     TxnReply(TxnReply),
 
+    /// The lease after applying a lease command, or `None` if the lease does not exist.
+    ///
+    /// An upsert, or a txn with a put, that attaches a record to a lease that does not exist
+    /// or is expired is rejected with `Lease(None)` too.
+    Lease(Option<Lease>),
+
     #[try_into(ignore)]
     None,
 }
@@ -65,6 +72,10 @@ impl fmt::Display for AppliedState {
             AppliedState::TxnReply(txnreply) => {
                 write!(f, "Txn: {}", txnreply)
             }
+            AppliedState::Lease(lease) => match lease {
+                Some(lease) => write!(f, "Lease: {}", lease),
+                None => write!(f, "Lease: None"),
+            },
             AppliedState::None => {
                 write!(f, "None")
             }
@@ -84,6 +95,7 @@ impl AppliedState {
             AppliedState::KV(ref ch) => ch.changed(),
             AppliedState::None => false,
             AppliedState::TxnReply(txn) => txn.success,
+            AppliedState::Lease(_) => true,
         }
     }
 
@@ -110,6 +122,7 @@ impl AppliedState {
             AppliedState::KV(Change { ref prev, .. }) => prev.is_none(),
             AppliedState::None => true,
             AppliedState::TxnReply(_txn) => true,
+            AppliedState::Lease(_) => true,
         }
     }
 
@@ -120,6 +133,7 @@ impl AppliedState {
             AppliedState::KV(Change { ref result, .. }) => result.is_none(),
             AppliedState::None => true,
             AppliedState::TxnReply(txn) => !txn.success,
+            AppliedState::Lease(lease) => lease.is_none(),
         }
     }
 }
@@ -43,6 +43,15 @@ pub enum Cmd {
 
     /// Update one or more kv with a transaction.
     Transaction(TxnRequest),
+
+    /// Grant a lease that expires `ttl_ms` after the log is proposed.
+    GrantLease { ttl_ms: u64 },
+
+    /// Push forward the expiration of a lease by its ttl.
+    KeepAliveLease { id: u64 },
+
+    /// Remove a lease and delete all keys attached to it.
+    RevokeLease { id: u64 },
 }
 
 /// Update or insert a general purpose kv store
@@ -81,6 +90,15 @@ impl fmt::Display for Cmd {
             Cmd::Transaction(txn) => {
                 write!(f, "txn:{}", txn)
             }
+            Cmd::GrantLease { ttl_ms } => {
+                write!(f, "grant_lease:ttl={}ms", ttl_ms)
+            }
+            Cmd::KeepAliveLease { id } => {
+                write!(f, "keep_alive_lease:{}", id)
+            }
+            Cmd::RevokeLease { id } => {
+                write!(f, "revoke_lease:{}", id)
+            }
         }
     }
 }
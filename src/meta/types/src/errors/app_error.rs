@@ -430,6 +430,70 @@ impl TxnRetryMaxTimes {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "TenantKeySpaceQuotaExceeded: tenant '{tenant}' uses {keys} keys and {bytes} bytes, quota: max_keys: {max_keys}, max_bytes: {max_bytes}"
+)]
+pub struct TenantKeySpaceQuotaExceeded {
+    tenant: String,
+    keys: u64,
+    bytes: u64,
+    max_keys: u64,
+    max_bytes: u64,
+}
+
+impl TenantKeySpaceQuotaExceeded {
+    pub fn new(
+        tenant: impl Into<String>,
+        keys: u64,
+        bytes: u64,
+        max_keys: u64,
+        max_bytes: u64,
+    ) -> Self {
+        Self {
+            tenant: tenant.into(),
+            keys,
+            bytes,
+            max_keys,
+            max_bytes,
+        }
+    }
+}
+
+#[derive(thiserror::Error, serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[error("UnknownLease: `{lease_id}` while `{context}`")]
+pub struct UnknownLease {
+    lease_id: u64,
+    context: String,
+}
+
+impl UnknownLease {
+    pub fn new(lease_id: u64, context: impl Into<String>) -> Self {
+        Self {
+            lease_id,
+            context: context.into(),
+        }
+    }
+}
+
+#[derive(thiserror::Error, serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[error("InvalidLockTtl: ttl {ttl_ms}ms of lock `{key}` is less than {min_ttl_ms}ms")]
+pub struct InvalidLockTtl {
+    key: String,
+    ttl_ms: u64,
+    min_ttl_ms: u64,
+}
+
+impl InvalidLockTtl {
+    pub fn new(key: impl Into<String>, ttl_ms: u64, min_ttl_ms: u64) -> Self {
+        Self {
+            key: key.into(),
+            ttl_ms,
+            min_ttl_ms,
+        }
+    }
+}
+
 /// Application error.
 ///
 /// The application does not get expected result but there is nothing wrong with meta-service.
@@ -486,6 +550,15 @@ pub enum AppError {
     #[error(transparent)]
     TxnRetryMaxTimes(#[from] TxnRetryMaxTimes),
 
+    #[error(transparent)]
+    TenantKeySpaceQuotaExceeded(#[from] TenantKeySpaceQuotaExceeded),
+
+    #[error(transparent)]
+    UnknownLease(#[from] UnknownLease),
+
+    #[error(transparent)]
+    InvalidLockTtl(#[from] InvalidLockTtl),
+
     // share api errors
     #[error(transparent)]
     ShareAlreadyExists(#[from] ShareAlreadyExists),
@@ -653,6 +726,30 @@ impl AppErrorMessage for TxnRetryMaxTimes {
     }
 }
 
+impl AppErrorMessage for TenantKeySpaceQuotaExceeded {
+    fn message(&self) -> String {
+        format!(
+            "Tenant '{}' uses {} keys and {} bytes in meta-service, exceeding the quota of {} keys or {} bytes",
+            self.tenant, self.keys, self.bytes, self.max_keys, self.max_bytes
+        )
+    }
+}
+
+impl AppErrorMessage for UnknownLease {
+    fn message(&self) -> String {
+        format!("Unknown or expired lease '{}'", self.lease_id)
+    }
+}
+
+impl AppErrorMessage for InvalidLockTtl {
+    fn message(&self) -> String {
+        format!(
+            "The ttl {}ms of lock '{}' is less than the minimum {}ms",
+            self.ttl_ms, self.key, self.min_ttl_ms
+        )
+    }
+}
+
 impl AppErrorMessage for UndropTableWithNoDropTime {
     fn message(&self) -> String {
         format!("Undrop table '{}' with no drop_on time", self.table_name)
@@ -726,6 +823,11 @@ impl From<AppError> for ErrorCode {
             }
             AppError::WrongShare(err) => ErrorCode::WrongShare(err.message()),
             AppError::TxnRetryMaxTimes(err) => ErrorCode::TxnRetryMaxTimes(err.message()),
+            AppError::TenantKeySpaceQuotaExceeded(err) => {
+                ErrorCode::TenantQuotaExceeded(err.message())
+            }
+            AppError::UnknownLease(err) => ErrorCode::UnknownLease(err.message()),
+            AppError::InvalidLockTtl(err) => ErrorCode::BadArguments(err.message()),
         }
     }
 }
@@ -19,6 +19,7 @@ use common_exception::ErrorCode;
 use openraft::error::ChangeMembershipError;
 use openraft::error::Fatal;
 use openraft::error::ForwardToLeader;
+use openraft::NodeId;
 
 use crate::InvalidReply;
 use crate::MetaNetworkError;
@@ -94,6 +95,10 @@ pub enum MetaDataError {
     /// Error occurred when reading.
     #[error(transparent)]
     ReadError(#[from] MetaDataReadError),
+
+    /// Error occurred when transferring leadership.
+    #[error(transparent)]
+    TransferLeaderError(#[from] TransferLeaderError),
 }
 
 /// Error occurred when a meta-node reads data.
@@ -120,6 +125,30 @@ impl MetaDataReadError {
     }
 }
 
+/// Error occurred when a meta-node transfers its leadership to another node.
+#[derive(thiserror::Error, serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[error("fail to transfer leadership to {to:?}: {msg}")]
+pub struct TransferLeaderError {
+    to: Option<NodeId>,
+    msg: String,
+}
+
+impl TransferLeaderError {
+    pub fn new(to: Option<NodeId>, msg: impl Display) -> Self {
+        Self {
+            to,
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<TransferLeaderError> for MetaOperationError {
+    fn from(e: TransferLeaderError) -> Self {
+        let de = MetaDataError::from(e);
+        MetaOperationError::from(de)
+    }
+}
+
 impl From<MetaDataReadError> for MetaOperationError {
     fn from(e: MetaDataReadError) -> Self {
         let de = MetaDataError::from(e);
@@ -127,6 +156,13 @@ impl From<MetaDataReadError> for MetaOperationError {
     }
 }
 
+impl From<MetaDataReadError> for MetaAPIError {
+    fn from(e: MetaDataReadError) -> Self {
+        let de = MetaDataError::from(e);
+        MetaAPIError::from(de)
+    }
+}
+
 impl From<InvalidReply> for MetaAPIError {
     fn from(e: InvalidReply) -> Self {
         let net_err = MetaNetworkError::from(e);
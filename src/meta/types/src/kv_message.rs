@@ -33,6 +33,117 @@ pub struct ListKVReq {
     pub prefix: String,
 }
 
+/// List kvs in a key range, at most `limit` kvs a time.
+///
+/// To list the next page, send the same request with `after_key` set to the last key in the previous reply.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListKVRangeReq {
+    /// The left bound of the range, inclusive.
+    pub start: String,
+
+    /// The right bound of the range, exclusive. `None` means unbounded.
+    pub end: Option<String>,
+
+    /// The max number of kvs to return. `None` means no limit.
+    pub limit: Option<u64>,
+
+    /// Only return keys after this key, in the listing order. It is exclusive.
+    pub after_key: Option<String>,
+
+    /// Return keys and seq only, with value data left empty.
+    pub keys_only: bool,
+
+    /// List in descending key order.
+    pub reverse: bool,
+}
+
+impl ListKVRangeReq {
+    /// Create a request that lists all kvs whose key starts with `prefix`.
+    pub fn prefix(prefix: impl ToString) -> Self {
+        let start = prefix.to_string();
+        let end = prefix_end(&start);
+        Self {
+            start,
+            end,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_after_key(mut self, after_key: impl ToString) -> Self {
+        self.after_key = Some(after_key.to_string());
+        self
+    }
+
+    pub fn with_keys_only(mut self, keys_only: bool) -> Self {
+        self.keys_only = keys_only;
+        self
+    }
+
+    pub fn with_reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+}
+
+/// Returns the smallest string that is greater than every string starting with `prefix`,
+/// or `None` if there is no such string.
+fn prefix_end(prefix: &str) -> Option<String> {
+    let mut chars = prefix.chars().collect::<Vec<_>>();
+    while let Some(last) = chars.pop() {
+        let next = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32);
+        if let Some(c) = next {
+            chars.push(c);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListKVRangeReply {
+    pub kvs: Vec<(String, SeqV<Vec<u8>>)>,
+
+    /// Whether there are more kvs in the range after the last one returned.
+    pub more: bool,
+}
+
+/// The grpc metadata key in a `KvApi` request to specify the consistency of a read.
+///
+/// The value is a json encoded `ReadConsistency`.
+/// Without it a read is served with `ReadConsistency::Leader`.
+pub const READ_CONSISTENCY_KEY: &str = "read-consistency";
+
+/// Specifies how up to date the data returned by a read has to be, and thus which node can serve it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// The read is forwarded to and served by the leader.
+    Leader,
+
+    /// The read is served by the receiving node, including a follower or learner,
+    /// after it has applied every log the leader applied when the read arrives (ReadIndex).
+    ///
+    /// It observes every write completed before the read is sent.
+    Linearizable,
+
+    /// The read is served by the receiving node,
+    /// if the data it has applied lags behind the leader for no more than `max_staleness_ms`.
+    ///
+    /// Otherwise the read is forwarded to the leader.
+    /// It is meant for callers that tolerate stale data, such as a cache.
+    BoundedStaleness { max_staleness_ms: u64 },
+}
+
+impl Default for ReadConsistency {
+    fn default() -> Self {
+        ReadConsistency::Leader
+    }
+}
+
 pub type UpsertKVReply = Change<Vec<u8>>;
 pub type GetKVReply = Option<SeqV<Vec<u8>>>;
 pub type MGetKVReply = Vec<Option<SeqV<Vec<u8>>>>;
@@ -0,0 +1,62 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A lease is a time-to-live granted by meta-service.
+///
+/// Keys attached to a lease are deleted all at once when the lease expires or is revoked.
+/// A lease is kept alive by its holder, which pushes the expiration forward by `ttl_ms`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Lease {
+    pub id: u64,
+
+    /// The time-to-live in millisecond.
+    pub ttl_ms: u64,
+
+    /// The time in millisecond since 1970 when this lease expires.
+    pub expire_at_ms: u64,
+}
+
+impl Lease {
+    pub fn new(id: u64, ttl_ms: u64, now_ms: u64) -> Self {
+        Self {
+            id,
+            ttl_ms,
+            expire_at_ms: now_ms + ttl_ms,
+        }
+    }
+
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms > self.expire_at_ms
+    }
+
+    /// Push forward the expiration by `ttl_ms`, starting from `now_ms`.
+    pub fn renew(&mut self, now_ms: u64) {
+        self.expire_at_ms = now_ms + self.ttl_ms;
+    }
+}
+
+impl fmt::Display for Lease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lease:{}(ttl: {}ms, expire_at: {}ms)",
+            self.id, self.ttl_ms, self.expire_at_ms
+        )
+    }
+}
@@ -24,13 +24,17 @@ pub mod config;
 mod endpoint;
 pub mod errors;
 mod kv_message;
+mod lease;
 mod log_entry;
+mod masking_policy;
 mod match_seq;
 mod message;
 mod operation;
+mod password_policy;
 mod raft_txid;
 mod raft_types;
 mod role_info;
+mod row_access_policy;
 mod seq_errors;
 mod seq_num;
 mod seq_value;
@@ -45,6 +49,7 @@ mod user_quota;
 mod user_setting;
 mod user_stage;
 mod with;
+mod workload_group;
 
 mod principal_identity;
 mod proto_display;
@@ -74,9 +79,11 @@ pub use errors::app_error::CreateTableWithDropTime;
 pub use errors::app_error::DatabaseAlreadyExists;
 pub use errors::app_error::DropDbWithDropTime;
 pub use errors::app_error::DropTableWithDropTime;
+pub use errors::app_error::InvalidLockTtl;
 pub use errors::app_error::ShareAlreadyExists;
 pub use errors::app_error::TableAlreadyExists;
 pub use errors::app_error::TableVersionMismatched;
+pub use errors::app_error::TenantKeySpaceQuotaExceeded;
 pub use errors::app_error::UndropDbHasNoHistory;
 pub use errors::app_error::UndropDbWithNoDropTime;
 pub use errors::app_error::UndropTableAlreadyExists;
@@ -84,6 +91,7 @@ pub use errors::app_error::UndropTableHasNoHistory;
 pub use errors::app_error::UndropTableWithNoDropTime;
 pub use errors::app_error::UnknownDatabase;
 pub use errors::app_error::UnknownDatabaseId;
+pub use errors::app_error::UnknownLease;
 pub use errors::app_error::UnknownShare;
 pub use errors::app_error::UnknownTable;
 pub use errors::app_error::UnknownTableId;
@@ -93,6 +101,7 @@ pub use errors::meta_api_errors::MetaAPIError;
 pub use errors::meta_api_errors::MetaDataError;
 pub use errors::meta_api_errors::MetaDataReadError;
 pub use errors::meta_api_errors::MetaOperationError;
+pub use errors::meta_api_errors::TransferLeaderError;
 pub use errors::meta_client_errors::MetaClientError;
 pub use errors::meta_errors::MetaError;
 pub use errors::meta_errors::MetaResult;
@@ -114,24 +123,35 @@ pub use errors::meta_startup_errors::MetaStartupError;
 pub use errors::rpc_errors::ForwardRPCError;
 pub use kv_message::GetKVReply;
 pub use kv_message::GetKVReq;
+pub use kv_message::ListKVRangeReply;
+pub use kv_message::ListKVRangeReq;
 pub use kv_message::ListKVReply;
 pub use kv_message::ListKVReq;
 pub use kv_message::MGetKVReply;
 pub use kv_message::MGetKVReq;
+pub use kv_message::ReadConsistency;
 pub use kv_message::UpsertKVReply;
 pub use kv_message::UpsertKVReq;
+pub use kv_message::READ_CONSISTENCY_KEY;
+pub use lease::Lease;
 pub use log_entry::LogEntry;
+pub use masking_policy::MaskingPolicy;
+pub use masking_policy::TableMaskingPolicies;
 pub use match_seq::MatchSeq;
 pub use match_seq::MatchSeqExt;
+pub use message::ElectRequest;
 pub use message::ForwardRequest;
 pub use message::ForwardRequestBody;
 pub use message::ForwardResponse;
 pub use message::JoinRequest;
 pub use message::LeaveRequest;
+pub use message::ReadIndexReq;
+pub use message::TransferLeaderRequest;
 pub use operation::GCDroppedDataReply;
 pub use operation::GCDroppedDataReq;
 pub use operation::MetaId;
 pub use operation::Operation;
+pub use password_policy::PasswordPolicy;
 pub use principal_identity::PrincipalIdentity;
 pub use protobuf::txn_condition;
 pub use protobuf::txn_condition::ConditionResult;
@@ -157,6 +177,8 @@ pub use raft_types::NodeId;
 pub use raft_types::Term;
 pub use role_info::RoleInfo;
 pub use role_info::RoleInfoSerdeError;
+pub use row_access_policy::RowAccessPolicy;
+pub use row_access_policy::TableRowAccessPolicy;
 pub use seq_errors::ConflictSeq;
 pub use seq_num::SeqNum;
 pub use seq_value::IntoSeqV;
@@ -167,6 +189,7 @@ pub use tenant_quota::TenantQuota;
 pub use user_auth::AuthInfo;
 pub use user_auth::AuthType;
 pub use user_auth::PasswordHashMethod;
+pub use user_auth::ScramSha256Secret;
 pub use user_defined_function::UserDefinedFunction;
 pub use user_grant::GrantEntry;
 pub use user_grant::GrantObject;
@@ -175,6 +198,7 @@ pub use user_identity::UserIdentity;
 pub use user_info::UserInfo;
 pub use user_info::UserOption;
 pub use user_info::UserOptionFlag;
+pub use user_info::MAX_PASSWORD_HISTORY;
 pub use user_privilege::UserPrivilegeSet;
 pub use user_privilege::UserPrivilegeType;
 pub use user_quota::UserQuota;
@@ -182,3 +206,4 @@ pub use user_setting::UserSetting;
 pub use user_setting::UserSettingValue;
 pub use user_stage::*;
 pub use with::With;
+pub use workload_group::WorkloadGroup;
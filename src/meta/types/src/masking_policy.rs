@@ -0,0 +1,70 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A masking policy, the definition is an expression on its parameters which is
+/// evaluated in place of the column the policy is set on.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct MaskingPolicy {
+    pub name: String,
+    /// Name and type of each parameter, the first one is the masked column.
+    pub parameters: Vec<(String, String)>,
+    pub return_type: String,
+    pub definition: String,
+}
+
+impl MaskingPolicy {
+    pub fn new(
+        name: &str,
+        parameters: Vec<(String, String)>,
+        return_type: &str,
+        definition: &str,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters,
+            return_type: return_type.to_string(),
+            definition: definition.to_string(),
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for MaskingPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalMaskingPolicyFormat(format!(
+                "Cannot deserialize masking policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+/// The masking policies set on the columns of a table, keyed by column name.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct TableMaskingPolicies {
+    pub columns: BTreeMap<String, String>,
+}
@@ -27,6 +27,8 @@ use crate::Endpoint;
 use crate::GetKVReply;
 use crate::GetKVReq;
 use crate::InvalidReply;
+use crate::ListKVRangeReply;
+use crate::ListKVRangeReq;
 use crate::ListKVReply;
 use crate::ListKVReq;
 use crate::LogEntry;
@@ -49,6 +51,10 @@ pub struct JoinRequest {
     pub node_id: NodeId,
     pub endpoint: Endpoint,
     pub grpc_api_addr: String,
+
+    /// Join as a non-voting learner, which replicates logs and serves reads but does not vote.
+    #[serde(default)]
+    pub learner: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -56,6 +62,24 @@ pub struct LeaveRequest {
     pub node_id: NodeId,
 }
 
+/// Transfer the leadership to another voter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TransferLeaderRequest {
+    /// The voter to transfer the leadership to.
+    ///
+    /// If it is `None`, another voter with the smallest id is chosen.
+    pub to: Option<NodeId>,
+}
+
+/// Ask a node to start an election once it has received the logs up to `last_log_index`.
+///
+/// It is sent by the leader to the node it transfers its leadership to.
+/// Unlike other forwardable requests, it is handled by the node it is sent to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ElectRequest {
+    pub last_log_index: u64,
+}
+
 #[derive(
     Serialize, Deserialize, Debug, Clone, PartialEq, Eq, derive_more::From, derive_more::TryInto,
 )]
@@ -64,14 +88,26 @@ pub enum ForwardRequestBody {
 
     Join(JoinRequest),
     Leave(LeaveRequest),
+    TransferLeader(TransferLeaderRequest),
+    Elect(ElectRequest),
 
     Write(LogEntry),
 
     GetKV(GetKVReq),
     MGetKV(MGetKVReq),
     ListKV(ListKVReq),
+    ListKVRange(ListKVRangeReq),
+
+    /// Get the read index from the leader for a linearizable read.
+    ReadIndex(ReadIndexReq),
 }
 
+/// Request for the leader to confirm its leadership and return the index of its last applied log.
+///
+/// A node that has applied logs up to this index can serve a linearizable read from its local state machine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReadIndexReq {}
+
 /// A request that is forwarded from one raft node to another
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ForwardRequest {
@@ -95,11 +131,23 @@ pub enum ForwardResponse {
 
     Join(()),
     Leave(()),
+
+    /// The id of the new leader.
+    ///
+    /// Ignored by `TryInto` because `NodeId` is the same type as the read index.
+    #[try_into(ignore)]
+    TransferLeader(NodeId),
+    Elect(()),
+
     AppliedState(AppliedState),
 
     GetKV(GetKVReply),
     MGetKV(MGetKVReply),
     ListKV(ListKVReply),
+    ListKVRange(ListKVRangeReply),
+
+    /// The read index, i.e., the index of the last log applied by the leader.
+    ReadIndex(u64),
 }
 
 impl tonic::IntoRequest<RaftRequest> for ForwardRequest {
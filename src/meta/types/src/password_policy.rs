@@ -0,0 +1,147 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A password policy constrains the passwords of the users attached to it,
+/// and locks a user out after too many failed logins.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(default)]
+pub struct PasswordPolicy {
+    pub name: String,
+    pub min_length: u64,
+    pub max_length: u64,
+    pub min_upper_case_chars: u64,
+    pub min_lower_case_chars: u64,
+    pub min_numeric_chars: u64,
+    pub min_special_chars: u64,
+    /// The days a password stays valid before it must be changed (0 is never expired).
+    pub max_age_days: u64,
+    /// The failed logins in a row before the user is locked (0 is never locked).
+    pub max_retries: u64,
+    /// The minutes a locked user must wait before login again.
+    pub lockout_time_mins: u64,
+    /// The number of recent passwords which can not be reused (0 is no limited).
+    pub history: u64,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            name: "".to_string(),
+            min_length: 8,
+            max_length: 256,
+            min_upper_case_chars: 1,
+            min_lower_case_chars: 1,
+            min_numeric_chars: 1,
+            min_special_chars: 0,
+            max_age_days: 90,
+            max_retries: 5,
+            lockout_time_mins: 15,
+            history: 0,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn check_password(&self, password: &str) -> Result<()> {
+        let length = password.chars().count() as u64;
+        if length < self.min_length || length > self.max_length {
+            return Err(ErrorCode::InvalidPassword(format!(
+                "password length must be between {} and {}, policy: {}",
+                self.min_length, self.max_length, self.name
+            )));
+        }
+
+        let count = |f: fn(&char) -> bool| password.chars().filter(f).count() as u64;
+        let checks = [
+            (
+                count(char::is_ascii_uppercase),
+                self.min_upper_case_chars,
+                "upper case",
+            ),
+            (
+                count(char::is_ascii_lowercase),
+                self.min_lower_case_chars,
+                "lower case",
+            ),
+            (
+                count(char::is_ascii_digit),
+                self.min_numeric_chars,
+                "numeric",
+            ),
+            (
+                count(|c| !c.is_ascii_alphanumeric()),
+                self.min_special_chars,
+                "special",
+            ),
+        ];
+        for (actual, expected, kind) in checks {
+            if actual < expected {
+                return Err(ErrorCode::InvalidPassword(format!(
+                    "password must contain at least {} {} characters, policy: {}",
+                    expected, kind, self.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a password last changed at `update_on` has expired at `now`.
+    pub fn is_password_expired(
+        &self,
+        update_on: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        match update_on {
+            Some(update_on) if self.max_age_days > 0 => {
+                update_on + Duration::days(self.max_age_days as i64) <= now
+            }
+            _ => false,
+        }
+    }
+
+    pub fn lockout_duration(&self) -> Duration {
+        Duration::minutes(self.lockout_time_mins as i64)
+    }
+}
+
+impl TryFrom<Vec<u8>> for PasswordPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalPasswordPolicyFormat(format!(
+                "Cannot deserialize password policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
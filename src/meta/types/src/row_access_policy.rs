@@ -0,0 +1,76 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A row access policy, the definition is a boolean expression on its parameters,
+/// rows of the tables the policy is attached to are only visible if it evaluates to true.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct RowAccessPolicy {
+    pub name: String,
+    /// Name and type of each parameter.
+    pub parameters: Vec<(String, String)>,
+    pub definition: String,
+    /// Roles that are not restricted by the policy.
+    pub exempt_roles: Vec<String>,
+}
+
+impl RowAccessPolicy {
+    pub fn new(
+        name: &str,
+        parameters: Vec<(String, String)>,
+        definition: &str,
+        exempt_roles: Vec<String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters,
+            definition: definition.to_string(),
+            exempt_roles,
+        }
+    }
+
+    pub fn is_exempt(&self, role: &str) -> bool {
+        self.exempt_roles.iter().any(|r| r == role)
+    }
+}
+
+impl TryFrom<Vec<u8>> for RowAccessPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalRowAccessPolicyFormat(format!(
+                "Cannot deserialize row access policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+/// A row access policy attached to a table, with the table columns bound to the
+/// parameters of the policy in order.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct TableRowAccessPolicy {
+    pub policy: String,
+    pub columns: Vec<String>,
+}
@@ -27,6 +27,11 @@ pub type PbSeqV = crate::protobuf::SeqV;
 pub struct KVMeta {
     /// expiration time in second since 1970
     pub expire_at: Option<u64>,
+
+    /// The id of the lease this record is attached to.
+    /// The record is deleted when the lease expires or is revoked.
+    #[serde(default)]
+    pub lease: Option<u64>,
 }
 
 /// Some value bound with a seq number
@@ -102,6 +107,11 @@ impl<T> SeqV<T> {
         }
     }
 
+    /// Returns the id of the lease this record is attached to.
+    pub fn get_lease(&self) -> Option<u64> {
+        self.meta.as_ref().and_then(|m| m.lease)
+    }
+
     #[must_use]
     pub fn set_seq(mut self, seq: u64) -> SeqV<T> {
         self.seq = seq;
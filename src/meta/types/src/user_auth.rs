@@ -15,12 +15,16 @@ use std::str::FromStr;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use hmac::Hmac;
+use hmac::Mac;
+use rand::Rng;
 use sha2::Digest;
 use sha2::Sha256;
 
 const NO_PASSWORD_STR: &str = "no_password";
 const SHA256_PASSWORD_STR: &str = "sha256_password";
 const DOUBLE_SHA1_PASSWORD_STR: &str = "double_sha1_password";
+const SCRAM_SHA256_PASSWORD_STR: &str = "scram_sha256_password";
 const JWT_AUTH_STR: &str = "jwt";
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -28,6 +32,7 @@ pub enum AuthType {
     NoPassword,
     Sha256Password,
     DoubleSha1Password,
+    ScramSha256Password,
     JWT,
 }
 
@@ -37,6 +42,7 @@ impl std::str::FromStr for AuthType {
         match s {
             SHA256_PASSWORD_STR => Ok(AuthType::Sha256Password),
             DOUBLE_SHA1_PASSWORD_STR => Ok(AuthType::DoubleSha1Password),
+            SCRAM_SHA256_PASSWORD_STR => Ok(AuthType::ScramSha256Password),
             NO_PASSWORD_STR => Ok(AuthType::NoPassword),
             JWT_AUTH_STR => Ok(AuthType::JWT),
             _ => Err(ErrorCode::InvalidAuthInfo(AuthType::bad_auth_types(s))),
@@ -50,6 +56,7 @@ impl AuthType {
             AuthType::NoPassword => NO_PASSWORD_STR,
             AuthType::Sha256Password => SHA256_PASSWORD_STR,
             AuthType::DoubleSha1Password => DOUBLE_SHA1_PASSWORD_STR,
+            AuthType::ScramSha256Password => SCRAM_SHA256_PASSWORD_STR,
             AuthType::JWT => JWT_AUTH_STR,
         }
     }
@@ -59,6 +66,7 @@ impl AuthType {
             NO_PASSWORD_STR,
             SHA256_PASSWORD_STR,
             DOUBLE_SHA1_PASSWORD_STR,
+            SCRAM_SHA256_PASSWORD_STR,
             JWT_AUTH_STR,
         ];
         let all = all
@@ -73,6 +81,7 @@ impl AuthType {
         match self {
             AuthType::Sha256Password => Some(PasswordHashMethod::Sha256),
             AuthType::DoubleSha1Password => Some(PasswordHashMethod::DoubleSha1),
+            AuthType::ScramSha256Password => Some(PasswordHashMethod::ScramSha256),
             _ => None,
         }
     }
@@ -103,7 +112,9 @@ impl AuthInfo {
         match auth_type {
             AuthType::NoPassword => Ok(AuthInfo::None),
             AuthType::JWT => Ok(AuthInfo::JWT),
-            AuthType::Sha256Password | AuthType::DoubleSha1Password => match auth_string {
+            AuthType::Sha256Password
+            | AuthType::DoubleSha1Password
+            | AuthType::ScramSha256Password => match auth_string {
                 Some(p) => {
                     let method = auth_type.get_password_type().unwrap();
                     Ok(AuthInfo::Password {
@@ -167,6 +178,7 @@ impl AuthInfo {
             } => match t {
                 PasswordHashMethod::Sha256 => AuthType::Sha256Password,
                 PasswordHashMethod::DoubleSha1 => AuthType::DoubleSha1Password,
+                PasswordHashMethod::ScramSha256 => AuthType::ScramSha256Password,
             },
         }
     }
@@ -201,6 +213,17 @@ impl AuthInfo {
         }
     }
 
+    /// Checks the password given in clear text.
+    pub fn verify_password(&self, password: &[u8]) -> bool {
+        match self {
+            AuthInfo::Password {
+                hash_value,
+                hash_method,
+            } => hash_method.verify(hash_value, password),
+            _ => false,
+        }
+    }
+
     fn restore_sha1_mysql(salt: &[u8], input: &[u8], user_password_hash: &[u8]) -> Result<Vec<u8>> {
         // SHA1( password ) XOR SHA1( "20-bytes random data from server" <concat> SHA1( SHA1( password ) ) )
         let mut m = sha1::Sha1::new();
@@ -232,6 +255,9 @@ impl AuthInfo {
                 PasswordHashMethod::Sha256 => Err(ErrorCode::AuthenticateFailure(
                     "login with sha256_password user for mysql protocol not supported yet.",
                 )),
+                PasswordHashMethod::ScramSha256 => Err(ErrorCode::AuthenticateFailure(
+                    "login with scram_sha256_password user for mysql protocol not supported yet.",
+                )),
             },
             _ => Err(ErrorCode::AuthenticateFailure(format!(
                 "user require auth type {}",
@@ -262,13 +288,34 @@ impl Default for AuthInfo {
 pub enum PasswordHashMethod {
     DoubleSha1 = 1,
     Sha256 = 2,
+    ScramSha256 = 3,
 }
 
 impl PasswordHashMethod {
+    /// Hashes the password, a random salt is generated for ScramSha256,
+    /// so use `verify` to check a password against the hash.
     pub fn hash(self, user_input: &[u8]) -> Vec<u8> {
         match self {
             PasswordHashMethod::DoubleSha1 => double_sha1(user_input).to_vec(),
             PasswordHashMethod::Sha256 => Sha256::digest(user_input).to_vec(),
+            PasswordHashMethod::ScramSha256 => {
+                let salt = rand::thread_rng().gen::<[u8; SCRAM_SALT_LEN]>();
+                ScramSha256Secret::new(user_input, &salt, SCRAM_ITERATIONS).encode()
+            }
+        }
+    }
+
+    pub fn verify(self, hash_value: &[u8], user_input: &[u8]) -> bool {
+        match self {
+            PasswordHashMethod::ScramSha256 => match ScramSha256Secret::decode(hash_value) {
+                Ok(secret) => {
+                    let expected =
+                        ScramSha256Secret::new(user_input, &secret.salt, secret.iterations);
+                    expected.stored_key == secret.stored_key
+                }
+                Err(_) => false,
+            },
+            _ => self.hash(user_input) == hash_value,
         }
     }
 
@@ -282,3 +329,101 @@ impl Default for PasswordHashMethod {
         PasswordHashMethod::Sha256
     }
 }
+
+// The same as the default `scram_iterations` of PostgreSQL.
+const SCRAM_ITERATIONS: u32 = 4096;
+const SCRAM_SALT_LEN: usize = 16;
+const SCRAM_KEY_LEN: usize = 32;
+
+/// The secret of SCRAM-SHA-256 (RFC 5802, RFC 7677) stored as the hash value,
+/// which is `iterations(4 bytes, big endian) | salt | StoredKey | ServerKey`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScramSha256Secret {
+    pub iterations: u32,
+    pub salt: Vec<u8>,
+    pub stored_key: [u8; SCRAM_KEY_LEN],
+    pub server_key: [u8; SCRAM_KEY_LEN],
+}
+
+impl ScramSha256Secret {
+    pub fn new(password: &[u8], salt: &[u8], iterations: u32) -> Self {
+        let salted_password = scram_hi(password, salt, iterations);
+        let client_key = hmac_sha256(&salted_password, b"Client Key");
+        ScramSha256Secret {
+            iterations,
+            salt: salt.to_vec(),
+            stored_key: Sha256::digest(client_key).into(),
+            server_key: hmac_sha256(&salted_password, b"Server Key"),
+        }
+    }
+
+    pub fn decode(hash_value: &[u8]) -> Result<Self> {
+        if hash_value.len() < 4 + 2 * SCRAM_KEY_LEN {
+            return Err(ErrorCode::InvalidAuthInfo(
+                "invalid scram_sha256_password hash value",
+            ));
+        }
+
+        let (iterations, rest) = hash_value.split_at(4);
+        let (salt, keys) = rest.split_at(rest.len() - 2 * SCRAM_KEY_LEN);
+        let (stored_key, server_key) = keys.split_at(SCRAM_KEY_LEN);
+        Ok(ScramSha256Secret {
+            iterations: u32::from_be_bytes(iterations.try_into().unwrap()),
+            salt: salt.to_vec(),
+            stored_key: stored_key.try_into().unwrap(),
+            server_key: server_key.try_into().unwrap(),
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(4 + self.salt.len() + 2 * SCRAM_KEY_LEN);
+        buf.extend_from_slice(&self.iterations.to_be_bytes());
+        buf.extend_from_slice(&self.salt);
+        buf.extend_from_slice(&self.stored_key);
+        buf.extend_from_slice(&self.server_key);
+        buf
+    }
+
+    /// Checks the ClientProof sent by the client for the AuthMessage of the exchange.
+    pub fn verify_client_proof(&self, auth_message: &[u8], client_proof: &[u8]) -> bool {
+        if client_proof.len() != SCRAM_KEY_LEN {
+            return false;
+        }
+
+        // ClientKey = ClientProof XOR HMAC(StoredKey, AuthMessage)
+        let client_signature = hmac_sha256(&self.stored_key, auth_message);
+        let client_key = client_proof
+            .iter()
+            .zip(client_signature.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        let stored_key: [u8; SCRAM_KEY_LEN] = Sha256::digest(client_key).into();
+        stored_key == self.stored_key
+    }
+
+    /// The ServerSignature which proves to the client that the server knows the password.
+    pub fn server_signature(&self, auth_message: &[u8]) -> [u8; SCRAM_KEY_LEN] {
+        hmac_sha256(&self.server_key, auth_message)
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; SCRAM_KEY_LEN] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+// Hi() of RFC 5802, which is PBKDF2 with HMAC-SHA-256 and a single output block.
+fn scram_hi(password: &[u8], salt: &[u8], iterations: u32) -> [u8; SCRAM_KEY_LEN] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(password).expect("HMAC can take key of any size");
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut u: [u8; SCRAM_KEY_LEN] = mac.finalize().into_bytes().into();
+
+    let mut result = u;
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &u);
+        result.iter_mut().zip(u.iter()).for_each(|(r, u)| *r ^= u);
+    }
+    result
+}
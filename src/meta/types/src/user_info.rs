@@ -15,6 +15,8 @@
 use core::fmt;
 use std::convert::TryFrom;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use enumflags2::bitflags;
@@ -41,8 +43,22 @@ pub struct UserInfo {
     pub quota: UserQuota,
 
     pub option: UserOption,
+
+    /// The previous passwords of the user, the latest at the end.
+    pub history_auth_infos: Vec<AuthInfo>,
+
+    /// The failed logins since the last successful one.
+    pub password_fails: Vec<DateTime<Utc>>,
+
+    pub password_update_on: Option<DateTime<Utc>>,
+
+    /// The user can not login until this time.
+    pub lockout_time: Option<DateTime<Utc>>,
 }
 
+/// The max previous passwords kept in `UserInfo::history_auth_infos`.
+pub const MAX_PASSWORD_HISTORY: usize = 24;
+
 impl UserInfo {
     pub fn new(name: &str, hostname: &str, auth_info: AuthInfo) -> Self {
         // Default is no privileges.
@@ -57,6 +73,10 @@ impl UserInfo {
             grants,
             quota,
             option,
+            history_auth_infos: vec![],
+            password_fails: vec![],
+            password_update_on: None,
+            lockout_time: None,
         }
     }
 
@@ -74,6 +94,25 @@ impl UserInfo {
     pub fn has_option_flag(&self, flag: UserOptionFlag) -> bool {
         self.option.has_option_flag(flag)
     }
+
+    /// Replace the auth info, keeping the old password in the history.
+    pub fn update_auth_info(&mut self, auth_info: AuthInfo, now: DateTime<Utc>) {
+        if self.auth_info == auth_info {
+            return;
+        }
+        if let AuthInfo::Password { .. } = self.auth_info {
+            self.history_auth_infos.push(self.auth_info.clone());
+            if self.history_auth_infos.len() > MAX_PASSWORD_HISTORY {
+                self.history_auth_infos.remove(0);
+            }
+        }
+        self.auth_info = auth_info;
+        self.password_update_on = Some(now);
+    }
+
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.lockout_time, Some(lockout_time) if lockout_time > now)
+    }
 }
 
 impl TryFrom<Vec<u8>> for UserInfo {
@@ -96,6 +135,10 @@ pub struct UserOption {
     flags: BitFlags<UserOptionFlag>,
 
     default_role: Option<String>,
+
+    password_policy: Option<String>,
+
+    must_change_password: bool,
 }
 
 impl UserOption {
@@ -103,6 +146,8 @@ impl UserOption {
         Self {
             flags,
             default_role: None,
+            password_policy: None,
+            must_change_password: false,
         }
     }
 
@@ -120,6 +165,16 @@ impl UserOption {
         self
     }
 
+    pub fn with_password_policy(mut self, password_policy: Option<String>) -> Self {
+        self.password_policy = password_policy;
+        self
+    }
+
+    pub fn with_must_change_password(mut self, must_change_password: bool) -> Self {
+        self.must_change_password = must_change_password;
+        self
+    }
+
     pub fn with_set_flag(mut self, flag: UserOptionFlag) -> Self {
         self.flags.insert(flag);
         self
@@ -137,6 +192,22 @@ impl UserOption {
         self.default_role = default_role;
     }
 
+    pub fn password_policy(&self) -> Option<&String> {
+        self.password_policy.as_ref()
+    }
+
+    pub fn set_password_policy(&mut self, password_policy: Option<String>) {
+        self.password_policy = password_policy;
+    }
+
+    pub fn must_change_password(&self) -> bool {
+        self.must_change_password
+    }
+
+    pub fn set_must_change_password(&mut self, must_change_password: bool) {
+        self.must_change_password = must_change_password;
+    }
+
     pub fn set_all_flag(&mut self) {
         self.flags = BitFlags::all();
     }
@@ -167,12 +238,14 @@ impl UserOption {
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, num_derive::FromPrimitive)]
 pub enum UserOptionFlag {
     TenantSetting = 1 << 0,
+    RequireSsl = 1 << 1,
 }
 
 impl std::fmt::Display for UserOptionFlag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             UserOptionFlag::TenantSetting => write!(f, "TENANTSETTING"),
+            UserOptionFlag::RequireSsl => write!(f, "REQUIRE SSL"),
         }
     }
 }
@@ -0,0 +1,70 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A workload group limits the queries of the users and roles assigned to it.
+/// Queries beyond the capacity of the group wait in the admission queue of the query node.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct WorkloadGroup {
+    pub name: String,
+    /// The max queries of the group running at the same time on a node (0 is no limited).
+    pub max_concurrency: u64,
+    /// The max memory(bytes) the running queries of the group use on a node
+    /// before new queries are held (0 is no limited).
+    pub memory_quota: u64,
+    /// The seconds a query waits in the queue before it fails (0 waits until admitted).
+    pub queue_timeout: u64,
+    /// Users assigned to the group.
+    pub users: Vec<String>,
+    /// Roles assigned to the group.
+    pub roles: Vec<String>,
+}
+
+impl WorkloadGroup {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_assigned_to_user(&self, user: &str) -> bool {
+        self.users.iter().any(|u| u == user)
+    }
+
+    pub fn is_assigned_to_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+impl TryFrom<Vec<u8>> for WorkloadGroup {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(group) => Ok(group),
+            Err(serialize_error) => Err(ErrorCode::IllegalWorkloadGroupFormat(format!(
+                "Cannot deserialize workload group from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -13,12 +13,17 @@
 //  limitations under the License.
 
 mod cluster;
+mod masking_policy;
 mod match_seq;
+mod password_policy;
+mod row_access_policy;
+mod user_auth;
 mod user_defined_function;
 mod user_grant;
 mod user_info;
 mod user_privilege;
 mod user_quota;
+mod workload_group;
 
 #[test]
 fn test_bin_commit_version() -> anyhow::Result<()> {
@@ -0,0 +1,41 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::MaskingPolicy;
+use common_meta_types::TableMaskingPolicies;
+
+#[test]
+fn test_masking_policy() -> Result<()> {
+    let policy = MaskingPolicy::new(
+        "email_mask",
+        vec![("val".to_string(), "STRING".to_string())],
+        "STRING",
+        "'***'",
+    );
+    let ser = serde_json::to_string(&policy)?;
+
+    let de = MaskingPolicy::try_from(ser.into_bytes())?;
+    assert_eq!(policy, de);
+
+    let mut policies = TableMaskingPolicies::default();
+    policies
+        .columns
+        .insert("email".to_string(), "email_mask".to_string());
+    let ser = serde_json::to_string(&policies)?;
+    let de = serde_json::from_str::<TableMaskingPolicies>(&ser)?;
+    assert_eq!(policies, de);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Duration;
+use chrono::TimeZone;
+use chrono::Utc;
+use common_exception::exception::Result;
+use common_meta_types::PasswordPolicy;
+
+#[test]
+fn test_password_policy() -> Result<()> {
+    let mut policy = PasswordPolicy::new("strict");
+    policy.min_length = 10;
+    policy.min_special_chars = 1;
+    policy.history = 3;
+    let ser = serde_json::to_string(&policy)?;
+
+    let de = PasswordPolicy::try_from(ser.into_bytes())?;
+    assert_eq!(policy, de);
+
+    assert!(de.check_password("Abc1!defgh").is_ok());
+    // Too short.
+    assert!(de.check_password("Ab1!").is_err());
+    // No upper case.
+    assert!(de.check_password("abc1!defgh").is_err());
+    // No lower case.
+    assert!(de.check_password("ABC1!DEFGH").is_err());
+    // No numeric.
+    assert!(de.check_password("Abcd!defgh").is_err());
+    // No special.
+    assert!(de.check_password("Abc12defgh").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_password_policy_expired() -> Result<()> {
+    let policy = PasswordPolicy::new("p1");
+    let update_on = Utc.ymd(2022, 10, 1).and_hms(0, 0, 0);
+
+    assert!(!policy.is_password_expired(None, Utc::now()));
+    assert!(!policy.is_password_expired(Some(update_on), update_on + Duration::days(89)));
+    assert!(policy.is_password_expired(Some(update_on), update_on + Duration::days(90)));
+
+    let never = PasswordPolicy {
+        max_age_days: 0,
+        ..PasswordPolicy::new("never")
+    };
+    assert!(!never.is_password_expired(Some(update_on), update_on + Duration::days(1000)));
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::RowAccessPolicy;
+
+#[test]
+fn test_row_access_policy() -> Result<()> {
+    let policy = RowAccessPolicy::new(
+        "region_policy",
+        vec![("region".to_string(), "STRING".to_string())],
+        "region = 'us'",
+        vec!["admin".to_string()],
+    );
+    let ser = serde_json::to_string(&policy)?;
+
+    let de = RowAccessPolicy::try_from(ser.into_bytes())?;
+    assert_eq!(policy, de);
+    assert!(de.is_exempt("admin"));
+    assert!(!de.is_exempt("analyst"));
+
+    Ok(())
+}
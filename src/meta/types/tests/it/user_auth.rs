@@ -0,0 +1,62 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::AuthInfo;
+use common_meta_types::AuthType;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
+
+#[test]
+fn test_scram_sha256_password() -> Result<()> {
+    let auth_info = AuthInfo::new(AuthType::ScramSha256Password, &Some("pencil".to_string()))?;
+    assert_eq!(auth_info.get_type(), AuthType::ScramSha256Password);
+    assert!(auth_info.verify_password(b"pencil"));
+    assert!(!auth_info.verify_password(b"pencil2"));
+
+    // The salt is random, the same password is hashed to different values.
+    let hash1 = PasswordHashMethod::ScramSha256.hash(b"pencil");
+    let hash2 = PasswordHashMethod::ScramSha256.hash(b"pencil");
+    assert_ne!(hash1, hash2);
+    assert!(PasswordHashMethod::ScramSha256.verify(&hash1, b"pencil"));
+    assert!(PasswordHashMethod::ScramSha256.verify(&hash2, b"pencil"));
+    assert!(!PasswordHashMethod::ScramSha256.verify(b"bad hash", b"pencil"));
+
+    assert!(PasswordHashMethod::Sha256.verify(&PasswordHashMethod::Sha256.hash(b"a"), b"a"));
+    Ok(())
+}
+
+#[test]
+fn test_scram_sha256_exchange() -> Result<()> {
+    // The example exchange of RFC 7677.
+    let salt = hex::decode("5b6d99689d12358eeca04b141236fa81").unwrap();
+    let secret = ScramSha256Secret::new(b"pencil", &salt, 4096);
+    assert_eq!(ScramSha256Secret::decode(&secret.encode())?, secret);
+
+    let auth_message = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+        r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+        c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    let client_proof =
+        hex::decode("747cdb65aa56224e2352137e52d7bdcad6a0f738df30782caa69a2cfb0277554").unwrap();
+    assert!(secret.verify_client_proof(auth_message.as_bytes(), &client_proof));
+    assert!(!secret.verify_client_proof(b"other message", &client_proof));
+
+    let server_signature =
+        hex::decode("eabae24d1062db75a9451ff0b6ea7e98c8546549ff741e672d3251b2397de46e").unwrap();
+    assert_eq!(
+        secret.server_signature(auth_message.as_bytes()).to_vec(),
+        server_signature
+    );
+    Ok(())
+}
@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono::Duration;
+use chrono::Utc;
 use common_exception::exception::Result;
 use common_meta_types::AuthInfo;
 use common_meta_types::PasswordHashMethod;
 use common_meta_types::UserInfo;
+use common_meta_types::MAX_PASSWORD_HISTORY;
 
 #[test]
 fn test_user_info() -> Result<()> {
@@ -49,3 +52,36 @@ fn test_user_info() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_user_info_password_history() -> Result<()> {
+    let mut user = UserInfo::new("u1", "%", AuthInfo::Password {
+        hash_value: Vec::from("pwd1"),
+        hash_method: PasswordHashMethod::Sha256,
+    });
+    assert!(user.password_update_on.is_none());
+
+    let now = Utc::now();
+    for i in 2..(MAX_PASSWORD_HISTORY + 4) {
+        user.update_auth_info(
+            AuthInfo::Password {
+                hash_value: Vec::from(format!("pwd{}", i)),
+                hash_method: PasswordHashMethod::Sha256,
+            },
+            now,
+        );
+    }
+    assert_eq!(user.history_auth_infos.len(), MAX_PASSWORD_HISTORY);
+    assert_eq!(
+        user.history_auth_infos.last().unwrap().get_password(),
+        Some(Vec::from(format!("pwd{}", MAX_PASSWORD_HISTORY + 2)))
+    );
+    assert_eq!(user.password_update_on, Some(now));
+
+    assert!(!user.is_locked(now));
+    user.lockout_time = Some(now + Duration::minutes(15));
+    assert!(user.is_locked(now));
+    assert!(!user.is_locked(now + Duration::minutes(15)));
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::WorkloadGroup;
+
+#[test]
+fn test_workload_group() -> Result<()> {
+    let mut group = WorkloadGroup::new("etl");
+    group.max_concurrency = 2;
+    group.memory_quota = 1024 * 1024 * 1024;
+    group.queue_timeout = 60;
+    group.users = vec!["loader".to_string()];
+    group.roles = vec!["etl_role".to_string()];
+    let ser = serde_json::to_string(&group)?;
+
+    let de = WorkloadGroup::try_from(ser.into_bytes())?;
+    assert_eq!(group, de);
+    assert!(de.is_assigned_to_user("loader"));
+    assert!(!de.is_assigned_to_user("analyst"));
+    assert!(de.is_assigned_to_role("etl_role"));
+    assert!(!de.is_assigned_to_role("public"));
+
+    Ok(())
+}
@@ -1343,6 +1343,32 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let action_format_ctx = AstFormatContext::with_children(action_name, 1);
                 FormatTreeNode::with_children(action_format_ctx, vec![point_node])
             }
+            AlterTableAction::AddRowAccessPolicy { policy, columns } => {
+                let mut children = Vec::with_capacity(columns.len());
+                for column in columns.iter() {
+                    self.visit_identifier(column);
+                    children.push(self.children.pop().unwrap());
+                }
+                let action_name = format!("Action AddRowAccessPolicy {}", policy);
+                let action_format_ctx =
+                    AstFormatContext::with_children(action_name, children.len());
+                FormatTreeNode::with_children(action_format_ctx, children)
+            }
+            AlterTableAction::DropRowAccessPolicy { policy } => {
+                let action_name = format!("Action DropRowAccessPolicy {}", policy);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::ModifyColumnMaskingPolicy { column, policy } => {
+                self.visit_identifier(column);
+                let column_node = self.children.pop().unwrap();
+                let action_name = match policy {
+                    Some(policy) => format!("Action SetMaskingPolicy {}", policy),
+                    None => "Action UnsetMaskingPolicy".to_string(),
+                };
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![column_node])
+            }
         };
 
         let name = "AlterTable".to_string();
@@ -1444,6 +1470,57 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_materialized_view(&mut self, stmt: &'ast CreateMaterializedViewStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
+        let view_child = self.children.pop().unwrap();
+        self.visit_query(&stmt.query);
+        let query_child = self.children.pop().unwrap();
+
+        let name = "CreateMaterializedView".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![view_child, query_child]);
+        self.children.push(node);
+    }
+
+    fn visit_refresh_materialized_view(&mut self, stmt: &'ast RefreshMaterializedViewStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.view);
+        let child = self.children.pop().unwrap();
+
+        let name = "RefreshMaterializedView".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_inverted_index(&mut self, stmt: &'ast CreateInvertedIndexStmt<'ast>) {
+        let index_name_format_ctx =
+            AstFormatContext::new(format!("IndexIdentifier {}", stmt.index_name));
+        let index_child = FormatTreeNode::new(index_name_format_ctx);
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+        self.visit_identifier(&stmt.column);
+        let column_child = self.children.pop().unwrap();
+
+        let name = "CreateInvertedIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 3);
+        let node =
+            FormatTreeNode::with_children(format_ctx, vec![index_child, table_child, column_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_inverted_index(&mut self, stmt: &'ast DropInvertedIndexStmt<'ast>) {
+        let index_name_format_ctx =
+            AstFormatContext::new(format!("IndexIdentifier {}", stmt.index_name));
+        let index_child = FormatTreeNode::new(index_name_format_ctx);
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "DropInvertedIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![index_child, table_child]);
+        self.children.push(node);
+    }
+
     fn visit_show_users(&mut self) {
         let name = "ShowUsers".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -1542,6 +1619,17 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_unlock_user(&mut self, user: &'ast UserIdentity) {
+        let user_name = format!("User {}", user);
+        let user_format_ctx = AstFormatContext::new(user_name);
+        let child = FormatTreeNode::new(user_format_ctx);
+
+        let name = "UnlockUser".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_show_roles(&mut self) {
         let name = "ShowRoles".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -1760,6 +1848,183 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_row_access_policy(&mut self, stmt: &'ast CreateRowAccessPolicyStmt<'ast>) {
+        let mut children = Vec::new();
+        let name_format_ctx =
+            AstFormatContext::new(format!("RowAccessPolicyIdentifier {}", stmt.name));
+        children.push(FormatTreeNode::new(name_format_ctx));
+        let mut parameters_children = Vec::with_capacity(stmt.parameters.len());
+        for (parameter, type_name) in stmt.parameters.iter() {
+            let parameter_format_ctx =
+                AstFormatContext::new(format!("Parameter {} {}", parameter, type_name));
+            parameters_children.push(FormatTreeNode::new(parameter_format_ctx));
+        }
+        let parameters_name = "RowAccessPolicyParameters".to_string();
+        let parameters_format_ctx =
+            AstFormatContext::with_children(parameters_name, parameters_children.len());
+        children.push(FormatTreeNode::with_children(
+            parameters_format_ctx,
+            parameters_children,
+        ));
+        self.visit_expr(&stmt.definition);
+        let definition_child = self.children.pop().unwrap();
+        let definition_name = "RowAccessPolicyDefinition".to_string();
+        let definition_format_ctx = AstFormatContext::with_children(definition_name, 1);
+        children.push(FormatTreeNode::with_children(definition_format_ctx, vec![
+            definition_child,
+        ]));
+        if !stmt.exempt_roles.is_empty() {
+            let exempt_roles_name = format!("ExemptRoles {}", stmt.exempt_roles.join(", "));
+            let exempt_roles_format_ctx = AstFormatContext::new(exempt_roles_name);
+            children.push(FormatTreeNode::new(exempt_roles_format_ctx));
+        }
+
+        let name = "CreateRowAccessPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_row_access_policy(&mut self, stmt: &'ast DropRowAccessPolicyStmt<'ast>) {
+        let name_format_ctx =
+            AstFormatContext::new(format!("RowAccessPolicyIdentifier {}", stmt.name));
+        let child = FormatTreeNode::new(name_format_ctx);
+
+        let name = "DropRowAccessPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_masking_policy(&mut self, stmt: &'ast CreateMaskingPolicyStmt<'ast>) {
+        let mut children = Vec::new();
+        let name_format_ctx =
+            AstFormatContext::new(format!("MaskingPolicyIdentifier {}", stmt.name));
+        children.push(FormatTreeNode::new(name_format_ctx));
+        let mut parameters_children = Vec::with_capacity(stmt.parameters.len());
+        for (parameter, type_name) in stmt.parameters.iter() {
+            let parameter_format_ctx =
+                AstFormatContext::new(format!("Parameter {} {}", parameter, type_name));
+            parameters_children.push(FormatTreeNode::new(parameter_format_ctx));
+        }
+        let parameters_name = "MaskingPolicyParameters".to_string();
+        let parameters_format_ctx =
+            AstFormatContext::with_children(parameters_name, parameters_children.len());
+        children.push(FormatTreeNode::with_children(
+            parameters_format_ctx,
+            parameters_children,
+        ));
+        let return_type_format_ctx =
+            AstFormatContext::new(format!("ReturnType {}", stmt.return_type));
+        children.push(FormatTreeNode::new(return_type_format_ctx));
+        self.visit_expr(&stmt.definition);
+        let definition_child = self.children.pop().unwrap();
+        let definition_name = "MaskingPolicyDefinition".to_string();
+        let definition_format_ctx = AstFormatContext::with_children(definition_name, 1);
+        children.push(FormatTreeNode::with_children(definition_format_ctx, vec![
+            definition_child,
+        ]));
+
+        let name = "CreateMaskingPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_masking_policy(&mut self, stmt: &'ast DropMaskingPolicyStmt<'ast>) {
+        let name_format_ctx =
+            AstFormatContext::new(format!("MaskingPolicyIdentifier {}", stmt.name));
+        let child = FormatTreeNode::new(name_format_ctx);
+
+        let name = "DropMaskingPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_workload_group(&mut self, stmt: &'ast CreateWorkloadGroupStmt<'ast>) {
+        let mut children = Vec::new();
+        let name_format_ctx =
+            AstFormatContext::new(format!("WorkloadGroupIdentifier {}", stmt.name));
+        children.push(FormatTreeNode::new(name_format_ctx));
+        let mut options_children = Vec::with_capacity(stmt.options.len());
+        for (option, value) in stmt.options.iter() {
+            let option_format_ctx = AstFormatContext::new(format!("Option {} {}", option, value));
+            options_children.push(FormatTreeNode::new(option_format_ctx));
+        }
+        let options_name = "WorkloadGroupOptions".to_string();
+        let options_format_ctx =
+            AstFormatContext::with_children(options_name, options_children.len());
+        children.push(FormatTreeNode::with_children(
+            options_format_ctx,
+            options_children,
+        ));
+        if !stmt.users.is_empty() {
+            let users_format_ctx =
+                AstFormatContext::new(format!("Users {}", stmt.users.join(", ")));
+            children.push(FormatTreeNode::new(users_format_ctx));
+        }
+        if !stmt.roles.is_empty() {
+            let roles_format_ctx =
+                AstFormatContext::new(format!("Roles {}", stmt.roles.join(", ")));
+            children.push(FormatTreeNode::new(roles_format_ctx));
+        }
+
+        let name = "CreateWorkloadGroup".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_workload_group(&mut self, stmt: &'ast DropWorkloadGroupStmt<'ast>) {
+        let name_format_ctx =
+            AstFormatContext::new(format!("WorkloadGroupIdentifier {}", stmt.name));
+        let child = FormatTreeNode::new(name_format_ctx);
+
+        let name = "DropWorkloadGroup".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_password_policy(&mut self, stmt: &'ast CreatePasswordPolicyStmt<'ast>) {
+        let mut children = Vec::new();
+        let name_format_ctx =
+            AstFormatContext::new(format!("PasswordPolicyIdentifier {}", stmt.name));
+        children.push(FormatTreeNode::new(name_format_ctx));
+        if !stmt.options.is_empty() {
+            let mut options_children = Vec::with_capacity(stmt.options.len());
+            for (option, value) in stmt.options.iter() {
+                let option_format_ctx =
+                    AstFormatContext::new(format!("Option {} {}", option, value));
+                options_children.push(FormatTreeNode::new(option_format_ctx));
+            }
+            let options_name = "PasswordPolicyOptions".to_string();
+            let options_format_ctx =
+                AstFormatContext::with_children(options_name, options_children.len());
+            children.push(FormatTreeNode::with_children(
+                options_format_ctx,
+                options_children,
+            ));
+        }
+
+        let name = "CreatePasswordPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_password_policy(&mut self, stmt: &'ast DropPasswordPolicyStmt<'ast>) {
+        let name_format_ctx =
+            AstFormatContext::new(format!("PasswordPolicyIdentifier {}", stmt.name));
+        let child = FormatTreeNode::new(name_format_ctx);
+
+        let name = "DropPasswordPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_create_stage(&mut self, stmt: &'ast CreateStageStmt) {
         let mut children = Vec::new();
         let stage_name_format_ctx = AstFormatContext::new(format!("StageName {}", stmt.stage_name));
@@ -185,6 +185,30 @@ pub(crate) fn pretty_alter_table_action(action: AlterTableAction) -> RcDoc {
             TimeTravelPoint::Snapshot(sid) => RcDoc::text(format!(" AT (SNAPSHOT => {sid})")),
             TimeTravelPoint::Timestamp(ts) => RcDoc::text(format!(" AT (TIMESTAMP => {ts})")),
         },
+        AlterTableAction::AddRowAccessPolicy { policy, columns } => RcDoc::line()
+            .append(RcDoc::text("ADD ROW ACCESS POLICY "))
+            .append(RcDoc::text(policy.to_string()))
+            .append(RcDoc::text(" ON "))
+            .append(parenthenized(
+                interweave_comma(
+                    columns
+                        .into_iter()
+                        .map(|column| RcDoc::text(column.to_string())),
+                )
+                .group(),
+            )),
+        AlterTableAction::DropRowAccessPolicy { policy } => RcDoc::line()
+            .append(RcDoc::text("DROP ROW ACCESS POLICY "))
+            .append(RcDoc::text(policy.to_string())),
+        AlterTableAction::ModifyColumnMaskingPolicy { column, policy } => RcDoc::line()
+            .append(RcDoc::text("MODIFY COLUMN "))
+            .append(RcDoc::text(column.to_string()))
+            .append(match policy {
+                Some(policy) => {
+                    RcDoc::text(" SET MASKING POLICY ").append(RcDoc::text(policy.to_string()))
+                }
+                None => RcDoc::text(" UNSET MASKING POLICY"),
+            }),
     }
 }
 
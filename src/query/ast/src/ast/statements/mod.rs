@@ -17,9 +17,13 @@ mod catalog;
 mod copy;
 mod database;
 mod explain;
+mod index;
 mod insert;
 mod kill;
+mod masking_policy;
+mod password_policy;
 mod presign;
+mod row_access_policy;
 mod share;
 mod show;
 mod stage;
@@ -29,15 +33,20 @@ mod unset;
 mod update;
 mod user;
 mod view;
+mod workload_group;
 
 pub use call::*;
 pub use catalog::*;
 pub use copy::*;
 pub use database::*;
 pub use explain::*;
+pub use index::*;
 pub use insert::*;
 pub use kill::*;
+pub use masking_policy::*;
+pub use password_policy::*;
 pub use presign::*;
+pub use row_access_policy::*;
 pub use share::*;
 pub use show::*;
 pub use stage::*;
@@ -47,3 +56,4 @@ pub use unset::*;
 pub use update::*;
 pub use user::*;
 pub use view::*;
+pub use workload_group::*;
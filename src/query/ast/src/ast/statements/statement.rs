@@ -112,6 +112,12 @@ pub enum Statement<'a> {
     CreateView(CreateViewStmt<'a>),
     AlterView(AlterViewStmt<'a>),
     DropView(DropViewStmt<'a>),
+    CreateMaterializedView(CreateMaterializedViewStmt<'a>),
+    RefreshMaterializedView(RefreshMaterializedViewStmt<'a>),
+
+    // Indexes
+    CreateInvertedIndex(CreateInvertedIndexStmt<'a>),
+    DropInvertedIndex(DropInvertedIndexStmt<'a>),
 
     // User
     ShowUsers,
@@ -121,6 +127,9 @@ pub enum Statement<'a> {
         if_exists: bool,
         user: UserIdentity,
     },
+    UnlockUser {
+        user: UserIdentity,
+    },
     ShowRoles,
     CreateRole {
         if_not_exists: bool,
@@ -155,6 +164,22 @@ pub enum Statement<'a> {
         description: Option<String>,
     },
 
+    // Row access policies
+    CreateRowAccessPolicy(CreateRowAccessPolicyStmt<'a>),
+    DropRowAccessPolicy(DropRowAccessPolicyStmt<'a>),
+
+    // Masking policies
+    CreateMaskingPolicy(CreateMaskingPolicyStmt<'a>),
+    DropMaskingPolicy(DropMaskingPolicyStmt<'a>),
+
+    // Workload groups
+    CreateWorkloadGroup(CreateWorkloadGroupStmt<'a>),
+    DropWorkloadGroup(DropWorkloadGroupStmt<'a>),
+
+    // Password policies
+    CreatePasswordPolicy(CreatePasswordPolicyStmt<'a>),
+    DropPasswordPolicy(DropPasswordPolicyStmt<'a>),
+
     // Stages
     CreateStage(CreateStageStmt),
     ShowStages,
@@ -301,6 +326,10 @@ impl<'a> Display for Statement<'a> {
             Statement::CreateView(stmt) => write!(f, "{stmt}")?,
             Statement::AlterView(stmt) => write!(f, "{stmt}")?,
             Statement::DropView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateMaterializedView(stmt) => write!(f, "{stmt}")?,
+            Statement::RefreshMaterializedView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateInvertedIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::DropInvertedIndex(stmt) => write!(f, "{stmt}")?,
             Statement::ShowUsers => write!(f, "SHOW USERS")?,
             Statement::ShowRoles => write!(f, "SHOW ROLES")?,
             Statement::CreateUser(stmt) => write!(f, "{stmt}")?,
@@ -312,6 +341,7 @@ impl<'a> Display for Statement<'a> {
                 }
                 write!(f, " {user}")?;
             }
+            Statement::UnlockUser { user } => write!(f, "ALTER USER {user} UNLOCK")?,
             Statement::CreateRole {
                 if_not_exists,
                 role_name: role,
@@ -382,6 +412,14 @@ impl<'a> Display for Statement<'a> {
                     write!(f, " DESC = '{description}'")?;
                 }
             }
+            Statement::CreateRowAccessPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropRowAccessPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateMaskingPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropMaskingPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateWorkloadGroup(stmt) => write!(f, "{stmt}")?,
+            Statement::DropWorkloadGroup(stmt) => write!(f, "{stmt}")?,
+            Statement::CreatePasswordPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropPasswordPolicy(stmt) => write!(f, "{stmt}")?,
             Statement::ListStage { location, pattern } => {
                 write!(f, "LIST @{location}")?;
                 if !pattern.is_empty() {
@@ -293,6 +293,18 @@ pub enum AlterTableAction<'a> {
     RevertTo {
         point: TimeTravelPoint<'a>,
     },
+    AddRowAccessPolicy {
+        policy: Identifier<'a>,
+        columns: Vec<Identifier<'a>>,
+    },
+    DropRowAccessPolicy {
+        policy: Identifier<'a>,
+    },
+    /// `MODIFY COLUMN c SET MASKING POLICY p`, or `UNSET MASKING POLICY` if `policy` is None.
+    ModifyColumnMaskingPolicy {
+        column: Identifier<'a>,
+        policy: Option<Identifier<'a>>,
+    },
 }
 
 impl Display for AlterTableAction<'_> {
@@ -325,6 +337,18 @@ impl Display for AlterTableAction<'_> {
                 write!(f, "REVERT TO {}", point)?;
                 Ok(())
             }
+            AlterTableAction::AddRowAccessPolicy { policy, columns } => {
+                write!(f, "ADD ROW ACCESS POLICY {policy} ON (")?;
+                write_comma_separated_list(f, columns)?;
+                write!(f, ")")
+            }
+            AlterTableAction::DropRowAccessPolicy { policy } => {
+                write!(f, "DROP ROW ACCESS POLICY {policy}")
+            }
+            AlterTableAction::ModifyColumnMaskingPolicy { column, policy } => match policy {
+                Some(policy) => write!(f, "MODIFY COLUMN {column} SET MASKING POLICY {policy}"),
+                None => write!(f, "MODIFY COLUMN {column} UNSET MASKING POLICY"),
+            },
         }
     }
 }
@@ -489,6 +513,11 @@ pub enum CompactTarget {
 pub enum OptimizeTableAction<'a> {
     All,
     Purge,
+    /// Removes the files under the table prefix that no retained snapshot reaches.
+    PurgeOrphans {
+        older_than: Option<Expr<'a>>,
+        dry_run: bool,
+    },
     Compact {
         target: CompactTarget,
         limit: Option<Expr<'a>>,
@@ -500,6 +529,19 @@ impl<'a> Display for OptimizeTableAction<'a> {
         match self {
             OptimizeTableAction::All => write!(f, "ALL"),
             OptimizeTableAction::Purge => write!(f, "PURGE"),
+            OptimizeTableAction::PurgeOrphans {
+                older_than,
+                dry_run,
+            } => {
+                write!(f, "PURGE ORPHANS")?;
+                if let Some(older_than) = older_than {
+                    write!(f, " OLDER THAN {older_than}")?;
+                }
+                if *dry_run {
+                    write!(f, " DRY RUN")?;
+                }
+                Ok(())
+            }
             OptimizeTableAction::Compact { target, limit } => {
                 match target {
                     CompactTarget::Block => {
@@ -158,6 +158,10 @@ pub enum AccountMgrLevel {
 pub enum UserOptionItem {
     TenantSetting(bool),
     DefaultRole(String),
+    RequireSsl(bool),
+    SetPasswordPolicy(String),
+    UnsetPasswordPolicy,
+    MustChangePassword(bool),
 }
 
 impl UserOptionItem {
@@ -167,6 +171,12 @@ impl UserOptionItem {
                 option.switch_option_flag(UserOptionFlag::TenantSetting, *enabled);
             }
             Self::DefaultRole(v) => option.set_default_role(Some(v.clone())),
+            Self::RequireSsl(enabled) => {
+                option.switch_option_flag(UserOptionFlag::RequireSsl, *enabled);
+            }
+            Self::SetPasswordPolicy(v) => option.set_password_policy(Some(v.clone())),
+            Self::UnsetPasswordPolicy => option.set_password_policy(None),
+            Self::MustChangePassword(v) => option.set_must_change_password(*v),
         }
     }
 }
@@ -229,6 +239,17 @@ impl Display for UserOptionItem {
             UserOptionItem::TenantSetting(true) => write!(f, "TENANTSETTING"),
             UserOptionItem::TenantSetting(false) => write!(f, "NOTENANTSETTING"),
             UserOptionItem::DefaultRole(v) => write!(f, "DEFAULT_ROLE = '{}'", v),
+            UserOptionItem::RequireSsl(true) => write!(f, "REQUIRE SSL"),
+            UserOptionItem::RequireSsl(false) => write!(f, "REQUIRE NONE"),
+            UserOptionItem::SetPasswordPolicy(v) => write!(f, "PASSWORD_POLICY = '{}'", v),
+            UserOptionItem::UnsetPasswordPolicy => write!(f, "UNSET PASSWORD_POLICY"),
+            UserOptionItem::MustChangePassword(v) => {
+                write!(
+                    f,
+                    "MUST_CHANGE_PASSWORD = {}",
+                    if *v { "TRUE" } else { "FALSE" }
+                )
+            }
         }
     }
 }
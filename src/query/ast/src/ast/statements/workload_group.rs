@@ -0,0 +1,71 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_quoted_comma_separated_list;
+use crate::ast::Identifier;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateWorkloadGroupStmt<'a> {
+    pub if_not_exists: bool,
+    pub name: Identifier<'a>,
+    pub options: Vec<(Identifier<'a>, u64)>,
+    pub users: Vec<String>,
+    pub roles: Vec<String>,
+}
+
+impl Display for CreateWorkloadGroupStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE WORKLOAD GROUP ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} WITH ", self.name)?;
+        for (i, (option, value)) in self.options.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{option} = {value}")?;
+        }
+        if !self.users.is_empty() {
+            write!(f, " USERS = (")?;
+            write_quoted_comma_separated_list(f, &self.users)?;
+            write!(f, ")")?;
+        }
+        if !self.roles.is_empty() {
+            write!(f, " ROLES = (")?;
+            write_quoted_comma_separated_list(f, &self.roles)?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropWorkloadGroupStmt<'a> {
+    pub if_exists: bool,
+    pub name: Identifier<'a>,
+}
+
+impl Display for DropWorkloadGroupStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP WORKLOAD GROUP ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
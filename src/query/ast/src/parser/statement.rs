@@ -595,6 +595,65 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_materialized_view = map(
+        rule! {
+            CREATE ~ MATERIALIZED ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #peroid_separated_idents_1_to_3
+            ~ AS ~ #query
+        },
+        |(_, _, _, opt_if_not_exists, (catalog, database, view), _, query)| {
+            Statement::CreateMaterializedView(CreateMaterializedViewStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                view,
+                query: Box::new(query),
+            })
+        },
+    );
+    let refresh_materialized_view = map(
+        rule! {
+            REFRESH ~ MATERIALIZED ~ VIEW ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, (catalog, database, view))| {
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStmt {
+                catalog,
+                database,
+                view,
+            })
+        },
+    );
+    let create_inverted_index = map(
+        rule! {
+            CREATE ~ INVERTED ~ INDEX ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3 ~ "(" ~ #ident ~ ")"
+        },
+        |(_, _, _, opt_if_not_exists, index_name, _, (catalog, database, table), _, column, _)| {
+            Statement::CreateInvertedIndex(CreateInvertedIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index_name,
+                catalog,
+                database,
+                table,
+                column,
+            })
+        },
+    );
+    let drop_inverted_index = map(
+        rule! {
+            DROP ~ INVERTED ~ INDEX ~ ( IF ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, opt_if_exists, index_name, _, (catalog, database, table))| {
+            Statement::DropInvertedIndex(DropInvertedIndexStmt {
+                if_exists: opt_if_exists.is_some(),
+                index_name,
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let show_users = value(Statement::ShowUsers, rule! { SHOW ~ USERS });
     let create_user = map(
         rule! {
@@ -617,6 +676,12 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let unlock_user = map(
+        rule! {
+            ALTER ~ USER ~ #user_identity ~ UNLOCK
+        },
+        |(_, _, user, _)| Statement::UnlockUser { user },
+    );
     let alter_user = map(
         rule! {
             ALTER ~ USER ~ ( #map(rule! { USER ~ "(" ~ ")" }, |_| None) | #map(user_identity, Some) )
@@ -760,6 +825,139 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
+    let create_row_access_policy = map(
+        rule! {
+            CREATE ~ ROW ~ ACCESS ~ POLICY ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ AS ~ "(" ~ #comma_separated_list1(policy_parameter) ~ ")"
+            ~ RETURNS ~ BOOLEAN ~ "->" ~ #expr
+            ~ ( EXEMPT ~ ^ROLES ~ ^"=" ~ ^"(" ~ ^#comma_separated_list1(literal_string) ~ ^")" )?
+        },
+        |(
+            _,
+            _,
+            _,
+            _,
+            opt_if_not_exists,
+            name,
+            _,
+            _,
+            parameters,
+            _,
+            _,
+            _,
+            _,
+            definition,
+            opt_exempt_roles,
+        )| {
+            Statement::CreateRowAccessPolicy(CreateRowAccessPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                parameters,
+                definition: Box::new(definition),
+                exempt_roles: opt_exempt_roles
+                    .map(|(_, _, _, _, roles, _)| roles)
+                    .unwrap_or_default(),
+            })
+        },
+    );
+    let drop_row_access_policy = map(
+        rule! {
+            DROP ~ ROW ~ ACCESS ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, _, opt_if_exists, name)| {
+            Statement::DropRowAccessPolicy(DropRowAccessPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+
+    let create_masking_policy = map(
+        rule! {
+            CREATE ~ MASKING ~ POLICY ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ AS ~ "(" ~ #comma_separated_list1(policy_parameter) ~ ")"
+            ~ RETURNS ~ #type_name ~ "->" ~ #expr
+        },
+        |(_, _, _, opt_if_not_exists, name, _, _, parameters, _, _, return_type, _, definition)| {
+            Statement::CreateMaskingPolicy(CreateMaskingPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                parameters,
+                return_type,
+                definition: Box::new(definition),
+            })
+        },
+    );
+    let drop_masking_policy = map(
+        rule! {
+            DROP ~ MASKING ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropMaskingPolicy(DropMaskingPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+
+    let create_workload_group = map(
+        rule! {
+            CREATE ~ WORKLOAD ~ GROUP ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ WITH ~ #comma_separated_list1(workload_group_option)
+            ~ ( USERS ~ ^"=" ~ ^"(" ~ ^#comma_separated_list1(literal_string) ~ ^")" )?
+            ~ ( ROLES ~ ^"=" ~ ^"(" ~ ^#comma_separated_list1(literal_string) ~ ^")" )?
+        },
+        |(_, _, _, opt_if_not_exists, name, _, options, opt_users, opt_roles)| {
+            Statement::CreateWorkloadGroup(CreateWorkloadGroupStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                options,
+                users: opt_users
+                    .map(|(_, _, _, users, _)| users)
+                    .unwrap_or_default(),
+                roles: opt_roles
+                    .map(|(_, _, _, roles, _)| roles)
+                    .unwrap_or_default(),
+            })
+        },
+    );
+    let drop_workload_group = map(
+        rule! {
+            DROP ~ WORKLOAD ~ GROUP ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropWorkloadGroup(DropWorkloadGroupStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+
+    let create_password_policy = map(
+        rule! {
+            CREATE ~ PASSWORD ~ POLICY ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ ( WITH ~ ^#comma_separated_list1(password_policy_option) )?
+        },
+        |(_, _, _, opt_if_not_exists, name, opt_options)| {
+            Statement::CreatePasswordPolicy(CreatePasswordPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                options: opt_options.map(|(_, options)| options).unwrap_or_default(),
+            })
+        },
+    );
+    let drop_password_policy = map(
+        rule! {
+            DROP ~ PASSWORD ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropPasswordPolicy(DropPasswordPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+
     // stages
     let create_stage = map_res(
         rule! {
@@ -1014,7 +1212,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #alter_table : "`ALTER TABLE [<database>.]<table> <action>`"
             | #rename_table : "`RENAME TABLE [<database>.]<table> TO <new_table>`"
             | #truncate_table : "`TRUNCATE TABLE [<database>.]<table> [PURGE]`"
-            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT [SEGMENT])`"
+            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE [ORPHANS [OLDER THAN <interval>] [DRY RUN]] | COMPACT [SEGMENT])`"
             | #analyze_table : "`ANALYZE TABLE [<database>.]<table>`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
         ),
@@ -1022,10 +1220,17 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
             | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
+            | #create_materialized_view : "`CREATE MATERIALIZED VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
+            | #refresh_materialized_view : "`REFRESH MATERIALIZED VIEW [<database>.]<view>`"
+        ),
+        rule!(
+            #create_inverted_index : "`CREATE INVERTED INDEX [IF NOT EXISTS] <index> ON [<database>.]<table>(<column>)`"
+            | #drop_inverted_index : "`DROP INVERTED INDEX [IF EXISTS] <index> ON [<database>.]<table>`"
         ),
         rule!(
             #show_users : "`SHOW USERS`"
             | #create_user : "`CREATE USER [IF NOT EXISTS] '<username>'@'hostname' IDENTIFIED [WITH <auth_type>] [BY <password>] [WITH <user_option>, ...]`"
+            | #unlock_user : "`ALTER USER '<username>'@'hostname' UNLOCK`"
             | #alter_user : "`ALTER USER ('<username>'@'hostname' | USER()) [IDENTIFIED [WITH <auth_type>] [BY <password>]] [WITH <user_option>, ...]`"
             | #drop_user : "`DROP USER [IF EXISTS] '<username>'@'hostname'`"
             | #show_roles : "`SHOW ROLES`"
@@ -1035,6 +1240,16 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #drop_udf : "`DROP FUNCTION [IF EXISTS] <udf_name>`"
             | #alter_udf : "`ALTER FUNCTION <udf_name> (<parameter>, ...) -> <definition_expr> [DESC = <description>]`"
         ),
+        rule!(
+            #create_row_access_policy : "`CREATE ROW ACCESS POLICY [IF NOT EXISTS] <name> AS (<parameter> <type>, ...) RETURNS BOOLEAN -> <definition expr> [EXEMPT ROLES = ('<role_name>', ...)]`"
+            | #drop_row_access_policy : "`DROP ROW ACCESS POLICY [IF EXISTS] <name>`"
+            | #create_masking_policy : "`CREATE MASKING POLICY [IF NOT EXISTS] <name> AS (<parameter> <type>, ...) RETURNS <type> -> <definition expr>`"
+            | #drop_masking_policy : "`DROP MASKING POLICY [IF EXISTS] <name>`"
+            | #create_workload_group : "`CREATE WORKLOAD GROUP [IF NOT EXISTS] <name> WITH <option> = <value>, ... [USERS = ('<user_name>', ...)] [ROLES = ('<role_name>', ...)]`"
+            | #drop_workload_group : "`DROP WORKLOAD GROUP [IF EXISTS] <name>`"
+            | #create_password_policy : "`CREATE PASSWORD POLICY [IF NOT EXISTS] <name> [WITH <option> = <value>, ...]`"
+            | #drop_password_policy : "`DROP PASSWORD POLICY [IF EXISTS] <name>`"
+        ),
         rule!(
             #create_stage: "`CREATE STAGE [ IF NOT EXISTS ] <stage_name>
                 [ FILE_FORMAT = ( { TYPE = { CSV | PARQUET } [ formatTypeOptions ] ) } ]
@@ -1471,18 +1686,87 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         |(_, _, point)| AlterTableAction::RevertTo { point },
     );
 
+    let add_row_access_policy = map(
+        rule! {
+            ADD ~ ROW ~ ACCESS ~ POLICY ~ #ident ~ ON ~ "(" ~ #comma_separated_list1(ident) ~ ")"
+        },
+        |(_, _, _, _, policy, _, _, columns, _)| AlterTableAction::AddRowAccessPolicy {
+            policy,
+            columns,
+        },
+    );
+
+    let drop_row_access_policy = map(
+        rule! {
+            DROP ~ ROW ~ ACCESS ~ POLICY ~ #ident
+        },
+        |(_, _, _, _, policy)| AlterTableAction::DropRowAccessPolicy { policy },
+    );
+
+    let set_masking_policy = map(
+        rule! {
+            SET ~ MASKING ~ POLICY ~ #ident
+        },
+        |(_, _, _, policy)| Some(policy),
+    );
+    let unset_masking_policy = map(
+        rule! {
+            UNSET ~ MASKING ~ POLICY
+        },
+        |_| None,
+    );
+    let modify_column_masking_policy = map(
+        rule! {
+            MODIFY ~ COLUMN ~ #ident ~ ( #set_masking_policy | #unset_masking_policy )
+        },
+        |(_, _, column, policy)| AlterTableAction::ModifyColumnMaskingPolicy { column, policy },
+    );
+
     rule!(
         #rename_table
         | #alter_table_cluster_key
         | #drop_table_cluster_key
         | #recluster_table
         | #revert_table
+        | #add_row_access_policy
+        | #drop_row_access_policy
+        | #modify_column_masking_policy
+    )(i)
+}
+
+pub fn policy_parameter(i: Input) -> IResult<(Identifier, TypeName)> {
+    rule! { #ident ~ #type_name }
+    (i)
+}
+
+pub fn workload_group_option(i: Input) -> IResult<(Identifier, u64)> {
+    map(
+        rule! {
+            #ident ~ "=" ~ #literal_u64
+        },
+        |(option, _, value)| (option, value),
+    )(i)
+}
+
+pub fn password_policy_option(i: Input) -> IResult<(Identifier, u64)> {
+    map(
+        rule! {
+            #ident ~ "=" ~ #literal_u64
+        },
+        |(option, _, value)| (option, value),
     )(i)
 }
 
 pub fn optimize_table_action(i: Input) -> IResult<OptimizeTableAction> {
     alt((
         value(OptimizeTableAction::All, rule! { ALL }),
+        map(
+            rule! { PURGE ~ ORPHANS ~ ( OLDER ~ THAN ~ ^#expr )? ~ ( DRY ~ RUN )? },
+            |(_, _, opt_older_than, opt_dry_run)| OptimizeTableAction::PurgeOrphans {
+                older_than: opt_older_than.map(|(_, _, older_than)| older_than),
+                dry_run: opt_dry_run.is_some(),
+            },
+        ),
         value(OptimizeTableAction::Purge, rule! { PURGE }),
         map(
             rule! { COMPACT ~ (SEGMENT)? ~ ( LIMIT ~ ^#expr )?},
@@ -1657,13 +1941,33 @@ pub fn user_option(i: Input) -> IResult<UserOptionItem> {
         },
         |(_, _, role)| UserOptionItem::DefaultRole(role),
     );
+    let set_password_policy_option = map(
+        rule! {
+            "PASSWORD_POLICY" ~ "=" ~ #literal_string
+        },
+        |(_, _, policy)| UserOptionItem::SetPasswordPolicy(policy),
+    );
+    let must_change_password_option = map(
+        rule! {
+            "MUST_CHANGE_PASSWORD" ~ "=" ~ #literal_bool
+        },
+        |(_, _, must_change)| UserOptionItem::MustChangePassword(must_change),
+    );
     alt((
         value(UserOptionItem::TenantSetting(true), rule! { TENANTSETTING }),
         value(
             UserOptionItem::TenantSetting(false),
             rule! { NOTENANTSETTING },
         ),
+        value(UserOptionItem::RequireSsl(true), rule! { REQUIRE ~ SSL }),
+        value(UserOptionItem::RequireSsl(false), rule! { REQUIRE ~ NONE }),
         default_role_option,
+        set_password_policy_option,
+        value(
+            UserOptionItem::UnsetPasswordPolicy,
+            rule! { UNSET ~ "PASSWORD_POLICY" },
+        ),
+        must_change_password_option,
     ))(i)
 }
 
@@ -1686,6 +1990,10 @@ pub fn auth_type(i: Input) -> IResult<AuthType> {
         value(AuthType::NoPassword, rule! { NO_PASSWORD }),
         value(AuthType::Sha256Password, rule! { SHA256_PASSWORD }),
         value(AuthType::DoubleSha1Password, rule! { DOUBLE_SHA1_PASSWORD }),
+        value(
+            AuthType::ScramSha256Password,
+            rule! { SCRAM_SHA256_PASSWORD },
+        ),
         value(AuthType::JWT, rule! { JWT }),
     ))(i)
 }
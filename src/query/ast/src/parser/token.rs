@@ -243,6 +243,8 @@ pub enum TokenKind {
     ALL,
     #[token("ADD", ignore(ascii_case))]
     ADD,
+    #[token("ACCESS", ignore(ascii_case))]
+    ACCESS,
     #[token("ANY", ignore(ascii_case))]
     ANY,
     #[token("SOME", ignore(ascii_case))]
@@ -295,6 +297,8 @@ pub enum TokenKind {
     CENTURY,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
@@ -365,10 +369,14 @@ pub enum TokenKind {
     DOWNLOAD,
     #[token("DROP", ignore(ascii_case))]
     DROP,
+    #[token("DRY", ignore(ascii_case))]
+    DRY,
     #[token("EXCEPT", ignore(ascii_case))]
     EXCEPT,
     #[token("EXCLUDE", ignore(ascii_case))]
     EXCLUDE,
+    #[token("EXEMPT", ignore(ascii_case))]
+    EXEMPT,
     #[token("ELSE", ignore(ascii_case))]
     ELSE,
     #[token("END", ignore(ascii_case))]
@@ -453,6 +461,8 @@ pub enum TokenKind {
     IF,
     #[token("IN", ignore(ascii_case))]
     IN,
+    #[token("INDEX", ignore(ascii_case))]
+    INDEX,
     #[token("INNER", ignore(ascii_case))]
     INNER,
     #[token("INSERT", ignore(ascii_case))]
@@ -473,6 +483,8 @@ pub enum TokenKind {
     INTERVAL,
     #[token("INTO", ignore(ascii_case))]
     INTO,
+    #[token("INVERTED", ignore(ascii_case))]
+    INVERTED,
     #[token("IS", ignore(ascii_case))]
     IS,
     #[token("ISODOW", ignore(ascii_case))]
@@ -505,10 +517,14 @@ pub enum TokenKind {
     LIST,
     #[token("MAP", ignore(ascii_case))]
     MAP,
+    #[token("MASKING", ignore(ascii_case))]
+    MASKING,
     #[token("MAX_FILE_SIZE", ignore(ascii_case))]
     MAX_FILE_SIZE,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATERIALIZED", ignore(ascii_case))]
+    MATERIALIZED,
     #[token("MEMO", ignore(ascii_case))]
     MEMO,
     #[token("MEMORY", ignore(ascii_case))]
@@ -523,12 +539,16 @@ pub enum TokenKind {
     MILLISECONDS,
     #[token("MINUTE", ignore(ascii_case))]
     MINUTE,
+    #[token("MODIFY", ignore(ascii_case))]
+    MODIFY,
     #[token("MONTH", ignore(ascii_case))]
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
     NATURAL,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
+    #[token("NONE", ignore(ascii_case))]
+    NONE,
     #[token("NOT", ignore(ascii_case))]
     NOT,
     #[token("NOTENANTSETTING", ignore(ascii_case))]
@@ -541,6 +561,8 @@ pub enum TokenKind {
     OF,
     #[token("OFFSET", ignore(ascii_case))]
     OFFSET,
+    #[token("OLDER", ignore(ascii_case))]
+    OLDER,
     #[token("ON", ignore(ascii_case))]
     ON,
     #[token("OPTIMIZE", ignore(ascii_case))]
@@ -549,6 +571,8 @@ pub enum TokenKind {
     OR,
     #[token("ORDER", ignore(ascii_case))]
     ORDER,
+    #[token("ORPHANS", ignore(ascii_case))]
+    ORPHANS,
     #[token("OUTER", ignore(ascii_case))]
     OUTER,
     #[token("ON_ERROR", ignore(ascii_case))]
@@ -557,12 +581,16 @@ pub enum TokenKind {
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PASSWORD", ignore(ascii_case))]
+    PASSWORD,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
     PIPELINE,
     #[token("PLAINTEXT_PASSWORD", ignore(ascii_case))]
     PLAINTEXT_PASSWORD,
+    #[token("POLICY", ignore(ascii_case))]
+    POLICY,
     #[token("POSITION", ignore(ascii_case))]
     POSITION,
     #[token("PROCESSLIST", ignore(ascii_case))]
@@ -581,10 +609,16 @@ pub enum TokenKind {
     RECORD_DELIMITER,
     #[token("REFERENCE_USAGE", ignore(ascii_case))]
     REFERENCE_USAGE,
+    #[token("REFRESH", ignore(ascii_case))]
+    REFRESH,
     #[token("REGEXP", ignore(ascii_case))]
     REGEXP,
     #[token("RENAME", ignore(ascii_case))]
     RENAME,
+    #[token("REQUIRE", ignore(ascii_case))]
+    REQUIRE,
+    #[token("RETURNS", ignore(ascii_case))]
+    RETURNS,
     #[token("GRANT", ignore(ascii_case))]
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
@@ -605,12 +639,18 @@ pub enum TokenKind {
     RIGHT,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("RUN", ignore(ascii_case))]
+    RUN,
     #[token("RAW", ignore(ascii_case))]
     RAW,
     #[token("SCHEMA", ignore(ascii_case))]
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
     SCHEMAS,
+    #[token("SCRAM_SHA256_PASSWORD", ignore(ascii_case))]
+    SCRAM_SHA256_PASSWORD,
     #[token("SECOND", ignore(ascii_case))]
     SECOND,
     #[token("SELECT", ignore(ascii_case))]
@@ -643,6 +683,8 @@ pub enum TokenKind {
     SNAPSHOT,
     #[token("SPLIT_SIZE", ignore(ascii_case))]
     SPLIT_SIZE,
+    #[token("SSL", ignore(ascii_case))]
+    SSL,
     #[token("STAGE", ignore(ascii_case))]
     STAGE,
     #[token("SYNTAX", ignore(ascii_case))]
@@ -679,6 +721,8 @@ pub enum TokenKind {
     TENANTSETTING,
     #[token("TENANTS", ignore(ascii_case))]
     TENANTS,
+    #[token("THAN", ignore(ascii_case))]
+    THAN,
     #[token("THEN", ignore(ascii_case))]
     THEN,
     #[token("TIMESTAMP", ignore(ascii_case))]
@@ -723,6 +767,8 @@ pub enum TokenKind {
     UINT8,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
+    #[token("UNLOCK", ignore(ascii_case))]
+    UNLOCK,
     #[token("UNSIGNED", ignore(ascii_case))]
     UNSIGNED,
     #[token("URL", ignore(ascii_case))]
@@ -753,6 +799,8 @@ pub enum TokenKind {
     WHERE,
     #[token("WITH", ignore(ascii_case))]
     WITH,
+    #[token("WORKLOAD", ignore(ascii_case))]
+    WORKLOAD,
     #[token("XOR", ignore(ascii_case))]
     XOR,
     #[token("YEAR", ignore(ascii_case))]
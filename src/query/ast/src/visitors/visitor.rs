@@ -446,6 +446,14 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_drop_view(&mut self, _stmt: &'ast DropViewStmt<'ast>) {}
 
+    fn visit_create_materialized_view(&mut self, _stmt: &'ast CreateMaterializedViewStmt<'ast>) {}
+
+    fn visit_refresh_materialized_view(&mut self, _stmt: &'ast RefreshMaterializedViewStmt<'ast>) {}
+
+    fn visit_create_inverted_index(&mut self, _stmt: &'ast CreateInvertedIndexStmt<'ast>) {}
+
+    fn visit_drop_inverted_index(&mut self, _stmt: &'ast DropInvertedIndexStmt<'ast>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &'ast CreateUserStmt) {}
@@ -454,6 +462,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_drop_user(&mut self, _if_exists: bool, _user: &'ast UserIdentity) {}
 
+    fn visit_unlock_user(&mut self, _user: &'ast UserIdentity) {}
+
     fn visit_show_roles(&mut self) {}
 
     fn visit_create_role(&mut self, _if_not_exists: bool, _role_name: &'ast str) {}
@@ -487,6 +497,22 @@ pub trait Visitor<'ast>: Sized {
     ) {
     }
 
+    fn visit_create_row_access_policy(&mut self, _stmt: &'ast CreateRowAccessPolicyStmt<'ast>) {}
+
+    fn visit_drop_row_access_policy(&mut self, _stmt: &'ast DropRowAccessPolicyStmt<'ast>) {}
+
+    fn visit_create_masking_policy(&mut self, _stmt: &'ast CreateMaskingPolicyStmt<'ast>) {}
+
+    fn visit_drop_masking_policy(&mut self, _stmt: &'ast DropMaskingPolicyStmt<'ast>) {}
+
+    fn visit_create_workload_group(&mut self, _stmt: &'ast CreateWorkloadGroupStmt<'ast>) {}
+
+    fn visit_drop_workload_group(&mut self, _stmt: &'ast DropWorkloadGroupStmt<'ast>) {}
+
+    fn visit_create_password_policy(&mut self, _stmt: &'ast CreatePasswordPolicyStmt<'ast>) {}
+
+    fn visit_drop_password_policy(&mut self, _stmt: &'ast DropPasswordPolicyStmt<'ast>) {}
+
     fn visit_create_stage(&mut self, _stmt: &'ast CreateStageStmt) {}
 
     fn visit_show_stages(&mut self) {}
@@ -449,6 +449,14 @@ pub trait VisitorMut: Sized {
 
     fn visit_drop_view(&mut self, _stmt: &mut DropViewStmt<'_>) {}
 
+    fn visit_create_materialized_view(&mut self, _stmt: &mut CreateMaterializedViewStmt<'_>) {}
+
+    fn visit_refresh_materialized_view(&mut self, _stmt: &mut RefreshMaterializedViewStmt<'_>) {}
+
+    fn visit_create_inverted_index(&mut self, _stmt: &mut CreateInvertedIndexStmt<'_>) {}
+
+    fn visit_drop_inverted_index(&mut self, _stmt: &mut DropInvertedIndexStmt<'_>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &mut CreateUserStmt) {}
@@ -457,6 +465,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_drop_user(&mut self, _if_exists: bool, _user: &mut UserIdentity) {}
 
+    fn visit_unlock_user(&mut self, _user: &mut UserIdentity) {}
+
     fn visit_show_roles(&mut self) {}
 
     fn visit_create_role(&mut self, _if_not_exists: bool, _role_name: &mut String) {}
@@ -490,6 +500,22 @@ pub trait VisitorMut: Sized {
     ) {
     }
 
+    fn visit_create_row_access_policy(&mut self, _stmt: &mut CreateRowAccessPolicyStmt<'_>) {}
+
+    fn visit_drop_row_access_policy(&mut self, _stmt: &mut DropRowAccessPolicyStmt<'_>) {}
+
+    fn visit_create_masking_policy(&mut self, _stmt: &mut CreateMaskingPolicyStmt<'_>) {}
+
+    fn visit_drop_masking_policy(&mut self, _stmt: &mut DropMaskingPolicyStmt<'_>) {}
+
+    fn visit_create_workload_group(&mut self, _stmt: &mut CreateWorkloadGroupStmt<'_>) {}
+
+    fn visit_drop_workload_group(&mut self, _stmt: &mut DropWorkloadGroupStmt<'_>) {}
+
+    fn visit_create_password_policy(&mut self, _stmt: &mut CreatePasswordPolicyStmt<'_>) {}
+
+    fn visit_drop_password_policy(&mut self, _stmt: &mut DropPasswordPolicyStmt<'_>) {}
+
     fn visit_create_stage(&mut self, _stmt: &mut CreateStageStmt) {}
 
     fn visit_show_stages(&mut self) {}
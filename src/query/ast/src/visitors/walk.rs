@@ -351,11 +351,16 @@ pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, statement: &'a Statem
         Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
         Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
         Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
+        Statement::CreateMaterializedView(stmt) => visitor.visit_create_materialized_view(stmt),
+        Statement::RefreshMaterializedView(stmt) => visitor.visit_refresh_materialized_view(stmt),
+        Statement::CreateInvertedIndex(stmt) => visitor.visit_create_inverted_index(stmt),
+        Statement::DropInvertedIndex(stmt) => visitor.visit_drop_inverted_index(stmt),
         Statement::ShowUsers => visitor.visit_show_users(),
         Statement::ShowRoles => visitor.visit_show_roles(),
         Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
         Statement::AlterUser(stmt) => visitor.visit_alter_user(stmt),
         Statement::DropUser { if_exists, user } => visitor.visit_drop_user(*if_exists, user),
+        Statement::UnlockUser { user } => visitor.visit_unlock_user(user),
         Statement::CreateRole {
             if_not_exists,
             role_name,
@@ -390,6 +395,14 @@ pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, statement: &'a Statem
             definition,
             description,
         } => visitor.visit_alter_udf(udf_name, parameters, definition, description),
+        Statement::CreateRowAccessPolicy(stmt) => visitor.visit_create_row_access_policy(stmt),
+        Statement::DropRowAccessPolicy(stmt) => visitor.visit_drop_row_access_policy(stmt),
+        Statement::CreateMaskingPolicy(stmt) => visitor.visit_create_masking_policy(stmt),
+        Statement::DropMaskingPolicy(stmt) => visitor.visit_drop_masking_policy(stmt),
+        Statement::CreateWorkloadGroup(stmt) => visitor.visit_create_workload_group(stmt),
+        Statement::DropWorkloadGroup(stmt) => visitor.visit_drop_workload_group(stmt),
+        Statement::CreatePasswordPolicy(stmt) => visitor.visit_create_password_policy(stmt),
+        Statement::DropPasswordPolicy(stmt) => visitor.visit_drop_password_policy(stmt),
         Statement::ListStage { location, pattern } => visitor.visit_list_stage(location, pattern),
         Statement::ShowStages => visitor.visit_show_stages(),
         Statement::DropStage {
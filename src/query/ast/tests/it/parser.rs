@@ -110,6 +110,8 @@ fn test_statement() {
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1';"#,
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1', TENANTSETTING;"#,
         r#"CREATE USER u1 IDENTIFIED BY '123456' WITH DEFAULT_ROLE='role123', TENANTSETTING"#,
+        r#"CREATE USER u1 IDENTIFIED BY '123456' WITH REQUIRE SSL;"#,
+        r#"ALTER USER u1 WITH REQUIRE NONE;"#,
         r#"DROP database if exists db1;"#,
         r#"select distinct a, count(*) from t where a = 1 and b - 1 < a group by a having a = 1;"#,
         r#"select * from t4;"#,
@@ -154,6 +156,21 @@ fn test_statement() {
         r#"ALTER TABLE t CLUSTER BY(c1);"#,
         r#"ALTER TABLE t DROP CLUSTER KEY;"#,
         r#"ALTER TABLE t RECLUSTER FINAL WHERE c1 > 0;"#,
+        r#"ALTER TABLE t ADD ROW ACCESS POLICY p1 ON (region);"#,
+        r#"ALTER TABLE t DROP ROW ACCESS POLICY p1;"#,
+        r#"CREATE ROW ACCESS POLICY p1 AS (region STRING) RETURNS BOOLEAN -> region = 'us' EXEMPT ROLES = ('admin');"#,
+        r#"DROP ROW ACCESS POLICY IF EXISTS p1;"#,
+        r#"ALTER TABLE t MODIFY COLUMN email SET MASKING POLICY m1;"#,
+        r#"ALTER TABLE t MODIFY COLUMN email UNSET MASKING POLICY;"#,
+        r#"CREATE MASKING POLICY m1 AS (val STRING) RETURNS STRING -> '***';"#,
+        r#"DROP MASKING POLICY IF EXISTS m1;"#,
+        r#"CREATE WORKLOAD GROUP IF NOT EXISTS etl WITH max_concurrency = 4, memory_quota = 1073741824, queue_timeout = 60 USERS = ('loader') ROLES = ('etl_role');"#,
+        r#"DROP WORKLOAD GROUP IF EXISTS etl;"#,
+        r#"CREATE PASSWORD POLICY IF NOT EXISTS strict WITH min_length = 12, max_retries = 3;"#,
+        r#"DROP PASSWORD POLICY IF EXISTS strict;"#,
+        r#"ALTER USER u1 WITH PASSWORD_POLICY = 'strict', MUST_CHANGE_PASSWORD = TRUE;"#,
+        r#"ALTER USER u1 WITH UNSET PASSWORD_POLICY;"#,
+        r#"ALTER USER 'u1'@'%' UNLOCK;"#,
         r#"ALTER DATABASE IF EXISTS ctl.c RENAME TO a;"#,
         r#"ALTER DATABASE c RENAME TO a;"#,
         r#"ALTER DATABASE ctl.c RENAME TO a;"#,
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use common_base::base::GlobalInstance;
 use common_exception::ErrorCode;
@@ -24,8 +26,14 @@ use super::Catalog;
 
 pub const CATALOG_DEFAULT: &str = "default";
 
+// Summing the stats of all the tables of a tenant lists every database and table,
+// the result is reused for this long by the queries checking the storage quota.
+const STORAGE_USAGE_CACHE_TTL: Duration = Duration::from_secs(10);
+
 pub struct CatalogManager {
     pub catalogs: DashMap<String, Arc<dyn Catalog>>,
+    // tenant -> (loaded at, storage usage in bytes)
+    pub storage_usages: DashMap<String, (Instant, u64)>,
 }
 
 impl CatalogManager {
@@ -74,4 +82,42 @@ impl CatalogManager {
             }
         }
     }
+
+    /// Get the bytes of storage taken by the tenant's Fuse tables, data and index included.
+    ///
+    /// The usage is cached for `STORAGE_USAGE_CACHE_TTL`, writes committed in the meantime
+    /// are not counted until it is loaded again.
+    pub async fn get_tenant_storage_usage(&self, tenant: &str) -> Result<u64> {
+        if let Some(cached) = self.storage_usages.get(tenant) {
+            let (loaded_at, storage_usage) = *cached;
+            if loaded_at.elapsed() < STORAGE_USAGE_CACHE_TTL {
+                return Ok(storage_usage);
+            }
+        }
+
+        let loaded_at = Instant::now();
+        let storage_usage = self.load_tenant_storage_usage(tenant).await?;
+        self.storage_usages
+            .insert(tenant.to_string(), (loaded_at, storage_usage));
+        Ok(storage_usage)
+    }
+
+    async fn load_tenant_storage_usage(&self, tenant: &str) -> Result<u64> {
+        // Fuse tables are only in the default catalog.
+        let catalog = self.get_catalog(CATALOG_DEFAULT)?;
+
+        let mut storage_usage = 0;
+        for database in catalog.list_databases(tenant).await? {
+            for table in catalog.list_tables(tenant, database.name()).await? {
+                if table.engine() != "FUSE" {
+                    continue;
+                }
+
+                let stats = &table.get_table_info().meta.statistics;
+                storage_usage += stats.compressed_data_bytes + stats.index_data_bytes;
+            }
+        }
+
+        Ok(storage_usage)
+    }
 }
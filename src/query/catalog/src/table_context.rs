@@ -55,6 +55,16 @@ pub struct ProcessInfo {
     pub created_time: SystemTime,
 }
 
+/// A query held in the admission queue of the node by its workload group.
+#[derive(Debug, Clone)]
+pub struct QueuedQueryInfo {
+    pub query_id: String,
+    pub user: String,
+    pub workload_group: String,
+    pub query_text: String,
+    pub enqueue_time: SystemTime,
+}
+
 #[derive(Debug, Clone)]
 pub struct StageAttachment {
     pub location: String,
@@ -87,6 +97,8 @@ pub trait TableContext: Send + Sync {
     fn get_current_database(&self) -> String;
     fn get_current_user(&self) -> Result<UserInfo>;
     fn get_current_role(&self) -> Option<RoleInfo>;
+    /// Get all the roles of the current user, including the inherited ones.
+    async fn get_available_roles(&self) -> Result<Vec<RoleInfo>>;
     fn get_fuse_version(&self) -> String;
     fn get_changed_settings(&self) -> Arc<Settings>;
     fn apply_changed_settings(&self, changed_settings: Arc<Settings>) -> Result<()>;
@@ -107,5 +119,6 @@ pub trait TableContext: Send + Sync {
     async fn get_table(&self, catalog: &str, database: &str, table: &str)
     -> Result<Arc<dyn Table>>;
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
+    fn get_queued_queries_info(&self) -> Vec<QueuedQueryInfo>;
     fn get_stage_attachment(&self) -> Option<StageAttachment>;
 }
@@ -29,6 +29,7 @@ use common_meta_types::TenantQuota;
 use common_storage::StorageConfig;
 use common_tracing::Config as LogConfig;
 use common_users::idm_config::IDMConfig;
+use common_users::JwtIssuerConfig;
 
 use super::outer_v0::Config as OuterV0Config;
 
@@ -121,6 +122,10 @@ pub struct QueryConfig {
     pub max_memory_limit_enabled: bool,
     pub clickhouse_http_handler_host: String,
     pub clickhouse_http_handler_port: u16,
+    pub postgres_handler_host: String,
+    pub postgres_handler_port: u16,
+    pub flight_sql_handler_host: String,
+    pub flight_sql_handler_port: u16,
     pub http_handler_host: String,
     pub http_handler_port: u16,
     pub http_handler_result_timeout_millis: u64,
@@ -133,6 +138,12 @@ pub struct QueryConfig {
     pub api_tls_server_cert: String,
     pub api_tls_server_key: String,
     pub api_tls_server_root_ca_cert: String,
+    pub mysql_tls_server_cert: String,
+    pub mysql_tls_server_key: String,
+    pub mysql_tls_server_root_ca_cert: String,
+    pub postgres_tls_server_cert: String,
+    pub postgres_tls_server_key: String,
+    pub postgres_tls_server_root_ca_cert: String,
     /// rpc server cert
     pub rpc_tls_server_cert: String,
     /// key for rpc server cert
@@ -167,6 +178,11 @@ pub struct QueryConfig {
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
+    /// Seconds to reload the JWKS keys.
+    pub jwt_key_refresh_interval: u64,
+    /// Minimal seconds between two reloads of the JWKS keys.
+    pub jwt_key_min_reload_interval: u64,
+    pub jwt_issuers: Vec<JwtIssuerConfig>,
     pub async_insert_max_data_size: u64,
     pub async_insert_busy_timeout: u64,
     pub async_insert_stale_timeout: u64,
@@ -189,6 +205,10 @@ impl Default for QueryConfig {
             max_memory_limit_enabled: false,
             clickhouse_http_handler_host: "127.0.0.1".to_string(),
             clickhouse_http_handler_port: 8124,
+            postgres_handler_host: "127.0.0.1".to_string(),
+            postgres_handler_port: 5433,
+            flight_sql_handler_host: "127.0.0.1".to_string(),
+            flight_sql_handler_port: 8900,
             http_handler_host: "127.0.0.1".to_string(),
             http_handler_port: 8000,
             http_handler_result_timeout_millis: 10000,
@@ -198,6 +218,12 @@ impl Default for QueryConfig {
             api_tls_server_cert: "".to_string(),
             api_tls_server_key: "".to_string(),
             api_tls_server_root_ca_cert: "".to_string(),
+            mysql_tls_server_cert: "".to_string(),
+            mysql_tls_server_key: "".to_string(),
+            mysql_tls_server_root_ca_cert: "".to_string(),
+            postgres_tls_server_cert: "".to_string(),
+            postgres_tls_server_key: "".to_string(),
+            postgres_tls_server_root_ca_cert: "".to_string(),
             http_handler_tls_server_cert: "".to_string(),
             http_handler_tls_server_key: "".to_string(),
             http_handler_tls_server_root_ca_cert: "".to_string(),
@@ -220,6 +246,9 @@ impl Default for QueryConfig {
             table_cache_bloom_index_data_bytes: 1024 * 1024 * 1024,
             management_mode: false,
             jwt_key_file: "".to_string(),
+            jwt_key_refresh_interval: 15 * 60,
+            jwt_key_min_reload_interval: 5,
+            jwt_issuers: vec![],
             async_insert_max_data_size: 10000,
             async_insert_busy_timeout: 200,
             async_insert_stale_timeout: 0,
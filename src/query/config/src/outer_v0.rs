@@ -39,10 +39,12 @@ use common_storage::StorageOssConfig as InnerStorageOssConfig;
 use common_storage::StorageParams;
 use common_storage::StorageRedisConfig as InnerStorageRedisConfig;
 use common_storage::StorageS3Config as InnerStorageS3Config;
+use common_tracing::AuditConfig as InnerAuditLogConfig;
 use common_tracing::Config as InnerLogConfig;
 use common_tracing::FileConfig as InnerFileLogConfig;
 use common_tracing::StderrConfig as InnerStderrLogConfig;
 use common_users::idm_config::IDMConfig as InnerIDMConfig;
+use common_users::JwtIssuerConfig as InnerJwtIssuerConfig;
 use serde::Deserialize;
 use serde::Serialize;
 use serfig::collectors::from_env;
@@ -1179,6 +1181,18 @@ pub struct QueryConfig {
     #[clap(long, default_value = "8124")]
     pub clickhouse_http_handler_port: u16,
 
+    #[clap(long, default_value = "127.0.0.1")]
+    pub postgres_handler_host: String,
+
+    #[clap(long, default_value = "5433")]
+    pub postgres_handler_port: u16,
+
+    #[clap(long, default_value = "127.0.0.1")]
+    pub flight_sql_handler_host: String,
+
+    #[clap(long, default_value = "8900")]
+    pub flight_sql_handler_port: u16,
+
     #[clap(long, default_value = "127.0.0.1")]
     pub http_handler_host: String,
 
@@ -1215,6 +1229,30 @@ pub struct QueryConfig {
     #[clap(long, default_value_t)]
     pub api_tls_server_root_ca_cert: String,
 
+    /// MySQL handler server cert, enables TLS upgrade of the MySQL handshake
+    #[clap(long, default_value_t)]
+    pub mysql_tls_server_cert: String,
+
+    /// Key for MySQL handler server cert
+    #[clap(long, default_value_t)]
+    pub mysql_tls_server_key: String,
+
+    /// Certificate to verify the MySQL client certificates, client certificates are not required if empty
+    #[clap(long, default_value_t)]
+    pub mysql_tls_server_root_ca_cert: String,
+
+    /// PostgreSQL handler server cert, enables TLS when the client sends SSLRequest
+    #[clap(long, default_value_t)]
+    pub postgres_tls_server_cert: String,
+
+    /// Key for PostgreSQL handler server cert
+    #[clap(long, default_value_t)]
+    pub postgres_tls_server_key: String,
+
+    /// Certificate to verify the PostgreSQL client certificates, client certificates are not required if empty
+    #[clap(long, default_value_t)]
+    pub postgres_tls_server_root_ca_cert: String,
+
     /// rpc server cert
     #[clap(long, default_value_t)]
     pub rpc_tls_server_cert: String,
@@ -1288,9 +1326,21 @@ pub struct QueryConfig {
     #[clap(long)]
     pub management_mode: bool,
 
+    /// File path or url of the JWKS, which accepts tokens of any issuer.
     #[clap(long, default_value_t)]
     pub jwt_key_file: String,
 
+    /// Seconds to reload the JWKS keys, they are also reloaded on unknown key id.
+    #[clap(long, default_value = "900")]
+    pub jwt_key_refresh_interval: u64,
+
+    /// Minimal seconds between two reloads of the JWKS keys, whether they succeed or not.
+    #[clap(long, default_value = "5")]
+    pub jwt_key_min_reload_interval: u64,
+
+    #[clap(skip)]
+    jwt_issuers: Vec<JwtIssuerConfig>,
+
     /// The maximum memory size of the buffered data collected per insert before being inserted.
     #[clap(long, default_value = "10000")]
     pub async_insert_max_data_size: u64,
@@ -1337,6 +1387,10 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             max_memory_limit_enabled: self.max_memory_limit_enabled,
             clickhouse_http_handler_host: self.clickhouse_http_handler_host,
             clickhouse_http_handler_port: self.clickhouse_http_handler_port,
+            postgres_handler_host: self.postgres_handler_host,
+            postgres_handler_port: self.postgres_handler_port,
+            flight_sql_handler_host: self.flight_sql_handler_host,
+            flight_sql_handler_port: self.flight_sql_handler_port,
             http_handler_host: self.http_handler_host,
             http_handler_port: self.http_handler_port,
             http_handler_result_timeout_millis: self.http_handler_result_timeout_millis,
@@ -1349,6 +1403,12 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             api_tls_server_cert: self.api_tls_server_cert,
             api_tls_server_key: self.api_tls_server_key,
             api_tls_server_root_ca_cert: self.api_tls_server_root_ca_cert,
+            mysql_tls_server_cert: self.mysql_tls_server_cert,
+            mysql_tls_server_key: self.mysql_tls_server_key,
+            mysql_tls_server_root_ca_cert: self.mysql_tls_server_root_ca_cert,
+            postgres_tls_server_cert: self.postgres_tls_server_cert,
+            postgres_tls_server_key: self.postgres_tls_server_key,
+            postgres_tls_server_root_ca_cert: self.postgres_tls_server_root_ca_cert,
             rpc_tls_server_cert: self.rpc_tls_server_cert,
             rpc_tls_server_key: self.rpc_tls_server_key,
             rpc_tls_query_server_root_ca_cert: self.rpc_tls_query_server_root_ca_cert,
@@ -1368,6 +1428,9 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_cache_bloom_index_data_bytes: self.table_cache_bloom_index_data_bytes,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
+            jwt_key_refresh_interval: self.jwt_key_refresh_interval,
+            jwt_key_min_reload_interval: self.jwt_key_min_reload_interval,
+            jwt_issuers: self.jwt_issuers.into_iter().map(|v| v.into()).collect(),
             async_insert_max_data_size: self.async_insert_max_data_size,
             async_insert_busy_timeout: self.async_insert_busy_timeout,
             async_insert_stale_timeout: self.async_insert_stale_timeout,
@@ -1400,6 +1463,10 @@ impl From<InnerQueryConfig> for QueryConfig {
 
             clickhouse_http_handler_host: inner.clickhouse_http_handler_host,
             clickhouse_http_handler_port: inner.clickhouse_http_handler_port,
+            postgres_handler_host: inner.postgres_handler_host,
+            postgres_handler_port: inner.postgres_handler_port,
+            flight_sql_handler_host: inner.flight_sql_handler_host,
+            flight_sql_handler_port: inner.flight_sql_handler_port,
             http_handler_host: inner.http_handler_host,
             http_handler_port: inner.http_handler_port,
             http_handler_result_timeout_millis: inner.http_handler_result_timeout_millis,
@@ -1412,6 +1479,12 @@ impl From<InnerQueryConfig> for QueryConfig {
             api_tls_server_cert: inner.api_tls_server_cert,
             api_tls_server_key: inner.api_tls_server_key,
             api_tls_server_root_ca_cert: inner.api_tls_server_root_ca_cert,
+            mysql_tls_server_cert: inner.mysql_tls_server_cert,
+            mysql_tls_server_key: inner.mysql_tls_server_key,
+            mysql_tls_server_root_ca_cert: inner.mysql_tls_server_root_ca_cert,
+            postgres_tls_server_cert: inner.postgres_tls_server_cert,
+            postgres_tls_server_key: inner.postgres_tls_server_key,
+            postgres_tls_server_root_ca_cert: inner.postgres_tls_server_root_ca_cert,
             rpc_tls_server_cert: inner.rpc_tls_server_cert,
             rpc_tls_server_key: inner.rpc_tls_server_key,
             rpc_tls_query_server_root_ca_cert: inner.rpc_tls_query_server_root_ca_cert,
@@ -1432,6 +1505,9 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_cache_bloom_index_data_bytes: inner.table_cache_bloom_index_data_bytes,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
+            jwt_key_refresh_interval: inner.jwt_key_refresh_interval,
+            jwt_key_min_reload_interval: inner.jwt_key_min_reload_interval,
+            jwt_issuers: inner.jwt_issuers.into_iter().map(|v| v.into()).collect(),
             async_insert_max_data_size: inner.async_insert_max_data_size,
             async_insert_busy_timeout: inner.async_insert_busy_timeout,
             async_insert_stale_timeout: inner.async_insert_stale_timeout,
@@ -1466,6 +1542,9 @@ pub struct LogConfig {
 
     #[clap(flatten)]
     pub stderr: StderrLogConfig,
+
+    #[clap(flatten)]
+    pub audit: AuditLogConfig,
 }
 
 impl Default for LogConfig {
@@ -1489,6 +1568,7 @@ impl TryInto<InnerLogConfig> for LogConfig {
         Ok(InnerLogConfig {
             file,
             stderr: self.stderr.try_into()?,
+            audit: self.audit.try_into()?,
         })
     }
 }
@@ -1501,6 +1581,7 @@ impl From<InnerLogConfig> for LogConfig {
             query_enabled: false,
             file: inner.file.into(),
             stderr: inner.stderr.into(),
+            audit: inner.audit.into(),
         }
     }
 }
@@ -1603,6 +1684,53 @@ impl From<InnerStderrLogConfig> for StderrLogConfig {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Args)]
+#[serde(default)]
+pub struct AuditLogConfig {
+    /// Write the audit events, independently of the file logging
+    #[clap(long = "log-audit-on", default_value = "true")]
+    #[serde(rename = "on")]
+    pub audit_on: bool,
+
+    /// Audit log dir
+    #[clap(long = "log-audit-dir", default_value = "./.databend/logs/audit")]
+    #[serde(rename = "dir")]
+    pub audit_dir: String,
+
+    /// Audit event categories, "all", "none" or a list of login, privilege, ddl and dml
+    #[clap(long = "log-audit-categories", default_value = "all")]
+    #[serde(rename = "categories")]
+    pub audit_categories: String,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        InnerAuditLogConfig::default().into()
+    }
+}
+
+impl TryInto<InnerAuditLogConfig> for AuditLogConfig {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<InnerAuditLogConfig> {
+        Ok(InnerAuditLogConfig {
+            on: self.audit_on,
+            dir: self.audit_dir,
+            categories: self.audit_categories,
+        })
+    }
+}
+
+impl From<InnerAuditLogConfig> for AuditLogConfig {
+    fn from(inner: InnerAuditLogConfig) -> Self {
+        Self {
+            audit_on: inner.on,
+            audit_dir: inner.dir,
+            audit_categories: inner.categories,
+        }
+    }
+}
+
 /// Meta config group.
 /// deny_unknown_fields to check unknown field, like the deprecated `address`.
 /// TODO(xuanwo): All meta_xxx should be rename to xxx.
@@ -1724,6 +1852,55 @@ fn users_to_inner(outer: Vec<UserConfig>) -> Result<HashMap<String, AuthInfo>> {
     Ok(inner)
 }
 
+/// A trusted JWT issuer, configured by `[[query.jwt_issuers]]`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JwtIssuerConfig {
+    pub issuer: String,
+    /// File path or url of the JWKS of the issuer.
+    pub jwks: String,
+    /// Accepted `aud` of tokens, not checked if empty.
+    pub audiences: Vec<String>,
+    /// Claim of the user name.
+    pub user_claim: String,
+    /// Claim of the tenant.
+    pub tenant_claim: String,
+    /// Claim of the roles, a string or an array of strings.
+    pub role_claim: String,
+}
+
+impl Default for JwtIssuerConfig {
+    fn default() -> Self {
+        InnerJwtIssuerConfig::default().into()
+    }
+}
+
+impl From<InnerJwtIssuerConfig> for JwtIssuerConfig {
+    fn from(inner: InnerJwtIssuerConfig) -> Self {
+        Self {
+            issuer: inner.issuer,
+            jwks: inner.jwks,
+            audiences: inner.audiences,
+            user_claim: inner.user_claim,
+            tenant_claim: inner.tenant_claim,
+            role_claim: inner.role_claim,
+        }
+    }
+}
+
+impl From<JwtIssuerConfig> for InnerJwtIssuerConfig {
+    fn from(outer: JwtIssuerConfig) -> Self {
+        Self {
+            issuer: outer.issuer,
+            jwks: outer.jwks,
+            audiences: outer.audiences,
+            user_claim: outer.user_claim,
+            tenant_claim: outer.tenant_claim,
+            role_claim: outer.role_claim,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UserConfig {
     pub name: String,
@@ -1755,7 +1932,9 @@ impl TryInto<AuthInfo> for UserAuthConfig {
         match auth_type {
             AuthType::NoPassword => check_no_auth_string(self.auth_string, AuthInfo::None),
             AuthType::JWT => check_no_auth_string(self.auth_string, AuthInfo::JWT),
-            AuthType::Sha256Password | AuthType::DoubleSha1Password => {
+            AuthType::Sha256Password
+            | AuthType::DoubleSha1Password
+            | AuthType::ScramSha256Password => {
                 let password_type = auth_type.get_password_type().expect("must success");
                 match self.auth_string {
                     None => Err(ErrorCode::InvalidConfig("must set auth_string")),
@@ -15,12 +15,14 @@
 use chrono_tz::Tz;
 use common_datavalues::serializations::ArraySerializer;
 use common_datavalues::serializations::StructSerializer;
+use common_io::consts::FALSE_BYTES_CHAR;
 use common_io::consts::FALSE_BYTES_NUM;
 use common_io::consts::INF_BYTES_LONG;
 use common_io::consts::INF_BYTES_LOWER;
 use common_io::consts::NAN_BYTES_LOWER;
 use common_io::consts::NAN_BYTES_SNAKE;
 use common_io::consts::NULL_BYTES_UPPER;
+use common_io::consts::TRUE_BYTES_CHAR;
 use common_io::consts::TRUE_BYTES_NUM;
 
 use super::helpers::write_escaped_string;
@@ -79,6 +81,21 @@ impl FieldEncoderValues {
             quote_char: b'\'',
         }
     }
+
+    // PostgreSQL text format, booleans are 't' and 'f', drivers like psycopg2 only accept these.
+    pub fn create_for_postgres_handler(timezone: Tz) -> Self {
+        FieldEncoderValues {
+            common_settings: CommonSettings {
+                true_bytes: TRUE_BYTES_CHAR.as_bytes().to_vec(),
+                false_bytes: FALSE_BYTES_CHAR.as_bytes().to_vec(),
+                null_bytes: NULL_BYTES_UPPER.as_bytes().to_vec(),
+                nan_bytes: NAN_BYTES_SNAKE.as_bytes().to_vec(),
+                inf_bytes: INF_BYTES_LONG.as_bytes().to_vec(),
+                timezone,
+            },
+            quote_char: b'\'',
+        }
+    }
 }
 
 impl FieldEncoderRowBased for FieldEncoderValues {
@@ -0,0 +1,83 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::assert_string;
+use crate::scalars::scalar_binary_op;
+use crate::scalars::EvalContext;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `CONTAINS(str, substr)` returns true if `substr` occurs in `str`.
+#[derive(Clone)]
+pub struct ContainsFunction {
+    display_name: String,
+}
+
+impl ContainsFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        for arg in args {
+            assert_string(arg)?;
+        }
+        Ok(Box::new(ContainsFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for ContainsFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        bool::to_data_type()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        _input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let col = scalar_binary_op::<Vu8, Vu8, bool, _>(
+            columns[0].column(),
+            columns[1].column(),
+            contains,
+            &mut EvalContext::default(),
+        )?;
+        Ok(col.arc())
+    }
+}
+
+impl fmt::Display for ContainsFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+#[inline]
+fn contains(str: &[u8], substr: &[u8], _ctx: &mut EvalContext) -> bool {
+    substr.is_empty() || str.windows(substr.len()).any(|w| w == substr)
+}
@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::assert_string;
+use crate::scalars::scalar_binary_op;
+use crate::scalars::EvalContext;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `MATCH(text, query)` returns the relevance of `text` to the terms of `query`,
+/// 0 if none of the terms occurs in `text`.
+///
+/// The score is the classic TF-IDF similarity without the IDF part, which needs the
+/// statistics of the whole column:
+///
+/// `score = coord * sum(sqrt(tf(t))) / sqrt(number of terms in text)`
+///
+/// where `coord` is the fraction of the query terms found in `text`.
+#[derive(Clone)]
+pub struct MatchFunction {
+    display_name: String,
+}
+
+impl MatchFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        for arg in args {
+            assert_string(arg)?;
+        }
+        Ok(Box::new(MatchFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for MatchFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        f64::to_data_type()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        _input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let col = scalar_binary_op::<Vu8, Vu8, f64, _>(
+            columns[0].column(),
+            columns[1].column(),
+            match_score,
+            &mut EvalContext::default(),
+        )?;
+        Ok(col.arc())
+    }
+}
+
+impl fmt::Display for MatchFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+/// Splits text into lower-cased terms, any character that is not alphanumeric is a separator.
+///
+/// Shared by `MATCH` and the inverted index, so that both agree on what a term is.
+pub fn tokenize(text: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+#[inline]
+fn match_score(text: &[u8], query: &[u8], _ctx: &mut EvalContext) -> f64 {
+    let mut query_terms = tokenize(query);
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return 0.0;
+    }
+
+    let text_terms = tokenize(text);
+    if text_terms.is_empty() {
+        return 0.0;
+    }
+    let mut frequencies: HashMap<&str, usize> = HashMap::with_capacity(text_terms.len());
+    for term in text_terms.iter() {
+        *frequencies.entry(term.as_str()).or_default() += 1;
+    }
+
+    let mut matched = 0;
+    let mut sum = 0.0;
+    for term in query_terms.iter() {
+        if let Some(tf) = frequencies.get(term.as_str()) {
+            matched += 1;
+            sum += (*tf as f64).sqrt();
+        }
+    }
+
+    let coord = matched as f64 / query_terms.len() as f64;
+    coord * sum / (text_terms.len() as f64).sqrt()
+}
@@ -20,6 +20,7 @@ mod char_;
 mod char_length;
 mod concat;
 mod concat_ws;
+mod contains;
 mod elt;
 mod export_set;
 mod field;
@@ -31,6 +32,7 @@ mod leftright;
 mod length;
 mod locate;
 mod lower;
+mod match_;
 mod new_trim;
 mod oct;
 mod octet_length;
@@ -67,6 +69,7 @@ pub use char_::CharFunction;
 pub use char_length::CharLengthFunction;
 pub use concat::ConcatFunction;
 pub use concat_ws::ConcatWsFunction;
+pub use contains::ContainsFunction;
 pub use elt::EltFunction;
 pub use export_set::ExportSetFunction;
 pub use field::FieldFunction;
@@ -81,6 +84,8 @@ pub use locate::InstrFunction;
 pub use locate::LocateFunction;
 pub use locate::PositionFunction;
 pub use lower::LowerFunction;
+pub use match_::tokenize;
+pub use match_::MatchFunction;
 pub use new_trim::TrimBothFunction;
 pub use new_trim::TrimLeadingFunction;
 pub use new_trim::TrimTrailingFunction;
@@ -23,6 +23,7 @@ use crate::scalars::CharFunction;
 use crate::scalars::CharLengthFunction;
 use crate::scalars::ConcatFunction;
 use crate::scalars::ConcatWsFunction;
+use crate::scalars::ContainsFunction;
 use crate::scalars::EltFunction;
 use crate::scalars::ExportSetFunction;
 use crate::scalars::FieldFunction;
@@ -37,6 +38,7 @@ use crate::scalars::LeftFunction;
 use crate::scalars::LeftPadFunction;
 use crate::scalars::LocateFunction;
 use crate::scalars::LowerFunction;
+use crate::scalars::MatchFunction;
 use crate::scalars::OctFunction;
 use crate::scalars::OctetLengthFunction;
 use crate::scalars::OrdFunction;
@@ -122,6 +124,8 @@ impl StringFunction {
         factory.register("locate", LocateFunction::desc());
         factory.register("position", PositionFunction::desc());
         factory.register("instr", InstrFunction::desc());
+        factory.register("match", MatchFunction::desc());
+        factory.register("contains", ContainsFunction::desc());
 
         // utf8 collation
         factory.register("length_utf8", StringUtf8LengthFunction::desc());
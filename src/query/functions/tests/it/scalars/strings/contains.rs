@@ -0,0 +1,53 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_contains_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "none, none",
+            columns: vec![
+                Series::from_data([Option::<&str>::None]),
+                Series::from_data([Option::<&str>::None]),
+            ],
+            expect: Series::from_data([Option::<bool>::None]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "substring",
+            columns: vec![
+                Series::from_data(["databend", "databend", "databend", "data"]),
+                Series::from_data(["tab", "bend", "Bend", "database"]),
+            ],
+            expect: Series::from_data([true, true, false, false]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "empty substring",
+            columns: vec![
+                Series::from_data(["databend", ""]),
+                Series::from_data(["", ""]),
+            ],
+            expect: Series::from_data([true, true]),
+            error: "",
+        },
+    ];
+
+    test_scalar_functions("contains", &tests)
+}
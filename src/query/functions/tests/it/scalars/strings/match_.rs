@@ -0,0 +1,62 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_match_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "none, none",
+            columns: vec![
+                Series::from_data([Option::<&str>::None]),
+                Series::from_data([Option::<&str>::None]),
+            ],
+            expect: Series::from_data([Option::<f64>::None]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "no term matched",
+            columns: vec![
+                Series::from_data(["quick brown fox", ""]),
+                Series::from_data(["dog", "dog"]),
+            ],
+            expect: Series::from_data([0.0f64, 0.0]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "case and punctuation insensitive",
+            columns: vec![
+                Series::from_data(["Quick, brown FOX!", "fox fox fox fox"]),
+                Series::from_data(["fox", "FOX"]),
+            ],
+            expect: Series::from_data([1.0f64 / 3.0f64.sqrt(), 1.0]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "partial match scaled by coord",
+            columns: vec![
+                Series::from_data(["quick fox", "quick fox"]),
+                Series::from_data(["quick fox", "quick dog"]),
+            ],
+            expect: Series::from_data([2.0f64 / 2.0f64.sqrt(), 0.5 / 2.0f64.sqrt()]),
+            error: "",
+        },
+    ];
+
+    test_scalar_functions("match", &tests)
+}
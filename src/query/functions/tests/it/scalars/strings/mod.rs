@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod contains;
 // mod locate;
 mod locate;
 mod lower;
+mod match_;
 mod regexp_instr;
 mod regexp_like;
 mod regexp_replace;
@@ -74,6 +74,7 @@ impl ClusterMgr {
 
         KVMeta {
             expire_at: Some(expire_at.as_secs()),
+            lease: None,
         }
     }
 }
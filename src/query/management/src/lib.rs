@@ -13,20 +13,30 @@
 // limitations under the License.
 
 mod cluster;
+mod masking_policy;
+mod password_policy;
 mod quota;
 mod role;
+mod row_access_policy;
 mod serde;
 mod setting;
 mod stage;
 mod udf;
 mod user;
+mod workload_group;
 
 pub use cluster::ClusterApi;
 pub use cluster::ClusterMgr;
+pub use masking_policy::MaskingPolicyApi;
+pub use masking_policy::MaskingPolicyMgr;
+pub use password_policy::PasswordPolicyApi;
+pub use password_policy::PasswordPolicyMgr;
 pub use quota::QuotaApi;
 pub use quota::QuotaMgr;
 pub use role::RoleApi;
 pub use role::RoleMgr;
+pub use row_access_policy::RowAccessPolicyApi;
+pub use row_access_policy::RowAccessPolicyMgr;
 pub use serde::deserialize_struct;
 pub use serde::serialize_struct;
 pub use setting::SettingApi;
@@ -37,3 +47,5 @@ pub use udf::UdfApi;
 pub use udf::UdfMgr;
 pub use user::UserApi;
 pub use user::UserMgr;
+pub use workload_group::WorkloadGroupApi;
+pub use workload_group::WorkloadGroupMgr;
@@ -0,0 +1,119 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::IntoSeqV;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::Operation;
+use common_meta_types::PasswordPolicy;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVReq;
+
+use crate::password_policy::PasswordPolicyApi;
+
+static PASSWORD_POLICY_API_KEY_PREFIX: &str = "__fd_password_policies";
+
+pub struct PasswordPolicyMgr {
+    kv_api: Arc<dyn KVApi>,
+    policy_prefix: String,
+}
+
+impl PasswordPolicyMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while password policy mgr create)",
+            ));
+        }
+
+        Ok(PasswordPolicyMgr {
+            kv_api,
+            policy_prefix: format!(
+                "{}/{}",
+                PASSWORD_POLICY_API_KEY_PREFIX,
+                escape_for_key(tenant)?
+            ),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PasswordPolicyApi for PasswordPolicyMgr {
+    async fn add_policy(&self, policy: PasswordPolicy) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(serde_json::to_vec(&policy)?);
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(&policy.name)?);
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.added_or_else(|v| {
+            ErrorCode::PasswordPolicyAlreadyExists(format!(
+                "Password policy already exists, seq [{}]",
+                v.seq
+            ))
+        })?;
+
+        Ok(res.seq)
+    }
+
+    async fn get_policy(&self, name: &str, seq: Option<u64>) -> Result<SeqV<PasswordPolicy>> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value = res.ok_or_else(|| {
+            ErrorCode::UnknownPasswordPolicy(format!("Unknown password policy {}", name))
+        })?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(seq_value.into_seqv()?),
+            Err(_) => Err(ErrorCode::UnknownPasswordPolicy(format!(
+                "Unknown password policy {}",
+                name
+            ))),
+        }
+    }
+
+    async fn get_policies(&self) -> Result<Vec<PasswordPolicy>> {
+        let values = self.kv_api.prefix_list_kv(&self.policy_prefix).await?;
+
+        let mut policies = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            let policy = serde_json::from_slice::<PasswordPolicy>(&value.data)?;
+            policies.push(policy);
+        }
+        Ok(policies)
+    }
+
+    async fn drop_policy(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq.into(), Operation::Delete, None))
+            .await?;
+        if res.prev.is_some() && res.result.is_none() {
+            Ok(())
+        } else {
+            Err(ErrorCode::UnknownPasswordPolicy(format!(
+                "Unknown password policy {}",
+                name
+            )))
+        }
+    }
+}
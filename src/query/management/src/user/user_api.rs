@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
 use common_meta_types::GrantObject;
@@ -20,6 +22,7 @@ use common_meta_types::UserIdentity;
 use common_meta_types::UserInfo;
 use common_meta_types::UserOption;
 use common_meta_types::UserPrivilegeSet;
+use common_meta_types::UserQuota;
 
 #[async_trait::async_trait]
 pub trait UserApi: Sync + Send {
@@ -37,6 +40,30 @@ pub trait UserApi: Sync + Send {
         seq: Option<u64>,
     ) -> Result<Option<u64>>;
 
+    /// Change the password of the user, the old one is kept in the password history.
+    async fn update_user_password(
+        &self,
+        user: UserIdentity,
+        auth_info: AuthInfo,
+        update_on: DateTime<Utc>,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>>;
+
+    async fn update_user_login_state(
+        &self,
+        user: UserIdentity,
+        password_fails: Vec<DateTime<Utc>>,
+        lockout_time: Option<DateTime<Utc>>,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>>;
+
+    async fn update_user_quota(
+        &self,
+        user: UserIdentity,
+        quota: UserQuota,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>>;
+
     async fn grant_privileges(
         &self,
         user: UserIdentity,
@@ -14,6 +14,8 @@
 
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_base::base::escape_for_key;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -29,6 +31,7 @@ use common_meta_types::UserIdentity;
 use common_meta_types::UserInfo;
 use common_meta_types::UserOption;
 use common_meta_types::UserPrivilegeSet;
+use common_meta_types::UserQuota;
 
 use crate::serde::deserialize_struct;
 use crate::serde::serialize_struct;
@@ -78,9 +81,11 @@ impl UserMgr {
                 None,
             ))
             .await?;
+        // The unchanged value is returned when the seq does not match.
+        let changed = res.changed();
         match res.result {
-            Some(SeqV { seq: s, .. }) => Ok(s),
-            None => Err(ErrorCode::UnknownUser(format!(
+            Some(SeqV { seq: s, .. }) if changed => Ok(s),
+            _ => Err(ErrorCode::UnknownUser(format!(
                 "unknown user, or seq not match {}",
                 user_info.name
             ))),
@@ -170,6 +175,48 @@ impl UserApi for UserMgr {
         Ok(Some(seq))
     }
 
+    async fn update_user_password(
+        &self,
+        user: UserIdentity,
+        auth_info: AuthInfo,
+        update_on: DateTime<Utc>,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>> {
+        let user_val_seq = self.get_user(user, seq);
+        let mut user_info = user_val_seq.await?.data;
+        user_info.update_auth_info(auth_info, update_on);
+        let seq = self.upsert_user_info(&user_info, seq).await?;
+        Ok(Some(seq))
+    }
+
+    async fn update_user_login_state(
+        &self,
+        user: UserIdentity,
+        password_fails: Vec<DateTime<Utc>>,
+        lockout_time: Option<DateTime<Utc>>,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>> {
+        let user_val_seq = self.get_user(user, seq);
+        let mut user_info = user_val_seq.await?.data;
+        user_info.password_fails = password_fails;
+        user_info.lockout_time = lockout_time;
+        let seq = self.upsert_user_info(&user_info, seq).await?;
+        Ok(Some(seq))
+    }
+
+    async fn update_user_quota(
+        &self,
+        user: UserIdentity,
+        quota: UserQuota,
+        seq: Option<u64>,
+    ) -> Result<Option<u64>> {
+        let user_val_seq = self.get_user(user, seq);
+        let mut user_info = user_val_seq.await?.data;
+        user_info.quota = quota;
+        let seq = self.upsert_user_info(&user_info, seq).await?;
+        Ok(Some(seq))
+    }
+
     async fn grant_privileges(
         &self,
         user: UserIdentity,
@@ -13,7 +13,11 @@
 // limitations under the License.
 
 mod cluster;
+mod masking_policy;
+mod password_policy;
+mod row_access_policy;
 mod setting;
 mod stage;
 mod udf;
 mod user;
+mod workload_group;
@@ -0,0 +1,96 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::PasswordPolicy;
+use common_meta_types::SeqV;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_password_policy() -> Result<()> {
+    let (kv_api, policy_api) = new_password_policy_api().await?;
+
+    let policy = create_test_policy();
+    policy_api.add_policy(policy.clone()).await?;
+    let value = kv_api.get_kv("__fd_password_policies/admin/strict").await?;
+
+    match value {
+        Some(SeqV {
+            seq: 1,
+            meta: _,
+            data: value,
+        }) => {
+            assert_eq!(value, serde_json::to_vec(&policy)?);
+        }
+        catch => panic!("GetKVActionReply{:?}", catch),
+    }
+
+    // Add again.
+    match policy_api.add_policy(policy.clone()).await {
+        Ok(_) => panic!("Already exists add password policy must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2643),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_and_drop_password_policy() -> Result<()> {
+    let (_, policy_api) = new_password_policy_api().await?;
+
+    let policies = policy_api.get_policies().await?;
+    assert_eq!(policies, vec![]);
+
+    let policy = create_test_policy();
+    policy_api.add_policy(policy.clone()).await?;
+
+    let got = policy_api.get_policy(&policy.name, None).await?;
+    assert_eq!(got.data, policy);
+    let policies = policy_api.get_policies().await?;
+    assert_eq!(policies, vec![policy.clone()]);
+
+    policy_api.drop_policy(&policy.name, None).await?;
+    let policies = policy_api.get_policies().await?;
+    assert_eq!(policies, vec![]);
+
+    match policy_api.get_policy(&policy.name, None).await {
+        Ok(_) => panic!("Unknown password policy get must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2642),
+    }
+    match policy_api.drop_policy(&policy.name, None).await {
+        Ok(_) => panic!("Unknown password policy drop must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2642),
+    }
+
+    Ok(())
+}
+
+fn create_test_policy() -> PasswordPolicy {
+    let mut policy = PasswordPolicy::new("strict");
+    policy.min_length = 12;
+    policy.max_retries = 3;
+    policy.history = 5;
+    policy
+}
+
+async fn new_password_policy_api() -> Result<(Arc<MetaEmbedded>, PasswordPolicyMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = PasswordPolicyMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
@@ -23,6 +23,8 @@ use common_meta_api::KVApi;
 use common_meta_types::AuthInfo;
 use common_meta_types::GetKVReply;
 use common_meta_types::KVAppError;
+use common_meta_types::ListKVRangeReply;
+use common_meta_types::ListKVRangeReq;
 use common_meta_types::ListKVReply;
 use common_meta_types::MGetKVReply;
 use common_meta_types::MatchSeq;
@@ -56,6 +58,8 @@ mock! {
 
         async fn prefix_list_kv(&self, prefix: &str) -> Result<ListKVReply, KVAppError>;
 
+        async fn list_kv(&self, req: ListKVRangeReq) -> Result<ListKVRangeReply, KVAppError>;
+
         async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError>;
 
         }
@@ -454,6 +458,7 @@ mod drop {
 }
 
 mod update {
+    use chrono::Utc;
     use common_meta_types::AuthInfo;
     use common_meta_types::UserInfo;
 
@@ -532,6 +537,61 @@ mod update {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_update_user_password() -> common_exception::Result<()> {
+        let test_user_name = "name";
+        let test_hostname = "localhost";
+
+        let test_key = format!(
+            "__fd_users/tenant1/{}",
+            escape_for_key(&format_user_key(test_user_name, test_hostname))?
+        );
+        let update_on = Utc::now();
+
+        let user_info = UserInfo::new(test_user_name, test_hostname, default_test_auth_info());
+        let prev_value = serialize_struct(&user_info, ErrorCode::IllegalUserInfoFormat, || "")?;
+
+        // get_kv should be called
+        let mut kv = MockKV::new();
+        {
+            let test_key = test_key.clone();
+            kv.expect_get_kv()
+                .with(predicate::function(move |v| v == test_key.as_str()))
+                .times(1)
+                .return_once(move |_k| Ok(Some(SeqV::new(0, prev_value))));
+        }
+
+        // and then, update_kv should be called with the old password in the history
+        let mut new_user_info =
+            UserInfo::new(test_user_name, test_hostname, new_test_auth_info(true));
+        new_user_info.history_auth_infos = vec![default_test_auth_info()];
+        new_user_info.password_update_on = Some(update_on);
+        let new_value = serialize_struct(&new_user_info, ErrorCode::IllegalUserInfoFormat, || "")?;
+
+        kv.expect_upsert_kv()
+            .with(predicate::eq(UpsertKVReq::new(
+                &test_key,
+                MatchSeq::GE(1),
+                Operation::Update(new_value),
+                None,
+            )))
+            .times(1)
+            .return_once(|_| Ok(UpsertKVReply::new(None, Some(SeqV::new(0, vec![])))));
+
+        let kv = Arc::new(kv);
+        let user_mgr = UserMgr::create(kv, "tenant1")?;
+
+        let res = user_mgr.update_user_password(
+            user_info.identity(),
+            new_test_auth_info(true),
+            update_on,
+            None,
+        );
+
+        assert!(res.await.is_ok());
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_update_user_partial_unknown() -> common_exception::Result<()> {
         let test_user_name = "name";
@@ -0,0 +1,258 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_ast::parser::token::TokenKind;
+use common_ast::parser::tokenize_sql;
+use common_config::GlobalConfig;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_tracing::AuditLogger;
+use serde::Serialize;
+use tracing::error;
+
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::TableContext;
+
+/// The categories of audit events, selected by the `log.audit.categories` config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditCategory {
+    Login,
+    Privilege,
+    Ddl,
+    Dml,
+}
+
+impl AuditCategory {
+    fn name(&self) -> &'static str {
+        match self {
+            AuditCategory::Login => "login",
+            AuditCategory::Privilege => "privilege",
+            AuditCategory::Ddl => "ddl",
+            AuditCategory::Dml => "dml",
+        }
+    }
+
+    /// Classify a statement by its query kind (the name of its plan), returns None
+    /// for the statements that are not audited, such as queries and SHOW.
+    pub fn from_query_kind(kind: &str) -> Option<AuditCategory> {
+        match kind {
+            "GrantPriv" | "RevokePriv" | "GrantRole" | "RevokeRole" | "CreateUser"
+            | "AlterUser" | "DropUser" | "CreateRole" | "DropRole" | "GrantShareObject"
+            | "RevokeShareObject" => Some(AuditCategory::Privilege),
+            "Insert" | "Delete" | "Update" | "Copy" => Some(AuditCategory::Dml),
+            "AddTableRowAccessPolicy" | "SetTableColumnMaskingPolicy" | "RevertTable" => {
+                Some(AuditCategory::Ddl)
+            }
+            _ if ["Create", "Drop", "Alter", "Rename", "Undrop", "Truncate"]
+                .iter()
+                .any(|prefix| kind.starts_with(prefix)) =>
+            {
+                Some(AuditCategory::Ddl)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if the category is selected by the value of `log.audit.categories`,
+    /// which is `all`, `none` or a comma separated list of category names.
+    pub fn is_selected_by(&self, categories: &str) -> bool {
+        categories.split(',').any(|category| {
+            let category = category.trim();
+            category.eq_ignore_ascii_case("all") || category.eq_ignore_ascii_case(self.name())
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditEvent {
+    pub category: AuditCategory,
+    // Micro seconds since the unix epoch.
+    pub event_time: i64,
+    pub tenant_id: String,
+    pub cluster_id: String,
+    pub session_id: String,
+    pub handler_type: String,
+    pub user: String,
+    pub client_address: String,
+
+    pub query_id: String,
+    pub query_kind: String,
+    pub query_text: String,
+    pub tables: Vec<String>,
+    pub written_rows: u64,
+
+    pub success: bool,
+    pub error_code: i32,
+    pub error_text: String,
+}
+
+/// AuditLog writes the audit events through the `AuditLogger`, they are kept in
+/// rotated json files instead of the in-memory `system.query_log`.
+pub struct AuditLog;
+
+impl AuditLog {
+    /// Log the result of an authentication, `user` and `client_address` come from
+    /// the credential since the session is not authenticated on failure.
+    pub fn log_login(
+        session: &Arc<Session>,
+        user: &str,
+        client_address: Option<&str>,
+        res: &Result<()>,
+    ) {
+        let client_address = match client_address {
+            Some(address) => address.to_string(),
+            None => match session.get_client_host() {
+                Some(address) => address.ip().to_string(),
+                None => "".to_string(),
+            },
+        };
+        let (success, error_code, error_text) = error_fields(res.as_ref().err());
+
+        let event = AuditEvent {
+            category: AuditCategory::Login,
+            event_time: convert_audit_timestamp(SystemTime::now()),
+            tenant_id: session.get_current_tenant(),
+            cluster_id: GlobalConfig::instance().query.cluster_id.clone(),
+            session_id: session.get_id(),
+            handler_type: session.get_type().to_string(),
+            user: user.to_string(),
+            client_address,
+            query_id: "".to_string(),
+            query_kind: "".to_string(),
+            query_text: "".to_string(),
+            tables: vec![],
+            written_rows: 0,
+            success,
+            error_code,
+            error_text,
+        };
+        Self::write_event(event).unwrap_or_else(|e| error!("fail to write audit log {:?}", e));
+    }
+
+    /// Log a statement once it is finished, or denied, statements not in any
+    /// audit category are ignored.
+    pub fn log_query(ctx: &QueryContext, err: Option<&ErrorCode>) {
+        let query_kind = ctx.get_query_kind();
+        let category = match AuditCategory::from_query_kind(&query_kind) {
+            Some(category) => category,
+            None => return,
+        };
+
+        let session = ctx.get_current_session();
+        let user = match ctx.get_current_user() {
+            Ok(user) => user.identity().to_string(),
+            Err(_) => "".to_string(),
+        };
+        let client_address = match ctx.get_client_address() {
+            Some(address) => address.ip().to_string(),
+            None => "".to_string(),
+        };
+        let mut tables: Vec<String> = ctx
+            .get_tables_refs()
+            .iter()
+            .map(|table| table.get_table_info().desc.clone())
+            .collect();
+        tables.sort();
+        let (success, error_code, error_text) = error_fields(err);
+
+        let event = AuditEvent {
+            category,
+            event_time: convert_audit_timestamp(SystemTime::now()),
+            tenant_id: ctx.get_tenant(),
+            cluster_id: GlobalConfig::instance().query.cluster_id.clone(),
+            session_id: session.get_id(),
+            handler_type: session.get_type().to_string(),
+            user,
+            client_address,
+            query_id: ctx.get_id(),
+            query_kind,
+            query_text: redact_query_text(&ctx.get_query_str()),
+            tables,
+            written_rows: ctx.get_write_progress_value().rows as u64,
+            success,
+            error_code,
+            error_text,
+        };
+        Self::write_event(event).unwrap_or_else(|e| error!("fail to write audit log {:?}", e));
+    }
+
+    fn write_event(event: AuditEvent) -> Result<()> {
+        let logger = AuditLogger::instance();
+        if !logger.is_enabled()
+            || !event
+                .category
+                .is_selected_by(&GlobalConfig::instance().log.audit.categories)
+        {
+            return Ok(());
+        }
+
+        logger.write(&serde_json::to_string(&event)?)
+    }
+}
+
+const REDACTED_PASSWORD: &str = "'***'";
+
+/// Replace the passwords of `IDENTIFIED [WITH auth_type] BY '<password>'` by `'***'`,
+/// the whole text is redacted if it can not be tokenized.
+pub fn redact_query_text(sql: &str) -> String {
+    let tokens = match tokenize_sql(sql) {
+        Ok(tokens) => tokens,
+        Err(_) => return REDACTED_PASSWORD.to_string(),
+    };
+
+    let mut redacted = String::with_capacity(sql.len());
+    let mut copied = 0;
+    let mut identified = false;
+    let mut after_by = false;
+    for token in tokens.iter() {
+        match token.kind {
+            TokenKind::IDENTIFIED => identified = true,
+            TokenKind::BY if identified => after_by = true,
+            TokenKind::QuotedString if after_by => {
+                redacted.push_str(&sql[copied..token.span.start]);
+                redacted.push_str(REDACTED_PASSWORD);
+                copied = token.span.end;
+                identified = false;
+                after_by = false;
+            }
+            TokenKind::SemiColon => {
+                identified = false;
+                after_by = false;
+            }
+            _ => after_by = false,
+        }
+    }
+    redacted.push_str(&sql[copied..]);
+    redacted
+}
+
+fn error_fields(err: Option<&ErrorCode>) -> (bool, i32, String) {
+    match err {
+        None => (true, 0, "".to_string()),
+        Some(e) => (false, e.code().into(), e.message()),
+    }
+}
+
+fn convert_audit_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::new(0, 0))
+        .as_micros() as i64
+}
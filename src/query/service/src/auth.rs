@@ -13,17 +13,20 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 pub use common_config::Config;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
 use common_meta_types::UserInfo;
-use common_users::CustomClaims;
 use common_users::JwtAuthenticator;
+use common_users::JwtIdentity;
 use common_users::UserApiProvider;
-use jwtk::Claims;
 
+use crate::audit::AuditLog;
 use crate::sessions::Session;
 
 pub struct AuthMgr {
@@ -40,16 +43,48 @@ pub enum Credential {
         password: Option<Vec<u8>>,
         hostname: Option<String>,
     },
+    /// The ClientProof of a SCRAM-SHA-256 exchange for the AuthMessage,
+    /// which is verified with the `ScramSha256Secret` of the user.
+    ScramSha256 {
+        name: String,
+        auth_message: Vec<u8>,
+        client_proof: Vec<u8>,
+        hostname: Option<String>,
+    },
 }
 
 impl AuthMgr {
     pub async fn create(cfg: &Config) -> Result<Arc<AuthMgr>> {
         Ok(Arc::new(AuthMgr {
-            jwt_auth: JwtAuthenticator::try_create(cfg.query.jwt_key_file.clone()).await?,
+            jwt_auth: JwtAuthenticator::try_create(
+                cfg.query.jwt_key_file.clone(),
+                Duration::from_secs(cfg.query.jwt_key_refresh_interval),
+                Duration::from_secs(cfg.query.jwt_key_min_reload_interval),
+                cfg.query.jwt_issuers.clone(),
+            )
+            .await?,
         }))
     }
 
     pub async fn auth(&self, session: Arc<Session>, credential: &Credential) -> Result<()> {
+        let res = self.auth_credential(&session, credential).await;
+
+        let (user_name, hostname) = match credential {
+            Credential::Jwt { hostname, .. } => (
+                session
+                    .get_current_user()
+                    .map(|user| user.name)
+                    .unwrap_or_default(),
+                hostname,
+            ),
+            Credential::Password { name, hostname, .. }
+            | Credential::ScramSha256 { name, hostname, .. } => (name.clone(), hostname),
+        };
+        AuditLog::log_login(&session, &user_name, hostname.as_deref(), &res);
+        res
+    }
+
+    async fn auth_credential(&self, session: &Arc<Session>, credential: &Credential) -> Result<()> {
         match credential {
             Credential::Jwt {
                 token: t,
@@ -59,10 +94,9 @@ impl AuthMgr {
                     .jwt_auth
                     .as_ref()
                     .ok_or_else(|| ErrorCode::AuthenticateFailure("jwt auth not configured."))?;
-                let parsed_jwt = jwt_auth.parse_jwt(t.as_str()).await?;
-                let (tenant, user_name, auth_role) = self
-                    .process_jwt_claims(&session, parsed_jwt.claims())
-                    .await?;
+                let identity = jwt_auth.parse_jwt(t.as_str()).await?;
+                let (tenant, user_name, auth_roles) =
+                    self.process_jwt_claims(session, &identity).await?;
                 let user_info = UserApiProvider::instance()
                     .get_user_with_client_ip(
                         &tenant,
@@ -70,35 +104,55 @@ impl AuthMgr {
                         h.as_ref().unwrap_or(&"%".to_string()),
                     )
                     .await?;
-                session.set_authed_user(user_info, auth_role).await?;
+                session.set_authed_user(user_info, auth_roles).await?;
             }
             Credential::Password {
                 name: n,
-                password: p,
                 hostname: h,
+                ..
+            }
+            | Credential::ScramSha256 {
+                name: n,
+                hostname: h,
+                ..
             } => {
                 let tenant = session.get_current_tenant();
                 let user = UserApiProvider::instance()
                     .get_user_with_client_ip(&tenant, n, h.as_ref().unwrap_or(&"%".to_string()))
                     .await?;
-                let user = match &user.auth_info {
-                    AuthInfo::None => user,
-                    AuthInfo::Password {
-                        hash_value: h,
-                        hash_method: t,
-                    } => match p {
-                        None => return Err(ErrorCode::AuthenticateFailure("password required")),
-                        Some(p) => {
-                            if *h == t.hash(p) {
-                                user
-                            } else {
-                                return Err(ErrorCode::AuthenticateFailure("wrong password"));
+                let user_api = UserApiProvider::instance();
+                user_api.check_user_lockout(&user)?;
+                let authed = match (&user.auth_info, credential) {
+                    (AuthInfo::None, _) => true,
+                    (AuthInfo::Password { .. }, Credential::Password { password, .. }) => {
+                        match password {
+                            None => {
+                                return Err(ErrorCode::AuthenticateFailure("password required"));
                             }
+                            Some(p) => user.auth_info.verify_password(p),
                         }
-                    },
+                    }
+                    (
+                        AuthInfo::Password {
+                            hash_value,
+                            hash_method: PasswordHashMethod::ScramSha256,
+                        },
+                        Credential::ScramSha256 {
+                            auth_message,
+                            client_proof,
+                            ..
+                        },
+                    ) => ScramSha256Secret::decode(hash_value)?
+                        .verify_client_proof(auth_message, client_proof),
                     _ => return Err(ErrorCode::AuthenticateFailure("wrong auth type")),
                 };
-                session.set_authed_user(user, None).await?;
+                user_api.record_user_login(&tenant, &user, authed).await?;
+                if !authed {
+                    return Err(ErrorCode::AuthenticateFailure("wrong password"));
+                }
+                let must_change_password = user_api.must_change_password(&tenant, &user).await?;
+                session.set_authed_user(user, vec![]).await?;
+                session.set_must_change_password(must_change_password);
             }
         };
         Ok(())
@@ -107,25 +161,22 @@ impl AuthMgr {
     async fn process_jwt_claims(
         &self,
         session: &Arc<Session>,
-        claims: &Claims<CustomClaims>,
-    ) -> Result<(String, String, Option<String>)> {
-        // setup tenant if the JWT claims contain extra.tenant_id
-        if let Some(ref tenant) = claims.extra.tenant_id {
+        identity: &JwtIdentity,
+    ) -> Result<(String, String, Vec<String>)> {
+        // setup tenant if the JWT claims contain the tenant claim
+        if let Some(ref tenant) = identity.tenant_id {
             session.set_current_tenant(tenant.clone());
         }
         let tenant = session.get_current_tenant();
 
-        // take `sub` field in the claims as user name
-        let user_name = claims
-            .sub
-            .clone()
-            .ok_or_else(|| ErrorCode::AuthenticateFailure("sub not found in claims"))?;
+        // the user claim, `sub` by default
+        let user_name = identity.user_name.clone();
 
-        // set user auth_role if claims contain extra.role
-        let auth_role = claims.extra.role.clone();
+        // set user auth_roles if claims contain the role claim
+        let auth_roles = identity.roles.clone();
 
         // create user if not exists when the JWT claims contains ensure_user
-        if let Some(ref ensure_user) = claims.extra.ensure_user {
+        if let Some(ref ensure_user) = identity.ensure_user {
             let mut user_info = UserInfo::new(&user_name, "%", AuthInfo::JWT);
             if let Some(ref roles) = ensure_user.roles {
                 for role in roles.clone().into_iter() {
@@ -139,6 +190,6 @@ impl AuthMgr {
                 .add_user(&tenant, user_info.clone(), true)
                 .await?;
         }
-        Ok((tenant, user_name, auth_role))
+        Ok((tenant, user_name, auth_roles))
     }
 }
@@ -57,6 +57,7 @@ impl CatalogManagerHelper for CatalogManager {
     async fn try_create(conf: &Config) -> Result<Arc<CatalogManager>> {
         let catalog_manager = CatalogManager {
             catalogs: DashMap::new(),
+            storage_usages: DashMap::new(),
         };
 
         catalog_manager.register_build_in_catalogs(conf).await?;
@@ -55,16 +55,19 @@ use crate::catalogs::SYS_DB_ID_BEGIN;
 use crate::catalogs::SYS_TBL_ID_BEGIN;
 use crate::databases::Database;
 use crate::databases::InformationSchemaDatabase;
+use crate::databases::PgCatalogDatabase;
 use crate::databases::SystemDatabase;
 use crate::storages::Table;
 
 /// System Catalog contains ... all the system databases (no surprise :)
 /// Currently, this is only one database here, the "system" db.
 /// "information_schema" db is supposed to held here
+/// "pg_catalog" db holds the catalog views queried by PostgreSQL clients
 #[derive(Clone)]
 pub struct ImmutableCatalog {
     // it's case sensitive, so we will need two same database only with the name's case
     info_schema_db: Arc<InformationSchemaDatabase>,
+    pg_catalog_db: Arc<PgCatalogDatabase>,
     sys_db: Arc<SystemDatabase>,
     sys_db_meta: Arc<InMemoryMetas>,
 }
@@ -75,12 +78,15 @@ impl ImmutableCatalog {
         let mut sys_db_meta = InMemoryMetas::create(SYS_DB_ID_BEGIN, SYS_TBL_ID_BEGIN);
         sys_db_meta.init_db("system");
         sys_db_meta.init_db("information_schema");
+        sys_db_meta.init_db("pg_catalog");
 
         let sys_db = SystemDatabase::create(&mut sys_db_meta, conf);
         let info_schema_db = InformationSchemaDatabase::create(&mut sys_db_meta);
+        let pg_catalog_db = PgCatalogDatabase::create(&mut sys_db_meta);
 
         Ok(Self {
             info_schema_db: Arc::new(info_schema_db),
+            pg_catalog_db: Arc::new(pg_catalog_db),
             sys_db: Arc::new(sys_db),
             sys_db_meta: Arc::new(sys_db_meta),
         })
@@ -97,6 +103,7 @@ impl Catalog for ImmutableCatalog {
         match db_name {
             "system" => Ok(self.sys_db.clone()),
             "information_schema" => Ok(self.info_schema_db.clone()),
+            "pg_catalog" => Ok(self.pg_catalog_db.clone()),
             _ => Err(ErrorCode::UnknownDatabase(format!(
                 "Unknown database {}",
                 db_name
@@ -105,7 +112,11 @@ impl Catalog for ImmutableCatalog {
     }
 
     async fn list_databases(&self, _tenant: &str) -> Result<Vec<Arc<dyn Database>>> {
-        Ok(vec![self.sys_db.clone(), self.info_schema_db.clone()])
+        Ok(vec![
+            self.sys_db.clone(),
+            self.info_schema_db.clone(),
+            self.pg_catalog_db.clone(),
+        ])
     }
 
     async fn create_database(&self, _req: CreateDatabaseReq) -> Result<CreateDatabaseReply> {
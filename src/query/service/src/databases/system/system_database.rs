@@ -35,12 +35,14 @@ use common_storages_system::MetricsTable;
 use common_storages_system::OneTable;
 use common_storages_system::ProcessesTable;
 use common_storages_system::QueryLogTable;
+use common_storages_system::QueryQueueTable;
 use common_storages_system::RolesTable;
 use common_storages_system::SettingsTable;
 use common_storages_system::StagesTable;
 use common_storages_system::TablesTableWithHistory;
 use common_storages_system::TablesTableWithoutHistory;
 use common_storages_system::TracingTable;
+use common_storages_system::UserQuotasTable;
 use common_storages_system::UsersTable;
 
 use crate::catalogs::InMemoryMetas;
@@ -72,6 +74,7 @@ impl SystemDatabase {
             MallocStatsTotalsTable::create(sys_db_meta.next_table_id()),
             ColumnsTable::create(sys_db_meta.next_table_id()),
             UsersTable::create(sys_db_meta.next_table_id()),
+            UserQuotasTable::create(sys_db_meta.next_table_id()),
             Arc::new(QueryLogTable::create(
                 sys_db_meta.next_table_id(),
                 config.query.max_query_log_size,
@@ -84,6 +87,7 @@ impl SystemDatabase {
             RolesTable::create(sys_db_meta.next_table_id()),
             StagesTable::create(sys_db_meta.next_table_id()),
             CatalogsTable::create(sys_db_meta.next_table_id()),
+            QueryQueueTable::create(sys_db_meta.next_table_id()),
         ];
 
         for tbl in table_list.into_iter() {
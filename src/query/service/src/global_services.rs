@@ -22,9 +22,11 @@ use common_storage::CacheOperator;
 use common_storage::DataOperator;
 use common_storage::ShareTableConfig;
 use common_storages_table_meta::caches::CacheManager;
+use common_tracing::AuditLogger;
 use common_tracing::QueryLogger;
 use common_users::RoleCacheManager;
 use common_users::UserApiProvider;
+use common_users::UserQuotaTracker;
 
 use crate::api::DataExchangeManager;
 use crate::catalogs::CatalogManagerHelper;
@@ -46,7 +48,8 @@ impl GlobalServices {
 
         let app_name_shuffle = format!("{}-{}", config.query.tenant_id, config.query.cluster_id);
 
-        QueryLogger::init(app_name_shuffle, &config.log)?;
+        QueryLogger::init(app_name_shuffle.clone(), &config.log)?;
+        AuditLogger::init(app_name_shuffle, &config.log)?;
         GlobalIORuntime::init(config.storage.num_cpus as usize)?;
 
         // Cluster discovery.
@@ -74,6 +77,7 @@ impl GlobalServices {
         )
         .await?;
         RoleCacheManager::init()?;
+        UserQuotaTracker::init()?;
 
         Ok(())
     }
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_exception::Result;
 
+use crate::interpreters::access::PasswordChangeAccess;
 use crate::interpreters::access::PrivilegeAccess;
 use crate::interpreters::ManagementModeAccess;
 use crate::sessions::QueryContext;
@@ -36,6 +37,10 @@ impl Accessor {
     pub fn create(ctx: Arc<QueryContext>) -> Self {
         let mut accessors: HashMap<String, Box<dyn AccessChecker>> = Default::default();
         accessors.insert("management".to_string(), ManagementModeAccess::create());
+        accessors.insert(
+            "password_change".to_string(),
+            PasswordChangeAccess::create(ctx.clone()),
+        );
         accessors.insert("privilege".to_string(), PrivilegeAccess::create(ctx));
         Accessor { accessors }
     }
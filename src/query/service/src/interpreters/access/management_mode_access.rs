@@ -71,6 +71,7 @@ impl AccessChecker for ManagementModeAccess {
                 | Plan::AlterUser(_)
                 | Plan::CreateUser(_)
                 | Plan::DropUser(_)
+                | Plan::UnlockUser(_)
                 // Privilege.
                 | Plan::GrantPriv(_)
                 | Plan::RevokePriv(_)
@@ -85,6 +86,22 @@ impl AccessChecker for ManagementModeAccess {
                 | Plan::CreateUDF(_)
                 | Plan::AlterUDF(_)
                 | Plan::DropUDF(_)
+
+                // Row access policy
+                | Plan::CreateRowAccessPolicy(_)
+                | Plan::DropRowAccessPolicy(_)
+
+                // Masking policy
+                | Plan::CreateMaskingPolicy(_)
+                | Plan::DropMaskingPolicy(_)
+
+                // Workload group
+                | Plan::CreateWorkloadGroup(_)
+                | Plan::DropWorkloadGroup(_)
+
+                // Password policy
+                | Plan::CreatePasswordPolicy(_)
+                | Plan::DropPasswordPolicy(_)
                 | Plan::UseDatabase(_)
                 | Plan::Call(_) => true,
                 _ => false
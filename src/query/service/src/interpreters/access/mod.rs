@@ -14,9 +14,11 @@
 
 mod accessor;
 mod management_mode_access;
+mod password_change_access;
 mod privilege_access;
 
 pub use accessor::AccessChecker;
 pub use accessor::Accessor;
 pub use management_mode_access::ManagementModeAccess;
+pub use password_change_access::PasswordChangeAccess;
 pub use privilege_access::PrivilegeAccess;
@@ -0,0 +1,54 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::interpreters::access::AccessChecker;
+use crate::sessions::QueryContext;
+use crate::sql::plans::Plan;
+
+pub struct PasswordChangeAccess {
+    ctx: Arc<QueryContext>,
+}
+
+impl PasswordChangeAccess {
+    pub fn create(ctx: Arc<QueryContext>) -> Box<dyn AccessChecker> {
+        Box::new(PasswordChangeAccess { ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl AccessChecker for PasswordChangeAccess {
+    // A session whose password must be changed can only change its own password.
+    async fn check(&self, plan: &Plan) -> Result<()> {
+        let session = self.ctx.get_current_session();
+        if !session.get_must_change_password() {
+            return Ok(());
+        }
+
+        let user = session.get_current_user()?;
+        match plan {
+            Plan::AlterUser(plan) if plan.auth_info.is_some() && plan.user == user.identity() => {
+                Ok(())
+            }
+            _ => Err(ErrorCode::MustChangePassword(format!(
+                "Password of user {} has expired or must be changed, please change it with ALTER USER ... IDENTIFIED BY before executing this statement",
+                user.identity()
+            ))),
+        }
+    }
+}
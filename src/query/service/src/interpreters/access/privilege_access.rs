@@ -190,12 +190,73 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::RefreshMaterializedView(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::CreateInvertedIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::DropInvertedIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
             Plan::AlterUser(_) => {}
             Plan::CreateUser(_) => {}
             Plan::DropUser(_) => {}
             Plan::CreateUDF(_) => {}
             Plan::AlterUDF(_) => {}
             Plan::DropUDF(_) => {}
+            // Row access policies decide what other users can see, only super users can manage them.
+            Plan::CreateRowAccessPolicy(_)
+            | Plan::DropRowAccessPolicy(_)
+            | Plan::AddTableRowAccessPolicy(_)
+            | Plan::DropTableRowAccessPolicy(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+            // Likewise masking policies decide what other users can see of a column.
+            Plan::CreateMaskingPolicy(_)
+            | Plan::DropMaskingPolicy(_)
+            | Plan::SetTableColumnMaskingPolicy(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+            // Workload groups limit the queries of other users.
+            Plan::CreateWorkloadGroup(_) | Plan::DropWorkloadGroup(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+            // Password policies and lockouts decide whether other users can login.
+            Plan::CreatePasswordPolicy(_) | Plan::DropPasswordPolicy(_) | Plan::UnlockUser(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
             Plan::CreateRole(_) => {}
             Plan::DropRole(_) => {}
             Plan::GrantRole(_) => {}
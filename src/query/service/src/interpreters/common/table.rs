@@ -19,9 +19,11 @@ use common_catalog::table::AppendMode;
 use common_catalog::table::Table;
 use common_catalog::table_context::TableContext;
 use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_pipeline_core::Pipeline;
 
+use crate::catalogs::CatalogManager;
 use crate::pipelines::processors::TransformAddOn;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -91,3 +93,24 @@ pub fn append2table(
 
     Ok(())
 }
+
+/// Reject writing into a Fuse table once the tenant's tables take up the user's storage quota,
+/// used by INSERT (OVERWRITE included) and COPY INTO.
+pub async fn check_storage_quota(ctx: &QueryContext, table: &Arc<dyn Table>) -> Result<()> {
+    let quota = ctx.get_current_user()?.quota;
+    if quota.max_storage_in_bytes == 0 || table.engine() != "FUSE" {
+        return Ok(());
+    }
+
+    let storage_usage = CatalogManager::instance()
+        .get_tenant_storage_usage(&ctx.get_tenant())
+        .await?;
+    if storage_usage >= quota.max_storage_in_bytes {
+        return Err(ErrorCode::UserQuotaExceeded(format!(
+            "Max storage quota exceeded: tables of the tenant take {} bytes, the quota is {}",
+            storage_usage, quota.max_storage_in_bytes
+        )));
+    }
+
+    Ok(())
+}
@@ -23,6 +23,7 @@ use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::audit::AuditLog;
 use crate::interpreters::InterpreterMetrics;
 use crate::interpreters::InterpreterQueryLog;
 use crate::pipelines::executor::ExecutorSettings;
@@ -73,6 +74,23 @@ pub trait Interpreter: Sync + Send {
             )));
         }
 
+        // Queries wait for their workload group before holding any resources.
+        if let Err(queue_error) = ctx.acquire_workload_permit().await {
+            InterpreterMetrics::record_query_error(&ctx);
+            log_query_finished(&ctx, Some(queue_error.clone()));
+            return Err(queue_error);
+        }
+
+        let settings = ctx.get_settings();
+        let max_threads = match ctx.acquire_quota_resources(settings.get_max_threads()?) {
+            Ok(max_threads) => max_threads,
+            Err(quota_error) => {
+                InterpreterMetrics::record_query_error(&ctx);
+                log_query_finished(&ctx, Some(quota_error.clone()));
+                return Err(quota_error);
+            }
+        };
+
         let query_ctx = ctx.clone();
         build_res.main_pipeline.set_on_finished(move |may_error| {
             InterpreterMetrics::record_query_finished(&query_ctx, may_error.clone());
@@ -84,10 +102,10 @@ pub trait Interpreter: Sync + Send {
             }
         });
 
-        let settings = ctx.get_settings();
         let query_id = ctx.get_id();
-        build_res.set_max_threads(settings.get_max_threads()? as usize);
-        let settings = ExecutorSettings::try_create(&settings, query_id)?;
+        build_res.set_max_threads(max_threads as usize);
+        let mut settings = ExecutorSettings::try_create(&settings, query_id)?;
+        settings.mem_stat = ctx.get_quota_mem_stat();
 
         if build_res.main_pipeline.is_complete_pipeline()? {
             let mut pipelines = build_res.sources_pipelines;
@@ -149,10 +167,13 @@ fn log_query_finished(ctx: &QueryContext, error: Option<ErrorCode>) {
     let session = ctx.get_current_session();
 
     session.get_status().write().query_finish();
+    ctx.release_quota_resources();
+    ctx.release_workload_permit();
     if session.get_type().is_user_session() {
         SessionManager::instance().status.write().query_finish(now)
     }
 
+    AuditLog::log_query(ctx, error.as_ref());
     if let Err(error) = InterpreterQueryLog::log_finish(ctx, now, error) {
         tracing::error!("interpreter.finish.error: {:?}", error)
     }
@@ -38,6 +38,7 @@ use tracing::error;
 use tracing::info;
 
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::check_storage_quota;
 use crate::interpreters::Interpreter;
 use crate::interpreters::SelectInterpreterV2;
 use crate::pipelines::PipelineBuildResult;
@@ -273,6 +274,11 @@ impl CopyInterpreterV2 {
     ) -> Result<PipelineBuildResult> {
         let start = Instant::now();
         let ctx = self.ctx.clone();
+        let to_table = ctx
+            .get_table(catalog_name, database_name, table_name)
+            .await?;
+        check_storage_quota(&ctx, &to_table).await?;
+
         let table_ctx: Arc<dyn TableContext> = ctx.clone();
         let mut stage_table_info = stage_table_info.clone();
         let mut all_source_file_infos =
@@ -319,9 +325,6 @@ impl CopyInterpreterV2 {
                 .await?
         };
 
-        let to_table = ctx
-            .get_table(catalog_name, database_name, table_name)
-            .await?;
         stage_table.set_block_compact_thresholds(to_table.get_block_compact_thresholds());
         stage_table.read_data(table_ctx, &read_source_plan, &mut build_res.main_pipeline)?;
 
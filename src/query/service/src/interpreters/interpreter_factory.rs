@@ -23,6 +23,7 @@ use super::interpreter_catalog_create::CreateCatalogInterpreter;
 use super::interpreter_share_desc::DescShareInterpreter;
 use super::interpreter_user_stage_drop::DropUserStageInterpreter;
 use super::*;
+use crate::audit::AuditLog;
 use crate::interpreters::access::Accessor;
 use crate::interpreters::interpreter_catalog_drop::DropCatalogInterpreter;
 use crate::interpreters::interpreter_copy_v2::CopyInterpreterV2;
@@ -49,6 +50,7 @@ impl InterpreterFactory {
         let access_checker = Accessor::create(ctx.clone());
         access_checker.check(plan).await.map_err(|e| {
             error!("Access.denied(v2): {:?}", e);
+            AuditLog::log_query(&ctx, Some(&e));
             e
         })?;
 
@@ -178,6 +180,17 @@ impl InterpreterFactory {
                 ctx,
                 *drop_view.clone(),
             )?)),
+            Plan::RefreshMaterializedView(refresh_view) => Ok(Arc::new(
+                RefreshMaterializedViewInterpreter::try_create(ctx, *refresh_view.clone())?,
+            )),
+
+            // Indexes
+            Plan::CreateInvertedIndex(create_index) => Ok(Arc::new(
+                CreateInvertedIndexInterpreter::try_create(ctx, *create_index.clone())?,
+            )),
+            Plan::DropInvertedIndex(drop_index) => Ok(Arc::new(
+                DropInvertedIndexInterpreter::try_create(ctx, *drop_index.clone())?,
+            )),
 
             // Users
             Plan::CreateUser(create_user) => Ok(Arc::new(CreateUserInterpreter::try_create(
@@ -188,6 +201,10 @@ impl InterpreterFactory {
                 ctx,
                 *drop_user.clone(),
             )?)),
+            Plan::UnlockUser(unlock_user) => Ok(Arc::new(UnlockUserInterpreter::try_create(
+                ctx,
+                *unlock_user.clone(),
+            )?)),
             Plan::AlterUser(alter_user) => Ok(Arc::new(AlterUserInterpreter::try_create(
                 ctx,
                 *alter_user.clone(),
@@ -270,6 +287,47 @@ impl InterpreterFactory {
                 *drop_udf.clone(),
             )?)),
 
+            // Row access policies
+            Plan::CreateRowAccessPolicy(create_policy) => Ok(Arc::new(
+                CreateRowAccessPolicyInterpreter::try_create(ctx, *create_policy.clone())?,
+            )),
+            Plan::DropRowAccessPolicy(drop_policy) => Ok(Arc::new(
+                DropRowAccessPolicyInterpreter::try_create(ctx, *drop_policy.clone())?,
+            )),
+            Plan::AddTableRowAccessPolicy(add_policy) => Ok(Arc::new(
+                AddTableRowAccessPolicyInterpreter::try_create(ctx, *add_policy.clone())?,
+            )),
+            Plan::DropTableRowAccessPolicy(drop_policy) => Ok(Arc::new(
+                DropTableRowAccessPolicyInterpreter::try_create(ctx, *drop_policy.clone())?,
+            )),
+
+            // Masking policies
+            Plan::CreateMaskingPolicy(create_policy) => Ok(Arc::new(
+                CreateMaskingPolicyInterpreter::try_create(ctx, *create_policy.clone())?,
+            )),
+            Plan::DropMaskingPolicy(drop_policy) => Ok(Arc::new(
+                DropMaskingPolicyInterpreter::try_create(ctx, *drop_policy.clone())?,
+            )),
+            Plan::SetTableColumnMaskingPolicy(set_policy) => Ok(Arc::new(
+                SetTableColumnMaskingPolicyInterpreter::try_create(ctx, *set_policy.clone())?,
+            )),
+
+            // Workload groups
+            Plan::CreateWorkloadGroup(create_group) => Ok(Arc::new(
+                CreateWorkloadGroupInterpreter::try_create(ctx, *create_group.clone())?,
+            )),
+            Plan::DropWorkloadGroup(drop_group) => Ok(Arc::new(
+                DropWorkloadGroupInterpreter::try_create(ctx, *drop_group.clone())?,
+            )),
+
+            // Password policies
+            Plan::CreatePasswordPolicy(create_policy) => Ok(Arc::new(
+                CreatePasswordPolicyInterpreter::try_create(ctx, *create_policy.clone())?,
+            )),
+            Plan::DropPasswordPolicy(drop_policy) => Ok(Arc::new(
+                DropPasswordPolicyInterpreter::try_create(ctx, *drop_policy.clone())?,
+            )),
+
             Plan::Presign(presign) => Ok(Arc::new(PresignInterpreter::try_create(
                 ctx,
                 *presign.clone(),
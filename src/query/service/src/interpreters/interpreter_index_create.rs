@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_sql::plans::CreateInvertedIndexPlan;
+use common_storages_index::InvertedIndexDefinitions;
+use common_storages_table_meta::table::OPT_KEY_INVERTED_INDEXES;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct CreateInvertedIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateInvertedIndexPlan,
+}
+
+impl CreateInvertedIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateInvertedIndexPlan) -> Result<Self> {
+        Ok(CreateInvertedIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateInvertedIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateInvertedIndexInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let table_info = table.get_table_info();
+        let mut definitions = InvertedIndexDefinitions::from_options(table_info.options())?;
+        if definitions.indexes.contains_key(&plan.index_name) {
+            return if plan.if_not_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::IndexAlreadyExists(format!(
+                    "inverted index {} already exists on table {}.{}",
+                    plan.index_name, plan.database, plan.table
+                )))
+            };
+        }
+
+        // The blocks written from now on are indexed as they are written,
+        // the existing blocks are indexed once the definition is saved.
+        definitions
+            .indexes
+            .insert(plan.index_name.clone(), plan.column.clone());
+        let req = UpsertTableOptionReq {
+            table_id: table_info.ident.table_id,
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options: HashMap::from([(
+                OPT_KEY_INVERTED_INDEXES.to_owned(),
+                Some(definitions.to_option_value()?),
+            )]),
+        };
+        catalog
+            .upsert_table_option(&plan.tenant, &plan.database, req)
+            .await?;
+
+        // Blocks committed concurrently by writers that loaded the table before the
+        // index is created may stay unindexed, which are never pruned by it.
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+        let table_ctx: Arc<dyn TableContext> = self.ctx.clone();
+        fuse_table.build_inverted_indexes(&table_ctx).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
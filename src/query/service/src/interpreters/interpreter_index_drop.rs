@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_sql::plans::DropInvertedIndexPlan;
+use common_storages_index::InvertedIndexDefinitions;
+use common_storages_table_meta::table::OPT_KEY_INVERTED_INDEXES;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct DropInvertedIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: DropInvertedIndexPlan,
+}
+
+impl DropInvertedIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: DropInvertedIndexPlan) -> Result<Self> {
+        Ok(DropInvertedIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropInvertedIndexInterpreter {
+    fn name(&self) -> &str {
+        "DropInvertedIndexInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let table_info = table.get_table_info();
+        let mut definitions = InvertedIndexDefinitions::from_options(table_info.options())?;
+        if definitions.indexes.remove(&plan.index_name).is_none() {
+            return if plan.if_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::UnknownIndex(format!(
+                    "inverted index {} does not exist on table {}.{}",
+                    plan.index_name, plan.database, plan.table
+                )))
+            };
+        }
+
+        // The index files already written are left to be purged along with their blocks.
+        let value = if definitions.indexes.is_empty() {
+            None
+        } else {
+            Some(definitions.to_option_value()?)
+        };
+        let req = UpsertTableOptionReq {
+            table_id: table_info.ident.table_id,
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options: HashMap::from([(OPT_KEY_INVERTED_INDEXES.to_owned(), value)]),
+        };
+        catalog
+            .upsert_table_option(&plan.tenant, &plan.database, req)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -53,6 +53,7 @@ use parking_lot::Mutex;
 use parking_lot::RwLock;
 
 use crate::interpreters::common::append2table;
+use crate::interpreters::common::check_storage_quota;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::pipelines::processors::TransformAddOn;
@@ -219,6 +220,7 @@ impl Interpreter for InsertInterpreterV2 {
             .ctx
             .get_table(&plan.catalog, &plan.database, &plan.table)
             .await?;
+        check_storage_quota(&self.ctx, &table).await?;
 
         let mut build_res = PipelineBuildResult::create();
 
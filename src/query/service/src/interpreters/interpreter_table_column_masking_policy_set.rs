@@ -0,0 +1,93 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_meta_types::TableMaskingPolicies;
+use common_sql::plans::SetTableColumnMaskingPolicyPlan;
+use common_storages_table_meta::table::OPT_KEY_MASKING_POLICIES;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct SetTableColumnMaskingPolicyInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: SetTableColumnMaskingPolicyPlan,
+}
+
+impl SetTableColumnMaskingPolicyInterpreter {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: SetTableColumnMaskingPolicyPlan,
+    ) -> Result<Self> {
+        Ok(SetTableColumnMaskingPolicyInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SetTableColumnMaskingPolicyInterpreter {
+    fn name(&self) -> &str {
+        "SetTableColumnMaskingPolicyInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let table_info = table.get_table_info();
+        let mut policies = match table_info.options().get(OPT_KEY_MASKING_POLICIES) {
+            Some(value) => serde_json::from_str::<TableMaskingPolicies>(value)?,
+            None => TableMaskingPolicies::default(),
+        };
+        match &plan.policy {
+            Some(policy) => {
+                policies.columns.insert(plan.column.clone(), policy.clone());
+            }
+            None => {
+                if policies.columns.remove(&plan.column).is_none() {
+                    return Err(ErrorCode::UnknownMaskingPolicy(format!(
+                        "column {} of table {}.{} has no masking policy",
+                        plan.column, plan.database, plan.table
+                    )));
+                }
+            }
+        }
+
+        let value = if policies.columns.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&policies)?)
+        };
+        let req = UpsertTableOptionReq {
+            table_id: table_info.ident.table_id,
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options: HashMap::from([(OPT_KEY_MASKING_POLICIES.to_owned(), value)]),
+        };
+        catalog
+            .upsert_table_option(&plan.tenant, &plan.database, req)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_ast::ast::Engine;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
@@ -22,6 +23,9 @@ use common_meta_app::schema::CreateTableReq;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableNameIdent;
 use common_sql::plans::CreateTablePlanV2;
+use common_storages_table_meta::table::OPT_KEY_MV_QUERY;
+use common_storages_view::materialized_view::MaterializedViewMeta;
+use common_storages_view::materialized_view::MaterializedViewRefs;
 use common_users::UserApiProvider;
 
 use crate::interpreters::InsertInterpreterV2;
@@ -33,6 +37,7 @@ use crate::sql::executor::PhysicalScalarBuilder;
 use crate::sql::plans::insert::Insert;
 use crate::sql::plans::insert::InsertInputSource;
 use crate::sql::plans::Plan;
+use crate::storages::fuse::FuseTable;
 use crate::storages::StorageDescription;
 
 pub struct CreateTableInterpreterV2 {
@@ -100,6 +105,18 @@ impl Interpreter for CreateTableInterpreterV2 {
             }
         }
 
+        if engine == Engine::Fuse {
+            FuseTable::check_ngram_filter_columns(&self.plan.options, &self.plan.schema)?;
+        }
+
+        // A materialized view is populated only once, when it is created.
+        if self.plan.if_not_exists
+            && !name_not_duplicate
+            && self.plan.options.contains_key(OPT_KEY_MV_QUERY)
+        {
+            return Ok(PipelineBuildResult::create());
+        }
+
         match &self.plan.as_select {
             Some(select_plan_node) => self.create_table_as_select(select_plan_node.clone()).await,
             None => self.create_table().await,
@@ -117,6 +134,17 @@ impl CreateTableInterpreterV2 {
         let table = catalog
             .get_table(tenant.as_str(), &self.plan.database, &self.plan.table)
             .await?;
+        if let Some(meta) = MaterializedViewMeta::from_table_info(table.get_table_info())? {
+            MaterializedViewRefs::update(
+                catalog.as_ref(),
+                &tenant,
+                &meta,
+                &self.plan.database,
+                &self.plan.table,
+                true,
+            )
+            .await?;
+        }
 
         // If the table creation query contains column definitions, like 'CREATE TABLE t1(a int) AS SELECT * from t2',
         // we use the definitions to create the table schema. It may happen that the "AS SELECT" query's schema doesn't
@@ -18,6 +18,8 @@ use common_catalog::table::TableExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_sql::plans::DropTablePlan;
+use common_storages_view::materialized_view::MaterializedViewMeta;
+use common_storages_view::materialized_view::MaterializedViewRefs;
 use common_storages_view::view_table::VIEW_ENGINE;
 
 use crate::interpreters::Interpreter;
@@ -65,6 +67,18 @@ impl Interpreter for DropTableInterpreter {
         catalog.drop_table(self.plan.clone().into()).await?;
 
         if let Some(tbl) = tbl {
+            if let Some(meta) = MaterializedViewMeta::from_table_info(tbl.get_table_info())? {
+                MaterializedViewRefs::update(
+                    catalog.as_ref(),
+                    &self.plan.tenant,
+                    &meta,
+                    db_name,
+                    tbl_name,
+                    false,
+                )
+                .await?;
+            }
+
             // if `plan.all`, truncate, then purge the historical data
             if self.plan.all {
                 let purge = true;
@@ -14,7 +14,10 @@
 
 use std::sync::Arc;
 
+use chrono::Duration;
 use common_catalog::table::CompactTarget;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_sql::plans::OptimizeTableAction;
 use common_sql::plans::OptimizeTablePlan;
@@ -26,6 +29,7 @@ use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
 
 pub struct OptimizeTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -44,6 +48,10 @@ impl Interpreter for OptimizeTableInterpreter {
         "OptimizeTableInterpreter"
     }
 
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = &self.plan;
         let ctx = self.ctx.clone();
@@ -53,6 +61,27 @@ impl Interpreter for OptimizeTableInterpreter {
             .await?;
 
         let action = &plan.action;
+        if let OptimizeTableAction::PurgeOrphans {
+            older_than,
+            dry_run,
+        } = action
+        {
+            let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+            fuse_table.check_mutable()?;
+            let table_ctx: Arc<dyn TableContext> = ctx;
+            let older_than = older_than.map(|seconds| Duration::seconds(seconds as i64));
+            let orphans = fuse_table
+                .do_purge_orphans(&table_ctx, older_than, *dry_run)
+                .await?;
+
+            let block = DataBlock::create(plan.schema(), vec![
+                Series::from_data(orphans.iter().map(|v| v.file_type).collect::<Vec<_>>()),
+                Series::from_data(orphans.iter().map(|v| v.file_count).collect::<Vec<_>>()),
+                Series::from_data(orphans.iter().map(|v| v.file_size).collect::<Vec<_>>()),
+            ]);
+            return PipelineBuildResult::from_blocks(vec![block]);
+        }
+
         let do_purge = matches!(
             action,
             OptimizeTableAction::Purge | OptimizeTableAction::All
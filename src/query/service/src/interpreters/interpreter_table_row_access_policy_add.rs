@@ -0,0 +1,82 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_meta_types::TableRowAccessPolicy;
+use common_sql::plans::AddTableRowAccessPolicyPlan;
+use common_storages_table_meta::table::OPT_KEY_ROW_ACCESS_POLICY;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct AddTableRowAccessPolicyInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AddTableRowAccessPolicyPlan,
+}
+
+impl AddTableRowAccessPolicyInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AddTableRowAccessPolicyPlan) -> Result<Self> {
+        Ok(AddTableRowAccessPolicyInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AddTableRowAccessPolicyInterpreter {
+    fn name(&self) -> &str {
+        "AddTableRowAccessPolicyInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let table_info = table.get_table_info();
+        if let Some(value) = table_info.options().get(OPT_KEY_ROW_ACCESS_POLICY) {
+            let attached: TableRowAccessPolicy = serde_json::from_str(value)?;
+            return Err(ErrorCode::RowAccessPolicyAlreadyExists(format!(
+                "table {}.{} already has row access policy {}",
+                plan.database, plan.table, attached.policy
+            )));
+        }
+
+        let attached = TableRowAccessPolicy {
+            policy: plan.policy.clone(),
+            columns: plan.columns.clone(),
+        };
+        let req = UpsertTableOptionReq {
+            table_id: table_info.ident.table_id,
+            seq: MatchSeq::Exact(table_info.ident.seq),
+            options: HashMap::from([(
+                OPT_KEY_ROW_ACCESS_POLICY.to_owned(),
+                Some(serde_json::to_string(&attached)?),
+            )]),
+        };
+        catalog
+            .upsert_table_option(&plan.tenant, &plan.database, req)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -46,9 +46,16 @@ impl Interpreter for AlterUserInterpreter {
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         if plan.auth_info.is_some() || plan.user_option.is_some() {
+            let session = self.ctx.get_current_session();
+            let password_changed =
+                plan.auth_info.is_some() && session.get_current_user()?.identity() == plan.user;
             UserApiProvider::instance()
                 .update_user(&tenant, plan.user, plan.auth_info, plan.user_option)
                 .await?;
+            // The session is released once the current user changed the password.
+            if password_changed {
+                session.set_must_change_password(false);
+            }
         }
 
         Ok(PipelineBuildResult::create())
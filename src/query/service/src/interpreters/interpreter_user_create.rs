@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::UserGrantSet;
@@ -70,6 +71,10 @@ impl Interpreter for CreateUserInterpreter {
             grants: UserGrantSet::empty(),
             quota: UserQuota::no_limit(),
             option: plan.user_option,
+            history_auth_infos: vec![],
+            password_fails: vec![],
+            password_update_on: Some(Utc::now()),
+            lockout_time: None,
         };
         user_mgr
             .add_user(&tenant, user_info, plan.if_not_exists)
@@ -0,0 +1,149 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_sql::plans::RefreshMaterializedViewPlan;
+use common_storages_table_meta::table::OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::MaterializedViewMeta;
+use tracing::info;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::Planner;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+
+pub struct RefreshMaterializedViewInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: RefreshMaterializedViewPlan,
+}
+
+impl RefreshMaterializedViewInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: RefreshMaterializedViewPlan) -> Result<Self> {
+        Ok(RefreshMaterializedViewInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for RefreshMaterializedViewInterpreter {
+    fn name(&self) -> &str {
+        "RefreshMaterializedViewInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let view = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.viewname)
+            .await?;
+        let meta =
+            MaterializedViewMeta::from_table_info(view.get_table_info())?.ok_or_else(|| {
+                ErrorCode::BadArguments(format!(
+                    "{}.{} is not a materialized view",
+                    plan.database, plan.viewname
+                ))
+            })?;
+
+        let source = catalog
+            .get_table(&plan.tenant, &meta.source_database, &meta.source_table)
+            .await?;
+        if source.get_id() != meta.source_table_id {
+            return Err(ErrorCode::UnknownTable(format!(
+                "source table {}.{} of materialized view {}.{} has been dropped",
+                meta.source_database, meta.source_table, plan.database, plan.viewname
+            )));
+        }
+
+        let source_snapshot_location = source
+            .get_table_info()
+            .options()
+            .get(OPT_KEY_SNAPSHOT_LOCATION)
+            .cloned();
+        if source_snapshot_location == meta.source_snapshot_location {
+            return Ok(PipelineBuildResult::create());
+        }
+
+        // Merge the data appended since the last refresh into the view if possible,
+        // otherwise recompute the view from the whole source table.
+        let changes = match &meta.source_snapshot_location {
+            Some(base_snapshot_location) => {
+                FuseTable::try_from_table(source.as_ref())?
+                    .navigate_to_changes(self.ctx.clone(), base_snapshot_location)
+                    .await?
+            }
+            None => None,
+        };
+        let (sql, source): (String, Arc<dyn Table>) = match changes {
+            Some(changes) => {
+                let sql =
+                    meta.incremental_refresh_sql(&plan.catalog, &plan.database, &plan.viewname)?;
+                (sql, changes as Arc<dyn Table>)
+            }
+            None => {
+                let sql = meta.full_refresh_sql(&plan.catalog, &plan.database, &plan.viewname)?;
+                (sql, source)
+            }
+        };
+        info!(
+            "refresh materialized view {}.{}: {}",
+            plan.database, plan.viewname, sql
+        );
+
+        // Both tables are pinned for the query: the source is read exactly at the snapshot
+        // recorded below, and the recorded snapshot is committed along with the new content.
+        // The commit fails if the view has been refreshed from another base meanwhile.
+        let mut view_info = view.get_table_info().clone();
+        view_info.meta.options.insert(
+            OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION.to_owned(),
+            serde_json::to_string(&meta.source_snapshot_location)?,
+        );
+        match source_snapshot_location {
+            Some(location) => {
+                view_info
+                    .meta
+                    .options
+                    .insert(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION.to_owned(), location);
+            }
+            None => {
+                view_info
+                    .meta
+                    .options
+                    .remove(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION);
+            }
+        }
+        let view = catalog.get_table_by_info(&view_info)?;
+        self.ctx.attach_table(
+            &plan.catalog,
+            &meta.source_database,
+            &meta.source_table,
+            source,
+        );
+        self.ctx
+            .attach_table(&plan.catalog, &plan.database, &plan.viewname, view);
+
+        let mut planner = Planner::new(self.ctx.clone());
+        let (refresh_plan, _, _) = planner.plan_sql(&sql).await?;
+        let interpreter = InterpreterFactory::get(self.ctx.clone(), &refresh_plan).await?;
+        interpreter.execute2().await
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_sql::plans::CreateWorkloadGroupPlan;
+use common_users::UserApiProvider;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::TableContext;
+
+#[derive(Debug)]
+pub struct CreateWorkloadGroupInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateWorkloadGroupPlan,
+}
+
+impl CreateWorkloadGroupInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateWorkloadGroupPlan) -> Result<Self> {
+        Ok(CreateWorkloadGroupInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateWorkloadGroupInterpreter {
+    fn name(&self) -> &str {
+        "CreateWorkloadGroupInterpreter"
+    }
+
+    #[tracing::instrument(level = "info", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = self.plan.clone();
+        let _ = UserApiProvider::instance()
+            .add_workload_group(&plan.tenant, plan.group, plan.if_not_exists)
+            .await?;
+        SessionManager::instance()
+            .query_queue
+            .invalidate_workload_groups(&plan.tenant);
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -31,10 +31,16 @@ mod interpreter_database_undrop;
 mod interpreter_delete;
 mod interpreter_explain_v2;
 mod interpreter_factory;
+mod interpreter_index_create;
+mod interpreter_index_drop;
 mod interpreter_insert_v2;
 mod interpreter_kill;
 mod interpreter_list;
+mod interpreter_masking_policy_create;
+mod interpreter_masking_policy_drop;
 mod interpreter_metrics;
+mod interpreter_password_policy_create;
+mod interpreter_password_policy_drop;
 mod interpreter_presign;
 mod interpreter_privilege_grant;
 mod interpreter_privilege_revoke;
@@ -45,6 +51,8 @@ mod interpreter_role_grant;
 mod interpreter_role_revoke;
 mod interpreter_role_set;
 mod interpreter_role_show;
+mod interpreter_row_access_policy_create;
+mod interpreter_row_access_policy_drop;
 mod interpreter_select_v2;
 mod interpreter_setting;
 mod interpreter_share_alter_tenants;
@@ -58,6 +66,7 @@ mod interpreter_share_show_grant_tenants;
 mod interpreter_show_grants;
 mod interpreter_show_object_grant_privileges;
 mod interpreter_table_analyze;
+mod interpreter_table_column_masking_policy_set;
 mod interpreter_table_create_v2;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
@@ -66,6 +75,8 @@ mod interpreter_table_optimize;
 mod interpreter_table_recluster;
 mod interpreter_table_rename;
 mod interpreter_table_revert;
+mod interpreter_table_row_access_policy_add;
+mod interpreter_table_row_access_policy_drop;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
 mod interpreter_table_undrop;
@@ -80,9 +91,13 @@ mod interpreter_user_stage_remove;
 mod interpreter_user_udf_alter;
 mod interpreter_user_udf_create;
 mod interpreter_user_udf_drop;
+mod interpreter_user_unlock;
 mod interpreter_view_alter;
 mod interpreter_view_create;
 mod interpreter_view_drop;
+mod interpreter_view_refresh;
+mod interpreter_workload_group_create;
+mod interpreter_workload_group_drop;
 
 pub use access::ManagementModeAccess;
 pub use common::append2table;
@@ -100,10 +115,16 @@ pub use interpreter_database_undrop::UndropDatabaseInterpreter;
 pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_explain_v2::ExplainInterpreter;
 pub use interpreter_factory::InterpreterFactory;
+pub use interpreter_index_create::CreateInvertedIndexInterpreter;
+pub use interpreter_index_drop::DropInvertedIndexInterpreter;
 pub use interpreter_insert_v2::InsertInterpreterV2;
 pub use interpreter_kill::KillInterpreter;
 pub use interpreter_list::ListInterpreter;
+pub use interpreter_masking_policy_create::CreateMaskingPolicyInterpreter;
+pub use interpreter_masking_policy_drop::DropMaskingPolicyInterpreter;
 pub use interpreter_metrics::InterpreterMetrics;
+pub use interpreter_password_policy_create::CreatePasswordPolicyInterpreter;
+pub use interpreter_password_policy_drop::DropPasswordPolicyInterpreter;
 pub use interpreter_privilege_grant::GrantPrivilegeInterpreter;
 pub use interpreter_privilege_revoke::RevokePrivilegeInterpreter;
 pub use interpreter_query_log::InterpreterQueryLog;
@@ -112,6 +133,8 @@ pub use interpreter_role_drop::DropRoleInterpreter;
 pub use interpreter_role_grant::GrantRoleInterpreter;
 pub use interpreter_role_revoke::RevokeRoleInterpreter;
 pub use interpreter_role_set::SetRoleInterpreter;
+pub use interpreter_row_access_policy_create::CreateRowAccessPolicyInterpreter;
+pub use interpreter_row_access_policy_drop::DropRowAccessPolicyInterpreter;
 pub use interpreter_select_v2::SelectInterpreterV2;
 pub use interpreter_setting::SettingInterpreter;
 pub use interpreter_share_alter_tenants::AlterShareTenantsInterpreter;
@@ -124,6 +147,7 @@ pub use interpreter_share_show_grant_tenants::ShowGrantTenantsOfShareInterpreter
 pub use interpreter_show_grants::ShowGrantsInterpreter;
 pub use interpreter_show_object_grant_privileges::ShowObjectGrantPrivilegesInterpreter;
 pub use interpreter_table_analyze::AnalyzeTableInterpreter;
+pub use interpreter_table_column_masking_policy_set::SetTableColumnMaskingPolicyInterpreter;
 pub use interpreter_table_create_v2::CreateTableInterpreterV2;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
@@ -131,6 +155,8 @@ pub use interpreter_table_exists::ExistsTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
 pub use interpreter_table_recluster::ReclusterTableInterpreter;
 pub use interpreter_table_rename::RenameTableInterpreter;
+pub use interpreter_table_row_access_policy_add::AddTableRowAccessPolicyInterpreter;
+pub use interpreter_table_row_access_policy_drop::DropTableRowAccessPolicyInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
 pub use interpreter_table_undrop::UndropTableInterpreter;
@@ -145,6 +171,10 @@ pub use interpreter_user_stage_remove::RemoveUserStageInterpreter;
 pub use interpreter_user_udf_alter::AlterUserUDFInterpreter;
 pub use interpreter_user_udf_create::CreateUserUDFInterpreter;
 pub use interpreter_user_udf_drop::DropUserUDFInterpreter;
+pub use interpreter_user_unlock::UnlockUserInterpreter;
 pub use interpreter_view_alter::AlterViewInterpreter;
 pub use interpreter_view_create::CreateViewInterpreter;
 pub use interpreter_view_drop::DropViewInterpreter;
+pub use interpreter_view_refresh::RefreshMaterializedViewInterpreter;
+pub use interpreter_workload_group_create::CreateWorkloadGroupInterpreter;
+pub use interpreter_workload_group_drop::DropWorkloadGroupInterpreter;
@@ -22,6 +22,7 @@ use common_base::runtime::GlobalIORuntime;
 use common_base::runtime::Runtime;
 use common_base::runtime::Thread;
 use common_base::runtime::ThreadJoinHandle;
+use common_base::runtime::ThreadTracker;
 use common_base::runtime::TrySpawn;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -235,6 +236,14 @@ impl PipelineExecutor {
     fn execute_threads(self: &Arc<Self>, threads: usize) -> Vec<ThreadJoinHandle<Result<()>>> {
         let mut thread_join_handles = Vec::with_capacity(threads);
 
+        // The spawned threads inherit the memory tracker of the current thread.
+        let mut tracker = ThreadTracker::create(self.settings.mem_stat.clone());
+        let _guard = self
+            .settings
+            .mem_stat
+            .is_some()
+            .then(|| ThreadTracker::enter(&mut tracker));
+
         for thread_num in 0..threads {
             let this = self.clone();
             #[allow(unused_mut)]
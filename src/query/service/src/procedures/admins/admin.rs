@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::tenant_quota::TenantQuotaProcedure;
+use super::user_quota::UserQuotaProcedure;
 use crate::procedures::ProcedureFactory;
 
 pub struct AdminProcedure;
@@ -23,5 +24,6 @@ impl AdminProcedure {
             "admin$tenant_quota",
             Box::new(TenantQuotaProcedure::try_create),
         );
+        factory.register("admin$user_quota", Box::new(UserQuotaProcedure::try_create));
     }
 }
@@ -14,5 +14,6 @@
 
 mod admin;
 pub mod tenant_quota;
+pub mod user_quota;
 
 pub use admin::AdminProcedure;
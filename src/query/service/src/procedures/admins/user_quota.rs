@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_meta_types::UserIdentity;
+use common_meta_types::UserQuota;
+use common_users::UserApiProvider;
+
+use crate::procedures::OneBlockProcedure;
+use crate::procedures::Procedure;
+use crate::procedures::ProcedureFeatures;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct UserQuotaProcedure;
+
+impl UserQuotaProcedure {
+    pub fn try_create() -> Result<Box<dyn Procedure>> {
+        Ok(UserQuotaProcedure {}.into_procedure())
+    }
+}
+
+#[async_trait::async_trait]
+impl OneBlockProcedure for UserQuotaProcedure {
+    fn name(&self) -> &str {
+        "USER_QUOTA"
+    }
+
+    fn features(&self) -> ProcedureFeatures {
+        ProcedureFeatures::default()
+            .variadic_arguments(2, 5)
+            .management_mode_required(true)
+    }
+
+    /// args:
+    /// user_name: string
+    /// hostname: string
+    /// max_cpu: u64
+    /// max_memory_in_bytes: u64
+    /// max_storage_in_bytes: u64
+    async fn all_data(&self, ctx: Arc<QueryContext>, args: Vec<String>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let user = UserIdentity::new(&args[0], &args[1]);
+        let user_mgr = UserApiProvider::instance();
+        let mut quota = user_mgr.get_user(&tenant, user.clone()).await?.quota;
+
+        if args.len() <= 2 {
+            return self.to_block(&quota);
+        };
+
+        quota.max_cpu = args[2].parse::<u64>()?;
+        if let Some(max_memory_in_bytes) = args.get(3) {
+            quota.max_memory_in_bytes = max_memory_in_bytes.parse::<u64>()?;
+        };
+        if let Some(max_storage_in_bytes) = args.get(4) {
+            quota.max_storage_in_bytes = max_storage_in_bytes.parse::<u64>()?;
+        };
+
+        user_mgr
+            .update_user_quota(&tenant, user, quota.clone())
+            .await?;
+
+        self.to_block(&quota)
+    }
+
+    fn schema(&self) -> Arc<DataSchema> {
+        DataSchemaRefExt::create(vec![
+            DataField::new("max_cpu", u64::to_data_type()),
+            DataField::new("max_memory_in_bytes", u64::to_data_type()),
+            DataField::new("max_storage_in_bytes", u64::to_data_type()),
+        ])
+    }
+}
+
+impl UserQuotaProcedure {
+    fn to_block(&self, quota: &UserQuota) -> Result<DataBlock> {
+        Ok(DataBlock::create(self.schema(), vec![
+            Series::from_data(vec![quota.max_cpu]),
+            Series::from_data(vec![quota.max_memory_in_bytes]),
+            Series::from_data(vec![quota.max_storage_in_bytes]),
+        ]))
+    }
+}
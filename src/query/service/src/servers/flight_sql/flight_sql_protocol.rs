@@ -0,0 +1,223 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Messages of the Arrow Flight SQL protocol, see `FlightSql.proto` in the arrow repository.
+//!
+//! The commands are packed into `google.protobuf.Any`, whose type url tells the command.
+
+use prost::Message;
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/arrow.flight.protocol.sql.";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+pub trait FlightSqlMessage: Message + Default + Sized {
+    const NAME: &'static str;
+
+    fn type_url() -> String {
+        format!("{}{}", TYPE_URL_PREFIX, Self::NAME)
+    }
+
+    fn to_any_bytes(&self) -> Vec<u8> {
+        Any {
+            type_url: Self::type_url(),
+            value: self.encode_to_vec(),
+        }
+        .encode_to_vec()
+    }
+}
+
+macro_rules! flight_sql_message {
+    ($message:ident) => {
+        impl FlightSqlMessage for $message {
+            const NAME: &'static str = stringify!($message);
+        }
+    };
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetSqlInfo {
+    #[prost(uint32, repeated, tag = "1")]
+    pub info: Vec<u32>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetCatalogs {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetDbSchemas {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTables {
+    #[prost(string, optional, tag = "1")]
+    pub catalog: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub db_schema_filter_pattern: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub table_name_filter_pattern: Option<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub table_types: Vec<String>,
+    #[prost(bool, tag = "5")]
+    pub include_schema: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandGetTableTypes {}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementQuery {
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TicketStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandStatementUpdate {
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandPreparedStatementQuery {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommandPreparedStatementUpdate {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DoPutUpdateResult {
+    #[prost(int64, tag = "1")]
+    pub record_count: i64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementRequest {
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionCreatePreparedStatementResult {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub dataset_schema: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub parameter_schema: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ActionClosePreparedStatementRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub prepared_statement_handle: Vec<u8>,
+}
+
+flight_sql_message!(CommandGetSqlInfo);
+flight_sql_message!(CommandGetCatalogs);
+flight_sql_message!(CommandGetDbSchemas);
+flight_sql_message!(CommandGetTables);
+flight_sql_message!(CommandGetTableTypes);
+flight_sql_message!(CommandStatementQuery);
+flight_sql_message!(TicketStatementQuery);
+flight_sql_message!(CommandStatementUpdate);
+flight_sql_message!(CommandPreparedStatementQuery);
+flight_sql_message!(CommandPreparedStatementUpdate);
+flight_sql_message!(DoPutUpdateResult);
+flight_sql_message!(ActionCreatePreparedStatementRequest);
+flight_sql_message!(ActionCreatePreparedStatementResult);
+flight_sql_message!(ActionClosePreparedStatementRequest);
+
+pub const CREATE_PREPARED_STATEMENT: &str = "CreatePreparedStatement";
+pub const CLOSE_PREPARED_STATEMENT: &str = "ClosePreparedStatement";
+
+/// The commands a `FlightDescriptor` or a `Ticket` may carry.
+pub enum Command {
+    GetSqlInfo(CommandGetSqlInfo),
+    GetCatalogs(CommandGetCatalogs),
+    GetDbSchemas(CommandGetDbSchemas),
+    GetTables(CommandGetTables),
+    GetTableTypes(CommandGetTableTypes),
+    StatementQuery(CommandStatementQuery),
+    TicketStatementQuery(TicketStatementQuery),
+    StatementUpdate(CommandStatementUpdate),
+    PreparedStatementQuery(CommandPreparedStatementQuery),
+    PreparedStatementUpdate(CommandPreparedStatementUpdate),
+}
+
+impl Command {
+    pub fn decode(bytes: &[u8]) -> Result<Command, prost::DecodeError> {
+        let any = Any::decode(bytes)?;
+        let value = any.value.as_slice();
+        let name = any
+            .type_url
+            .strip_prefix(TYPE_URL_PREFIX)
+            .unwrap_or(any.type_url.as_str());
+
+        Ok(match name {
+            CommandGetSqlInfo::NAME => Command::GetSqlInfo(Message::decode(value)?),
+            CommandGetCatalogs::NAME => Command::GetCatalogs(Message::decode(value)?),
+            CommandGetDbSchemas::NAME => Command::GetDbSchemas(Message::decode(value)?),
+            CommandGetTables::NAME => Command::GetTables(Message::decode(value)?),
+            CommandGetTableTypes::NAME => Command::GetTableTypes(Message::decode(value)?),
+            CommandStatementQuery::NAME => Command::StatementQuery(Message::decode(value)?),
+            TicketStatementQuery::NAME => Command::TicketStatementQuery(Message::decode(value)?),
+            CommandStatementUpdate::NAME => Command::StatementUpdate(Message::decode(value)?),
+            CommandPreparedStatementQuery::NAME => {
+                Command::PreparedStatementQuery(Message::decode(value)?)
+            }
+            CommandPreparedStatementUpdate::NAME => {
+                Command::PreparedStatementUpdate(Message::decode(value)?)
+            }
+            other => {
+                return Err(prost::DecodeError::new(format!(
+                    "unsupported flight sql command {}",
+                    other
+                )));
+            }
+        })
+    }
+
+    pub fn decode_action<T: FlightSqlMessage>(bytes: &[u8]) -> Result<T, prost::DecodeError> {
+        let any = Any::decode(bytes)?;
+        if any.type_url != T::type_url() {
+            return Err(prost::DecodeError::new(format!(
+                "expect {}, but got {}",
+                T::type_url(),
+                any.type_url
+            )));
+        }
+        T::decode(any.value.as_slice())
+    }
+}
@@ -0,0 +1,953 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_arrow::arrow::array::BinaryArray;
+use common_arrow::arrow::array::BooleanArray;
+use common_arrow::arrow::array::Int32Array;
+use common_arrow::arrow::array::Int64Array;
+use common_arrow::arrow::array::UInt32Array;
+use common_arrow::arrow::array::UnionArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::datatypes::UnionMode;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_schemas;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::flight::serialize_schema;
+use common_arrow::arrow::io::flight::serialize_schema_to_info;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow_format::flight::data::Action;
+use common_arrow::arrow_format::flight::data::ActionType;
+use common_arrow::arrow_format::flight::data::Criteria;
+use common_arrow::arrow_format::flight::data::Empty;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::FlightEndpoint;
+use common_arrow::arrow_format::flight::data::FlightInfo;
+use common_arrow::arrow_format::flight::data::HandshakeRequest;
+use common_arrow::arrow_format::flight::data::HandshakeResponse;
+use common_arrow::arrow_format::flight::data::PutResult;
+use common_arrow::arrow_format::flight::data::Result as FlightResult;
+use common_arrow::arrow_format::flight::data::SchemaResult;
+use common_arrow::arrow_format::flight::data::Ticket;
+use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_arrow::ArrayRef;
+use common_base::base::tokio;
+use common_config::DATABEND_COMMIT_VERSION;
+use common_datablocks::SendableDataBlockStream;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_users::jwt_expiration;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use prost::Message;
+use tokio_stream::Stream;
+use tonic::metadata::MetadataValue;
+use tonic::Request;
+use tonic::Response as RawResponse;
+use tonic::Status;
+use tonic::Streaming;
+use tracing::info;
+
+use crate::auth::Credential;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterQueryLog;
+use crate::servers::flight_sql::flight_sql_protocol::*;
+use crate::servers::flight_sql::flight_sql_types::bind_parameters;
+use crate::servers::flight_sql::flight_sql_types::count_parameters;
+use crate::servers::flight_sql::flight_sql_types::parameter_literal;
+use crate::servers::flight_sql::flight_sql_types::quote_string;
+use crate::servers::flight_sql::flight_sql_types::to_client_chunk;
+use crate::servers::flight_sql::flight_sql_types::to_client_schema;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::sql::Planner;
+
+pub type FlightStream<T> =
+    Pin<Box<dyn Stream<Item = std::result::Result<T, Status>> + Send + Sync + 'static>>;
+
+type Response<T> = std::result::Result<RawResponse<T>, Status>;
+type StreamReq<T> = Request<Streaming<T>>;
+type ChunkStream = BoxStream<'static, Result<Chunk<ArrayRef>>>;
+
+// Sessions of the tokens not used for this long are closed.
+const TOKEN_IDLE_TIMEOUT: Duration = Duration::from_secs(3600);
+// How often the idle sessions and the sessions of expired JWT are closed.
+const SESSION_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+// SqlInfo ids, see `SqlInfo` in `FlightSql.proto`.
+const FLIGHT_SQL_SERVER_NAME: u32 = 0;
+const FLIGHT_SQL_SERVER_VERSION: u32 = 1;
+const FLIGHT_SQL_SERVER_ARROW_VERSION: u32 = 2;
+const FLIGHT_SQL_SERVER_READ_ONLY: u32 = 3;
+const SQL_DDL_CATALOG: u32 = 500;
+const SQL_DDL_SCHEMA: u32 = 501;
+const SQL_DDL_TABLE: u32 = 502;
+const SQL_IDENTIFIER_QUOTE_CHAR: u32 = 504;
+
+struct PreparedStatement {
+    query: String,
+    // Literals of the parameters bound by DoPut, one row per execution.
+    parameters: Vec<Vec<String>>,
+}
+
+impl PreparedStatement {
+    fn bound_queries(&self) -> Result<Vec<String>> {
+        match self.parameters.is_empty() {
+            true => Ok(vec![bind_parameters(&self.query, &[])?]),
+            false => self
+                .parameters
+                .iter()
+                .map(|row| bind_parameters(&self.query, row))
+                .collect(),
+        }
+    }
+
+    // Parameters do not change the result schema, plan the query with NULLs.
+    fn describe_query(&self) -> Result<String> {
+        let nulls = vec!["NULL".to_string(); count_parameters(&self.query)];
+        bind_parameters(&self.query, &nulls)
+    }
+}
+
+struct FlightSqlSession {
+    session: Arc<Session>,
+    statements: HashMap<Vec<u8>, PreparedStatement>,
+    last_access: Instant,
+    // The `exp` of the JWT the session is logged in with.
+    expires_at: Option<SystemTime>,
+}
+
+impl FlightSqlSession {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.last_access) >= TOKEN_IDLE_TIMEOUT
+            || matches!(self.expires_at, Some(expires_at) if expires_at <= SystemTime::now())
+    }
+}
+
+type FlightSqlSessions = Mutex<HashMap<String, FlightSqlSession>>;
+
+/// The Arrow Flight SQL service for external clients.
+///
+/// Clients login by Handshake with a `Basic` authorization header, or a `Bearer` JWT,
+/// and are given a bearer token in the response header, which refers to a session
+/// for the following calls.
+pub struct FlightSqlServiceImpl {
+    sessions: Arc<FlightSqlSessions>,
+}
+
+impl FlightSqlServiceImpl {
+    pub fn create() -> Self {
+        let sessions = Arc::new(Mutex::new(HashMap::new()));
+        tokio::spawn(Self::reap_sessions(Arc::downgrade(&sessions)));
+        FlightSqlServiceImpl { sessions }
+    }
+
+    // Closes the expired sessions periodically, until the service is dropped.
+    async fn reap_sessions(sessions: Weak<FlightSqlSessions>) {
+        loop {
+            tokio::time::sleep(SESSION_REAP_INTERVAL).await;
+            let sessions = match sessions.upgrade() {
+                None => return,
+                Some(sessions) => sessions,
+            };
+
+            let now = Instant::now();
+            sessions.lock().retain(|_, s| {
+                let expired = s.is_expired(now);
+                if expired {
+                    info!("Flight SQL session {} expired", s.session.get_id());
+                }
+                !expired
+            });
+        }
+    }
+
+    fn authorization<T>(request: &Request<T>) -> std::result::Result<String, Status> {
+        match request.metadata().get("authorization") {
+            None => Err(Status::unauthenticated("authorization header required")),
+            Some(value) => value
+                .to_str()
+                .map(|v| v.to_string())
+                .map_err(|e| Status::unauthenticated(format!("invalid authorization, {}", e))),
+        }
+    }
+
+    async fn login(&self, authorization: &str, client_ip: Option<String>) -> Result<String> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::FlightSQL)
+            .await?;
+
+        let (token, credential, expires_at) = if let Some(basic) =
+            authorization.strip_prefix("Basic ")
+        {
+            let decoded = base64::decode(basic.trim()).map_err(|e| {
+                ErrorCode::AuthenticateFailure(format!("invalid basic authorization, {}", e))
+            })?;
+            let decoded = String::from_utf8(decoded)?;
+            let (name, password) = decoded.split_once(':').ok_or_else(|| {
+                ErrorCode::AuthenticateFailure("invalid basic authorization, ':' is missing")
+            })?;
+            let token = uuid::Uuid::new_v4().simple().to_string();
+            let credential = Credential::Password {
+                name: name.to_string(),
+                password: Some(password.as_bytes().to_vec()),
+                hostname: client_ip,
+            };
+            (token, credential, None)
+        } else if let Some(jwt) = authorization.strip_prefix("Bearer ") {
+            let jwt = jwt.trim().to_string();
+            // The JWT is the token, which must not be used after it expires.
+            let expires_at = jwt_expiration(&jwt)?.map(|exp| UNIX_EPOCH + Duration::from_secs(exp));
+            if matches!(expires_at, Some(expires_at) if expires_at <= SystemTime::now()) {
+                return Err(ErrorCode::AuthenticateFailure("jwt expired"));
+            }
+            let credential = Credential::Jwt {
+                token: jwt.clone(),
+                hostname: client_ip,
+            };
+            (jwt, credential, expires_at)
+        } else {
+            return Err(ErrorCode::AuthenticateFailure(
+                "unsupported authorization, expect Basic or Bearer",
+            ));
+        };
+
+        let ctx = session.create_query_context().await?;
+        ctx.get_auth_manager()
+            .auth(session.clone(), &credential)
+            .await?;
+
+        self.sessions
+            .lock()
+            .insert(token.clone(), FlightSqlSession {
+                session,
+                statements: HashMap::new(),
+                last_access: Instant::now(),
+                expires_at,
+            });
+        Ok(token)
+    }
+
+    // Returns the token of the request, a bearer JWT is logged in by the way.
+    async fn get_token<T>(&self, request: &Request<T>) -> std::result::Result<String, Status> {
+        let authorization = Self::authorization(request)?;
+        let token = match authorization.strip_prefix("Bearer ") {
+            None => return Err(Status::unauthenticated("bearer token required")),
+            Some(token) => token.trim().to_string(),
+        };
+
+        {
+            let mut sessions = self.sessions.lock();
+            let now = Instant::now();
+            match sessions.get_mut(&token) {
+                // An expired JWT is rejected when it is logged in again.
+                Some(session) if session.is_expired(now) => {
+                    sessions.remove(&token);
+                }
+                Some(session) => {
+                    session.last_access = now;
+                    return Ok(token);
+                }
+                None => {}
+            }
+        }
+
+        let client_ip = request.remote_addr().map(|addr| addr.ip().to_string());
+        self.login(&authorization, client_ip)
+            .await
+            .map_err(|e| Status::unauthenticated(e.message()))
+    }
+
+    fn get_session(&self, token: &str) -> Result<Arc<Session>> {
+        match self.sessions.lock().get(token) {
+            Some(s) => Ok(s.session.clone()),
+            None => Err(ErrorCode::AuthenticateFailure("session expired")),
+        }
+    }
+
+    fn get_statement<T>(
+        &self,
+        token: &str,
+        handle: &[u8],
+        f: impl FnOnce(&mut PreparedStatement) -> Result<T>,
+    ) -> Result<T> {
+        let mut sessions = self.sessions.lock();
+        let statement = sessions
+            .get_mut(token)
+            .and_then(|s| s.statements.get_mut(handle))
+            .ok_or_else(|| ErrorCode::BadArguments("prepared statement not found"))?;
+        f(statement)
+    }
+
+    async fn plan_schema(session: &Arc<Session>, query: &str) -> Result<DataSchemaRef> {
+        let context = session.create_query_context().await?;
+        let mut planner = Planner::new(context);
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        Ok(plan.schema())
+    }
+
+    async fn execute_query(
+        session: &Arc<Session>,
+        query: &str,
+    ) -> Result<(Arc<QueryContext>, DataSchemaRef, SendableDataBlockStream)> {
+        info!("Flight SQL query: {}", query);
+        let context = session.create_query_context().await?;
+
+        let mut planner = Planner::new(context.clone());
+        let (plan, _, _) = planner.plan_sql(query).await?;
+
+        context.attach_query_str(plan.to_string(), query);
+        let interpreter = match InterpreterFactory::get(context.clone(), &plan).await {
+            Ok(interpreter) => interpreter,
+            Err(e) => {
+                InterpreterQueryLog::fail_to_start(context, e.clone());
+                return Err(e);
+            }
+        };
+
+        let stream = interpreter.execute(context.clone()).await?;
+        Ok((context, interpreter.schema(), stream))
+    }
+
+    async fn execute_update(session: &Arc<Session>, query: &str) -> Result<i64> {
+        let (context, _, mut stream) = Self::execute_query(session, query).await?;
+        while let Some(block) = stream.next().await {
+            block?;
+        }
+        Ok(context.get_write_progress_value().rows as i64)
+    }
+
+    async fn query_chunks(
+        session: &Arc<Session>,
+        query: &str,
+    ) -> Result<(ArrowSchema, ChunkStream)> {
+        let (_, schema, stream) = Self::execute_query(session, query).await?;
+        let chunks = stream.map(|block| block.and_then(to_client_chunk)).boxed();
+        Ok((to_client_schema(&schema), chunks))
+    }
+
+    async fn command_schema(&self, token: &str, command: &Command) -> Result<ArrowSchema> {
+        let session = self.get_session(token)?;
+        match command {
+            Command::GetSqlInfo(_) => Ok(sql_info_schema()),
+            Command::GetCatalogs(_) => Ok(utf8_schema(&["catalog_name"])),
+            Command::GetDbSchemas(_) => Ok(utf8_schema(&["catalog_name", "db_schema_name"])),
+            Command::GetTables(cmd) => Ok(tables_schema(cmd.include_schema)),
+            Command::GetTableTypes(_) => Ok(utf8_schema(&["table_type"])),
+            Command::StatementQuery(cmd) => {
+                let schema = Self::plan_schema(&session, &cmd.query).await?;
+                Ok(to_client_schema(&schema))
+            }
+            Command::PreparedStatementQuery(cmd) => {
+                let query = self.get_statement(token, &cmd.prepared_statement_handle, |s| {
+                    s.describe_query()
+                })?;
+                let schema = Self::plan_schema(&session, &query).await?;
+                Ok(to_client_schema(&schema))
+            }
+            _ => Err(ErrorCode::BadArguments(
+                "the command has no result set".to_string(),
+            )),
+        }
+    }
+
+    async fn command_chunks(
+        &self,
+        token: &str,
+        command: Command,
+    ) -> Result<(ArrowSchema, ChunkStream)> {
+        let session = self.get_session(token)?;
+        match command {
+            Command::GetSqlInfo(cmd) => {
+                let chunk = sql_info_chunk(&cmd.info)?;
+                Ok((
+                    sql_info_schema(),
+                    futures::stream::iter(vec![Ok(chunk)]).boxed(),
+                ))
+            }
+            Command::GetCatalogs(_) => {
+                let chunk = Chunk::try_new(vec![
+                    Box::new(Utf8Array::<i32>::from_slice(["default"])) as ArrayRef,
+                ])?;
+                Ok((
+                    utf8_schema(&["catalog_name"]),
+                    futures::stream::iter(vec![Ok(chunk)]).boxed(),
+                ))
+            }
+            Command::GetTableTypes(_) => {
+                let chunk = Chunk::try_new(vec![Box::new(Utf8Array::<i32>::from_slice([
+                    "TABLE", "VIEW",
+                ])) as ArrayRef])?;
+                Ok((
+                    utf8_schema(&["table_type"]),
+                    futures::stream::iter(vec![Ok(chunk)]).boxed(),
+                ))
+            }
+            Command::GetDbSchemas(cmd) => {
+                let mut query = "SELECT 'default' AS catalog_name, name AS db_schema_name \
+                    FROM system.databases WHERE 1 = 1"
+                    .to_string();
+                push_catalog_filter(&mut query, &cmd.catalog);
+                if let Some(pattern) = &cmd.db_schema_filter_pattern {
+                    query.push_str(&format!(" AND name LIKE {}", quote_string(pattern)));
+                }
+                query.push_str(" ORDER BY db_schema_name");
+
+                let (_, chunks) = Self::query_chunks(&session, &query).await?;
+                Ok((utf8_schema(&["catalog_name", "db_schema_name"]), chunks))
+            }
+            Command::GetTables(cmd) => {
+                let mut query = "SELECT 'default' AS catalog_name, database AS db_schema_name, \
+                    name AS table_name, if(engine = 'VIEW', 'VIEW', 'TABLE') AS table_type \
+                    FROM system.tables WHERE 1 = 1"
+                    .to_string();
+                push_catalog_filter(&mut query, &cmd.catalog);
+                if let Some(pattern) = &cmd.db_schema_filter_pattern {
+                    query.push_str(&format!(" AND database LIKE {}", quote_string(pattern)));
+                }
+                if let Some(pattern) = &cmd.table_name_filter_pattern {
+                    query.push_str(&format!(" AND name LIKE {}", quote_string(pattern)));
+                }
+                if !cmd.table_types.is_empty() {
+                    let types = cmd
+                        .table_types
+                        .iter()
+                        .map(|t| quote_string(t))
+                        .collect::<Vec<_>>();
+                    query.push_str(&format!(
+                        " AND if(engine = 'VIEW', 'VIEW', 'TABLE') IN ({})",
+                        types.join(", ")
+                    ));
+                }
+                query.push_str(" ORDER BY db_schema_name, table_name");
+
+                let (_, chunks) = Self::query_chunks(&session, &query).await?;
+                let schema = tables_schema(cmd.include_schema);
+                if !cmd.include_schema {
+                    return Ok((schema, chunks));
+                }
+
+                let chunks = chunks.collect::<Vec<_>>().await;
+                let mut with_schemas = Vec::with_capacity(chunks.len());
+                for chunk in chunks {
+                    with_schemas.push(Ok(Self::append_table_schemas(&session, chunk?).await?));
+                }
+                Ok((schema, futures::stream::iter(with_schemas).boxed()))
+            }
+            Command::StatementQuery(cmd) => Self::query_chunks(&session, &cmd.query).await,
+            Command::TicketStatementQuery(ticket) => {
+                let query = String::from_utf8(ticket.statement_handle)?;
+                Self::query_chunks(&session, &query).await
+            }
+            Command::PreparedStatementQuery(cmd) => {
+                let queries = self
+                    .get_statement(token, &cmd.prepared_statement_handle, |s| s.bound_queries())?;
+                match &queries[..] {
+                    [query] => Self::query_chunks(&session, query).await,
+                    _ => Err(ErrorCode::BadArguments(format!(
+                        "a query with result set can only be executed with one row of parameters, got {}",
+                        queries.len()
+                    ))),
+                }
+            }
+            _ => Err(ErrorCode::BadArguments(
+                "the command has no result set".to_string(),
+            )),
+        }
+    }
+
+    // Appends the `table_schema` column of GetTables.
+    async fn append_table_schemas(
+        session: &Arc<Session>,
+        chunk: Chunk<ArrayRef>,
+    ) -> Result<Chunk<ArrayRef>> {
+        let utf8 = |i: usize| {
+            chunk.arrays()[i]
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .cloned()
+                .ok_or_else(|| ErrorCode::Internal("expect utf8 column"))
+        };
+        let (databases, tables) = (utf8(1)?, utf8(2)?);
+
+        let ctx = session.create_query_context().await?;
+        let mut schemas = Vec::with_capacity(chunk.len());
+        for (database, table) in databases.values_iter().zip(tables.values_iter()) {
+            let table = ctx.get_table("default", database, table).await?;
+            let schema = to_client_schema(&table.schema());
+            let ipc_fields = default_ipc_fields(&schema.fields);
+            schemas.push(serialize_schema_to_info(&schema, Some(&ipc_fields))?);
+        }
+
+        let mut arrays = chunk.into_arrays();
+        arrays.push(Box::new(BinaryArray::<i32>::from_slice(schemas)));
+        Ok(Chunk::try_new(arrays)?)
+    }
+
+    fn flight_info(
+        schema: &ArrowSchema,
+        descriptor: FlightDescriptor,
+        ticket: Vec<u8>,
+    ) -> Result<FlightInfo> {
+        let ipc_fields = default_ipc_fields(&schema.fields);
+        Ok(FlightInfo {
+            schema: serialize_schema_to_info(schema, Some(&ipc_fields))?,
+            flight_descriptor: Some(descriptor),
+            endpoint: vec![FlightEndpoint {
+                ticket: Some(Ticket { ticket }),
+                location: vec![],
+            }],
+            total_records: -1,
+            total_bytes: -1,
+        })
+    }
+
+    // Streams the schema message followed by the record batches.
+    fn flight_data_stream(
+        schema: ArrowSchema,
+        mut chunks: ChunkStream,
+    ) -> FlightStream<FlightData> {
+        let (tx, rx) = async_channel::bounded(4);
+        tokio::spawn(async move {
+            let ipc_fields = default_ipc_fields(&schema.fields);
+            if tx
+                .send(Ok(serialize_schema(&schema, Some(&ipc_fields))))
+                .await
+                .is_err()
+            {
+                return;
+            }
+
+            let options = WriteOptions { compression: None };
+            while let Some(chunk) = chunks.next().await {
+                let serialized = chunk.and_then(|chunk| {
+                    let (dicts, values) = serialize_batch(&chunk, &ipc_fields, &options)?;
+                    match dicts.is_empty() {
+                        true => Ok(values),
+                        false => Err(ErrorCode::Unimplemented(
+                            "Flight SQL does not implement dicts.",
+                        )),
+                    }
+                });
+
+                let failed = serialized.is_err();
+                if tx.send(serialized.map_err(Status::from)).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+        Box::pin(rx)
+    }
+
+    // Reads the parameters of a prepared statement, as rows of SQL literals.
+    async fn read_parameters(
+        first: FlightData,
+        stream: &mut Streaming<FlightData>,
+    ) -> Result<Vec<Vec<String>>> {
+        if first.data_header.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (schema, ipc_schema) = deserialize_schemas(&first.data_header)?;
+        let mut rows = vec![];
+        while let Some(data) = stream.message().await? {
+            let chunk = deserialize_batch(&data, &schema.fields, &ipc_schema, &Default::default())?;
+            for row in 0..chunk.len() {
+                let literals = chunk
+                    .arrays()
+                    .iter()
+                    .map(|array| parameter_literal(array.as_ref(), row))
+                    .collect::<Result<Vec<_>>>()?;
+                rows.push(literals);
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[async_trait::async_trait]
+impl FlightService for FlightSqlServiceImpl {
+    type HandshakeStream = FlightStream<HandshakeResponse>;
+
+    async fn handshake(
+        &self,
+        request: StreamReq<HandshakeRequest>,
+    ) -> Response<Self::HandshakeStream> {
+        let authorization = Self::authorization(&request)?;
+        let client_ip = request.remote_addr().map(|addr| addr.ip().to_string());
+        let token = self
+            .login(&authorization, client_ip)
+            .await
+            .map_err(|e| Status::unauthenticated(e.message()))?;
+
+        let output = HandshakeResponse {
+            protocol_version: 0,
+            payload: token.as_bytes().to_vec(),
+        };
+        let mut response = RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(output))) as FlightStream<HandshakeResponse>
+        );
+        let header = MetadataValue::try_from(format!("Bearer {}", token).as_str())
+            .map_err(|e| Status::internal(e.to_string()))?;
+        response.metadata_mut().insert("authorization", header);
+        Ok(response)
+    }
+
+    type ListFlightsStream = FlightStream<FlightInfo>;
+
+    async fn list_flights(&self, _: Request<Criteria>) -> Response<Self::ListFlightsStream> {
+        Err(Status::unimplemented(
+            "Flight SQL does not implement list_flights.",
+        ))
+    }
+
+    async fn get_flight_info(&self, request: Request<FlightDescriptor>) -> Response<FlightInfo> {
+        let token = self.get_token(&request).await?;
+        let descriptor = request.into_inner();
+        let command = Command::decode(&descriptor.cmd)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let schema = self.command_schema(&token, &command).await?;
+        let ticket = match command {
+            Command::StatementQuery(cmd) => TicketStatementQuery {
+                statement_handle: cmd.query.into_bytes(),
+            }
+            .to_any_bytes(),
+            _ => descriptor.cmd.clone(),
+        };
+        Ok(RawResponse::new(Self::flight_info(
+            &schema, descriptor, ticket,
+        )?))
+    }
+
+    async fn get_schema(&self, request: Request<FlightDescriptor>) -> Response<SchemaResult> {
+        let token = self.get_token(&request).await?;
+        let descriptor = request.into_inner();
+        let command = Command::decode(&descriptor.cmd)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let schema = self.command_schema(&token, &command).await?;
+        let ipc_fields = default_ipc_fields(&schema.fields);
+        Ok(RawResponse::new(SchemaResult {
+            schema: serialize_schema_to_info(&schema, Some(&ipc_fields))
+                .map_err(ErrorCode::from)?,
+        }))
+    }
+
+    type DoGetStream = FlightStream<FlightData>;
+
+    async fn do_get(&self, request: Request<Ticket>) -> Response<Self::DoGetStream> {
+        let token = self.get_token(&request).await?;
+        let ticket = request.into_inner();
+        let command =
+            Command::decode(&ticket.ticket).map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let (schema, chunks) = self.command_chunks(&token, command).await?;
+        Ok(RawResponse::new(Self::flight_data_stream(schema, chunks)))
+    }
+
+    type DoPutStream = FlightStream<PutResult>;
+
+    async fn do_put(&self, request: StreamReq<FlightData>) -> Response<Self::DoPutStream> {
+        let token = self.get_token(&request).await?;
+        let session = self.get_session(&token)?;
+        let mut stream = request.into_inner();
+        let first = match stream.message().await? {
+            None => return Err(Status::invalid_argument("empty do_put stream")),
+            Some(first) => first,
+        };
+        let descriptor = first
+            .flight_descriptor
+            .clone()
+            .ok_or_else(|| Status::invalid_argument("flight descriptor required"))?;
+        let command = Command::decode(&descriptor.cmd)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let record_count = match command {
+            Command::StatementUpdate(cmd) => Self::execute_update(&session, &cmd.query).await?,
+            Command::PreparedStatementQuery(cmd) => {
+                let parameters = Self::read_parameters(first, &mut stream).await?;
+                self.get_statement(&token, &cmd.prepared_statement_handle, |s| {
+                    s.parameters = parameters;
+                    Ok(())
+                })?;
+                return Ok(RawResponse::new(
+                    Box::pin(tokio_stream::empty()) as FlightStream<PutResult>
+                ));
+            }
+            Command::PreparedStatementUpdate(cmd) => {
+                let parameters = Self::read_parameters(first, &mut stream).await?;
+                let queries = self.get_statement(&token, &cmd.prepared_statement_handle, |s| {
+                    if !parameters.is_empty() {
+                        s.parameters = parameters;
+                    }
+                    s.bound_queries()
+                })?;
+
+                let mut record_count = 0;
+                for query in queries {
+                    record_count += Self::execute_update(&session, &query).await?;
+                }
+                record_count
+            }
+            _ => return Err(Status::invalid_argument("unsupported do_put command")),
+        };
+
+        let result = PutResult {
+            app_metadata: DoPutUpdateResult { record_count }.encode_to_vec(),
+        };
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(result))) as FlightStream<PutResult>
+        ))
+    }
+
+    type DoExchangeStream = FlightStream<FlightData>;
+
+    async fn do_exchange(&self, _: StreamReq<FlightData>) -> Response<Self::DoExchangeStream> {
+        Err(Status::unimplemented(
+            "Flight SQL does not implement do_exchange.",
+        ))
+    }
+
+    type DoActionStream = FlightStream<FlightResult>;
+
+    async fn do_action(&self, request: Request<Action>) -> Response<Self::DoActionStream> {
+        let token = self.get_token(&request).await?;
+        let action = request.into_inner();
+
+        let body = match action.r#type.as_str() {
+            CREATE_PREPARED_STATEMENT => {
+                let req =
+                    Command::decode_action::<ActionCreatePreparedStatementRequest>(&action.body)
+                        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+                let statement = PreparedStatement {
+                    query: req.query,
+                    parameters: vec![],
+                };
+
+                let session = self.get_session(&token)?;
+                let dataset_schema =
+                    Self::plan_schema(&session, &statement.describe_query()?).await?;
+                let dataset_schema = to_client_schema(&dataset_schema);
+                let parameter_schema = parameter_schema(count_parameters(&statement.query));
+
+                let handle = uuid::Uuid::new_v4().simple().to_string().into_bytes();
+                match self.sessions.lock().get_mut(&token) {
+                    None => return Err(Status::unauthenticated("session expired")),
+                    Some(s) => s.statements.insert(handle.clone(), statement),
+                };
+
+                ActionCreatePreparedStatementResult {
+                    prepared_statement_handle: handle,
+                    dataset_schema: schema_bytes(&dataset_schema)?,
+                    parameter_schema: match parameter_schema.fields.is_empty() {
+                        true => vec![],
+                        false => schema_bytes(&parameter_schema)?,
+                    },
+                }
+                .to_any_bytes()
+            }
+            CLOSE_PREPARED_STATEMENT => {
+                let req =
+                    Command::decode_action::<ActionClosePreparedStatementRequest>(&action.body)
+                        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+                if let Some(s) = self.sessions.lock().get_mut(&token) {
+                    s.statements.remove(&req.prepared_statement_handle);
+                }
+                return Ok(RawResponse::new(
+                    Box::pin(tokio_stream::empty()) as FlightStream<FlightResult>
+                ));
+            }
+            other => {
+                return Err(Status::invalid_argument(format!(
+                    "unsupported action {}",
+                    other
+                )));
+            }
+        };
+
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(FlightResult { body }))) as FlightStream<FlightResult>,
+        ))
+    }
+
+    type ListActionsStream = FlightStream<ActionType>;
+
+    async fn list_actions(&self, _: Request<Empty>) -> Response<Self::ListActionsStream> {
+        Ok(RawResponse::new(Box::pin(tokio_stream::iter(vec![
+            Ok(ActionType {
+                r#type: CREATE_PREPARED_STATEMENT.to_string(),
+                description: "Creates a reusable prepared statement resource on the server."
+                    .to_string(),
+            }),
+            Ok(ActionType {
+                r#type: CLOSE_PREPARED_STATEMENT.to_string(),
+                description: "Closes a reusable prepared statement resource on the server."
+                    .to_string(),
+            }),
+        ])) as FlightStream<ActionType>))
+    }
+}
+
+fn push_catalog_filter(query: &mut String, catalog: &Option<String>) {
+    if let Some(catalog) = catalog {
+        if catalog != "default" {
+            query.push_str(" AND 1 = 0");
+        }
+    }
+}
+
+fn schema_bytes(schema: &ArrowSchema) -> Result<Vec<u8>> {
+    let ipc_fields = default_ipc_fields(&schema.fields);
+    Ok(serialize_schema_to_info(schema, Some(&ipc_fields))?)
+}
+
+fn utf8_schema(names: &[&str]) -> ArrowSchema {
+    let fields = names
+        .iter()
+        .map(|name| ArrowField::new(*name, ArrowDataType::Utf8, false))
+        .collect::<Vec<_>>();
+    ArrowSchema::from(fields)
+}
+
+fn tables_schema(include_schema: bool) -> ArrowSchema {
+    let mut schema = utf8_schema(&["catalog_name", "db_schema_name", "table_name", "table_type"]);
+    if include_schema {
+        schema.fields.push(ArrowField::new(
+            "table_schema",
+            ArrowDataType::Binary,
+            false,
+        ));
+    }
+    schema
+}
+
+// The types of the parameters are not inferred, clients bind them in any type.
+fn parameter_schema(num_params: usize) -> ArrowSchema {
+    let fields = (1..=num_params)
+        .map(|i| ArrowField::new(format!("${}", i), ArrowDataType::Utf8, true))
+        .collect::<Vec<_>>();
+    ArrowSchema::from(fields)
+}
+
+// Only the scalar members of the `value` union are used.
+fn sql_info_union_type() -> ArrowDataType {
+    ArrowDataType::Union(
+        vec![
+            ArrowField::new("string_value", ArrowDataType::Utf8, false),
+            ArrowField::new("bool_value", ArrowDataType::Boolean, false),
+            ArrowField::new("bigint_value", ArrowDataType::Int64, false),
+            ArrowField::new("int32_bitmask", ArrowDataType::Int32, false),
+        ],
+        Some(vec![0, 1, 2, 3]),
+        UnionMode::Dense,
+    )
+}
+
+fn sql_info_schema() -> ArrowSchema {
+    ArrowSchema::from(vec![
+        ArrowField::new("info_name", ArrowDataType::UInt32, false),
+        ArrowField::new("value", sql_info_union_type(), false),
+    ])
+}
+
+enum SqlInfoValue {
+    String(String),
+    Bool(bool),
+}
+
+fn sql_info_chunk(requested: &[u32]) -> Result<Chunk<ArrayRef>> {
+    let infos = vec![
+        (
+            FLIGHT_SQL_SERVER_NAME,
+            SqlInfoValue::String("Databend".to_string()),
+        ),
+        (
+            FLIGHT_SQL_SERVER_VERSION,
+            SqlInfoValue::String(DATABEND_COMMIT_VERSION.to_string()),
+        ),
+        (
+            FLIGHT_SQL_SERVER_ARROW_VERSION,
+            SqlInfoValue::String("1.3".to_string()),
+        ),
+        (FLIGHT_SQL_SERVER_READ_ONLY, SqlInfoValue::Bool(false)),
+        (SQL_DDL_CATALOG, SqlInfoValue::Bool(false)),
+        (SQL_DDL_SCHEMA, SqlInfoValue::Bool(true)),
+        (SQL_DDL_TABLE, SqlInfoValue::Bool(true)),
+        (
+            SQL_IDENTIFIER_QUOTE_CHAR,
+            SqlInfoValue::String("\"".to_string()),
+        ),
+    ];
+
+    let mut names = vec![];
+    let mut types = vec![];
+    let mut offsets = vec![];
+    let mut strings = vec![];
+    let mut bools = vec![];
+    for (name, value) in infos {
+        if !requested.is_empty() && !requested.contains(&name) {
+            continue;
+        }
+
+        names.push(name);
+        match value {
+            SqlInfoValue::String(v) => {
+                types.push(0i8);
+                offsets.push(strings.len() as i32);
+                strings.push(v);
+            }
+            SqlInfoValue::Bool(v) => {
+                types.push(1i8);
+                offsets.push(bools.len() as i32);
+                bools.push(v);
+            }
+        }
+    }
+
+    let value = UnionArray::try_new(
+        sql_info_union_type(),
+        types.into(),
+        vec![
+            Box::new(Utf8Array::<i32>::from_slice(strings)) as ArrayRef,
+            Box::new(BooleanArray::from_slice(bools)),
+            Box::new(Int64Array::from_slice([])),
+            Box::new(Int32Array::from_slice([])),
+        ],
+        Some(offsets.into()),
+    )?;
+    Ok(Chunk::try_new(vec![
+        Box::new(UInt32Array::from_slice(names)) as ArrayRef,
+        Box::new(value),
+    ])?)
+}
@@ -0,0 +1,203 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::BinaryArray;
+use common_arrow::arrow::array::BooleanArray;
+use common_arrow::arrow::array::PrimitiveArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::types::NativeType;
+use common_arrow::ArrayRef;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Strings are binary in Databend, but clients expect utf8 for the textual columns.
+pub fn to_client_schema(schema: &DataSchema) -> ArrowSchema {
+    let fields = schema
+        .to_arrow()
+        .fields
+        .into_iter()
+        .map(|field| match field.data_type() {
+            ArrowDataType::LargeBinary => {
+                ArrowField::new(&field.name, ArrowDataType::Utf8, field.is_nullable)
+            }
+            _ => field,
+        })
+        .collect::<Vec<_>>();
+    ArrowSchema::from(fields)
+}
+
+pub fn to_client_chunk(block: DataBlock) -> Result<Chunk<ArrayRef>> {
+    let chunk: Chunk<ArrayRef> = block.try_into()?;
+    let arrays = chunk
+        .into_arrays()
+        .into_iter()
+        .map(|array| match array.data_type() {
+            ArrowDataType::LargeBinary => {
+                let binary = array
+                    .as_any()
+                    .downcast_ref::<BinaryArray<i64>>()
+                    .ok_or_else(|| ErrorCode::Internal("LargeBinary array is not binary"))?;
+                let values = binary
+                    .iter()
+                    .map(|v| v.map(std::str::from_utf8).transpose())
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(Box::new(Utf8Array::<i32>::from(values)) as ArrayRef)
+            }
+            _ => Ok(array),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Chunk::try_new(arrays)?)
+}
+
+/// Renders the value at `row` of a parameter array as a SQL literal.
+pub fn parameter_literal(array: &dyn Array, row: usize) -> Result<String> {
+    if array.is_null(row) {
+        return Ok("NULL".to_string());
+    }
+
+    fn primitive<T: NativeType + ToString>(array: &dyn Array, row: usize) -> String {
+        let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+        array.value(row).to_string()
+    }
+
+    fn float<T: NativeType + Into<f64>>(array: &dyn Array, row: usize) -> Result<String> {
+        let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+        let v: f64 = array.value(row).into();
+        match v.is_finite() {
+            true => Ok(format!("{:?}", v)),
+            false => Err(ErrorCode::BadArguments(format!(
+                "Unsupported float parameter {}",
+                v
+            ))),
+        }
+    }
+
+    match array.data_type() {
+        ArrowDataType::Boolean => {
+            let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+            Ok(match array.value(row) {
+                true => "TRUE".to_string(),
+                false => "FALSE".to_string(),
+            })
+        }
+        ArrowDataType::Int8 => Ok(primitive::<i8>(array, row)),
+        ArrowDataType::Int16 => Ok(primitive::<i16>(array, row)),
+        ArrowDataType::Int32 => Ok(primitive::<i32>(array, row)),
+        ArrowDataType::Int64 => Ok(primitive::<i64>(array, row)),
+        ArrowDataType::UInt8 => Ok(primitive::<u8>(array, row)),
+        ArrowDataType::UInt16 => Ok(primitive::<u16>(array, row)),
+        ArrowDataType::UInt32 => Ok(primitive::<u32>(array, row)),
+        ArrowDataType::UInt64 => Ok(primitive::<u64>(array, row)),
+        ArrowDataType::Float32 => float::<f32>(array, row),
+        ArrowDataType::Float64 => float::<f64>(array, row),
+        ArrowDataType::Utf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+            Ok(quote_string(array.value(row)))
+        }
+        ArrowDataType::LargeUtf8 => {
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            Ok(quote_string(array.value(row)))
+        }
+        data_type => Err(ErrorCode::BadArguments(format!(
+            "Unsupported parameter of type {:?}",
+            data_type
+        ))),
+    }
+}
+
+pub fn quote_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\'' => quoted.push_str("''"),
+            '\\' => quoted.push_str("\\\\"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// Byte offsets of the `?` placeholders, skipping quoted strings, identifiers and comments.
+fn placeholders(query: &str) -> Vec<usize> {
+    let bytes = query.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'?' => {
+                result.push(i);
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    result
+}
+
+pub fn count_parameters(query: &str) -> usize {
+    placeholders(query).len()
+}
+
+/// Replaces the `?` placeholders with the literals in order.
+pub fn bind_parameters(query: &str, literals: &[String]) -> Result<String> {
+    let placeholders = placeholders(query);
+    if placeholders.len() != literals.len() {
+        return Err(ErrorCode::BadArguments(format!(
+            "Query has {} parameters, but {} are bound",
+            placeholders.len(),
+            literals.len()
+        )));
+    }
+
+    let mut bound = String::with_capacity(query.len());
+    let mut last = 0;
+    for (pos, literal) in placeholders.into_iter().zip(literals) {
+        bound.push_str(&query[last..pos]);
+        bound.push_str(literal);
+        last = pos + 1;
+    }
+    bound.push_str(&query[last..]);
+    Ok(bound)
+}
@@ -343,7 +343,8 @@ impl HttpQueryHandle {
 
         let query_ctx = ctx.clone();
         let query_id = ctx.get_id();
-        let executor_settings = ExecutorSettings::try_create(&ctx.get_settings(), query_id)?;
+        let mut executor_settings = ExecutorSettings::try_create(&ctx.get_settings(), query_id)?;
+        executor_settings.mem_stat = ctx.get_quota_mem_stat();
 
         let run = move || -> Result<()> {
             let mut pipelines = build_res.sources_pipelines;
@@ -17,13 +17,21 @@
 pub use server::Server;
 pub use server::ShutdownHandle;
 
+pub use self::flight_sql::FlightSQLServer;
 pub use self::http::HttpHandler;
 pub use self::http::HttpHandlerKind;
 pub use self::mysql::MySQLConnection;
 pub use self::mysql::MySQLFederated;
 pub use self::mysql::MySQLHandler;
+pub use self::postgres::PostgresConnection;
+pub use self::postgres::PostgresFederated;
+pub use self::postgres::PostgresHandler;
+pub use self::server_tls::ServerTlsConfig;
 
 pub(crate) mod federated_helper;
+pub mod flight_sql;
 pub mod http;
 mod mysql;
+mod postgres;
 pub(crate) mod server;
+mod server_tls;
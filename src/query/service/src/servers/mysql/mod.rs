@@ -23,6 +23,7 @@ mod writers;
 
 pub use self::mysql_federated::MySQLFederated;
 pub use self::mysql_handler::MySQLHandler;
+pub(crate) use self::mysql_interactive_worker::has_result_set_by_plan;
 pub use self::mysql_session::MySQLConnection;
 
 const MYSQL_VERSION: &str = "8.0.26";
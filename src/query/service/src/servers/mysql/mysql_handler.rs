@@ -28,6 +28,7 @@ use futures::future::AbortRegistration;
 use futures::future::Abortable;
 use futures::StreamExt;
 use opensrv_mysql::*;
+use rustls::ServerConfig;
 use tokio_stream::wrappers::TcpListenerStream;
 use tracing::error;
 use tracing::info;
@@ -37,6 +38,7 @@ use crate::servers::mysql::mysql_session::MySQLConnection;
 use crate::servers::mysql::reject_connection::RejectConnection;
 use crate::servers::server::ListeningStream;
 use crate::servers::server::Server;
+use crate::servers::server_tls::ServerTlsConfig;
 use crate::sessions::SessionManager;
 use crate::sessions::SessionType;
 
@@ -44,15 +46,17 @@ pub struct MySQLHandler {
     abort_handle: AbortHandle,
     abort_registration: Option<AbortRegistration>,
     join_handle: Option<JoinHandle<()>>,
+    tls: Option<Arc<ServerConfig>>,
 }
 
 impl MySQLHandler {
-    pub fn create() -> Result<Box<dyn Server>> {
+    pub fn create(tls: ServerTlsConfig) -> Result<Box<dyn Server>> {
         let (abort_handle, registration) = AbortHandle::new_pair();
         Ok(Box::new(MySQLHandler {
             abort_handle,
             abort_registration: Some(registration),
             join_handle: None,
+            tls: tls.setup()?,
         }))
     }
 
@@ -67,19 +71,26 @@ impl MySQLHandler {
     }
 
     fn listen_loop(&self, stream: ListeningStream, rt: Arc<Runtime>) -> impl Future<Output = ()> {
+        let tls = self.tls.clone();
         stream.for_each(move |accept_socket| {
             let executor = rt.clone();
             let sessions = SessionManager::instance();
+            let tls = tls.clone();
             async move {
                 match accept_socket {
                     Err(error) => error!("Broken session connection: {}", error),
-                    Ok(socket) => MySQLHandler::accept_socket(sessions, executor, socket),
+                    Ok(socket) => MySQLHandler::accept_socket(sessions, executor, socket, tls),
                 };
             }
         })
     }
 
-    fn accept_socket(sessions: Arc<SessionManager>, executor: Arc<Runtime>, socket: TcpStream) {
+    fn accept_socket(
+        sessions: Arc<SessionManager>,
+        executor: Arc<Runtime>,
+        socket: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) {
         executor.spawn(async move {
             match sessions.create_session(SessionType::MySQL).await {
                 Err(error) => {
@@ -88,7 +99,7 @@ impl MySQLHandler {
                 }
                 Ok(session) => {
                     info!("MySQL connection coming: {:?}", socket.peer_addr());
-                    if let Err(error) = MySQLConnection::run_on_stream(session, socket) {
+                    if let Err(error) = MySQLConnection::run_on_stream(session, socket, tls) {
                         error!("Unexpected error occurred during query: {:?}", error);
                     };
                 }
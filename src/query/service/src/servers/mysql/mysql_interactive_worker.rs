@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -26,6 +25,7 @@ use common_datablocks::SendableDataBlockStream;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use common_meta_types::UserOptionFlag;
 use common_users::CertifiedInfo;
 use common_users::UserApiProvider;
 use futures_util::StreamExt;
@@ -41,6 +41,7 @@ use tracing::error;
 use tracing::info;
 use tracing::Instrument;
 
+use crate::audit::AuditLog;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterQueryLog;
@@ -53,11 +54,13 @@ use crate::servers::mysql::MYSQL_VERSION;
 use crate::sessions::QueryContext;
 use crate::sessions::Session;
 use crate::sessions::TableContext;
+use crate::sql::plans::OptimizeTableAction;
+use crate::sql::plans::OptimizeTablePlan;
 use crate::sql::plans::Plan;
 use crate::sql::Planner;
 use crate::stream::DataBlockStream;
 
-fn has_result_set_by_plan(plan: &Plan) -> bool {
+pub(crate) fn has_result_set_by_plan(plan: &Plan) -> bool {
     matches!(
         plan,
         Plan::Query { .. }
@@ -76,23 +79,28 @@ fn has_result_set_by_plan(plan: &Plan) -> bool {
             | Plan::ShowGrants(_)
             | Plan::ListStage(_)
             | Plan::Presign(_)
+            | Plan::OptimizeTable(box OptimizeTablePlan {
+                action: OptimizeTableAction::PurgeOrphans { .. },
+                ..
+            })
     )
 }
 
-struct InteractiveWorkerBase<W: AsyncWrite + Send + Unpin> {
+struct InteractiveWorkerBase {
     session: Arc<Session>,
-    generic_hold: PhantomData<W>,
 }
 
-pub struct InteractiveWorker<W: AsyncWrite + Send + Unpin> {
-    base: InteractiveWorkerBase<W>,
+pub struct InteractiveWorker {
+    base: InteractiveWorkerBase,
     version: String,
     salt: [u8; 20],
     client_addr: String,
+    // Whether the connection is upgraded to TLS by SSLRequest.
+    secure: bool,
 }
 
 #[async_trait::async_trait]
-impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorker<W> {
+impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorker {
     type Error = ErrorCode;
 
     fn version(&self) -> &str {
@@ -132,8 +140,16 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
         let client_addr = self.client_addr.clone();
         let info = CertifiedInfo::create(&username, auth_data, &client_addr);
 
-        let authenticate = self.base.authenticate(salt, info);
-        match authenticate.await {
+        let authenticate = self.base.authenticate(salt, info, self.secure).await;
+        let audit_res = match &authenticate {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ErrorCode::AuthenticateFailure("wrong password")),
+            Err(failure) => Err(failure.clone()),
+        };
+        let client_ip = client_addr.split(':').next();
+        AuditLog::log_login(&self.base.session, &username, client_ip, &audit_res);
+
+        match authenticate {
             Ok(res) => res,
             Err(failure) => {
                 error!(
@@ -260,8 +276,8 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
     }
 }
 
-impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
-    async fn authenticate(&self, salt: &[u8], info: CertifiedInfo) -> Result<bool> {
+impl InteractiveWorkerBase {
+    async fn authenticate(&self, salt: &[u8], info: CertifiedInfo, secure: bool) -> Result<bool> {
         let user_name = &info.user_name;
         let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0];
 
@@ -270,14 +286,35 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
             .get_user_with_client_ip(&ctx.get_tenant(), user_name, client_ip)
             .await?;
 
+        let user_api = UserApiProvider::instance();
+        user_api.check_user_lockout(&user_info)?;
         let authed = user_info.auth_info.auth_mysql(&info.user_password, salt)?;
+        user_api
+            .record_user_login(&ctx.get_tenant(), &user_info, authed)
+            .await?;
         if authed {
-            self.session.set_authed_user(user_info, None).await?;
+            // Checked after the password, so that the option is not told to anyone else.
+            if !secure && user_info.has_option_flag(UserOptionFlag::RequireSsl) {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "user {} requires SSL connection",
+                    user_name
+                )));
+            }
+
+            let must_change_password = user_api
+                .must_change_password(&ctx.get_tenant(), &user_info)
+                .await?;
+            self.session.set_authed_user(user_info, vec![]).await?;
+            self.session.set_must_change_password(must_change_password);
         }
         Ok(authed)
     }
 
-    async fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+    async fn do_prepare<W: AsyncWrite + Send + Unpin>(
+        &mut self,
+        _: &str,
+        writer: StatementMetaWriter<'_, W>,
+    ) -> Result<()> {
         writer
             .error(
                 ErrorKind::ER_UNKNOWN_ERROR,
@@ -287,7 +324,7 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
         Ok(())
     }
 
-    async fn do_execute(
+    async fn do_execute<W: AsyncWrite + Send + Unpin>(
         &mut self,
         _: u32,
         _: ParamParser<'_>,
@@ -419,8 +456,8 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
     }
 }
 
-impl<W: AsyncWrite + Send + Unpin> InteractiveWorker<W> {
-    pub fn create(session: Arc<Session>, client_addr: String) -> InteractiveWorker<W> {
+impl InteractiveWorker {
+    pub fn create(session: Arc<Session>, client_addr: String) -> InteractiveWorker {
         let mut bs = vec![0u8; 20];
         let mut rng = rand::thread_rng();
         rng.fill_bytes(bs.as_mut());
@@ -433,16 +470,18 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorker<W> {
             }
         }
 
-        InteractiveWorker::<W> {
-            base: InteractiveWorkerBase::<W> {
-                session,
-                generic_hold: PhantomData::default(),
-            },
+        InteractiveWorker {
+            base: InteractiveWorkerBase { session },
             salt: scramble,
             version: format!("{}-{}", MYSQL_VERSION, *DATABEND_COMMIT_VERSION),
             client_addr,
+            secure: false,
         }
     }
+
+    pub fn set_secure(&mut self, secure: bool) {
+        self.secure = secure;
+    }
 }
 
 struct ContextProgressReporter {
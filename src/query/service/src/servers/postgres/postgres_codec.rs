@@ -0,0 +1,429 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding and decoding of the PostgreSQL frontend/backend protocol (version 3.0).
+//!
+//! https://www.postgresql.org/docs/current/protocol-message-formats.html
+
+use std::collections::HashMap;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+pub const PROTOCOL_VERSION_3: i32 = 196608;
+pub const CANCEL_REQUEST_CODE: i32 = 80877102;
+pub const SSL_REQUEST_CODE: i32 = 80877103;
+pub const GSSENC_REQUEST_CODE: i32 = 80877104;
+
+// Same as the default `max_allowed_packet` we announce for MySQL clients: 128M.
+const MAX_MESSAGE_LENGTH: usize = 128 * 1024 * 1024;
+
+pub enum StartupMessage {
+    SslRequest,
+    GssEncRequest,
+    CancelRequest { process_id: i32, secret_key: i32 },
+    Startup { parameters: HashMap<String, String> },
+}
+
+pub enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<i32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Bytes>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: u8,
+        name: String,
+    },
+    Sync,
+    Flush,
+    /// PasswordMessage, SASLInitialResponse or SASLResponse, which share the same tag,
+    /// the body is parsed by the authentication that is expecting it.
+    Password(Bytes),
+    Terminate,
+}
+
+pub struct FieldDescription {
+    pub name: String,
+    pub type_oid: i32,
+    pub type_len: i16,
+    pub format: i16,
+}
+
+pub enum BackendMessage {
+    AuthenticationOk,
+    AuthenticationCleartextPassword,
+    AuthenticationSasl(&'static str),
+    AuthenticationSaslContinue(Vec<u8>),
+    AuthenticationSaslFinal(Vec<u8>),
+    ParameterStatus(&'static str, String),
+    BackendKeyData { process_id: i32, secret_key: i32 },
+    ReadyForQuery,
+    RowDescription(Vec<FieldDescription>),
+    DataRow(Vec<Option<Vec<u8>>>),
+    CommandComplete(String),
+    EmptyQueryResponse,
+    ErrorResponse { code: &'static str, message: String },
+    ParseComplete,
+    BindComplete,
+    CloseComplete,
+    NoData,
+    PortalSuspended,
+    ParameterDescription(Vec<i32>),
+}
+
+impl BackendMessage {
+    pub fn encode(&self, buf: &mut BytesMut) {
+        let start = match self {
+            BackendMessage::AuthenticationOk => {
+                let start = begin_message(buf, b'R');
+                buf.put_i32(0);
+                start
+            }
+            BackendMessage::AuthenticationCleartextPassword => {
+                let start = begin_message(buf, b'R');
+                buf.put_i32(3);
+                start
+            }
+            BackendMessage::AuthenticationSasl(mechanism) => {
+                let start = begin_message(buf, b'R');
+                buf.put_i32(10);
+                // The list of mechanisms is terminated by an empty one.
+                put_cstring(buf, mechanism);
+                buf.put_u8(0);
+                start
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                let start = begin_message(buf, b'R');
+                buf.put_i32(11);
+                buf.put_slice(data);
+                start
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                let start = begin_message(buf, b'R');
+                buf.put_i32(12);
+                buf.put_slice(data);
+                start
+            }
+            BackendMessage::ParameterStatus(name, value) => {
+                let start = begin_message(buf, b'S');
+                put_cstring(buf, name);
+                put_cstring(buf, value);
+                start
+            }
+            BackendMessage::BackendKeyData {
+                process_id,
+                secret_key,
+            } => {
+                let start = begin_message(buf, b'K');
+                buf.put_i32(*process_id);
+                buf.put_i32(*secret_key);
+                start
+            }
+            BackendMessage::ReadyForQuery => {
+                let start = begin_message(buf, b'Z');
+                // Transactions are not supported, we are always idle.
+                buf.put_u8(b'I');
+                start
+            }
+            BackendMessage::RowDescription(fields) => {
+                let start = begin_message(buf, b'T');
+                buf.put_i16(fields.len() as i16);
+                for field in fields {
+                    put_cstring(buf, &field.name);
+                    // table oid and column attribute number.
+                    buf.put_i32(0);
+                    buf.put_i16(0);
+                    buf.put_i32(field.type_oid);
+                    buf.put_i16(field.type_len);
+                    // type modifier.
+                    buf.put_i32(-1);
+                    buf.put_i16(field.format);
+                }
+                start
+            }
+            BackendMessage::DataRow(values) => {
+                let start = begin_message(buf, b'D');
+                buf.put_i16(values.len() as i16);
+                for value in values {
+                    match value {
+                        None => buf.put_i32(-1),
+                        Some(value) => {
+                            buf.put_i32(value.len() as i32);
+                            buf.put_slice(value);
+                        }
+                    }
+                }
+                start
+            }
+            BackendMessage::CommandComplete(tag) => {
+                let start = begin_message(buf, b'C');
+                put_cstring(buf, tag);
+                start
+            }
+            BackendMessage::EmptyQueryResponse => begin_message(buf, b'I'),
+            BackendMessage::ErrorResponse { code, message } => {
+                let start = begin_message(buf, b'E');
+                buf.put_u8(b'S');
+                put_cstring(buf, "ERROR");
+                buf.put_u8(b'V');
+                put_cstring(buf, "ERROR");
+                buf.put_u8(b'C');
+                put_cstring(buf, code);
+                buf.put_u8(b'M');
+                put_cstring(buf, message);
+                buf.put_u8(0);
+                start
+            }
+            BackendMessage::ParseComplete => begin_message(buf, b'1'),
+            BackendMessage::BindComplete => begin_message(buf, b'2'),
+            BackendMessage::CloseComplete => begin_message(buf, b'3'),
+            BackendMessage::NoData => begin_message(buf, b'n'),
+            BackendMessage::PortalSuspended => begin_message(buf, b's'),
+            BackendMessage::ParameterDescription(types) => {
+                let start = begin_message(buf, b't');
+                buf.put_i16(types.len() as i16);
+                for oid in types {
+                    buf.put_i32(*oid);
+                }
+                start
+            }
+        };
+        finish_message(buf, start);
+    }
+}
+
+fn begin_message(buf: &mut BytesMut, tag: u8) -> usize {
+    let start = buf.len();
+    buf.put_u8(tag);
+    // Placeholder of the length, patched in `finish_message`.
+    buf.put_i32(0);
+    start
+}
+
+fn finish_message(buf: &mut BytesMut, start: usize) {
+    // The length includes itself but not the tag.
+    let len = (buf.len() - start - 1) as i32;
+    buf[start + 1..start + 5].copy_from_slice(&len.to_be_bytes());
+}
+
+fn put_cstring(buf: &mut BytesMut, value: &str) {
+    buf.put_slice(value.as_bytes());
+    buf.put_u8(0);
+}
+
+/// Reads the first message of a connection, which has no tag.
+pub async fn read_startup<R: AsyncRead + Unpin>(reader: &mut R) -> Result<StartupMessage> {
+    let len = reader.read_i32().await? as usize;
+    let mut body = read_body(reader, len).await?;
+    let code = get_i32(&mut body)?;
+    match code {
+        SSL_REQUEST_CODE => Ok(StartupMessage::SslRequest),
+        GSSENC_REQUEST_CODE => Ok(StartupMessage::GssEncRequest),
+        CANCEL_REQUEST_CODE => Ok(StartupMessage::CancelRequest {
+            process_id: get_i32(&mut body)?,
+            secret_key: get_i32(&mut body)?,
+        }),
+        PROTOCOL_VERSION_3 => {
+            let mut parameters = HashMap::new();
+            loop {
+                let name = get_cstring(&mut body)?;
+                if name.is_empty() {
+                    break;
+                }
+                let value = get_cstring(&mut body)?;
+                parameters.insert(name, value);
+            }
+            Ok(StartupMessage::Startup { parameters })
+        }
+        version => Err(ErrorCode::BadBytes(format!(
+            "Unsupported PostgreSQL protocol version {}.{}",
+            version >> 16,
+            version & 0xFFFF
+        ))),
+    }
+}
+
+/// Reads a tagged message, returns None if the client has gone.
+pub async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<FrontendMessage>> {
+    let tag = match reader.read_u8().await {
+        Ok(tag) => tag,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let len = reader.read_i32().await? as usize;
+    let mut body = read_body(reader, len).await?;
+
+    let message = match tag {
+        b'Q' => FrontendMessage::Query(get_cstring(&mut body)?),
+        b'P' => {
+            let name = get_cstring(&mut body)?;
+            let query = get_cstring(&mut body)?;
+            let num_types = get_i16(&mut body)?;
+            let mut param_types = Vec::with_capacity(num_types.max(0) as usize);
+            for _ in 0..num_types {
+                param_types.push(get_i32(&mut body)?);
+            }
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            }
+        }
+        b'B' => {
+            let portal = get_cstring(&mut body)?;
+            let statement = get_cstring(&mut body)?;
+            let param_formats = get_i16_array(&mut body)?;
+            let num_params = get_i16(&mut body)?;
+            let mut params = Vec::with_capacity(num_params.max(0) as usize);
+            for _ in 0..num_params {
+                let len = get_i32(&mut body)?;
+                if len < 0 {
+                    params.push(None);
+                } else {
+                    let len = len as usize;
+                    ensure_remaining(&body, len)?;
+                    params.push(Some(body.split_to(len)));
+                }
+            }
+            let result_formats = get_i16_array(&mut body)?;
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            }
+        }
+        b'D' => FrontendMessage::Describe {
+            kind: get_u8(&mut body)?,
+            name: get_cstring(&mut body)?,
+        },
+        b'E' => FrontendMessage::Execute {
+            portal: get_cstring(&mut body)?,
+            max_rows: get_i32(&mut body)?,
+        },
+        b'C' => FrontendMessage::Close {
+            kind: get_u8(&mut body)?,
+            name: get_cstring(&mut body)?,
+        },
+        b'S' => FrontendMessage::Sync,
+        b'H' => FrontendMessage::Flush,
+        b'p' => FrontendMessage::Password(body),
+        b'X' => FrontendMessage::Terminate,
+        tag => {
+            return Err(ErrorCode::BadBytes(format!(
+                "Unsupported PostgreSQL message type '{}'",
+                tag as char
+            )));
+        }
+    };
+    Ok(Some(message))
+}
+
+/// Parses the body of a PasswordMessage, which is null terminated.
+pub fn parse_password(mut body: Bytes) -> Bytes {
+    let len = body.len().saturating_sub(1);
+    body.split_to(len)
+}
+
+/// Parses the body of a SASLInitialResponse, returns the selected mechanism and the data.
+pub fn parse_sasl_initial_response(mut body: Bytes) -> Result<(String, Bytes)> {
+    let mechanism = get_cstring(&mut body)?;
+    let len = get_i32(&mut body)?;
+    if len < 0 {
+        return Ok((mechanism, Bytes::new()));
+    }
+    ensure_remaining(&body, len as usize)?;
+    Ok((mechanism, body.split_to(len as usize)))
+}
+
+async fn read_body<R: AsyncRead + Unpin>(reader: &mut R, len: usize) -> Result<Bytes> {
+    if !(4..=MAX_MESSAGE_LENGTH).contains(&len) {
+        return Err(ErrorCode::BadBytes(format!(
+            "Invalid PostgreSQL message length {}",
+            len
+        )));
+    }
+    let mut body = vec![0; len - 4];
+    reader.read_exact(&mut body).await?;
+    Ok(Bytes::from(body))
+}
+
+fn ensure_remaining(body: &Bytes, len: usize) -> Result<()> {
+    match body.remaining() >= len {
+        true => Ok(()),
+        false => Err(ErrorCode::BadBytes("Unexpected end of PostgreSQL message")),
+    }
+}
+
+fn get_u8(body: &mut Bytes) -> Result<u8> {
+    ensure_remaining(body, 1)?;
+    Ok(body.get_u8())
+}
+
+fn get_i16(body: &mut Bytes) -> Result<i16> {
+    ensure_remaining(body, 2)?;
+    Ok(body.get_i16())
+}
+
+fn get_i32(body: &mut Bytes) -> Result<i32> {
+    ensure_remaining(body, 4)?;
+    Ok(body.get_i32())
+}
+
+fn get_i16_array(body: &mut Bytes) -> Result<Vec<i16>> {
+    let len = get_i16(body)?;
+    let mut values = Vec::with_capacity(len.max(0) as usize);
+    for _ in 0..len {
+        values.push(get_i16(body)?);
+    }
+    Ok(values)
+}
+
+fn get_cstring(body: &mut Bytes) -> Result<String> {
+    match body.iter().position(|b| *b == 0) {
+        None => Err(ErrorCode::BadBytes(
+            "Unterminated string in PostgreSQL message",
+        )),
+        Some(pos) => {
+            let value = body.split_to(pos);
+            body.advance(1);
+            String::from_utf8(value.to_vec())
+                .map_err(|e| ErrorCode::BadBytes(format!("Invalid utf8 string, {}", e)))
+        }
+    }
+}
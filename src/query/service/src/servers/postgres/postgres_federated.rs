@@ -0,0 +1,160 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_config::DATABEND_COMMIT_VERSION;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataSchemaRefExt;
+
+use crate::servers::federated_helper::FederatedHelper;
+use crate::servers::postgres::POSTGRES_VERSION;
+
+pub struct PostgresFederated {
+    postgres_version: String,
+    databend_version: String,
+    current_database: String,
+}
+
+impl PostgresFederated {
+    pub fn create(current_database: String) -> Self {
+        PostgresFederated {
+            postgres_version: POSTGRES_VERSION.to_string(),
+            databend_version: DATABEND_COMMIT_VERSION.to_string(),
+            current_database,
+        }
+    }
+
+    // Build block for select function.
+    // Format:
+    // |function_name|
+    // |value|
+    fn select_function_block(name: &str, value: &str) -> Option<DataBlock> {
+        Some(DataBlock::create(
+            DataSchemaRefExt::create(vec![DataField::new(name, StringType::new_impl())]),
+            vec![Series::from_data(vec![value])],
+        ))
+    }
+
+    // Build block for show parameter statement.
+    // Format is:
+    // |parameter_name|
+    // | value        |
+    fn show_parameter_block(name: &str, value: &str) -> Option<DataBlock> {
+        Self::select_function_block(name, value)
+    }
+
+    // Check SHOW <parameter>, the parameters reported to the client at startup.
+    fn federated_show_parameter_check(&self, query: &str) -> Option<DataBlock> {
+        let rules: Vec<(&str, Option<DataBlock>)> = vec![
+            (
+                r"(?i)^(SHOW TRANSACTION ISOLATION LEVEL\s*;?\s*)$",
+                Self::show_parameter_block("transaction_isolation", "read committed"),
+            ),
+            (
+                r"(?i)^(SHOW transaction_isolation\s*;?\s*)$",
+                Self::show_parameter_block("transaction_isolation", "read committed"),
+            ),
+            (
+                r"(?i)^(SHOW standard_conforming_strings\s*;?\s*)$",
+                Self::show_parameter_block("standard_conforming_strings", "on"),
+            ),
+            (
+                r"(?i)^(SHOW server_version\s*;?\s*)$",
+                Self::show_parameter_block("server_version", &self.postgres_version),
+            ),
+            (
+                r"(?i)^(SHOW server_encoding\s*;?\s*)$",
+                Self::show_parameter_block("server_encoding", "UTF8"),
+            ),
+            (
+                r"(?i)^(SHOW client_encoding\s*;?\s*)$",
+                Self::show_parameter_block("client_encoding", "UTF8"),
+            ),
+            (
+                r"(?i)^(SHOW search_path\s*;?\s*)$",
+                Self::show_parameter_block("search_path", "\"$user\", public"),
+            ),
+            (
+                r"(?i)^(SHOW max_identifier_length\s*;?\s*)$",
+                Self::show_parameter_block("max_identifier_length", "63"),
+            ),
+        ];
+        FederatedHelper::block_match_rule(query, rules)
+    }
+
+    // Check for SET or others query, this is the final check of the federated query.
+    fn federated_mixed_check(&self, query: &str) -> Option<DataBlock> {
+        let rules: Vec<(&str, Option<DataBlock>)> = vec![
+            (
+                r"(?i)^(SELECT (pg_catalog\.)?VERSION\(\s*\)\s*;?\s*)$",
+                Self::select_function_block(
+                    "version",
+                    &format!(
+                        "PostgreSQL {} (Databend Query {})",
+                        self.postgres_version, self.databend_version
+                    ),
+                ),
+            ),
+            (
+                r"(?i)^(SELECT (pg_catalog\.)?CURRENT_SCHEMA(\(\s*\))?\s*;?\s*)$",
+                Self::select_function_block("current_schema", &self.current_database),
+            ),
+            // Txn.
+            ("(?i)^(BEGIN(.*))", None),
+            ("(?i)^(START TRANSACTION(.*))", None),
+            ("(?i)^(COMMIT(.*))", None),
+            ("(?i)^(END(.*))", None),
+            ("(?i)^(ROLLBACK(.*))", None),
+            // Session reset by connection pools.
+            ("(?i)^(DISCARD ALL(.*))", None),
+            ("(?i)^(RESET ALL(.*))", None),
+            ("(?i)^(DEALLOCATE(.*))", None),
+            ("(?i)^(UNLISTEN(.*))", None),
+            ("(?i)^(CLOSE ALL(.*))", None),
+            // Set, sent by drivers on connect.
+            (r"(?i)^(SET\s+(SESSION\s+)?extra_float_digits(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?application_name(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?client_encoding(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?datestyle(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?intervalstyle(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?search_path(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?statement_timeout(.*))", None),
+            (r"(?i)^(SET\s+(SESSION\s+)?bytea_output(.*))", None),
+            (
+                r"(?i)^(SET\s+(SESSION\s+)?standard_conforming_strings(.*))",
+                None,
+            ),
+            (r"(?i)^(SET\s+(SESSION\s+)?TIME ZONE(.*))", None),
+            (r"(?i)^(SET\s+SESSION\s+CHARACTERISTICS(.*))", None),
+            (r"(?i)^(SET\s+TRANSACTION(.*))", None),
+        ];
+
+        FederatedHelper::block_match_rule(query, rules)
+    }
+
+    // Check the query is a federated or driver setup command.
+    // Here we fake some values for the command which Databend not supported.
+    pub fn check(&self, query: &str) -> Option<DataBlock> {
+        let query = query.trim();
+
+        // First to check the show parameters.
+        let show_parameter = self.federated_show_parameter_check(query);
+        if show_parameter.is_some() {
+            return show_parameter;
+        }
+
+        // Last check.
+        self.federated_mixed_check(query)
+    }
+}
@@ -0,0 +1,960 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use bytes::Bytes;
+use bytes::BytesMut;
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::runtime::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datablocks::SendableDataBlockStream;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_formats::field_encoder::FieldEncoderRowBased;
+use common_formats::field_encoder::FieldEncoderValues;
+use common_meta_types::AuthInfo;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::ScramSha256Secret;
+use common_users::UserApiProvider;
+use futures_util::StreamExt;
+use metrics::histogram;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rand::Rng;
+use tracing::error;
+use tracing::info;
+use tracing::Instrument;
+
+use crate::auth::Credential;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterQueryLog;
+use crate::servers::mysql::has_result_set_by_plan;
+use crate::servers::postgres::postgres_codec::parse_password;
+use crate::servers::postgres::postgres_codec::parse_sasl_initial_response;
+use crate::servers::postgres::postgres_codec::read_message;
+use crate::servers::postgres::postgres_codec::read_startup;
+use crate::servers::postgres::postgres_codec::BackendMessage;
+use crate::servers::postgres::postgres_codec::FieldDescription;
+use crate::servers::postgres::postgres_codec::FrontendMessage;
+use crate::servers::postgres::postgres_codec::StartupMessage;
+use crate::servers::postgres::postgres_types::bind_parameters;
+use crate::servers::postgres::postgres_types::count_parameters;
+use crate::servers::postgres::postgres_types::has_binary_encoding;
+use crate::servers::postgres::postgres_types::parameter_literal;
+use crate::servers::postgres::postgres_types::type_len;
+use crate::servers::postgres::postgres_types::type_oid;
+use crate::servers::postgres::postgres_types::write_binary;
+use crate::servers::postgres::postgres_types::BINARY_FORMAT;
+use crate::servers::postgres::postgres_types::TEXT_FORMAT;
+use crate::servers::postgres::postgres_types::TEXT_OID;
+use crate::servers::postgres::PostgresFederated;
+use crate::servers::postgres::POSTGRES_VERSION;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::SessionManager;
+use crate::sessions::TableContext;
+use crate::sql::Planner;
+use crate::stream::DataBlockStream;
+
+const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+static NEXT_PROCESS_ID: AtomicI32 = AtomicI32::new(1);
+
+// The (secret key, session id) of the connections, keyed by the process id
+// reported in BackendKeyData, which a CancelRequest refers to.
+static CANCEL_KEYS: Lazy<Mutex<HashMap<i32, (i32, String)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct PreparedStatement {
+    query: String,
+    param_types: Vec<i32>,
+}
+
+struct Portal {
+    query: String,
+    result_formats: Vec<i16>,
+    // The result of a portal executed with a row limit, resumed by the next Execute.
+    running: Option<RunningQuery>,
+}
+
+struct QueryOutput {
+    blocks: SendableDataBlockStream,
+    has_result_set: bool,
+    schema: DataSchemaRef,
+    context: Option<Arc<QueryContext>>,
+}
+
+struct RunningQuery {
+    query: String,
+    output: QueryOutput,
+    type_oids: Vec<i32>,
+    formats: Vec<i16>,
+    encoder: FieldEncoderValues,
+    pending_rows: VecDeque<Vec<Option<Vec<u8>>>>,
+    sent_rows: usize,
+}
+
+impl RunningQuery {
+    fn create(
+        query: &str,
+        output: QueryOutput,
+        formats: &[i16],
+        encoder: FieldEncoderValues,
+    ) -> Self {
+        let type_oids = output
+            .schema
+            .fields()
+            .iter()
+            .map(|f| type_oid(f.data_type()))
+            .collect::<Vec<_>>();
+        let formats = column_formats(formats, type_oids.len());
+        RunningQuery {
+            query: query.to_string(),
+            output,
+            type_oids,
+            formats,
+            encoder,
+            pending_rows: VecDeque::new(),
+            sent_rows: 0,
+        }
+    }
+
+    fn row_description(&self) -> BackendMessage {
+        row_description(&self.output.schema, &self.formats)
+    }
+
+    async fn next_row(&mut self) -> Result<Option<Vec<Option<Vec<u8>>>>> {
+        loop {
+            if let Some(row) = self.pending_rows.pop_front() {
+                self.sent_rows += 1;
+                return Ok(Some(row));
+            }
+
+            match self.output.blocks.next().await {
+                None => return Ok(None),
+                Some(block) => {
+                    let block = block?;
+                    // Blocks of the statements without result set are drained.
+                    if self.output.has_result_set {
+                        self.encode_block(&block)?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode_block(&mut self, block: &DataBlock) -> Result<()> {
+        let serializers = block.get_serializers()?;
+        for row_index in 0..block.num_rows() {
+            let mut row = Vec::with_capacity(serializers.len());
+            for (col_index, serializer) in serializers.iter().enumerate() {
+                let value = block.column(col_index).get_checked(row_index)?;
+                if value.is_null() {
+                    row.push(None);
+                    continue;
+                }
+
+                let oid = self.type_oids[col_index];
+                let mut buf = Vec::new();
+                if self.formats[col_index] == BINARY_FORMAT && has_binary_encoding(oid) {
+                    write_binary(oid, &value, &mut buf)?;
+                } else {
+                    self.encoder
+                        .write_field(serializer, row_index, &mut buf, true);
+                }
+                row.push(Some(buf));
+            }
+            self.pending_rows.push_back(row);
+        }
+        Ok(())
+    }
+
+    fn command_tag(&self) -> String {
+        if self.output.has_result_set {
+            return format!("SELECT {}", self.sent_rows);
+        }
+
+        let words = self
+            .query
+            .split_whitespace()
+            .take(2)
+            .map(|w| w.trim_end_matches(';').to_uppercase())
+            .collect::<Vec<_>>();
+        match words.first().map(|w| w.as_str()) {
+            Some("INSERT") => {
+                let rows = match &self.output.context {
+                    Some(ctx) => ctx.get_write_progress_value().rows,
+                    None => 0,
+                };
+                format!("INSERT 0 {}", rows)
+            }
+            Some("CREATE" | "DROP" | "ALTER") if words.len() > 1 => {
+                format!("{} {}", words[0], words[1])
+            }
+            Some(word) => word.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+pub struct InteractiveWorker<R: AsyncRead + Send + Unpin, W: AsyncWrite + Send + Unpin> {
+    session: Arc<Session>,
+    client_addr: String,
+    reader: R,
+    writer: W,
+    buf: BytesMut,
+    process_id: i32,
+    statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+}
+
+impl<R: AsyncRead + Send + Unpin, W: AsyncWrite + Send + Unpin> InteractiveWorker<R, W> {
+    pub fn create(
+        session: Arc<Session>,
+        client_addr: String,
+        reader: R,
+        writer: W,
+    ) -> InteractiveWorker<R, W> {
+        InteractiveWorker {
+            session,
+            client_addr,
+            reader,
+            writer,
+            buf: BytesMut::new(),
+            process_id: NEXT_PROCESS_ID.fetch_add(1, Ordering::Relaxed),
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+        }
+    }
+
+    /// Serves the connection, `startup` is the first message if it has been read
+    /// while negotiating the encryption.
+    pub async fn run(&mut self, startup: Option<StartupMessage>) -> Result<()> {
+        let parameters = match self.startup(startup).await? {
+            None => return Ok(()),
+            Some(parameters) => parameters,
+        };
+
+        if let Err(cause) = self.authenticate(&parameters).await {
+            error!(
+                "PostgreSQL handler authenticate failed, \
+                    client_address: {}, \
+                    failure_cause: {}",
+                self.client_addr, cause
+            );
+            self.send_error(&cause);
+            self.flush().await?;
+            return Ok(());
+        }
+
+        self.report_parameters(&parameters)?;
+        self.flush().await?;
+
+        // The messages of the extended query protocol are discarded after an error until Sync.
+        let mut ignore_till_sync = false;
+        while let Some(message) = read_message(&mut self.reader).await? {
+            if self.session.is_aborting() {
+                let cause = ErrorCode::AbortedSession(
+                    "Aborting this connection. because we are try aborting server.",
+                );
+                self.send_error(&cause);
+                self.flush().await?;
+                return Err(cause);
+            }
+
+            if ignore_till_sync
+                && !matches!(message, FrontendMessage::Sync | FrontendMessage::Terminate)
+            {
+                continue;
+            }
+
+            let res = match message {
+                FrontendMessage::Query(query) => {
+                    self.on_query(&query).await;
+                    self.send(BackendMessage::ReadyForQuery);
+                    self.flush().await?;
+                    Ok(())
+                }
+                FrontendMessage::Parse {
+                    name,
+                    query,
+                    param_types,
+                } => self.on_parse(name, query, param_types),
+                FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                } => self.on_bind(portal, &statement, &param_formats, &params, result_formats),
+                FrontendMessage::Describe { kind, name } => self.on_describe(kind, &name).await,
+                FrontendMessage::Execute { portal, max_rows } => {
+                    self.on_execute(&portal, max_rows).await
+                }
+                FrontendMessage::Close { kind, name } => {
+                    match kind {
+                        b'S' => self.statements.remove(&name).map(|_| ()),
+                        _ => self.portals.remove(&name).map(|_| ()),
+                    };
+                    self.send(BackendMessage::CloseComplete);
+                    Ok(())
+                }
+                FrontendMessage::Sync => {
+                    ignore_till_sync = false;
+                    self.portals.remove("");
+                    self.send(BackendMessage::ReadyForQuery);
+                    self.flush().await
+                }
+                FrontendMessage::Flush => self.flush().await,
+                FrontendMessage::Password(_) => Err(ErrorCode::BadBytes(
+                    "Unexpected password message after authentication",
+                )),
+                FrontendMessage::Terminate => return Ok(()),
+            };
+
+            if let Err(cause) = res {
+                self.send_error(&cause);
+                ignore_till_sync = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn startup(
+        &mut self,
+        mut startup: Option<StartupMessage>,
+    ) -> Result<Option<HashMap<String, String>>> {
+        loop {
+            let message = match startup.take() {
+                Some(message) => message,
+                None => read_startup(&mut self.reader).await?,
+            };
+            match message {
+                // The encryption has been negotiated, the client may continue as it is.
+                StartupMessage::SslRequest | StartupMessage::GssEncRequest => {
+                    self.writer.write_all(b"N").await?;
+                    self.writer.flush().await?;
+                }
+                StartupMessage::CancelRequest {
+                    process_id,
+                    secret_key,
+                } => {
+                    Self::cancel(process_id, secret_key);
+                    return Ok(None);
+                }
+                StartupMessage::Startup { parameters } => return Ok(Some(parameters)),
+            }
+        }
+    }
+
+    fn cancel(process_id: i32, secret_key: i32) {
+        let session_id = match CANCEL_KEYS.lock().get(&process_id) {
+            Some((secret, session_id)) if *secret == secret_key => session_id.clone(),
+            _ => return,
+        };
+
+        if let Some(session) = SessionManager::instance().get_session_by_id(&session_id) {
+            info!("Cancel PostgreSQL query of session {}", session_id);
+            session.force_kill_query(ErrorCode::AbortedQuery(
+                "canceling statement due to user request",
+            ));
+        }
+    }
+
+    // The users with `scram_sha256_password` are authenticated by SCRAM-SHA-256. Only
+    // one-way hashes are stored for the other users, which rules out MD5, the password is
+    // requested in clear text (protected if TLS is enabled) and checked by the AuthMgr.
+    async fn authenticate(&mut self, parameters: &HashMap<String, String>) -> Result<()> {
+        let user_name = parameters
+            .get("user")
+            .ok_or_else(|| ErrorCode::AuthenticateFailure("user is required in startup message"))?
+            .clone();
+
+        if let Err(cause) = self.authenticate_user(&user_name).await {
+            info!(
+                "PostgreSQL authentication of user {} failed: {}",
+                user_name, cause
+            );
+            // The same error for unknown users and wrong passwords, not to tell which users exist.
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "password authentication failed for user \"{}\"",
+                user_name
+            )));
+        }
+        self.send(BackendMessage::AuthenticationOk);
+
+        if let Some(database) = parameters.get("database") {
+            if !database.is_empty() {
+                // Set directly instead of running `USE`, the name comes from the client as is.
+                let ctx = self.session.create_query_context().await?;
+                ctx.set_current_database(database.clone()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn authenticate_user(&mut self, user_name: &str) -> Result<()> {
+        let client_ip = client_ip(&self.client_addr);
+        let user_info = UserApiProvider::instance()
+            .get_user_with_client_ip(&self.session.get_current_tenant(), user_name, &client_ip)
+            .await;
+        let auth_info = match user_info {
+            Ok(user_info) => user_info.auth_info,
+            Err(cause) => {
+                // Unknown users are asked for the password as well.
+                self.read_password().await?;
+                return Err(cause);
+            }
+        };
+
+        let mut server_signature = None;
+        let credential = match auth_info {
+            AuthInfo::None => Credential::Password {
+                name: user_name.to_string(),
+                password: None,
+                hostname: Some(client_ip),
+            },
+            // The JWT is sent in the password field.
+            AuthInfo::JWT => Credential::Jwt {
+                token: String::from_utf8(self.read_password().await?)?,
+                hostname: Some(client_ip),
+            },
+            AuthInfo::Password {
+                hash_value,
+                hash_method: PasswordHashMethod::ScramSha256,
+            } => {
+                let secret = ScramSha256Secret::decode(&hash_value)?;
+                let (auth_message, client_proof) = self.scram_sha256_exchange(&secret).await?;
+                server_signature = Some(secret.server_signature(&auth_message));
+                Credential::ScramSha256 {
+                    name: user_name.to_string(),
+                    auth_message,
+                    client_proof,
+                    hostname: Some(client_ip),
+                }
+            }
+            _ => Credential::Password {
+                name: user_name.to_string(),
+                password: Some(self.read_password().await?),
+                hostname: Some(client_ip),
+            },
+        };
+
+        let ctx = self.session.create_query_context().await?;
+        ctx.get_auth_manager()
+            .auth(self.session.clone(), &credential)
+            .await?;
+
+        if let Some(signature) = server_signature {
+            let server_final = format!("v={}", base64::encode(signature));
+            self.send(BackendMessage::AuthenticationSaslFinal(
+                server_final.into_bytes(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn read_password(&mut self) -> Result<Vec<u8>> {
+        self.send(BackendMessage::AuthenticationCleartextPassword);
+        self.flush().await?;
+        Ok(parse_password(self.read_auth_response().await?).to_vec())
+    }
+
+    // SCRAM-SHA-256 (RFC 5802, RFC 7677) without channel binding,
+    // returns the AuthMessage and the ClientProof of the client.
+    async fn scram_sha256_exchange(
+        &mut self,
+        secret: &ScramSha256Secret,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.send(BackendMessage::AuthenticationSasl(SCRAM_SHA_256));
+        self.flush().await?;
+
+        let (mechanism, client_first) =
+            parse_sasl_initial_response(self.read_auth_response().await?)?;
+        if mechanism != SCRAM_SHA_256 {
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "unsupported SASL mechanism {}",
+                mechanism
+            )));
+        }
+
+        // client-first-message: gs2-cbind-flag "," [authzid] "," client-first-message-bare
+        let client_first = String::from_utf8(client_first.to_vec())?;
+        let parts = client_first.splitn(3, ',').collect::<Vec<_>>();
+        let (gs2_header, client_first_bare) = match parts[..] {
+            // Channel binding is not offered, "y" means the client supports it but thinks we don't.
+            [flag @ ("n" | "y"), "", bare] => (format!("{},,", flag), bare),
+            _ => {
+                return Err(ErrorCode::AuthenticateFailure(
+                    "unsupported SCRAM channel binding or authorization identity",
+                ));
+            }
+        };
+        let client_nonce = scram_attribute(client_first_bare, "r")?;
+
+        let nonce = format!(
+            "{}{}",
+            client_nonce,
+            base64::encode(rand::thread_rng().gen::<[u8; 18]>())
+        );
+        let server_first = format!(
+            "r={},s={},i={}",
+            nonce,
+            base64::encode(&secret.salt),
+            secret.iterations
+        );
+        self.send(BackendMessage::AuthenticationSaslContinue(
+            server_first.clone().into_bytes(),
+        ));
+        self.flush().await?;
+
+        // client-final-message: "c=" base64(gs2-header) ",r=" nonce ",p=" base64(ClientProof)
+        let client_final = String::from_utf8(self.read_auth_response().await?.to_vec())?;
+        let (client_final_without_proof, proof) = client_final
+            .rsplit_once(",p=")
+            .ok_or_else(|| ErrorCode::AuthenticateFailure("SCRAM client proof is required"))?;
+        if scram_attribute(client_final_without_proof, "c")? != base64::encode(&gs2_header)
+            || scram_attribute(client_final_without_proof, "r")? != nonce
+        {
+            return Err(ErrorCode::AuthenticateFailure(
+                "SCRAM channel binding or nonce mismatch",
+            ));
+        }
+        let client_proof = base64::decode(proof)
+            .map_err(|e| ErrorCode::AuthenticateFailure(format!("invalid SCRAM proof: {}", e)))?;
+
+        let auth_message = format!(
+            "{},{},{}",
+            client_first_bare, server_first, client_final_without_proof
+        );
+        Ok((auth_message.into_bytes(), client_proof))
+    }
+
+    async fn read_auth_response(&mut self) -> Result<Bytes> {
+        match read_message(&mut self.reader).await? {
+            Some(FrontendMessage::Password(body)) => Ok(body),
+            _ => Err(ErrorCode::AuthenticateFailure("password required")),
+        }
+    }
+
+    fn report_parameters(&mut self, parameters: &HashMap<String, String>) -> Result<()> {
+        let timezone = self.session.get_format_settings()?.timezone.to_string();
+        let application_name = parameters
+            .get("application_name")
+            .cloned()
+            .unwrap_or_default();
+        let statuses = vec![
+            ("server_version", POSTGRES_VERSION.to_string()),
+            ("server_encoding", "UTF8".to_string()),
+            ("client_encoding", "UTF8".to_string()),
+            ("DateStyle", "ISO, MDY".to_string()),
+            ("IntervalStyle", "postgres".to_string()),
+            ("TimeZone", timezone),
+            ("integer_datetimes", "on".to_string()),
+            ("standard_conforming_strings", "on".to_string()),
+            ("application_name", application_name),
+        ];
+        for (name, value) in statuses {
+            self.send(BackendMessage::ParameterStatus(name, value));
+        }
+
+        let secret_key = rand::thread_rng().gen::<i32>();
+        CANCEL_KEYS
+            .lock()
+            .insert(self.process_id, (secret_key, self.session.get_id()));
+        self.send(BackendMessage::BackendKeyData {
+            process_id: self.process_id,
+            secret_key,
+        });
+        self.send(BackendMessage::ReadyForQuery);
+        Ok(())
+    }
+
+    async fn on_query(&mut self, query: &str) {
+        if query.trim().trim_end_matches(';').trim().is_empty() {
+            self.send(BackendMessage::EmptyQueryResponse);
+            return;
+        }
+
+        let instant = Instant::now();
+        let res = self.run_query(query).await;
+        if let Err(cause) = res {
+            let suffix = format!("(while in query {})", query);
+            self.send_error(&cause.add_message_back(suffix));
+        }
+
+        histogram!(
+            super::postgres_metrics::METRIC_POSTGRES_PROCESSOR_REQUEST_DURATION,
+            instant.elapsed()
+        );
+    }
+
+    async fn run_query(&mut self, query: &str) -> Result<()> {
+        let output = self.do_query(query).await?;
+        let mut running = RunningQuery::create(query, output, &[], self.encoder()?);
+        if running.output.has_result_set {
+            self.send(running.row_description());
+        }
+        self.send_rows(&mut running, 0).await?;
+        Ok(())
+    }
+
+    fn on_parse(&mut self, name: String, query: String, mut param_types: Vec<i32>) -> Result<()> {
+        let num_params = count_parameters(&query)?;
+        if param_types.len() < num_params {
+            param_types.resize(num_params, 0);
+        }
+        self.statements
+            .insert(name, PreparedStatement { query, param_types });
+        self.send(BackendMessage::ParseComplete);
+        Ok(())
+    }
+
+    fn on_bind(
+        &mut self,
+        portal: String,
+        statement: &str,
+        param_formats: &[i16],
+        params: &[Option<bytes::Bytes>],
+        result_formats: Vec<i16>,
+    ) -> Result<()> {
+        let statement = self.statements.get(statement).ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "prepared statement \"{}\" does not exist",
+                statement
+            ))
+        })?;
+
+        let tz = self.session.get_format_settings()?.timezone;
+        let formats = column_formats(param_formats, params.len());
+        let mut literals = Vec::with_capacity(params.len());
+        for (i, param) in params.iter().enumerate() {
+            let oid = statement.param_types.get(i).copied().unwrap_or(0);
+            literals.push(parameter_literal(oid, formats[i], param.as_deref(), &tz)?);
+        }
+
+        let query = bind_parameters(&statement.query, &literals)?;
+        self.portals.insert(portal, Portal {
+            query,
+            result_formats,
+            running: None,
+        });
+        self.send(BackendMessage::BindComplete);
+        Ok(())
+    }
+
+    async fn on_describe(&mut self, kind: u8, name: &str) -> Result<()> {
+        match kind {
+            b'S' => {
+                let statement = self.statements.get(name).ok_or_else(|| {
+                    ErrorCode::BadArguments(format!(
+                        "prepared statement \"{}\" does not exist",
+                        name
+                    ))
+                })?;
+                let param_types = statement
+                    .param_types
+                    .iter()
+                    .map(|oid| if *oid == 0 { TEXT_OID } else { *oid })
+                    .collect::<Vec<_>>();
+                // Parameters do not change the result schema, plan the query with NULLs.
+                let nulls = vec!["NULL".to_string(); param_types.len()];
+                let query = bind_parameters(&statement.query, &nulls)?;
+
+                self.send(BackendMessage::ParameterDescription(param_types));
+                match self.describe_query(&query).await? {
+                    None => self.send(BackendMessage::NoData),
+                    Some(schema) => self.send(row_description(&schema, &[])),
+                }
+            }
+            _ => {
+                let portal = self.portals.get(name).ok_or_else(|| {
+                    ErrorCode::BadArguments(format!("portal \"{}\" does not exist", name))
+                })?;
+                let (query, formats) = (portal.query.clone(), portal.result_formats.clone());
+                match self.describe_query(&query).await? {
+                    None => self.send(BackendMessage::NoData),
+                    Some(schema) => self.send(row_description(&schema, &formats)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn on_execute(&mut self, name: &str, max_rows: i32) -> Result<()> {
+        let mut portal = self.portals.remove(name).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("portal \"{}\" does not exist", name))
+        })?;
+
+        let instant = Instant::now();
+        let mut running = match portal.running.take() {
+            Some(running) => running,
+            None => {
+                let output = self.do_query(&portal.query).await?;
+                RunningQuery::create(
+                    &portal.query,
+                    output,
+                    &portal.result_formats,
+                    self.encoder()?,
+                )
+            }
+        };
+
+        let completed = self
+            .send_rows(&mut running, max_rows.max(0) as usize)
+            .await?;
+        if !completed {
+            portal.running = Some(running);
+        }
+        self.portals.insert(name.to_string(), portal);
+
+        histogram!(
+            super::postgres_metrics::METRIC_POSTGRES_PROCESSOR_REQUEST_DURATION,
+            instant.elapsed()
+        );
+        Ok(())
+    }
+
+    // Sends at most `max_rows` rows (0 for no limit), returns whether the query is completed.
+    async fn send_rows(&mut self, running: &mut RunningQuery, max_rows: usize) -> Result<bool> {
+        let mut rows = 0;
+        while max_rows == 0 || rows < max_rows {
+            match running.next_row().await? {
+                None => {
+                    self.send(BackendMessage::CommandComplete(running.command_tag()));
+                    return Ok(true);
+                }
+                Some(row) => {
+                    self.send(BackendMessage::DataRow(row));
+                    rows += 1;
+                }
+            }
+        }
+
+        self.send(BackendMessage::PortalSuspended);
+        Ok(false)
+    }
+
+    fn federated_server_command_check(&self, query: &str) -> Option<DataBlock> {
+        // INSERT don't need PostgreSQL federated check
+        if query.len() > 6 && query[..6].eq_ignore_ascii_case("INSERT") {
+            return None;
+        }
+        let federated = PostgresFederated::create(self.session.get_current_database());
+        federated.check(query)
+    }
+
+    async fn describe_query(&self, query: &str) -> Result<Option<DataSchemaRef>> {
+        if let Some(data_block) = self.federated_server_command_check(query) {
+            return Ok(match data_block.num_rows() > 0 {
+                true => Some(data_block.schema().clone()),
+                false => None,
+            });
+        }
+
+        let context = self.session.create_query_context().await?;
+        let mut planner = Planner::new(context);
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        Ok(match has_result_set_by_plan(&plan) {
+            true => Some(plan.schema()),
+            false => None,
+        })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_query(&mut self, query: &str) -> Result<QueryOutput> {
+        match self.federated_server_command_check(query) {
+            Some(data_block) => {
+                info!("Federated query: {}", query);
+                let has_result_set = data_block.num_rows() > 0;
+                let schema = data_block.schema().clone();
+                Ok(QueryOutput {
+                    blocks: DataBlockStream::create(schema.clone(), None, vec![data_block]).boxed(),
+                    has_result_set,
+                    schema,
+                    context: None,
+                })
+            }
+            None => {
+                info!("Normal query: {}", query);
+                let context = self.session.create_query_context().await?;
+
+                let mut planner = Planner::new(context.clone());
+                let (plan, _, _) = planner.plan_sql(query).await?;
+
+                context.attach_query_str(plan.to_string(), query);
+                let interpreter = InterpreterFactory::get(context.clone(), &plan).await;
+                let has_result_set = has_result_set_by_plan(&plan);
+
+                match interpreter {
+                    Ok(interpreter) => {
+                        let blocks = Self::exec_query(interpreter.clone(), &context).await?;
+                        Ok(QueryOutput {
+                            blocks,
+                            has_result_set,
+                            schema: interpreter.schema(),
+                            context: Some(context),
+                        })
+                    }
+                    Err(e) => {
+                        InterpreterQueryLog::fail_to_start(context, e.clone());
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(interpreter, context))]
+    async fn exec_query(
+        interpreter: Arc<dyn Interpreter>,
+        context: &Arc<QueryContext>,
+    ) -> Result<SendableDataBlockStream> {
+        let instant = Instant::now();
+
+        let query_result = context.try_spawn({
+            let ctx = context.clone();
+            async move {
+                let data_stream = interpreter.execute(ctx.clone()).await?;
+                histogram!(
+                    super::postgres_metrics::METRIC_INTERPRETER_USEDTIME,
+                    instant.elapsed()
+                );
+                Ok::<_, ErrorCode>(data_stream)
+            }
+            .in_current_span()
+        })?;
+
+        query_result.await.map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot join handle from context's runtime",
+        )?
+    }
+
+    fn encoder(&self) -> Result<FieldEncoderValues> {
+        let format = self.session.get_format_settings()?;
+        Ok(FieldEncoderValues::create_for_postgres_handler(
+            format.timezone,
+        ))
+    }
+
+    fn send(&mut self, message: BackendMessage) {
+        message.encode(&mut self.buf);
+    }
+
+    fn send_error(&mut self, cause: &ErrorCode) {
+        self.send(BackendMessage::ErrorResponse {
+            code: sql_state(cause),
+            message: cause.message(),
+        });
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.writer.write_all(&self.buf).await?;
+        self.writer.flush().await?;
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+impl<R: AsyncRead + Send + Unpin, W: AsyncWrite + Send + Unpin> Drop for InteractiveWorker<R, W> {
+    fn drop(&mut self) {
+        CANCEL_KEYS.lock().remove(&self.process_id);
+    }
+}
+
+// The format codes of the Bind message apply to all the columns if only one is given.
+fn column_formats(formats: &[i16], num_columns: usize) -> Vec<i16> {
+    match formats.len() {
+        0 => vec![TEXT_FORMAT; num_columns],
+        1 => vec![formats[0]; num_columns],
+        _ => {
+            let mut formats = formats.to_vec();
+            formats.resize(num_columns, TEXT_FORMAT);
+            formats
+        }
+    }
+}
+
+fn row_description(schema: &DataSchemaRef, formats: &[i16]) -> BackendMessage {
+    let formats = column_formats(formats, schema.fields().len());
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(formats)
+        .map(|(field, format)| {
+            let type_oid = type_oid(field.data_type());
+            FieldDescription {
+                name: field.name().clone(),
+                type_oid,
+                type_len: type_len(type_oid),
+                // Types without a binary encoding are sent as text, which is the same.
+                format,
+            }
+        })
+        .collect();
+    BackendMessage::RowDescription(fields)
+}
+
+// The address is `ip:port`, or `[ip]:port` for IPv6.
+fn client_ip(client_addr: &str) -> String {
+    match client_addr.parse::<SocketAddr>() {
+        Ok(addr) => addr.ip().to_string(),
+        Err(_) => client_addr.to_string(),
+    }
+}
+
+// The value of the attribute `name=value` in a SCRAM message.
+fn scram_attribute<'a>(message: &'a str, name: &str) -> Result<&'a str> {
+    message
+        .split(',')
+        .find_map(|attr| attr.strip_prefix(name)?.strip_prefix('='))
+        .ok_or_else(|| {
+            ErrorCode::AuthenticateFailure(format!(
+                "attribute {} is required in SCRAM message",
+                name
+            ))
+        })
+}
+
+fn sql_state(cause: &ErrorCode) -> &'static str {
+    match cause.code() {
+        // invalid_password
+        1051 => "28P01",
+        // invalid_catalog_name
+        1003 => "3D000",
+        // undefined_table
+        1025 => "42P01",
+        // syntax_error
+        1005 => "42601",
+        // query_canceled
+        1043 => "57014",
+        // admin_shutdown
+        1042 => "57P01",
+        _ => "XX000",
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::Shutdown;
+use std::sync::Arc;
+
+use common_base::base::tokio::io;
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::io::BufReader;
+use common_base::base::tokio::io::BufWriter;
+use common_base::base::tokio::net::TcpStream;
+use common_base::runtime::Runtime;
+use common_base::runtime::Thread;
+use common_base::runtime::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+use tracing::error;
+use tracing::warn;
+
+use crate::servers::postgres::postgres_codec::read_startup;
+use crate::servers::postgres::postgres_codec::StartupMessage;
+use crate::servers::postgres::postgres_interactive_worker::InteractiveWorker;
+use crate::sessions::Session;
+
+// default size of the read and write buffers: 100KB
+const DEFAULT_BUFFER_SIZE: usize = 100 * 1024;
+
+pub struct PostgresConnection;
+
+impl PostgresConnection {
+    pub fn run_on_stream(
+        session: Arc<Session>,
+        stream: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) -> Result<()> {
+        let blocking_stream = Self::convert_stream(stream)?;
+        PostgresConnection::attach_session(&session, &blocking_stream)?;
+
+        let non_blocking_stream = TcpStream::from_std(blocking_stream)?;
+        let query_executor =
+            Runtime::with_worker_threads(1, Some("postgres-query-executor".to_string()))?;
+        Thread::spawn(move || {
+            let join_handle = query_executor.spawn(async move {
+                let client_addr = match non_blocking_stream.peer_addr() {
+                    Ok(addr) => addr.to_string(),
+                    Err(e) => {
+                        warn!(
+                            "Failed to get postgres conn peer address for {:?}: {}",
+                            non_blocking_stream, e
+                        );
+                        return;
+                    }
+                };
+
+                if let Err(error) =
+                    Self::run_worker(session, client_addr, non_blocking_stream, tls).await
+                {
+                    error!("PostgreSQL connection closed with error: {:?}", error);
+                }
+            });
+            let _ = futures::executor::block_on(join_handle);
+        });
+        Ok(())
+    }
+
+    async fn run_worker(
+        session: Arc<Session>,
+        client_addr: String,
+        mut stream: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) -> Result<()> {
+        match Self::negotiate_encryption(&mut stream, tls.is_some()).await? {
+            None => {
+                let acceptor = TlsAcceptor::from(tls.expect("TLS is enabled"));
+                let tls_stream = acceptor.accept(stream).await?;
+                Self::run_worker_on(session, client_addr, tls_stream, None).await
+            }
+            Some(startup) => Self::run_worker_on(session, client_addr, stream, Some(startup)).await,
+        }
+    }
+
+    // The client asks for encryption by SSLRequest or GSSENCRequest before the startup
+    // message, returns None if TLS is accepted, or the first message of the plain connection.
+    async fn negotiate_encryption(
+        stream: &mut TcpStream,
+        tls_enabled: bool,
+    ) -> Result<Option<StartupMessage>> {
+        loop {
+            match read_startup(stream).await? {
+                StartupMessage::SslRequest if tls_enabled => {
+                    stream.write_all(b"S").await?;
+                    stream.flush().await?;
+                    return Ok(None);
+                }
+                // The client may continue in plain text, or close the connection.
+                StartupMessage::SslRequest | StartupMessage::GssEncRequest => {
+                    stream.write_all(b"N").await?;
+                    stream.flush().await?;
+                }
+                message => return Ok(Some(message)),
+            }
+        }
+    }
+
+    async fn run_worker_on<S: AsyncRead + AsyncWrite + Send + Unpin>(
+        session: Arc<Session>,
+        client_addr: String,
+        stream: S,
+        startup: Option<StartupMessage>,
+    ) -> Result<()> {
+        let (r, w) = io::split(stream);
+        let r = BufReader::with_capacity(DEFAULT_BUFFER_SIZE, r);
+        let w = BufWriter::with_capacity(DEFAULT_BUFFER_SIZE, w);
+        let mut interactive_worker = InteractiveWorker::create(session, client_addr, r, w);
+        interactive_worker.run(startup).await
+    }
+
+    fn attach_session(session: &Arc<Session>, blocking_stream: &std::net::TcpStream) -> Result<()> {
+        let host = blocking_stream.peer_addr().ok();
+        let blocking_stream_ref = blocking_stream.try_clone()?;
+        session.attach(host, move || {
+            if let Err(error) = blocking_stream_ref.shutdown(Shutdown::Both) {
+                error!("Cannot shutdown PostgreSQL session io {}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn convert_stream(stream: TcpStream) -> Result<std::net::TcpStream> {
+        let stream = stream.into_std().map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+        stream.set_nonblocking(false).map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+
+        Ok(stream)
+    }
+}
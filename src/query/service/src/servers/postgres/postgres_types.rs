@@ -0,0 +1,379 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use common_datavalues::prelude::TypeID;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+pub const BOOL_OID: i32 = 16;
+pub const INT8_OID: i32 = 20;
+pub const INT2_OID: i32 = 21;
+pub const INT4_OID: i32 = 23;
+pub const TEXT_OID: i32 = 25;
+pub const OID_OID: i32 = 26;
+pub const JSON_OID: i32 = 114;
+pub const FLOAT4_OID: i32 = 700;
+pub const FLOAT8_OID: i32 = 701;
+pub const UNKNOWN_OID: i32 = 705;
+pub const VARCHAR_OID: i32 = 1043;
+pub const DATE_OID: i32 = 1082;
+pub const TIMESTAMP_OID: i32 = 1114;
+pub const TIMESTAMPTZ_OID: i32 = 1184;
+pub const NUMERIC_OID: i32 = 1700;
+
+pub const TEXT_FORMAT: i16 = 0;
+pub const BINARY_FORMAT: i16 = 1;
+
+// Days between 1970-01-01 and 2000-01-01, the epoch of PostgreSQL dates and timestamps.
+const POSTGRES_EPOCH_DAYS: i64 = 10957;
+const POSTGRES_EPOCH_MICROS: i64 = POSTGRES_EPOCH_DAYS * 86_400_000_000;
+
+// The same limit as PostgreSQL, parameters are counted by 16-bit integers in the protocol.
+const MAX_PARAMETERS: usize = 65535;
+
+/// Keep in sync with the `atttypid` of `pg_catalog.pg_attribute`.
+pub fn type_oid(data_type: &DataTypeImpl) -> i32 {
+    match remove_nullable(data_type).data_type_id() {
+        TypeID::Boolean => BOOL_OID,
+        TypeID::Int8 | TypeID::UInt8 | TypeID::Int16 => INT2_OID,
+        TypeID::UInt16 | TypeID::Int32 => INT4_OID,
+        TypeID::UInt32 | TypeID::Int64 | TypeID::Interval => INT8_OID,
+        // UInt64 may overflow int8.
+        TypeID::UInt64 => NUMERIC_OID,
+        TypeID::Float32 => FLOAT4_OID,
+        TypeID::Float64 => FLOAT8_OID,
+        TypeID::Date => DATE_OID,
+        // Timestamps are instants, rendered in the session timezone which is
+        // reported to the client by the `TimeZone` parameter status.
+        TypeID::Timestamp => TIMESTAMPTZ_OID,
+        TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => JSON_OID,
+        _ => TEXT_OID,
+    }
+}
+
+pub fn type_len(oid: i32) -> i16 {
+    match oid {
+        BOOL_OID => 1,
+        INT2_OID => 2,
+        INT4_OID | OID_OID | FLOAT4_OID | DATE_OID => 4,
+        INT8_OID | FLOAT8_OID | TIMESTAMP_OID | TIMESTAMPTZ_OID => 8,
+        _ => -1,
+    }
+}
+
+/// Types whose binary representation differs from the text one.
+pub fn has_binary_encoding(oid: i32) -> bool {
+    matches!(
+        oid,
+        BOOL_OID
+            | INT2_OID
+            | INT4_OID
+            | INT8_OID
+            | FLOAT4_OID
+            | FLOAT8_OID
+            | DATE_OID
+            | TIMESTAMPTZ_OID
+            | NUMERIC_OID
+    )
+}
+
+/// Writes the binary representation of a non-null value of the given type.
+pub fn write_binary(oid: i32, value: &DataValue, buf: &mut Vec<u8>) -> Result<()> {
+    match (oid, value) {
+        (BOOL_OID, DataValue::Boolean(v)) => buf.push(*v as u8),
+        (INT2_OID, v) => buf.extend_from_slice(&(as_i64(v)? as i16).to_be_bytes()),
+        (INT4_OID, v) => buf.extend_from_slice(&(as_i64(v)? as i32).to_be_bytes()),
+        (INT8_OID, v) => buf.extend_from_slice(&as_i64(v)?.to_be_bytes()),
+        (FLOAT4_OID, DataValue::Float64(v)) => buf.extend_from_slice(&(*v as f32).to_be_bytes()),
+        (FLOAT8_OID, DataValue::Float64(v)) => buf.extend_from_slice(&v.to_be_bytes()),
+        (DATE_OID, DataValue::Int64(v)) => {
+            buf.extend_from_slice(&((v - POSTGRES_EPOCH_DAYS) as i32).to_be_bytes())
+        }
+        (TIMESTAMPTZ_OID, DataValue::Int64(v)) => {
+            buf.extend_from_slice(&(v - POSTGRES_EPOCH_MICROS).to_be_bytes())
+        }
+        (NUMERIC_OID, DataValue::UInt64(v)) => write_numeric(*v, buf),
+        (oid, v) => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Unsupported binary encoding of {:?} for type oid {}",
+                v.data_type(),
+                oid
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn as_i64(value: &DataValue) -> Result<i64> {
+    match value {
+        DataValue::Int64(v) => Ok(*v),
+        DataValue::UInt64(v) => Ok(*v as i64),
+        DataValue::Boolean(v) => Ok(*v as i64),
+        v => Err(ErrorCode::BadDataValueType(format!(
+            "Expected an integer, but got {:?}",
+            v.data_type()
+        ))),
+    }
+}
+
+// Numeric is a sequence of base 10000 digits, most significant first.
+fn write_numeric(mut value: u64, buf: &mut Vec<u8>) {
+    let mut digits = vec![];
+    while value > 0 {
+        digits.push((value % 10000) as i16);
+        value /= 10000;
+    }
+    digits.reverse();
+    let weight = (digits.len() as i16 - 1).max(0);
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+
+    buf.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    buf.extend_from_slice(&weight.to_be_bytes());
+    // sign (positive) and display scale.
+    buf.extend_from_slice(&0_i16.to_be_bytes());
+    buf.extend_from_slice(&0_i16.to_be_bytes());
+    for digit in digits {
+        buf.extend_from_slice(&digit.to_be_bytes());
+    }
+}
+
+/// Renders a bound parameter as a SQL literal, which replaces its placeholder in the query.
+pub fn parameter_literal(oid: i32, format: i16, value: Option<&[u8]>, tz: &Tz) -> Result<String> {
+    let value = match value {
+        None => return Ok("NULL".to_string()),
+        Some(value) => value,
+    };
+
+    if format == BINARY_FORMAT {
+        return binary_parameter_literal(oid, value, tz);
+    }
+
+    let text = std::str::from_utf8(value)
+        .map_err(|e| ErrorCode::BadArguments(format!("Invalid utf8 parameter, {}", e)))?;
+    match oid {
+        INT2_OID | INT4_OID | INT8_OID | OID_OID | FLOAT4_OID | FLOAT8_OID | NUMERIC_OID => {
+            let text = text.trim();
+            match text.parse::<f64>() {
+                Ok(v) if v.is_finite() => Ok(numeric_literal(text)),
+                _ => Err(ErrorCode::BadArguments(format!(
+                    "Invalid numeric parameter '{}'",
+                    text
+                ))),
+            }
+        }
+        BOOL_OID => match text.trim().to_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" | "on" | "1" => Ok("TRUE".to_string()),
+            "f" | "false" | "n" | "no" | "off" | "0" => Ok("FALSE".to_string()),
+            other => Err(ErrorCode::BadArguments(format!(
+                "Invalid boolean parameter '{}'",
+                other
+            ))),
+        },
+        _ => Ok(quote_string(text)),
+    }
+}
+
+fn binary_parameter_literal(oid: i32, value: &[u8], tz: &Tz) -> Result<String> {
+    let fixed = |len: usize| -> Result<&[u8]> {
+        match value.len() == len {
+            true => Ok(value),
+            false => Err(ErrorCode::BadArguments(format!(
+                "Invalid binary parameter length {} for type oid {}",
+                value.len(),
+                oid
+            ))),
+        }
+    };
+
+    match oid {
+        BOOL_OID => Ok(match fixed(1)?[0] {
+            0 => "FALSE".to_string(),
+            _ => "TRUE".to_string(),
+        }),
+        INT2_OID => Ok(numeric_literal(i16::from_be_bytes(
+            fixed(2)?.try_into().unwrap(),
+        ))),
+        INT4_OID => Ok(numeric_literal(i32::from_be_bytes(
+            fixed(4)?.try_into().unwrap(),
+        ))),
+        OID_OID => Ok(numeric_literal(u32::from_be_bytes(
+            fixed(4)?.try_into().unwrap(),
+        ))),
+        INT8_OID => Ok(numeric_literal(i64::from_be_bytes(
+            fixed(8)?.try_into().unwrap(),
+        ))),
+        FLOAT4_OID => float_literal(f32::from_be_bytes(fixed(4)?.try_into().unwrap()) as f64),
+        FLOAT8_OID => float_literal(f64::from_be_bytes(fixed(8)?.try_into().unwrap())),
+        DATE_OID => {
+            let days = i32::from_be_bytes(fixed(4)?.try_into().unwrap()) as i64;
+            let date = NaiveDate::from_ymd(2000, 1, 1) + Duration::days(days);
+            Ok(quote_string(&date.format("%Y-%m-%d").to_string()))
+        }
+        TIMESTAMP_OID | TIMESTAMPTZ_OID => {
+            let micros = i64::from_be_bytes(fixed(8)?.try_into().unwrap());
+            let utc =
+                NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0) + Duration::microseconds(micros);
+            // Timestamp literals are parsed in the session timezone.
+            let local = match oid {
+                TIMESTAMPTZ_OID => tz.from_utc_datetime(&utc).naive_local(),
+                _ => utc,
+            };
+            Ok(quote_string(
+                &local.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+            ))
+        }
+        0 | TEXT_OID | VARCHAR_OID | JSON_OID | UNKNOWN_OID => {
+            let text = std::str::from_utf8(value)
+                .map_err(|e| ErrorCode::BadArguments(format!("Invalid utf8 parameter, {}", e)))?;
+            Ok(quote_string(text))
+        }
+        oid => Err(ErrorCode::BadArguments(format!(
+            "Unsupported binary parameter of type oid {}",
+            oid
+        ))),
+    }
+}
+
+// Numbers are parenthesized, so that a negative one is not merged with the operator
+// before the placeholder, e.g. `10-$1` would become the comment `10--5` otherwise.
+fn numeric_literal(v: impl std::fmt::Display) -> String {
+    format!("({})", v)
+}
+
+fn float_literal(v: f64) -> Result<String> {
+    match v.is_finite() {
+        true => Ok(numeric_literal(format!("{:?}", v))),
+        false => Err(ErrorCode::BadArguments(format!(
+            "Unsupported float parameter {}",
+            v
+        ))),
+    }
+}
+
+fn quote_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\'' => quoted.push_str("''"),
+            '\\' => quoted.push_str("\\\\"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Positions of the `$n` placeholders in the query, as (start, end, n).
+/// Placeholders inside quotes and comments are skipped.
+fn placeholders(query: &str) -> Vec<(usize, usize, usize)> {
+    let bytes = query.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == b'\\' && quote == b'\'' {
+                        i += 2;
+                        continue;
+                    }
+                    if bytes[i] == quote {
+                        // A doubled quote is an escaped one.
+                        if bytes.get(i + 1) == Some(&quote) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'$' if i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_') => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if let Ok(n) = query[start + 1..i].parse::<usize>() {
+                    if n > 0 {
+                        result.push((start, i, n));
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    result
+}
+
+/// The number of parameters referred by the query, that is the largest `$n`.
+pub fn count_parameters(query: &str) -> Result<usize> {
+    let num_params = placeholders(query)
+        .iter()
+        .map(|(_, _, n)| *n)
+        .max()
+        .unwrap_or(0);
+    match num_params > MAX_PARAMETERS {
+        true => Err(ErrorCode::BadArguments(format!(
+            "Query refers to parameter ${}, but at most {} parameters are supported",
+            num_params, MAX_PARAMETERS
+        ))),
+        false => Ok(num_params),
+    }
+}
+
+/// Replaces the `$n` placeholders with the n-th literal.
+pub fn bind_parameters(query: &str, literals: &[String]) -> Result<String> {
+    let mut bound = String::with_capacity(query.len());
+    let mut last = 0;
+    for (start, end, n) in placeholders(query) {
+        let literal = literals.get(n - 1).ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "Query refers to parameter ${}, but only {} parameters are bound",
+                n,
+                literals.len()
+            ))
+        })?;
+        bound.push_str(&query[last..start]);
+        bound.push_str(literal);
+        last = end;
+    }
+    bound.push_str(&query[last..]);
+    Ok(bound)
+}
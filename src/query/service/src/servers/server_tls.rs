@@ -0,0 +1,139 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use common_config::Config;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::Certificate;
+use rustls::PrivateKey;
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+
+/// TLS settings of the MySQL and PostgreSQL handlers, the connection is upgraded
+/// to TLS when the client sends SSLRequest before the handshake.
+#[derive(Clone, Debug, Default)]
+pub struct ServerTlsConfig {
+    cert: String,
+    key: String,
+    // Client certificates are required and verified with it if not empty.
+    root_ca_cert: String,
+}
+
+impl ServerTlsConfig {
+    pub fn new(cert: String, key: String, root_ca_cert: String) -> Self {
+        ServerTlsConfig {
+            cert,
+            key,
+            root_ca_cert,
+        }
+    }
+
+    pub fn mysql(config: &Config) -> Self {
+        ServerTlsConfig::new(
+            config.query.mysql_tls_server_cert.clone(),
+            config.query.mysql_tls_server_key.clone(),
+            config.query.mysql_tls_server_root_ca_cert.clone(),
+        )
+    }
+
+    pub fn postgres(config: &Config) -> Self {
+        ServerTlsConfig::new(
+            config.query.postgres_tls_server_cert.clone(),
+            config.query.postgres_tls_server_key.clone(),
+            config.query.postgres_tls_server_root_ca_cert.clone(),
+        )
+    }
+
+    pub fn enabled(&self) -> bool {
+        !self.cert.is_empty() && !self.key.is_empty()
+    }
+
+    pub fn setup(&self) -> Result<Option<Arc<ServerConfig>>> {
+        if !self.enabled() {
+            return Ok(None);
+        }
+
+        let certs = Self::load_certs(&self.cert)?;
+        let key = Self::load_key(&self.key)?;
+
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match self.root_ca_cert.is_empty() {
+            true => builder.with_no_client_auth(),
+            false => {
+                let mut roots = RootCertStore::empty();
+                for cert in Self::load_certs(&self.root_ca_cert)? {
+                    roots.add(&cert).map_err(|e| {
+                        ErrorCode::TLSConfigurationFailure(format!(
+                            "invalid tls root ca cert {}: {}",
+                            self.root_ca_cert, e
+                        ))
+                    })?;
+                }
+                builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
+            }
+        };
+
+        let config = builder.with_single_cert(certs, key).map_err(|e| {
+            ErrorCode::TLSConfigurationFailure(format!("invalid tls cert or key: {}", e))
+        })?;
+        Ok(Some(Arc::new(config)))
+    }
+
+    fn load_certs(path: &str) -> Result<Vec<Certificate>> {
+        let mut reader = BufReader::new(Self::open(path)?);
+        let certs = rustls_pemfile::certs(&mut reader).map_err(|e| {
+            ErrorCode::TLSConfigurationFailure(format!("cannot read certs {}: {}", path, e))
+        })?;
+
+        match certs.is_empty() {
+            true => Err(ErrorCode::TLSConfigurationFailure(format!(
+                "no certs found in {}",
+                path
+            ))),
+            false => Ok(certs.into_iter().map(Certificate).collect()),
+        }
+    }
+
+    fn load_key(path: &str) -> Result<PrivateKey> {
+        let mut reader = BufReader::new(Self::open(path)?);
+        loop {
+            let item = rustls_pemfile::read_one(&mut reader).map_err(|e| {
+                ErrorCode::TLSConfigurationFailure(format!("cannot read key {}: {}", path, e))
+            })?;
+
+            match item {
+                Some(rustls_pemfile::Item::RSAKey(key))
+                | Some(rustls_pemfile::Item::PKCS8Key(key))
+                | Some(rustls_pemfile::Item::ECKey(key)) => return Ok(PrivateKey(key)),
+                Some(_) => continue,
+                None => {
+                    return Err(ErrorCode::TLSConfigurationFailure(format!(
+                        "no private key found in {}",
+                        path
+                    )));
+                }
+            }
+        }
+    }
+
+    fn open(path: &str) -> Result<File> {
+        File::open(path)
+            .map_err(|e| ErrorCode::TLSConfigurationFailure(format!("cannot open {}: {}", path, e)))
+    }
+}
@@ -15,6 +15,7 @@
 mod query_affect;
 pub mod query_ctx;
 mod query_ctx_shared;
+mod query_queue;
 mod session;
 mod session_ctx;
 mod session_info;
@@ -27,6 +28,8 @@ pub use common_catalog::table_context::TableContext;
 pub use query_affect::QueryAffect;
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
+pub use query_queue::AdmissionPermit;
+pub use query_queue::QueryQueue;
 pub use session::Session;
 pub use session_ctx::SessionContext;
 pub use session_info::ProcessInfo;
@@ -26,12 +26,14 @@ use chrono_tz::Tz;
 use common_base::base::tokio::task::JoinHandle;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
+use common_base::runtime::MemStat;
 use common_base::runtime::TrySpawn;
 use common_catalog::plan::DataSourceInfo;
 use common_catalog::plan::DataSourcePlan;
 use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::Partitions;
 use common_catalog::plan::StageTableInfo;
+use common_catalog::table_context::QueuedQueryInfo;
 use common_catalog::table_context::StageAttachment;
 use common_config::DATABEND_COMMIT_VERSION;
 use common_datablocks::DataBlock;
@@ -47,6 +49,7 @@ use common_settings::Settings;
 use common_storage::DataOperator;
 use common_storage::StorageMetrics;
 use common_storages_stage::StageTable;
+use common_users::UserQuotaTracker;
 use parking_lot::RwLock;
 use tracing::debug;
 
@@ -185,6 +188,84 @@ impl QueryContext {
         self.shared.get_data_metrics()
     }
 
+    /// Get all tables that already attached in this query.
+    pub fn get_tables_refs(&self) -> Vec<Arc<dyn Table>> {
+        self.shared.get_tables_refs()
+    }
+
+    /// Reserve executor threads and a memory tracker for this query out of the current user's
+    /// quota, returns the number of threads granted.
+    pub fn acquire_quota_resources(&self, wanted_threads: u64) -> Result<u64> {
+        let user = self.get_current_user()?;
+        let usage = UserQuotaTracker::instance().get_user_usage(&self.get_tenant(), &user);
+        let permit = usage.acquire(wanted_threads)?;
+        let threads = permit.threads();
+        *self.shared.quota_permit.lock() = Some(permit);
+        Ok(threads)
+    }
+
+    /// Get the memory tracker the query reports to, if its quota resources are acquired.
+    pub fn get_quota_mem_stat(&self) -> Option<Arc<MemStat>> {
+        self.shared
+            .quota_permit
+            .lock()
+            .as_ref()
+            .map(|permit| permit.get_mem_stat())
+    }
+
+    pub fn release_quota_resources(&self) {
+        self.shared.quota_permit.lock().take();
+    }
+
+    /// Wait in the admission queue of the node until the workload group the current user
+    /// or any of its available roles is assigned to has capacity for this query.
+    pub async fn acquire_workload_permit(&self) -> Result<()> {
+        if !self.get_current_session().get_type().is_user_session() {
+            return Ok(());
+        }
+
+        let tenant = self.get_tenant();
+        let user = self.get_current_user()?;
+        let query_queue = SessionManager::instance().query_queue.clone();
+        let groups = query_queue.get_workload_groups(&tenant).await?;
+        if groups.is_empty() {
+            return Ok(());
+        }
+
+        let roles = self.get_available_roles().await?;
+        let group = groups.iter().find(|group| {
+            group.is_assigned_to_user(&user.name)
+                || roles
+                    .iter()
+                    .any(|role| group.is_assigned_to_role(&role.name))
+        });
+
+        if let Some(group) = group {
+            let query = QueuedQueryInfo {
+                query_id: self.get_id(),
+                user: user.identity().to_string(),
+                workload_group: group.name.clone(),
+                query_text: self.get_query_str(),
+                enqueue_time: SystemTime::now(),
+            };
+            let permit = query_queue
+                .admit(
+                    &tenant,
+                    group,
+                    query,
+                    self.shared.quota_permit.clone(),
+                    self.get_aborting(),
+                )
+                .await?;
+            *self.shared.workload_permit.lock() = Some(permit);
+        }
+        Ok(())
+    }
+
+    pub fn release_workload_permit(&self) {
+        self.shared.workload_permit.lock().take();
+    }
+
     pub fn set_affect(self: &Arc<Self>, affect: QueryAffect) {
         self.shared.set_affect(affect)
     }
@@ -197,6 +278,12 @@ impl QueryContext {
         self.shared.attach_stage(attachment);
     }
 
+    /// Let the following `get_table` calls of the query return the given table instance,
+    /// e.g. a table navigated to a specific snapshot.
+    pub fn attach_table(&self, catalog: &str, database: &str, name: &str, table: Arc<dyn Table>) {
+        self.shared.attach_table(catalog, database, name, table)
+    }
+
     pub fn get_created_time(&self) -> SystemTime {
         self.shared.created_time
     }
@@ -285,6 +372,9 @@ impl TableContext for QueryContext {
     fn get_current_role(&self) -> Option<RoleInfo> {
         self.shared.get_current_role()
     }
+    async fn get_available_roles(&self) -> Result<Vec<RoleInfo>> {
+        self.shared.get_available_roles().await
+    }
     fn get_fuse_version(&self) -> String {
         self.version.clone()
     }
@@ -360,6 +450,10 @@ impl TableContext for QueryContext {
         SessionManager::instance().processes_info()
     }
 
+    fn get_queued_queries_info(&self) -> Vec<QueuedQueryInfo> {
+        SessionManager::instance().queued_queries_info()
+    }
+
     // Get Stage Attachment.
     fn get_stage_attachment(&self) -> Option<StageAttachment> {
         self.shared.get_stage_attachment()
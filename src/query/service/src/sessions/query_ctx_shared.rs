@@ -32,6 +32,7 @@ use common_meta_types::UserInfo;
 use common_settings::Settings;
 use common_storage::DataOperator;
 use common_storage::StorageMetrics;
+use common_users::ResourcePermit;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
 use uuid::Uuid;
@@ -42,6 +43,7 @@ use crate::clusters::Cluster;
 use crate::pipelines::executor::PipelineExecutor;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
+use crate::sessions::AdmissionPermit;
 use crate::sessions::Session;
 use crate::storages::Table;
 
@@ -81,6 +83,8 @@ pub struct QueryContextShared {
     pub(in crate::sessions) precommit_blocks: Arc<RwLock<Vec<DataBlock>>>,
     pub(in crate::sessions) stage_attachment: Arc<RwLock<Option<StageAttachment>>>,
     pub(in crate::sessions) created_time: SystemTime,
+    pub(in crate::sessions) quota_permit: Arc<Mutex<Option<ResourcePermit>>>,
+    pub(in crate::sessions) workload_permit: Arc<Mutex<Option<AdmissionPermit>>>,
 }
 
 impl QueryContextShared {
@@ -111,6 +115,8 @@ impl QueryContextShared {
             precommit_blocks: Arc::new(RwLock::new(vec![])),
             stage_attachment: Arc::new(RwLock::new(None)),
             created_time: SystemTime::now(),
+            quota_permit: Arc::new(Mutex::new(None)),
+            workload_permit: Arc::new(Mutex::new(None)),
         }))
     }
 
@@ -158,6 +164,10 @@ impl QueryContextShared {
         self.session.get_current_role()
     }
 
+    pub async fn get_available_roles(&self) -> Result<Vec<RoleInfo>> {
+        self.session.get_all_available_roles().await
+    }
+
     pub fn set_current_tenant(&self, tenant: String) {
         self.session.set_current_tenant(tenant);
     }
@@ -217,6 +227,11 @@ impl QueryContextShared {
         }
     }
 
+    pub fn attach_table(&self, catalog: &str, database: &str, name: &str, table: Arc<dyn Table>) {
+        let table_meta_key = (catalog.to_string(), database.to_string(), name.to_string());
+        self.tables_refs.lock().insert(table_meta_key, table);
+    }
+
     async fn get_table_to_cache(
         &self,
         catalog: &str,
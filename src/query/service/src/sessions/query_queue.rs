@@ -0,0 +1,221 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Notify;
+use common_catalog::table_context::QueuedQueryInfo;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+use common_users::ResourcePermit;
+use common_users::UserApiProvider;
+use parking_lot::Mutex;
+
+/// The memory of running queries is released without notifying the queue,
+/// so the queued queries check their group again at least this often.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The workload groups of a tenant are loaded from the meta service at most this often,
+/// groups created or dropped on other nodes take effect after it.
+const WORKLOAD_GROUPS_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+type QuotaPermitRef = Arc<Mutex<Option<ResourcePermit>>>;
+
+struct WorkloadGroupState {
+    /// The quota permits of the running queries of the group, by query id.
+    running: Mutex<HashMap<String, QuotaPermitRef>>,
+    notify: Notify,
+}
+
+impl WorkloadGroupState {
+    fn has_capacity(running: &HashMap<String, QuotaPermitRef>, group: &WorkloadGroup) -> bool {
+        if group.max_concurrency > 0 && running.len() as u64 >= group.max_concurrency {
+            return false;
+        }
+
+        if group.memory_quota > 0 {
+            let memory_usage: i64 = running
+                .values()
+                .filter_map(|permit| {
+                    permit
+                        .lock()
+                        .as_ref()
+                        .map(|permit| permit.get_mem_stat().get_memory_usage())
+                })
+                .sum();
+            if memory_usage >= group.memory_quota as i64 {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// The slot of a running query in its workload group, released when dropped.
+pub struct AdmissionPermit {
+    query_id: String,
+    state: Arc<WorkloadGroupState>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.state.running.lock().remove(&self.query_id);
+        self.state.notify.notify_waiters();
+    }
+}
+
+/// Admission queue of the node, holds the queries of a workload group at capacity
+/// until the running queries of the group finish.
+pub struct QueryQueue {
+    groups: Mutex<HashMap<String, Arc<WorkloadGroupState>>>,
+    queued: Mutex<HashMap<String, QueuedQueryInfo>>,
+    // tenant -> (loaded at, workload groups of the tenant)
+    cached_groups: Mutex<HashMap<String, (Instant, Arc<Vec<WorkloadGroup>>)>>,
+}
+
+impl QueryQueue {
+    pub fn create() -> Arc<QueryQueue> {
+        Arc::new(QueryQueue {
+            groups: Mutex::new(HashMap::new()),
+            queued: Mutex::new(HashMap::new()),
+            cached_groups: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Get the workload groups of the tenant, loaded again from the meta service once
+    /// they are cached for longer than `WORKLOAD_GROUPS_REFRESH_INTERVAL`.
+    pub async fn get_workload_groups(&self, tenant: &str) -> Result<Arc<Vec<WorkloadGroup>>> {
+        if let Some((loaded_at, groups)) = self.cached_groups.lock().get(tenant) {
+            if loaded_at.elapsed() < WORKLOAD_GROUPS_REFRESH_INTERVAL {
+                return Ok(groups.clone());
+            }
+        }
+
+        let loaded_at = Instant::now();
+        let groups = Arc::new(
+            UserApiProvider::instance()
+                .get_workload_groups(tenant)
+                .await?,
+        );
+        self.cached_groups
+            .lock()
+            .insert(tenant.to_string(), (loaded_at, groups.clone()));
+        Ok(groups)
+    }
+
+    /// Drop the cached workload groups of the tenant, after they are changed on this node.
+    pub fn invalidate_workload_groups(&self, tenant: &str) {
+        self.cached_groups.lock().remove(tenant);
+    }
+
+    /// Wait until the group has capacity for the query, or fails if the query waits
+    /// longer than the queue timeout of the group or is killed.
+    ///
+    /// The memory the group uses is the sum of the quota permits of its running queries.
+    pub async fn admit(
+        &self,
+        tenant: &str,
+        group: &WorkloadGroup,
+        query: QueuedQueryInfo,
+        quota_permit: QuotaPermitRef,
+        aborting: Arc<AtomicBool>,
+    ) -> Result<AdmissionPermit> {
+        let state = self
+            .groups
+            .lock()
+            .entry(format!("{}/{}", tenant, group.name))
+            .or_insert_with(|| {
+                Arc::new(WorkloadGroupState {
+                    running: Mutex::new(HashMap::new()),
+                    notify: Notify::new(),
+                })
+            })
+            .clone();
+
+        let query_id = query.query_id.clone();
+        let deadline = match group.queue_timeout {
+            0 => None,
+            secs => Some(Instant::now() + Duration::from_secs(secs)),
+        };
+
+        let mut query = Some(query);
+        let _queued = QueuedGuard {
+            queue: self,
+            query_id: query_id.clone(),
+        };
+        loop {
+            let notified = {
+                let mut running = state.running.lock();
+                if WorkloadGroupState::has_capacity(&running, group) {
+                    running.insert(query_id.clone(), quota_permit);
+                    return Ok(AdmissionPermit {
+                        query_id,
+                        state: state.clone(),
+                    });
+                }
+
+                // Registered before the lock is released, to not miss a query finishing meanwhile.
+                state.notify.notified()
+            };
+
+            if let Some(query) = query.take() {
+                self.queued.lock().insert(query_id.clone(), query);
+            }
+
+            if aborting.load(Ordering::Acquire) {
+                return Err(ErrorCode::AbortedQuery(
+                    "Aborted query, because the server is shutting down or the query was killed.",
+                ));
+            }
+
+            let mut wait = RECHECK_INTERVAL;
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(ErrorCode::QueryQueueTimeout(format!(
+                        "Query {} waited more than {} secs in the queue of workload group {}",
+                        query_id, group.queue_timeout, group.name
+                    )));
+                }
+                wait = wait.min(deadline - now);
+            }
+
+            let _ = tokio::time::timeout(wait, notified).await;
+        }
+    }
+
+    pub fn queued_queries_info(&self) -> Vec<QueuedQueryInfo> {
+        self.queued.lock().values().cloned().collect()
+    }
+}
+
+/// Removes the query from the queue when it is admitted, fails or is cancelled.
+struct QueuedGuard<'a> {
+    queue: &'a QueryQueue,
+    query_id: String,
+}
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.queue.queued.lock().remove(&self.query_id);
+    }
+}
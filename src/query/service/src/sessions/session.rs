@@ -177,6 +177,10 @@ impl Session {
         self.session_ctx.get_current_catalog()
     }
 
+    pub fn get_client_host(self: &Arc<Self>) -> Option<SocketAddr> {
+        self.session_ctx.get_client_host()
+    }
+
     pub fn get_current_tenant(self: &Arc<Self>) -> String {
         self.session_ctx.get_current_tenant()
     }
@@ -192,20 +196,28 @@ impl Session {
     }
 
     // set_authed_user() is called after authentication is passed in various protocol handlers, like
-    // HTTP handler, clickhouse query handler, mysql query handler. auth_role represents the role
-    // granted by external authenticator, it will over write the current user's granted roles, and
-    // becomes the CURRENT ROLE if not set X-DATABEND-ROLE.
+    // HTTP handler, clickhouse query handler, mysql query handler. auth_roles represent the roles
+    // granted by external authenticator, they will over write the current user's granted roles, and
+    // the first of them becomes the CURRENT ROLE if not set X-DATABEND-ROLE.
     pub async fn set_authed_user(
         self: &Arc<Self>,
         user: UserInfo,
-        auth_role: Option<String>,
+        auth_roles: Vec<String>,
     ) -> Result<()> {
         self.session_ctx.set_current_user(user);
-        self.session_ctx.set_auth_role(auth_role);
+        self.session_ctx.set_auth_roles(auth_roles);
         self.ensure_current_role().await?;
         Ok(())
     }
 
+    pub fn get_must_change_password(self: &Arc<Self>) -> bool {
+        self.session_ctx.get_must_change_password()
+    }
+
+    pub fn set_must_change_password(self: &Arc<Self>, v: bool) {
+        self.session_ctx.set_must_change_password(v);
+    }
+
     // ensure_current_role() is called after authentication and before any privilege checks
     async fn ensure_current_role(self: &Arc<Self>) -> Result<()> {
         let tenant = self.get_current_tenant();
@@ -217,7 +229,7 @@ impl Session {
         // if CURRENT ROLE is not set, take current session's AUTH ROLE
         let mut current_role_name = self.get_current_role().map(|r| r.name);
         if current_role_name.is_none() {
-            current_role_name = self.session_ctx.get_auth_role();
+            current_role_name = self.session_ctx.get_auth_roles().first().cloned();
         }
 
         // if CURRENT ROLE and AUTH ROLE are not set, take current user's DEFAULT ROLE
@@ -279,13 +291,14 @@ impl Session {
         self.session_ctx.get_current_role()
     }
 
-    // Returns all the roles the current session has. If the user have been granted auth_role,
+    // Returns all the roles the current session has. If the user have been granted auth_roles,
     // the other roles will be ignored.
     // On executing SET ROLE, the role have to be one of the available roles.
     pub async fn get_all_available_roles(self: &Arc<Self>) -> Result<Vec<RoleInfo>> {
-        let roles = match self.session_ctx.get_auth_role() {
-            Some(auth_role) => vec![auth_role],
-            None => {
+        let auth_roles = self.session_ctx.get_auth_roles();
+        let roles = match auth_roles.is_empty() {
+            false => auth_roles,
+            true => {
                 let current_user = self.get_current_user()?;
                 current_user.grants.roles()
             }
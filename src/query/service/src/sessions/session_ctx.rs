@@ -44,9 +44,12 @@ pub struct SessionContext {
     // roles will not take effect. The user can switch to another available role by `SET ROLE`.
     // If the current_role is not set, it takes the user's default role.
     current_role: RwLock<Option<RoleInfo>>,
-    // The role granted to user by external auth provider, when auth_role is provided, the current
-    // user's all other roles are overridden by this role.
-    auth_role: RwLock<Option<String>>,
+    // The roles granted to user by external auth provider, when auth_roles are provided, the current
+    // user's all other roles are overridden by these roles.
+    auth_roles: RwLock<Vec<String>>,
+    // Set by the authentication phase when the user's password has expired or is flagged with
+    // MUST_CHANGE_PASSWORD, only ALTER USER on the current user is allowed until it is cleared.
+    must_change_password: AtomicBool,
     // The client IP from the client.
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
@@ -60,7 +63,8 @@ impl SessionContext {
             abort: Default::default(),
             current_user: Default::default(),
             current_role: Default::default(),
-            auth_role: Default::default(),
+            auth_roles: Default::default(),
+            must_change_password: Default::default(),
             current_tenant: Default::default(),
             client_host: Default::default(),
             current_catalog: RwLock::new("default".to_string()),
@@ -156,15 +160,23 @@ impl SessionContext {
         *lock = Some(user);
     }
 
-    // Get auth role. Auth role is the role granted by authenticator.
-    pub fn get_auth_role(&self) -> Option<String> {
-        let lock = self.auth_role.read();
+    // Get auth roles. Auth roles are the roles granted by authenticator.
+    pub fn get_auth_roles(&self) -> Vec<String> {
+        let lock = self.auth_roles.read();
         lock.clone()
     }
 
-    pub fn set_auth_role(&self, role: Option<String>) {
-        let mut lock = self.auth_role.write();
-        *lock = role;
+    pub fn set_auth_roles(&self, roles: Vec<String>) {
+        let mut lock = self.auth_roles.write();
+        *lock = roles;
+    }
+
+    pub fn get_must_change_password(&self) -> bool {
+        self.must_change_password.load(Ordering::Relaxed)
+    }
+
+    pub fn set_must_change_password(&self, v: bool) {
+        self.must_change_password.store(v, Ordering::Relaxed);
     }
 
     pub fn get_client_host(&self) -> Option<SocketAddr> {
@@ -24,6 +24,7 @@ use std::time::Duration;
 use common_base::base::tokio;
 use common_base::base::GlobalInstance;
 use common_base::base::SignalStream;
+use common_catalog::table_context::QueuedQueryInfo;
 use common_config::Config;
 use common_config::GlobalConfig;
 use common_exception::ErrorCode;
@@ -40,6 +41,7 @@ use tracing::info;
 
 use crate::sessions::session::Session;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryQueue;
 use crate::sessions::SessionContext;
 use crate::sessions::SessionManagerStatus;
 use crate::sessions::SessionType;
@@ -56,6 +58,9 @@ pub struct SessionManager {
     // When typ is MySQL, insert into this map, key is id, val is MySQL connection id.
     pub(crate) mysql_conn_map: Arc<RwLock<HashMap<Option<u32>, String>>>,
     pub(in crate::sessions) mysql_basic_conn_id: AtomicU32,
+
+    // Queries held by their workload groups on this node.
+    pub query_queue: Arc<QueryQueue>,
 }
 
 impl SessionManager {
@@ -73,6 +78,7 @@ impl SessionManager {
             status: Arc::new(RwLock::new(SessionManagerStatus::default())),
             mysql_conn_map: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_sessions))),
+            query_queue: QueryQueue::create(),
         })
     }
 
@@ -225,6 +231,10 @@ impl SessionManager {
         processes_info
     }
 
+    pub fn queued_queries_info(&self) -> Vec<QueuedQueryInfo> {
+        self.query_queue.queued_queries_info()
+    }
+
     fn destroy_idle_sessions(sessions: &Arc<RwLock<HashMap<String, Weak<Session>>>>) -> bool {
         // Read lock does not support reentrant
         // https://github.com/Amanieu/parking_lot::/blob/lock_api-0.4.4/lock_api/src/rwlock.rs#L422
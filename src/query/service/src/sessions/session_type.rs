@@ -18,6 +18,8 @@ use std::fmt;
 pub enum SessionType {
     Clickhouse,
     MySQL,
+    PostgreSQL,
+    FlightSQL,
     HTTPQuery,
     HTTPStreamingLoad,
     ClickHouseHttpHandler,
@@ -42,6 +44,8 @@ impl fmt::Display for SessionType {
             SessionType::ClickHouseHttpHandler => "ClickhouseHTTPHandler".to_string(),
             SessionType::Clickhouse => "Clickhouse".to_string(),
             SessionType::MySQL => "MySQL".to_string(),
+            SessionType::PostgreSQL => "PostgreSQL".to_string(),
+            SessionType::FlightSQL => "FlightSQL".to_string(),
             SessionType::HTTPQuery => "HTTPQuery".to_string(),
             SessionType::HTTPStreamingLoad => "HTTPStreamingLoad".to_string(),
             SessionType::Dummy => "Dummy".to_string(),
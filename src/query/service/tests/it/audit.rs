@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::Result;
+use databend_query::audit::redact_query_text;
+use databend_query::audit::AuditCategory;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::TableContext;
+use databend_query::sql::Planner;
+use futures::TryStreamExt;
+
+use crate::tests::create_query_context_with_config;
+use crate::tests::ConfigBuilder;
+
+#[test]
+fn test_audit_category_from_query_kind() {
+    let cases = vec![
+        ("GrantPriv", Some(AuditCategory::Privilege)),
+        ("RevokeRole", Some(AuditCategory::Privilege)),
+        ("CreateUser", Some(AuditCategory::Privilege)),
+        ("CreateTable", Some(AuditCategory::Ddl)),
+        ("DropDatabase", Some(AuditCategory::Ddl)),
+        ("AlterView", Some(AuditCategory::Ddl)),
+        ("TruncateTable", Some(AuditCategory::Ddl)),
+        ("SetTableColumnMaskingPolicy", Some(AuditCategory::Ddl)),
+        ("Insert", Some(AuditCategory::Dml)),
+        ("Delete", Some(AuditCategory::Dml)),
+        ("Copy", Some(AuditCategory::Dml)),
+        ("Query", None),
+        ("ShowGrants", None),
+        ("SetVariable", None),
+        ("Unknown", None),
+    ];
+
+    for (kind, expected) in cases {
+        assert_eq!(AuditCategory::from_query_kind(kind), expected, "{}", kind);
+    }
+}
+
+#[test]
+fn test_audit_category_selected_by() {
+    assert!(AuditCategory::Login.is_selected_by("all"));
+    assert!(AuditCategory::Dml.is_selected_by("ALL"));
+    assert!(!AuditCategory::Login.is_selected_by("none"));
+    assert!(!AuditCategory::Login.is_selected_by(""));
+    assert!(AuditCategory::Ddl.is_selected_by("login, ddl"));
+    assert!(AuditCategory::Privilege.is_selected_by("privilege"));
+    assert!(!AuditCategory::Dml.is_selected_by("login,ddl,privilege"));
+}
+
+#[test]
+fn test_audit_redact_query_text() {
+    let cases = vec![
+        (
+            "CREATE USER 'u' IDENTIFIED BY 'secret'",
+            "CREATE USER 'u' IDENTIFIED BY '***'",
+        ),
+        (
+            "alter user 'u' identified with sha256_password by 'secret' with require ssl",
+            "alter user 'u' identified with sha256_password by '***' with require ssl",
+        ),
+        (
+            "CREATE USER 'u' IDENTIFIED BY 'a'; CREATE USER 'v' IDENTIFIED BY 'b'",
+            "CREATE USER 'u' IDENTIFIED BY '***'; CREATE USER 'v' IDENTIFIED BY '***'",
+        ),
+        (
+            "SELECT 'secret' FROM t ORDER BY a",
+            "SELECT 'secret' FROM t ORDER BY a",
+        ),
+        ("SELECT 'unclosed", "'***'"),
+    ];
+
+    for (sql, expected) in cases {
+        assert_eq!(redact_query_text(sql), expected, "{}", sql);
+    }
+}
+
+#[tokio::test]
+async fn test_audit_log_written() -> Result<()> {
+    let audit_dir = tempfile::tempdir()?;
+    let mut conf = ConfigBuilder::create().config();
+    conf.log.audit.dir = audit_dir.path().to_str().unwrap().to_string();
+    let (_guard, ctx) = create_query_context_with_config(conf, None).await?;
+
+    let sql = "CREATE USER 'audit_u' IDENTIFIED BY 'secret_pw'";
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    ctx.attach_query_str(plan.to_string(), sql);
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let _: Vec<_> = executor.execute(ctx.clone()).await?.try_collect().await?;
+
+    let mut lines = vec![];
+    for entry in std::fs::read_dir(audit_dir.path())? {
+        let content = std::fs::read_to_string(entry?.path())?;
+        lines.extend(content.lines().map(|line| line.to_string()));
+    }
+
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("\"query_kind\":\"CreateUser\"")
+                && line.contains("\"category\":\"privilege\"")),
+        "{:?}",
+        lines
+    );
+    assert!(lines.iter().all(|line| !line.contains("secret_pw")));
+    Ok(())
+}
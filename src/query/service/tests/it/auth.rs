@@ -15,11 +15,16 @@
 use base64::encode_config;
 use base64::URL_SAFE_NO_PAD;
 use common_base::base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserIdentity;
 use common_users::CustomClaims;
 use common_users::EnsureUser;
+use common_users::JwtIssuerConfig;
+use common_users::RoleCacheManager;
 use common_users::UserApiProvider;
+use databend_query::auth::AuthMgr;
 use databend_query::auth::Credential;
 use databend_query::sessions::TableContext;
 use jwt_simple::prelude::*;
@@ -300,3 +305,277 @@ async fn test_jwt_auth_mgr_with_management() -> Result<()> {
         Ok(())
     }
 }
+
+async fn mount_jwks(server: &MockServer, json_path: &str, keys: Vec<serde_json::Value>) {
+    let j = serde_json::json!({ "keys": keys }).to_string();
+    let template = ResponseTemplate::new(200).set_body_raw(j, "application/json");
+    Mock::given(method("GET"))
+        .and(path(json_path))
+        .respond_with(template)
+        .mount(server)
+        .await;
+}
+
+fn rsa_jwk(kid: &str, key_pair: &RS256KeyPair) -> serde_json::Value {
+    let rsa_components = key_pair.public_key().to_components();
+    let e = encode_config(rsa_components.e, URL_SAFE_NO_PAD);
+    let n = encode_config(rsa_components.n, URL_SAFE_NO_PAD);
+    serde_json::json!({"kty": "RSA", "kid": kid, "alg": "RS256", "e": e, "n": n})
+}
+
+fn ec_jwk(kid: &str, key_pair: &ES256KeyPair) -> serde_json::Value {
+    // Uncompressed point: 0x04 | x | y
+    let point = key_pair.public_key().public_key().to_bytes_uncompressed();
+    let x = encode_config(&point[1..33], URL_SAFE_NO_PAD);
+    let y = encode_config(&point[33..65], URL_SAFE_NO_PAD);
+    serde_json::json!({"kty": "EC", "kid": kid, "alg": "ES256", "crv": "P-256", "x": x, "y": y})
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_jwt_auth_mgr_with_issuers() -> Result<()> {
+    let rsa_key_pair = RS256KeyPair::generate(2048)?.with_key_id("rsa_kid");
+    let ec_key_pair = ES256KeyPair::generate().with_key_id("ec_kid");
+
+    let server = MockServer::start().await;
+    mount_jwks(&server, "/rsa/jwks.json", vec![rsa_jwk(
+        "rsa_kid",
+        &rsa_key_pair,
+    )])
+    .await;
+    mount_jwks(&server, "/ec/jwks.json", vec![ec_jwk(
+        "ec_kid",
+        &ec_key_pair,
+    )])
+    .await;
+
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.jwt_issuers = vec![
+        JwtIssuerConfig {
+            issuer: "https://rsa.example.com".to_string(),
+            jwks: format!("http://{}/rsa/jwks.json", server.address()),
+            audiences: vec!["databend".to_string()],
+            ..Default::default()
+        },
+        JwtIssuerConfig {
+            issuer: "https://ec.example.com".to_string(),
+            jwks: format!("http://{}/ec/jwks.json", server.address()),
+            audiences: vec![],
+            user_claim: "email".to_string(),
+            tenant_claim: "org".to_string(),
+            role_claim: "groups".to_string(),
+        },
+    ];
+    let (_guard, ctx) = crate::tests::create_query_context_with_config(conf, None).await?;
+    let auth_mgr = ctx.get_auth_manager();
+
+    // trusted issuer and accepted audience
+    {
+        let claims = Claims::create(Duration::from_hours(2))
+            .with_issuer("https://rsa.example.com")
+            .with_audience("databend")
+            .with_subject("root");
+        let token = rsa_key_pair.sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: Some("localhost".to_string()),
+            })
+            .await;
+        assert!(res.is_ok());
+    }
+
+    // audience not accepted
+    {
+        let claims = Claims::create(Duration::from_hours(2))
+            .with_issuer("https://rsa.example.com")
+            .with_audience("other")
+            .with_subject("root");
+        let token = rsa_key_pair.sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: Some("localhost".to_string()),
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = jwt audience [\"other\"] is not accepted by issuer 'https://rsa.example.com'.",
+            res.err().unwrap().to_string()
+        );
+    }
+
+    // untrusted issuer
+    {
+        let claims = Claims::create(Duration::from_hours(2))
+            .with_issuer("https://unknown.example.com")
+            .with_audience("databend")
+            .with_subject("root");
+        let token = rsa_key_pair.sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: Some("localhost".to_string()),
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = untrusted jwt issuer 'https://unknown.example.com'.",
+            res.err().unwrap().to_string()
+        );
+    }
+
+    // signed by the key of another issuer
+    {
+        let claims = Claims::create(Duration::from_hours(2))
+            .with_issuer("https://ec.example.com")
+            .with_subject("root");
+        let token = rsa_key_pair.sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: Some("localhost".to_string()),
+            })
+            .await;
+        assert!(res.is_err());
+    }
+
+    // ES256 key with custom claim mapping
+    {
+        let custom_claims = serde_json::json!({
+            "email": "jwt-user@example.com",
+            "org": "test",
+            "groups": ["jwt-role", "jwt-role2"],
+            "ensure_user": {"roles": ["jwt-role"]},
+        });
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2))
+            .with_issuer("https://ec.example.com");
+        let token = ec_key_pair.sign(claims)?;
+
+        for role in ["jwt-role", "jwt-role2"] {
+            UserApiProvider::instance()
+                .add_role("test", RoleInfo::new(role), true)
+                .await?;
+        }
+        RoleCacheManager::instance().force_reload("test").await?;
+
+        let session = ctx.get_current_session();
+        auth_mgr
+            .auth(session.clone(), &Credential::Jwt {
+                token,
+                hostname: None,
+            })
+            .await?;
+        let user_info = ctx.get_current_user()?;
+        assert_eq!(user_info.name, "jwt-user@example.com");
+        assert_eq!(user_info.grants.roles(), vec!["jwt-role".to_string()]);
+
+        // All the roles of the claim are available.
+        let roles = session
+            .get_all_available_roles()
+            .await?
+            .into_iter()
+            .map(|r| r.name)
+            .collect::<Vec<_>>();
+        assert!(roles.contains(&"jwt-role".to_string()));
+        assert!(roles.contains(&"jwt-role2".to_string()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_jwt_auth_mgr_with_key_rotation() -> Result<()> {
+    let key_pair1 = RS256KeyPair::generate(2048)?.with_key_id("kid1");
+    let key_pair2 = RS256KeyPair::generate(2048)?.with_key_id("kid2");
+
+    let server = MockServer::start().await;
+    let json_path = "/jwks.json";
+    let j = serde_json::json!({ "keys": [rsa_jwk("kid1", &key_pair1)] }).to_string();
+    Mock::given(method("GET"))
+        .and(path(json_path))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(j, "application/json"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    // Keys after rotation.
+    mount_jwks(&server, json_path, vec![
+        rsa_jwk("kid1", &key_pair1),
+        rsa_jwk("kid2", &key_pair2),
+    ])
+    .await;
+
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.jwt_key_file = format!("http://{}{}", server.address(), json_path);
+    conf.query.jwt_key_min_reload_interval = 1;
+    let (_guard, ctx) = crate::tests::create_query_context_with_config(conf, None).await?;
+    let auth_mgr = ctx.get_auth_manager();
+
+    let claims = Claims::create(Duration::from_hours(2)).with_subject("root".to_string());
+    let token1 = key_pair1.sign(claims.clone())?;
+    let token2 = key_pair2.sign(claims)?;
+
+    auth_mgr
+        .auth(ctx.get_current_session(), &Credential::Jwt {
+            token: token1,
+            hostname: Some("localhost".to_string()),
+        })
+        .await?;
+
+    // Unknown kid right after a reload, the cached keys are kept.
+    let res = auth_mgr
+        .auth(ctx.get_current_session(), &Credential::Jwt {
+            token: token2.clone(),
+            hostname: Some("localhost".to_string()),
+        })
+        .await;
+    assert!(res.is_err());
+
+    // Unknown kid triggers a reload once the minimal reload interval passed.
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    auth_mgr
+        .auth(ctx.get_current_session(), &Credential::Jwt {
+            token: token2,
+            hostname: Some("localhost".to_string()),
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_jwt_auth_mgr_with_invalid_jwks() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jwks.json"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    // The keys are loaded at startup, an unavailable key source is a config error.
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.jwt_key_file = format!("http://{}/jwks.json", server.address());
+    let res = AuthMgr::create(&conf).await;
+    assert_eq!(
+        res.err().unwrap().code(),
+        ErrorCode::InvalidConfig("").code()
+    );
+
+    // So is a key source without any supported key.
+    let server = MockServer::start().await;
+    mount_jwks(&server, "/jwks.json", vec![
+        serde_json::json!({"kty": "oct", "kid": "kid", "k": "c2VjcmV0"}),
+    ])
+    .await;
+    conf.query.jwt_key_file = format!("http://{}/jwks.json", server.address());
+    let res = AuthMgr::create(&conf).await;
+    assert_eq!(
+        res.err().unwrap().code(),
+        ErrorCode::InvalidConfig("").code()
+    );
+
+    Ok(())
+}
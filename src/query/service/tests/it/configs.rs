@@ -45,6 +45,10 @@ clickhouse_handler_host = "127.0.0.1"
 clickhouse_handler_port = 9000
 clickhouse_http_handler_host = "127.0.0.1"
 clickhouse_http_handler_port = 8124
+postgres_handler_host = "127.0.0.1"
+postgres_handler_port = 5433
+flight_sql_handler_host = "127.0.0.1"
+flight_sql_handler_port = 8900
 http_handler_host = "127.0.0.1"
 http_handler_port = 8000
 http_handler_result_timeout_millis = 10000
@@ -57,6 +61,12 @@ http_handler_tls_server_root_ca_cert = ""
 api_tls_server_cert = ""
 api_tls_server_key = ""
 api_tls_server_root_ca_cert = ""
+mysql_tls_server_cert = ""
+mysql_tls_server_key = ""
+mysql_tls_server_root_ca_cert = ""
+postgres_tls_server_cert = ""
+postgres_tls_server_key = ""
+postgres_tls_server_root_ca_cert = ""
 rpc_tls_server_cert = ""
 rpc_tls_server_key = ""
 rpc_tls_query_server_root_ca_cert = ""
@@ -77,6 +87,9 @@ table_cache_bloom_index_meta_count = 3000
 table_cache_bloom_index_data_bytes = 1073741824
 management_mode = false
 jwt_key_file = ""
+jwt_key_refresh_interval = 900
+jwt_key_min_reload_interval = 5
+jwt_issuers = []
 async_insert_max_data_size = 10000
 async_insert_busy_timeout = 200
 async_insert_stale_timeout = 0
@@ -100,6 +113,11 @@ on = false
 level = "INFO"
 format = "text"
 
+[log.audit]
+on = true
+dir = "./.databend/logs/audit"
+categories = "all"
+
 [meta]
 embedded_dir = ""
 endpoints = []
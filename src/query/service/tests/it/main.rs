@@ -14,6 +14,7 @@
 #![feature(thread_local)]
 
 mod api;
+mod audit;
 mod auth;
 mod catalogs;
 mod clusters;
@@ -0,0 +1,205 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+use std::time::Duration as StdDuration;
+
+use base64::encode_config;
+use base64::URL_SAFE_NO_PAD;
+use common_arrow::arrow::array::Int32Array;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_schemas;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::data::HandshakeRequest;
+use common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::servers::flight_sql::CommandStatementQuery;
+use databend_query::servers::flight_sql::FlightSqlMessage;
+use databend_query::servers::FlightSQLServer;
+use jwt_simple::prelude::*;
+use tonic::transport::Channel;
+use tonic::Code;
+use tonic::Request;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+async fn connect(listening: SocketAddr) -> Result<FlightServiceClient<Channel>> {
+    let channel = Channel::from_shared(format!("http://{}", listening))
+        .map_err(|e| ErrorCode::BadArguments(e.to_string()))?
+        .connect()
+        .await
+        .map_err(|e| ErrorCode::CannotConnectNode(e.to_string()))?;
+    Ok(FlightServiceClient::new(channel))
+}
+
+fn with_authorization<T>(message: T, authorization: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("authorization", authorization.parse().unwrap());
+    request
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_sql_statement_query() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut server = FlightSQLServer::create(config)?;
+    let listening = server.start("127.0.0.1:0".parse()?).await?;
+    let mut client = connect(listening).await?;
+
+    // root:<empty password>
+    let handshake = tokio_stream::iter(vec![HandshakeRequest::default()]);
+    let response = client
+        .handshake(with_authorization(handshake, "Basic cm9vdDo="))
+        .await?;
+    let authorization = response
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+        .unwrap();
+    assert!(authorization.starts_with("Bearer "));
+
+    let descriptor = FlightDescriptor {
+        r#type: 2,
+        cmd: CommandStatementQuery {
+            query: "SELECT number::INT AS a, 'x' AS b FROM numbers(3) ORDER BY a".to_string(),
+        }
+        .to_any_bytes(),
+        path: vec![],
+    };
+
+    // Calls without the token are rejected.
+    let status = client
+        .get_flight_info(Request::new(descriptor.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let info = client
+        .get_flight_info(with_authorization(descriptor, &authorization))
+        .await?
+        .into_inner();
+    let ticket = info.endpoint[0].ticket.clone().unwrap();
+
+    let mut stream = client
+        .do_get(with_authorization(ticket, &authorization))
+        .await?
+        .into_inner();
+    let first = stream.message().await?.unwrap();
+    let (schema, ipc_schema) = deserialize_schemas(&first.data_header)?;
+    assert_eq!(schema.fields[0].name, "a");
+    assert_eq!(schema.fields[1].name, "b");
+
+    let mut a = vec![];
+    let mut b = vec![];
+    while let Some(data) = stream.message().await? {
+        let chunk = deserialize_batch(&data, &schema.fields, &ipc_schema, &Default::default())?;
+        let arrays = chunk.arrays();
+        let ints = arrays[0].as_any().downcast_ref::<Int32Array>().unwrap();
+        let strings = arrays[1].as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        a.extend(ints.values_iter().copied());
+        b.extend(strings.values_iter().map(|s| s.to_string()));
+    }
+    assert_eq!(a, vec![0, 1, 2]);
+    assert_eq!(b, vec!["x", "x", "x"]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_sql_handshake_failure() -> Result<()> {
+    let config = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut server = FlightSQLServer::create(config)?;
+    let listening = server.start("127.0.0.1:0".parse()?).await?;
+    let mut client = connect(listening).await?;
+
+    // not_exists_user:
+    let handshake = tokio_stream::iter(vec![HandshakeRequest::default()]);
+    let status = client
+        .handshake(with_authorization(
+            handshake,
+            "Basic bm90X2V4aXN0c191c2VyOg==",
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_sql_jwt_expired() -> Result<()> {
+    let kid = "test_kid";
+    let key_pair = RS256KeyPair::generate(2048)?.with_key_id(kid);
+    let rsa_components = key_pair.public_key().to_components();
+    let e = encode_config(rsa_components.e, URL_SAFE_NO_PAD);
+    let n = encode_config(rsa_components.n, URL_SAFE_NO_PAD);
+    let j =
+        serde_json::json!({"keys": [ {"kty": "RSA", "kid": kid, "e": e, "n": n, } ] }).to_string();
+
+    let server = MockServer::start().await;
+    let template = ResponseTemplate::new(200).set_body_raw(j, "application/json");
+    Mock::given(method("GET"))
+        .and(path("/jwks.json"))
+        .respond_with(template)
+        .mount(&server)
+        .await;
+
+    let mut config = ConfigBuilder::create().config();
+    config.query.jwt_key_file = format!("http://{}/jwks.json", server.address());
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut server = FlightSQLServer::create(config)?;
+    let listening = server.start("127.0.0.1:0".parse()?).await?;
+    let mut client = connect(listening).await?;
+
+    let claims = Claims::create(Duration::from_secs(2)).with_subject("root".to_string());
+    let authorization = format!("Bearer {}", key_pair.sign(claims)?);
+    let descriptor = FlightDescriptor {
+        r#type: 2,
+        cmd: CommandStatementQuery {
+            query: "SELECT 1".to_string(),
+        }
+        .to_any_bytes(),
+        path: vec![],
+    };
+
+    client
+        .get_flight_info(with_authorization(descriptor.clone(), &authorization))
+        .await?;
+
+    // The session of the JWT is closed after it expires.
+    tokio::time::sleep(StdDuration::from_secs(3)).await;
+    let status = client
+        .get_flight_info(with_authorization(descriptor, &authorization))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    Ok(())
+}
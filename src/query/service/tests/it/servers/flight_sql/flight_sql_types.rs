@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::servers::flight_sql::bind_parameters;
+use databend_query::servers::flight_sql::count_parameters;
+
+#[test]
+fn test_count_parameters() -> Result<()> {
+    assert_eq!(count_parameters("SELECT 1"), 0);
+    assert_eq!(count_parameters("SELECT ?, ? FROM t WHERE a = ?"), 3);
+    // Placeholders in literals, quoted identifiers and comments are ignored.
+    assert_eq!(count_parameters("SELECT '?', \"?\", `?` -- ?"), 0);
+    assert_eq!(count_parameters("SELECT 'it''s ?', ? /* ? */"), 1);
+    Ok(())
+}
+
+#[test]
+fn test_bind_parameters() -> Result<()> {
+    let query = "SELECT * FROM t WHERE a = ? AND b = '?' AND c = ?";
+    let bound = bind_parameters(query, &["1".to_string(), "'x'".to_string()])?;
+    assert_eq!(bound, "SELECT * FROM t WHERE a = 1 AND b = '?' AND c = 'x'");
+
+    // The number of the parameters must match.
+    assert!(bind_parameters(query, &["1".to_string()]).is_err());
+    assert!(bind_parameters("SELECT 1", &["1".to_string()]).is_err());
+    Ok(())
+}
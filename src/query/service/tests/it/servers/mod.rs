@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod flight_sql;
 mod http;
 mod mysql;
+mod postgres;
@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use databend_query::servers::PostgresFederated;
+
+#[test]
+fn test_postgres_federated() -> Result<()> {
+    let federated = PostgresFederated::create("default".to_string());
+
+    //
+    {
+        let query = "select 1";
+        let result = federated.check(query);
+        assert!(result.is_none());
+    }
+
+    // select version()
+    {
+        let query = "select version()";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some(block) = result {
+            assert!(!block.is_empty())
+        }
+
+        let query = "select versiona";
+        let result = federated.check(query);
+        assert!(result.is_none());
+    }
+
+    // select current_schema()
+    {
+        let query = "SELECT current_schema()";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some(block) = result {
+            let expect = vec![
+                "+----------------+",
+                "| current_schema |",
+                "+----------------+",
+                "| default        |",
+                "+----------------+",
+            ];
+
+            assert_blocks_eq(expect, &[block]);
+        }
+    }
+
+    // show parameters
+    {
+        let query = "SHOW TRANSACTION ISOLATION LEVEL";
+        let result = federated.check(query);
+        assert!(result.is_some());
+
+        if let Some(block) = result {
+            let expect = vec![
+                "+-----------------------+",
+                "| transaction_isolation |",
+                "+-----------------------+",
+                "| read committed        |",
+                "+-----------------------+",
+            ];
+
+            assert_blocks_eq(expect, &[block]);
+        }
+    }
+
+    // set and transaction commands sent by drivers
+    {
+        for query in [
+            "SET extra_float_digits = 3",
+            "SET application_name = 'PostgreSQL JDBC Driver'",
+            "SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL READ COMMITTED",
+            "BEGIN",
+            "COMMIT",
+            "DISCARD ALL",
+        ] {
+            let result = federated.check(query);
+            assert!(result.is_some(), "{}", query);
+
+            if let Some(block) = result {
+                assert!(block.is_empty(), "{}", query);
+            }
+        }
+    }
+
+    Ok(())
+}
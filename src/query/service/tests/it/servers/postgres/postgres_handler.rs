@@ -0,0 +1,483 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+
+use common_base::base::tokio;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_exception::Result;
+use databend_query::servers::PostgresHandler;
+use databend_query::servers::ServerTlsConfig;
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::tests::tls_constants::TEST_TLS_SERVER_CERT;
+use crate::tests::tls_constants::TEST_TLS_SERVER_KEY;
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_simple_query() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut stream = connect(listening.port(), "default").await?;
+
+    let mut query = b"SELECT 1, 'a'".to_vec();
+    query.push(0);
+    send_message(&mut stream, b'Q', &query).await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'T', b'D', b'C', b'Z']);
+    assert_eq!(data_row(&messages[1].1), vec![
+        Some("1".to_string()),
+        Some("a".to_string())
+    ]);
+    assert_eq!(&messages[2].1[..], b"SELECT 1\0");
+
+    // Errors are reported and the connection stays usable.
+    let mut query = b"SELECT * FROM not_exists_table".to_vec();
+    query.push(0);
+    send_message(&mut stream, b'Q', &query).await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'E', b'Z']);
+
+    send_message(&mut stream, b'Q', b"\0").await?;
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'I', b'Z']);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_extended_query() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut stream = connect(listening.port(), "default").await?;
+
+    // Parse: unnamed statement with an int4 parameter.
+    let mut parse = vec![0u8];
+    parse.extend_from_slice(b"SELECT $1::BIGINT AS x\0");
+    parse.extend_from_slice(&1i16.to_be_bytes());
+    parse.extend_from_slice(&23i32.to_be_bytes());
+    send_message(&mut stream, b'P', &parse).await?;
+
+    // Bind: text parameter, binary results.
+    let mut bind = vec![0u8, 0u8];
+    bind.extend_from_slice(&0i16.to_be_bytes());
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    bind.extend_from_slice(&2i32.to_be_bytes());
+    bind.extend_from_slice(b"41");
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    send_message(&mut stream, b'B', &bind).await?;
+
+    send_message(&mut stream, b'D', b"P\0").await?;
+    let mut execute = vec![0u8];
+    execute.extend_from_slice(&0i32.to_be_bytes());
+    send_message(&mut stream, b'E', &execute).await?;
+    send_message(&mut stream, b'S', &[]).await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'1', b'2', b'T', b'D', b'C', b'Z']);
+    // BIGINT is an int8 in binary.
+    let row = &messages[3].1;
+    assert_eq!(&row[0..2], &1i16.to_be_bytes());
+    assert_eq!(&row[2..6], &8i32.to_be_bytes());
+    assert_eq!(&row[6..14], &41i64.to_be_bytes());
+
+    // Errors in the extended protocol skip messages until Sync.
+    let mut parse = vec![0u8];
+    parse.extend_from_slice(b"SELEC 1\0");
+    parse.extend_from_slice(&0i16.to_be_bytes());
+    send_message(&mut stream, b'P', &parse).await?;
+    send_message(&mut stream, b'B', &[0, 0, 0, 0, 0, 0, 0, 0]).await?;
+    send_message(&mut stream, b'D', b"P\0").await?;
+    send_message(&mut stream, b'S', &[]).await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'1', b'E', b'Z']);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_extended_query_negative_parameter() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut stream = connect(listening.port(), "default").await?;
+
+    // Parse: `10-$1` must not become the comment `10--5`.
+    let mut parse = vec![0u8];
+    parse.extend_from_slice(b"SELECT (10-$1)::BIGINT AS x\0");
+    parse.extend_from_slice(&1i16.to_be_bytes());
+    parse.extend_from_slice(&23i32.to_be_bytes());
+    send_message(&mut stream, b'P', &parse).await?;
+
+    // Bind: text parameter -5, binary results.
+    let mut bind = vec![0u8, 0u8];
+    bind.extend_from_slice(&0i16.to_be_bytes());
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    bind.extend_from_slice(&2i32.to_be_bytes());
+    bind.extend_from_slice(b"-5");
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    bind.extend_from_slice(&1i16.to_be_bytes());
+    send_message(&mut stream, b'B', &bind).await?;
+
+    send_message(&mut stream, b'D', b"P\0").await?;
+    let mut execute = vec![0u8];
+    execute.extend_from_slice(&0i32.to_be_bytes());
+    send_message(&mut stream, b'E', &execute).await?;
+    send_message(&mut stream, b'S', &[]).await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'1', b'2', b'T', b'D', b'C', b'Z']);
+    let row = &messages[3].1;
+    assert_eq!(&row[6..14], &15i64.to_be_bytes());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_unknown_database() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    // The name is not put into a query, quotes in it are harmless.
+    for database in ["not_exists_database", "a`; DROP DATABASE default; --"] {
+        let mut stream = TcpStream::connect(("127.0.0.1", listening.port())).await?;
+        send_startup(&mut stream, "root", database).await?;
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..], &0i32.to_be_bytes());
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'E');
+        assert!(String::from_utf8_lossy(&body).contains("3D000"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_too_many_parameters() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut stream = connect(listening.port(), "default").await?;
+
+    let mut parse = vec![0u8];
+    parse.extend_from_slice(b"SELECT $999999999999\0");
+    parse.extend_from_slice(&0i16.to_be_bytes());
+    send_message(&mut stream, b'P', &parse).await?;
+    send_message(&mut stream, b'S', &[]).await?;
+
+    let messages = read_until_ready(&mut stream).await?;
+    let tags = messages.iter().map(|(tag, _)| *tag).collect::<Vec<_>>();
+    assert_eq!(tags, vec![b'E', b'Z']);
+    assert!(String::from_utf8_lossy(&messages[0].1).contains("at most 65535 parameters"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_password_authentication() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut root = connect(listening.port(), "default").await?;
+    simple_query(&mut root, "CREATE USER 'pg_user' IDENTIFIED BY 'password'").await?;
+
+    let mut errors = Vec::new();
+    for (user, password) in [
+        ("pg_user", "password"),
+        ("pg_user", "wrong"),
+        ("no_user", "any"),
+    ] {
+        let mut stream = TcpStream::connect(("127.0.0.1", listening.port())).await?;
+        send_startup(&mut stream, user, "default").await?;
+
+        // AuthenticationCleartextPassword
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..], &3i32.to_be_bytes());
+        let mut message = password.as_bytes().to_vec();
+        message.push(0);
+        send_message(&mut stream, b'p', &message).await?;
+
+        let (tag, body) = read_message(&mut stream).await?;
+        match tag {
+            b'R' => assert_eq!(&body[..], &0i32.to_be_bytes()),
+            _ => errors.push(String::from_utf8_lossy(&body).replace(user, "<user>")),
+        }
+    }
+
+    // Unknown users can't be told apart from wrong passwords.
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0], errors[1]);
+    assert!(errors[0].contains("28P01"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_scram_sha256_authentication() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut root = connect(listening.port(), "default").await?;
+    simple_query(
+        &mut root,
+        "CREATE USER 'scram_user' IDENTIFIED WITH scram_sha256_password BY 'pencil'",
+    )
+    .await?;
+
+    for (password, authed) in [("pencil", true), ("wrong", false)] {
+        let mut stream = TcpStream::connect(("127.0.0.1", listening.port())).await?;
+        send_startup(&mut stream, "scram_user", "default").await?;
+
+        // AuthenticationSASL
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..], b"\0\0\0\x0aSCRAM-SHA-256\0\0");
+
+        let client_first_bare = "n=,r=rOprNGfwEbeRWgbNEkqO";
+        let client_first = format!("n,,{}", client_first_bare);
+        let mut initial = b"SCRAM-SHA-256\0".to_vec();
+        initial.extend_from_slice(&(client_first.len() as i32).to_be_bytes());
+        initial.extend_from_slice(client_first.as_bytes());
+        send_message(&mut stream, b'p', &initial).await?;
+
+        // AuthenticationSASLContinue
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..4], &11i32.to_be_bytes());
+        let server_first = String::from_utf8(body[4..].to_vec()).unwrap();
+        let attrs = server_first
+            .split(',')
+            .map(|attr| attr.split_at(2))
+            .collect::<Vec<_>>();
+        let (nonce, salt, iterations) = (attrs[0].1, attrs[1].1, attrs[2].1);
+        assert!(nonce.starts_with("rOprNGfwEbeRWgbNEkqO"));
+
+        let salt = base64::decode(salt).unwrap();
+        let salted_password = hi(password.as_bytes(), &salt, iterations.parse().unwrap());
+        let client_key = hmac_sha256(&salted_password, b"Client Key");
+        let stored_key = Sha256::digest(client_key);
+        let client_final_without_proof = format!("c=biws,r={}", nonce);
+        let auth_message = format!(
+            "{},{},{}",
+            client_first_bare, server_first, client_final_without_proof
+        );
+        let client_signature = hmac_sha256(&stored_key, auth_message.as_bytes());
+        let proof = client_key
+            .iter()
+            .zip(client_signature.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        let client_final = format!("{},p={}", client_final_without_proof, base64::encode(proof));
+        send_message(&mut stream, b'p', client_final.as_bytes()).await?;
+
+        let (tag, body) = read_message(&mut stream).await?;
+        if !authed {
+            assert_eq!(tag, b'E');
+            assert!(String::from_utf8_lossy(&body).contains("28P01"));
+            continue;
+        }
+
+        // AuthenticationSASLFinal with the server signature, then AuthenticationOk.
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..4], &12i32.to_be_bytes());
+        let server_key = hmac_sha256(&salted_password, b"Server Key");
+        let server_signature = hmac_sha256(&server_key, auth_message.as_bytes());
+        assert_eq!(
+            &body[4..],
+            format!("v={}", base64::encode(server_signature)).as_bytes()
+        );
+        let (tag, body) = read_message(&mut stream).await?;
+        assert_eq!(tag, b'R');
+        assert_eq!(&body[..], &0i32.to_be_bytes());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_ssl_request_accepted() -> Result<()> {
+    let config = ConfigBuilder::create()
+        .postgres_tls_server_cert(TEST_TLS_SERVER_CERT)
+        .postgres_tls_server_key(TEST_TLS_SERVER_KEY)
+        .build();
+    let _guard = TestGlobalServices::setup(config.clone()).await?;
+
+    let mut handler = PostgresHandler::create(ServerTlsConfig::postgres(&config))?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    let mut stream = TcpStream::connect(("127.0.0.1", listening.port())).await?;
+    send_ssl_request(&mut stream).await?;
+    assert_eq!(stream.read_u8().await?, b'S');
+
+    Ok(())
+}
+
+async fn connect(port: u16, database: &str) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await?;
+
+    // SSL is declined if TLS is not configured.
+    send_ssl_request(&mut stream).await?;
+    assert_eq!(stream.read_u8().await?, b'N');
+
+    send_startup(&mut stream, "root", database).await?;
+    let messages = read_until_ready(&mut stream).await?;
+    // AuthenticationOk, then the parameter statuses and the key data.
+    assert_eq!(messages[0].0, b'R');
+    assert_eq!(&messages[0].1[..], &0i32.to_be_bytes());
+    assert!(
+        messages
+            .iter()
+            .any(|(tag, body)| *tag == b'S' && body.starts_with(b"server_version\0"))
+    );
+    assert!(messages.iter().any(|(tag, _)| *tag == b'K'));
+    Ok(stream)
+}
+
+async fn send_ssl_request(stream: &mut TcpStream) -> Result<()> {
+    let mut ssl_request = Vec::new();
+    ssl_request.extend_from_slice(&8i32.to_be_bytes());
+    ssl_request.extend_from_slice(&80877103i32.to_be_bytes());
+    stream.write_all(&ssl_request).await?;
+    Ok(())
+}
+
+async fn send_startup(stream: &mut TcpStream, user: &str, database: &str) -> Result<()> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&196608i32.to_be_bytes());
+    for (name, value) in [("user", user), ("database", database)] {
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        body.extend_from_slice(value.as_bytes());
+        body.push(0);
+    }
+    body.push(0);
+
+    let mut message = ((body.len() + 4) as i32).to_be_bytes().to_vec();
+    message.extend_from_slice(&body);
+    stream.write_all(&message).await?;
+    Ok(())
+}
+
+async fn send_message(stream: &mut TcpStream, tag: u8, body: &[u8]) -> Result<()> {
+    let mut message = vec![tag];
+    message.extend_from_slice(&((body.len() + 4) as i32).to_be_bytes());
+    message.extend_from_slice(body);
+    stream.write_all(&message).await?;
+    Ok(())
+}
+
+async fn read_message(stream: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let tag = stream.read_u8().await?;
+    let len = stream.read_i32().await? as usize;
+    let mut body = vec![0u8; len - 4];
+    stream.read_exact(&mut body).await?;
+    Ok((tag, body))
+}
+
+async fn simple_query(stream: &mut TcpStream, query: &str) -> Result<()> {
+    let mut message = query.as_bytes().to_vec();
+    message.push(0);
+    send_message(stream, b'Q', &message).await?;
+    let messages = read_until_ready(stream).await?;
+    assert!(messages.iter().all(|(tag, _)| *tag != b'E'), "{}", query);
+    Ok(())
+}
+
+async fn read_until_ready(stream: &mut TcpStream) -> Result<Vec<(u8, Vec<u8>)>> {
+    let mut messages = Vec::new();
+    loop {
+        let message = read_message(stream).await?;
+        let ready = message.0 == b'Z';
+        messages.push(message);
+        if ready {
+            return Ok(messages);
+        }
+    }
+}
+
+fn data_row(body: &[u8]) -> Vec<Option<String>> {
+    let num_columns = i16::from_be_bytes([body[0], body[1]]);
+    let mut offset = 2;
+    let mut values = Vec::new();
+    for _ in 0..num_columns {
+        let len = i32::from_be_bytes(body[offset..offset + 4].try_into().unwrap());
+        offset += 4;
+        if len < 0 {
+            values.push(None);
+        } else {
+            let len = len as usize;
+            values.push(Some(
+                String::from_utf8_lossy(&body[offset..offset + len]).to_string(),
+            ));
+            offset += len;
+        }
+    }
+    values
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hi(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut u = hmac_sha256(password, &[salt, &1u32.to_be_bytes()].concat());
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &u);
+        result.iter_mut().zip(u.iter()).for_each(|(r, u)| *r ^= u);
+    }
+    result
+}
@@ -0,0 +1,154 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use common_base::base::tokio;
+use common_catalog::table_context::QueuedQueryInfo;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+use databend_query::sessions::AdmissionPermit;
+use databend_query::sessions::QueryQueue;
+use parking_lot::Mutex;
+
+fn workload_group(max_concurrency: u64, queue_timeout: u64) -> WorkloadGroup {
+    WorkloadGroup {
+        max_concurrency,
+        queue_timeout,
+        ..WorkloadGroup::new("wg")
+    }
+}
+
+async fn admit(
+    queue: &QueryQueue,
+    group: &WorkloadGroup,
+    query_id: &str,
+    aborting: Arc<AtomicBool>,
+) -> Result<AdmissionPermit> {
+    let query = QueuedQueryInfo {
+        query_id: query_id.to_string(),
+        user: "'u'@'%'".to_string(),
+        workload_group: group.name.clone(),
+        query_text: "SELECT 1".to_string(),
+        enqueue_time: SystemTime::now(),
+    };
+    queue
+        .admit("test", group, query, Arc::new(Mutex::new(None)), aborting)
+        .await
+}
+
+#[tokio::test]
+async fn test_query_queue_admit_after_release() -> Result<()> {
+    let queue = QueryQueue::create();
+    let group = workload_group(1, 0);
+
+    let permit = admit(&queue, &group, "q1", Arc::new(AtomicBool::new(false))).await?;
+
+    let queued = {
+        let queue = queue.clone();
+        let group = group.clone();
+        tokio::spawn(
+            async move { admit(&queue, &group, "q2", Arc::new(AtomicBool::new(false))).await },
+        )
+    };
+
+    // q2 waits in the queue while q1 is running.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!queued.is_finished());
+    let queued_ids: Vec<String> = queue
+        .queued_queries_info()
+        .into_iter()
+        .map(|query| query.query_id)
+        .collect();
+    assert_eq!(queued_ids, vec!["q2".to_string()]);
+
+    // Releasing the permit of q1 admits q2, which leaves the queue.
+    drop(permit);
+    let permit = tokio::time::timeout(Duration::from_secs(5), queued)
+        .await
+        .expect("q2 is admitted once q1 is released")
+        .unwrap()?;
+    assert!(queue.queued_queries_info().is_empty());
+
+    drop(permit);
+    let _permit = tokio::time::timeout(
+        Duration::from_millis(100),
+        admit(&queue, &group, "q3", Arc::new(AtomicBool::new(false))),
+    )
+    .await
+    .expect("q3 is admitted without waiting")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_queue_timeout() -> Result<()> {
+    let queue = QueryQueue::create();
+    let group = workload_group(1, 1);
+
+    let _permit = admit(&queue, &group, "q1", Arc::new(AtomicBool::new(false))).await?;
+    let res = admit(&queue, &group, "q2", Arc::new(AtomicBool::new(false))).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::QueryQueueTimeout("").code())
+    );
+    assert!(queue.queued_queries_info().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_queue_aborted() -> Result<()> {
+    let queue = QueryQueue::create();
+    let group = workload_group(1, 0);
+
+    let _permit = admit(&queue, &group, "q1", Arc::new(AtomicBool::new(false))).await?;
+    let aborting = Arc::new(AtomicBool::new(false));
+    let queued = {
+        let queue = queue.clone();
+        let group = group.clone();
+        let aborting = aborting.clone();
+        tokio::spawn(async move { admit(&queue, &group, "q2", aborting).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    aborting.store(true, Ordering::Release);
+    let res = tokio::time::timeout(Duration::from_secs(5), queued)
+        .await
+        .expect("a killed query leaves the queue")
+        .unwrap();
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::AbortedQuery("").code())
+    );
+    assert!(queue.queued_queries_info().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_queue_unlimited_group() -> Result<()> {
+    let queue = QueryQueue::create();
+    let group = workload_group(0, 0);
+
+    let mut permits = vec![];
+    for i in 0..10 {
+        let query_id = format!("q{}", i);
+        permits.push(admit(&queue, &group, &query_id, Arc::new(AtomicBool::new(false))).await?);
+    }
+    assert!(queue.queued_queries_info().is_empty());
+    Ok(())
+}
@@ -27,6 +27,7 @@ use common_catalog::plan::PartInfoPtr;
 use common_catalog::plan::Partitions;
 use common_catalog::table::Table;
 use common_catalog::table_context::ProcessInfo;
+use common_catalog::table_context::QueuedQueryInfo;
 use common_catalog::table_context::StageAttachment;
 use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
@@ -386,6 +387,10 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    async fn get_available_roles(&self) -> Result<Vec<RoleInfo>> {
+        todo!()
+    }
+
     fn get_fuse_version(&self) -> String {
         todo!()
     }
@@ -455,6 +460,10 @@ impl TableContext for CtxDelegation {
         todo!()
     }
 
+    fn get_queued_queries_info(&self) -> Vec<QueuedQueryInfo> {
+        todo!()
+    }
+
     fn get_stage_attachment(&self) -> Option<StageAttachment> {
         todo!()
     }
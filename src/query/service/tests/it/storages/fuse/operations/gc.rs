@@ -23,6 +23,7 @@ use common_exception::Result;
 use common_storages_fuse::io::write_meta;
 use common_storages_fuse::io::BlockWriter;
 use common_storages_fuse::io::SegmentWriter;
+use common_storages_fuse::operations::OrphanFiles;
 use common_storages_fuse::statistics::gen_columns_statistics;
 use common_storages_fuse::FuseTable;
 use common_storages_table_meta::meta::Location;
@@ -241,6 +242,84 @@ async fn test_fuse_purge_orphan_retention() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_fuse_purge_orphans() -> Result<()> {
+    // verifies that:
+    //
+    // - segments (and their blocks/indexes) that are not referenced by any
+    //   snapshot are reported as orphans, but only removed if not in dry run
+    // - files younger than `older_than` are left untouched
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    let number_of_block = 1;
+    append_sample_data(number_of_block, &fixture).await?;
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+    // an orphan segment, with one block and one bloom index
+    let _ = utils::generate_segments(fuse_table, 1, 1).await?;
+
+    let table_ctx: Arc<dyn TableContext> = ctx.clone();
+    let orphan_counts = |orphans: &[OrphanFiles]| {
+        orphans
+            .iter()
+            .map(|o| (o.file_type, o.file_count))
+            .collect::<Vec<_>>()
+    };
+
+    // 1. files are too young to be purged
+    let orphans = fuse_table
+        .do_purge_orphans(&table_ctx, Some(Duration::days(1)), false)
+        .await?;
+    assert!(orphans.iter().all(|o| o.file_count == 0));
+
+    // 2. dry run reports, but keeps the files
+    let orphans = fuse_table
+        .do_purge_orphans(&table_ctx, Some(Duration::zero()), true)
+        .await?;
+    assert_eq!(orphan_counts(&orphans), vec![
+        ("snapshot", 0),
+        ("segment", 1),
+        ("block", 1),
+        ("index", 1),
+        ("point_index", 0),
+    ]);
+    check_data_dir(
+        &fixture,
+        "purge orphans: dry run",
+        1,
+        0,
+        2,
+        2,
+        2,
+        Some(()),
+        None,
+    )
+    .await?;
+
+    // 3. orphans removed
+    let orphans = fuse_table
+        .do_purge_orphans(&table_ctx, Some(Duration::zero()), false)
+        .await?;
+    assert_eq!(orphans.iter().map(|o| o.file_count).sum::<u64>(), 3);
+    check_data_dir(
+        &fixture,
+        "purge orphans: purged",
+        1,
+        0,
+        1,
+        1,
+        1,
+        Some(()),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
 mod utils {
     use std::sync::Arc;
 
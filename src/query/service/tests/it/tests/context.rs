@@ -58,7 +58,7 @@ pub async fn create_query_context_with_session(
         UserPrivilegeSet::available_privileges_on_global(),
     );
 
-    dummy_session.set_authed_user(user_info, None).await?;
+    dummy_session.set_authed_user(user_info, vec![]).await?;
 
     let dummy_query_context = dummy_session.create_query_context().await?;
     dummy_query_context.get_settings().set_max_threads(8)?;
@@ -93,7 +93,7 @@ pub async fn create_query_context_with_config(
     }
 
     dummy_session
-        .set_authed_user(current_user.unwrap(), None)
+        .set_authed_user(current_user.unwrap(), vec![])
         .await?;
     let dummy_query_context = dummy_session.create_query_context().await?;
 
@@ -476,6 +476,16 @@ impl Settings {
                 desc: "How many hours will the COPY file metadata expired in the metasrv, default value: 24*7=7days",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1),
+                user_setting: UserSetting::create(
+                    "enable_materialized_view_rewrite",
+                    UserSettingValue::UInt64(1),
+                ),
+                level: ScopeLevel::Session,
+                desc: "If enable answering queries from up-to-date materialized views, default value: 1",
+                possible_values: None,
+            },
         ];
 
         let settings: Arc<DashMap<String, SettingValue>> = Arc::new(DashMap::default());
@@ -785,6 +795,18 @@ impl Settings {
         self.try_get_u64(key)
     }
 
+    pub fn get_enable_materialized_view_rewrite(&self) -> Result<bool> {
+        static KEY: &str = "enable_materialized_view_rewrite";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_materialized_view_rewrite(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_materialized_view_rewrite";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
     pub fn has_setting(&self, key: &str) -> bool {
         self.settings.get(key).is_some()
     }
@@ -39,6 +39,7 @@ use crate::plans::Plan;
 use crate::plans::RewriteKind;
 use crate::plans::ShowGrantsPlan;
 use crate::plans::ShowRolesPlan;
+use crate::plans::UnlockUserPlan;
 use crate::plans::UseDatabasePlan;
 use crate::BindContext;
 use crate::ColumnBinding;
@@ -58,6 +59,9 @@ pub struct Binder {
     pub catalogs: Arc<CatalogManager>,
     pub name_resolution_ctx: NameResolutionContext,
     pub metadata: MetadataRef,
+    /// Masking policies only apply to reads, statements evaluating predicates
+    /// directly on the storage (e.g. DELETE) must bind the raw columns.
+    pub(super) apply_masking_policies: bool,
 }
 
 impl<'a> Binder {
@@ -72,6 +76,7 @@ impl<'a> Binder {
             catalogs,
             name_resolution_ctx,
             metadata,
+            apply_masking_policies: true,
         }
     }
 
@@ -88,6 +93,13 @@ impl<'a> Binder {
     ) -> Result<Plan> {
         let plan = match stmt {
             Statement::Query(query) => {
+                if let Some(plan) = self
+                    .try_bind_materialized_view_rewrite(bind_context, query)
+                    .await?
+                {
+                    return Ok(plan);
+                }
+
                 let (s_expr, bind_context) = self.bind_query(bind_context, query).await?;
                 Plan::Query {
                     s_expr: Box::new(s_expr),
@@ -168,6 +180,16 @@ impl<'a> Binder {
             Statement::CreateView(stmt) => self.bind_create_view(stmt).await?,
             Statement::AlterView(stmt) => self.bind_alter_view(stmt).await?,
             Statement::DropView(stmt) => self.bind_drop_view(stmt).await?,
+            Statement::CreateMaterializedView(stmt) => {
+                self.bind_create_materialized_view(stmt).await?
+            }
+            Statement::RefreshMaterializedView(stmt) => {
+                self.bind_refresh_materialized_view(stmt).await?
+            }
+
+            // Indexes
+            Statement::CreateInvertedIndex(stmt) => self.bind_create_inverted_index(stmt).await?,
+            Statement::DropInvertedIndex(stmt) => self.bind_drop_inverted_index(stmt).await?,
 
             // Users
             Statement::CreateUser(stmt) => self.bind_create_user(stmt).await?,
@@ -177,6 +199,9 @@ impl<'a> Binder {
             })),
             Statement::ShowUsers => self.bind_rewrite_to_query(bind_context, "SELECT name, hostname, auth_type, auth_string FROM system.users ORDER BY name", RewriteKind::ShowUsers).await?,
             Statement::AlterUser(stmt) => self.bind_alter_user(stmt).await?,
+            Statement::UnlockUser { user } => Plan::UnlockUser(Box::new(UnlockUserPlan {
+                user: user.clone(),
+            })),
 
             // Roles
             Statement::ShowRoles => Plan::ShowRoles(Box::new(ShowRolesPlan {})),
@@ -285,6 +310,25 @@ impl<'a> Binder {
                 if_exists: *if_exists,
                 name: udf_name.to_string(),
             })),
+
+            // Row access policies
+            Statement::CreateRowAccessPolicy(stmt) => {
+                self.bind_create_row_access_policy(stmt).await?
+            }
+            Statement::DropRowAccessPolicy(stmt) => self.bind_drop_row_access_policy(stmt).await?,
+
+            // Masking policies
+            Statement::CreateMaskingPolicy(stmt) => self.bind_create_masking_policy(stmt).await?,
+            Statement::DropMaskingPolicy(stmt) => self.bind_drop_masking_policy(stmt).await?,
+
+            // Workload groups
+            Statement::CreateWorkloadGroup(stmt) => self.bind_create_workload_group(stmt).await?,
+            Statement::DropWorkloadGroup(stmt) => self.bind_drop_workload_group(stmt).await?,
+
+            // Password policies
+            Statement::CreatePasswordPolicy(stmt) => self.bind_create_password_policy(stmt).await?,
+            Statement::DropPasswordPolicy(stmt) => self.bind_drop_password_policy(stmt).await?,
+
             Statement::Call(stmt) => Plan::Call(Box::new(CallPlan {
                 name: stmt.name.clone(),
                 args: stmt.args.clone(),
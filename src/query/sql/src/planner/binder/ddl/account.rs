@@ -15,12 +15,15 @@
 use common_ast::ast::AccountMgrLevel;
 use common_ast::ast::AccountMgrSource;
 use common_ast::ast::AlterUserStmt;
+use common_ast::ast::AuthOption;
 use common_ast::ast::CreateUserStmt;
 use common_ast::ast::GrantStmt;
 use common_ast::ast::RevokeStmt;
+use common_ast::ast::UserOptionItem;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
 use common_meta_types::GrantObject;
+use common_meta_types::UserInfo;
 use common_meta_types::UserOption;
 use common_meta_types::UserPrivilegeSet;
 use common_users::UserApiProvider;
@@ -156,9 +159,12 @@ impl<'a> Binder {
         for option in user_options {
             option.apply(&mut user_option);
         }
+        let auth_info = AuthInfo::create2(&auth_option.auth_type, &auth_option.password)?;
+        self.check_password_policy(&user_option, auth_option, &auth_info, None)
+            .await?;
         let plan = CreateUserPlan {
             user: user.clone(),
-            auth_info: AuthInfo::create2(&auth_option.auth_type, &auth_option.password)?,
+            auth_info,
             user_option,
             if_not_exists: *if_not_exists,
         };
@@ -183,23 +189,42 @@ impl<'a> Binder {
                 .await?
         };
 
+        let mut user_option = user_info.option.clone();
+        for option in user_options {
+            option.apply(&mut user_option);
+        }
+
         // None means no change to make
         let new_auth_info = if let Some(auth_option) = &auth_option {
             let auth_info = user_info
                 .auth_info
                 .alter2(&auth_option.auth_type, &auth_option.password)?;
+            self.check_password_policy(&user_option, auth_option, &auth_info, Some(&user_info))
+                .await?;
             if user_info.auth_info == auth_info {
                 None
             } else {
                 Some(auth_info)
             }
         } else {
+            if user_option.password_policy() != user_info.option.password_policy() {
+                self.check_password_policy(
+                    &user_option,
+                    &AuthOption::default(),
+                    &user_info.auth_info,
+                    None,
+                )
+                .await?;
+            }
             None
         };
 
-        let mut user_option = user_info.option.clone();
-        for option in user_options {
-            option.apply(&mut user_option);
+        // A new password fulfills the must change password, unless it is set again.
+        let set_must_change_password = user_options
+            .iter()
+            .any(|option| matches!(option, UserOptionItem::MustChangePassword(_)));
+        if new_auth_info.is_some() && !set_must_change_password {
+            user_option.set_must_change_password(false);
         }
         let new_user_option = if user_option == user_info.option {
             None
@@ -214,4 +239,28 @@ impl<'a> Binder {
 
         Ok(Plan::AlterUser(Box::new(plan)))
     }
+
+    // Check the password against the password policy of the user, the policy must exist.
+    async fn check_password_policy(
+        &self,
+        user_option: &UserOption,
+        auth_option: &AuthOption,
+        auth_info: &AuthInfo,
+        user_info: Option<&UserInfo>,
+    ) -> Result<()> {
+        let policy_name = match user_option.password_policy() {
+            None => return Ok(()),
+            Some(policy_name) => policy_name,
+        };
+        let user_api = UserApiProvider::instance();
+        let policy = user_api
+            .get_password_policy(&self.ctx.get_tenant(), policy_name)
+            .await?;
+        match (&auth_option.password, auth_info) {
+            (Some(password), AuthInfo::Password { .. }) => {
+                user_api.check_password_policy(&policy, password, auth_info, user_info)
+            }
+            _ => Ok(()),
+        }
+    }
 }
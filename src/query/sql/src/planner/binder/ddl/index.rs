@@ -0,0 +1,129 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateInvertedIndexStmt;
+use common_ast::ast::DropInvertedIndexStmt;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::TypeID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateInvertedIndexPlan;
+use crate::plans::DropInvertedIndexPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_inverted_index(
+        &mut self,
+        stmt: &CreateInvertedIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateInvertedIndexStmt {
+            if_not_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+            column,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index_name = normalize_identifier(index_name, &self.name_resolution_ctx).name;
+        let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+
+        let table_ref = self.ctx.get_table(&catalog, &database, &table).await?;
+        if table_ref.engine() != "FUSE" {
+            return Err(ErrorCode::SemanticError(format!(
+                "inverted index can only be created on FUSE table, but {}.{} is {}",
+                database,
+                table,
+                table_ref.engine()
+            )));
+        }
+        let schema = table_ref.schema();
+        let field = schema.field_with_name(&column).map_err(|_| {
+            ErrorCode::SemanticError(format!(
+                "column {} does not exist in table {}.{}",
+                column, database, table
+            ))
+        })?;
+        if !is_string_type(field.data_type()) {
+            return Err(ErrorCode::SemanticError(format!(
+                "inverted index can only be created on string column, but {} is {:?}",
+                column,
+                field.data_type()
+            )));
+        }
+
+        let plan = CreateInvertedIndexPlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index_name,
+            column,
+        };
+        Ok(Plan::CreateInvertedIndex(Box::new(plan)))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_inverted_index(
+        &mut self,
+        stmt: &DropInvertedIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let DropInvertedIndexStmt {
+            if_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index_name = normalize_identifier(index_name, &self.name_resolution_ctx).name;
+
+        let plan = DropInvertedIndexPlan {
+            if_exists: *if_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index_name,
+        };
+        Ok(Plan::DropInvertedIndex(Box::new(plan)))
+    }
+}
+
+fn is_string_type(data_type: &DataTypeImpl) -> bool {
+    remove_nullable(data_type).data_type_id() == TypeID::String
+}
@@ -0,0 +1,141 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateMaskingPolicyStmt;
+use common_ast::ast::DropMaskingPolicyStmt;
+use common_datavalues::remove_nullable;
+use common_datavalues::TypeFactory;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::MaskingPolicy;
+
+use crate::binder::Binder;
+use crate::binder::ColumnBinding;
+use crate::binder::ScalarBinder;
+use crate::binder::Visibility;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateMaskingPolicyPlan;
+use crate::plans::DropMaskingPolicyPlan;
+use crate::plans::Plan;
+use crate::BindContext;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_masking_policy(
+        &mut self,
+        stmt: &CreateMaskingPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateMaskingPolicyStmt {
+            if_not_exists,
+            name,
+            parameters,
+            return_type,
+            definition,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+
+        // The masked value replaces the column, so it must keep the column type.
+        if parameters.len() != 1 {
+            return Err(ErrorCode::SemanticError(format!(
+                "masking policy {} must have exactly one parameter, but got {}",
+                name,
+                parameters.len()
+            )));
+        }
+        let return_data_type = TypeFactory::instance().get(return_type.to_string())?;
+        let parameter_data_type = TypeFactory::instance().get(parameters[0].1.to_string())?;
+        if remove_nullable(&return_data_type).data_type_id()
+            != remove_nullable(&parameter_data_type).data_type_id()
+        {
+            return Err(ErrorCode::SemanticError(format!(
+                "masking policy {} must return the type of its parameter {}, but got {}",
+                name, parameters[0].1, return_type
+            )));
+        }
+
+        // Bind the definition on the parameters, to reject invalid policies early
+        // instead of failing every query on the tables it is attached to.
+        let mut bind_context = BindContext::new();
+        let mut policy_parameters: Vec<(String, String)> = Vec::with_capacity(parameters.len());
+        for (parameter, type_name) in parameters.iter() {
+            let parameter = normalize_identifier(parameter, &self.name_resolution_ctx).name;
+            if policy_parameters.iter().any(|(name, _)| name == &parameter) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "duplicate parameter {} in masking policy {}",
+                    parameter, name
+                )));
+            }
+            let data_type = TypeFactory::instance().get(type_name.to_string())?;
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(parameter.clone(), data_type.clone());
+            bind_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: parameter.clone(),
+                index,
+                data_type: Box::new(data_type),
+                visibility: Visibility::Visible,
+            });
+            policy_parameters.push((parameter, type_name.to_string()));
+        }
+
+        let mut scalar_binder = ScalarBinder::new(
+            &bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let (_, data_type) = scalar_binder.bind(definition).await?;
+        if remove_nullable(&data_type).data_type_id()
+            != remove_nullable(&return_data_type).data_type_id()
+        {
+            return Err(ErrorCode::SemanticError(format!(
+                "masking policy {} must return {}, but got {:?}",
+                name, return_type, data_type
+            )));
+        }
+
+        let policy = MaskingPolicy::new(
+            &name,
+            policy_parameters,
+            &return_type.to_string(),
+            &definition.to_string(),
+        );
+        Ok(Plan::CreateMaskingPolicy(Box::new(
+            CreateMaskingPolicyPlan {
+                if_not_exists: *if_not_exists,
+                tenant,
+                policy,
+            },
+        )))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_masking_policy(
+        &mut self,
+        stmt: &DropMaskingPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropMaskingPolicyStmt { if_exists, name } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+        Ok(Plan::DropMaskingPolicy(Box::new(DropMaskingPolicyPlan {
+            if_exists: *if_exists,
+            tenant,
+            name,
+        })))
+    }
+}
@@ -15,8 +15,13 @@
 mod account;
 mod catalog;
 mod database;
+mod index;
+mod masking_policy;
+mod password_policy;
 mod role;
+mod row_access_policy;
 mod share;
 mod stage;
 mod table;
 mod view;
+mod workload_group;
@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreatePasswordPolicyStmt;
+use common_ast::ast::DropPasswordPolicyStmt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::PasswordPolicy;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreatePasswordPolicyPlan;
+use crate::plans::DropPasswordPolicyPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_password_policy(
+        &mut self,
+        stmt: &CreatePasswordPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreatePasswordPolicyStmt {
+            if_not_exists,
+            name,
+            options,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+
+        let mut policy = PasswordPolicy::new(&name);
+        for (option, value) in options.iter() {
+            let option = normalize_identifier(option, &self.name_resolution_ctx).name;
+            match option.to_lowercase().as_str() {
+                "min_length" => policy.min_length = *value,
+                "max_length" => policy.max_length = *value,
+                "min_upper_case_chars" => policy.min_upper_case_chars = *value,
+                "min_lower_case_chars" => policy.min_lower_case_chars = *value,
+                "min_numeric_chars" => policy.min_numeric_chars = *value,
+                "min_special_chars" => policy.min_special_chars = *value,
+                "max_age_days" => policy.max_age_days = *value,
+                "max_retries" => policy.max_retries = *value,
+                "lockout_time_mins" => policy.lockout_time_mins = *value,
+                "history" => policy.history = *value,
+                _ => {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "unknown option {} of password policy {}, expect min_length, max_length, min_upper_case_chars, min_lower_case_chars, min_numeric_chars, min_special_chars, max_age_days, max_retries, lockout_time_mins or history",
+                        option, name
+                    )));
+                }
+            }
+        }
+        let min_chars = policy.min_upper_case_chars
+            + policy.min_lower_case_chars
+            + policy.min_numeric_chars
+            + policy.min_special_chars;
+        if policy.min_length > policy.max_length || min_chars > policy.max_length {
+            return Err(ErrorCode::SemanticError(format!(
+                "max_length {} of password policy {} is less than the required characters",
+                policy.max_length, name
+            )));
+        }
+
+        Ok(Plan::CreatePasswordPolicy(Box::new(
+            CreatePasswordPolicyPlan {
+                if_not_exists: *if_not_exists,
+                tenant,
+                policy,
+            },
+        )))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_password_policy(
+        &mut self,
+        stmt: &DropPasswordPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropPasswordPolicyStmt { if_exists, name } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+        Ok(Plan::DropPasswordPolicy(Box::new(DropPasswordPolicyPlan {
+            if_exists: *if_exists,
+            tenant,
+            name,
+        })))
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateRowAccessPolicyStmt;
+use common_ast::ast::DropRowAccessPolicyStmt;
+use common_datavalues::remove_nullable;
+use common_datavalues::TypeFactory;
+use common_datavalues::TypeID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::RowAccessPolicy;
+
+use crate::binder::Binder;
+use crate::binder::ColumnBinding;
+use crate::binder::ScalarBinder;
+use crate::binder::Visibility;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateRowAccessPolicyPlan;
+use crate::plans::DropRowAccessPolicyPlan;
+use crate::plans::Plan;
+use crate::BindContext;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_row_access_policy(
+        &mut self,
+        stmt: &CreateRowAccessPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateRowAccessPolicyStmt {
+            if_not_exists,
+            name,
+            parameters,
+            definition,
+            exempt_roles,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+
+        // Bind the definition on the parameters, to reject invalid policies early
+        // instead of failing every query on the tables it is attached to.
+        let mut bind_context = BindContext::new();
+        let mut policy_parameters: Vec<(String, String)> = Vec::with_capacity(parameters.len());
+        for (parameter, type_name) in parameters.iter() {
+            let parameter = normalize_identifier(parameter, &self.name_resolution_ctx).name;
+            if policy_parameters.iter().any(|(name, _)| name == &parameter) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "duplicate parameter {} in row access policy {}",
+                    parameter, name
+                )));
+            }
+            let data_type = TypeFactory::instance().get(type_name.to_string())?;
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(parameter.clone(), data_type.clone());
+            bind_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: parameter.clone(),
+                index,
+                data_type: Box::new(data_type),
+                visibility: Visibility::Visible,
+            });
+            policy_parameters.push((parameter, type_name.to_string()));
+        }
+
+        let mut scalar_binder = ScalarBinder::new(
+            &bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let (_, data_type) = scalar_binder.bind(definition).await?;
+        if remove_nullable(&data_type).data_type_id() != TypeID::Boolean {
+            return Err(ErrorCode::SemanticError(format!(
+                "row access policy {} must return BOOLEAN, but got {:?}",
+                name, data_type
+            )));
+        }
+
+        let policy = RowAccessPolicy::new(
+            &name,
+            policy_parameters,
+            &definition.to_string(),
+            exempt_roles.clone(),
+        );
+        Ok(Plan::CreateRowAccessPolicy(Box::new(
+            CreateRowAccessPolicyPlan {
+                if_not_exists: *if_not_exists,
+                tenant,
+                policy,
+            },
+        )))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_row_access_policy(
+        &mut self,
+        stmt: &DropRowAccessPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropRowAccessPolicyStmt { if_exists, name } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+        Ok(Plan::DropRowAccessPolicy(Box::new(
+            DropRowAccessPolicyPlan {
+                if_exists: *if_exists,
+                tenant,
+                name,
+            },
+        )))
+    }
+}
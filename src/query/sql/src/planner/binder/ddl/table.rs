@@ -28,6 +28,7 @@ use common_ast::ast::Engine;
 use common_ast::ast::ExistsTableStmt;
 use common_ast::ast::Expr;
 use common_ast::ast::Identifier;
+use common_ast::ast::IntervalKind;
 use common_ast::ast::Literal;
 use common_ast::ast::OptimizeTableAction as AstOptimizeTableAction;
 use common_ast::ast::OptimizeTableStmt;
@@ -46,6 +47,7 @@ use common_ast::parser::tokenize_sql;
 use common_ast::walk_expr_mut;
 use common_ast::Backtrace;
 use common_ast::Dialect;
+use common_datavalues::remove_nullable;
 use common_datavalues::type_coercion::compare_coercion;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
@@ -60,6 +62,7 @@ use common_storages_table_meta::table::is_reserved_opt_key;
 use common_storages_table_meta::table::OPT_KEY_DATABASE_ID;
 use common_storages_view::view_table::QUERY;
 use common_storages_view::view_table::VIEW_ENGINE;
+use common_users::UserApiProvider;
 use tracing::debug;
 
 use crate::binder::location::parse_uri_location;
@@ -71,6 +74,7 @@ use crate::optimizer::OptimizerConfig;
 use crate::optimizer::OptimizerContext;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::IdentifierNormalizer;
+use crate::plans::AddTableRowAccessPolicyPlan;
 use crate::plans::AlterTableClusterKeyPlan;
 use crate::plans::AnalyzeTablePlan;
 use crate::plans::CastExpr;
@@ -78,6 +82,7 @@ use crate::plans::CreateTablePlanV2;
 use crate::plans::DescribeTablePlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTablePlan;
+use crate::plans::DropTableRowAccessPolicyPlan;
 use crate::plans::ExistsTablePlan;
 use crate::plans::OptimizeTableAction;
 use crate::plans::OptimizeTablePlan;
@@ -88,6 +93,7 @@ use crate::plans::RenameTablePlan;
 use crate::plans::RevertTablePlan;
 use crate::plans::RewriteKind;
 use crate::plans::Scalar;
+use crate::plans::SetTableColumnMaskingPolicyPlan;
 use crate::plans::ShowCreateTablePlan;
 use crate::plans::TruncateTablePlan;
 use crate::plans::UndropTablePlan;
@@ -611,6 +617,8 @@ impl<'a> Binder {
                 is_final,
                 selection,
             } => {
+                // The raw columns are bound, the masked columns in the WHERE are replaced below.
+                self.apply_masking_policies = false;
                 let (_, context) = self
                     .bind_table_reference(bind_context, table_reference)
                     .await?;
@@ -625,6 +633,10 @@ impl<'a> Binder {
 
                 let push_downs = if let Some(expr) = selection {
                     let (scalar, _) = scalar_binder.bind(expr).await?;
+                    let table_meta = self.ctx.get_table(&catalog, &database, &table).await?;
+                    let scalar = self
+                        .apply_masking_policies_to_scalar(&context, table_meta.as_ref(), scalar)
+                        .await?;
                     Some(scalar)
                 } else {
                     None
@@ -650,6 +662,117 @@ impl<'a> Binder {
                     point,
                 })))
             }
+            AlterTableAction::AddRowAccessPolicy { policy, columns } => {
+                let policy = normalize_identifier(policy, &self.name_resolution_ctx).name;
+                let columns: Vec<String> = columns
+                    .iter()
+                    .map(|column| normalize_identifier(column, &self.name_resolution_ctx).name)
+                    .collect();
+
+                let row_access_policy = UserApiProvider::instance()
+                    .get_row_access_policy(&tenant, &policy)
+                    .await?;
+                if row_access_policy.parameters.len() != columns.len() {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "row access policy {} expects {} columns, but got {}",
+                        policy,
+                        row_access_policy.parameters.len(),
+                        columns.len()
+                    )));
+                }
+
+                let table_ref = self.ctx.get_table(&catalog, &database, &table).await?;
+                if table_ref.engine() == VIEW_ENGINE {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "cannot add row access policy to view {}.{}",
+                        database, table
+                    )));
+                }
+                let schema = table_ref.schema();
+                for (column, (parameter, type_name)) in
+                    columns.iter().zip(row_access_policy.parameters.iter())
+                {
+                    let field = schema.field_with_name(column)?;
+                    let expected = TypeFactory::instance().get(type_name)?;
+                    if remove_nullable(field.data_type()).data_type_id()
+                        != remove_nullable(&expected).data_type_id()
+                    {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "column {} has type {:?}, but parameter {} of row access policy {} is {}",
+                            column,
+                            field.data_type(),
+                            parameter,
+                            policy,
+                            type_name
+                        )));
+                    }
+                }
+
+                Ok(Plan::AddTableRowAccessPolicy(Box::new(
+                    AddTableRowAccessPolicyPlan {
+                        tenant,
+                        catalog,
+                        database,
+                        table,
+                        policy,
+                        columns,
+                    },
+                )))
+            }
+            AlterTableAction::DropRowAccessPolicy { policy } => Ok(Plan::DropTableRowAccessPolicy(
+                Box::new(DropTableRowAccessPolicyPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    policy: normalize_identifier(policy, &self.name_resolution_ctx).name,
+                }),
+            )),
+            AlterTableAction::ModifyColumnMaskingPolicy { column, policy } => {
+                let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                let policy = policy
+                    .as_ref()
+                    .map(|policy| normalize_identifier(policy, &self.name_resolution_ctx).name);
+
+                if let Some(policy) = &policy {
+                    let masking_policy = UserApiProvider::instance()
+                        .get_masking_policy(&tenant, policy)
+                        .await?;
+
+                    let table_ref = self.ctx.get_table(&catalog, &database, &table).await?;
+                    if table_ref.engine() == VIEW_ENGINE {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "cannot set masking policy on view {}.{}",
+                            database, table
+                        )));
+                    }
+                    let schema = table_ref.schema();
+                    let field = schema.field_with_name(&column)?;
+                    let expected = TypeFactory::instance().get(&masking_policy.return_type)?;
+                    if remove_nullable(field.data_type()).data_type_id()
+                        != remove_nullable(&expected).data_type_id()
+                    {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "column {} has type {:?}, but masking policy {} is for {}",
+                            column,
+                            field.data_type(),
+                            policy,
+                            masking_policy.return_type
+                        )));
+                    }
+                }
+
+                Ok(Plan::SetTableColumnMaskingPolicy(Box::new(
+                    SetTableColumnMaskingPolicyPlan {
+                        tenant,
+                        catalog,
+                        database,
+                        table,
+                        column,
+                        policy,
+                    },
+                )))
+            }
         }
     }
 
@@ -761,6 +884,13 @@ impl<'a> Binder {
             match ast_action {
                 AstOptimizeTableAction::All => OptimizeTableAction::All,
                 AstOptimizeTableAction::Purge => OptimizeTableAction::Purge,
+                AstOptimizeTableAction::PurgeOrphans {
+                    older_than,
+                    dry_run,
+                } => OptimizeTableAction::PurgeOrphans {
+                    older_than: older_than.as_ref().map(interval_seconds).transpose()?,
+                    dry_run: *dry_run,
+                },
                 AstOptimizeTableAction::Compact { target, limit } => {
                     let limit_cnt = match limit {
                         Some(Expr::Literal {
@@ -1019,3 +1149,36 @@ impl<'a> Binder {
         source_fields
     }
 }
+
+// Converts the literal interval like `INTERVAL 7 DAY` to seconds.
+fn interval_seconds(expr: &Expr) -> Result<u64> {
+    if let Expr::Interval {
+        expr: value, unit, ..
+    } = expr
+    {
+        let value = match value.as_ref() {
+            Expr::Literal {
+                lit: Literal::Integer(v),
+                ..
+            } => Some(*v),
+            Expr::Literal {
+                lit: Literal::String(v),
+                ..
+            } => v.trim().parse::<u64>().ok(),
+            _ => None,
+        };
+        let seconds_of_unit = match unit {
+            IntervalKind::Day => Some(24 * 60 * 60),
+            IntervalKind::Hour => Some(60 * 60),
+            IntervalKind::Minute => Some(60),
+            IntervalKind::Second => Some(1),
+            _ => None,
+        };
+        if let (Some(value), Some(seconds_of_unit)) = (value, seconds_of_unit) {
+            return Ok(value * seconds_of_unit);
+        }
+    }
+    Err(ErrorCode::BadArguments(format!(
+        "expect an interval of days, hours, minutes or seconds, like `INTERVAL 7 DAY`, but got `{expr}`"
+    )))
+}
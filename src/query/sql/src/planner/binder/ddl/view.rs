@@ -12,17 +12,39 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use common_ast::ast::AlterViewStmt;
+use common_ast::ast::CreateMaterializedViewStmt;
 use common_ast::ast::CreateViewStmt;
 use common_ast::ast::DropViewStmt;
+use common_ast::ast::Engine;
+use common_ast::ast::RefreshMaterializedViewStmt;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_ast::Dialect;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_storages_table_meta::table::OPT_KEY_DATABASE_ID;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::MaterializedViewMeta;
 
 use crate::binder::Binder;
+use crate::optimizer::optimize;
+use crate::optimizer::OptimizerConfig;
+use crate::optimizer::OptimizerContext;
 use crate::planner::semantic::normalize_identifier;
 use crate::plans::AlterViewPlan;
+use crate::plans::CreateTablePlanV2;
 use crate::plans::CreateViewPlan;
 use crate::plans::DropViewPlan;
 use crate::plans::Plan;
+use crate::plans::RefreshMaterializedViewPlan;
+use crate::BindContext;
 
 impl<'a> Binder {
     pub(in crate::planner::binder) async fn bind_create_view(
@@ -124,4 +146,158 @@ impl<'a> Binder {
         };
         Ok(Plan::DropView(Box::new(plan)))
     }
+
+    pub(in crate::planner::binder) async fn bind_create_materialized_view(
+        &mut self,
+        stmt: &CreateMaterializedViewStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateMaterializedViewStmt {
+            if_not_exists,
+            catalog,
+            database,
+            view,
+            query,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+
+        if query.with.is_some()
+            || !query.order_by.is_empty()
+            || !query.limit.is_empty()
+            || query.offset.is_some()
+        {
+            return Err(ErrorCode::SemanticError(
+                "WITH, ORDER BY, LIMIT and OFFSET are not supported in materialized view",
+            ));
+        }
+        let select = match &query.body {
+            SetExpr::Select(select) => select,
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    "materialized view must be defined by a single SELECT",
+                ));
+            }
+        };
+        let mv_query = self.analyze_materialized_view_query(select)?;
+        if mv_query.catalog != catalog {
+            return Err(ErrorCode::SemanticError(
+                "materialized view must be in the same catalog as its source table",
+            ));
+        }
+
+        let source = self
+            .ctx
+            .get_table(&mv_query.catalog, &mv_query.database, &mv_query.table)
+            .await?;
+        if source.engine() != "FUSE" {
+            return Err(ErrorCode::SemanticError(format!(
+                "materialized view can only be created on FUSE table, but {}.{} is {}",
+                mv_query.database,
+                mv_query.table,
+                source.engine()
+            )));
+        }
+
+        // The view is populated by the canonical query, exactly what will be
+        // evaluated against the newly appended data while refreshing.
+        let tokens = tokenize_sql(&mv_query.canonical_sql)?;
+        let backtrace = Backtrace::new();
+        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
+        let canonical_query = match &stmt {
+            Statement::Query(canonical_query) => canonical_query,
+            _ => unreachable!("canonical materialized view query must be a query"),
+        };
+        let init_bind_context = BindContext::new();
+        let (s_expr, bind_context) = self.bind_query(&init_bind_context, canonical_query).await?;
+        let schema = bind_context.output_schema();
+        let select_plan = Plan::Query {
+            s_expr: Box::new(s_expr),
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(bind_context),
+            rewrite_kind: None,
+            ignore_result: false,
+        };
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig::default()));
+        let select_plan = optimize(self.ctx.clone(), opt_ctx, select_plan)?;
+
+        let meta = MaterializedViewMeta {
+            query: mv_query.canonical_sql,
+            source_database: mv_query.database,
+            source_table: mv_query.table,
+            source_table_id: source.get_id(),
+            // `get_table` is cached during the query, the snapshot here is the one being read.
+            source_snapshot_location: source
+                .get_table_info()
+                .options()
+                .get(OPT_KEY_SNAPSHOT_LOCATION)
+                .cloned(),
+            merge_kinds: mv_query.merge_kinds,
+        };
+        let mut options: BTreeMap<String, String> = meta.to_options();
+        let db_id = self
+            .ctx
+            .get_catalog(&catalog)?
+            .get_database(&tenant, &database)
+            .await?
+            .get_db_info()
+            .ident
+            .db_id;
+        options.insert(OPT_KEY_DATABASE_ID.to_owned(), db_id.to_string());
+
+        let plan = CreateTablePlanV2 {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table: viewname,
+            schema,
+            engine: Engine::Fuse,
+            storage_params: None,
+            options,
+            field_default_exprs: vec![],
+            field_comments: vec![],
+            cluster_key: None,
+            as_select: Some(Box::new(select_plan)),
+        };
+        Ok(Plan::CreateTable(Box::new(plan)))
+    }
+
+    pub(in crate::planner::binder) async fn bind_refresh_materialized_view(
+        &mut self,
+        stmt: &RefreshMaterializedViewStmt<'a>,
+    ) -> Result<Plan> {
+        let RefreshMaterializedViewStmt {
+            catalog,
+            database,
+            view,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+
+        let plan = RefreshMaterializedViewPlan {
+            tenant,
+            catalog,
+            database,
+            viewname,
+        };
+        Ok(Plan::RefreshMaterializedView(Box::new(plan)))
+    }
 }
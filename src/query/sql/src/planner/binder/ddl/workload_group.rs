@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateWorkloadGroupStmt;
+use common_ast::ast::DropWorkloadGroupStmt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::CreateWorkloadGroupPlan;
+use crate::plans::DropWorkloadGroupPlan;
+use crate::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) async fn bind_create_workload_group(
+        &mut self,
+        stmt: &CreateWorkloadGroupStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateWorkloadGroupStmt {
+            if_not_exists,
+            name,
+            options,
+            users,
+            roles,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+
+        let mut group = WorkloadGroup::new(&name);
+        for (option, value) in options.iter() {
+            let option = normalize_identifier(option, &self.name_resolution_ctx).name;
+            match option.to_lowercase().as_str() {
+                "max_concurrency" => group.max_concurrency = *value,
+                "memory_quota" => group.memory_quota = *value,
+                "queue_timeout" => group.queue_timeout = *value,
+                _ => {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "unknown option {} of workload group {}, expect max_concurrency, memory_quota or queue_timeout",
+                        option, name
+                    )));
+                }
+            }
+        }
+        group.users = users.clone();
+        group.roles = roles.clone();
+
+        Ok(Plan::CreateWorkloadGroup(Box::new(
+            CreateWorkloadGroupPlan {
+                if_not_exists: *if_not_exists,
+                tenant,
+                group,
+            },
+        )))
+    }
+
+    pub(in crate::planner::binder) async fn bind_drop_workload_group(
+        &mut self,
+        stmt: &DropWorkloadGroupStmt<'a>,
+    ) -> Result<Plan> {
+        let DropWorkloadGroupStmt { if_exists, name } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+        Ok(Plan::DropWorkloadGroup(Box::new(DropWorkloadGroupPlan {
+            if_exists: *if_exists,
+            tenant,
+            name,
+        })))
+    }
+}
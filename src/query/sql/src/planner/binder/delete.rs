@@ -53,6 +53,8 @@ impl<'a> Binder {
             ));
         };
 
+        // The raw columns are bound, the masked columns in the WHERE are replaced below.
+        self.apply_masking_policies = false;
         let (_, context) = self
             .bind_table_reference(bind_context, table_reference)
             .await?;
@@ -75,10 +77,16 @@ impl<'a> Binder {
 
         let selection = if let Some(expr) = filter {
             let (scalar, _) = scalar_binder.bind(expr).await?;
+            let scalar = self
+                .apply_masking_policies_to_scalar(&context, table.as_ref(), scalar)
+                .await?;
             Some(scalar)
         } else {
             None
         };
+        let selection = self
+            .bind_row_access_selection(&context, table.as_ref(), selection)
+            .await?;
 
         let plan = DeletePlan {
             catalog_name,
@@ -0,0 +1,162 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_ast::parser::parse_expr;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_catalog::table::Table;
+use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableMaskingPolicies;
+use common_storages_table_meta::table::OPT_KEY_MASKING_POLICIES;
+use common_users::UserApiProvider;
+
+use crate::binder::Binder;
+use crate::binder::ScalarBinder;
+use crate::binder::Visibility;
+use crate::optimizer::SExpr;
+use crate::plans::BoundColumnRef;
+use crate::plans::EvalScalar;
+use crate::plans::Scalar;
+use crate::plans::ScalarItem;
+use crate::BindContext;
+
+impl Binder {
+    /// Replace the columns of `table` that have a masking policy with the masked
+    /// expression, evaluated on top of the scan.
+    ///
+    /// The column bindings are rebound to the derived columns, so every reference
+    /// to them, including predicates, sees the masked value. Such predicates can't
+    /// be pushed down through the `EvalScalar`, hence never reach the storage.
+    pub(super) async fn bind_masking_policies(
+        &mut self,
+        bind_context: &mut BindContext,
+        table: &dyn Table,
+        s_expr: SExpr,
+    ) -> Result<SExpr> {
+        if !self.apply_masking_policies {
+            return Ok(s_expr);
+        }
+        let masked_columns = self.bind_masked_columns(bind_context, table).await?;
+        if masked_columns.is_empty() {
+            return Ok(s_expr);
+        }
+
+        let mut items = Vec::with_capacity(masked_columns.len());
+        for (position, scalar, data_type) in masked_columns {
+            let masked = &mut bind_context.columns[position];
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(masked.column_name.clone(), data_type.clone());
+            items.push(ScalarItem { scalar, index });
+
+            masked.index = index;
+            masked.data_type = Box::new(data_type);
+        }
+
+        let eval_scalar = EvalScalar { items };
+        Ok(SExpr::create_unary(eval_scalar.into(), s_expr))
+    }
+
+    /// Replace the references to the masked columns of `table` in `scalar` with the
+    /// masked expression.
+    ///
+    /// For the statements that bind the raw columns of the table, e.g. DELETE, so the
+    /// predicates and the values of them see the masked value as SELECT does.
+    pub(super) async fn apply_masking_policies_to_scalar(
+        &mut self,
+        bind_context: &BindContext,
+        table: &dyn Table,
+        scalar: Scalar,
+    ) -> Result<Scalar> {
+        let masked_columns = self.bind_masked_columns(bind_context, table).await?;
+        if masked_columns.is_empty() {
+            return Ok(scalar);
+        }
+
+        let masked = masked_columns
+            .into_iter()
+            .map(|(position, scalar, _)| (bind_context.columns[position].index, scalar))
+            .collect::<HashMap<_, _>>();
+        self.rewrite_scalar_with_replacement(&scalar, &|scalar| match scalar {
+            Scalar::BoundColumnRef(BoundColumnRef { column }) => {
+                Ok(masked.get(&column.index).cloned())
+            }
+            _ => Ok(None),
+        })
+    }
+
+    // The masked expression of each column of `table` that has a masking policy, with
+    // the position of the column in `bind_context`.
+    async fn bind_masked_columns(
+        &mut self,
+        bind_context: &BindContext,
+        table: &dyn Table,
+    ) -> Result<Vec<(usize, Scalar, DataTypeImpl)>> {
+        let table_policies = match table.options().get(OPT_KEY_MASKING_POLICIES) {
+            Some(value) => serde_json::from_str::<TableMaskingPolicies>(value)?,
+            None => return Ok(vec![]),
+        };
+
+        let tenant = self.ctx.get_tenant();
+        let settings = self.ctx.get_settings();
+        let mut masked_columns = Vec::with_capacity(table_policies.columns.len());
+        for (column, policy) in table_policies.columns.iter() {
+            let policy = UserApiProvider::instance()
+                .get_masking_policy(&tenant, policy)
+                .await?;
+            let position = bind_context
+                .columns
+                .iter()
+                .position(|c| &c.column_name == column && c.visibility == Visibility::Visible)
+                .ok_or_else(|| {
+                    ErrorCode::SemanticError(format!(
+                        "column {} masked by policy {} not found in table {}",
+                        column,
+                        policy.name,
+                        table.name()
+                    ))
+                })?;
+            let (parameter, _) = policy.parameters.first().ok_or_else(|| {
+                ErrorCode::SemanticError(format!("masking policy {} has no parameter", policy.name))
+            })?;
+
+            // Bind the parameter of the policy to the raw column.
+            let mut policy_context = BindContext::new();
+            let mut column_binding = bind_context.columns[position].clone();
+            column_binding.database_name = None;
+            column_binding.table_name = None;
+            column_binding.column_name = parameter.clone();
+            policy_context.add_column_binding(column_binding);
+
+            let tokens = tokenize_sql(&policy.definition)?;
+            let backtrace = Backtrace::new();
+            let expr = parse_expr(&tokens, settings.get_sql_dialect()?, &backtrace)?;
+            let mut scalar_binder = ScalarBinder::new(
+                &policy_context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let (scalar, data_type) = scalar_binder.bind(&expr).await?;
+            masked_columns.push((position, scalar, data_type));
+        }
+        Ok(masked_columns)
+    }
+}
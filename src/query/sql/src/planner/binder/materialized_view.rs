@@ -0,0 +1,334 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Literal;
+use common_ast::ast::Query;
+use common_ast::ast::SelectStmt;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::ast::TableReference;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_ast::Dialect;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_table_meta::table::OPT_KEY_MASKING_POLICIES;
+use common_storages_table_meta::table::OPT_KEY_ROW_ACCESS_POLICY;
+use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
+use common_storages_view::materialized_view::quote_table_name;
+use common_storages_view::materialized_view::MaterializedViewMeta;
+use common_storages_view::materialized_view::MaterializedViewRefs;
+use common_storages_view::materialized_view::MergeKind;
+
+use crate::binder::Binder;
+use crate::planner::semantic::normalize_identifier;
+use crate::plans::Plan;
+use crate::BindContext;
+
+/// The defining query of a materialized view, in the only shape we know
+/// how to maintain incrementally:
+///
+/// `SELECT <keys>, <aggs> FROM <fuse_table> [WHERE ...] [GROUP BY <keys>]`
+///
+/// where the aggregates are SUM, COUNT, MIN or MAX.
+pub(crate) struct MaterializedViewQuery {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    /// The query text with the source table fully qualified, which is both
+    /// stored in the view and used to match queries against the view.
+    pub canonical_sql: String,
+    pub merge_kinds: Vec<MergeKind>,
+}
+
+impl<'a> Binder {
+    pub(in crate::planner::binder) fn analyze_materialized_view_query(
+        &self,
+        select: &SelectStmt<'a>,
+    ) -> Result<MaterializedViewQuery> {
+        if select.distinct {
+            return Err(ErrorCode::SemanticError(
+                "SELECT DISTINCT is not supported in materialized view, use GROUP BY instead",
+            ));
+        }
+        if select.having.is_some() {
+            return Err(ErrorCode::SemanticError(
+                "HAVING is not supported in materialized view",
+            ));
+        }
+
+        let (catalog, database, table) = match select.from.as_slice() {
+            [
+                TableReference::Table {
+                    catalog,
+                    database,
+                    table,
+                    alias: None,
+                    travel_point: None,
+                    ..
+                },
+            ] => {
+                let catalog = catalog
+                    .as_ref()
+                    .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+                    .unwrap_or_else(|| self.ctx.get_current_catalog());
+                let database = database
+                    .as_ref()
+                    .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+                    .unwrap_or_else(|| self.ctx.get_current_database());
+                let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+                (catalog, database, table)
+            }
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    "materialized view must select from exactly one table, without alias or AT clause",
+                ));
+            }
+        };
+
+        // `GROUP BY 1` refers to the first select item, other keys are matched by text.
+        let mut key_positions = HashSet::new();
+        let mut key_exprs = HashSet::new();
+        for expr in select.group_by.iter() {
+            match expr {
+                Expr::Literal {
+                    lit: Literal::Integer(pos),
+                    ..
+                } => {
+                    key_positions.insert(*pos as usize);
+                }
+                expr => {
+                    key_exprs.insert(expr.to_string());
+                }
+            }
+        }
+
+        let mut items = Vec::with_capacity(select.select_list.len());
+        let mut merge_kinds = Vec::with_capacity(select.select_list.len());
+        for (i, target) in select.select_list.iter().enumerate() {
+            let (expr, alias) = match target {
+                SelectTarget::AliasedExpr { expr, alias } => (expr, alias),
+                SelectTarget::QualifiedName { .. } => {
+                    return Err(ErrorCode::SemanticError(
+                        "wildcard is not supported in materialized view",
+                    ));
+                }
+            };
+
+            let merge_kind = match expr.as_ref() {
+                Expr::CountAll { .. } => Some(MergeKind::Sum),
+                Expr::FunctionCall {
+                    distinct: false,
+                    name,
+                    params,
+                    ..
+                } if params.is_empty() => match name.name.to_lowercase().as_str() {
+                    "sum" | "count" => Some(MergeKind::Sum),
+                    "min" => Some(MergeKind::Min),
+                    "max" => Some(MergeKind::Max),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let merge_kind = match merge_kind {
+                Some(kind) => kind,
+                None => {
+                    let by_position = key_positions.remove(&(i + 1));
+                    let by_expr = key_exprs.remove(&expr.to_string());
+                    let by_alias = alias
+                        .as_ref()
+                        .map_or(false, |alias| key_exprs.remove(&alias.to_string()));
+                    if !(by_position || by_expr || by_alias) {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "`{}` in materialized view must be either a GROUP BY key or one of SUM, COUNT, MIN and MAX",
+                            expr
+                        )));
+                    }
+                    MergeKind::Key
+                }
+            };
+
+            match alias {
+                Some(alias) => items.push(format!("{} AS {}", expr, alias)),
+                None => items.push(expr.to_string()),
+            }
+            merge_kinds.push(merge_kind);
+        }
+
+        // Rows of different groups can't be told apart after refreshing
+        // if some of the GROUP BY keys are not in the view.
+        if !key_positions.is_empty() || !key_exprs.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "all the GROUP BY keys of materialized view must be selected",
+            ));
+        }
+
+        let mut canonical_sql = format!(
+            "SELECT {} FROM {}",
+            items.join(", "),
+            quote_table_name(&catalog, &database, &table)?
+        );
+        if let Some(selection) = &select.selection {
+            canonical_sql.push_str(&format!(" WHERE {}", selection));
+        }
+        if !select.group_by.is_empty() {
+            let keys = select
+                .group_by
+                .iter()
+                .map(|expr| expr.to_string())
+                .collect::<Vec<_>>();
+            canonical_sql.push_str(&format!(" GROUP BY {}", keys.join(", ")));
+        }
+
+        Ok(MaterializedViewQuery {
+            catalog,
+            database,
+            table,
+            canonical_sql,
+            merge_kinds,
+        })
+    }
+
+    /// Answers the query from a materialized view if there is one defined by the same
+    /// query, and it is up to date with the current snapshot of its source table.
+    ///
+    /// The views of the source table are recorded in its options, no listing is needed.
+    pub(in crate::planner::binder) async fn try_bind_materialized_view_rewrite(
+        &mut self,
+        bind_context: &BindContext,
+        query: &Query<'a>,
+    ) -> Result<Option<Plan>> {
+        if !self
+            .ctx
+            .get_settings()
+            .get_enable_materialized_view_rewrite()?
+        {
+            return Ok(None);
+        }
+
+        let select = match (&query.with, &query.body) {
+            (None, SetExpr::Select(select)) => select,
+            _ => return Ok(None),
+        };
+        let mv_query = match self.analyze_materialized_view_query(select) {
+            Ok(mv_query) => mv_query,
+            Err(_) => return Ok(None),
+        };
+
+        let source = self
+            .ctx
+            .get_table(&mv_query.catalog, &mv_query.database, &mv_query.table)
+            .await?;
+        // The view holds the rows and the columns as they are, without the policies.
+        let options = source.options();
+        if options.contains_key(OPT_KEY_ROW_ACCESS_POLICY)
+            || options.contains_key(OPT_KEY_MASKING_POLICIES)
+        {
+            return Ok(None);
+        }
+        let source_snapshot_location = source
+            .get_table_info()
+            .options()
+            .get(OPT_KEY_SNAPSHOT_LOCATION)
+            .cloned();
+
+        let refs = MaterializedViewRefs::from_table_info(source.get_table_info())?;
+        for (database, name) in refs.views.iter() {
+            // The recorded view may have been dropped or replaced, check it as it is now.
+            let table = match self.ctx.get_table(&mv_query.catalog, database, name).await {
+                Ok(table) => table,
+                Err(_) => continue,
+            };
+            let meta = match MaterializedViewMeta::from_table_info(table.get_table_info())? {
+                Some(meta)
+                    if meta.source_table_id == source.get_id()
+                        && meta.query == mv_query.canonical_sql
+                        && meta.source_snapshot_location == source_snapshot_location =>
+                {
+                    meta
+                }
+                _ => continue,
+            };
+
+            // ORDER BY can only refer to the output columns, which are kept by the view.
+            let schema = table.schema();
+            let mut order_by = Vec::with_capacity(query.order_by.len());
+            for item in query.order_by.iter() {
+                match &item.expr {
+                    Expr::ColumnRef {
+                        database: None,
+                        table: None,
+                        column,
+                        ..
+                    } if schema
+                        .field_with_name(
+                            &normalize_identifier(column, &self.name_resolution_ctx).name,
+                        )
+                        .is_ok() =>
+                    {
+                        order_by.push(item.to_string())
+                    }
+                    _ => return Ok(None),
+                }
+            }
+
+            // The partial rows appended by incremental refreshes are merged by the keys.
+            let column_names = schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect::<Vec<_>>();
+            let merged_sql =
+                meta.merged_query_sql(&mv_query.catalog, database, name, &column_names)?;
+            let mut sql = format!("SELECT * FROM ({}) AS _mv", merged_sql);
+            if !order_by.is_empty() {
+                sql.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+            }
+            if !query.limit.is_empty() {
+                let limit = query
+                    .limit
+                    .iter()
+                    .map(|expr| expr.to_string())
+                    .collect::<Vec<_>>();
+                sql.push_str(&format!(" LIMIT {}", limit.join(", ")));
+            }
+            if let Some(offset) = &query.offset {
+                sql.push_str(&format!(" OFFSET {}", offset));
+            }
+
+            let tokens = tokenize_sql(&sql)?;
+            let backtrace = Backtrace::new();
+            let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
+            let rewritten = match &stmt {
+                Statement::Query(rewritten) => rewritten,
+                _ => unreachable!("rewritten materialized view query must be a query"),
+            };
+            let (s_expr, bind_context) = self.bind_query(bind_context, rewritten).await?;
+            return Ok(Some(Plan::Query {
+                s_expr: Box::new(s_expr),
+                metadata: self.metadata.clone(),
+                bind_context: Box::new(bind_context),
+                rewrite_kind: None,
+                ignore_result: query.ignore_result,
+            }));
+        }
+
+        Ok(None)
+    }
+}
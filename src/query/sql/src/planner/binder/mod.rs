@@ -28,8 +28,11 @@ mod join;
 mod kill;
 mod limit;
 mod location;
+mod masking_policy;
+mod materialized_view;
 mod presign;
 mod project;
+mod row_access_policy;
 mod scalar;
 mod scalar_common;
 mod scalar_visitor;
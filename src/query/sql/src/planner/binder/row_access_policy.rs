@@ -0,0 +1,140 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::parser::parse_expr;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_meta_types::TableRowAccessPolicy;
+use common_storages_table_meta::table::OPT_KEY_ROW_ACCESS_POLICY;
+use common_users::UserApiProvider;
+
+use crate::binder::scalar_common::split_conjunctions;
+use crate::binder::Binder;
+use crate::binder::ScalarBinder;
+use crate::optimizer::SExpr;
+use crate::plans::AndExpr;
+use crate::plans::Filter;
+use crate::plans::Scalar;
+use crate::plans::ScalarExpr;
+use crate::BindContext;
+
+impl Binder {
+    /// Wrap the scan of `table` with the predicate of the row access policy attached
+    /// to it, unless the current role is exempted by the policy.
+    pub(super) async fn bind_row_access_policy(
+        &mut self,
+        bind_context: &BindContext,
+        table: &dyn Table,
+        s_expr: SExpr,
+    ) -> Result<SExpr> {
+        match self.bind_row_access_predicate(bind_context, table).await? {
+            Some(scalar) => {
+                let filter = Filter {
+                    predicates: split_conjunctions(&scalar),
+                    is_having: false,
+                };
+                Ok(SExpr::create_unary(filter.into(), s_expr))
+            }
+            None => Ok(s_expr),
+        }
+    }
+
+    /// Combine the predicate of the row access policy of `table` with the `selection`
+    /// of a DELETE or UPDATE, so rows hidden by the policy are not modified.
+    pub(super) async fn bind_row_access_selection(
+        &mut self,
+        bind_context: &BindContext,
+        table: &dyn Table,
+        selection: Option<Scalar>,
+    ) -> Result<Option<Scalar>> {
+        let predicate = self.bind_row_access_predicate(bind_context, table).await?;
+        match (predicate, selection) {
+            (Some(predicate), Some(selection)) => {
+                let func = FunctionFactory::instance()
+                    .get("and", &[&predicate.data_type(), &selection.data_type()])?;
+                Ok(Some(
+                    AndExpr {
+                        left: Box::new(predicate),
+                        right: Box::new(selection),
+                        return_type: Box::new(func.return_type()),
+                    }
+                    .into(),
+                ))
+            }
+            (predicate, selection) => Ok(predicate.or(selection)),
+        }
+    }
+
+    // The predicate of the row access policy attached to `table`, bound to the columns
+    // of `bind_context`, or `None` if there is no policy or the current role is exempted.
+    async fn bind_row_access_predicate(
+        &mut self,
+        bind_context: &BindContext,
+        table: &dyn Table,
+    ) -> Result<Option<Scalar>> {
+        let table_policy = match table.options().get(OPT_KEY_ROW_ACCESS_POLICY) {
+            Some(value) => serde_json::from_str::<TableRowAccessPolicy>(value)?,
+            None => return Ok(None),
+        };
+
+        let tenant = self.ctx.get_tenant();
+        let policy = UserApiProvider::instance()
+            .get_row_access_policy(&tenant, &table_policy.policy)
+            .await?;
+        // Exempted if any role of the user is, not only the current role.
+        let roles = self.ctx.get_available_roles().await?;
+        if roles.iter().any(|role| policy.is_exempt(&role.name)) {
+            return Ok(None);
+        }
+
+        // Bind the parameters of the policy to the columns of the table.
+        let mut policy_context = BindContext::new();
+        for ((parameter, _), column) in policy.parameters.iter().zip(table_policy.columns.iter()) {
+            let mut column_binding = bind_context
+                .columns
+                .iter()
+                .find(|c| &c.column_name == column)
+                .cloned()
+                .ok_or_else(|| {
+                    ErrorCode::SemanticError(format!(
+                        "column {} referenced by row access policy {} not found in table {}",
+                        column,
+                        policy.name,
+                        table.name()
+                    ))
+                })?;
+            column_binding.database_name = None;
+            column_binding.table_name = None;
+            column_binding.column_name = parameter.clone();
+            policy_context.add_column_binding(column_binding);
+        }
+
+        let settings = self.ctx.get_settings();
+        let tokens = tokenize_sql(&policy.definition)?;
+        let backtrace = Backtrace::new();
+        let expr = parse_expr(&tokens, settings.get_sql_dialect()?, &backtrace)?;
+        let mut scalar_binder = ScalarBinder::new(
+            &policy_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let (scalar, _) = scalar_binder.bind(&expr).await?;
+        Ok(Some(scalar))
+    }
+}
@@ -342,7 +342,7 @@ impl<'a> Binder {
     }
 
     #[allow(clippy::only_used_in_recursion)]
-    fn rewrite_scalar_with_replacement<F>(
+    pub(super) fn rewrite_scalar_with_replacement<F>(
         &self,
         original_scalar: &Scalar,
         replacement_fn: &F,
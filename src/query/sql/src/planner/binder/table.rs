@@ -198,13 +198,19 @@ impl<'a> Binder {
                         let table_index = self.metadata.write().add_table(
                             catalog,
                             database.clone(),
-                            table_meta,
+                            table_meta.clone(),
                             table_alias_name,
                         );
 
                         let (s_expr, mut bind_context) = self
                             .bind_base_table(bind_context, database.as_str(), table_index)
                             .await?;
+                        let s_expr = self
+                            .bind_row_access_policy(&bind_context, table_meta.as_ref(), s_expr)
+                            .await?;
+                        let s_expr = self
+                            .bind_masking_policies(&mut bind_context, table_meta.as_ref(), s_expr)
+                            .await?;
                         if let Some(alias) = alias {
                             bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
                         }
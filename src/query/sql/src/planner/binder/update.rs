@@ -61,6 +61,8 @@ impl<'a> Binder {
             ));
         };
 
+        // The raw columns are bound, the masked columns in SET and WHERE are replaced below.
+        self.apply_masking_policies = false;
         let (_, context) = self.bind_table_reference(bind_context, table).await?;
 
         let table = self
@@ -99,6 +101,22 @@ impl<'a> Binder {
             None
         };
 
+        for scalar in update_columns.values_mut() {
+            *scalar = self
+                .apply_masking_policies_to_scalar(&context, table.as_ref(), scalar.clone())
+                .await?;
+        }
+        let push_downs = match push_downs {
+            Some(scalar) => Some(
+                self.apply_masking_policies_to_scalar(&context, table.as_ref(), scalar)
+                    .await?,
+            ),
+            None => None,
+        };
+        let push_downs = self
+            .bind_row_access_selection(&context, table.as_ref(), push_downs)
+            .await?;
+
         let plan = UpdatePlan {
             catalog: catalog_name,
             database: database_name,
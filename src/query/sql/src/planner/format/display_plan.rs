@@ -72,6 +72,11 @@ impl Plan {
             Plan::CreateView(create_view) => Ok(format!("{:?}", create_view)),
             Plan::AlterView(alter_view) => Ok(format!("{:?}", alter_view)),
             Plan::DropView(drop_view) => Ok(format!("{:?}", drop_view)),
+            Plan::RefreshMaterializedView(refresh_view) => Ok(format!("{:?}", refresh_view)),
+
+            // Indexes
+            Plan::CreateInvertedIndex(create_index) => Ok(format!("{:?}", create_index)),
+            Plan::DropInvertedIndex(drop_index) => Ok(format!("{:?}", drop_index)),
 
             // Insert
             Plan::Insert(insert) => Ok(format!("{:?}", insert)),
@@ -92,9 +97,23 @@ impl Plan {
             Plan::RevokeRole(revoke_role) => Ok(format!("{:?}", revoke_role)),
             Plan::CreateUser(create_user) => Ok(format!("{:?}", create_user)),
             Plan::DropUser(drop_user) => Ok(format!("{:?}", drop_user)),
+            Plan::UnlockUser(unlock_user) => Ok(format!("{:?}", unlock_user)),
             Plan::CreateUDF(create_user_udf) => Ok(format!("{:?}", create_user_udf)),
             Plan::AlterUDF(alter_user_udf) => Ok(format!("{alter_user_udf:?}")),
             Plan::DropUDF(drop_udf) => Ok(format!("{drop_udf:?}")),
+
+            // Row access policies
+            Plan::CreateRowAccessPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropRowAccessPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::AddTableRowAccessPolicy(add_policy) => Ok(format!("{:?}", add_policy)),
+            Plan::DropTableRowAccessPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::CreateMaskingPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropMaskingPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::SetTableColumnMaskingPolicy(set_policy) => Ok(format!("{:?}", set_policy)),
+            Plan::CreateWorkloadGroup(create_group) => Ok(format!("{:?}", create_group)),
+            Plan::DropWorkloadGroup(drop_group) => Ok(format!("{:?}", drop_group)),
+            Plan::CreatePasswordPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropPasswordPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
             Plan::AlterUser(alter_user) => Ok(format!("{:?}", alter_user)),
             Plan::CreateRole(create_role) => Ok(format!("{:?}", create_role)),
             Plan::DropRole(drop_role) => Ok(format!("{:?}", drop_role)),
@@ -67,6 +67,17 @@ impl DropUserPlan {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnlockUserPlan {
+    pub user: UserIdentity,
+}
+
+impl UnlockUserPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CreateRolePlan {
     pub if_not_exists: bool,
@@ -14,15 +14,25 @@
 mod account;
 mod catalog;
 mod database;
+mod index;
+mod masking_policy;
+mod password_policy;
+mod row_access_policy;
 mod stage;
 mod table;
 mod udf;
 mod view;
+mod workload_group;
 
 pub use account::*;
 pub use catalog::*;
 pub use database::*;
+pub use index::*;
+pub use masking_policy::*;
+pub use password_policy::*;
+pub use row_access_policy::*;
 pub use stage::*;
 pub use table::*;
 pub use udf::*;
 pub use view::*;
+pub use workload_group::*;
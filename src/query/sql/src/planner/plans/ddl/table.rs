@@ -19,7 +19,9 @@ use common_ast::ast::Engine;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::ToDataType;
+use common_datavalues::Vu8;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::TableNameIdent;
 use common_meta_app::schema::UndropTableReq;
@@ -113,7 +115,15 @@ pub struct OptimizeTablePlan {
 
 impl OptimizeTablePlan {
     pub fn schema(&self) -> DataSchemaRef {
-        Arc::new(DataSchema::empty())
+        match self.action {
+            // reports the number and the size of the orphan files, by the kind of them
+            OptimizeTableAction::PurgeOrphans { .. } => DataSchemaRefExt::create(vec![
+                DataField::new("file_type", Vu8::to_data_type()),
+                DataField::new("file_count", u64::to_data_type()),
+                DataField::new("file_size", u64::to_data_type()),
+            ]),
+            _ => Arc::new(DataSchema::empty()),
+        }
     }
 }
 
@@ -121,6 +131,12 @@ impl OptimizeTablePlan {
 pub enum OptimizeTableAction {
     All,
     Purge,
+    /// Files that are not reachable from any retained snapshot, and are older
+    /// than `older_than` (in seconds), are purged. Nothing is removed if `dry_run`.
+    PurgeOrphans {
+        older_than: Option<u64>,
+        dry_run: bool,
+    },
     Statistic,
     CompactBlocks(Option<usize>),
     CompactSegments(Option<usize>),
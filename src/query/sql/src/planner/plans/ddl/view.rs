@@ -61,3 +61,17 @@ impl DropViewPlan {
         Arc::new(DataSchema::empty())
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefreshMaterializedViewPlan {
+    pub tenant: String,
+    pub catalog: String,
+    pub database: String,
+    pub viewname: String,
+}
+
+impl RefreshMaterializedViewPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -36,6 +36,7 @@ use crate::plans::share::RevokeShareObjectPlan;
 use crate::plans::share::ShowGrantTenantsOfSharePlan;
 use crate::plans::share::ShowObjectGrantPrivilegesPlan;
 use crate::plans::share::ShowSharesPlan;
+use crate::plans::AddTableRowAccessPolicyPlan;
 use crate::plans::AlterTableClusterKeyPlan;
 use crate::plans::AlterUDFPlan;
 use crate::plans::AlterUserPlan;
@@ -44,29 +45,41 @@ use crate::plans::AnalyzeTablePlan;
 use crate::plans::CallPlan;
 use crate::plans::CreateCatalogPlan;
 use crate::plans::CreateDatabasePlan;
+use crate::plans::CreateInvertedIndexPlan;
+use crate::plans::CreateMaskingPolicyPlan;
+use crate::plans::CreatePasswordPolicyPlan;
 use crate::plans::CreateRolePlan;
+use crate::plans::CreateRowAccessPolicyPlan;
 use crate::plans::CreateStagePlan;
 use crate::plans::CreateTablePlanV2;
 use crate::plans::CreateUDFPlan;
 use crate::plans::CreateUserPlan;
 use crate::plans::CreateViewPlan;
+use crate::plans::CreateWorkloadGroupPlan;
 use crate::plans::DeletePlan;
 use crate::plans::DescribeTablePlan;
 use crate::plans::DropCatalogPlan;
 use crate::plans::DropDatabasePlan;
+use crate::plans::DropInvertedIndexPlan;
+use crate::plans::DropMaskingPolicyPlan;
+use crate::plans::DropPasswordPolicyPlan;
 use crate::plans::DropRolePlan;
+use crate::plans::DropRowAccessPolicyPlan;
 use crate::plans::DropStagePlan;
 use crate::plans::DropTableClusterKeyPlan;
 use crate::plans::DropTablePlan;
+use crate::plans::DropTableRowAccessPolicyPlan;
 use crate::plans::DropUDFPlan;
 use crate::plans::DropUserPlan;
 use crate::plans::DropViewPlan;
+use crate::plans::DropWorkloadGroupPlan;
 use crate::plans::ExistsTablePlan;
 use crate::plans::GrantPrivilegePlan;
 use crate::plans::GrantRolePlan;
 use crate::plans::KillPlan;
 use crate::plans::ListPlan;
 use crate::plans::OptimizeTablePlan;
+use crate::plans::RefreshMaterializedViewPlan;
 use crate::plans::RemoveStagePlan;
 use crate::plans::RenameDatabasePlan;
 use crate::plans::RenameTablePlan;
@@ -74,6 +87,7 @@ use crate::plans::RevertTablePlan;
 use crate::plans::RevokePrivilegePlan;
 use crate::plans::RevokeRolePlan;
 use crate::plans::SetRolePlan;
+use crate::plans::SetTableColumnMaskingPolicyPlan;
 use crate::plans::SettingPlan;
 use crate::plans::ShowCreateCatalogPlan;
 use crate::plans::ShowCreateDatabasePlan;
@@ -84,6 +98,7 @@ use crate::plans::TruncateTablePlan;
 use crate::plans::UnSettingPlan;
 use crate::plans::UndropDatabasePlan;
 use crate::plans::UndropTablePlan;
+use crate::plans::UnlockUserPlan;
 use crate::plans::UpdatePlan;
 use crate::plans::UseDatabasePlan;
 use crate::BindContext;
@@ -155,17 +170,42 @@ pub enum Plan {
     CreateView(Box<CreateViewPlan>),
     AlterView(Box<AlterViewPlan>),
     DropView(Box<DropViewPlan>),
+    RefreshMaterializedView(Box<RefreshMaterializedViewPlan>),
+
+    // Indexes
+    CreateInvertedIndex(Box<CreateInvertedIndexPlan>),
+    DropInvertedIndex(Box<DropInvertedIndexPlan>),
 
     // Account
     AlterUser(Box<AlterUserPlan>),
     CreateUser(Box<CreateUserPlan>),
     DropUser(Box<DropUserPlan>),
+    UnlockUser(Box<UnlockUserPlan>),
 
     // UDF
     CreateUDF(Box<CreateUDFPlan>),
     AlterUDF(Box<AlterUDFPlan>),
     DropUDF(Box<DropUDFPlan>),
 
+    // Row access policies
+    CreateRowAccessPolicy(Box<CreateRowAccessPolicyPlan>),
+    DropRowAccessPolicy(Box<DropRowAccessPolicyPlan>),
+    AddTableRowAccessPolicy(Box<AddTableRowAccessPolicyPlan>),
+    DropTableRowAccessPolicy(Box<DropTableRowAccessPolicyPlan>),
+
+    // Masking policies
+    CreateMaskingPolicy(Box<CreateMaskingPolicyPlan>),
+    DropMaskingPolicy(Box<DropMaskingPolicyPlan>),
+    SetTableColumnMaskingPolicy(Box<SetTableColumnMaskingPolicyPlan>),
+
+    // Workload groups
+    CreateWorkloadGroup(Box<CreateWorkloadGroupPlan>),
+    DropWorkloadGroup(Box<DropWorkloadGroupPlan>),
+
+    // Password policies
+    CreatePasswordPolicy(Box<CreatePasswordPolicyPlan>),
+    DropPasswordPolicy(Box<DropPasswordPolicyPlan>),
+
     // Role
     ShowRoles(Box<ShowRolesPlan>),
     CreateRole(Box<CreateRolePlan>),
@@ -254,9 +294,13 @@ impl Display for Plan {
             Plan::CreateView(_) => write!(f, "CreateView"),
             Plan::AlterView(_) => write!(f, "AlterView"),
             Plan::DropView(_) => write!(f, "DropView"),
+            Plan::RefreshMaterializedView(_) => write!(f, "RefreshMaterializedView"),
+            Plan::CreateInvertedIndex(_) => write!(f, "CreateInvertedIndex"),
+            Plan::DropInvertedIndex(_) => write!(f, "DropInvertedIndex"),
             Plan::AlterUser(_) => write!(f, "AlterUser"),
             Plan::CreateUser(_) => write!(f, "CreateUser"),
             Plan::DropUser(_) => write!(f, "DropUser"),
+            Plan::UnlockUser(_) => write!(f, "UnlockUser"),
             Plan::CreateRole(_) => write!(f, "CreateRole"),
             Plan::DropRole(_) => write!(f, "DropRole"),
             Plan::ListStage(_) => write!(f, "ListStage"),
@@ -272,6 +316,17 @@ impl Display for Plan {
             Plan::CreateUDF(_) => write!(f, "CreateUDF"),
             Plan::AlterUDF(_) => write!(f, "AlterUDF"),
             Plan::DropUDF(_) => write!(f, "DropUDF"),
+            Plan::CreateRowAccessPolicy(_) => write!(f, "CreateRowAccessPolicy"),
+            Plan::DropRowAccessPolicy(_) => write!(f, "DropRowAccessPolicy"),
+            Plan::AddTableRowAccessPolicy(_) => write!(f, "AddTableRowAccessPolicy"),
+            Plan::DropTableRowAccessPolicy(_) => write!(f, "DropTableRowAccessPolicy"),
+            Plan::CreateMaskingPolicy(_) => write!(f, "CreateMaskingPolicy"),
+            Plan::DropMaskingPolicy(_) => write!(f, "DropMaskingPolicy"),
+            Plan::SetTableColumnMaskingPolicy(_) => write!(f, "SetTableColumnMaskingPolicy"),
+            Plan::CreateWorkloadGroup(_) => write!(f, "CreateWorkloadGroup"),
+            Plan::DropWorkloadGroup(_) => write!(f, "DropWorkloadGroup"),
+            Plan::CreatePasswordPolicy(_) => write!(f, "CreatePasswordPolicy"),
+            Plan::DropPasswordPolicy(_) => write!(f, "DropPasswordPolicy"),
             Plan::Insert(_) => write!(f, "Insert"),
             Plan::Delete(_) => write!(f, "Delete"),
             Plan::Update(_) => write!(f, "Update"),
@@ -336,9 +391,13 @@ impl Plan {
             Plan::CreateView(plan) => plan.schema(),
             Plan::AlterView(plan) => plan.schema(),
             Plan::DropView(plan) => plan.schema(),
+            Plan::RefreshMaterializedView(plan) => plan.schema(),
+            Plan::CreateInvertedIndex(plan) => plan.schema(),
+            Plan::DropInvertedIndex(plan) => plan.schema(),
             Plan::AlterUser(plan) => plan.schema(),
             Plan::CreateUser(plan) => plan.schema(),
             Plan::DropUser(plan) => plan.schema(),
+            Plan::UnlockUser(plan) => plan.schema(),
             Plan::CreateRole(plan) => plan.schema(),
             Plan::DropRole(plan) => plan.schema(),
             Plan::ShowRoles(plan) => plan.schema(),
@@ -354,6 +413,17 @@ impl Plan {
             Plan::CreateUDF(_) => Arc::new(DataSchema::empty()),
             Plan::AlterUDF(_) => Arc::new(DataSchema::empty()),
             Plan::DropUDF(_) => Arc::new(DataSchema::empty()),
+            Plan::CreateRowAccessPolicy(plan) => plan.schema(),
+            Plan::DropRowAccessPolicy(plan) => plan.schema(),
+            Plan::AddTableRowAccessPolicy(plan) => plan.schema(),
+            Plan::DropTableRowAccessPolicy(plan) => plan.schema(),
+            Plan::CreateMaskingPolicy(plan) => plan.schema(),
+            Plan::DropMaskingPolicy(plan) => plan.schema(),
+            Plan::SetTableColumnMaskingPolicy(plan) => plan.schema(),
+            Plan::CreateWorkloadGroup(plan) => plan.schema(),
+            Plan::DropWorkloadGroup(plan) => plan.schema(),
+            Plan::CreatePasswordPolicy(plan) => plan.schema(),
+            Plan::DropPasswordPolicy(plan) => plan.schema(),
             Plan::Insert(plan) => plan.schema(),
             Plan::Delete(_) => Arc::new(DataSchema::empty()),
             Plan::Update(_) => Arc::new(DataSchema::empty()),
@@ -13,7 +13,9 @@
 //  limitations under the License.
 
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::str;
 use std::str::FromStr;
@@ -35,6 +37,10 @@ use common_catalog::table_context::TableContext;
 use common_catalog::table_mutator::TableMutator;
 use common_datablocks::BlockCompactThresholds;
 use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::TypeID;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::DatabaseType;
@@ -48,8 +54,10 @@ use common_storage::FuseCachePolicy;
 use common_storage::ShareTableConfig;
 use common_storage::StorageMetrics;
 use common_storage::StorageMetricsLayer;
+use common_storages_index::InvertedIndexDefinitions;
 use common_storages_table_meta::meta::ClusterKey;
 use common_storages_table_meta::meta::ColumnStatistics as FuseColumnStatistics;
+use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::Statistics as FuseStatistics;
 use common_storages_table_meta::meta::TableSnapshot;
 use common_storages_table_meta::meta::TableSnapshotStatistics;
@@ -74,6 +82,8 @@ use crate::TableStatistics;
 use crate::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
 use crate::DEFAULT_ROW_PER_BLOCK;
 use crate::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
+use crate::FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS;
+use crate::FUSE_OPT_KEY_POINT_INDEX_COLUMNS;
 use crate::FUSE_OPT_KEY_ROW_PER_BLOCK;
 use crate::FUSE_TBL_LAST_SNAPSHOT_HINT;
 
@@ -88,6 +98,9 @@ pub struct FuseTable {
 
     pub(crate) operator: Operator,
     pub(crate) data_metrics: Arc<StorageMetrics>,
+
+    // Segments to be skipped while reading, set by `navigate_to_changes`.
+    pub(crate) base_segments: Option<Arc<HashSet<Location>>>,
 }
 
 impl FuseTable {
@@ -138,6 +151,7 @@ impl FuseTable {
             operator,
             data_metrics,
             storage_format: FuseStorageFormat::from_str(storage_format.as_str())?,
+            base_segments: None,
         }))
     }
 
@@ -262,6 +276,74 @@ impl FuseTable {
     pub fn transient(&self) -> bool {
         self.table_info.meta.options.contains_key("TRANSIENT")
     }
+
+    /// Columns that have inverted indexes created on, postings of them are built for the new blocks.
+    pub fn inverted_index_columns(&self) -> Result<Vec<String>> {
+        let definitions = InvertedIndexDefinitions::from_options(&self.table_info.meta.options)?;
+        Ok(definitions.columns())
+    }
+
+    /// Columns that n-gram filters are configured for by the table option [FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS].
+    pub fn ngram_filter_columns(&self) -> Vec<String> {
+        Self::ngram_filter_columns_of(self.table_info.options())
+    }
+
+    fn ngram_filter_columns_of(options: &BTreeMap<String, String>) -> Vec<String> {
+        options
+            .get(FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS)
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(|column| column.trim().to_owned())
+                    .filter(|column| !column.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks that the columns of the table option [FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS]
+    /// are string columns of the schema.
+    pub fn check_ngram_filter_columns(
+        options: &BTreeMap<String, String>,
+        schema: &DataSchema,
+    ) -> Result<()> {
+        for column in Self::ngram_filter_columns_of(options) {
+            let field = schema.field_with_name(&column).map_err(|_| {
+                ErrorCode::TableOptionInvalid(format!(
+                    "{} refers to unknown column {}",
+                    FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS, column
+                ))
+            })?;
+            if remove_nullable(field.data_type()).data_type_id() != TypeID::String {
+                return Err(ErrorCode::TableOptionInvalid(format!(
+                    "{} only supports string columns, but {} is {}",
+                    FUSE_OPT_KEY_NGRAM_FILTER_COLUMNS,
+                    column,
+                    field.data_type().name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Columns that point-lookup indexes are configured for by the table option [FUSE_OPT_KEY_POINT_INDEX_COLUMNS].
+    pub fn point_index_columns(&self) -> Vec<String> {
+        Self::point_index_columns_of(&self.table_info)
+    }
+
+    pub(crate) fn point_index_columns_of(table_info: &TableInfo) -> Vec<String> {
+        table_info
+            .options()
+            .get(FUSE_OPT_KEY_POINT_INDEX_COLUMNS)
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(|column| column.trim().to_owned())
+                    .filter(|column| !column.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait::async_trait]
@@ -17,14 +17,20 @@ use std::marker::PhantomData;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_storages_table_meta::meta::BlockFilter;
+use common_storages_table_meta::meta::BlockPointIndex;
+use common_storages_table_meta::meta::InvertedIndex;
 use common_storages_table_meta::meta::Location;
+use common_storages_table_meta::meta::PointIndexRunMeta;
 use common_storages_table_meta::meta::SegmentInfo;
+use common_storages_table_meta::meta::SegmentPointIndex;
+use common_storages_table_meta::meta::SnapshotPointIndex;
 use common_storages_table_meta::meta::SnapshotVersion;
 use common_storages_table_meta::meta::TableSnapshotStatisticsVersion;
 use common_storages_table_meta::meta::Versioned;
 use uuid::Uuid;
 
 use crate::constants::FUSE_TBL_BLOCK_PREFIX;
+use crate::constants::FUSE_TBL_POINT_INDEX_PREFIX;
 use crate::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX;
@@ -80,6 +86,79 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    /// The inverted index of a block is kept alongside its bloom filter index, and named after it:
+    ///
+    /// `_i_b_v2/<block id>_v2.parquet` => `_i_b_v2/<block id>_inverted_v1.json`
+    pub fn block_inverted_index_location(bloom_index_location: &str) -> String {
+        let stem = bloom_index_location
+            .rsplit_once("_v")
+            .map_or(bloom_index_location, |(stem, _)| stem);
+        format!("{}_inverted_v{}.json", stem, InvertedIndex::VERSION)
+    }
+
+    /// The point index of a block is kept alongside its bloom filter index, and named after it:
+    ///
+    /// `_i_b_v2/<block id>_v2.parquet` => `_i_b_v2/<block id>_point_v2.bin`
+    pub fn block_point_index_location(bloom_index_location: &str) -> String {
+        let stem = bloom_index_location
+            .rsplit_once("_v")
+            .map_or(bloom_index_location, |(stem, _)| stem);
+        format!("{}_point_v{}.bin", stem, BlockPointIndex::VERSION)
+    }
+
+    /// The point index of a segment is named after the segment:
+    ///
+    /// `_sg/<segment id>_v1.json` => `_i_pk/<segment id>_v2.bin`
+    pub fn segment_point_index_location(segment_location: &str) -> String {
+        let (table_prefix, stem) =
+            Self::split_meta_location(segment_location, FUSE_TBL_SEGMENT_PREFIX);
+        format!(
+            "{}{}/{}_v{}.bin",
+            table_prefix,
+            FUSE_TBL_POINT_INDEX_PREFIX,
+            stem,
+            SegmentPointIndex::VERSION
+        )
+    }
+
+    /// The point index of a snapshot is named after the snapshot:
+    ///
+    /// `_ss/<snapshot id>_v1.json` => `_i_pk/<snapshot id>_snapshot_v2.bin`
+    pub fn snapshot_point_index_location(snapshot_location: &str) -> String {
+        let (table_prefix, stem) =
+            Self::split_meta_location(snapshot_location, FUSE_TBL_SNAPSHOT_PREFIX);
+        format!(
+            "{}{}/{}_snapshot_v{}.bin",
+            table_prefix,
+            FUSE_TBL_POINT_INDEX_PREFIX,
+            stem,
+            SnapshotPointIndex::VERSION
+        )
+    }
+
+    /// The point index run written by the commit of a snapshot is named after the snapshot:
+    ///
+    /// `_ss/<snapshot id>_v1.json` => `_i_pk/<snapshot id>_run_v1.bin`
+    pub fn snapshot_point_index_run_location(snapshot_location: &str) -> String {
+        let (table_prefix, stem) =
+            Self::split_meta_location(snapshot_location, FUSE_TBL_SNAPSHOT_PREFIX);
+        format!(
+            "{}{}/{}_run_v{}.bin",
+            table_prefix,
+            FUSE_TBL_POINT_INDEX_PREFIX,
+            stem,
+            PointIndexRunMeta::VERSION
+        )
+    }
+
+    // `<table prefix>/<dir>/<id>_v1.json` => (`<table prefix>/`, `<id>`)
+    fn split_meta_location<'a>(location: &'a str, dir_name: &str) -> (&'a str, &'a str) {
+        let (dir, file) = location.rsplit_once('/').unwrap_or(("", location));
+        let table_prefix = dir.strip_suffix(dir_name).unwrap_or(dir);
+        let stem = file.rsplit_once("_v").map_or(file, |(stem, _)| stem);
+        (table_prefix, stem)
+    }
+
     pub fn gen_segment_info_location(&self) -> String {
         let segment_uuid = Uuid::new_v4().simple().to_string();
         format!(
@@ -22,6 +22,9 @@ mod write;
 pub use files::Files;
 pub use locations::TableMetaLocationGenerator;
 pub use read::load_bloom_filter_by_columns;
+pub use read::lookup_point_index;
+pub use read::read_point_index;
+pub use read::read_point_index_run;
 pub use read::BlockFilterReader;
 pub use read::BlockReader;
 pub use read::MetaReaders;
@@ -33,8 +36,11 @@ pub use segments::SegmentsIO;
 pub use snapshots::ListSnapshotLiteOption;
 pub use snapshots::SnapshotLiteListExtended;
 pub use snapshots::SnapshotsIO;
+pub use write::encode_point_index;
 pub use write::write_block;
 pub use write::write_data;
 pub use write::write_meta;
+pub use write::write_segment_point_index;
+pub use write::write_snapshot_point_index;
 pub use write::BlockWriter;
 pub use write::SegmentWriter;
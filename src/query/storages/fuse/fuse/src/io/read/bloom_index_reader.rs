@@ -101,6 +101,21 @@ mod util_v1 {
         }
         let row_group = &file_meta.row_groups[0];
 
+        // The optional filters, like the n-gram filters, may be absent from the index
+        // of blocks that were written before they are configured. Skip them.
+        let column_needed = column_needed
+            .iter()
+            .filter(|name| {
+                row_group
+                    .columns()
+                    .iter()
+                    .any(|c| &c.descriptor().path_in_schema[0] == *name)
+            })
+            .collect::<Vec<_>>();
+        if column_needed.is_empty() {
+            return Ok(DataBlock::empty());
+        }
+
         let fields = column_needed
             .iter()
             .map(|name| DataField::new(name, Vu8::to_data_type()))
@@ -15,6 +15,7 @@
 mod block_reader;
 mod bloom_index_reader;
 mod meta_readers;
+mod point_index_reader;
 mod snapshot_history_reader;
 mod versioned_reader;
 
@@ -24,4 +25,7 @@ pub use bloom_index_reader::BlockFilterReader;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoReader;
 pub use meta_readers::TableSnapshotReader;
+pub use point_index_reader::lookup_point_index;
+pub use point_index_reader::read_point_index;
+pub use point_index_reader::read_point_index_run;
 pub use snapshot_history_reader::SnapshotHistoryReader;
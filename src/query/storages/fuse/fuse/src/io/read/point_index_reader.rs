@@ -0,0 +1,189 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_io::prelude::deserialize_from_slice;
+use common_storages_index::PointIndexLookup;
+use common_storages_table_meta::meta::ColumnPointIndex;
+use common_storages_table_meta::meta::PointIndexPage;
+use common_storages_table_meta::meta::PointIndexPageMeta;
+use common_storages_table_meta::meta::PointIndexRun;
+use common_storages_table_meta::meta::PointIndexRunMeta;
+use common_storages_table_meta::meta::SnapshotPointIndex;
+use futures::future;
+use opendal::Operator;
+use serde::de::DeserializeOwned;
+
+/// Reads the whole point index file, returns None if there is no such file.
+pub async fn read_point_index<T: DeserializeOwned>(
+    dal: &Operator,
+    location: &str,
+) -> Result<Option<T>> {
+    match dal.object(location).read().await {
+        Ok(bytes) => Ok(Some(deserialize_from_slice(&mut bytes.as_slice())?)),
+        Err(e) if e.kind() == opendal::ErrorKind::ObjectNotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reads all the pages of the run, which is what the runs are merged from.
+pub async fn read_point_index_run(
+    dal: &Operator,
+    run: &PointIndexRun,
+) -> Result<BTreeMap<String, ColumnPointIndex>> {
+    let bytes = dal.object(&run.location).read().await?;
+    let meta_end = (run.meta_offset + run.meta_len) as usize;
+    let meta: PointIndexRunMeta =
+        deserialize_from_slice(&mut &bytes[run.meta_offset as usize..meta_end])?;
+
+    let mut columns = BTreeMap::new();
+    for (column_name, pages) in meta.columns {
+        let segments = match run.columns.get(&column_name) {
+            Some(column) => column.segments.clone(),
+            None => continue,
+        };
+        let mut keys = vec![];
+        for page in pages {
+            let end = (page.offset + page.len) as usize;
+            let page: PointIndexPage =
+                deserialize_from_slice(&mut &bytes[page.offset as usize..end])?;
+            keys.extend(page);
+        }
+        columns.insert(column_name, ColumnPointIndex { segments, keys });
+    }
+    Ok(columns)
+}
+
+/// Looks up the keys of the columns in the runs of the snapshot point index.
+///
+/// Only the runs whose key range of the column covers some of the keys are read, the
+/// meta of the run first, then the pages whose key fences cover the keys.
+#[tracing::instrument(level = "debug", skip_all)]
+pub async fn lookup_point_index(
+    dal: &Operator,
+    index: &SnapshotPointIndex,
+    keys: &[(String, Vec<DataValue>)],
+) -> Result<PointIndexLookup> {
+    let mut bases = Vec::with_capacity(index.runs.len());
+    let mut segment_count = 0;
+    for run in &index.runs {
+        bases.push(segment_count);
+        segment_count += run.segments.len() as u32;
+    }
+
+    let entries = future::try_join_all(
+        index
+            .runs
+            .iter()
+            .map(|run| lookup_point_index_run(dal, run, keys)),
+    )
+    .await?;
+
+    let mut lookup = PointIndexLookup {
+        segment_count,
+        ..Default::default()
+    };
+    for (column_name, _) in keys {
+        for (run, base) in index.runs.iter().zip(bases.iter()) {
+            if let Some(column) = run.columns.get(column_name) {
+                let (indexed, _) = lookup.columns.entry(column_name.clone()).or_default();
+                indexed.extend(column.segments.iter().map(|segment| base + segment));
+            }
+        }
+    }
+    for (run_entries, base) in entries.into_iter().zip(bases.iter()) {
+        for (column_name, key, blocks) in run_entries {
+            if let Some((_, entries)) = lookup.columns.get_mut(&column_name) {
+                entries.entry(key).or_insert_with(Vec::new).extend(
+                    blocks
+                        .into_iter()
+                        .map(|(segment, block)| (base + segment, block)),
+                );
+            }
+        }
+    }
+    Ok(lookup)
+}
+
+/// Returns the (column name, key, blocks) entries of the keys found in the run.
+async fn lookup_point_index_run(
+    dal: &Operator,
+    run: &PointIndexRun,
+    keys: &[(String, Vec<DataValue>)],
+) -> Result<Vec<(String, DataValue, Vec<(u32, u32)>)>> {
+    let in_range = |column_name: &String, key: &DataValue| match run.columns.get(column_name) {
+        Some(column) => match &column.range {
+            Some((min, max)) => min <= key && key <= max,
+            None => false,
+        },
+        None => false,
+    };
+    if !keys
+        .iter()
+        .any(|(column_name, keys)| keys.iter().any(|key| in_range(column_name, key)))
+    {
+        return Ok(vec![]);
+    }
+
+    let object = dal.object(&run.location);
+    let bytes = object
+        .range_read(run.meta_offset..run.meta_offset + run.meta_len)
+        .await?;
+    let meta: PointIndexRunMeta = deserialize_from_slice(&mut bytes.as_slice())?;
+
+    // the pages to read, with the keys to look up in them
+    let mut pages: BTreeMap<(String, u64), (&PointIndexPageMeta, BTreeSet<&DataValue>)> =
+        BTreeMap::new();
+    for (column_name, keys) in keys {
+        let column_pages = match meta.columns.get(column_name) {
+            Some(column_pages) => column_pages,
+            None => continue,
+        };
+        for key in keys {
+            let pos = column_pages.partition_point(|page| &page.max < key);
+            if let Some(page) = column_pages.get(pos) {
+                if &page.min <= key {
+                    pages
+                        .entry((column_name.clone(), page.offset))
+                        .or_insert_with(|| (page, BTreeSet::new()))
+                        .1
+                        .insert(key);
+                }
+            }
+        }
+    }
+
+    let object = &object;
+    let entries = future::try_join_all(pages.into_iter().map(
+        |((column_name, _), (page, keys))| async move {
+            let bytes = object
+                .range_read(page.offset..page.offset + page.len)
+                .await?;
+            let page: PointIndexPage = deserialize_from_slice(&mut bytes.as_slice())?;
+            let mut entries = Vec::with_capacity(keys.len());
+            for key in keys {
+                if let Ok(pos) = page.binary_search_by(|(k, _)| k.cmp(key)) {
+                    entries.push((column_name.clone(), key.clone(), page[pos].1.clone()));
+                }
+            }
+            Result::Ok(entries)
+        },
+    ))
+    .await?;
+    Ok(entries.into_iter().flatten().collect())
+}
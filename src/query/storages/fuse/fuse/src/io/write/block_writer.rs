@@ -98,7 +98,7 @@ impl<'a> BlockWriter<'a> {
         block: &DataBlock,
         block_id: Uuid,
     ) -> Result<(u64, Location)> {
-        let bloom_index = BlockFilter::try_create(&[block])?;
+        let bloom_index = BlockFilter::try_create(&[block], &[])?;
         let index_block = bloom_index.filter_block;
         let location = self
             .location_generator
@@ -14,10 +14,14 @@
 
 mod block_writer;
 mod meta_writer;
+mod point_index_writer;
 mod segment_writer;
 
 pub use block_writer::write_block;
 pub use block_writer::write_data;
 pub use block_writer::BlockWriter;
 pub use meta_writer::write_meta;
+pub use point_index_writer::encode_point_index;
+pub use point_index_writer::write_segment_point_index;
+pub use point_index_writer::write_snapshot_point_index;
 pub use segment_writer::SegmentWriter;
@@ -0,0 +1,275 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use common_exception::Result;
+use common_io::prelude::serialize_into_buf;
+use common_storages_index::PointIndex;
+use common_storages_table_meta::meta::BlockPointIndex;
+use common_storages_table_meta::meta::ColumnPointIndex;
+use common_storages_table_meta::meta::Location;
+use common_storages_table_meta::meta::PointIndexPageMeta;
+use common_storages_table_meta::meta::PointIndexRun;
+use common_storages_table_meta::meta::PointIndexRunColumn;
+use common_storages_table_meta::meta::PointIndexRunMeta;
+use common_storages_table_meta::meta::SegmentInfo;
+use common_storages_table_meta::meta::SegmentPointIndex;
+use common_storages_table_meta::meta::SnapshotPointIndex;
+use common_storages_table_meta::meta::TableSnapshot;
+use futures::future;
+use opendal::Operator;
+use serde::Serialize;
+
+use crate::io::read_point_index;
+use crate::io::read_point_index_run;
+use crate::io::write_data;
+use crate::io::TableMetaLocationGenerator;
+
+/// Max number of keys of a column in a page of a point index run.
+const POINT_INDEX_PAGE_SIZE: usize = 4096;
+
+/// Max number of runs of a snapshot point index, the runs are merged into one
+/// by the commit that would exceed it.
+const MAX_POINT_INDEX_RUNS: usize = 8;
+
+/// Encodes the point index in the binary format that the point index files are kept in.
+pub fn encode_point_index<T: Serialize>(index: &T) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    serialize_into_buf(&mut buf, index)?;
+    Ok(buf)
+}
+
+/// Builds the point index of the segment from the point indexes of its blocks, and writes it
+/// down next to the segment. Returns the location of the segment point index, if written.
+///
+/// Nothing is written, if any block of the segment has no point index, e.g. the blocks written
+/// before the point index columns were configured. Such segments are not pruned by point index.
+pub async fn write_segment_point_index(
+    dal: &Operator,
+    segment_location: &str,
+    segment: &SegmentInfo,
+    columns: &[String],
+) -> Result<Option<String>> {
+    if columns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut block_index_locations = Vec::with_capacity(segment.blocks.len());
+    for block in &segment.blocks {
+        match &block.bloom_filter_index_location {
+            Some(location) => block_index_locations.push(
+                TableMetaLocationGenerator::block_point_index_location(&location.0),
+            ),
+            None => return Ok(None),
+        }
+    }
+
+    let block_indexes = future::try_join_all(
+        block_index_locations
+            .iter()
+            .map(|location| read_point_index::<BlockPointIndex>(dal, location)),
+    )
+    .await?
+    .into_iter()
+    .collect::<Option<Vec<_>>>();
+
+    match block_indexes {
+        Some(block_indexes) => {
+            let segment_index = PointIndex::build_segment_index(&block_indexes);
+            let location =
+                TableMetaLocationGenerator::segment_point_index_location(segment_location);
+            write_data(&encode_point_index(&segment_index)?, dal, &location).await?;
+            Ok(Some(location))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Writes down the point index of the snapshot next to the snapshot, which lists the runs
+/// of the previous snapshot's index that still index segments of the snapshot, followed by
+/// a new run for the segments that none of them index.
+///
+/// Only the point indexes of the new segments are read, unless there would be too many
+/// runs, then all the runs are merged into the new run. Segments without point index,
+/// e.g. written before the point index columns were configured, are not pruned by it.
+pub async fn write_snapshot_point_index(
+    dal: &Operator,
+    snapshot_location: &str,
+    snapshot: &TableSnapshot,
+    prev_snapshot_location: Option<&str>,
+    columns: &[String],
+) -> Result<()> {
+    if columns.is_empty() {
+        return Ok(());
+    }
+
+    let prev = match prev_snapshot_location {
+        Some(location) => {
+            let location = TableMetaLocationGenerator::snapshot_point_index_location(location);
+            read_point_index::<SnapshotPointIndex>(dal, &location).await?
+        }
+        None => None,
+    };
+
+    let live_segments = snapshot
+        .segments
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<HashSet<_>>();
+    // runs that index none of the segments of the snapshot are dropped
+    let mut runs = prev
+        .map(|prev| prev.runs)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|run| {
+            run.segments
+                .iter()
+                .any(|(path, _)| live_segments.contains(path.as_str()))
+        })
+        .collect::<Vec<_>>();
+    let indexed_segments = runs
+        .iter()
+        .flat_map(|run| run.segments.iter().map(|(path, _)| path.as_str()))
+        .collect::<HashSet<_>>();
+    let new_segments = snapshot
+        .segments
+        .iter()
+        .filter(|(path, _)| !indexed_segments.contains(path.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !new_segments.is_empty() {
+        let run_location =
+            TableMetaLocationGenerator::snapshot_point_index_run_location(snapshot_location);
+        if runs.len() + 1 > MAX_POINT_INDEX_RUNS {
+            let merged = merge_runs(dal, &runs, &snapshot.segments, columns).await?;
+            let run = write_point_index_run(dal, &run_location, snapshot.segments.clone(), &merged)
+                .await?;
+            runs = vec![run];
+        } else {
+            let segment_indexes =
+                read_segment_point_indexes(dal, &new_segments, &HashSet::new()).await?;
+            let columns = PointIndex::build_run(&new_segments, &[], &segment_indexes, columns);
+            let run = write_point_index_run(dal, &run_location, new_segments, &columns).await?;
+            runs.push(run);
+        }
+    }
+
+    let location = TableMetaLocationGenerator::snapshot_point_index_location(snapshot_location);
+    let index = SnapshotPointIndex::new(runs);
+    write_data(&encode_point_index(&index)?, dal, &location).await
+}
+
+/// Merges the runs into the entries of a run which indexes the given `segments`, the
+/// point indexes of the segments that none of the runs index are read.
+async fn merge_runs(
+    dal: &Operator,
+    runs: &[PointIndexRun],
+    segments: &[Location],
+    columns: &[String],
+) -> Result<BTreeMap<String, ColumnPointIndex>> {
+    let run_columns =
+        future::try_join_all(runs.iter().map(|run| read_point_index_run(dal, run))).await?;
+    let indexed_segments = runs
+        .iter()
+        .flat_map(|run| run.segments.iter().map(|(path, _)| path.as_str()))
+        .collect::<HashSet<_>>();
+    let segment_indexes = read_segment_point_indexes(dal, segments, &indexed_segments).await?;
+
+    let runs = runs
+        .iter()
+        .zip(run_columns.iter())
+        .map(|(run, columns)| (run.segments.as_slice(), columns))
+        .collect::<Vec<_>>();
+    Ok(PointIndex::build_run(
+        segments,
+        &runs,
+        &segment_indexes,
+        columns,
+    ))
+}
+
+/// Reads the point indexes of the segments except the `skipped` ones, keyed by the offsets
+/// of the segments. Segments without point index are left out.
+async fn read_segment_point_indexes(
+    dal: &Operator,
+    segments: &[Location],
+    skipped: &HashSet<&str>,
+) -> Result<BTreeMap<u32, SegmentPointIndex>> {
+    let segments = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, (path, _))| !skipped.contains(path.as_str()))
+        .collect::<Vec<_>>();
+    let segment_indexes = future::try_join_all(segments.iter().map(|(_, (path, _))| {
+        let location = TableMetaLocationGenerator::segment_point_index_location(path);
+        async move { read_point_index(dal, &location).await }
+    }))
+    .await?;
+    Ok(segments
+        .iter()
+        .zip(segment_indexes.into_iter())
+        .filter_map(|((offset, _), index)| index.map(|index| (*offset as u32, index)))
+        .collect())
+}
+
+/// Writes down the run file, the pages of each column one after another, followed by
+/// the [PointIndexRunMeta] with the key fences of the pages.
+async fn write_point_index_run(
+    dal: &Operator,
+    location: &str,
+    segments: Vec<Location>,
+    columns: &BTreeMap<String, ColumnPointIndex>,
+) -> Result<PointIndexRun> {
+    let mut data = vec![];
+    let mut pages_meta = BTreeMap::new();
+    let mut run_columns = BTreeMap::new();
+    for (column_name, column) in columns {
+        let mut pages = Vec::with_capacity(column.keys.len() / POINT_INDEX_PAGE_SIZE + 1);
+        for page in column.keys.chunks(POINT_INDEX_PAGE_SIZE) {
+            let offset = data.len() as u64;
+            serialize_into_buf(&mut data, &page)?;
+            pages.push(PointIndexPageMeta {
+                min: page[0].0.clone(),
+                max: page[page.len() - 1].0.clone(),
+                offset,
+                len: data.len() as u64 - offset,
+            });
+        }
+        let range = match (column.keys.first(), column.keys.last()) {
+            (Some((min, _)), Some((max, _))) => Some((min.clone(), max.clone())),
+            _ => None,
+        };
+        run_columns.insert(column_name.clone(), PointIndexRunColumn {
+            segments: column.segments.clone(),
+            range,
+        });
+        pages_meta.insert(column_name.clone(), pages);
+    }
+
+    let meta_offset = data.len() as u64;
+    serialize_into_buf(&mut data, &PointIndexRunMeta::new(pages_meta))?;
+    let meta_len = data.len() as u64 - meta_offset;
+    write_data(&data, dal, location).await?;
+
+    Ok(PointIndexRun {
+        location: location.to_string(),
+        segments,
+        columns: run_columns,
+        meta_offset,
+        meta_len,
+    })
+}
@@ -23,6 +23,7 @@ use common_storages_table_meta::meta::Versioned;
 use opendal::Operator;
 
 use crate::io::write_meta;
+use crate::io::write_segment_point_index;
 use crate::io::TableMetaLocationGenerator;
 
 #[derive(Clone)]
@@ -30,6 +31,7 @@ pub struct SegmentWriter<'a> {
     location_generator: &'a TableMetaLocationGenerator,
     data_accessor: &'a Operator,
     cache: &'a Option<SegmentInfoCache>,
+    point_index_columns: &'a [String],
 }
 
 impl<'a> SegmentWriter<'a> {
@@ -42,9 +44,16 @@ impl<'a> SegmentWriter<'a> {
             location_generator,
             data_accessor,
             cache,
+            point_index_columns: &[],
         }
     }
 
+    /// Also writes down the point indexes of the segments, for the given columns.
+    pub fn with_point_index_columns(mut self, point_index_columns: &'a [String]) -> Self {
+        self.point_index_columns = point_index_columns;
+        self
+    }
+
     pub async fn write_segment(&self, segment: SegmentInfo) -> Result<Location> {
         let location = self.write_segment_no_cache(&segment).await?;
         let segment = Arc::new(segment);
@@ -59,6 +68,13 @@ impl<'a> SegmentWriter<'a> {
         let path = self.location_generator.gen_segment_info_location();
         let location = (path, SegmentInfo::VERSION);
         write_meta(self.data_accessor, location.0.as_str(), segment).await?;
+        write_segment_point_index(
+            self.data_accessor,
+            &location.0,
+            segment,
+            self.point_index_columns,
+        )
+        .await?;
         Ok(location)
     }
 }
@@ -99,6 +99,9 @@ impl FuseTable {
             })?;
         }
 
+        let inverted_index_columns = self.inverted_index_columns()?;
+        let ngram_filter_columns = self.ngram_filter_columns();
+        let point_index_columns = self.point_index_columns();
         if need_output {
             pipeline.add_transform(|transform_input_port, transform_output_port| {
                 FuseTableSink::try_create(
@@ -110,6 +113,9 @@ impl FuseTable {
                     cluster_stats_gen.clone(),
                     block_compact_thresholds,
                     self.storage_format,
+                    inverted_index_columns.clone(),
+                    ngram_filter_columns.clone(),
+                    point_index_columns.clone(),
                     Some(transform_output_port),
                 )
             })?;
@@ -124,6 +130,9 @@ impl FuseTable {
                     cluster_stats_gen.clone(),
                     block_compact_thresholds,
                     self.storage_format,
+                    inverted_index_columns.clone(),
+                    ngram_filter_columns.clone(),
+                    point_index_columns.clone(),
                     None,
                 )
             })?;
@@ -0,0 +1,97 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableStatistics;
+use common_storages_table_meta::meta::Location;
+
+use crate::io::MetaReaders;
+use crate::io::SegmentsIO;
+use crate::io::TableMetaLocationGenerator;
+use crate::statistics::reducers::reduce_statistics;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Returns a read-only instance of the table, which only reads the data
+    /// appended since the snapshot at `base_snapshot_location`.
+    ///
+    /// `None` is returned if the changes can not be told apart by segments, i.e.
+    /// the table has been mutated by operations other than append (delete, compact,
+    /// recluster, truncate...), or the base snapshot has been purged.
+    pub async fn navigate_to_changes(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        base_snapshot_location: &str,
+    ) -> Result<Option<Arc<FuseTable>>> {
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        let reader = MetaReaders::table_snapshot_reader(self.get_operator());
+        let ver = TableMetaLocationGenerator::snapshot_version(base_snapshot_location);
+        let base_snapshot = match reader.read(base_snapshot_location, None, ver).await {
+            Ok(base_snapshot) => base_snapshot,
+            Err(e) if e.code() == ErrorCode::STORAGE_NOT_FOUND => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        // Append only prepends new segments, the segments of base snapshot are kept as they are.
+        let current_segments = snapshot.segments.iter().collect::<HashSet<_>>();
+        if !base_snapshot
+            .segments
+            .iter()
+            .all(|location| current_segments.contains(location))
+        {
+            return Ok(None);
+        }
+
+        let base_segments = base_snapshot
+            .segments
+            .iter()
+            .cloned()
+            .collect::<HashSet<Location>>();
+        let appended_segments = snapshot
+            .segments
+            .iter()
+            .filter(|location| !base_segments.contains(*location))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let segments_io = SegmentsIO::create(ctx, self.operator.clone());
+        let mut summaries = Vec::with_capacity(appended_segments.len());
+        for segment in segments_io.read_segments(&appended_segments).await? {
+            summaries.push(segment?.summary.clone());
+        }
+        let summary = reduce_statistics(&summaries)?;
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta.statistics = TableStatistics {
+            number_of_rows: summary.row_count,
+            data_bytes: summary.uncompressed_byte_size,
+            compressed_data_bytes: summary.compressed_byte_size,
+            index_data_bytes: summary.index_size,
+        };
+
+        let read_only = true;
+        let mut fuse_tbl = FuseTable::do_create(table_info, read_only)?;
+        fuse_tbl.base_segments = Some(Arc::new(base_segments));
+        Ok(Some(fuse_tbl.into()))
+    }
+}
@@ -40,6 +40,8 @@ use common_storages_table_meta::meta::Statistics;
 use common_storages_table_meta::meta::TableSnapshot;
 use common_storages_table_meta::meta::TableSnapshotStatistics;
 use common_storages_table_meta::meta::Versioned;
+use common_storages_table_meta::table::OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION;
 use common_storages_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
 use opendal::Operator;
 use tracing::debug;
@@ -48,6 +50,7 @@ use tracing::warn;
 use uuid::Uuid;
 
 use crate::io::write_meta;
+use crate::io::write_snapshot_point_index;
 use crate::io::SegmentsIO;
 use crate::io::TableMetaLocationGenerator;
 use crate::metrics::metrics_inc_commit_mutation_resolvable_conflict;
@@ -153,6 +156,15 @@ impl FuseTable {
                             );
                             common_base::base::tokio::time::sleep(d).await;
                             latest = tbl.refresh(ctx.as_ref()).await?;
+                            latest = match self.keep_materialized_view_state(latest) {
+                                Ok(latest) => latest,
+                                Err(e) => {
+                                    let _ =
+                                        utils::abort_operations(self.get_operator(), operation_log)
+                                            .await;
+                                    break Err(e);
+                                }
+                            };
                             tbl = FuseTable::try_from_table(latest.as_ref())?;
                             retry_times += 1;
                             continue;
@@ -258,6 +270,45 @@ impl FuseTable {
         .await
     }
 
+    // The source snapshot a materialized view reflects is committed along with the rows
+    // of a refresh, it must not be lost while retrying, otherwise the content and the
+    // recorded source snapshot are out of sync. The rows are computed from the base the
+    // refresh started with, they can't be committed if the view has moved on from it.
+    fn keep_materialized_view_state(&self, latest: Arc<dyn Table>) -> Result<Arc<dyn Table>> {
+        let options = self.table_info.options();
+        let base = match options.get(OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION) {
+            Some(base) => serde_json::from_str::<Option<String>>(base)?,
+            None => return Ok(latest),
+        };
+        let latest_options = latest.get_table_info().options();
+        if latest_options.get(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION) != base.as_ref() {
+            return Err(ErrorCode::TableVersionMismatched(format!(
+                "materialized view {} has been refreshed concurrently",
+                self.table_info.desc
+            )));
+        }
+
+        let mut table_info = latest.get_table_info().clone();
+        for key in [
+            OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION,
+            OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION,
+        ] {
+            match options.get(key) {
+                Some(value) => {
+                    table_info
+                        .meta
+                        .options
+                        .insert(key.to_owned(), value.clone());
+                }
+                None => {
+                    table_info.meta.options.remove(key);
+                }
+            }
+        }
+        let table: Arc<FuseTable> = FuseTable::do_create(table_info, false)?.into();
+        Ok(table)
+    }
+
     fn merge_table_operations(
         schema: &DataSchema,
         previous: Option<Arc<TableSnapshot>>,
@@ -311,7 +362,22 @@ impl FuseTable {
         let need_to_save_statistics =
             snapshot.table_statistics_location.is_some() && table_statistics.is_some();
 
-        // 1. write down snapshot
+        // 1. write down snapshot, and the point index of it
+        let point_index_columns = FuseTable::point_index_columns_of(table_info);
+        if !point_index_columns.is_empty() {
+            let prev_snapshot_location = match snapshot.prev_snapshot_id {
+                Some((id, ver)) => Some(location_generator.snapshot_location_from_uuid(&id, ver)?),
+                None => None,
+            };
+            write_snapshot_point_index(
+                operator,
+                &snapshot_location,
+                &snapshot,
+                prev_snapshot_location.as_deref(),
+                &point_index_columns,
+            )
+            .await?;
+        }
         write_meta(operator, &snapshot_location, &snapshot).await?;
         if need_to_save_statistics {
             write_meta(
@@ -331,6 +397,10 @@ impl FuseTable {
         );
         // remove legacy options
         utils::remove_legacy_options(&mut new_table_meta.options);
+        // the base of a materialized view refresh is only checked while retrying
+        new_table_meta
+            .options
+            .remove(OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION);
 
         // 2.2 setup table statistics
         let stats = &snapshot.summary;
@@ -389,6 +459,17 @@ impl FuseTable {
                         snapshot_location, table_info.desc, table_info.ident
                     );
                     let _ = operator.object(&snapshot_location).delete().await;
+                    if !point_index_columns.is_empty() {
+                        let point_index = TableMetaLocationGenerator::snapshot_point_index_location(
+                            &snapshot_location,
+                        );
+                        let _ = operator.object(&point_index).delete().await;
+                        let point_index_run =
+                            TableMetaLocationGenerator::snapshot_point_index_run_location(
+                                &snapshot_location,
+                            );
+                        let _ = operator.object(&point_index_run).delete().await;
+                    }
                     if need_to_save_statistics {
                         let _ = operator
                             .object(&snapshot.table_statistics_location.unwrap())
@@ -674,9 +755,20 @@ mod utils {
                 let _ = operator.object(block_location).delete().await;
                 if let Some(index) = &block.bloom_filter_index_location {
                     let _ = operator.object(&index.0).delete().await;
+                    // the inverted index may not exist, if there is no inverted index created
+                    let inverted_index =
+                        TableMetaLocationGenerator::block_inverted_index_location(&index.0);
+                    let _ = operator.object(&inverted_index).delete().await;
+                    // so as the point index
+                    let point_index =
+                        TableMetaLocationGenerator::block_point_index_location(&index.0);
+                    let _ = operator.object(&point_index).delete().await;
                 }
             }
             let _ = operator.object(&entry.segment_location).delete().await;
+            let segment_point_index =
+                TableMetaLocationGenerator::segment_point_index_location(&entry.segment_location);
+            let _ = operator.object(&segment_point_index).delete().await;
         }
         Ok(())
     }
@@ -91,7 +91,8 @@ impl FuseTable {
             options,
             self.meta_location_generator().clone(),
             self.operator.clone(),
-        )?;
+        )?
+        .with_point_index_columns(self.point_index_columns());
 
         if !segment_mutator.target_select().await? {
             return Ok(false);
@@ -143,6 +144,9 @@ impl FuseTable {
         let projection = Projection::Columns(all_col_ids);
         let block_reader = self.create_block_reader(projection)?;
 
+        let inverted_index_columns = self.inverted_index_columns()?;
+        let ngram_filter_columns = self.ngram_filter_columns();
+        let point_index_columns = self.point_index_columns();
         pipeline.add_transform(|input, output| {
             CompactTransform::try_create(
                 ctx.clone(),
@@ -153,6 +157,9 @@ impl FuseTable {
                 self.meta_location_generator().clone(),
                 self.operator.clone(),
                 thresholds,
+                inverted_index_columns.clone(),
+                ngram_filter_columns.clone(),
+                point_index_columns.clone(),
             )
         })?;
 
@@ -157,12 +157,14 @@ impl FuseTable {
         });
 
         let segments_location = base_snapshot.segments.clone();
-        let block_metas = BlockPruner::prune(
+        let block_metas = BlockPruner::prune_with_point_index(
             &ctx,
             self.operator.clone(),
             self.table_info.schema(),
             &push_down,
             segments_location,
+            &self.point_index_columns(),
+            self.snapshot_loc().await?,
         )
         .await?;
 
@@ -253,6 +255,7 @@ impl FuseTable {
                     self.meta_location_generator().clone(),
                     base_segments,
                     self.get_block_compact_thresholds(),
+                    self.point_index_columns(),
                 )?;
                 pipeline.pipes.push(Pipe::ResizePipe {
                     inputs_port,
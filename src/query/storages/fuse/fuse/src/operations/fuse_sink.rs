@@ -59,9 +59,10 @@ impl BloomIndexState {
     pub fn try_create(
         block: &DataBlock,
         location: Location,
+        ngram_filter_columns: &[String],
     ) -> Result<(Self, HashMap<usize, usize>)> {
         // write index
-        let bloom_index = BlockFilter::try_create(&[block])?;
+        let bloom_index = BlockFilter::try_create(&[block], ngram_filter_columns)?;
         let index_block = bloom_index.filter_block;
         let mut data = Vec::with_capacity(100 * 1024);
         let index_block_schema = &bloom_index.filter_schema;
@@ -82,6 +83,56 @@ impl BloomIndexState {
     }
 }
 
+pub struct InvertedIndexState {
+    pub(crate) data: Vec<u8>,
+    pub(crate) location: String,
+}
+
+impl InvertedIndexState {
+    /// Returns `None` if there is no inverted index created on the table.
+    pub fn try_create(
+        block: &DataBlock,
+        columns: &[String],
+        bloom_index_location: &Location,
+    ) -> Result<Option<Self>> {
+        if columns.is_empty() {
+            return Ok(None);
+        }
+        let inverted_index = BlockInvertedIndex::try_create(block, columns)?;
+        Ok(Some(Self {
+            data: inverted_index.to_bytes()?,
+            location: TableMetaLocationGenerator::block_inverted_index_location(
+                &bloom_index_location.0,
+            ),
+        }))
+    }
+}
+
+pub struct PointIndexState {
+    pub(crate) data: Vec<u8>,
+    pub(crate) location: String,
+}
+
+impl PointIndexState {
+    /// Returns `None` if there is no point index columns configured for the table.
+    pub fn try_create(
+        block: &DataBlock,
+        columns: &[String],
+        bloom_index_location: &Location,
+    ) -> Result<Option<Self>> {
+        if columns.is_empty() {
+            return Ok(None);
+        }
+        let point_index = PointIndex::build_block_index(block, columns)?;
+        Ok(Some(Self {
+            data: io::encode_point_index(&point_index)?,
+            location: TableMetaLocationGenerator::block_point_index_location(
+                &bloom_index_location.0,
+            ),
+        }))
+    }
+}
+
 enum State {
     None,
     NeedSerialize(DataBlock),
@@ -91,6 +142,8 @@ enum State {
         meta_data: HashMap<ColumnId, ColumnMeta>,
         block_statistics: BlockStatistics,
         bloom_index_state: BloomIndexState,
+        inverted_index_state: Option<InvertedIndexState>,
+        point_index_state: Option<PointIndexState>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -114,6 +167,9 @@ pub struct FuseTableSink {
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
     cluster_stats_gen: ClusterStatsGenerator,
+    inverted_index_columns: Vec<String>,
+    ngram_filter_columns: Vec<String>,
+    point_index_columns: Vec<String>,
 
     storage_format: FuseStorageFormat,
     // A dummy output port for distributed insert select to connect Exchange Sink.
@@ -131,6 +187,9 @@ impl FuseTableSink {
         cluster_stats_gen: ClusterStatsGenerator,
         thresholds: BlockCompactThresholds,
         storage_format: FuseStorageFormat,
+        inverted_index_columns: Vec<String>,
+        ngram_filter_columns: Vec<String>,
+        point_index_columns: Vec<String>,
         output: Option<Arc<OutputPort>>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
@@ -142,6 +201,9 @@ impl FuseTableSink {
             accumulator: StatisticsAccumulator::new(thresholds),
             num_block_threshold: num_block_threshold as u64,
             cluster_stats_gen,
+            inverted_index_columns,
+            ngram_filter_columns,
+            point_index_columns,
             storage_format,
             output,
         })))
@@ -204,7 +266,17 @@ impl Processor for FuseTableSink {
 
                 let location = self.meta_locations.block_bloom_index_location(&block_id);
                 let (bloom_index_state, column_distinct_count) =
-                    BloomIndexState::try_create(&block, location)?;
+                    BloomIndexState::try_create(&block, location, &self.ngram_filter_columns)?;
+                let inverted_index_state = InvertedIndexState::try_create(
+                    &block,
+                    &self.inverted_index_columns,
+                    &bloom_index_state.location,
+                )?;
+                let point_index_state = PointIndexState::try_create(
+                    &block,
+                    &self.point_index_columns,
+                    &bloom_index_state.location,
+                )?;
 
                 let block_statistics = BlockStatistics::from(
                     &block,
@@ -222,6 +294,8 @@ impl Processor for FuseTableSink {
                     block_statistics,
                     meta_data,
                     bloom_index_state,
+                    inverted_index_state,
+                    point_index_state,
                 };
             }
             State::GenerateSegment => {
@@ -271,6 +345,8 @@ impl Processor for FuseTableSink {
                 meta_data,
                 block_statistics,
                 bloom_index_state,
+                inverted_index_state,
+                point_index_state,
             } => {
                 // write data block
                 io::write_data(
@@ -288,6 +364,26 @@ impl Processor for FuseTableSink {
                 )
                 .await?;
 
+                // write inverted index
+                if let Some(inverted_index_state) = inverted_index_state {
+                    io::write_data(
+                        &inverted_index_state.data,
+                        &self.data_accessor,
+                        &inverted_index_state.location,
+                    )
+                    .await?;
+                }
+
+                // write point index
+                if let Some(point_index_state) = point_index_state {
+                    io::write_data(
+                        &point_index_state.data,
+                        &self.data_accessor,
+                        &point_index_state.location,
+                    )
+                    .await?;
+                }
+
                 let bloom_filter_index_size = bloom_index_state.size;
                 self.accumulator.add_block(
                     size,
@@ -308,6 +404,15 @@ impl Processor for FuseTableSink {
             } => {
                 self.data_accessor.object(&location).write(data).await?;
 
+                // the point indexes of the blocks have been written, build the segment's from them
+                io::write_segment_point_index(
+                    &self.data_accessor,
+                    &location,
+                    &segment,
+                    &self.point_index_columns,
+                )
+                .await?;
+
                 self.state = State::PreCommitSegment { location, segment };
             }
             _state => {
@@ -19,6 +19,7 @@ use std::time::Instant;
 
 use chrono::DateTime;
 use chrono::Duration;
+use chrono::TimeZone;
 use chrono::Utc;
 use common_cache::Cache;
 use common_catalog::table_context::TableContext;
@@ -27,15 +28,35 @@ use common_exception::Result;
 use common_storages_table_meta::caches::CacheManager;
 use common_storages_table_meta::meta::Location;
 use common_storages_table_meta::meta::SnapshotId;
+use common_storages_table_meta::meta::SnapshotPointIndex;
 use common_storages_table_meta::meta::TableSnapshotLite;
+use futures_util::TryStreamExt;
+use opendal::ObjectMode;
 use tracing::info;
 use tracing::warn;
 
+use crate::io::read_point_index;
+use crate::io::try_join_futures;
 use crate::io::Files;
 use crate::io::ListSnapshotLiteOption;
+use crate::io::MetaReaders;
 use crate::io::SegmentsIO;
 use crate::io::SnapshotsIO;
+use crate::io::TableMetaLocationGenerator;
 use crate::FuseTable;
+use crate::FUSE_TBL_BLOCK_PREFIX;
+use crate::FUSE_TBL_POINT_INDEX_PREFIX;
+use crate::FUSE_TBL_SEGMENT_PREFIX;
+use crate::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::FUSE_TBL_XOR_BLOOM_INDEX_PREFIX;
+
+/// The orphan files of one kind, found by [FuseTable::do_purge_orphans].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrphanFiles {
+    pub file_type: &'static str,
+    pub file_count: u64,
+    pub file_size: u64,
+}
 
 #[derive(Default)]
 struct LocationTuple {
@@ -210,6 +231,11 @@ impl FuseTable {
             let mut status_bloom_to_be_purged_count = 0;
             let mut status_segment_to_be_purged_count = 0;
 
+            // inverted indexes are kept alongside the bloom indexes, and purged with them
+            let has_inverted_index = !self.inverted_index_columns()?.is_empty();
+            // point indexes are kept alongside the bloom indexes and segments, and purged with them
+            let has_point_index = !self.point_index_columns().is_empty();
+
             let start = Instant::now();
             let segment_locations = Vec::from_iter(segments_to_be_purged);
             for chunk in segment_locations.chunks(chunk_size) {
@@ -241,6 +267,16 @@ impl FuseTable {
                             continue;
                         }
                         bloom_locations_to_be_pruged.insert(loc.to_string());
+                        if has_inverted_index {
+                            bloom_locations_to_be_pruged.insert(
+                                TableMetaLocationGenerator::block_inverted_index_location(loc),
+                            );
+                        }
+                        if has_point_index {
+                            bloom_locations_to_be_pruged.insert(
+                                TableMetaLocationGenerator::block_point_index_location(loc),
+                            );
+                        }
                     }
                     status_bloom_to_be_purged_count += bloom_locations_to_be_pruged.len();
                     self.try_purge_location_files(ctx.clone(), bloom_locations_to_be_pruged)
@@ -249,12 +285,17 @@ impl FuseTable {
 
                 // 3. Try to purge segment file chunks.
                 {
-                    let segment_locations_to_be_purged = HashSet::from_iter(
+                    let mut segment_locations_to_be_purged = HashSet::from_iter(
                         chunk
                             .iter()
                             .map(|loc| loc.0.clone())
                             .collect::<Vec<String>>(),
                     );
+                    if has_point_index {
+                        segment_locations_to_be_purged.extend(chunk.iter().map(|loc| {
+                            TableMetaLocationGenerator::segment_point_index_location(&loc.0)
+                        }));
+                    }
                     self.try_purge_location_files(ctx.clone(), segment_locations_to_be_purged)
                         .await?;
                 }
@@ -282,6 +323,23 @@ impl FuseTable {
             let mut status_purged_count = 0;
 
             let location_gen = self.meta_location_generator();
+            // point indexes of snapshots are named after them, and purged with them, except
+            // the runs that the point index of the root snapshot still lists
+            let has_point_index = !self.point_index_columns().is_empty();
+            let mut runs_referenced_by_root = HashSet::new();
+            if has_point_index && keep_last_snapshot {
+                if let Some(root_snapshot_location) = self.snapshot_loc().await? {
+                    let location = TableMetaLocationGenerator::snapshot_point_index_location(
+                        &root_snapshot_location,
+                    );
+                    if let Some(index) =
+                        read_point_index::<SnapshotPointIndex>(&self.operator, &location).await?
+                    {
+                        runs_referenced_by_root
+                            .extend(index.runs.into_iter().map(|run| run.location));
+                    }
+                }
+            }
             let snapshots_to_be_purged_vec = Vec::from_iter(
                 snapshots_to_be_purged.into_iter().chain(
                     orphan_snapshots
@@ -298,6 +356,16 @@ impl FuseTable {
                 let mut snapshot_locations_to_be_purged = HashSet::new();
                 for (id, ver) in chunk {
                     if let Ok(loc) = location_gen.snapshot_location_from_uuid(id, *ver) {
+                        if has_point_index {
+                            snapshot_locations_to_be_purged.insert(
+                                TableMetaLocationGenerator::snapshot_point_index_location(&loc),
+                            );
+                            let run =
+                                TableMetaLocationGenerator::snapshot_point_index_run_location(&loc);
+                            if !runs_referenced_by_root.contains(&run) {
+                                snapshot_locations_to_be_purged.insert(run);
+                            }
+                        }
                         snapshot_locations_to_be_purged.insert(loc);
                     }
                 }
@@ -349,6 +417,231 @@ impl FuseTable {
         Ok(())
     }
 
+    /// Purges the files under the table prefix, which are not reachable from any retained
+    /// snapshot, i.e. the files left behind by aborted inserts, failed compactions or crashed nodes.
+    ///
+    /// The retained snapshots are those chained from the current snapshot, and those modified
+    /// within `older_than`(or the `retention_period` setting if not specified), since they may
+    /// belong to ongoing transactions. Only the files modified before `older_than` are purged.
+    ///
+    /// If `dry_run` is true, the orphan files are only counted, nothing is purged.
+    pub async fn do_purge_orphans(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        older_than: Option<Duration>,
+        dry_run: bool,
+    ) -> Result<Vec<OrphanFiles>> {
+        let older_than = match older_than {
+            Some(v) => v,
+            None => Duration::hours(ctx.get_settings().get_retention_period()? as i64),
+        };
+        let purge_before = Utc::now() - older_than;
+        let prefix = self.meta_location_generator.prefix();
+
+        // 1. Find the retained snapshots.
+        let snapshot_files = self
+            .list_files_with_meta(&format!("{}/{}/", prefix, FUSE_TBL_SNAPSHOT_PREFIX))
+            .await?;
+        let mut snapshots = HashMap::with_capacity(snapshot_files.len());
+        {
+            let operator = self.operator.clone();
+            let tasks = snapshot_files.iter().map(|(location, _, _)| {
+                let reader = MetaReaders::table_snapshot_reader(operator.clone());
+                let location = location.clone();
+                async move {
+                    let ver = TableMetaLocationGenerator::snapshot_version(location.as_str());
+                    let snapshot = reader.read(location.as_str(), None, ver).await?;
+                    Ok::<_, ErrorCode>((location, snapshot))
+                }
+            });
+            let results =
+                try_join_futures(ctx.clone(), tasks, "fuse-purge-orphans-worker".to_owned())
+                    .await?;
+            for result in results {
+                match result {
+                    Ok((location, snapshot)) => {
+                        snapshots.insert(location, snapshot);
+                    }
+                    // concurrent gc: someone else has already collected this snapshot, ignore it
+                    Err(e) if e.code() == ErrorCode::STORAGE_NOT_FOUND => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        // the current snapshot may not be listed, e.g. the legacy snapshot location
+        let current_snapshot_location = self.snapshot_loc().await?;
+        if let Some(location) = &current_snapshot_location {
+            if !snapshots.contains_key(location) {
+                if let Some(snapshot) = self.read_table_snapshot().await? {
+                    snapshots.insert(location.clone(), snapshot);
+                }
+            }
+        }
+
+        let mut retained_snapshots = HashSet::new();
+        let mut snapshot_location = current_snapshot_location;
+        while let Some(location) = snapshot_location.take() {
+            if let Some(snapshot) = snapshots.get(&location) {
+                snapshot_location = snapshot.prev_snapshot_id.and_then(|(id, ver)| {
+                    self.meta_location_generator
+                        .snapshot_location_from_uuid(&id, ver)
+                        .ok()
+                });
+                if !retained_snapshots.insert(location) {
+                    break;
+                }
+            }
+        }
+        for (location, modified, _) in &snapshot_files {
+            if !Self::is_orphan_candidate(modified, &purge_before) {
+                retained_snapshots.insert(location.clone());
+            }
+        }
+
+        // 2. Find the files reachable from the retained snapshots.
+        let mut reachable_segments = HashSet::new();
+        for location in &retained_snapshots {
+            if let Some(snapshot) = snapshots.get(location) {
+                reachable_segments.extend(snapshot.segments.iter().cloned());
+            }
+        }
+        let reachable_segments = Vec::from_iter(reachable_segments);
+        let locations = self
+            .get_block_locations(ctx.clone(), &reachable_segments)
+            .await?;
+        let mut reachable_indexes = HashSet::new();
+        for bloom_location in locations.bloom_location {
+            // the inverted index and point index of block are kept alongside the bloom filter
+            reachable_indexes.insert(TableMetaLocationGenerator::block_inverted_index_location(
+                &bloom_location,
+            ));
+            reachable_indexes.insert(TableMetaLocationGenerator::block_point_index_location(
+                &bloom_location,
+            ));
+            reachable_indexes.insert(bloom_location);
+        }
+        let mut reachable_point_indexes = reachable_segments
+            .iter()
+            .map(|(location, _)| TableMetaLocationGenerator::segment_point_index_location(location))
+            .chain(retained_snapshots.iter().map(|location| {
+                TableMetaLocationGenerator::snapshot_point_index_location(location)
+            }))
+            .collect::<HashSet<_>>();
+        // the runs of the point index of a snapshot may be written by the earlier snapshots
+        let snapshot_point_indexes = try_join_futures(
+            ctx.clone(),
+            retained_snapshots
+                .iter()
+                .map(|location| {
+                    let operator = self.operator.clone();
+                    let location =
+                        TableMetaLocationGenerator::snapshot_point_index_location(location);
+                    async move {
+                        read_point_index::<SnapshotPointIndex>(&operator, &location).await
+                    }
+                })
+                .collect::<Vec<_>>(),
+            "fuse-purge-orphans-worker".to_owned(),
+        )
+        .await?;
+        for index in snapshot_point_indexes {
+            if let Some(index) = index? {
+                reachable_point_indexes.extend(index.runs.into_iter().map(|run| run.location));
+            }
+        }
+        let reachable_segments = reachable_segments
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect::<HashSet<_>>();
+
+        // 3. Purge the files that are not reachable, and old enough.
+        let chunk_size = ctx.get_settings().get_max_storage_io_requests()? as usize;
+        let kinds = [
+            ("snapshot", FUSE_TBL_SNAPSHOT_PREFIX, &retained_snapshots),
+            ("segment", FUSE_TBL_SEGMENT_PREFIX, &reachable_segments),
+            ("block", FUSE_TBL_BLOCK_PREFIX, &locations.block_location),
+            ("index", FUSE_TBL_XOR_BLOOM_INDEX_PREFIX, &reachable_indexes),
+            (
+                "point_index",
+                FUSE_TBL_POINT_INDEX_PREFIX,
+                &reachable_point_indexes,
+            ),
+        ];
+        let mut result = Vec::with_capacity(kinds.len());
+        for (file_type, dir, reachable) in kinds {
+            let files = if dir == FUSE_TBL_SNAPSHOT_PREFIX {
+                snapshot_files.clone()
+            } else {
+                self.list_files_with_meta(&format!("{}/{}/", prefix, dir))
+                    .await?
+            };
+            let orphans = files
+                .into_iter()
+                .filter(|(location, modified, _)| {
+                    !reachable.contains(location)
+                        && Self::is_orphan_candidate(modified, &purge_before)
+                })
+                .collect::<Vec<_>>();
+
+            let orphan_files = OrphanFiles {
+                file_type,
+                file_count: orphans.len() as u64,
+                file_size: orphans.iter().map(|(_, _, size)| size).sum(),
+            };
+            let status = format!(
+                "purge orphans: {} {} files found, {} bytes, dry run: {}",
+                orphan_files.file_count, file_type, orphan_files.file_size, dry_run
+            );
+            self.data_metrics.set_status(&status);
+            info!(status);
+
+            if !dry_run {
+                for chunk in orphans.chunks(chunk_size) {
+                    let orphan_locations = chunk
+                        .iter()
+                        .map(|(location, _, _)| location.clone())
+                        .collect();
+                    self.try_purge_location_files(ctx.clone(), orphan_locations)
+                        .await?;
+                }
+            }
+            result.push(orphan_files);
+        }
+
+        Ok(result)
+    }
+
+    // The files of unknown modification time are never purged.
+    fn is_orphan_candidate(modified: &Option<DateTime<Utc>>, purge_before: &DateTime<Utc>) -> bool {
+        matches!(modified, Some(modified) if modified < purge_before)
+    }
+
+    // List the files under the given directory, with the last modified time and size of them.
+    async fn list_files_with_meta(
+        &self,
+        dir: &str,
+    ) -> Result<Vec<(String, Option<DateTime<Utc>>, u64)>> {
+        let mut files = vec![];
+        let mut ds = match self.operator.object(dir).list().await {
+            Ok(ds) => ds,
+            Err(e) if e.kind() == opendal::ErrorKind::ObjectNotFound => return Ok(files),
+            Err(e) => return Err(e.into()),
+        };
+        while let Some(de) = ds.try_next().await? {
+            if !matches!(de.mode().await?, ObjectMode::FILE) {
+                continue;
+            }
+            let modified = de
+                .last_modified()
+                .await?
+                .map(|t| Utc.timestamp(t.unix_timestamp(), 0));
+            let size = de.content_length().await?;
+            files.push((de.path().to_string(), modified, size));
+        }
+        Ok(files)
+    }
+
     // Partition snapshot_lites into two parts
     // - those are beyond retention period
     // - those are within retention period
@@ -0,0 +1,76 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::plan::Projection;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use tracing::info;
+
+use crate::io::write_data;
+use crate::io::SegmentsIO;
+use crate::operations::InvertedIndexState;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Builds the inverted indexes of the blocks in the current snapshot.
+    ///
+    /// New blocks are indexed as they are written, this is for the blocks written
+    /// before an inverted index is created. The index files of the blocks are
+    /// overwritten with the postings of all the indexed columns.
+    pub async fn build_inverted_indexes(&self, ctx: &Arc<dyn TableContext>) -> Result<()> {
+        let columns = self.inverted_index_columns()?;
+        if columns.is_empty() {
+            return Ok(());
+        }
+        let snapshot = match self.read_table_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let schema = self.table_info.schema();
+        let col_ids = columns
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        let block_reader = self.create_block_reader(Projection::Columns(col_ids))?;
+
+        let segments_io = SegmentsIO::create(ctx.clone(), self.operator.clone());
+        let segments = segments_io.read_segments(&snapshot.segments).await?;
+        let mut num_blocks = 0;
+        for segment in segments {
+            let segment = segment?;
+            for block_meta in segment.blocks.iter() {
+                let bloom_index_location = match &block_meta.bloom_filter_index_location {
+                    Some(location) => location,
+                    None => continue,
+                };
+                let block = block_reader.read_with_block_meta(block_meta).await?;
+                if let Some(state) =
+                    InvertedIndexState::try_create(&block, &columns, bloom_index_location)?
+                {
+                    write_data(&state.data, &self.operator, &state.location).await?;
+                    num_blocks += 1;
+                }
+            }
+        }
+
+        info!(
+            "built inverted indexes of {} blocks for table {}",
+            num_blocks, self.table_info.desc
+        );
+        Ok(())
+    }
+}
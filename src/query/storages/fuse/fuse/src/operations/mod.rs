@@ -14,11 +14,13 @@
 
 mod analyze;
 mod append;
+mod changes;
 mod commit;
 mod compact;
 mod delete;
 mod fuse_sink;
 mod gc;
+mod inverted_index;
 mod mutation;
 mod navigate;
 mod operation_log;
@@ -36,7 +38,10 @@ pub mod util;
 pub use compact::CompactOptions;
 pub use fuse_sink::BloomIndexState;
 pub use fuse_sink::FuseTableSink;
+pub use fuse_sink::InvertedIndexState;
+pub use fuse_sink::PointIndexState;
 pub use fuse_source::FuseTableSource;
+pub use gc::OrphanFiles;
 pub use mutation::ReclusterMutator;
 pub use mutation::SegmentCompactMutator;
 pub use mutation::SegmentCompactionState;
@@ -26,6 +26,8 @@ pub struct AbortOperation {
     pub segments: Vec<String>,
     pub blocks: Vec<String>,
     pub bloom_filter_indexes: Vec<String>,
+    pub inverted_indexes: Vec<String>,
+    pub point_indexes: Vec<String>,
 }
 
 impl AbortOperation {
@@ -34,6 +36,8 @@ impl AbortOperation {
         self.blocks.extend(rhs.blocks.clone());
         self.bloom_filter_indexes
             .extend(rhs.bloom_filter_indexes.clone());
+        self.inverted_indexes.extend(rhs.inverted_indexes.clone());
+        self.point_indexes.extend(rhs.point_indexes.clone());
     }
 
     pub fn add_block(&mut self, block: &BlockMeta) {
@@ -44,6 +48,14 @@ impl AbortOperation {
         }
     }
 
+    pub fn add_inverted_index(&mut self, location: String) {
+        self.inverted_indexes.push(location);
+    }
+
+    pub fn add_point_index(&mut self, location: String) {
+        self.point_indexes.push(location);
+    }
+
     pub fn add_segment(&mut self, segment: String) {
         self.segments.push(segment);
     }
@@ -54,6 +66,8 @@ impl AbortOperation {
             .blocks
             .into_iter()
             .chain(self.bloom_filter_indexes.into_iter())
+            .chain(self.inverted_indexes.into_iter())
+            .chain(self.point_indexes.into_iter())
             .chain(self.segments.into_iter());
         fuse_file.remove_file_in_batch(locations).await
     }
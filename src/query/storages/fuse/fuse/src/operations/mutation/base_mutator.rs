@@ -51,6 +51,7 @@ pub struct BaseMutator {
     pub(crate) data_accessor: Operator,
     pub(crate) base_snapshot: Arc<TableSnapshot>,
     pub(crate) thresholds: BlockCompactThresholds,
+    pub(crate) point_index_columns: Vec<String>,
 }
 
 impl BaseMutator {
@@ -68,6 +69,7 @@ impl BaseMutator {
             data_accessor: op,
             base_snapshot,
             thresholds,
+            point_index_columns: vec![],
         })
     }
 
@@ -99,7 +101,8 @@ impl BaseMutator {
             &self.data_accessor,
             &self.location_generator,
             &segment_info_cache,
-        );
+        )
+        .with_point_index_columns(&self.point_index_columns);
 
         // apply mutations
         for (seg_idx, replacements) in self.mutations.clone() {
@@ -155,6 +158,13 @@ impl BaseMutator {
                 // write down new segment
                 let new_segment_location = seg_writer.write_segment(new_segment).await?;
                 segments_editor.insert(seg_idx, new_segment_location.clone());
+                if !self.point_index_columns.is_empty() {
+                    abort_operation.add_point_index(
+                        TableMetaLocationGenerator::segment_point_index_location(
+                            &new_segment_location.0,
+                        ),
+                    );
+                }
                 abort_operation.add_segment(new_segment_location.0);
             }
         }
@@ -38,10 +38,13 @@ use super::compact_meta::CompactSourceMeta;
 use super::compact_part::CompactTask;
 use super::CompactSinkMeta;
 use crate::io::write_data;
+use crate::io::write_segment_point_index;
 use crate::io::BlockReader;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::mutation::AbortOperation;
 use crate::operations::util;
+use crate::operations::InvertedIndexState;
+use crate::operations::PointIndexState;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
@@ -55,6 +58,8 @@ struct SerializeState {
     block_location: String,
     index_data: Vec<u8>,
     index_location: String,
+    inverted_index_state: Option<InvertedIndexState>,
+    point_index_state: Option<PointIndexState>,
 }
 
 enum State {
@@ -97,6 +102,9 @@ pub struct CompactTransform {
     block_metas: Vec<Arc<BlockMeta>>,
     order: usize,
     thresholds: BlockCompactThresholds,
+    inverted_index_columns: Vec<String>,
+    ngram_filter_columns: Vec<String>,
+    point_index_columns: Vec<String>,
     abort_operation: AbortOperation,
 }
 
@@ -111,6 +119,9 @@ impl CompactTransform {
         location_gen: TableMetaLocationGenerator,
         dal: Operator,
         thresholds: BlockCompactThresholds,
+        inverted_index_columns: Vec<String>,
+        ngram_filter_columns: Vec<String>,
+        point_index_columns: Vec<String>,
     ) -> Result<ProcessorPtr> {
         let settings = ctx.get_settings();
         let max_memory_usage = (settings.get_max_memory_usage()? as f64 * 0.8) as u64;
@@ -132,6 +143,9 @@ impl CompactTransform {
             block_metas: Vec::new(),
             order: 0,
             thresholds,
+            inverted_index_columns,
+            ngram_filter_columns,
+            point_index_columns,
             abort_operation: AbortOperation::default(),
         })))
     }
@@ -227,7 +241,8 @@ impl Processor for CompactTransform {
                     // build block index.
                     let (index_data, index_size, index_location) = {
                         // write index
-                        let bloom_index = BlockFilter::try_create(&[&new_block])?;
+                        let bloom_index =
+                            BlockFilter::try_create(&[&new_block], &self.ngram_filter_columns)?;
                         let index_block = bloom_index.filter_block;
                         let location = self.location_gen.block_bloom_index_location(&block_id);
                         let mut data = Vec::with_capacity(100 * 1024);
@@ -240,6 +255,16 @@ impl Processor for CompactTransform {
                         )?;
                         (data, size, location)
                     };
+                    let inverted_index_state = InvertedIndexState::try_create(
+                        &new_block,
+                        &self.inverted_index_columns,
+                        &index_location,
+                    )?;
+                    let point_index_state = PointIndexState::try_create(
+                        &new_block,
+                        &self.point_index_columns,
+                        &index_location,
+                    )?;
 
                     // serialize data block.
                     let mut block_data = Vec::with_capacity(100 * 1024 * 1024);
@@ -261,6 +286,14 @@ impl Processor for CompactTransform {
                         index_size,
                     );
                     self.abort_operation.add_block(&new_meta);
+                    if let Some(inverted_index_state) = &inverted_index_state {
+                        self.abort_operation
+                            .add_inverted_index(inverted_index_state.location.clone());
+                    }
+                    if let Some(point_index_state) = &point_index_state {
+                        self.abort_operation
+                            .add_point_index(point_index_state.location.clone());
+                    }
                     self.block_metas.push(Arc::new(new_meta));
 
                     serialize_states.push(SerializeState {
@@ -268,6 +301,8 @@ impl Processor for CompactTransform {
                         block_location: block_location.0,
                         index_data,
                         index_location: index_location.0,
+                        inverted_index_state,
+                        point_index_state,
                     });
                 }
                 self.state = State::SerializedBlocks(serialize_states);
@@ -370,7 +405,22 @@ impl Processor for CompactTransform {
                         // write block data.
                         write_data(&state.block_data, dal, &state.block_location).await?;
                         // write index data.
-                        write_data(&state.index_data, dal, &state.index_location).await
+                        write_data(&state.index_data, dal, &state.index_location).await?;
+                        // write inverted index data.
+                        if let Some(inverted_index_state) = &state.inverted_index_state {
+                            write_data(
+                                &inverted_index_state.data,
+                                dal,
+                                &inverted_index_state.location,
+                            )
+                            .await?;
+                        }
+                        // write point index data.
+                        if let Some(point_index_state) = &state.point_index_state {
+                            write_data(&point_index_state.data, dal, &point_index_state.location)
+                                .await?;
+                        }
+                        Ok::<_, ErrorCode>(())
                     });
                 }
                 futures::future::try_join_all(handles).await?;
@@ -386,6 +436,16 @@ impl Processor for CompactTransform {
                 segment,
             } => {
                 self.dal.object(&location).write(data).await?;
+                if let Some(point_index_location) = write_segment_point_index(
+                    &self.dal,
+                    &location,
+                    &segment,
+                    &self.point_index_columns,
+                )
+                .await?
+                {
+                    self.abort_operation.add_point_index(point_index_location);
+                }
                 self.state = State::Output { location, segment };
             }
             _ => return Err(ErrorCode::Internal("It's a bug.")),
@@ -52,6 +52,7 @@ pub struct SegmentCompactMutator {
     data_accessor: Operator,
     location_generator: TableMetaLocationGenerator,
     compaction: SegmentCompactionState,
+    point_index_columns: Vec<String>,
 }
 
 impl SegmentCompactMutator {
@@ -67,9 +68,16 @@ impl SegmentCompactMutator {
             data_accessor: operator,
             location_generator,
             compaction: Default::default(),
+            point_index_columns: vec![],
         })
     }
 
+    /// Builds the point indexes of the compacted segments, for the given columns.
+    pub fn with_point_index_columns(mut self, point_index_columns: Vec<String>) -> Self {
+        self.point_index_columns = point_index_columns;
+        self
+    }
+
     fn has_compaction(&self) -> bool {
         !self.compaction.new_segment_paths.is_empty()
     }
@@ -104,7 +112,8 @@ impl TableMutator for SegmentCompactMutator {
             &self.data_accessor,
             &self.location_generator,
             &segment_info_cache,
-        );
+        )
+        .with_point_index_columns(&self.point_index_columns);
 
         let compactor =
             SegmentCompactor::new(self.compact_params.block_per_seg as u64, segment_writer);
@@ -127,8 +136,18 @@ impl TableMutator for SegmentCompactMutator {
             return Ok(());
         }
 
+        let point_indexes = if self.point_index_columns.is_empty() {
+            vec![]
+        } else {
+            self.compaction
+                .new_segment_paths
+                .iter()
+                .map(|path| TableMetaLocationGenerator::segment_point_index_location(path))
+                .collect()
+        };
         let abort_action = AbortOperation {
             segments: self.compaction.new_segment_paths,
+            point_indexes,
             ..Default::default()
         };
 
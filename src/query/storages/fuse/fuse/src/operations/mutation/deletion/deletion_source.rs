@@ -39,6 +39,8 @@ use crate::io::BlockReader;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::util;
 use crate::operations::BloomIndexState;
+use crate::operations::InvertedIndexState;
+use crate::operations::PointIndexState;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
@@ -56,6 +58,8 @@ struct SerializeState {
     block_location: String,
     index_data: Vec<u8>,
     index_location: String,
+    inverted_index_state: Option<InvertedIndexState>,
+    point_index_state: Option<PointIndexState>,
 }
 
 enum State {
@@ -93,6 +97,9 @@ pub struct DeletionSource {
     index: BlockIndex,
     cluster_stats_gen: ClusterStatsGenerator,
     origin_stats: Option<ClusterStatistics>,
+    inverted_index_columns: Vec<String>,
+    ngram_filter_columns: Vec<String>,
+    point_index_columns: Vec<String>,
 }
 
 impl DeletionSource {
@@ -117,6 +124,9 @@ impl DeletionSource {
             index: (0, 0),
             cluster_stats_gen: table.cluster_stats_gen()?,
             origin_stats: None,
+            inverted_index_columns: table.inverted_index_columns()?,
+            ngram_filter_columns: table.ngram_filter_columns(),
+            point_index_columns: table.point_index_columns(),
         })))
     }
 }
@@ -248,7 +258,17 @@ impl Processor for DeletionSource {
                 // build block index.
                 let location = self.location_gen.block_bloom_index_location(&block_id);
                 let (bloom_index_state, column_distinct_count) =
-                    BloomIndexState::try_create(&block, location)?;
+                    BloomIndexState::try_create(&block, location, &self.ngram_filter_columns)?;
+                let inverted_index_state = InvertedIndexState::try_create(
+                    &block,
+                    &self.inverted_index_columns,
+                    &bloom_index_state.location,
+                )?;
+                let point_index_state = PointIndexState::try_create(
+                    &block,
+                    &self.point_index_columns,
+                    &bloom_index_state.location,
+                )?;
                 let col_stats = gen_columns_statistics(&block, Some(column_distinct_count))?;
 
                 // serialize data block.
@@ -277,6 +297,8 @@ impl Processor for DeletionSource {
                         block_location: block_location.0,
                         index_data: bloom_index_state.data,
                         index_location: bloom_index_state.location.0,
+                        inverted_index_state,
+                        point_index_state,
                     },
                     new_meta,
                 );
@@ -333,6 +355,24 @@ impl Processor for DeletionSource {
                     &serialize_state.index_location,
                 )
                 .await?;
+                // write inverted index data.
+                if let Some(inverted_index_state) = &serialize_state.inverted_index_state {
+                    write_data(
+                        &inverted_index_state.data,
+                        &self.dal,
+                        &inverted_index_state.location,
+                    )
+                    .await?;
+                }
+                // write point index data.
+                if let Some(point_index_state) = &serialize_state.point_index_state {
+                    write_data(
+                        &point_index_state.data,
+                        &self.dal,
+                        &point_index_state.location,
+                    )
+                    .await?;
+                }
                 self.state = State::Generated(Deletion::Replaced(block_meta));
             }
             _ => return Err(ErrorCode::Internal("It's a bug.")),
@@ -31,6 +31,7 @@ use common_storages_table_meta::meta::Statistics;
 use opendal::Operator;
 
 use crate::io::try_join_futures;
+use crate::io::write_segment_point_index;
 use crate::io::SegmentsIO;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::mutation::deletion::deletion_meta::DeletionSourceMeta;
@@ -78,6 +79,7 @@ pub struct DeletionTransform {
     base_segments: Vec<Location>,
     thresholds: BlockCompactThresholds,
     abort_operation: AbortOperation,
+    point_index_columns: Vec<String>,
 
     inputs: Vec<Arc<InputPort>>,
     input_metas: DeletionMap,
@@ -95,6 +97,7 @@ impl DeletionTransform {
         location_gen: TableMetaLocationGenerator,
         base_segments: Vec<Location>,
         thresholds: BlockCompactThresholds,
+        point_index_columns: Vec<String>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(DeletionTransform {
             state: State::None,
@@ -104,6 +107,7 @@ impl DeletionTransform {
             base_segments,
             thresholds,
             abort_operation: AbortOperation::default(),
+            point_index_columns,
             inputs,
             input_metas: HashMap::new(),
             cur_input_index: 0,
@@ -147,8 +151,16 @@ impl DeletionTransform {
         let mut handles = Vec::with_capacity(segments.len());
         for segment in segments {
             let op = self.dal.clone();
+            let point_index_columns = self.point_index_columns.clone();
             handles.push(async move {
                 op.object(&segment.location).write(segment.data).await?;
+                write_segment_point_index(
+                    &op,
+                    &segment.location,
+                    &segment.segment,
+                    &point_index_columns,
+                )
+                .await?;
                 if let Some(segment_cache) = CacheManager::instance().get_table_segment_cache() {
                     let cache = &mut segment_cache.write();
                     cache.put(segment.location.clone(), segment.segment.clone());
@@ -283,6 +295,13 @@ impl Processor for DeletionTransform {
 
                             let location = self.location_gen.gen_segment_info_location();
                             self.abort_operation.add_segment(location.clone());
+                            if !self.point_index_columns.is_empty() {
+                                self.abort_operation.add_point_index(
+                                    TableMetaLocationGenerator::segment_point_index_location(
+                                        &location,
+                                    ),
+                                );
+                            }
                             segments_editor
                                 .insert(seg_idx, (location.clone(), new_segment.format_version()));
                             serialized_data.push(SerializedData {
@@ -73,6 +73,12 @@ impl ReclusterMutator {
         })
     }
 
+    /// Builds the point indexes of the rewritten segments, for the given columns.
+    pub fn with_point_index_columns(mut self, point_index_columns: Vec<String>) -> Self {
+        self.base_mutator.point_index_columns = point_index_columns;
+        self
+    }
+
     pub fn partitions_total(&self) -> usize {
         self.base_mutator.base_snapshot.summary.block_count as usize
     }
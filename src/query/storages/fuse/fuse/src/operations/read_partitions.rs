@@ -37,6 +37,7 @@ use tracing::info;
 
 use crate::fuse_lazy_part::FuseLazyPartInfo;
 use crate::fuse_part::FusePartInfo;
+use crate::io::SegmentsIO;
 use crate::pruning::BlockPruner;
 use crate::FuseTable;
 
@@ -52,6 +53,32 @@ impl FuseTable {
         let snapshot = self.read_table_snapshot().await?;
         match snapshot {
             Some(snapshot) => {
+                if let Some(base_segments) = &self.base_segments {
+                    // Only the appended segments are visible, the summary of the whole
+                    // snapshot can't be used, see `navigate_to_changes`.
+                    let segments_location = snapshot
+                        .segments
+                        .iter()
+                        .filter(|location| !base_segments.contains(*location))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let segments_io = SegmentsIO::create(ctx.clone(), self.operator.clone());
+                    let mut partitions_total = 0;
+                    for segment in segments_io.read_segments(&segments_location).await? {
+                        partitions_total += segment?.summary.block_count as usize;
+                    }
+                    return self
+                        .prune_snapshot_blocks(
+                            ctx.clone(),
+                            self.operator.clone(),
+                            push_downs.clone(),
+                            self.table_info.clone(),
+                            segments_location,
+                            partitions_total,
+                        )
+                        .await;
+                }
+
                 if let Some(result) = self.check_quick_path(&snapshot, &push_downs) {
                     return Ok(result);
                 }
@@ -108,12 +135,14 @@ impl FuseTable {
             segments_location.len()
         );
 
-        let block_metas = BlockPruner::prune(
+        let block_metas = BlockPruner::prune_with_point_index(
             &ctx,
             dal,
             table_info.schema(),
             &push_downs,
             segments_location,
+            &self.point_index_columns(),
+            self.snapshot_loc().await?,
         )
         .await?
         .into_iter()
@@ -108,7 +108,8 @@ impl FuseTable {
             block_compact_thresholds,
             blocks_map,
             self.operator.clone(),
-        )?;
+        )?
+        .with_point_index_columns(self.point_index_columns());
 
         let need_recluster = mutator.target_select().await?;
         if !need_recluster {
@@ -207,6 +208,9 @@ impl FuseTable {
             )
         })?;
 
+        let inverted_index_columns = self.inverted_index_columns()?;
+        let ngram_filter_columns = self.ngram_filter_columns();
+        let point_index_columns = self.point_index_columns();
         pipeline.add_sink(|input| {
             FuseTableSink::try_create(
                 input,
@@ -217,6 +221,9 @@ impl FuseTable {
                 cluster_stats_gen.clone(),
                 block_compact_thresholds,
                 self.storage_format,
+                inverted_index_columns.clone(),
+                ngram_filter_columns.clone(),
+                point_index_columns.clone(),
                 None,
             )
         })?;
@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod point_index_pruner;
 mod pruner;
 mod pruning_executor;
 mod topn_pruner;
@@ -0,0 +1,126 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use common_catalog::plan::Expression;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_sql::executor::ExpressionOp;
+use common_storages_index::PointIndex;
+use common_storages_index::PointIndexCandidates;
+use common_storages_table_meta::meta::SnapshotPointIndex;
+use opendal::Operator;
+
+use crate::io::lookup_point_index;
+use crate::io::read_point_index;
+use crate::io::TableMetaLocationGenerator;
+
+/// Looks up the blocks of a snapshot by the point index of the snapshot, it is consulted
+/// before any segments are read, for the equality and `IN` predicates.
+pub struct PointIndexPruner {
+    index: PointIndex,
+    filter_expression: Expression,
+    dal: Operator,
+}
+
+impl PointIndexPruner {
+    /// Returns `None` if there is no point index columns configured, or no filters pushed down.
+    pub fn try_create(
+        filter_exprs: Option<&[Expression]>,
+        schema: &DataSchemaRef,
+        point_index_columns: &[String],
+        dal: Operator,
+    ) -> Result<Option<Self>> {
+        if point_index_columns.is_empty() {
+            return Ok(None);
+        }
+        let filter_expression = match filter_exprs {
+            Some(exprs) if !exprs.is_empty() => {
+                let mut expr = exprs[0].clone();
+                for item in &exprs[1..] {
+                    expr = expr.and(item)?;
+                }
+                expr
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(Self {
+            index: PointIndex::create(schema.clone()),
+            filter_expression,
+            dal,
+        }))
+    }
+
+    /// Returns the candidate blocks of the snapshot, or `None` if all of them should be kept.
+    pub async fn candidates(&self, snapshot_location: &str) -> Option<SegmentCandidates> {
+        let location = TableMetaLocationGenerator::snapshot_point_index_location(snapshot_location);
+        match self.find_blocks(&location).await {
+            Ok(v) => v,
+            Err(e) => {
+                // swallow exceptions intentionally, corrupted index should not prevent execution
+                tracing::warn!("failed to apply point index, keep all the blocks. {}", e);
+                None
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn find_blocks(&self, location: &str) -> Result<Option<SegmentCandidates>> {
+        let keys = self.index.lookup_keys(&self.filter_expression)?;
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        // None if the snapshot was written before the point index columns are configured
+        let snapshot_index =
+            match read_point_index::<SnapshotPointIndex>(&self.dal, location).await? {
+                Some(snapshot_index) => snapshot_index,
+                None => return Ok(None),
+            };
+
+        let lookup = lookup_point_index(&self.dal, &snapshot_index, &keys).await?;
+        let candidates = self
+            .index
+            .find_snapshot_blocks(&lookup, &self.filter_expression)?;
+        Ok(candidates.map(|candidates| SegmentCandidates {
+            offsets: snapshot_index
+                .runs
+                .into_iter()
+                .flat_map(|run| run.segments.into_iter())
+                .enumerate()
+                .map(|(offset, (path, _))| (path, offset as u32))
+                .collect(),
+            candidates,
+        }))
+    }
+}
+
+/// The candidate blocks looked up by the point index of a snapshot.
+pub struct SegmentCandidates {
+    // segment path => offset of the segment in the runs of the snapshot point index
+    offsets: HashMap<String, u32>,
+    candidates: PointIndexCandidates,
+}
+
+impl SegmentCandidates {
+    /// Returns the offsets of the candidate blocks of the segment, or `None` if all
+    /// the blocks of it should be kept. The segment can be skipped if none is returned.
+    pub fn blocks_of_segment(&self, segment_path: &str) -> Option<BTreeSet<u32>> {
+        match self.offsets.get(segment_path) {
+            Some(offset) => self.candidates.blocks_of_segment(*offset),
+            None => None,
+        }
+    }
+}
@@ -21,10 +21,12 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_sql::executor::ExpressionOp;
 use common_storages_index::BlockFilter;
+use common_storages_index::BlockInvertedIndex;
 use common_storages_table_meta::meta::Location;
 use opendal::Operator;
 
 use crate::io::BlockFilterReader;
+use crate::io::TableMetaLocationGenerator;
 
 #[async_trait::async_trait]
 pub trait Pruner {
@@ -96,11 +98,66 @@ impl Pruner for FilterPruner {
     }
 }
 
+/// Prunes blocks by the inverted indexes, which are kept alongside the bloom filter indexes.
+struct InvertedIndexPruner {
+    /// the expression that would be evaluate
+    filter_expression: Expression,
+
+    /// the data accessor
+    dal: Operator,
+
+    /// the schema of data being indexed
+    data_schema: DataSchemaRef,
+}
+
+#[async_trait::async_trait]
+impl Pruner for InvertedIndexPruner {
+    async fn should_keep(&self, index_location: &Option<Location>, _index_length: u64) -> bool {
+        if let Some(loc) = index_location {
+            let location = TableMetaLocationGenerator::block_inverted_index_location(&loc.0);
+            match should_keep_by_inverted_index(
+                self.dal.clone(),
+                &self.data_schema,
+                &self.filter_expression,
+                &location,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    // swallow exceptions intentionally, corrupted index should not prevent execution
+                    tracing::warn!("failed to apply inverted index, returning ture. {}", e);
+                    true
+                }
+            }
+        } else {
+            true
+        }
+    }
+}
+
+/// Keeps the target only if all the pruners keep it.
+struct ConjunctivePruner {
+    pruners: Vec<Arc<dyn Pruner + Send + Sync>>,
+}
+
+#[async_trait::async_trait]
+impl Pruner for ConjunctivePruner {
+    async fn should_keep(&self, index_location: &Option<Location>, index_length: u64) -> bool {
+        for pruner in &self.pruners {
+            if !pruner.should_keep(index_location, index_length).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Try to build a pruner.
 ///
-/// if `filter_expr` is empty, or is not applicable, e.g. have no point queries
-/// a [NonPruner] will be return, which prunes nothing.
-/// otherwise, a [Filter] backed pruner will be return
+/// if `filter_expr` is empty, or is not applicable, e.g. have no point queries, substring
+/// queries or `MATCH`, a [NonPruner] will be return, which prunes nothing.
+/// otherwise, a [Filter] and/or inverted index backed pruner will be return
 pub fn new_filter_pruner(
     ctx: &Arc<dyn TableContext>,
     filter_exprs: Option<&[Expression]>,
@@ -120,23 +177,44 @@ pub fn new_filter_pruner(
             })
             .unwrap();
 
+        let mut pruners: Vec<Arc<dyn Pruner + Send + Sync>> = vec![];
         let point_query_cols = columns_of_eq_expressions(&expr)?;
-        if !point_query_cols.is_empty() {
+        let substring_query_cols = columns_of_substring_expressions(&expr)?;
+        if !point_query_cols.is_empty() || !substring_query_cols.is_empty() {
             // convert to filter column names
             let filter_block_cols = point_query_cols
                 .iter()
                 .map(|n| BlockFilter::build_filter_column_name(n))
+                .chain(
+                    substring_query_cols
+                        .iter()
+                        .map(|n| BlockFilter::build_ngram_filter_column_name(n)),
+                )
                 .collect();
 
-            return Ok(Some(Arc::new(FilterPruner::new(
+            pruners.push(Arc::new(FilterPruner::new(
                 ctx.clone(),
                 filter_block_cols,
-                expr,
-                dal,
+                expr.clone(),
+                dal.clone(),
                 schema.clone(),
-            ))));
-        } else {
-            tracing::debug!("no point filters found, using NonPruner");
+            )));
+        }
+
+        if has_match_expressions(&expr)? {
+            pruners.push(Arc::new(InvertedIndexPruner {
+                filter_expression: expr,
+                dal,
+                data_schema: schema.clone(),
+            }));
+        }
+
+        match pruners.len() {
+            0 => tracing::debug!(
+                "no point filters, substring filters or MATCH found, using NonPruner"
+            ),
+            1 => return Ok(pruners.pop()),
+            _ => return Ok(Some(Arc::new(ConjunctivePruner { pruners }))),
         }
     }
     Ok(None)
@@ -177,6 +255,25 @@ mod util {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn should_keep_by_inverted_index(
+        dal: Operator,
+        schema: &DataSchemaRef,
+        filter_expr: &Expression,
+        index_location: &str,
+    ) -> Result<bool> {
+        match dal.object(index_location).read().await {
+            Ok(bytes) => {
+                BlockInvertedIndex::from_bytes(schema.clone(), &bytes)?.maybe_true(filter_expr)
+            }
+            Err(e) if e.kind() == opendal::ErrorKind::ObjectNotFound => {
+                // The block was written before any inverted index is created on the table.
+                Ok(true)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     struct PointQueryVisitor {
         // indices of columns which used by point query kept here
         columns: HashSet<String>,
@@ -214,4 +311,68 @@ mod util {
             .accept(visitor)
             .map(|r| r.columns.into_iter().collect())
     }
+
+    struct SubstringQueryVisitor {
+        // columns which are tested for containing a constant substring kept here
+        columns: HashSet<String>,
+    }
+
+    impl ExpressionVisitor for SubstringQueryVisitor {
+        fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+            // "like", "contains" and "instr" take the column first, the others take the substring first
+            match expr {
+                Expression::Function { name, args, .. } if args.len() == 2 => {
+                    let (haystack, needle) = match name.to_lowercase().as_str() {
+                        "like" | "contains" | "instr" => (&args[0], &args[1]),
+                        "position" | "locate" => (&args[1], &args[0]),
+                        _ => return Ok(Recursion::Continue(self)),
+                    };
+                    if let (Expression::IndexedVariable { name, .. }, Expression::Constant { .. }) =
+                        (haystack, needle)
+                    {
+                        self.columns.insert(name.clone());
+                    }
+                    Ok(Recursion::Stop(self))
+                }
+                _ => Ok(Recursion::Continue(self)),
+            }
+        }
+    }
+
+    pub fn columns_of_substring_expressions(filter_expr: &Expression) -> Result<Vec<String>> {
+        let visitor = SubstringQueryVisitor {
+            columns: HashSet::new(),
+        };
+
+        filter_expr
+            .accept(visitor)
+            .map(|r| r.columns.into_iter().collect())
+    }
+
+    struct MatchQueryVisitor {
+        found: bool,
+    }
+
+    impl ExpressionVisitor for MatchQueryVisitor {
+        fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+            match expr {
+                Expression::Function { name, args, .. }
+                    if name.to_lowercase() == "match" && args.len() == 2 =>
+                {
+                    if let (Expression::IndexedVariable { .. }, Expression::Constant { .. }) =
+                        (&args[0], &args[1])
+                    {
+                        self.found = true;
+                    }
+                    Ok(Recursion::Stop(self))
+                }
+                _ => Ok(Recursion::Continue(self)),
+            }
+        }
+    }
+
+    pub fn has_match_expressions(filter_expr: &Expression) -> Result<bool> {
+        let visitor = MatchQueryVisitor { found: false };
+        filter_expr.accept(visitor).map(|r| r.found)
+    }
 }
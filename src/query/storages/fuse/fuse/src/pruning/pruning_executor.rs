@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::BTreeSet;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -37,6 +38,7 @@ use opendal::Operator;
 use tracing::warn;
 use tracing::Instrument;
 
+use super::point_index_pruner::PointIndexPruner;
 use super::pruner;
 use crate::io::MetaReaders;
 use crate::pruning::pruner::Pruner;
@@ -63,6 +65,21 @@ impl BlockPruner {
         schema: DataSchemaRef,
         push_down: &Option<PushDownInfo>,
         segment_locs: Vec<Location>,
+    ) -> Result<Vec<(BlockIndex, Arc<BlockMeta>)>> {
+        Self::prune_with_point_index(ctx, dal, schema, push_down, segment_locs, &[], None).await
+    }
+
+    // prune blocks as `prune` does, but looks up the point index of the snapshot first,
+    // if there are point index columns configured.
+    #[tracing::instrument(level = "debug", skip(schema, ctx, point_index_columns), fields(ctx.id = ctx.get_id().as_str()))]
+    pub async fn prune_with_point_index(
+        ctx: &Arc<dyn TableContext>,
+        dal: Operator,
+        schema: DataSchemaRef,
+        push_down: &Option<PushDownInfo>,
+        segment_locs: Vec<Location>,
+        point_index_columns: &[String],
+        snapshot_location: Option<String>,
     ) -> Result<Vec<(BlockIndex, Arc<BlockMeta>)>> {
         if segment_locs.is_empty() {
             return Ok(vec![]);
@@ -90,6 +107,21 @@ impl BlockPruner {
         let filter_pruner =
             pruner::new_filter_pruner(ctx, filter_expressions, &schema, dal.clone())?;
 
+        // prepare the point index pruner, and look up the candidate blocks of the snapshot.
+        // None will be returned, if there is no point index columns configured, or no filters.
+        let point_index_pruner = PointIndexPruner::try_create(
+            filter_expressions,
+            &schema,
+            point_index_columns,
+            dal.clone(),
+        )?;
+        let candidates = match (&point_index_pruner, &snapshot_location) {
+            (Some(point_index_pruner), Some(location)) => {
+                point_index_pruner.candidates(location).await
+            }
+            _ => None,
+        };
+
         // 2. constraint the degree of parallelism
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
         let max_concurrency = {
@@ -123,20 +155,42 @@ impl BlockPruner {
 
         // 4. kick off
         // 4.1 generates the iterator of segment pruning tasks.
-        let mut segments = segment_locs.into_iter().enumerate();
+        // the segments that have no candidate blocks are skipped, without being read.
+        let mut segments =
+            segment_locs
+                .into_iter()
+                .enumerate()
+                .filter_map(|(segment_idx, segment_location)| {
+                    let candidate_blocks = candidates
+                        .as_ref()
+                        .and_then(|candidates| candidates.blocks_of_segment(&segment_location.0));
+                    match &candidate_blocks {
+                        Some(blocks) if blocks.is_empty() => None,
+                        _ => Some((segment_idx, segment_location, candidate_blocks)),
+                    }
+                });
         let tasks = std::iter::from_fn(|| {
             // pruning tasks are executed concurrently, check if limit exceeded before proceeding
             if pruning_ctx.limiter.exceeded() {
                 None
             } else {
-                segments.next().map(|(segment_idx, segment_location)| {
-                    let dal = dal.clone();
-                    let pruning_ctx = pruning_ctx.clone();
-                    move |permit| async move {
-                        Self::prune_segment(permit, dal, pruning_ctx, segment_idx, segment_location)
+                segments
+                    .next()
+                    .map(|(segment_idx, segment_location, candidate_blocks)| {
+                        let dal = dal.clone();
+                        let pruning_ctx = pruning_ctx.clone();
+                        move |permit| async move {
+                            Self::prune_segment(
+                                permit,
+                                dal,
+                                pruning_ctx,
+                                segment_idx,
+                                segment_location,
+                                candidate_blocks,
+                            )
                             .await
-                    }
-                })
+                        }
+                    })
             }
         });
 
@@ -172,6 +226,7 @@ impl BlockPruner {
         pruning_ctx: Arc<PruningContext>,
         segment_idx: usize,
         segment_location: Location,
+        candidate_blocks: Option<BTreeSet<u32>>,
     ) -> Result<Vec<(BlockIndex, Arc<BlockMeta>)>> {
         let segment_reader = MetaReaders::segment_info_reader(dal.clone());
 
@@ -187,11 +242,18 @@ impl BlockPruner {
             segment_info.summary.row_count,
         ) {
             if let Some(filter_pruner) = &pruning_ctx.filter_pruner {
-                Self::prune_blocks(&pruning_ctx, filter_pruner, segment_idx, &segment_info).await?
+                Self::prune_blocks(
+                    &pruning_ctx,
+                    filter_pruner,
+                    segment_idx,
+                    &segment_info,
+                    &candidate_blocks,
+                )
+                .await?
             } else {
                 // if no available filter pruners, just prune the blocks by
                 // using zone map index, and do not spawn async tasks
-                Self::prune_blocks_sync(&pruning_ctx, segment_idx, &segment_info)
+                Self::prune_blocks_sync(&pruning_ctx, segment_idx, &segment_info, &candidate_blocks)
             }
         } else {
             vec![]
@@ -205,8 +267,13 @@ impl BlockPruner {
         filter_pruner: &Arc<dyn Pruner + Send + Sync>,
         segment_idx: usize,
         segment_info: &SegmentInfo,
+        candidate_blocks: &Option<BTreeSet<u32>>,
     ) -> Result<Vec<(BlockIndex, Arc<BlockMeta>)>> {
-        let mut blocks = segment_info.blocks.iter().enumerate();
+        let mut blocks = segment_info
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(block_idx, _)| Self::is_candidate(candidate_blocks, *block_idx));
         let pruning_runtime = &pruning_ctx.rt;
         let semaphore = &pruning_ctx.semaphore;
 
@@ -273,9 +340,13 @@ impl BlockPruner {
         pruning_ctx: &Arc<PruningContext>,
         segment_idx: usize,
         segment_info: &SegmentInfo,
+        candidate_blocks: &Option<BTreeSet<u32>>,
     ) -> Vec<(BlockIndex, Arc<BlockMeta>)> {
         let mut result = Vec::with_capacity(segment_info.blocks.len());
         for (block_idx, block_meta) in segment_info.blocks.iter().enumerate() {
+            if !Self::is_candidate(candidate_blocks, block_idx) {
+                continue;
+            }
             // check limit speculatively
             if pruning_ctx.limiter.exceeded() {
                 break;
@@ -292,6 +363,13 @@ impl BlockPruner {
         result
     }
 
+    #[inline]
+    fn is_candidate(candidate_blocks: &Option<BTreeSet<u32>>, block_idx: usize) -> bool {
+        candidate_blocks
+            .as_ref()
+            .map_or(true, |blocks| blocks.contains(&(block_idx as u32)))
+    }
+
     #[inline]
     #[tracing::instrument(level = "debug", skip_all)]
     async fn join_flatten_result(
@@ -33,6 +33,10 @@ use crate::SupportedType;
 /// are not applicable for a filter, we skip the creation.
 /// That is to say, it is legal to have a BlockFilter with zero columns.
 ///
+/// For the string columns configured with n-gram filters, the n-grams of the values are also
+/// put into a filter, stored with field name 'Ngram(column_name)'. They are used to prune
+/// substring predicates like `LIKE '%abc%'`, `position('abc', s)` and `contains(s, 'abc')`.
+///
 /// For example, for the source data block as follows:
 /// ```
 ///         +---name--+--age--+
@@ -75,12 +79,23 @@ pub enum FilterEvalResult {
     NotApplicable,
 }
 
+/// Length in bytes of the n-grams kept by the n-gram filters.
+///
+/// Substrings shorter than it can not be looked up, predicates on them are NotApplicable.
+pub const NGRAM_SIZE: usize = 3;
+
 impl BlockFilter {
     /// For every applicable column, we will create a filter.
     /// The filter will be stored with field name 'Bloom(column_name)'
     pub fn build_filter_column_name(column_name: &str) -> String {
         format!("Bloom({})", column_name)
     }
+
+    /// The n-gram filter of column will be stored with field name 'Ngram(column_name)'
+    pub fn build_ngram_filter_column_name(column_name: &str) -> String {
+        format!("Ngram({})", column_name)
+    }
+
     pub fn build_filter_schema(data_schema: &DataSchema) -> DataSchema {
         let mut filter_fields = vec![];
         let fields = data_schema.fields();
@@ -115,7 +130,9 @@ impl BlockFilter {
     /// Create a filter block from source data.
     ///
     /// All input blocks should belong to a Parquet file, e.g. the block array represents the parquet file in memory.
-    pub fn try_create(blocks: &[&DataBlock]) -> Result<Self> {
+    ///
+    /// `ngram_columns` are the string columns that n-gram filters should be created for.
+    pub fn try_create(blocks: &[&DataBlock], ngram_columns: &[String]) -> Result<Self> {
         if blocks.is_empty() {
             return Err(ErrorCode::BadArguments("data blocks is empty"));
         }
@@ -154,7 +171,45 @@ impl BlockFilter {
             }
         }
 
-        let filter_schema = Arc::new(Self::build_filter_schema(source_schema.as_ref()));
+        let mut filter_fields = Self::build_filter_schema(source_schema.as_ref())
+            .fields()
+            .clone();
+        for (i, field) in fields.iter().enumerate() {
+            if !ngram_columns.contains(field.name())
+                || remove_nullable(field.data_type()).data_type_id() != TypeID::String
+            {
+                continue;
+            }
+
+            let mut filter_builder = Xor8Builder::create();
+            let mut has_ngram = false;
+            for block in blocks.iter() {
+                let col = block.column(i);
+                for idx in 0..col.len() {
+                    if let DataValue::String(value) = col.get(idx) {
+                        for ngram in value.windows(NGRAM_SIZE) {
+                            filter_builder.add_key(&ngram);
+                            has_ngram = true;
+                        }
+                    }
+                }
+            }
+            // all the values are shorter than NGRAM_SIZE, nothing to filter
+            if !has_ngram {
+                continue;
+            }
+
+            let filter = filter_builder.build()?;
+            let filter_value = DataValue::String(filter.to_bytes()?);
+            let filter_column: ColumnRef =
+                filter_value.as_const_column(&StringType::new_impl(), 1)?;
+            filter_columns.push(filter_column);
+
+            let column_name = Self::build_ngram_filter_column_name(field.name());
+            filter_fields.push(DataField::new(&column_name, Vu8::to_data_type()));
+        }
+
+        let filter_schema = Arc::new(DataSchema::new(filter_fields));
         let filter_block = DataBlock::create(filter_schema.clone(), filter_columns);
         Ok(Self {
             source_schema,
@@ -188,6 +243,23 @@ impl BlockFilter {
         }
     }
 
+    /// Returns False if any of the n-grams is not in the n-gram filter of the column.
+    pub fn find_ngrams(&self, column_name: &str, ngrams: &[&[u8]]) -> Result<FilterEvalResult> {
+        let filter_column = Self::build_ngram_filter_column_name(column_name);
+        if !self.filter_block.schema().has_field(&filter_column) || ngrams.is_empty() {
+            // The column doesn't have a n-gram filter, or the substring is too short
+            return Ok(FilterEvalResult::NotApplicable);
+        }
+
+        let filter_bytes = self.filter_block.first(&filter_column)?.as_string()?;
+        let (filter, _size) = Xor8Filter::from_bytes(&filter_bytes)?;
+        if ngrams.iter().all(|ngram| filter.contains(ngram)) {
+            Ok(FilterEvalResult::Maybe)
+        } else {
+            Ok(FilterEvalResult::False)
+        }
+    }
+
     /// Returns false when the expression must be false, otherwise true.
     /// The 'true' doesn't really mean the expression is true, but 'maybe true'.
     /// That is to say, you still need the load all data and run the execution.
@@ -207,6 +279,12 @@ impl BlockFilter {
             Expression::Function { name, args, .. } if args.len() == 2 => {
                 match name.to_lowercase().as_str() {
                     "=" => self.eval_equivalent_expression(&args[0], &args[1]),
+                    "like" => self.eval_like_expression(&args[0], &args[1]),
+                    "contains" | "instr" => self.eval_substring_expression(&args[0], &args[1]),
+                    "position" | "locate" => self.eval_substring_expression(&args[1], &args[0]),
+                    op @ (">" | ">=" | "<" | "<=" | "!=" | "<>") => {
+                        self.eval_locating_comparison(op, &args[0], &args[1])
+                    }
                     "and" => self.eval_logical_and(&args[0], &args[1]),
                     "or" => self.eval_logical_or(&args[0], &args[1]),
                     _ => Ok(FilterEvalResult::NotApplicable),
@@ -246,6 +324,99 @@ impl BlockFilter {
         }
     }
 
+    // Evaluate the like expression like "name LIKE '%lic%'"
+    fn eval_like_expression(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<FilterEvalResult> {
+        match (left, right) {
+            (Expression::IndexedVariable { name, .. }, Expression::Constant { value, .. })
+                if !value.is_null() =>
+            {
+                let pattern = value.as_string()?;
+                let segments = like_pattern_literals(&pattern);
+                let ngrams = segments
+                    .iter()
+                    .flat_map(|segment| segment.windows(NGRAM_SIZE))
+                    .collect::<Vec<_>>();
+                self.find_ngrams(name, &ngrams)
+            }
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    // Evaluate the substring test like "contains(name, 'lic')", true if the substring occurs
+    fn eval_substring_expression(
+        &self,
+        haystack: &Expression,
+        needle: &Expression,
+    ) -> Result<FilterEvalResult> {
+        match (haystack, needle) {
+            (Expression::IndexedVariable { name, .. }, Expression::Constant { value, .. })
+                if !value.is_null() =>
+            {
+                let substr = value.as_string()?;
+                let ngrams = substr.windows(NGRAM_SIZE).collect::<Vec<_>>();
+                self.find_ngrams(name, &ngrams)
+            }
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    // Evaluate the comparison between the locating functions and a constant, like
+    // "position('lic', name) > 0". Only the comparisons that require the substring to be
+    // found, e.g. a non-zero position, are applicable.
+    fn eval_locating_comparison(
+        &self,
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<FilterEvalResult> {
+        // normalize to "position op constant"
+        let (op, position, constant) = match (left, right) {
+            (_, Expression::Constant { value, .. }) => (op, left, value),
+            (Expression::Constant { value, .. }, _) => {
+                let op = match op {
+                    ">" => "<",
+                    ">=" => "<=",
+                    "<" => ">",
+                    "<=" => ">=",
+                    op => op,
+                };
+                (op, right, value)
+            }
+            _ => return Ok(FilterEvalResult::NotApplicable),
+        };
+
+        let (haystack, needle) = match position {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match name.to_lowercase().as_str() {
+                    "position" | "locate" => (&args[1], &args[0]),
+                    "instr" => (&args[0], &args[1]),
+                    _ => return Ok(FilterEvalResult::NotApplicable),
+                }
+            }
+            _ => return Ok(FilterEvalResult::NotApplicable),
+        };
+        let constant = match constant.as_f64() {
+            Ok(constant) => constant,
+            Err(_) => return Ok(FilterEvalResult::NotApplicable),
+        };
+
+        let requires_found = match op {
+            ">" => constant >= 0.0,
+            ">=" => constant > 0.0,
+            "!=" | "<>" => constant == 0.0,
+            _ => false,
+        };
+        if requires_found {
+            self.eval_substring_expression(haystack, needle)
+        } else {
+            Ok(FilterEvalResult::NotApplicable)
+        }
+    }
+
     // Evaluate the logical and expression
     fn eval_logical_and(&self, left: &Expression, right: &Expression) -> Result<FilterEvalResult> {
         let left_result = self.eval(left)?;
@@ -280,3 +451,31 @@ impl BlockFilter {
         }
     }
 }
+
+/// Splits the LIKE pattern into the literal parts, which must occur in the matched strings.
+///
+/// '%' and '_' are the separators, a '\\' escapes the next character.
+fn like_pattern_literals(pattern: &[u8]) -> Vec<Vec<u8>> {
+    let mut segments = vec![];
+    let mut current = vec![];
+    let mut index = 0;
+    while index < pattern.len() {
+        match pattern[index] {
+            b'%' | b'_' => {
+                if !current.is_empty() {
+                    segments.push(std::mem::take(&mut current));
+                }
+            }
+            b'\\' if index + 1 < pattern.len() => {
+                index += 1;
+                current.push(pattern[index]);
+            }
+            c => current.push(c),
+        }
+        index += 1;
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
@@ -0,0 +1,283 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::tokenize;
+use common_storages_table_meta::meta::ColumnPostings;
+use common_storages_table_meta::meta::InvertedIndex;
+use common_storages_table_meta::table::OPT_KEY_INVERTED_INDEXES;
+
+use crate::FilterEvalResult;
+
+/// The inverted indexes created on a table, index name => column name.
+///
+/// Kept in the table option [OPT_KEY_INVERTED_INDEXES] as a json object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InvertedIndexDefinitions {
+    pub indexes: BTreeMap<String, String>,
+}
+
+impl InvertedIndexDefinitions {
+    pub fn from_options(options: &BTreeMap<String, String>) -> Result<Self> {
+        let indexes = match options.get(OPT_KEY_INVERTED_INDEXES) {
+            None => BTreeMap::new(),
+            Some(value) => serde_json::from_str(value).map_err(|e| {
+                ErrorCode::Internal(format!(
+                    "invalid table option {}: {}",
+                    OPT_KEY_INVERTED_INDEXES, e
+                ))
+            })?,
+        };
+        Ok(Self { indexes })
+    }
+
+    pub fn to_option_value(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.indexes)?)
+    }
+
+    /// Names of the indexed columns, without duplication.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = self.indexes.values().cloned().collect::<Vec<_>>();
+        columns.sort();
+        columns.dedup();
+        columns
+    }
+}
+
+/// BlockInvertedIndex keeps the token postings of the indexed string columns of a data block.
+///
+/// For example, for the source data block as follows, with column `doc` indexed:
+/// ```
+///         +---id--+------doc------+
+///         |   1   | "Hello world" |
+///         |   2   | "hello"       |
+///         +-------+---------------+
+/// ```
+/// We will get the postings of `doc`:
+/// ```
+///         hello => [0, 1]
+///         world => [0]
+/// ```
+///
+/// Terms are the same as what `MATCH(doc, 'query')` splits its arguments into,
+/// so that blocks without any of the query terms can be skipped.
+pub struct BlockInvertedIndex {
+    /// The schema of the source table/block, which the index work for.
+    pub source_schema: DataSchemaRef,
+
+    pub index: InvertedIndex,
+}
+
+impl BlockInvertedIndex {
+    /// Builds postings for the given columns of the block, columns that are
+    /// not of string type are ignored.
+    pub fn try_create(block: &DataBlock, columns: &[String]) -> Result<Self> {
+        let source_schema = block.schema().clone();
+        let mut postings = HashMap::with_capacity(columns.len());
+        for column_name in columns {
+            let field = match source_schema.field_with_name(column_name) {
+                Ok(field) => field,
+                Err(_) => continue,
+            };
+            if !Self::is_supported_type(field.data_type()) {
+                continue;
+            }
+
+            let mut terms: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            let column = block.try_column_by_name(column_name)?;
+            for row in 0..column.len() {
+                if let DataValue::String(text) = column.get(row) {
+                    for term in tokenize(&text) {
+                        let rows = terms.entry(term).or_default();
+                        // the same term may occur in a row multiple times
+                        if rows.last() != Some(&(row as u32)) {
+                            rows.push(row as u32);
+                        }
+                    }
+                }
+            }
+            postings.insert(column_name.clone(), ColumnPostings { terms });
+        }
+
+        Ok(Self {
+            source_schema,
+            index: InvertedIndex::new(postings),
+        })
+    }
+
+    pub fn from_bytes(source_schema: DataSchemaRef, bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            source_schema,
+            index: serde_json::from_slice(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&self.index)?)
+    }
+
+    pub fn is_supported_type(data_type: &DataTypeImpl) -> bool {
+        remove_nullable(data_type).data_type_id() == TypeID::String
+    }
+
+    /// Returns false when no row of the block can satisfy the expression, otherwise true.
+    pub fn maybe_true(&self, expr: &Expression) -> Result<bool> {
+        Ok(self.eval(expr)? != FilterEvalResult::False)
+    }
+
+    /// Apply the predicate expression, return False if none of the rows could have
+    /// a positive `MATCH` score that the predicate requires.
+    ///
+    /// Otherwise return either Maybe or NotApplicable.
+    #[tracing::instrument(level = "debug", name = "block_inverted_index_eval", skip_all)]
+    pub fn eval(&self, expr: &Expression) -> Result<FilterEvalResult> {
+        match expr {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match name.to_lowercase().as_str() {
+                    "match" => self.eval_match(&args[0], &args[1]),
+                    "and" => self.eval_logical_and(&args[0], &args[1]),
+                    "or" => self.eval_logical_or(&args[0], &args[1]),
+                    op @ (">" | ">=" | "=" | "<" | "<=") => {
+                        self.eval_comparison(op, &args[0], &args[1])
+                    }
+                    _ => Ok(FilterEvalResult::NotApplicable),
+                }
+            }
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    /// Evaluate `MATCH(column, 'query')`, which is false if none of the query terms occurs in the column.
+    fn eval_match(&self, column: &Expression, query: &Expression) -> Result<FilterEvalResult> {
+        match (column, query) {
+            (Expression::IndexedVariable { name, .. }, Expression::Constant { value, .. })
+                if !value.is_null() =>
+            {
+                let postings = match self.index.columns.get(name) {
+                    Some(postings) => postings,
+                    None => return Ok(FilterEvalResult::NotApplicable),
+                };
+                let query = value.as_string()?;
+                if tokenize(&query)
+                    .iter()
+                    .any(|term| postings.terms.contains_key(term))
+                {
+                    Ok(FilterEvalResult::Maybe)
+                } else {
+                    Ok(FilterEvalResult::False)
+                }
+            }
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+
+    // Evaluate the comparison between the score and a constant, like "MATCH(doc, 'hello') > 0".
+    // Only the comparisons that require a positive score are applicable.
+    fn eval_comparison(
+        &self,
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<FilterEvalResult> {
+        // normalize to "score op constant"
+        let (op, score, constant) = match (constant_value(left), constant_value(right)) {
+            (None, Some(value)) => (op, left, value),
+            (Some(value), None) => {
+                let op = match op {
+                    ">" => "<",
+                    ">=" => "<=",
+                    "<" => ">",
+                    "<=" => ">=",
+                    op => op,
+                };
+                (op, right, value)
+            }
+            _ => return Ok(FilterEvalResult::NotApplicable),
+        };
+
+        let (column, query) = match score {
+            Expression::Function { name, args, .. }
+                if args.len() == 2 && name.to_lowercase() == "match" =>
+            {
+                (&args[0], &args[1])
+            }
+            _ => return Ok(FilterEvalResult::NotApplicable),
+        };
+        let constant = match constant.as_f64() {
+            Ok(constant) => constant,
+            Err(_) => return Ok(FilterEvalResult::NotApplicable),
+        };
+
+        let requires_positive = match op {
+            ">" => constant >= 0.0,
+            ">=" | "=" => constant > 0.0,
+            _ => false,
+        };
+        if requires_positive {
+            self.eval_match(column, query)
+        } else {
+            Ok(FilterEvalResult::NotApplicable)
+        }
+    }
+
+    // Evaluate the logical and expression
+    fn eval_logical_and(&self, left: &Expression, right: &Expression) -> Result<FilterEvalResult> {
+        let left_result = self.eval(left)?;
+        if left_result == FilterEvalResult::False {
+            return Ok(FilterEvalResult::False);
+        }
+
+        let right_result = self.eval(right)?;
+        if right_result == FilterEvalResult::False {
+            return Ok(FilterEvalResult::False);
+        }
+
+        if left_result == FilterEvalResult::NotApplicable
+            || right_result == FilterEvalResult::NotApplicable
+        {
+            Ok(FilterEvalResult::NotApplicable)
+        } else {
+            Ok(FilterEvalResult::Maybe)
+        }
+    }
+
+    // Evaluate the logical or expression
+    fn eval_logical_or(&self, left: &Expression, right: &Expression) -> Result<FilterEvalResult> {
+        let left_result = self.eval(left)?;
+        let right_result = self.eval(right)?;
+        match (&left_result, &right_result) {
+            (&FilterEvalResult::False, &FilterEvalResult::False) => Ok(FilterEvalResult::False),
+            (&FilterEvalResult::False, _) => Ok(right_result),
+            (_, &FilterEvalResult::False) => Ok(left_result),
+            (&FilterEvalResult::Maybe, &FilterEvalResult::Maybe) => Ok(FilterEvalResult::Maybe),
+            _ => Ok(FilterEvalResult::NotApplicable),
+        }
+    }
+}
+
+// The value of a constant, which may be casted to the type of the other side of comparison.
+fn constant_value(expr: &Expression) -> Option<&DataValue> {
+    match expr {
+        Expression::Constant { value, .. } => Some(value),
+        Expression::Cast { input, .. } => constant_value(input),
+        _ => None,
+    }
+}
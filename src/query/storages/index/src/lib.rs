@@ -20,11 +20,18 @@ use common_datavalues::DataTypeImpl;
 mod bloom;
 pub mod filters;
 pub mod index_min_max;
+mod inverted;
+mod point;
 pub mod range_filter;
 
 pub use bloom::BlockFilter;
 pub use bloom::FilterEvalResult;
 pub use index_min_max::*;
+pub use inverted::BlockInvertedIndex;
+pub use inverted::InvertedIndexDefinitions;
+pub use point::PointIndex;
+pub use point::PointIndexCandidates;
+pub use point::PointIndexLookup;
 pub use range_filter::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
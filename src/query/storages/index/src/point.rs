@@ -0,0 +1,469 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_storages_table_meta::meta::BlockPointIndex;
+use common_storages_table_meta::meta::ColumnPointIndex;
+use common_storages_table_meta::meta::Location;
+use common_storages_table_meta::meta::SegmentPointIndex;
+
+use crate::filters::Xor8Filter;
+use crate::SupportedType;
+
+/// PointIndex looks up the blocks that may satisfy the equality predicates, like
+/// `order_id = 123` or `order_id IN (1, 2, 3)`, by the sorted runs of the snapshot,
+/// which are merged from the [SegmentPointIndex] of the segments.
+///
+/// For example, for the blocks of a segment as follows, with column `order_id` indexed:
+/// ```
+///         block 0: order_id in [1, 3]
+///         block 1: order_id in [2, 3]
+/// ```
+/// We will get the segment point index:
+/// ```
+///         1 => [0]
+///         2 => [1]
+///         3 => [0, 1]
+/// ```
+/// and `order_id = 2` only needs to read block 1.
+pub struct PointIndex {
+    /// The schema of the source table/block, which the index work for.
+    pub source_schema: DataSchemaRef,
+}
+
+impl PointIndex {
+    pub fn create(source_schema: DataSchemaRef) -> Self {
+        Self { source_schema }
+    }
+
+    /// Same as the bloom filter, only integral and string columns are supported.
+    pub fn is_supported_type(data_type: &DataTypeImpl) -> bool {
+        Xor8Filter::is_supported_type(data_type)
+    }
+
+    /// Collects the distinct values of the given columns of the block, columns that are
+    /// not of supported types are ignored.
+    pub fn build_block_index(block: &DataBlock, columns: &[String]) -> Result<BlockPointIndex> {
+        let schema = block.schema();
+        let mut indexed = BTreeMap::new();
+        for column_name in columns {
+            let field = match schema.field_with_name(column_name) {
+                Ok(field) => field,
+                Err(_) => continue,
+            };
+            if !Self::is_supported_type(field.data_type()) {
+                continue;
+            }
+
+            let column = block.try_column_by_name(column_name)?;
+            let mut keys = BTreeSet::new();
+            for row in 0..column.len() {
+                let value = column.get(row);
+                if !value.is_null() {
+                    keys.insert(value);
+                }
+            }
+            indexed.insert(column_name.clone(), keys.into_iter().collect());
+        }
+        Ok(BlockPointIndex::new(indexed))
+    }
+
+    /// Merges the block indexes into the index of the segment, the i-th block index
+    /// should be of the i-th block of the segment.
+    ///
+    /// Only the columns indexed by all the blocks are included.
+    pub fn build_segment_index(blocks: &[BlockPointIndex]) -> SegmentPointIndex {
+        let mut columns: BTreeMap<String, BTreeMap<DataValue, Vec<u32>>> = BTreeMap::new();
+        if let Some(first) = blocks.first() {
+            for column_name in first.columns.keys() {
+                if blocks.iter().all(|b| b.columns.contains_key(column_name)) {
+                    columns.insert(column_name.clone(), BTreeMap::new());
+                }
+            }
+        }
+
+        for (block_idx, block) in blocks.iter().enumerate() {
+            for (column_name, keys) in columns.iter_mut() {
+                for key in &block.columns[column_name] {
+                    keys.entry(key.clone()).or_default().push(block_idx as u32);
+                }
+            }
+        }
+
+        SegmentPointIndex::new(
+            columns
+                .into_iter()
+                .map(|(column_name, keys)| (column_name, keys.into_iter().collect()))
+                .collect(),
+        )
+    }
+
+    /// Merges the indexes into a sorted run, which indexes the given `segments`.
+    ///
+    /// `runs` are the runs being merged, with the segments they index, their entries are
+    /// kept for the segments in `segments`. `segment_indexes` are the indexes of the other
+    /// segments, keyed by the offsets of them in `segments`. Segments in neither are not indexed.
+    pub fn build_run(
+        segments: &[Location],
+        runs: &[(&[Location], &BTreeMap<String, ColumnPointIndex>)],
+        segment_indexes: &BTreeMap<u32, SegmentPointIndex>,
+        columns: &[String],
+    ) -> BTreeMap<String, ColumnPointIndex> {
+        let mut merged: BTreeMap<String, (BTreeSet<u32>, BTreeMap<DataValue, Vec<(u32, u32)>>)> =
+            BTreeMap::new();
+
+        let offsets = segments
+            .iter()
+            .enumerate()
+            .map(|(offset, (path, _))| (path.as_str(), offset as u32))
+            .collect::<HashMap<_, _>>();
+        for (run_segments, run_columns) in runs {
+            // offsets of the segments of the run in the new run, None if removed
+            let new_offsets = run_segments
+                .iter()
+                .map(|(path, _)| offsets.get(path.as_str()).cloned())
+                .collect::<Vec<_>>();
+            let new_offset = |offset: &u32| new_offsets.get(*offset as usize).cloned().flatten();
+
+            for (column_name, column) in run_columns.iter() {
+                if !columns.contains(column_name) {
+                    continue;
+                }
+                let (indexed, keys) = merged.entry(column_name.clone()).or_default();
+                indexed.extend(column.segments.iter().filter_map(new_offset));
+                for (key, blocks) in column.keys.iter() {
+                    let blocks = blocks
+                        .iter()
+                        .filter_map(|(segment, block)| new_offset(segment).map(|s| (s, *block)))
+                        .collect::<Vec<_>>();
+                    if !blocks.is_empty() {
+                        keys.entry(key.clone()).or_default().extend(blocks);
+                    }
+                }
+            }
+        }
+
+        for (offset, segment_index) in segment_indexes.iter() {
+            for (column_name, keys) in segment_index.columns.iter() {
+                if !columns.contains(column_name) {
+                    continue;
+                }
+                let (indexed, merged_keys) = merged.entry(column_name.clone()).or_default();
+                indexed.insert(*offset);
+                for (key, blocks) in keys.iter() {
+                    merged_keys
+                        .entry(key.clone())
+                        .or_default()
+                        .extend(blocks.iter().map(|block| (*offset, *block)));
+                }
+            }
+        }
+
+        merged
+            .into_iter()
+            .map(|(column_name, (segments, keys))| {
+                let keys = keys
+                    .into_iter()
+                    .map(|(key, mut blocks)| {
+                        blocks.sort_unstable();
+                        blocks.dedup();
+                        (key, blocks)
+                    })
+                    .collect();
+                (column_name, ColumnPointIndex { segments, keys })
+            })
+            .collect()
+    }
+
+    /// Returns the keys that the expression looks up, by column, which are what should
+    /// be read from the runs of a snapshot point index.
+    pub fn lookup_keys(&self, expr: &Expression) -> Result<Vec<(String, Vec<DataValue>)>> {
+        let looked_up = RefCell::new(vec![]);
+        self.find::<PointIndexCandidates>(expr, &|column_name, keys| {
+            looked_up
+                .borrow_mut()
+                .push((column_name.to_string(), keys.to_vec()));
+            Ok(None)
+        })?;
+        Ok(looked_up.into_inner())
+    }
+
+    /// Returns the offsets of the blocks that may satisfy the expression.
+    ///
+    /// None is returned if the expression is not applicable, e.g. there are no
+    /// equality predicates on the indexed columns, all the blocks should be kept.
+    #[tracing::instrument(level = "debug", name = "segment_point_index_find_blocks", skip_all)]
+    pub fn find_blocks(
+        &self,
+        index: &SegmentPointIndex,
+        expr: &Expression,
+    ) -> Result<Option<BTreeSet<u32>>> {
+        self.find(expr, &|column_name, keys| {
+            let entries = match index.columns.get(column_name) {
+                Some(entries) => entries,
+                None => return Ok(None),
+            };
+            let mut blocks = BTreeSet::new();
+            for key in keys {
+                if let Ok(pos) = entries.binary_search_by(|(k, _)| k.cmp(key)) {
+                    blocks.extend(entries[pos].1.iter().cloned());
+                }
+            }
+            Ok(Some(blocks))
+        })
+    }
+
+    /// Returns the blocks of the snapshot that may satisfy the expression, by the entries
+    /// of the keys returned by [PointIndex::lookup_keys], read from the runs of the snapshot.
+    ///
+    /// None is returned if the expression is not applicable, all the blocks should be kept.
+    #[tracing::instrument(level = "debug", name = "snapshot_point_index_find_blocks", skip_all)]
+    pub fn find_snapshot_blocks(
+        &self,
+        lookup: &PointIndexLookup,
+        expr: &Expression,
+    ) -> Result<Option<PointIndexCandidates>> {
+        self.find(expr, &|column_name, keys| {
+            let (indexed, entries) = match lookup.columns.get(column_name) {
+                Some(column) => column,
+                None => return Ok(None),
+            };
+            let mut candidates = PointIndexCandidates {
+                blocks: BTreeSet::new(),
+                segments: (0..lookup.segment_count)
+                    .filter(|segment| !indexed.contains(segment))
+                    .collect(),
+            };
+            for key in keys {
+                if let Some(blocks) = entries.get(key) {
+                    candidates.blocks.extend(blocks.iter().cloned());
+                }
+            }
+            Ok(Some(candidates))
+        })
+    }
+
+    fn find<C: Candidates>(
+        &self,
+        expr: &Expression,
+        find_keys: &dyn Fn(&str, &[DataValue]) -> Result<Option<C>>,
+    ) -> Result<Option<C>> {
+        match expr {
+            Expression::Function { name, args, .. } if args.len() == 2 => {
+                match name.to_lowercase().as_str() {
+                    "=" => match Self::equivalent_keys(&args[0], &args[1]) {
+                        Some((column_name, values)) => {
+                            self.find_keys(column_name, &values, find_keys)
+                        }
+                        None => Ok(None),
+                    },
+                    "in" => match Self::in_list_keys(&args[0], &args[1]) {
+                        Some((column_name, values)) => {
+                            self.find_keys(column_name, &values, find_keys)
+                        }
+                        None => Ok(None),
+                    },
+                    "and" => {
+                        let left = self.find(&args[0], find_keys)?;
+                        let right = self.find(&args[1], find_keys)?;
+                        Ok(match (left, right) {
+                            (Some(left), Some(right)) => Some(left.and(right)),
+                            (Some(blocks), None) | (None, Some(blocks)) => Some(blocks),
+                            (None, None) => None,
+                        })
+                    }
+                    "or" => {
+                        let left = self.find(&args[0], find_keys)?;
+                        let right = self.find(&args[1], find_keys)?;
+                        Ok(match (left, right) {
+                            (Some(left), Some(right)) => Some(left.or(right)),
+                            _ => None,
+                        })
+                    }
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // The keys of the equivalent expression like "order_id = 123"
+    fn equivalent_keys<'a>(
+        left: &'a Expression,
+        right: &'a Expression,
+    ) -> Option<(&'a str, Vec<DataValue>)> {
+        match (left, right) {
+            (Expression::IndexedVariable { name, .. }, Expression::Constant { value, .. })
+            | (Expression::Constant { value, .. }, Expression::IndexedVariable { name, .. }) => {
+                Some((name, vec![value.clone()]))
+            }
+            _ => None,
+        }
+    }
+
+    // The keys of the in list expression like "order_id IN (1, 2, 3)"
+    fn in_list_keys<'a>(
+        left: &'a Expression,
+        right: &'a Expression,
+    ) -> Option<(&'a str, Vec<DataValue>)> {
+        let name = match left {
+            Expression::IndexedVariable { name, .. } => name,
+            _ => return None,
+        };
+        let values = match right {
+            Expression::Function { name, args, .. } if name.to_lowercase() == "tuple" => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    match arg {
+                        Expression::Constant { value, .. } => values.push(value.clone()),
+                        _ => return None,
+                    }
+                }
+                values
+            }
+            Expression::Constant {
+                value: DataValue::Struct(values),
+                ..
+            } => values.clone(),
+            _ => return None,
+        };
+        Some((name, values))
+    }
+
+    fn find_keys<C: Candidates>(
+        &self,
+        column_name: &str,
+        values: &[DataValue],
+        find_keys: &dyn Fn(&str, &[DataValue]) -> Result<Option<C>>,
+    ) -> Result<Option<C>> {
+        let data_field = match self.source_schema.field_with_name(column_name) {
+            Ok(data_field) => data_field,
+            Err(_) => return Ok(None),
+        };
+        let data_type = data_field.data_type();
+
+        let mut keys = Vec::with_capacity(values.len());
+        for value in values {
+            if value.is_null() {
+                // null never equals to anything
+                continue;
+            }
+            // cast the value to the type of column, as the keys are collected from the column
+            let key = if &value.data_type() != data_type {
+                match value
+                    .as_const_column(data_type, 1)
+                    .and_then(|col| col.get_checked(0))
+                {
+                    Ok(key) if !key.is_null() => key,
+                    _ => return Ok(None),
+                }
+            } else {
+                value.clone()
+            };
+            keys.push(key);
+        }
+        find_keys(column_name, &keys)
+    }
+}
+
+/// The entries of the looked up keys, read from the runs of a snapshot point index.
+///
+/// The segments of the runs are numbered one run after another, in the order of the runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PointIndexLookup {
+    /// number of the segments of all the runs
+    pub segment_count: u32,
+    /// column name => (segments indexed on the column, key => (segment, offset of block in segment))
+    pub columns: HashMap<String, (BTreeSet<u32>, BTreeMap<DataValue, Vec<(u32, u32)>>)>,
+}
+
+/// The blocks of a snapshot that may satisfy the equality predicates.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PointIndexCandidates {
+    /// (offset of segment, offset of block in segment)
+    pub blocks: BTreeSet<(u32, u32)>,
+    /// offsets of the segments that are not indexed, all the blocks of them are candidates
+    pub segments: BTreeSet<u32>,
+}
+
+impl PointIndexCandidates {
+    pub fn contains(&self, segment: u32, block: u32) -> bool {
+        self.segments.contains(&segment) || self.blocks.contains(&(segment, block))
+    }
+
+    /// Returns None if all the blocks of the segment are candidates.
+    pub fn blocks_of_segment(&self, segment: u32) -> Option<BTreeSet<u32>> {
+        if self.segments.contains(&segment) {
+            return None;
+        }
+        Some(
+            self.blocks
+                .range((segment, 0)..=(segment, u32::MAX))
+                .map(|(_, block)| *block)
+                .collect(),
+        )
+    }
+}
+
+trait Candidates: Sized {
+    fn and(self, other: Self) -> Self;
+    fn or(self, other: Self) -> Self;
+}
+
+impl Candidates for BTreeSet<u32> {
+    fn and(self, other: Self) -> Self {
+        self.intersection(&other).cloned().collect()
+    }
+
+    fn or(self, other: Self) -> Self {
+        self.union(&other).cloned().collect()
+    }
+}
+
+impl Candidates for PointIndexCandidates {
+    fn and(self, other: Self) -> Self {
+        let blocks = self
+            .blocks
+            .iter()
+            .filter(|(segment, block)| other.contains(*segment, *block))
+            .chain(
+                other
+                    .blocks
+                    .iter()
+                    .filter(|(segment, block)| self.contains(*segment, *block)),
+            )
+            .cloned()
+            .collect();
+        let segments = self
+            .segments
+            .intersection(&other.segments)
+            .cloned()
+            .collect();
+        PointIndexCandidates { blocks, segments }
+    }
+
+    fn or(self, other: Self) -> Self {
+        PointIndexCandidates {
+            blocks: self.blocks.union(&other.blocks).cloned().collect(),
+            segments: self.segments.union(&other.segments).cloned().collect(),
+        }
+    }
+}
@@ -15,12 +15,15 @@
 
 use std::collections::HashSet;
 
+use common_catalog::plan::Expression;
 use common_datablocks::DataBlock;
 use common_datavalues::BooleanType;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
 use common_datavalues::StringType;
 use common_datavalues::ToDataType;
 use common_exception::Result;
@@ -65,7 +68,7 @@ fn test_column_type_support() -> Result<()> {
         u64::to_data_type(),
         i64::to_data_type(),
     ]);
-    let index = BlockFilter::try_create(&[&block])?;
+    let index = BlockFilter::try_create(&[&block], &[])?;
 
     // String type and 8 integral types are supported
     assert_eq!(supported_types.len(), index.filter_block.columns().len());
@@ -106,3 +109,123 @@ fn test_column_type_support() -> Result<()> {
     });
     Ok(())
 }
+
+fn column(name: &str) -> Expression {
+    Expression::IndexedVariable {
+        name: name.to_string(),
+        data_type: StringType::new_impl(),
+    }
+}
+
+fn string(value: &str) -> Expression {
+    Expression::Constant {
+        value: DataValue::String(value.as_bytes().to_vec()),
+        data_type: StringType::new_impl(),
+    }
+}
+
+fn function(name: &str, args: Vec<Expression>) -> Expression {
+    Expression::Function {
+        name: name.to_string(),
+        args,
+        return_type: BooleanType::new_impl(),
+    }
+}
+
+#[test]
+fn test_ngram_filter() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("s", StringType::new_impl()),
+        DataField::new("t", StringType::new_impl()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec!["Alice in wonderland", "Bob"]),
+        Series::from_data(vec!["Alice in wonderland", "Bob"]),
+    ]);
+    let index = BlockFilter::try_create(&[&block], &["s".to_string()])?;
+
+    // n-gram filter is only created for the configured columns
+    let ngram_col = BlockFilter::build_ngram_filter_column_name("s");
+    assert!(index.filter_block.try_column_by_name(&ngram_col).is_ok());
+    let ngram_col = BlockFilter::build_ngram_filter_column_name("t");
+    assert!(index.filter_block.try_column_by_name(&ngram_col).is_err());
+
+    let position =
+        |needle: &str, haystack: &str| function("position", vec![string(needle), column(haystack)]);
+    let zero = Expression::Constant {
+        value: DataValue::UInt64(0),
+        data_type: u64::to_data_type(),
+    };
+    let cases = vec![
+        (
+            "like hit",
+            function("like", vec![column("s"), string("%wonder%")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "like with all parts hit",
+            function("like", vec![column("s"), string("Ali_e%land")]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "like miss",
+            function("like", vec![column("s"), string("%xyzzy%")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "like with one part miss",
+            function("like", vec![column("s"), string("Alice%xyzzy%")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "like with escaped wildcard miss",
+            function("like", vec![column("s"), string("%in\\%wo%")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "like with short parts",
+            function("like", vec![column("s"), string("%xy%zz%")]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "like without n-gram filter",
+            function("like", vec![column("t"), string("%xyzzy%")]),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "contains miss",
+            function("contains", vec![column("s"), string("xyzzy")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "contains is case sensitive",
+            function("contains", vec![column("s"), string("ALICE")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "position > 0 hit",
+            function(">", vec![position("Bob", "s"), zero.clone()]),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "position > 0 miss",
+            function(">", vec![position("xyzzy", "s"), zero.clone()]),
+            FilterEvalResult::False,
+        ),
+        (
+            "0 <> position miss",
+            function("<>", vec![zero.clone(), position("xyzzy", "s")]),
+            FilterEvalResult::False,
+        ),
+        (
+            "position = 0 is not applicable",
+            function("=", vec![position("xyzzy", "s"), zero]),
+            FilterEvalResult::NotApplicable,
+        ),
+    ];
+
+    for (name, expr, expected) in cases {
+        assert_eq!(expected, index.eval(&expr)?, "case: {}", name);
+    }
+    Ok(())
+}
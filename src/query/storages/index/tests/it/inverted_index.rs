@@ -0,0 +1,176 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_storages_index::BlockInvertedIndex;
+use common_storages_index::FilterEvalResult;
+use common_storages_index::InvertedIndexDefinitions;
+
+fn match_expr(column: &str, query: &str) -> Expression {
+    Expression::Function {
+        name: "match".to_string(),
+        args: vec![
+            Expression::IndexedVariable {
+                name: column.to_string(),
+                data_type: StringType::new_impl(),
+            },
+            Expression::Constant {
+                value: DataValue::String(query.as_bytes().to_vec()),
+                data_type: StringType::new_impl(),
+            },
+        ],
+        return_type: f64::to_data_type(),
+    }
+}
+
+fn compare(op: &str, left: Expression, right: Expression) -> Expression {
+    Expression::Function {
+        name: op.to_string(),
+        args: vec![left, right],
+        return_type: BooleanType::new_impl(),
+    }
+}
+
+fn constant(value: f64) -> Expression {
+    Expression::Constant {
+        value: DataValue::Float64(value),
+        data_type: f64::to_data_type(),
+    }
+}
+
+#[test]
+fn test_inverted_index_postings() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", i32::to_data_type()),
+        DataField::new("doc", StringType::new_impl()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec![1i32, 2, 3]),
+        Series::from_data(vec!["Hello, world", "hello hello", ""]),
+    ]);
+
+    let index = BlockInvertedIndex::try_create(&block, &["doc".to_string(), "id".to_string()])?;
+
+    // only string columns are indexed
+    assert_eq!(1, index.index.columns.len());
+    let postings = &index.index.columns["doc"];
+    assert_eq!(2, postings.terms.len());
+    assert_eq!(Some(&vec![0, 1]), postings.terms.get("hello"));
+    assert_eq!(Some(&vec![0]), postings.terms.get("world"));
+
+    let bytes = index.to_bytes()?;
+    let loaded = BlockInvertedIndex::from_bytes(index.source_schema.clone(), &bytes)?;
+    assert_eq!(index.index, loaded.index);
+    Ok(())
+}
+
+#[test]
+fn test_inverted_index_eval() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("doc", StringType::new_impl()),
+        DataField::new("title", StringType::new_impl()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec!["quick brown fox", "lazy dog"]),
+        Series::from_data(vec!["a", "b"]),
+    ]);
+    let index = BlockInvertedIndex::try_create(&block, &["doc".to_string()])?;
+
+    let cases = vec![
+        (
+            "match hit",
+            match_expr("doc", "Fox"),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "match miss",
+            match_expr("doc", "cat"),
+            FilterEvalResult::False,
+        ),
+        (
+            "one of the terms hit",
+            match_expr("doc", "cat dog"),
+            FilterEvalResult::Maybe,
+        ),
+        (
+            "column not indexed",
+            match_expr("title", "cat"),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "score > 0",
+            compare(">", match_expr("doc", "cat"), constant(0.0)),
+            FilterEvalResult::False,
+        ),
+        (
+            "0 < score",
+            compare("<", constant(0.0), match_expr("doc", "cat")),
+            FilterEvalResult::False,
+        ),
+        (
+            "score >= 0 holds for every row",
+            compare(">=", match_expr("doc", "cat"), constant(0.0)),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "score < 1 holds for unmatched rows",
+            compare("<", match_expr("doc", "cat"), constant(1.0)),
+            FilterEvalResult::NotApplicable,
+        ),
+        (
+            "and",
+            compare("and", match_expr("doc", "fox"), match_expr("doc", "cat")),
+            FilterEvalResult::False,
+        ),
+        (
+            "or",
+            compare("or", match_expr("doc", "fox"), match_expr("doc", "cat")),
+            FilterEvalResult::Maybe,
+        ),
+    ];
+
+    for (name, expr, expected) in cases {
+        assert_eq!(expected, index.eval(&expr)?, "{}", name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_inverted_index_definitions() -> Result<()> {
+    let mut definitions = InvertedIndexDefinitions::default();
+    definitions
+        .indexes
+        .insert("idx_doc".to_string(), "doc".to_string());
+    definitions
+        .indexes
+        .insert("idx_doc2".to_string(), "doc".to_string());
+
+    let mut options = BTreeMap::new();
+    options.insert(
+        "inverted_indexes".to_string(),
+        definitions.to_option_value()?,
+    );
+    let loaded = InvertedIndexDefinitions::from_options(&options)?;
+    assert_eq!(definitions, loaded);
+    assert_eq!(vec!["doc".to_string()], loaded.columns());
+
+    let empty = InvertedIndexDefinitions::from_options(&BTreeMap::new())?;
+    assert!(empty.indexes.is_empty());
+    Ok(())
+}
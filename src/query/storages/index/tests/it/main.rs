@@ -13,3 +13,5 @@
 // limitations under the License.
 
 mod filters;
+mod inverted_index;
+mod point_index;
@@ -0,0 +1,298 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use common_catalog::plan::Expression;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_storages_index::PointIndex;
+use common_storages_index::PointIndexCandidates;
+use common_storages_index::PointIndexLookup;
+use common_storages_table_meta::meta::ColumnPointIndex;
+
+fn column(name: &str) -> Expression {
+    Expression::IndexedVariable {
+        name: name.to_string(),
+        data_type: i64::to_data_type(),
+    }
+}
+
+fn constant(value: i64) -> Expression {
+    Expression::Constant {
+        value: DataValue::Int64(value),
+        data_type: i64::to_data_type(),
+    }
+}
+
+fn function(op: &str, args: Vec<Expression>) -> Expression {
+    Expression::Function {
+        name: op.to_string(),
+        args,
+        return_type: BooleanType::new_impl(),
+    }
+}
+
+fn blocks(offsets: &[u32]) -> Option<BTreeSet<u32>> {
+    Some(offsets.iter().cloned().collect())
+}
+
+#[test]
+fn test_point_index_find_blocks() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("order_id", i32::to_data_type()),
+        DataField::new("amount", i32::to_data_type()),
+    ]);
+    let block_0 = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1i32, 3, 3]),
+        Series::from_data(vec![10i32, 20, 30]),
+    ]);
+    let block_1 = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![2i32, 3]),
+        Series::from_data(vec![10i32, 20]),
+    ]);
+
+    let columns = vec!["order_id".to_string()];
+    let block_indexes = vec![
+        PointIndex::build_block_index(&block_0, &columns)?,
+        PointIndex::build_block_index(&block_1, &columns)?,
+    ];
+    assert_eq!(
+        vec![DataValue::Int64(1), DataValue::Int64(3)],
+        block_indexes[0].columns["order_id"]
+    );
+
+    let segment_index = PointIndex::build_segment_index(&block_indexes);
+    assert_eq!(3, segment_index.columns["order_id"].len());
+
+    let index = PointIndex::create(schema);
+    let cases = vec![
+        (
+            "eq",
+            function("=", vec![column("order_id"), constant(2)]),
+            blocks(&[1]),
+        ),
+        (
+            "eq reversed",
+            function("=", vec![constant(1), column("order_id")]),
+            blocks(&[0]),
+        ),
+        (
+            "eq in both",
+            function("=", vec![column("order_id"), constant(3)]),
+            blocks(&[0, 1]),
+        ),
+        (
+            "eq miss",
+            function("=", vec![column("order_id"), constant(4)]),
+            blocks(&[]),
+        ),
+        (
+            "in",
+            function("in", vec![
+                column("order_id"),
+                function("tuple", vec![constant(1), constant(4)]),
+            ]),
+            blocks(&[0]),
+        ),
+        (
+            "not indexed",
+            function("=", vec![column("amount"), constant(10)]),
+            None,
+        ),
+        (
+            "not eq",
+            function("<>", vec![column("order_id"), constant(1)]),
+            None,
+        ),
+        (
+            "and",
+            function("and", vec![
+                function("=", vec![column("order_id"), constant(3)]),
+                function("=", vec![column("amount"), constant(10)]),
+            ]),
+            blocks(&[0, 1]),
+        ),
+        (
+            "or",
+            function("or", vec![
+                function("=", vec![column("order_id"), constant(1)]),
+                function("=", vec![column("order_id"), constant(2)]),
+            ]),
+            blocks(&[0, 1]),
+        ),
+        (
+            "or not indexed",
+            function("or", vec![
+                function("=", vec![column("order_id"), constant(1)]),
+                function("=", vec![column("amount"), constant(10)]),
+            ]),
+            None,
+        ),
+    ];
+
+    for (name, expr, expected) in cases {
+        assert_eq!(
+            expected,
+            index.find_blocks(&segment_index, &expr)?,
+            "{}",
+            name
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_point_index_find_snapshot_blocks() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("order_id", i32::to_data_type()),
+        DataField::new("amount", i32::to_data_type()),
+    ]);
+    let columns = vec!["order_id".to_string()];
+    let segment_index = |blocks: Vec<Vec<i32>>| -> Result<_> {
+        let block_indexes = blocks
+            .into_iter()
+            .map(|order_ids| {
+                let amounts = vec![0i32; order_ids.len()];
+                let block = DataBlock::create(schema.clone(), vec![
+                    Series::from_data(order_ids),
+                    Series::from_data(amounts),
+                ]);
+                PointIndex::build_block_index(&block, &columns)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(PointIndex::build_segment_index(&block_indexes))
+    };
+    let location = |name: &str| (name.to_string(), 1);
+
+    // segment "a" and "b" are indexed, "c" is not.
+    let segments = vec![location("a"), location("b"), location("c")];
+    let segment_indexes = BTreeMap::from([
+        (0, segment_index(vec![vec![1, 3], vec![2, 3]])?),
+        (1, segment_index(vec![vec![4], vec![1]])?),
+    ]);
+    let run = PointIndex::build_run(&segments, &[], &segment_indexes, &columns);
+    let order_id = &run["order_id"];
+    assert_eq!(BTreeSet::from([0, 1]), order_id.segments);
+    assert_eq!(
+        (DataValue::Int64(1), vec![(0, 0), (1, 1)]),
+        order_id.keys[0]
+    );
+
+    let point_index = PointIndex::create(schema.clone());
+    let expr = function("=", vec![column("order_id"), constant(1)]);
+    let keys = point_index.lookup_keys(&expr)?;
+    assert_eq!(
+        vec![("order_id".to_string(), vec![DataValue::Int64(1)])],
+        keys
+    );
+    let found = point_index.find_snapshot_blocks(&lookup(&[&run], &[3], &keys), &expr)?;
+    assert_eq!(
+        Some(PointIndexCandidates {
+            blocks: BTreeSet::from([(0, 0), (1, 1)]),
+            segments: BTreeSet::from([2]),
+        }),
+        found
+    );
+    let found = found.unwrap();
+    assert_eq!(Some(BTreeSet::from([1])), found.blocks_of_segment(1));
+    assert_eq!(None, found.blocks_of_segment(2));
+
+    // segment "d" is added by the next commit, as a new run.
+    let new_segments = vec![location("d")];
+    let segment_indexes = BTreeMap::from([(0, segment_index(vec![vec![5, 1]])?)]);
+    let new_run = PointIndex::build_run(&new_segments, &[], &segment_indexes, &columns);
+    let expr = function("and", vec![
+        function("in", vec![
+            column("order_id"),
+            function("tuple", vec![constant(4), constant(5)]),
+        ]),
+        function("=", vec![column("amount"), constant(0)]),
+    ]);
+    let keys = point_index.lookup_keys(&expr)?;
+    let found =
+        point_index.find_snapshot_blocks(&lookup(&[&run, &new_run], &[3, 1], &keys), &expr)?;
+    assert_eq!(
+        Some(PointIndexCandidates {
+            blocks: BTreeSet::from([(1, 0), (3, 0)]),
+            segments: BTreeSet::from([2]),
+        }),
+        found
+    );
+
+    // segment "a" is removed, and the runs are merged.
+    let merged_segments = vec![location("b"), location("c"), location("d")];
+    let merged = PointIndex::build_run(
+        &merged_segments,
+        &[(&segments, &run), (&new_segments, &new_run)],
+        &BTreeMap::new(),
+        &columns,
+    );
+    let order_id = &merged["order_id"];
+    assert_eq!(BTreeSet::from([0, 2]), order_id.segments);
+    assert_eq!(
+        (DataValue::Int64(1), vec![(0, 1), (2, 0)]),
+        order_id.keys[0]
+    );
+    assert!(
+        order_id
+            .keys
+            .iter()
+            .all(|(key, _)| key != &DataValue::Int64(2))
+    );
+
+    let found = point_index.find_snapshot_blocks(&lookup(&[&merged], &[3], &keys), &expr)?;
+    assert_eq!(
+        Some(PointIndexCandidates {
+            blocks: BTreeSet::from([(0, 0), (2, 0)]),
+            segments: BTreeSet::from([1]),
+        }),
+        found
+    );
+    Ok(())
+}
+
+// Looks up the keys in the runs, as the pages of the runs are read from storage.
+fn lookup(
+    runs: &[&BTreeMap<String, ColumnPointIndex>],
+    segment_counts: &[u32],
+    keys: &[(String, Vec<DataValue>)],
+) -> PointIndexLookup {
+    let mut lookup = PointIndexLookup::default();
+    for (run, segment_count) in runs.iter().zip(segment_counts.iter()) {
+        let base = lookup.segment_count;
+        lookup.segment_count += segment_count;
+        for (column_name, keys) in keys {
+            let column = match run.get(column_name) {
+                Some(column) => column,
+                None => continue,
+            };
+            let (indexed, entries) = lookup.columns.entry(column_name.clone()).or_default();
+            indexed.extend(column.segments.iter().map(|segment| base + segment));
+            for key in keys {
+                if let Ok(pos) = column.keys.binary_search_by(|(k, _)| k.cmp(key)) {
+                    entries.entry(key.clone()).or_default().extend(
+                        column.keys[pos]
+                            .1
+                            .iter()
+                            .map(|(segment, block)| (base + segment, *block)),
+                    );
+                }
+            }
+        }
+    }
+    lookup
+}
@@ -13,12 +13,20 @@
 // limitations under the License.
 mod columns_table;
 mod keywords_table;
+mod pg_catalog;
 mod schemata_table;
 mod tables_table;
 mod views_table;
 
 pub use columns_table::ColumnsTable;
 pub use keywords_table::KeywordsTable;
+pub use pg_catalog::PgAttributeTable;
+pub use pg_catalog::PgClassTable;
+pub use pg_catalog::PgDatabaseTable;
+pub use pg_catalog::PgNamespaceTable;
+pub use pg_catalog::PgSettingsTable;
+pub use pg_catalog::PgTablesTable;
+pub use pg_catalog::PgTypeTable;
 pub use schemata_table::SchemataTable;
 pub use tables_table::TablesTable;
 pub use views_table::ViewsTable;
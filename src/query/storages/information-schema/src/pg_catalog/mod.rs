@@ -0,0 +1,29 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod pg_attribute_table;
+mod pg_class_table;
+mod pg_database_table;
+mod pg_namespace_table;
+mod pg_settings_table;
+mod pg_tables_table;
+mod pg_type_table;
+
+pub use pg_attribute_table::PgAttributeTable;
+pub use pg_class_table::PgClassTable;
+pub use pg_database_table::PgDatabaseTable;
+pub use pg_namespace_table::PgNamespaceTable;
+pub use pg_settings_table::PgSettingsTable;
+pub use pg_tables_table::PgTablesTable;
+pub use pg_type_table::PgTypeTable;
@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_storages_view::view_table::ViewTable;
+use common_storages_view::view_table::QUERY;
+
+pub struct PgAttributeTable {}
+
+impl PgAttributeTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let query = "SELECT
+            crc32(concat(database, '.', table)) AS attrelid,
+            name AS attname,
+            multi_if(
+                type = 'BOOLEAN', 16,
+                type IN ('TINYINT', 'TINYINT UNSIGNED', 'SMALLINT'), 21,
+                type IN ('SMALLINT UNSIGNED', 'INT'), 23,
+                type IN ('INT UNSIGNED', 'BIGINT'), 20,
+                type = 'BIGINT UNSIGNED', 1700,
+                type = 'FLOAT', 700,
+                type = 'DOUBLE', 701,
+                type = 'DATE', 1082,
+                type LIKE 'TIMESTAMP%', 1184,
+                type IN ('VARIANT', 'ARRAY', 'OBJECT'), 114,
+                25
+            ) AS atttypid,
+            1 AS attnum,
+            -1 AS atttypmod,
+            is_nullable = 'NO' AS attnotnull,
+            default_kind != '' AS atthasdef,
+            false AS attisdropped
+        FROM system.columns;";
+
+        let mut options = BTreeMap::new();
+        options.insert(QUERY.to_string(), query.to_string());
+        let table_info = TableInfo {
+            desc: "'pg_catalog'.'pg_attribute'".to_string(),
+            name: "pg_attribute".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                options,
+                engine: "VIEW".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        ViewTable::create(table_info)
+    }
+}
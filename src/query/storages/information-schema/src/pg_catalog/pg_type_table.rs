@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_storages_view::view_table::ViewTable;
+use common_storages_view::view_table::QUERY;
+
+pub struct PgTypeTable {}
+
+impl PgTypeTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let query = "SELECT 16 AS oid, 'bool' AS typname, crc32('pg_catalog') AS typnamespace, 1 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 17 AS oid, 'bytea' AS typname, crc32('pg_catalog') AS typnamespace, -1 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 20 AS oid, 'int8' AS typname, crc32('pg_catalog') AS typnamespace, 8 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 21 AS oid, 'int2' AS typname, crc32('pg_catalog') AS typnamespace, 2 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 23 AS oid, 'int4' AS typname, crc32('pg_catalog') AS typnamespace, 4 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 25 AS oid, 'text' AS typname, crc32('pg_catalog') AS typnamespace, -1 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 26 AS oid, 'oid' AS typname, crc32('pg_catalog') AS typnamespace, 4 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 114 AS oid, 'json' AS typname, crc32('pg_catalog') AS typnamespace, -1 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 700 AS oid, 'float4' AS typname, crc32('pg_catalog') AS typnamespace, 4 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 701 AS oid, 'float8' AS typname, crc32('pg_catalog') AS typnamespace, 8 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 1043 AS oid, 'varchar' AS typname, crc32('pg_catalog') AS typnamespace, -1 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 1082 AS oid, 'date' AS typname, crc32('pg_catalog') AS typnamespace, 4 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 1114 AS oid, 'timestamp' AS typname, crc32('pg_catalog') AS typnamespace, 8 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 1184 AS oid, 'timestamptz' AS typname, crc32('pg_catalog') AS typnamespace, 8 AS typlen, 'b' AS typtype
+            UNION ALL SELECT 1700 AS oid, 'numeric' AS typname, crc32('pg_catalog') AS typnamespace, -1 AS typlen, 'b' AS typtype;";
+
+        let mut options = BTreeMap::new();
+        options.insert(QUERY.to_string(), query.to_string());
+        let table_info = TableInfo {
+            desc: "'pg_catalog'.'pg_type'".to_string(),
+            name: "pg_type".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                options,
+                engine: "VIEW".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        ViewTable::create(table_info)
+    }
+}
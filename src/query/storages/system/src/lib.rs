@@ -31,12 +31,14 @@ mod metrics_table;
 mod one_table;
 mod processes_table;
 mod query_log_table;
+mod query_queue_table;
 mod roles_table;
 mod settings_table;
 mod stages_table;
 mod table;
 mod tables_table;
 mod tracing_table;
+mod user_quotas_table;
 mod users_table;
 
 pub use catalogs_table::CatalogsTable;
@@ -63,6 +65,7 @@ pub use query_log_table::LogType;
 pub use query_log_table::QueryLogElement;
 pub use query_log_table::QueryLogQueue;
 pub use query_log_table::QueryLogTable;
+pub use query_queue_table::QueryQueueTable;
 pub use roles_table::RolesTable;
 pub use settings_table::SettingsTable;
 pub use stages_table::StagesTable;
@@ -72,4 +75,5 @@ pub use tables_table::TablesTable;
 pub use tables_table::TablesTableWithHistory;
 pub use tables_table::TablesTableWithoutHistory;
 pub use tracing_table::TracingTable;
+pub use user_quotas_table::UserQuotasTable;
 pub use users_table::UsersTable;
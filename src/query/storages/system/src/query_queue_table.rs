@@ -0,0 +1,109 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::SyncOneBlockSystemTable;
+use crate::SyncSystemTable;
+
+pub struct QueryQueueTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for QueryQueueTable {
+    const NAME: &'static str = "system.query_queue";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let queued_queries = ctx.get_queued_queries_info();
+
+        let mut query_ids = Vec::with_capacity(queued_queries.len());
+        let mut users = Vec::with_capacity(queued_queries.len());
+        let mut workload_groups = Vec::with_capacity(queued_queries.len());
+        let mut query_texts = Vec::with_capacity(queued_queries.len());
+        let mut enqueue_times: Vec<i64> = Vec::with_capacity(queued_queries.len());
+        let mut wait_times: Vec<u64> = Vec::with_capacity(queued_queries.len());
+
+        for query in &queued_queries {
+            query_ids.push(query.query_id.clone().into_bytes());
+            users.push(query.user.clone().into_bytes());
+            workload_groups.push(query.workload_group.clone().into_bytes());
+            query_texts.push(query.query_text.clone().into_bytes());
+            enqueue_times.push(
+                query
+                    .enqueue_time
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or(Duration::from_secs(0))
+                    .as_micros() as i64,
+            );
+            wait_times.push(
+                query
+                    .enqueue_time
+                    .elapsed()
+                    .unwrap_or(Duration::from_secs(0))
+                    .as_secs(),
+            );
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(query_ids),
+            Series::from_data(users),
+            Series::from_data(workload_groups),
+            Series::from_data(query_texts),
+            Series::from_data(enqueue_times),
+            Series::from_data(wait_times),
+        ]))
+    }
+}
+
+impl QueryQueueTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("query_id", Vu8::to_data_type()),
+            DataField::new("user", Vu8::to_data_type()),
+            DataField::new("workload_group", Vu8::to_data_type()),
+            DataField::new("query_text", Vu8::to_data_type()),
+            DataField::new("enqueue_time", TimestampType::new_impl()),
+            DataField::new("wait_time", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'query_queue'".to_string(),
+            name: "query_queue".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemQueryQueue".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        SyncOneBlockSystemTable::create(QueryQueueTable { table_info })
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::catalog::CatalogManager;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+use common_users::UserQuotaTracker;
+
+use crate::table::AsyncOneBlockSystemTable;
+use crate::table::AsyncSystemTable;
+
+pub struct UserQuotasTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for UserQuotasTable {
+    const NAME: &'static str = "system.user_quotas";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let users = UserApiProvider::instance().get_users(&tenant).await?;
+        // The storage quota is checked against the storage of the whole tenant.
+        let storage_usage = CatalogManager::instance()
+            .get_tenant_storage_usage(&tenant)
+            .await?;
+
+        let tracker = UserQuotaTracker::instance();
+        let usages: Vec<_> = users
+            .iter()
+            .map(|x| tracker.try_get_user_usage(&tenant, &x.identity().to_string()))
+            .collect();
+
+        let names: Vec<&str> = users.iter().map(|x| x.name.as_str()).collect();
+        let hostnames: Vec<&str> = users.iter().map(|x| x.hostname.as_str()).collect();
+        let max_cpus: Vec<u64> = users.iter().map(|x| x.quota.max_cpu).collect();
+        let used_threads: Vec<u64> = usages
+            .iter()
+            .map(|x| x.as_ref().map(|v| v.get_used_threads()).unwrap_or_default())
+            .collect();
+        let max_memories: Vec<u64> = users.iter().map(|x| x.quota.max_memory_in_bytes).collect();
+        let memory_usages: Vec<u64> = usages
+            .iter()
+            .map(|x| x.as_ref().map(|v| v.get_memory_usage()).unwrap_or_default())
+            .collect();
+        let max_storages: Vec<u64> = users.iter().map(|x| x.quota.max_storage_in_bytes).collect();
+        let storage_usages: Vec<u64> = vec![storage_usage; users.len()];
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(hostnames),
+            Series::from_data(max_cpus),
+            Series::from_data(used_threads),
+            Series::from_data(max_memories),
+            Series::from_data(memory_usages),
+            Series::from_data(max_storages),
+            Series::from_data(storage_usages),
+        ]))
+    }
+}
+
+impl UserQuotasTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("hostname", Vu8::to_data_type()),
+            DataField::new("max_cpu", u64::to_data_type()),
+            DataField::new("used_threads", u64::to_data_type()),
+            DataField::new("max_memory_in_bytes", u64::to_data_type()),
+            DataField::new("memory_usage", u64::to_data_type()),
+            DataField::new("max_storage_in_bytes", u64::to_data_type()),
+            DataField::new("storage_usage", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'user_quotas'".to_string(),
+            name: "user_quotas".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemUserQuotas".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        AsyncOneBlockSystemTable::create(UserQuotasTable { table_info })
+    }
+}
@@ -15,7 +15,18 @@
 pub use v0::ColumnMeta;
 pub use v1::BlockFilter;
 pub use v1::BlockMeta;
+pub use v1::BlockPointIndex;
+pub use v1::ColumnPointIndex;
+pub use v1::ColumnPostings;
+pub use v1::InvertedIndex;
+pub use v1::PointIndexPage;
+pub use v1::PointIndexPageMeta;
+pub use v1::PointIndexRun;
+pub use v1::PointIndexRunColumn;
+pub use v1::PointIndexRunMeta;
 pub use v1::SegmentInfo;
+pub use v1::SegmentPointIndex;
+pub use v1::SnapshotPointIndex;
 pub use v1::TableSnapshot;
 pub use v1::TableSnapshotLite;
 pub use v1::TableSnapshotStatistics;
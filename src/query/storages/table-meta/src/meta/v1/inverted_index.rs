@@ -0,0 +1,54 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::meta::common::FormatVersion;
+use crate::meta::Versioned;
+
+/// Token postings of the inverted indexes of a Block.
+///
+/// Kept in a file alongside the bloom filter index of the block, only the
+/// columns that have an inverted index created on them at writing are included.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InvertedIndex {
+    /// format version
+    format_version: FormatVersion,
+    /// postings of indexed columns, keyed by column name
+    pub columns: HashMap<String, ColumnPostings>,
+}
+
+/// Postings of a string column, maps each term to the rows it occurs in.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ColumnPostings {
+    /// term => offsets of rows in block, in ascending order
+    pub terms: BTreeMap<String, Vec<u32>>,
+}
+
+impl InvertedIndex {
+    pub fn new(columns: HashMap<String, ColumnPostings>) -> Self {
+        Self {
+            format_version: InvertedIndex::VERSION,
+            columns,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
@@ -13,11 +13,24 @@
 //  limitations under the License.
 
 mod index;
+mod inverted_index;
+mod point_index;
 mod segment;
 mod snapshot;
 mod table_snapshot_statistics;
 
 pub use index::BlockFilter;
+pub use inverted_index::ColumnPostings;
+pub use inverted_index::InvertedIndex;
+pub use point_index::BlockPointIndex;
+pub use point_index::ColumnPointIndex;
+pub use point_index::PointIndexPage;
+pub use point_index::PointIndexPageMeta;
+pub use point_index::PointIndexRun;
+pub use point_index::PointIndexRunColumn;
+pub use point_index::PointIndexRunMeta;
+pub use point_index::SegmentPointIndex;
+pub use point_index::SnapshotPointIndex;
 pub use segment::BlockMeta;
 pub use segment::SegmentInfo;
 pub use snapshot::TableSnapshot;
@@ -0,0 +1,168 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use common_datavalues::DataValue;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::meta::common::FormatVersion;
+use crate::meta::Location;
+use crate::meta::Versioned;
+
+/// Distinct values of the point indexed columns of a Block.
+///
+/// Kept in a file alongside the bloom filter index of the block, it is what the
+/// [SegmentPointIndex] of the segments that the block belongs to are built from.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BlockPointIndex {
+    /// format version
+    format_version: FormatVersion,
+    /// sorted distinct non-null values, keyed by column name
+    pub columns: BTreeMap<String, Vec<DataValue>>,
+}
+
+impl BlockPointIndex {
+    pub fn new(columns: BTreeMap<String, Vec<DataValue>>) -> Self {
+        Self {
+            format_version: BlockPointIndex::VERSION,
+            columns,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// Sorted keys of the point indexed columns of a Segment, with the blocks they occur in.
+///
+/// Kept in a file named after the segment, it is what the runs of the [SnapshotPointIndex]
+/// are built from.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SegmentPointIndex {
+    /// format version
+    format_version: FormatVersion,
+    /// key => offsets of blocks in segment, sorted by key, keyed by column name
+    pub columns: BTreeMap<String, Vec<(DataValue, Vec<u32>)>>,
+}
+
+impl SegmentPointIndex {
+    pub fn new(columns: BTreeMap<String, Vec<(DataValue, Vec<u32>)>>) -> Self {
+        Self {
+            format_version: SegmentPointIndex::VERSION,
+            columns,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// The sorted runs of the point index of a Snapshot.
+///
+/// Each commit writes one run for the segments it adds, sorted by key and split into pages,
+/// so that an equality lookup only reads the pages whose key fences cover the key, and the
+/// segments and blocks listed there. The runs are merged into one once there are too many.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SnapshotPointIndex {
+    /// format version
+    format_version: FormatVersion,
+    /// oldest first
+    pub runs: Vec<PointIndexRun>,
+}
+
+impl SnapshotPointIndex {
+    pub fn new(runs: Vec<PointIndexRun>) -> Self {
+        Self {
+            format_version: SnapshotPointIndex::VERSION,
+            runs,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// A sorted run of a snapshot point index, which indexes the blocks of some segments.
+///
+/// The run file holds the pages of the columns, followed by the [PointIndexRunMeta].
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PointIndexRun {
+    pub location: String,
+    /// segments indexed by the run, which are referred to by offset, entries of the
+    /// segments that are no longer in the snapshot are ignored.
+    pub segments: Vec<Location>,
+    /// keyed by column name
+    pub columns: BTreeMap<String, PointIndexRunColumn>,
+    /// position of the [PointIndexRunMeta] in the run file
+    pub meta_offset: u64,
+    pub meta_len: u64,
+}
+
+/// A point indexed column of a run.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PointIndexRunColumn {
+    /// offsets of the segments that are indexed on this column, blocks of the other
+    /// segments may contain any key.
+    pub segments: BTreeSet<u32>,
+    /// the min and max key of the column in the run, None if there is no key
+    pub range: Option<(DataValue, DataValue)>,
+}
+
+/// The pages of the columns of a run file, kept at the end of it.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PointIndexRunMeta {
+    /// format version
+    format_version: FormatVersion,
+    /// sorted by key, keyed by column name
+    pub columns: BTreeMap<String, Vec<PointIndexPageMeta>>,
+}
+
+impl PointIndexRunMeta {
+    pub fn new(columns: BTreeMap<String, Vec<PointIndexPageMeta>>) -> Self {
+        Self {
+            format_version: PointIndexRunMeta::VERSION,
+            columns,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
+
+/// The key fences and the position of a page in the run file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PointIndexPageMeta {
+    pub min: DataValue,
+    pub max: DataValue,
+    pub offset: u64,
+    pub len: u64,
+}
+
+/// key => (offset of segment in run, offset of block in segment), sorted by key
+pub type PointIndexPage = Vec<(DataValue, Vec<(u32, u32)>)>;
+
+/// All the keys of a point indexed column of a run, as a run is built or merged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnPointIndex {
+    /// offsets of the segments that are indexed on this column
+    pub segments: BTreeSet<u32>,
+    pub keys: PointIndexPage,
+}
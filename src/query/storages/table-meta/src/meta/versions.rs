@@ -20,6 +20,11 @@ use common_exception::ErrorCode;
 use crate::meta::v0;
 use crate::meta::v1;
 use crate::meta::v1::BlockFilter;
+use crate::meta::v1::BlockPointIndex;
+use crate::meta::v1::InvertedIndex;
+use crate::meta::v1::PointIndexRunMeta;
+use crate::meta::v1::SegmentPointIndex;
+use crate::meta::v1::SnapshotPointIndex;
 use crate::meta::Versioned;
 
 // Here versions of meta are tagged with numeric values
@@ -89,6 +94,16 @@ pub enum BlockBloomFilterIndexVersion {
     V2(PhantomData<v1::BlockFilter>),
 }
 
+impl Versioned<1> for InvertedIndex {}
+
+impl Versioned<2> for BlockPointIndex {}
+
+impl Versioned<2> for SegmentPointIndex {}
+
+impl Versioned<2> for SnapshotPointIndex {}
+
+impl Versioned<1> for PointIndexRunMeta {}
+
 mod converters {
 
     use super::*;
@@ -30,6 +30,38 @@ pub const OPT_KEY_STORAGE_FORMAT: &str = "storage_format";
 /// If both OPT_KEY_SNAPSHOT_LOC and OPT_KEY_SNAPSHOT_LOCATION exist, the latter will be used
 pub const OPT_KEY_LEGACY_SNAPSHOT_LOC: &str = "snapshot_loc";
 
+/// Materialized view option keys
+///
+/// A materialized view is a FUSE table carrying its definition in the table options:
+/// - the canonical text of the defining query
+/// - the source table it is maintained from, and the source snapshot it reflects
+/// - how each output column is merged during an incremental refresh
+pub const OPT_KEY_MV_QUERY: &str = "mv_query";
+pub const OPT_KEY_MV_SOURCE_DATABASE: &str = "mv_source_database";
+pub const OPT_KEY_MV_SOURCE_TABLE: &str = "mv_source_table";
+pub const OPT_KEY_MV_SOURCE_TABLE_ID: &str = "mv_source_table_id";
+pub const OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION: &str = "mv_source_snapshot_location";
+pub const OPT_KEY_MV_MERGE: &str = "mv_merge";
+
+/// The source snapshot a materialized view reflected before a refresh, a json string or null
+///
+/// Only present in the table info a refresh commits with, it is checked against the latest
+/// state of the view and never stored in the meta.
+pub const OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION: &str = "mv_refresh_base_snapshot_location";
+
+/// Materialized views maintained from the table, a json array of database and view names
+pub const OPT_KEY_MATERIALIZED_VIEWS: &str = "materialized_views";
+
+/// Inverted indexes created on the table, a json object of index name => column name
+pub const OPT_KEY_INVERTED_INDEXES: &str = "inverted_indexes";
+
+/// The row access policy attached to the table, a json object of the policy name and
+/// the columns bound to its parameters
+pub const OPT_KEY_ROW_ACCESS_POLICY: &str = "row_access_policy";
+
+/// Masking policies set on the columns of the table, a json object of column name => policy name
+pub const OPT_KEY_MASKING_POLICIES: &str = "masking_policies";
+
 /// Table option keys that reserved for internal usage only
 /// - Users are not allowed to specified this option keys in DDL
 /// - Should not be shown in `show create table` statement
@@ -37,6 +69,17 @@ pub static RESERVED_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_DATABASE_ID);
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
+    r.insert(OPT_KEY_MV_QUERY);
+    r.insert(OPT_KEY_MV_SOURCE_DATABASE);
+    r.insert(OPT_KEY_MV_SOURCE_TABLE);
+    r.insert(OPT_KEY_MV_SOURCE_TABLE_ID);
+    r.insert(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION);
+    r.insert(OPT_KEY_MV_MERGE);
+    r.insert(OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION);
+    r.insert(OPT_KEY_MATERIALIZED_VIEWS);
+    r.insert(OPT_KEY_INVERTED_INDEXES);
+    r.insert(OPT_KEY_ROW_ACCESS_POLICY);
+    r.insert(OPT_KEY_MASKING_POLICIES);
     r
 });
 
@@ -45,6 +88,17 @@ pub static INTERNAL_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
     r.insert(OPT_KEY_DATABASE_ID);
+    r.insert(OPT_KEY_MV_QUERY);
+    r.insert(OPT_KEY_MV_SOURCE_DATABASE);
+    r.insert(OPT_KEY_MV_SOURCE_TABLE);
+    r.insert(OPT_KEY_MV_SOURCE_TABLE_ID);
+    r.insert(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION);
+    r.insert(OPT_KEY_MV_MERGE);
+    r.insert(OPT_KEY_MV_REFRESH_BASE_SNAPSHOT_LOCATION);
+    r.insert(OPT_KEY_MATERIALIZED_VIEWS);
+    r.insert(OPT_KEY_INVERTED_INDEXES);
+    r.insert(OPT_KEY_ROW_ACCESS_POLICY);
+    r.insert(OPT_KEY_MASKING_POLICIES);
     r
 });
 
@@ -0,0 +1,354 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use common_catalog::catalog::Catalog;
+use common_catalog::table::Table;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_storages_table_meta::table::OPT_KEY_MATERIALIZED_VIEWS;
+use common_storages_table_meta::table::OPT_KEY_MV_MERGE;
+use common_storages_table_meta::table::OPT_KEY_MV_QUERY;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_DATABASE;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_TABLE;
+use common_storages_table_meta::table::OPT_KEY_MV_SOURCE_TABLE_ID;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How the partial results of an output column are combined,
+/// when the rows computed from newly appended data are merged into a materialized view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeKind {
+    /// A GROUP BY key
+    Key,
+    /// SUM and COUNT
+    Sum,
+    Min,
+    Max,
+}
+
+impl MergeKind {
+    /// The aggregate function that merges two partial results, `None` for keys.
+    pub fn merge_function(&self) -> Option<&'static str> {
+        match self {
+            MergeKind::Key => None,
+            MergeKind::Sum => Some("SUM"),
+            MergeKind::Min => Some("MIN"),
+            MergeKind::Max => Some("MAX"),
+        }
+    }
+}
+
+impl Display for MergeKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MergeKind::Key => write!(f, "key"),
+            MergeKind::Sum => write!(f, "sum"),
+            MergeKind::Min => write!(f, "min"),
+            MergeKind::Max => write!(f, "max"),
+        }
+    }
+}
+
+impl FromStr for MergeKind {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "key" => Ok(MergeKind::Key),
+            "sum" => Ok(MergeKind::Sum),
+            "min" => Ok(MergeKind::Min),
+            "max" => Ok(MergeKind::Max),
+            other => Err(ErrorCode::Internal(format!(
+                "unknown materialized view merge kind: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// The definition of a materialized view, kept in the options of its FUSE table.
+///
+/// An incremental refresh appends partial rows, a key may have several rows until the
+/// next full refresh, the view is read through [`MaterializedViewMeta::merged_query_sql`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaterializedViewMeta {
+    /// Canonical text of the defining query, the source table is fully qualified
+    pub query: String,
+    pub source_database: String,
+    pub source_table: String,
+    pub source_table_id: u64,
+    /// The snapshot of the source table that the content of the view reflects,
+    /// `None` if the source table was empty.
+    pub source_snapshot_location: Option<String>,
+    /// One per output column, in the order of the view schema
+    pub merge_kinds: Vec<MergeKind>,
+}
+
+impl MaterializedViewMeta {
+    pub fn is_materialized_view(table_info: &TableInfo) -> bool {
+        table_info.options().contains_key(OPT_KEY_MV_QUERY)
+    }
+
+    /// Returns `None` if the table is not a materialized view.
+    pub fn from_table_info(table_info: &TableInfo) -> Result<Option<Self>> {
+        let options = table_info.options();
+        let query = match options.get(OPT_KEY_MV_QUERY) {
+            None => return Ok(None),
+            Some(query) => query.clone(),
+        };
+
+        let get = |key: &str| -> Result<String> {
+            options.get(key).cloned().ok_or_else(|| {
+                ErrorCode::Internal(format!(
+                    "materialized view {} is missing option {}",
+                    table_info.desc, key
+                ))
+            })
+        };
+
+        let source_table_id = get(OPT_KEY_MV_SOURCE_TABLE_ID)?
+            .parse::<u64>()
+            .map_err(|e| {
+                ErrorCode::Internal(format!(
+                    "invalid {} of materialized view {}: {}",
+                    OPT_KEY_MV_SOURCE_TABLE_ID, table_info.desc, e
+                ))
+            })?;
+
+        let merge_kinds = get(OPT_KEY_MV_MERGE)?
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(MergeKind::from_str)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(MaterializedViewMeta {
+            query,
+            source_database: get(OPT_KEY_MV_SOURCE_DATABASE)?,
+            source_table: get(OPT_KEY_MV_SOURCE_TABLE)?,
+            source_table_id,
+            source_snapshot_location: options.get(OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION).cloned(),
+            merge_kinds,
+        }))
+    }
+
+    pub fn to_options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        options.insert(OPT_KEY_MV_QUERY.to_string(), self.query.clone());
+        options.insert(
+            OPT_KEY_MV_SOURCE_DATABASE.to_string(),
+            self.source_database.clone(),
+        );
+        options.insert(
+            OPT_KEY_MV_SOURCE_TABLE.to_string(),
+            self.source_table.clone(),
+        );
+        options.insert(
+            OPT_KEY_MV_SOURCE_TABLE_ID.to_string(),
+            self.source_table_id.to_string(),
+        );
+        if let Some(location) = &self.source_snapshot_location {
+            options.insert(
+                OPT_KEY_MV_SOURCE_SNAPSHOT_LOCATION.to_string(),
+                location.clone(),
+            );
+        }
+        let merge = self
+            .merge_kinds
+            .iter()
+            .map(|kind| kind.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        options.insert(OPT_KEY_MV_MERGE.to_string(), merge);
+        options
+    }
+
+    /// Recomputes the whole view from the source table.
+    pub fn full_refresh_sql(&self, catalog: &str, database: &str, view: &str) -> Result<String> {
+        Ok(format!(
+            "INSERT OVERWRITE {} {}",
+            quote_table_name(catalog, database, view)?,
+            self.query
+        ))
+    }
+
+    /// Appends the rows computed from the data appended to the source table to the view.
+    ///
+    /// The defining query is evaluated against the appended data only, the partial rows
+    /// are merged with the existing ones of the same keys when the view is read.
+    pub fn incremental_refresh_sql(
+        &self,
+        catalog: &str,
+        database: &str,
+        view: &str,
+    ) -> Result<String> {
+        Ok(format!(
+            "INSERT INTO {} {}",
+            quote_table_name(catalog, database, view)?,
+            self.query
+        ))
+    }
+
+    /// Reads the view with the partial rows of the same keys merged, the result is
+    /// the same as the defining query evaluated against the source table.
+    pub fn merged_query_sql(
+        &self,
+        catalog: &str,
+        database: &str,
+        view: &str,
+        column_names: &[String],
+    ) -> Result<String> {
+        if column_names.len() != self.merge_kinds.len() {
+            return Err(ErrorCode::Internal(format!(
+                "materialized view {}.{} has {} columns, but {} merge kinds",
+                database,
+                view,
+                column_names.len(),
+                self.merge_kinds.len()
+            )));
+        }
+
+        let mut projections = Vec::with_capacity(column_names.len());
+        let mut keys = vec![];
+        for (name, kind) in column_names.iter().zip(self.merge_kinds.iter()) {
+            let column = quote_ident(name)?;
+            match kind.merge_function() {
+                None => {
+                    projections.push(column.clone());
+                    keys.push(column);
+                }
+                Some(func) => projections.push(format!("{}({}) AS {}", func, column, column)),
+            }
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            projections.join(", "),
+            quote_table_name(catalog, database, view)?
+        );
+        if !keys.is_empty() {
+            sql.push_str(&format!(" GROUP BY {}", keys.join(", ")));
+        }
+        Ok(sql)
+    }
+}
+
+/// The materialized views maintained from a table, kept in the options of the table,
+/// so that the views of a table are found without listing the databases.
+///
+/// Entries are not removed when a view is dropped along with its database, so they
+/// must be checked against the views they refer to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterializedViewRefs {
+    /// `(database, view)` in the catalog of the table
+    pub views: BTreeSet<(String, String)>,
+}
+
+impl MaterializedViewRefs {
+    pub fn from_table_info(table_info: &TableInfo) -> Result<Self> {
+        match table_info.options().get(OPT_KEY_MATERIALIZED_VIEWS) {
+            Some(value) => Ok(serde_json::from_str(value)?),
+            None => Ok(MaterializedViewRefs::default()),
+        }
+    }
+
+    /// Adds (`add` is true) or removes a view in the options of its source table.
+    ///
+    /// Nothing is done if the source table has been dropped.
+    pub async fn update(
+        catalog: &dyn Catalog,
+        tenant: &str,
+        meta: &MaterializedViewMeta,
+        database: &str,
+        view: &str,
+        add: bool,
+    ) -> Result<()> {
+        let entry = (database.to_string(), view.to_string());
+        loop {
+            let source = match catalog
+                .get_table(tenant, &meta.source_database, &meta.source_table)
+                .await
+            {
+                Ok(source) if source.get_id() == meta.source_table_id => source,
+                Ok(_) => return Ok(()),
+                Err(e) if e.code() == ErrorCode::UNKNOWN_TABLE => return Ok(()),
+                Err(e) => return Err(e),
+            };
+
+            let table_info = source.get_table_info();
+            let mut refs = MaterializedViewRefs::from_table_info(table_info)?;
+            let changed = if add {
+                refs.views.insert(entry.clone())
+            } else {
+                refs.views.remove(&entry)
+            };
+            if !changed {
+                return Ok(());
+            }
+
+            let value = if refs.views.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&refs)?)
+            };
+            let req = UpsertTableOptionReq {
+                table_id: table_info.ident.table_id,
+                seq: MatchSeq::Exact(table_info.ident.seq),
+                options: HashMap::from([(OPT_KEY_MATERIALIZED_VIEWS.to_owned(), value)]),
+            };
+            // The source table is updated by every commit to it, read it again and retry.
+            match catalog
+                .upsert_table_option(tenant, &meta.source_database, req)
+                .await
+            {
+                Ok(_) => return Ok(()),
+                Err(e) if e.code() == ErrorCode::TABLE_VERSION_MISMATCHED => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Quotes an identifier with backticks.
+///
+/// The tokenizer has no escape for a backtick inside a quoted identifier,
+/// so such names can't be quoted and are rejected.
+pub fn quote_ident(ident: &str) -> Result<String> {
+    if ident.contains('`') {
+        return Err(ErrorCode::SemanticError(format!(
+            "identifier {} containing backtick is not supported in materialized view",
+            ident
+        )));
+    }
+    Ok(format!("`{}`", ident))
+}
+
+pub fn quote_table_name(catalog: &str, database: &str, table: &str) -> Result<String> {
+    Ok(format!(
+        "{}.{}.{}",
+        quote_ident(catalog)?,
+        quote_ident(database)?,
+        quote_ident(table)?
+    ))
+}
@@ -16,17 +16,41 @@ use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
-use jwtk::jwk::RemoteJwksVerifier;
-use jwtk::HeaderAndClaims;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
 
-pub struct JwtAuthenticator {
-    // Todo(youngsofun): verify settings, like issuer
-    verifier: RemoteJwksVerifier,
+use crate::jwt::jwks::JwksKeys;
+
+/// A trusted issuer of JWT, and how its claims map to databend users.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwtIssuerConfig {
+    /// Matched with the `iss` claim of tokens.
+    pub issuer: String,
+    /// File path or http(s) url of the JWKS document of the issuer.
+    pub jwks: String,
+    /// Tokens must contain one of the audiences in `aud`, not checked if empty.
+    pub audiences: Vec<String>,
+    pub user_claim: String,
+    pub tenant_claim: String,
+    pub role_claim: String,
 }
 
-#[derive(Default, Deserialize, Serialize)]
+impl Default for JwtIssuerConfig {
+    fn default() -> Self {
+        JwtIssuerConfig {
+            issuer: "".to_string(),
+            jwks: "".to_string(),
+            audiences: vec![],
+            user_claim: "sub".to_string(),
+            tenant_claim: "tenant_id".to_string(),
+            role_claim: "role".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct EnsureUser {
     pub roles: Option<Vec<String>>,
 }
@@ -63,26 +87,181 @@ impl CustomClaims {
     }
 }
 
+/// The user identity taken from the claims of a verified JWT.
+#[derive(Clone, Debug)]
+pub struct JwtIdentity {
+    pub user_name: String,
+    pub tenant_id: Option<String>,
+    pub roles: Vec<String>,
+    pub ensure_user: Option<EnsureUser>,
+}
+
+struct JwtIssuer {
+    config: JwtIssuerConfig,
+    keys: JwksKeys,
+}
+
+pub struct JwtAuthenticator {
+    issuers: Vec<JwtIssuer>,
+    // Keys of `jwt_key_file`, which accept tokens of any issuer with the default claims.
+    default_keys: Option<JwksKeys>,
+}
+
 impl JwtAuthenticator {
-    pub async fn try_create(jwt_key_file: String) -> Result<Option<Self>> {
-        if jwt_key_file.is_empty() {
+    pub async fn try_create(
+        jwt_key_file: String,
+        refresh_interval: Duration,
+        min_reload_interval: Duration,
+        issuers: Vec<JwtIssuerConfig>,
+    ) -> Result<Option<Self>> {
+        if jwt_key_file.is_empty() && issuers.is_empty() {
             return Ok(None);
         }
-        let mut verifier =
-            RemoteJwksVerifier::new(jwt_key_file, None, Duration::from_secs(15 * 60));
-        verifier.set_require_kid(false);
-        Ok(Some(JwtAuthenticator { verifier }))
-    }
 
-    pub async fn parse_jwt(&self, token: &str) -> Result<HeaderAndClaims<CustomClaims>> {
-        match self.verifier.verify::<CustomClaims>(token).await {
-            Ok(c) => match c.claims().sub {
-                None => Err(ErrorCode::AuthenticateFailure(
-                    "missing field `subject` in jwt",
-                )),
-                Some(_) => Ok(c),
-            },
-            Err(e) => Err(ErrorCode::AuthenticateFailure(e.to_string())),
+        let mut jwt_issuers = Vec::with_capacity(issuers.len());
+        for config in issuers.into_iter() {
+            if config.issuer.is_empty() || config.jwks.is_empty() {
+                return Err(ErrorCode::InvalidConfig(
+                    "issuer and jwks of jwt issuer must not be empty",
+                ));
+            }
+
+            let keys =
+                JwksKeys::try_create(config.jwks.clone(), refresh_interval, min_reload_interval)?;
+            // Fail at startup on an invalid key source instead of on each login.
+            keys.load().await.map_err(|e| {
+                ErrorCode::InvalidConfig(format!(
+                    "cannot load jwks of jwt issuer '{}': {}",
+                    config.issuer,
+                    e.message()
+                ))
+            })?;
+            jwt_issuers.push(JwtIssuer { config, keys });
         }
+
+        let default_keys = match jwt_key_file.is_empty() {
+            true => None,
+            false => {
+                let keys =
+                    JwksKeys::try_create(jwt_key_file, refresh_interval, min_reload_interval)?;
+                keys.load().await.map_err(|e| {
+                    ErrorCode::InvalidConfig(format!("cannot load jwt_key_file: {}", e.message()))
+                })?;
+                Some(keys)
+            }
+        };
+
+        Ok(Some(JwtAuthenticator {
+            issuers: jwt_issuers,
+            default_keys,
+        }))
     }
+
+    pub async fn parse_jwt(&self, token: &str) -> Result<JwtIdentity> {
+        let header = decode_segment(token, 0)?;
+        let claims = decode_segment(token, 1)?;
+        let kid = header.get("kid").and_then(Value::as_str);
+        let iss = claims.get("iss").and_then(Value::as_str);
+
+        let issuer = self
+            .issuers
+            .iter()
+            .find(|issuer| Some(issuer.config.issuer.as_str()) == iss);
+
+        let default_config = JwtIssuerConfig::default();
+        let (keys, config) = match (issuer, &self.default_keys) {
+            (Some(issuer), _) => (&issuer.keys, &issuer.config),
+            (None, Some(keys)) => (keys, &default_config),
+            (None, None) => {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "untrusted jwt issuer '{}'",
+                    iss.unwrap_or_default()
+                )));
+            }
+        };
+
+        keys.verify(token, kid).await?;
+        check_audience(config, &claims)?;
+        map_claims(config, &claims)
+    }
+}
+
+/// The expiration time (the `exp` claim) of a JWT in seconds since the epoch, without verifying.
+pub fn jwt_expiration(token: &str) -> Result<Option<u64>> {
+    let claims = decode_segment(token, 1)?;
+    Ok(claims
+        .get("exp")
+        .and_then(|exp| exp.as_u64().or_else(|| exp.as_f64().map(|exp| exp as u64))))
+}
+
+// Decode the header or the claims of a JWT, without verifying.
+fn decode_segment(token: &str, index: usize) -> Result<Map<String, Value>> {
+    let segment = token
+        .split('.')
+        .nth(index)
+        .ok_or_else(|| ErrorCode::AuthenticateFailure("invalid jwt format"))?;
+    let decoded = base64::decode_config(segment, base64::URL_SAFE_NO_PAD)
+        .map_err(|e| ErrorCode::AuthenticateFailure(format!("invalid jwt encoding: {}", e)))?;
+    serde_json::from_slice(&decoded)
+        .map_err(|e| ErrorCode::AuthenticateFailure(format!("invalid jwt json: {}", e)))
+}
+
+fn string_values(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(v)) => vec![v.clone()],
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(|v| v.as_str().map(|v| v.to_string()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn check_audience(config: &JwtIssuerConfig, claims: &Map<String, Value>) -> Result<()> {
+    if config.audiences.is_empty() {
+        return Ok(());
+    }
+
+    let audiences = string_values(claims.get("aud"));
+    match audiences.iter().any(|aud| config.audiences.contains(aud)) {
+        true => Ok(()),
+        false => Err(ErrorCode::AuthenticateFailure(format!(
+            "jwt audience {:?} is not accepted by issuer '{}'",
+            audiences, config.issuer
+        ))),
+    }
+}
+
+fn map_claims(config: &JwtIssuerConfig, claims: &Map<String, Value>) -> Result<JwtIdentity> {
+    let user_name = match claims.get(&config.user_claim).and_then(Value::as_str) {
+        Some(user_name) => user_name.to_string(),
+        None if config.user_claim == "sub" => {
+            return Err(ErrorCode::AuthenticateFailure(
+                "missing field `subject` in jwt",
+            ));
+        }
+        None => {
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "missing field `{}` in jwt",
+                config.user_claim
+            )));
+        }
+    };
+
+    let ensure_user = match claims.get("ensure_user") {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(serde_json::from_value(v.clone()).map_err(|e| {
+            ErrorCode::AuthenticateFailure(format!("invalid ensure_user in jwt: {}", e))
+        })?),
+    };
+
+    Ok(JwtIdentity {
+        user_name,
+        tenant_id: claims
+            .get(&config.tenant_claim)
+            .and_then(Value::as_str)
+            .map(|v| v.to_string()),
+        roles: string_values(claims.get(&config.role_claim)),
+        ensure_user,
+    })
 }
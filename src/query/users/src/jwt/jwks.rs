@@ -0,0 +1,230 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Mutex;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use jwtk::jwk::JwkSet;
+use jwtk::SomePublicKey;
+use parking_lot::RwLock;
+use tracing::info;
+use tracing::warn;
+
+// Timeouts of fetching the keys from a http(s) url, so a hanging key source
+// cannot block the logins for long.
+const JWKS_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const JWKS_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct CachedKeys {
+    keys: Vec<(Option<String>, Arc<SomePublicKey>)>,
+    loaded_at: Instant,
+}
+
+impl CachedKeys {
+    fn contains(&self, kid: &str) -> bool {
+        self.keys.iter().any(|(k, _)| k.as_deref() == Some(kid))
+    }
+}
+
+/// The keys of a JWKS document, loaded from a file path or a http(s) url.
+///
+/// The keys are reloaded when they are older than `refresh_interval`, or when a
+/// token is signed by a key id not in the set, e.g. the keys are rotated.
+/// Reloading is tried at most once per `min_reload_interval`, whether it succeeds
+/// or not, so bad tokens or a failing key source cannot flood the key source.
+pub struct JwksKeys {
+    location: String,
+    refresh_interval: Duration,
+    min_reload_interval: Duration,
+    client: reqwest::Client,
+    cached: RwLock<Option<Arc<CachedKeys>>>,
+    // When the keys were last tried to reload.
+    reloaded_at: RwLock<Option<Instant>>,
+    // Concurrent reloads wait for the one in progress and use its keys.
+    reload_lock: Mutex<()>,
+}
+
+impl JwksKeys {
+    pub fn try_create(
+        location: String,
+        refresh_interval: Duration,
+        min_reload_interval: Duration,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(JWKS_CONNECT_TIMEOUT)
+            .timeout(JWKS_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ErrorCode::InvalidConfig(format!("cannot create jwks client: {}", e)))?;
+        Ok(JwksKeys {
+            location,
+            refresh_interval,
+            min_reload_interval,
+            client,
+            cached: RwLock::new(None),
+            reloaded_at: RwLock::new(None),
+            reload_lock: Mutex::new(()),
+        })
+    }
+
+    /// Load the keys, fails if they can't be loaded or none of them is supported.
+    pub async fn load(&self) -> Result<()> {
+        let keys = self.reload().await?;
+        match keys.keys.is_empty() {
+            true => Err(ErrorCode::InvalidConfig(format!(
+                "no supported jwk found in {}",
+                self.location
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// Verify the signature and the time claims of the token.
+    pub async fn verify(&self, token: &str, kid: Option<&str>) -> Result<()> {
+        let keys = self.get_keys(kid).await?;
+        let candidates = keys
+            .keys
+            .iter()
+            .filter(|(k, _)| match (kid, k) {
+                (Some(kid), Some(k)) => kid == k,
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "no jwk found for kid {} in {}",
+                kid.unwrap_or_default(),
+                self.location
+            )));
+        }
+
+        let mut last_error = None;
+        for (_, key) in candidates {
+            match jwtk::verify::<serde_json::Map<String, serde_json::Value>>(token, key.as_ref()) {
+                Ok(_) => return Ok(()),
+                Err(cause) => last_error = Some(cause),
+            }
+        }
+
+        Err(ErrorCode::AuthenticateFailure(
+            last_error.map(|e| e.to_string()).unwrap_or_default(),
+        ))
+    }
+
+    async fn get_keys(&self, kid: Option<&str>) -> Result<Arc<CachedKeys>> {
+        if let Some(keys) = self.cached_keys(kid)? {
+            return Ok(keys);
+        }
+
+        let _guard = self.reload_lock.lock().await;
+        // The keys may have been reloaded while waiting for the lock.
+        if let Some(keys) = self.cached_keys(kid)? {
+            return Ok(keys);
+        }
+
+        let cached = self.cached.read().clone();
+        match (self.reload().await, cached) {
+            (Ok(keys), _) => Ok(keys),
+            // Keep the stale keys if the key source is temporarily unavailable.
+            (Err(cause), Some(cached)) => {
+                warn!("Failed to reload jwks from {}: {}", self.location, cause);
+                Ok(cached)
+            }
+            (Err(cause), None) => Err(cause),
+        }
+    }
+
+    // The cached keys, or `None` if they should be reloaded.
+    fn cached_keys(&self, kid: Option<&str>) -> Result<Option<Arc<CachedKeys>>> {
+        let throttled = matches!(
+            *self.reloaded_at.read(),
+            Some(reloaded_at) if reloaded_at.elapsed() < self.min_reload_interval
+        );
+        match self.cached.read().clone() {
+            None if throttled => Err(ErrorCode::AuthenticateFailure(format!(
+                "jwks from {} is not available",
+                self.location
+            ))),
+            None => Ok(None),
+            Some(cached) => {
+                let unknown_kid = matches!(kid, Some(kid) if !cached.contains(kid));
+                let expired = cached.loaded_at.elapsed() >= self.refresh_interval;
+                match throttled || !(expired || unknown_kid) {
+                    true => Ok(Some(cached)),
+                    false => Ok(None),
+                }
+            }
+        }
+    }
+
+    async fn reload(&self) -> Result<Arc<CachedKeys>> {
+        *self.reloaded_at.write() = Some(Instant::now());
+        let body = self.fetch().await?;
+        let jwk_set: JwkSet = serde_json::from_slice(&body).map_err(|e| {
+            ErrorCode::AuthenticateFailure(format!("invalid jwks from {}: {}", self.location, e))
+        })?;
+
+        let mut keys = Vec::with_capacity(jwk_set.keys.len());
+        for jwk in jwk_set.keys.iter() {
+            match jwk.to_verification_key() {
+                Ok(key) => keys.push((jwk.kid.clone(), Arc::new(key))),
+                Err(cause) => warn!(
+                    "Skip unsupported jwk {:?} from {}: {}",
+                    jwk.kid, self.location, cause
+                ),
+            }
+        }
+
+        info!("Loaded {} jwks keys from {}", keys.len(), self.location);
+        let cached = Arc::new(CachedKeys {
+            keys,
+            loaded_at: Instant::now(),
+        });
+        *self.cached.write() = Some(cached.clone());
+        Ok(cached)
+    }
+
+    async fn fetch(&self) -> Result<Vec<u8>> {
+        if self.location.starts_with("http://") || self.location.starts_with("https://") {
+            let response = self
+                .client
+                .get(&self.location)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| {
+                    ErrorCode::AuthenticateFailure(format!(
+                        "cannot fetch jwks from {}: {}",
+                        self.location, e
+                    ))
+                })?;
+            let body = response.bytes().await.map_err(|e| {
+                ErrorCode::AuthenticateFailure(format!(
+                    "cannot fetch jwks from {}: {}",
+                    self.location, e
+                ))
+            })?;
+            return Ok(body.to_vec());
+        }
+
+        tokio::fs::read(&self.location).await.map_err(|e| {
+            ErrorCode::AuthenticateFailure(format!("cannot read jwks {}: {}", self.location, e))
+        })
+    }
+}
@@ -13,7 +13,12 @@
 // limitations under the License.
 
 mod authenticator;
+mod jwks;
 
+pub use authenticator::jwt_expiration;
 pub use authenticator::CustomClaims;
 pub use authenticator::EnsureUser;
 pub use authenticator::JwtAuthenticator;
+pub use authenticator::JwtIdentity;
+pub use authenticator::JwtIssuerConfig;
+pub use jwks::JwksKeys;
@@ -13,13 +13,18 @@
 // limitations under the License.
 
 mod jwt;
+mod masking_policy;
+mod password_policy;
 mod role_mgr;
+mod row_access_policy;
 mod user;
 mod user_api;
 mod user_mgr;
+mod user_quota_tracker;
 mod user_setting;
 mod user_stage;
 mod user_udf;
+mod workload_group;
 
 pub mod idm_config;
 pub mod role_cache_mgr;
@@ -31,3 +36,6 @@ pub use role_mgr::BUILTIN_ROLE_ACCOUNT_ADMIN;
 pub use role_mgr::BUILTIN_ROLE_PUBLIC;
 pub use user::CertifiedInfo;
 pub use user_api::UserApiProvider;
+pub use user_quota_tracker::ResourcePermit;
+pub use user_quota_tracker::UserQuotaTracker;
+pub use user_quota_tracker::UserResourceUsage;
@@ -0,0 +1,221 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::AuthInfo;
+use common_meta_types::PasswordPolicy;
+use common_meta_types::UserIdentity;
+use common_meta_types::UserInfo;
+
+use crate::UserApiProvider;
+
+// Concurrent logins of a user update its login state at the same time, the update
+// is retried against the latest state when another one wins.
+const LOGIN_STATE_UPDATE_RETRIES: usize = 10;
+
+/// Password policy operations.
+impl UserApiProvider {
+    // Add a new password policy.
+    pub async fn add_password_policy(
+        &self,
+        tenant: &str,
+        policy: PasswordPolicy,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        let policy_api_client = self.get_password_policy_api_client(tenant)?;
+        match policy_api_client.add_policy(policy).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::PASSWORD_POLICY_ALREADY_EXISTS {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get a password policy by name.
+    pub async fn get_password_policy(&self, tenant: &str, name: &str) -> Result<PasswordPolicy> {
+        let policy_api_client = self.get_password_policy_api_client(tenant)?;
+        let get_policy = policy_api_client.get_policy(name, None);
+        Ok(get_policy.await?.data)
+    }
+
+    // Get all password policies for the tenant.
+    pub async fn get_password_policies(&self, tenant: &str) -> Result<Vec<PasswordPolicy>> {
+        let policy_api_client = self.get_password_policy_api_client(tenant)?;
+        match policy_api_client.get_policies().await {
+            Err(e) => Err(e.add_message_back("(while get password policies).")),
+            Ok(policies) => Ok(policies),
+        }
+    }
+
+    // Drop a password policy by name.
+    pub async fn drop_password_policy(
+        &self,
+        tenant: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        let policy_api_client = self.get_password_policy_api_client(tenant)?;
+        match policy_api_client.drop_policy(name, None).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop password policy)"))
+                }
+            }
+        }
+    }
+
+    // Get the password policy attached to the user, a dropped policy is ignored.
+    pub async fn get_user_password_policy(
+        &self,
+        tenant: &str,
+        user_info: &UserInfo,
+    ) -> Result<Option<PasswordPolicy>> {
+        let name = match user_info.option.password_policy() {
+            None => return Ok(None),
+            Some(name) => name,
+        };
+        match self.get_password_policy(tenant, name).await {
+            Ok(policy) => Ok(Some(policy)),
+            Err(e) if e.code() == ErrorCode::UNKNOWN_PASSWORD_POLICY => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Check the new password of a user against the password policy and the password history.
+    pub fn check_password_policy(
+        &self,
+        policy: &PasswordPolicy,
+        password: &str,
+        auth_info: &AuthInfo,
+        user_info: Option<&UserInfo>,
+    ) -> Result<()> {
+        policy.check_password(password)?;
+
+        if let Some(user_info) = user_info {
+            let history = &user_info.history_auth_infos;
+            let recent = history.len().saturating_sub(policy.history as usize);
+            // Salted hashes differ for the same password, check the password itself.
+            let reused = |h: &AuthInfo| h == auth_info || h.verify_password(password.as_bytes());
+            if policy.history > 0
+                && (reused(&user_info.auth_info) || history[recent..].iter().any(reused))
+            {
+                return Err(ErrorCode::InvalidPassword(format!(
+                    "password can not reuse the last {} passwords, policy: {}",
+                    policy.history, policy.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // Fail if the user is locked out by too many failed logins.
+    pub fn check_user_lockout(&self, user_info: &UserInfo) -> Result<()> {
+        if user_info.is_locked(Utc::now()) {
+            return Err(ErrorCode::UserLocked(format!(
+                "user '{}'@'{}' is locked because of too many failed logins, try again later",
+                user_info.name, user_info.hostname
+            )));
+        }
+        Ok(())
+    }
+
+    // Record a login of the user, lock the user out when the failures reach the policy limit.
+    pub async fn record_user_login(
+        &self,
+        tenant: &str,
+        user_info: &UserInfo,
+        success: bool,
+    ) -> Result<()> {
+        let policy = match self.get_user_password_policy(tenant, user_info).await? {
+            None => return Ok(()),
+            Some(policy) => policy,
+        };
+
+        if !success && policy.max_retries == 0 {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let client = self.get_user_api_client(tenant)?;
+        for _ in 0..LOGIN_STATE_UPDATE_RETRIES {
+            // Update the latest state of the user, only if no one changed it meanwhile.
+            let user_val_seq = client.get_user(user_info.identity(), None).await?;
+            let latest = &user_val_seq.data;
+
+            let (password_fails, lockout_time) = if success {
+                if latest.password_fails.is_empty() && latest.lockout_time.is_none() {
+                    return Ok(());
+                }
+                (vec![], None)
+            } else {
+                let mut password_fails = latest.password_fails.clone();
+                password_fails.push(now);
+                if password_fails.len() as u64 >= policy.max_retries {
+                    (vec![], Some(now + policy.lockout_duration()))
+                } else {
+                    (password_fails, None)
+                }
+            };
+
+            let update_login_state = client.update_user_login_state(
+                user_info.identity(),
+                password_fails,
+                lockout_time,
+                Some(user_val_seq.seq),
+            );
+            match update_login_state.await {
+                Ok(_) => return Ok(()),
+                Err(e) if e.code() == ErrorCode::UNKNOWN_USER => continue,
+                Err(e) => return Err(e.add_message_back("(while record user login).")),
+            }
+        }
+
+        Err(ErrorCode::Internal(format!(
+            "fail to record the login of user '{}'@'{}', the user is changed concurrently",
+            user_info.name, user_info.hostname
+        )))
+    }
+
+    // Unlock a user locked out by failed logins.
+    pub async fn unlock_user(&self, tenant: &str, user: UserIdentity) -> Result<()> {
+        let client = self.get_user_api_client(tenant)?;
+        let update_login_state = client.update_user_login_state(user, vec![], None, None);
+        match update_login_state.await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.add_message_back("(while unlock user).")),
+        }
+    }
+
+    // Whether the user must change the password before running other statements.
+    pub async fn must_change_password(&self, tenant: &str, user_info: &UserInfo) -> Result<bool> {
+        if user_info.option.must_change_password() {
+            return Ok(true);
+        }
+        match self.get_user_password_policy(tenant, user_info).await? {
+            None => Ok(false),
+            Some(policy) => {
+                Ok(policy.is_password_expired(user_info.password_update_on, Utc::now()))
+            }
+        }
+    }
+}
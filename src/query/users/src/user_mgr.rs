@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
@@ -20,6 +21,7 @@ use common_meta_types::UserIdentity;
 use common_meta_types::UserInfo;
 use common_meta_types::UserOption;
 use common_meta_types::UserPrivilegeSet;
+use common_meta_types::UserQuota;
 
 use crate::role_mgr::BUILTIN_ROLE_ACCOUNT_ADMIN;
 use crate::UserApiProvider;
@@ -213,10 +215,37 @@ impl UserApiProvider {
         user_option: Option<UserOption>,
     ) -> Result<Option<u64>> {
         let client = self.get_user_api_client(tenant)?;
-        let update_user = client.update_user(user, auth_info, user_option, None);
-        match update_user.await {
+        let mut res = None;
+        if let Some(auth_info) = auth_info {
+            let update_password =
+                client.update_user_password(user.clone(), auth_info, Utc::now(), None);
+            res = match update_password.await {
+                Ok(res) => res,
+                Err(e) => return Err(e.add_message_back("(while alter user).")),
+            };
+        }
+        if user_option.is_some() {
+            let update_user = client.update_user(user, None, user_option, None);
+            res = match update_user.await {
+                Ok(res) => res,
+                Err(e) => return Err(e.add_message_back("(while alter user).")),
+            };
+        }
+        Ok(res)
+    }
+
+    // Update an user's resource quota.
+    pub async fn update_user_quota(
+        &self,
+        tenant: &str,
+        user: UserIdentity,
+        quota: UserQuota,
+    ) -> Result<Option<u64>> {
+        let client = self.get_user_api_client(tenant)?;
+        let update_user_quota = client.update_user_quota(user, quota, None);
+        match update_user_quota.await {
             Ok(res) => Ok(res),
-            Err(e) => Err(e.add_message_back("(while alter user).")),
+            Err(e) => Err(e.add_message_back("(while set user quota).")),
         }
     }
 
@@ -0,0 +1,188 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_base::base::GlobalInstance;
+use common_base::runtime::MemStat;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UserInfo;
+use common_meta_types::UserQuota;
+use parking_lot::RwLock;
+
+/// Runtime resource usage of one user, shared by all the queries the user is running.
+pub struct UserResourceUsage {
+    // Memory of the user's queries is reported to this tracker, limited by `max_memory_in_bytes`.
+    mem_stat: Arc<MemStat>,
+    // The executor threads held by the user's running queries.
+    used_threads: AtomicU64,
+    max_cpu: AtomicU64,
+}
+
+impl UserResourceUsage {
+    fn create(quota: &UserQuota) -> Arc<UserResourceUsage> {
+        let usage = UserResourceUsage {
+            mem_stat: MemStat::create_child(None),
+            used_threads: AtomicU64::new(0),
+            max_cpu: AtomicU64::new(0),
+        };
+
+        usage.update_quota(quota);
+        Arc::new(usage)
+    }
+
+    fn update_quota(&self, quota: &UserQuota) {
+        // MemStat raises a small non-zero limit to its lowest allowed value.
+        self.mem_stat.set_limit(quota.max_memory_in_bytes as i64);
+        self.max_cpu.store(quota.max_cpu, Ordering::Relaxed);
+    }
+
+    pub fn get_memory_usage(&self) -> u64 {
+        self.mem_stat.get_memory_usage().max(0) as u64
+    }
+
+    pub fn get_used_threads(&self) -> u64 {
+        self.used_threads.load(Ordering::Relaxed)
+    }
+
+    /// Reserve the resources of one query: up to `wanted_threads` executor threads out of the
+    /// user's `max_cpu` quota, and a memory tracker counted against `max_memory_in_bytes`.
+    ///
+    /// The query is rejected if the user's running queries already hold all the threads.
+    pub fn acquire(self: &Arc<Self>, wanted_threads: u64) -> Result<ResourcePermit> {
+        let max_cpu = self.max_cpu.load(Ordering::Relaxed);
+        let mut used = self.used_threads.load(Ordering::Relaxed);
+
+        loop {
+            let granted = match max_cpu {
+                0 => wanted_threads,
+                _ => wanted_threads.min(max_cpu.saturating_sub(used)),
+            };
+
+            if granted == 0 {
+                return Err(ErrorCode::UserQuotaExceeded(format!(
+                    "Max cpu quota exceeded: {} threads are in use, the quota is {}",
+                    used, max_cpu
+                )));
+            }
+
+            match self.used_threads.compare_exchange_weak(
+                used,
+                used + granted,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    return Ok(ResourcePermit {
+                        usage: self.clone(),
+                        threads: granted,
+                        mem_stat: MemStat::create_child(Some(self.mem_stat.clone())),
+                    });
+                }
+                Err(current) => used = current,
+            }
+        }
+    }
+}
+
+/// Resources reserved for one query, given back to the user's quota when dropped.
+pub struct ResourcePermit {
+    usage: Arc<UserResourceUsage>,
+    threads: u64,
+    mem_stat: Arc<MemStat>,
+}
+
+impl ResourcePermit {
+    pub fn threads(&self) -> u64 {
+        self.threads
+    }
+
+    pub fn get_mem_stat(&self) -> Arc<MemStat> {
+        self.mem_stat.clone()
+    }
+}
+
+impl Drop for ResourcePermit {
+    fn drop(&mut self) {
+        self.usage
+            .used_threads
+            .fetch_sub(self.threads, Ordering::SeqCst);
+        // Blocks of the query may still be alive, or be freed under other trackers.
+        self.mem_stat.release_from_parent();
+    }
+}
+
+/// Tracks the cpu and memory usage of users against their `UserQuota`.
+pub struct UserQuotaTracker {
+    // tenant -> user identity -> usage
+    usages: RwLock<HashMap<String, HashMap<String, Arc<UserResourceUsage>>>>,
+}
+
+impl UserQuotaTracker {
+    pub fn init() -> Result<()> {
+        GlobalInstance::set(Self::try_create()?);
+        Ok(())
+    }
+
+    pub fn try_create() -> Result<Arc<UserQuotaTracker>> {
+        Ok(Arc::new(UserQuotaTracker {
+            usages: RwLock::new(HashMap::new()),
+        }))
+    }
+
+    pub fn instance() -> Arc<UserQuotaTracker> {
+        GlobalInstance::get()
+    }
+
+    /// Get the usage of the user, refreshing its limits from the user's current quota.
+    pub fn get_user_usage(&self, tenant: &str, user: &UserInfo) -> Arc<UserResourceUsage> {
+        let identity = user.identity().to_string();
+
+        if let Some(usage) = self
+            .usages
+            .read()
+            .get(tenant)
+            .and_then(|users| users.get(&identity))
+        {
+            usage.update_quota(&user.quota);
+            return usage.clone();
+        }
+
+        let mut usages = self.usages.write();
+        let usage = usages
+            .entry(tenant.to_string())
+            .or_default()
+            .entry(identity)
+            .or_insert_with(|| UserResourceUsage::create(&user.quota));
+        usage.update_quota(&user.quota);
+        usage.clone()
+    }
+
+    /// Get the usage of the user if any of its queries has run on this node.
+    pub fn try_get_user_usage(
+        &self,
+        tenant: &str,
+        identity: &str,
+    ) -> Option<Arc<UserResourceUsage>> {
+        self.usages
+            .read()
+            .get(tenant)
+            .and_then(|users| users.get(identity))
+            .cloned()
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod password_policy;
 mod role_cache_mgr;
 mod role_mgr;
 mod user_mgr;
+mod user_quota_tracker;
 mod user_udf;
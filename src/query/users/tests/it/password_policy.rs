@@ -0,0 +1,74 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_grpc::RpcClientConf;
+use common_meta_types::AuthInfo;
+use common_meta_types::PasswordHashMethod;
+use common_meta_types::PasswordPolicy;
+use common_meta_types::UserInfo;
+use common_users::UserApiProvider;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_record_user_login_concurrently() -> Result<()> {
+    let conf = RpcClientConf::default();
+    let user_mgr = UserApiProvider::try_create_simple(conf).await?;
+    let tenant = "test";
+
+    let policy = PasswordPolicy {
+        name: "test-policy".to_string(),
+        max_retries: 3,
+        ..Default::default()
+    };
+    user_mgr.add_password_policy(tenant, policy, false).await?;
+
+    let auth_info = AuthInfo::Password {
+        hash_value: Vec::from("test-pwd"),
+        hash_method: PasswordHashMethod::Sha256,
+    };
+    let mut user_info = UserInfo::new("test-user", "%", auth_info);
+    user_info
+        .option
+        .set_password_policy(Some("test-policy".to_string()));
+    user_mgr.add_user(tenant, user_info.clone(), false).await?;
+
+    // Both failures are counted, though they are recorded from the same stale user info.
+    let (res1, res2) = tokio::join!(
+        user_mgr.record_user_login(tenant, &user_info, false),
+        user_mgr.record_user_login(tenant, &user_info, false),
+    );
+    res1?;
+    res2?;
+    let latest = user_mgr.get_user(tenant, user_info.identity()).await?;
+    assert_eq!(latest.password_fails.len(), 2);
+    assert_eq!(latest.lockout_time, None);
+
+    // The third failure locks the user out.
+    user_mgr
+        .record_user_login(tenant, &user_info, false)
+        .await?;
+    let latest = user_mgr.get_user(tenant, user_info.identity()).await?;
+    assert!(latest.password_fails.is_empty());
+    assert!(latest.lockout_time.is_some());
+
+    // A success clears the state, even with the stale user info.
+    user_mgr.record_user_login(tenant, &user_info, true).await?;
+    let latest = user_mgr.get_user(tenant, user_info.identity()).await?;
+    assert!(latest.password_fails.is_empty());
+    assert_eq!(latest.lockout_time, None);
+
+    Ok(())
+}
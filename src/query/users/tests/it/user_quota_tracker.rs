@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_base::runtime::ThreadTracker;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_grpc::RpcClientConf;
+use common_meta_types::UserInfo;
+use common_meta_types::UserQuota;
+use common_users::UserApiProvider;
+use common_users::UserQuotaTracker;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_update_user_quota() -> Result<()> {
+    let conf = RpcClientConf::default();
+    let user_mgr = UserApiProvider::try_create_simple(conf).await?;
+
+    let tenant = "test";
+    let user_info = UserInfo::new_no_auth("test-user1", "%");
+    user_mgr.add_user(tenant, user_info.clone(), false).await?;
+
+    let quota = UserQuota {
+        max_cpu: 4,
+        max_memory_in_bytes: 1024 * 1024 * 1024,
+        max_storage_in_bytes: 1024,
+    };
+    user_mgr
+        .update_user_quota(tenant, user_info.identity(), quota.clone())
+        .await?;
+
+    let user = user_mgr.get_user(tenant, user_info.identity()).await?;
+    assert_eq!(user.quota, quota);
+
+    Ok(())
+}
+
+#[test]
+fn test_user_quota_tracker() -> Result<()> {
+    let tracker = UserQuotaTracker::try_create()?;
+
+    let mut user_info = UserInfo::new_no_auth("test-user1", "%");
+    user_info.quota.max_cpu = 4;
+    let usage = tracker.get_user_usage("test", &user_info);
+
+    // Threads are granted up to the remaining quota.
+    let permit1 = usage.acquire(3)?;
+    assert_eq!(permit1.threads(), 3);
+    let permit2 = usage.acquire(3)?;
+    assert_eq!(permit2.threads(), 1);
+    assert_eq!(usage.get_used_threads(), 4);
+
+    // No thread left.
+    let res = usage.acquire(1);
+    assert_eq!(res.err().unwrap().code(), ErrorCode::USER_QUOTA_EXCEEDED);
+
+    // Threads are given back when the permit is dropped.
+    drop(permit1);
+    assert_eq!(usage.get_used_threads(), 1);
+    let permit3 = usage.acquire(8)?;
+    assert_eq!(permit3.threads(), 3);
+
+    // The usage is shared by the user's queries.
+    let same = tracker
+        .try_get_user_usage("test", &user_info.identity().to_string())
+        .unwrap();
+    assert_eq!(same.get_used_threads(), 4);
+
+    // The memory of a query is counted for the user until the query is done.
+    let memory = {
+        let mut thread_tracker = ThreadTracker::create(Some(permit2.get_mem_stat()));
+        let _guard = ThreadTracker::enter(&mut thread_tracker);
+        vec![0_u8; 8 * 1024 * 1024]
+    };
+    assert!(usage.get_memory_usage() >= 8 * 1024 * 1024);
+    drop(permit2);
+    assert_eq!(usage.get_memory_usage(), 0);
+    drop(memory);
+
+    // No limit.
+    user_info.quota.max_cpu = 0;
+    let usage = tracker.get_user_usage("test", &user_info);
+    assert_eq!(usage.acquire(16)?.threads(), 16);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+#!/bin/sh
+
+set -o errexit
+
+SCRIPT_PATH="$(cd "$(dirname "$0")" >/dev/null 2>&1 && pwd)"
+BUILD_PROFILE="${BUILD_PROFILE:-debug}"
+
+METACTL="./target/${BUILD_PROFILE}/databend-metactl --grpc-api-address 127.0.0.1:9191"
+
+rm -fr .databend/
+
+echo " === start a single node databend-meta"
+chmod +x ./target/${BUILD_PROFILE}/databend-meta
+./target/${BUILD_PROFILE}/databend-meta --single &
+METASRV_PID=$!
+echo $METASRV_PID
+python3 scripts/ci/wait_tcp.py --timeout 5 --port 9191
+sleep 3
+
+echo " === upsert more keys than a list page holds"
+for i in $(seq 1 1100); do
+    $METACTL --cmd upsert --key "kvapi_test/$i" --value "v$i" >/dev/null
+done
+
+echo " === list with a prefix"
+count=$($METACTL --cmd list --prefix kvapi_test/ --json | wc -l)
+if [ "$count" -ne 1100 ]; then
+    echo " === expect 1100 records with prefix 'kvapi_test/', got $count"
+    kill $METASRV_PID
+    exit 1
+fi
+
+echo " === list with an empty prefix"
+count=$($METACTL --cmd list --json | grep -c '"key":"kvapi_test/')
+if [ "$count" -ne 1100 ]; then
+    echo " === expect 1100 records with an empty prefix, got $count"
+    kill $METASRV_PID
+    exit 1
+fi
+
+echo " === get and delete a key"
+$METACTL --cmd get --key kvapi_test/1 --json | grep '"value":"v1"'
+$METACTL --cmd delete --key kvapi_test/1
+if $METACTL --cmd get --key kvapi_test/1 --json | grep kvapi_test/1; then
+    echo " === 'kvapi_test/1' is not deleted"
+    kill $METASRV_PID
+    exit 1
+fi
+
+echo " === a role is decoded into RoleInfo"
+$METACTL --cmd upsert --key __fd_roles/test/r1 --value '{"name":"r1"}'
+if $METACTL --cmd get --key __fd_roles/test/r1 --json | grep '"grants"'; then
+    echo " === role is decoded"
+else
+    echo " === role is not decoded into RoleInfo"
+    kill $METASRV_PID
+    exit 1
+fi
+
+echo " === a corrupted role is output with the decode error"
+$METACTL --cmd upsert --key __fd_roles/test/r2 --value 'not-a-role'
+if $METACTL --cmd get --key __fd_roles/test/r2 --json | grep '"decode_error"'; then
+    echo " === decode error is output"
+else
+    echo " === no decode error for a corrupted role"
+    kill $METASRV_PID
+    exit 1
+fi
+
+kill $METASRV_PID
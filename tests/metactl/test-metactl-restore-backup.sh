@@ -0,0 +1,56 @@
+#!/bin/sh
+
+set -o errexit
+
+SCRIPT_PATH="$(cd "$(dirname "$0")" >/dev/null 2>&1 && pwd)"
+
+rm -fr .databend/
+
+BACKUP_URI="fs://$(pwd)/.databend/backup/"
+
+echo " === start a meta node that takes a backup every second"
+
+nohup ./target/debug/databend-meta --config-file=./tests/metactl/config/databend-meta-node-1.toml \
+    --backup-uri "$BACKUP_URI" --backup-interval 1 &
+python3 scripts/ci/wait_tcp.py --timeout 5 --port 9191
+
+./target/debug/databend-metactl --cmd upsert --grpc-api-address 127.0.0.1:9191 \
+    --key restore_test_key --value restore_test_value
+
+echo " === sleep 3 sec to wait for a backup"
+sleep 3
+
+echo " === stop the meta node"
+killall databend-meta
+sleep 2
+
+echo " === restore the latest backup into a new raft dir"
+./target/debug/databend-metactl --restore --from "$BACKUP_URI" --raft-dir ./.databend/restored_meta1 \
+    --id=1 --initial-cluster 1=localhost:28103,0.0.0.0:9191
+
+echo " === check if the restored state machine contains the key"
+if ./target/debug/databend-metactl --export --raft-dir ./.databend/restored_meta1 | grep restore_test_key; then
+    echo "=== 'restore_test_key' is found"
+else
+    echo "=== 'restore_test_key' is not found"
+    exit 1
+fi
+
+echo " === boot a meta node from the restored raft dir"
+nohup ./target/debug/databend-meta --config-file=./tests/metactl/config/databend-meta-node-1.toml \
+    --raft-dir ./.databend/restored_meta1 &
+python3 scripts/ci/wait_tcp.py --timeout 5 --port 9191
+
+echo " === sleep 3 sec to wait for the leader to be elected"
+sleep 3
+
+echo " === check if the key is served by the restored node"
+if ./target/debug/databend-metactl --cmd get --grpc-api-address 127.0.0.1:9191 --key restore_test_key | grep restore_test_value; then
+    echo "=== 'restore_test_value' is found"
+else
+    echo "=== 'restore_test_value' is not found"
+    killall databend-meta
+    exit 1
+fi
+
+killall databend-meta